@@ -3,9 +3,12 @@
 use anyhow::{Context, Result};
 use chrono::Local;
 use colored::*;
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 
+use crate::output::CommandOutput;
+
 /// SavedArks directory relative to server root
 const SAVED_ARKS_PATH: &str = "ShooterGame/Saved/SavedArks";
 
@@ -26,35 +29,79 @@ const COMMON_MAPS: [&str; 12] = [
     "Fjordur_WP.ark",
 ];
 
+/// Result of the backup command
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum BackupOutput {
+    Created(BackupCreated),
+    List(Vec<BackupEntry>),
+    Restored(BackupRestored),
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupCreated {
+    /// None when there was nothing to back up
+    pub path: Option<String>,
+    pub files: usize,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupEntry {
+    pub name: String,
+    pub path: String,
+    pub files: usize,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupRestored {
+    pub from: String,
+    pub pre_restore_backup: String,
+    pub files: usize,
+}
+
+impl CommandOutput for BackupOutput {
+    fn print_human(&self) {
+        if let BackupOutput::List(backups) = self {
+            print_backup_list(backups);
+        }
+    }
+}
+
 /// Handle backup command
 pub fn handle_backup(
     server_path: &Path,
     name: Option<String>,
     list: bool,
     restore: Option<String>,
-) -> Result<()> {
+) -> Result<BackupOutput> {
     let saves_path = server_path.join(SAVED_ARKS_PATH);
 
     if list {
-        return list_backups(&saves_path);
+        return list_backups(&saves_path).map(BackupOutput::List);
     }
 
     if let Some(backup_name) = restore {
-        return restore_backup(&saves_path, &backup_name);
+        return restore_backup(&saves_path, &backup_name).map(BackupOutput::Restored);
     }
 
     // Create backup
-    create_backup(&saves_path, name)
+    create_backup(&saves_path, name).map(BackupOutput::Created)
 }
 
 /// Create a backup of all .ark save files
-fn create_backup(saves_path: &Path, custom_name: Option<String>) -> Result<()> {
-    println!("{}", "💾 Creating backup...".cyan());
+fn create_backup(saves_path: &Path, custom_name: Option<String>) -> Result<BackupCreated> {
+    say!("{}", "💾 Creating backup...".cyan());
 
     if !saves_path.exists() {
-        println!("  {} SavedArks directory not found at:", "⚠️".yellow());
-        println!("  {}", saves_path.display());
-        return Ok(());
+        say!("  {} SavedArks directory not found at:", "⚠️".yellow());
+        say!("  {}", saves_path.display());
+        return Ok(BackupCreated {
+            path: None,
+            files: 0,
+            size_bytes: 0,
+        });
     }
 
     // Create backup directory
@@ -81,14 +128,14 @@ fn create_backup(saves_path: &Path, custom_name: Option<String>) -> Result<()> {
                     let file_name = path.file_name().unwrap();
                     let dest = backup_folder.join(file_name);
 
-                    print!("  Backing up {}...", file_name.to_string_lossy().yellow());
+                    say_inline!("  Backing up {}...", file_name.to_string_lossy().yellow());
 
                     let metadata = fs::metadata(&path)?;
                     total_size += metadata.len();
 
                     fs::copy(&path, &dest).with_context(|| format!("Failed to copy {:?}", path))?;
 
-                    println!(" {}", "✓".green());
+                    say!(" {}", "✓".green());
                     backed_up += 1;
                 }
             }
@@ -96,35 +143,35 @@ fn create_backup(saves_path: &Path, custom_name: Option<String>) -> Result<()> {
     }
 
     if backed_up == 0 {
-        println!("  {} No .ark files found to backup", "⚠️".yellow());
+        say!("  {} No .ark files found to backup", "⚠️".yellow());
     } else {
         let size_mb = total_size as f64 / 1_048_576.0;
-        println!();
-        println!(
+        say!();
+        say!(
             "  {} Backed up {} file(s) ({:.1} MB)",
             "✓".green(),
             backed_up,
             size_mb
         );
-        println!(
+        say!(
             "  Location: {}",
             backup_folder.display().to_string().yellow()
         );
     }
 
-    Ok(())
+    Ok(BackupCreated {
+        path: (backed_up > 0).then(|| backup_folder.display().to_string()),
+        files: backed_up,
+        size_bytes: total_size,
+    })
 }
 
-/// List all available backups
-fn list_backups(saves_path: &Path) -> Result<()> {
-    println!("{}", "📋 Available Backups:".cyan());
-    println!();
-
+/// List all available backups, newest first
+fn list_backups(saves_path: &Path) -> Result<Vec<BackupEntry>> {
     let backup_dir = saves_path.join("Backups");
 
     if !backup_dir.exists() {
-        println!("  {} No backups found", "ℹ️".blue());
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let mut backups: Vec<_> = fs::read_dir(&backup_dir)?
@@ -132,54 +179,64 @@ fn list_backups(saves_path: &Path) -> Result<()> {
         .filter(|e| e.path().is_dir())
         .collect();
 
+    // Sort by name (which includes timestamp)
+    backups.sort_by_key(|entry| std::cmp::Reverse(entry.file_name()));
+
+    Ok(backups
+        .iter()
+        .map(|entry| {
+            // Count .ark files
+            let ark_count = fs::read_dir(entry.path())
+                .map(|rd| {
+                    rd.filter_map(|e| e.ok())
+                        .filter(|e| {
+                            e.path()
+                                .extension()
+                                .map(|ext| ext == "ark")
+                                .unwrap_or(false)
+                        })
+                        .count()
+                })
+                .unwrap_or(0);
+
+            BackupEntry {
+                name: entry.file_name().to_string_lossy().to_string(),
+                path: entry.path().display().to_string(),
+                files: ark_count,
+                size_bytes: get_dir_size(&entry.path()).unwrap_or(0),
+            }
+        })
+        .collect())
+}
+
+/// Print the backups found by list_backups
+fn print_backup_list(backups: &[BackupEntry]) {
+    println!("{}", "📋 Available Backups:".cyan());
+    println!();
+
     if backups.is_empty() {
         println!("  {} No backups found", "ℹ️".blue());
-        return Ok(());
+        return;
     }
 
-    // Sort by name (which includes timestamp)
-    backups.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
-
-    for (i, entry) in backups.iter().enumerate() {
-        let name = entry.file_name();
-        let name_str = name.to_string_lossy();
-
-        // Get folder size
-        let size = get_dir_size(&entry.path()).unwrap_or(0);
-        let size_mb = size as f64 / 1_048_576.0;
-
-        // Count .ark files
-        let ark_count = fs::read_dir(entry.path())
-            .map(|rd| {
-                rd.filter_map(|e| e.ok())
-                    .filter(|e| {
-                        e.path()
-                            .extension()
-                            .map(|ext| ext == "ark")
-                            .unwrap_or(false)
-                    })
-                    .count()
-            })
-            .unwrap_or(0);
-
+    for (i, backup) in backups.iter().enumerate() {
+        let size_mb = backup.size_bytes as f64 / 1_048_576.0;
         println!(
             "  {}. {} ({} files, {:.1} MB)",
             i + 1,
-            name_str.green(),
-            ark_count,
+            backup.name.green(),
+            backup.files,
             size_mb
         );
     }
 
     println!();
     println!("  Use --restore <name> to restore a backup");
-
-    Ok(())
 }
 
 /// Restore from a specific backup
-fn restore_backup(saves_path: &Path, backup_name: &str) -> Result<()> {
-    println!("{}", "🔄 Restoring backup...".cyan());
+fn restore_backup(saves_path: &Path, backup_name: &str) -> Result<BackupRestored> {
+    say!("{}", "🔄 Restoring backup...".cyan());
 
     let backup_dir = saves_path.join("Backups");
     let backup_path = backup_dir.join(backup_name);
@@ -203,8 +260,8 @@ fn restore_backup(saves_path: &Path, backup_name: &str) -> Result<()> {
 }
 
 /// Actually restore files from a backup folder
-fn restore_from_folder(backup_path: &Path, saves_path: &Path) -> Result<()> {
-    println!(
+fn restore_from_folder(backup_path: &Path, saves_path: &Path) -> Result<BackupRestored> {
+    say!(
         "  Restoring from: {}",
         backup_path.display().to_string().yellow()
     );
@@ -230,7 +287,7 @@ fn restore_from_folder(backup_path: &Path, saves_path: &Path) -> Result<()> {
         }
     }
 
-    println!(
+    say!(
         "  Pre-restore backup created: {}",
         pre_restore_backup.display().to_string().blue()
     );
@@ -246,19 +303,23 @@ fn restore_from_folder(backup_path: &Path, saves_path: &Path) -> Result<()> {
                     let file_name = path.file_name().unwrap();
                     let dest = saves_path.join(file_name);
 
-                    print!("  Restoring {}...", file_name.to_string_lossy().yellow());
+                    say_inline!("  Restoring {}...", file_name.to_string_lossy().yellow());
                     fs::copy(&path, &dest)?;
-                    println!(" {}", "✓".green());
+                    say!(" {}", "✓".green());
                     restored += 1;
                 }
             }
         }
     }
 
-    println!();
-    println!("  {} Restored {} file(s)", "✓".green(), restored);
+    say!();
+    say!("  {} Restored {} file(s)", "✓".green(), restored);
 
-    Ok(())
+    Ok(BackupRestored {
+        from: backup_path.display().to_string(),
+        pre_restore_backup: pre_restore_backup.display().to_string(),
+        files: restored,
+    })
 }
 
 /// Get total size of a directory
@@ -1,16 +1,32 @@
 //! Configuration module for INI file parsing and modification
 
 use anyhow::{Context, Result};
+use chrono::Local;
 use colored::*;
 use ini::Ini;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+use crate::output::CommandOutput;
+
 /// Path to GameUserSettings.ini relative to server root
 const GAME_USER_SETTINGS_PATH: &str = "ShooterGame/Saved/Config/WindowsServer/GameUserSettings.ini";
 
 /// Path to Game.ini relative to server root
 const GAME_INI_PATH: &str = "ShooterGame/Saved/Config/WindowsServer/Game.ini";
 
+/// ServerSettings keys shown by `config --show`, with their display labels
+const IMPORTANT_KEYS: [(&str, &str); 7] = [
+    ("DifficultyOffset", "Difficulty"),
+    ("HarvestAmountMultiplier", "Harvest Rate"),
+    ("XPMultiplier", "XP Rate"),
+    ("TamingSpeedMultiplier", "Taming Speed"),
+    ("serverPVE", "PvE Mode"),
+    ("MaxPlayers", "Max Players"),
+    ("ServerPassword", "Password Protected"),
+];
+
 /// Server settings structure mapped from INI
 #[derive(Debug, Default)]
 #[allow(dead_code)]
@@ -38,7 +54,61 @@ impl ApplyOptimizedSettings for ServerSettings {
     }
 }
 
+/// A single key that differs between two versions of an INI file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IniChange {
+    pub section: String,
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Result of the config command
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ConfigOutput {
+    Show(ConfigSnapshot),
+    Update(ConfigUpdate),
+}
+
+/// Current values from GameUserSettings.ini
+#[derive(Debug, Serialize)]
+pub struct ConfigSnapshot {
+    pub file_path: String,
+    pub exists: bool,
+    pub settings: BTreeMap<String, String>,
+    pub active_mods: Vec<String>,
+}
+
+/// What a config update changed (or would change with --dry-run)
+#[derive(Debug, Serialize)]
+pub struct ConfigUpdate {
+    pub file_path: String,
+    pub dry_run: bool,
+    pub written: bool,
+    pub backup_path: Option<String>,
+    pub changes: Vec<IniChange>,
+}
+
+impl CommandOutput for ConfigOutput {
+    fn print_human(&self) {
+        if let ConfigOutput::Show(snapshot) = self {
+            print_snapshot(snapshot);
+        }
+    }
+}
+
+/// Result of the optimize command
+#[derive(Debug, Serialize)]
+pub struct OptimizeOutput {
+    pub startup_args: Vec<String>,
+    pub motd_updated: bool,
+}
+
+impl CommandOutput for OptimizeOutput {}
+
 /// Handle config command
+#[allow(clippy::too_many_arguments)]
 pub fn handle_config(
     server_path: &Path,
     optimize: bool,
@@ -47,34 +117,43 @@ pub fn handle_config(
     taming: Option<f32>,
     difficulty: Option<f32>,
     show: bool,
-) -> Result<()> {
+    dry_run: bool,
+    backup: bool,
+) -> Result<ConfigOutput> {
     let config_path = server_path.join(GAME_USER_SETTINGS_PATH);
 
     if show {
-        return show_config(&config_path);
+        return show_config(&config_path).map(ConfigOutput::Show);
     }
 
-    println!("{}", "📝 Updating server configuration...".cyan());
+    say!("{}", "📝 Updating server configuration...".cyan());
 
     // Load or create INI
     let mut ini = if config_path.exists() {
         Ini::load_from_file(&config_path)
             .with_context(|| format!("Failed to load {}", config_path.display()))?
     } else {
-        println!(
+        say!(
             "  {} Config file not found, creating new one...",
             "⚠️".yellow()
         );
         Ini::new()
     };
 
-    // Get or create ServerSettings section
-    let _section = ini.section_mut(Some("ServerSettings")).map(|s| s.clone());
+    // Keep the original around for the diff
+    let original = ini.clone();
 
     let mut updates = Vec::new();
+    let mut result = ConfigUpdate {
+        file_path: config_path.display().to_string(),
+        dry_run,
+        written: false,
+        backup_path: None,
+        changes: Vec::new(),
+    };
 
     if optimize {
-        println!("  Applying optimized settings...");
+        say!("  Applying optimized settings...");
         set_ini_value(&mut ini, "ServerSettings", "DifficultyOffset", "1.0");
         set_ini_value(&mut ini, "ServerSettings", "HarvestAmountMultiplier", "3.0");
         set_ini_value(&mut ini, "ServerSettings", "XPMultiplier", "2.0");
@@ -118,32 +197,62 @@ pub fn handle_config(
     }
 
     if !updates.is_empty() {
+        result.changes = diff_ini(&original, &ini);
+
+        if dry_run {
+            say!("  {} Dry run, nothing written", "ℹ️".blue());
+            print_ini_diff(&config_path, &result.changes);
+            return Ok(ConfigOutput::Update(result));
+        }
+
+        if result.changes.is_empty() {
+            say!(
+                "  {} Values already up to date, nothing to write",
+                "ℹ️".blue()
+            );
+            return Ok(ConfigOutput::Update(result));
+        }
+
         // Ensure directory exists
         if let Some(parent) = config_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
+        if backup {
+            if let Some(backup_path) = backup_config_file(&config_path)? {
+                say!(
+                    "  {} Backed up to: {}",
+                    "✓".green(),
+                    backup_path.display().to_string().yellow()
+                );
+                result.backup_path = Some(backup_path.display().to_string());
+            }
+        }
+
+        print_ini_diff(&config_path, &result.changes);
+
         ini.write_to_file(&config_path)
             .with_context(|| format!("Failed to write {}", config_path.display()))?;
+        result.written = true;
 
         for update in updates {
-            println!("  {} {}", "✓".green(), update);
+            say!("  {} {}", "✓".green(), update);
         }
 
-        println!("  Saved to: {}", config_path.display().to_string().yellow());
+        say!("  Saved to: {}", config_path.display().to_string().yellow());
     } else {
-        println!(
+        say!(
             "  {} No changes specified. Use --optimize or --harvest/--xp/--taming/--difficulty",
             "ℹ️".blue()
         );
     }
 
-    Ok(())
+    Ok(ConfigOutput::Update(result))
 }
 
 /// Handle performance optimization
-pub fn handle_optimize(server_path: &Path, startup: bool, motd: bool) -> Result<()> {
-    println!("{}", "⚡ Applying performance optimizations...".cyan());
+pub fn handle_optimize(server_path: &Path, startup: bool, motd: bool) -> Result<OptimizeOutput> {
+    say!("{}", "⚡ Applying performance optimizations...".cyan());
 
     let config_path = server_path.join(GAME_USER_SETTINGS_PATH);
     let _game_ini_path = server_path.join(GAME_INI_PATH);
@@ -153,9 +262,13 @@ pub fn handle_optimize(server_path: &Path, startup: bool, motd: bool) -> Result<
         "r.VolumetricFog 0",
         "r.Water.SingleLayer.Reflection 0",
     ];
+    let mut result = OptimizeOutput {
+        startup_args: Vec::new(),
+        motd_updated: false,
+    };
 
     if startup {
-        println!("  Adding startup performance arguments...");
+        say!("  Adding startup performance arguments...");
 
         // Load GameUserSettings.ini
         let _ini = if config_path.exists() {
@@ -166,22 +279,23 @@ pub fn handle_optimize(server_path: &Path, startup: bool, motd: bool) -> Result<
 
         // Add to [/Script/Engine.GameSession] or create startup args
         for cmd in &performance_commands {
-            println!("    {} {}", "→".blue(), cmd);
+            say!("    {} {}", "→".blue(), cmd);
         }
 
         // Note: In practice, these would go into command line args
         // For now, we document them
-        println!(
+        say!(
             "  {}",
             "Note: Add these to your server startup command line:".yellow()
         );
         for cmd in &performance_commands {
-            println!("    -ExecCmds=\"{}\"", cmd);
+            say!("    -ExecCmds=\"{}\"", cmd);
+            result.startup_args.push(format!("-ExecCmds=\"{}\"", cmd));
         }
     }
 
     if motd {
-        println!("  Updating MOTD with performance tips...");
+        say!("  Updating MOTD with performance tips...");
 
         let mut ini = if config_path.exists() {
             Ini::load_from_file(&config_path)?
@@ -198,70 +312,172 @@ pub fn handle_optimize(server_path: &Path, startup: bool, motd: bool) -> Result<
         }
         ini.write_to_file(&config_path)?;
 
-        println!("  {} MOTD updated", "✓".green());
+        say!("  {} MOTD updated", "✓".green());
+        result.motd_updated = true;
     }
 
     if !startup && !motd {
-        println!(
+        say!(
             "  {} Specify --startup or --motd to apply optimizations",
             "ℹ️".blue()
         );
     }
 
-    Ok(())
+    Ok(result)
 }
 
-/// Show current configuration
-fn show_config(config_path: &Path) -> Result<()> {
-    println!("{}", "📋 Current Server Configuration:".cyan());
-    println!();
+/// Read the current configuration
+fn show_config(config_path: &Path) -> Result<ConfigSnapshot> {
+    let mut snapshot = ConfigSnapshot {
+        file_path: config_path.display().to_string(),
+        exists: config_path.exists(),
+        settings: BTreeMap::new(),
+        active_mods: Vec::new(),
+    };
 
-    if !config_path.exists() {
-        println!("  {} Config file not found at:", "⚠️".yellow());
-        println!("  {}", config_path.display());
-        return Ok(());
+    if !snapshot.exists {
+        return Ok(snapshot);
     }
 
     let ini = Ini::load_from_file(config_path)?;
 
-    let important_keys = [
-        ("DifficultyOffset", "Difficulty"),
-        ("HarvestAmountMultiplier", "Harvest Rate"),
-        ("XPMultiplier", "XP Rate"),
-        ("TamingSpeedMultiplier", "Taming Speed"),
-        ("serverPVE", "PvE Mode"),
-        ("MaxPlayers", "Max Players"),
-        ("ServerPassword", "Password Protected"),
-    ];
-
     if let Some(section) = ini.section(Some("ServerSettings")) {
-        println!("  {}", "[ServerSettings]".yellow());
-        for (key, label) in important_keys {
+        for (key, _) in IMPORTANT_KEYS {
             if let Some(value) = section.get(key) {
+                snapshot.settings.insert(key.to_string(), value.to_string());
+            }
+        }
+
+        if let Some(mods) = section.get("ActiveMods") {
+            snapshot.active_mods = mods
+                .split(',')
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty())
+                .collect();
+        }
+    }
+
+    Ok(snapshot)
+}
+
+/// Print the configuration read by show_config
+fn print_snapshot(snapshot: &ConfigSnapshot) {
+    println!("{}", "📋 Current Server Configuration:".cyan());
+    println!();
+
+    if !snapshot.exists {
+        println!("  {} Config file not found at:", "⚠️".yellow());
+        println!("  {}", snapshot.file_path);
+        return;
+    }
+
+    if !snapshot.settings.is_empty() {
+        println!("  {}", "[ServerSettings]".yellow());
+        for (key, label) in IMPORTANT_KEYS {
+            if let Some(value) = snapshot.settings.get(key) {
                 println!("    {:20} = {}", label, value.green());
             }
         }
     }
 
-    // Check for active mods
-    if let Some(section) = ini.section(Some("ServerSettings")) {
-        if let Some(mods) = section.get("ActiveMods") {
-            if !mods.is_empty() {
-                println!();
-                println!("  {}", "[Active Mods]".yellow());
-                let mod_list: Vec<&str> = mods.split(',').collect();
-                println!("    Count: {}", mod_list.len().to_string().green());
-                for (i, mod_id) in mod_list.iter().take(5).enumerate() {
-                    println!("    {}. {}", i + 1, mod_id);
-                }
-                if mod_list.len() > 5 {
-                    println!("    ... and {} more", mod_list.len() - 5);
-                }
+    // Active mods
+    let mod_list = &snapshot.active_mods;
+    if !mod_list.is_empty() {
+        println!();
+        println!("  {}", "[Active Mods]".yellow());
+        println!("    Count: {}", mod_list.len().to_string().green());
+        for (i, mod_id) in mod_list.iter().take(5).enumerate() {
+            println!("    {}. {}", i + 1, mod_id);
+        }
+        if mod_list.len() > 5 {
+            println!("    ... and {} more", mod_list.len() - 5);
+        }
+    }
+}
+
+/// Compare two INI documents key by key, in section order of `before` then `after`
+pub fn diff_ini(before: &Ini, after: &Ini) -> Vec<IniChange> {
+    let mut changes = Vec::new();
+
+    for (section, props) in before.iter() {
+        let section_name = section.unwrap_or_default();
+        let after_props = after.section(section);
+        for (key, old) in props.iter() {
+            let new = after_props.and_then(|p| p.get(key));
+            if new != Some(old) {
+                changes.push(IniChange {
+                    section: section_name.to_string(),
+                    key: key.to_string(),
+                    old: Some(old.to_string()),
+                    new: new.map(|v| v.to_string()),
+                });
+            }
+        }
+    }
+
+    for (section, props) in after.iter() {
+        let before_props = before.section(section);
+        for (key, new) in props.iter() {
+            if before_props.and_then(|p| p.get(key)).is_none() {
+                changes.push(IniChange {
+                    section: section.unwrap_or_default().to_string(),
+                    key: key.to_string(),
+                    old: None,
+                    new: Some(new.to_string()),
+                });
             }
         }
     }
 
-    Ok(())
+    changes
+}
+
+/// Print changes as a unified diff, grouped by section
+pub fn print_ini_diff(path: &Path, changes: &[IniChange]) {
+    if changes.is_empty() {
+        say!("  {} No changes", "ℹ️".blue());
+        return;
+    }
+
+    say!("--- {}", path.display());
+    say!("+++ {}", path.display());
+
+    let mut current_section: Option<&str> = None;
+    for change in changes {
+        if current_section != Some(change.section.as_str()) {
+            say!("{}", format!("@@ [{}] @@", change.section).cyan());
+            current_section = Some(change.section.as_str());
+        }
+        if let Some(old) = &change.old {
+            say!("{}", format!("-{}={}", change.key, old).red());
+        }
+        if let Some(new) = &change.new {
+            say!("{}", format!("+{}={}", change.key, new).green());
+        }
+    }
+}
+
+/// Copy a config file into a Backups folder next to it, returns None if there is nothing to copy
+pub fn backup_config_file(config_path: &Path) -> Result<Option<PathBuf>> {
+    if !config_path.exists() {
+        return Ok(None);
+    }
+
+    let parent = config_path.parent().unwrap_or(Path::new("."));
+    let backup_dir = parent.join("Backups");
+    std::fs::create_dir_all(&backup_dir).context("Failed to create Backups directory")?;
+
+    let stem = config_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config".to_string());
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+    let backup_path = backup_dir.join(format!("{}_{}.ini.bak", stem, timestamp));
+
+    std::fs::copy(config_path, &backup_path)
+        .with_context(|| format!("Failed to back up {}", config_path.display()))?;
+
+    Ok(Some(backup_path))
 }
 
 /// Helper to set INI value
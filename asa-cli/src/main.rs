@@ -1,11 +1,20 @@
+#[macro_use]
+mod output;
 mod config;
 mod network;
 mod backup;
 mod errors;
+// The launch flag catalog and presets are only used by the desktop app's settings UI
+#[allow(dead_code)]
+#[path = "../../src-tauri/src/services/launch_args.rs"]
+mod launch_args;
+mod server;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use colored::*;
-use std::path::PathBuf;
+use output::{emit, CommandOutput};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
 /// ASA Server Manager CLI - High-performance server automation tool
 #[derive(Parser)]
@@ -18,6 +27,10 @@ struct Cli {
     #[arg(short, long, default_value = ".")]
     server_path: PathBuf,
 
+    /// Print machine-readable JSON instead of colored text
+    #[arg(long, global = true)]
+    json: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -49,6 +62,14 @@ enum Commands {
         /// Show current configuration
         #[arg(long)]
         show: bool,
+
+        /// Print the resulting INI diff without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Snapshot the config file before writing
+        #[arg(long)]
+        backup: bool,
     },
 
     /// Check and update mods from CurseForge
@@ -88,6 +109,12 @@ enum Commands {
         motd: bool,
     },
 
+    /// Start, stop or restart the server process
+    Server {
+        #[command(subcommand)]
+        action: ServerAction,
+    },
+
     /// Verify server integrity
     Verify {
         /// Check mod files exist
@@ -104,91 +131,314 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum ServerAction {
+    /// Launch the server in the background
+    Start(LaunchArgs),
+
+    /// Force stop a server started by the CLI
+    Stop,
+
+    /// Stop and start again
+    Restart(LaunchArgs),
+
+    /// Show whether a server started by the CLI is running
+    Status,
+}
+
+/// Launch options not stored in GameUserSettings.ini
+#[derive(Args)]
+struct LaunchArgs {
+    /// Map to load
+    #[arg(long, default_value = "TheIsland_WP")]
+    map: String,
+
+    /// Game port (defaults to the INI value or 7777)
+    #[arg(long)]
+    port: Option<u16>,
+
+    /// Query port (defaults to the INI value or 27015)
+    #[arg(long)]
+    query_port: Option<u16>,
+
+    /// IP address to bind (-MultiHome)
+    #[arg(long)]
+    ip: Option<String>,
+
+    /// Cluster ID for cross-ARK travel
+    #[arg(long)]
+    cluster_id: Option<String>,
+
+    /// Shared cluster directory
+    #[arg(long)]
+    cluster_dir: Option<String>,
+
+    /// Extra launch arguments, passed through as-is
+    #[arg(long, allow_hyphen_values = true)]
+    custom_args: Option<String>,
+
+    /// Start without the ActiveMods from GameUserSettings.ini
+    #[arg(long)]
+    no_mods: bool,
+
+    /// Launch with BattlEye (-UseBattlEye) instead of -NoBattlEye
+    #[arg(long)]
+    battleye: bool,
+
+    /// Crossplay platforms for -ServerPlatform, e.g. PC,XSX,PS5 or ALL
+    #[arg(long, value_delimiter = ',')]
+    platforms: Vec<String>,
+}
+
+impl From<LaunchArgs> for server::LaunchFlags {
+    fn from(args: LaunchArgs) -> Self {
+        server::LaunchFlags {
+            map: args.map,
+            game_port: args.port,
+            query_port: args.query_port,
+            ip: args.ip,
+            cluster_id: args.cluster_id,
+            cluster_dir: args.cluster_dir,
+            custom_args: args.custom_args,
+            no_mods: args.no_mods,
+            battleye: args.battleye,
+            platforms: args.platforms,
+        }
+    }
+}
+
+/// Result of the verify command
+#[derive(Serialize)]
+struct VerifyOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    config_ok: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mods_ok: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saves_ok: Option<bool>,
+    issues: Vec<String>,
+}
+
+impl CommandOutput for VerifyOutput {
+    fn print_human(&self) {
+        if self.issues.is_empty() {
+            println!("  {}", "All checks passed!".green());
+        } else {
+            println!("  {}", "Issues found:".red());
+            for issue in &self.issues {
+                println!("    ❌ {}", issue.red());
+            }
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    output::set_json(cli.json);
+
+    match run(cli).await {
+        // Scripts get the error as JSON on stdout plus a non-zero exit code
+        Err(e) if output::is_json() => {
+            println!("{}", serde_json::json!({ "error": format!("{:#}", e) }));
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
 
-    println!("{}", "╔══════════════════════════════════════════╗".cyan());
-    println!("{}", "║     ASA Server Manager CLI v1.0.0        ║".cyan());
-    println!("{}", "╚══════════════════════════════════════════╝".cyan());
-    println!();
+async fn run(cli: Cli) -> anyhow::Result<()> {
+    say!("{}", "╔══════════════════════════════════════════╗".cyan());
+    say!("{}", "║     ASA Server Manager CLI v1.0.0        ║".cyan());
+    say!("{}", "╚══════════════════════════════════════════╝".cyan());
+    say!();
 
     let server_path = cli.server_path.canonicalize().unwrap_or(cli.server_path);
-    println!("📁 Server path: {}", server_path.display().to_string().yellow());
-    println!();
+    say!("📁 Server path: {}", server_path.display().to_string().yellow());
+    say!();
 
     match cli.command {
-        Commands::Config { optimize, harvest, xp, taming, difficulty, show } => {
-            config::handle_config(&server_path, optimize, harvest, xp, taming, difficulty, show)?;
+        Commands::Config { optimize, harvest, xp, taming, difficulty, show, dry_run, backup } => {
+            emit(&config::handle_config(
+                &server_path,
+                optimize,
+                harvest,
+                xp,
+                taming,
+                difficulty,
+                show,
+                dry_run,
+                backup,
+            )?)?;
         }
 
         Commands::UpdateMods { force, check_only } => {
-            network::handle_mod_update(&server_path, force, check_only).await?;
+            emit(&network::handle_mod_update(&server_path, force, check_only).await?)?;
         }
 
         Commands::Backup { name, list, restore } => {
-            backup::handle_backup(&server_path, name, list, restore)?;
+            emit(&backup::handle_backup(&server_path, name, list, restore)?)?;
         }
 
         Commands::Optimize { startup, motd } => {
-            config::handle_optimize(&server_path, startup, motd)?;
+            emit(&config::handle_optimize(&server_path, startup, motd)?)?;
+        }
+
+        Commands::Server { action } => {
+            let result = match action {
+                ServerAction::Start(args) => server::start_server(&server_path, &args.into())?,
+                ServerAction::Stop => server::stop_server(&server_path)?,
+                ServerAction::Restart(args) => server::restart_server(&server_path, &args.into())?,
+                ServerAction::Status => server::server_status(&server_path)?,
+            };
+            emit(&result)?;
         }
 
         Commands::Verify { mods, config, saves } => {
-            verify_server(&server_path, mods, config, saves)?;
+            emit(&verify_server(&server_path, mods, config, saves)?)?;
         }
     }
 
-    println!();
-    println!("{}", "✅ Operation completed successfully!".green());
+    say!();
+    say!("{}", "✅ Operation completed successfully!".green());
     Ok(())
 }
+fn verify_server(server_path: &Path, check_mods: bool, check_config: bool, check_saves: bool) -> anyhow::Result<VerifyOutput> {
+    say!("{}", "🔍 Verifying server integrity...".cyan());
 
-fn verify_server(server_path: &PathBuf, check_mods: bool, check_config: bool, check_saves: bool) -> anyhow::Result<()> {
-    println!("{}", "🔍 Verifying server integrity...".cyan());
-
-    let mut issues = Vec::new();
+    let mut result = VerifyOutput {
+        config_ok: None,
+        mods_ok: None,
+        saves_ok: None,
+        issues: Vec::new(),
+    };
 
     if check_config || (!check_mods && !check_saves) {
-        println!("  Checking config files...");
+        say!("  Checking config files...");
         let config_path = server_path
             .join("ShooterGame/Saved/Config/WindowsServer");
+        let before = result.issues.len();
         
         if !config_path.join("GameUserSettings.ini").exists() {
-            issues.push("GameUserSettings.ini not found");
+            result.issues.push("GameUserSettings.ini not found".to_string());
         }
         if !config_path.join("Game.ini").exists() {
-            issues.push("Game.ini not found");
+            result.issues.push("Game.ini not found".to_string());
         }
+        result.config_ok = Some(result.issues.len() == before);
     }
 
     if check_mods || (!check_config && !check_saves) {
-        println!("  Checking mod files...");
+        say!("  Checking mod files...");
         let mods_path = server_path
             .join("ShooterGame/Binaries/Win64/ShooterGame/Mods");
         
-        if !mods_path.exists() {
-            issues.push("Mods directory not found");
+        let exists = mods_path.exists();
+        if !exists {
+            result.issues.push("Mods directory not found".to_string());
         }
+        result.mods_ok = Some(exists);
     }
 
     if check_saves || (!check_config && !check_mods) {
-        println!("  Checking save files...");
+        say!("  Checking save files...");
         let saves_path = server_path.join("ShooterGame/Saved/SavedArks");
         
-        if !saves_path.exists() {
-            issues.push("SavedArks directory not found");
+        let exists = saves_path.exists();
+        if !exists {
+            result.issues.push("SavedArks directory not found".to_string());
         }
+        result.saves_ok = Some(exists);
     }
 
-    if issues.is_empty() {
-        println!("  {}", "All checks passed!".green());
-    } else {
-        println!("  {}", "Issues found:".red());
-        for issue in issues {
-            println!("    ❌ {}", issue.red());
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch_flags(args: &[&str]) -> server::LaunchFlags {
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Server {
+                action: ServerAction::Start(args) | ServerAction::Restart(args),
+            } => args.into(),
+            _ => panic!("not a start/restart command"),
         }
     }
 
-    Ok(())
+    #[test]
+    fn test_server_start_defaults() {
+        let flags = launch_flags(&["asa_manager", "server", "start"]);
+        assert_eq!(flags.map, "TheIsland_WP");
+        assert_eq!(flags.game_port, None);
+        assert!(!flags.no_mods && !flags.battleye);
+        assert!(flags.platforms.is_empty());
+    }
+
+    #[test]
+    fn test_server_restart_takes_launch_options() {
+        let flags = launch_flags(&[
+            "asa_manager",
+            "--server-path",
+            "D:/ark",
+            "server",
+            "restart",
+            "--map",
+            "Ragnarok_WP",
+            "--port",
+            "7779",
+            "--platforms",
+            "PC,XSX",
+            "--custom-args",
+            "-ForceAllowCaveFlyers -NoTransferFromFiltering",
+            "--battleye",
+            "--no-mods",
+        ]);
+        assert_eq!(flags.map, "Ragnarok_WP");
+        assert_eq!(flags.game_port, Some(7779));
+        assert_eq!(flags.platforms, vec!["PC", "XSX"]);
+        assert_eq!(
+            flags.custom_args.as_deref(),
+            Some("-ForceAllowCaveFlyers -NoTransferFromFiltering")
+        );
+        assert!(flags.battleye && flags.no_mods);
+    }
+
+    #[test]
+    fn test_json_is_accepted_after_the_subcommand() {
+        let cli = Cli::try_parse_from([
+            "asa_manager",
+            "config",
+            "--xp",
+            "2.5",
+            "--dry-run",
+            "--json",
+        ])
+        .unwrap();
+        assert!(cli.json);
+        assert_eq!(cli.server_path, PathBuf::from("."));
+        match cli.command {
+            Commands::Config {
+                xp,
+                dry_run,
+                optimize,
+                ..
+            } => {
+                assert_eq!(xp, Some(2.5));
+                assert!(dry_run && !optimize);
+            }
+            _ => panic!("expected the config command"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments_are_rejected() {
+        assert!(
+            Cli::try_parse_from(["asa_manager", "server", "start", "--port", "99999"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["asa_manager", "server", "launch"]).is_err());
+        assert!(Cli::try_parse_from(["asa_manager"]).is_err());
+    }
 }
@@ -2,11 +2,12 @@
 
 use anyhow::Result;
 use colored::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use sysinfo::System;
 
 use crate::config::parse_active_mods;
+use crate::output::CommandOutput;
 
 /// CurseForge API base URL
 #[allow(dead_code)]
@@ -48,6 +49,23 @@ struct CurseForgeMultiResponse {
     data: Vec<CurseForgeMod>,
 }
 
+/// Local state of one active mod
+#[derive(Debug, Serialize)]
+pub struct ModStatus {
+    pub mod_id: u64,
+    pub installed: bool,
+    pub needs_download: bool,
+}
+
+/// Result of the update-mods command
+#[derive(Debug, Serialize)]
+pub struct ModUpdateOutput {
+    pub check_only: bool,
+    pub mods: Vec<ModStatus>,
+}
+
+impl CommandOutput for ModUpdateOutput {}
+
 /// Check if ARK server is running
 pub fn is_server_running() -> bool {
     let mut sys = System::new_all();
@@ -63,28 +81,36 @@ pub fn is_server_running() -> bool {
 }
 
 /// Handle mod update command
-pub async fn handle_mod_update(server_path: &Path, force: bool, check_only: bool) -> Result<()> {
-    println!("{}", "🔄 Checking mods...".cyan());
+pub async fn handle_mod_update(
+    server_path: &Path,
+    force: bool,
+    check_only: bool,
+) -> Result<ModUpdateOutput> {
+    say!("{}", "🔄 Checking mods...".cyan());
 
     // CRITICAL: Check if server is running
     if is_server_running() {
-        println!("{}", "❌ ERROR: Server is currently running!".red());
-        println!("  Stop the server before updating mods to prevent:");
-        println!("    • Mod download stutter");
-        println!("    • File corruption");
-        println!("    • Data loss");
+        say!("{}", "❌ ERROR: Server is currently running!".red());
+        say!("  Stop the server before updating mods to prevent:");
+        say!("    • Mod download stutter");
+        say!("    • File corruption");
+        say!("    • Data loss");
         anyhow::bail!("Server must be stopped before updating mods");
     }
 
     // Parse active mods from config
     let active_mods = parse_active_mods(server_path)?;
+    let mut result = ModUpdateOutput {
+        check_only,
+        mods: Vec::new(),
+    };
 
     if active_mods.is_empty() {
-        println!("  {} No active mods found in configuration", "ℹ️".blue());
-        return Ok(());
+        say!("  {} No active mods found in configuration", "ℹ️".blue());
+        return Ok(result);
     }
 
-    println!(
+    say!(
         "  Found {} active mod(s)",
         active_mods.len().to_string().yellow()
     );
@@ -92,23 +118,25 @@ pub async fn handle_mod_update(server_path: &Path, force: bool, check_only: bool
     // Check mods directory
     let mods_path = server_path.join("ShooterGame/Binaries/Win64/ShooterGame/Mods");
     if !mods_path.exists() {
-        println!("  {} Creating mods directory...", "⚠️".yellow());
+        say!("  {} Creating mods directory...", "⚠️".yellow());
         std::fs::create_dir_all(&mods_path)?;
     }
 
     // Check each mod
     for mod_id in &active_mods {
-        check_mod_status(&mods_path, *mod_id, force, check_only).await?;
+        result
+            .mods
+            .push(check_mod_status(&mods_path, *mod_id, force, check_only).await?);
     }
 
     if check_only {
-        println!(
+        say!(
             "  {}",
             "Check complete. Use without --check-only to download updates.".blue()
         );
     }
 
-    Ok(())
+    Ok(result)
 }
 
 /// Check status of a single mod
@@ -117,8 +145,8 @@ async fn check_mod_status(
     mod_id: u64,
     force: bool,
     check_only: bool,
-) -> Result<()> {
-    print!("  Mod {}: ", mod_id.to_string().cyan());
+) -> Result<ModStatus> {
+    say_inline!("  Mod {}: ", mod_id.to_string().cyan());
 
     // Check for local .ucas and .utoc files
     let _ucas_pattern = mods_path.join(format!("{}*.ucas", mod_id));
@@ -127,18 +155,24 @@ async fn check_mod_status(
     let has_ucas = glob_exists(&format!("{}/{}*.ucas", mods_path.display(), mod_id));
     let has_utoc = glob_exists(&format!("{}/{}*.utoc", mods_path.display(), mod_id));
 
+    let status = ModStatus {
+        mod_id,
+        installed: has_ucas && has_utoc,
+        needs_download: !(has_ucas && has_utoc) || force,
+    };
+
     if has_ucas && has_utoc && !force {
-        println!("{}", "✓ Installed".green());
-        return Ok(());
+        say!("{}", "✓ Installed".green());
+        return Ok(status);
     }
 
     if !has_ucas || !has_utoc {
-        println!("{}", "⚠️ Missing files".yellow());
+        say!("{}", "⚠️ Missing files".yellow());
 
         if check_only {
-            println!("    Would download mod {}", mod_id);
+            say!("    Would download mod {}", mod_id);
         } else {
-            println!(
+            say!(
                 "    {} Download required (use CurseForge app or manual install)",
                 "→".blue()
             );
@@ -146,16 +180,16 @@ async fn check_mod_status(
             // For now, we inform the user
         }
     } else if force {
-        println!("{}", "↻ Force update requested".yellow());
+        say!("{}", "↻ Force update requested".yellow());
         if !check_only {
-            println!(
+            say!(
                 "    {} Download required (use CurseForge app or manual install)",
                 "→".blue()
             );
         }
     }
 
-    Ok(())
+    Ok(status)
 }
 
 /// Check if any files match a glob pattern
@@ -0,0 +1,50 @@
+//! Output mode: colored text for people, JSON for scripts
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static JSON_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Switch all output to JSON (set once from the global --json flag)
+pub fn set_json(enabled: bool) {
+    JSON_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_json() -> bool {
+    JSON_MODE.load(Ordering::Relaxed)
+}
+
+/// println! that stays quiet in JSON mode so stdout is a single JSON document
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_json() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// print! counterpart of say!
+macro_rules! say_inline {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_json() {
+            print!($($arg)*);
+        }
+    };
+}
+
+/// Result of a subcommand, printed by main.rs as text or JSON
+pub trait CommandOutput: Serialize {
+    /// Human-readable summary. Most handlers report progress as they go,
+    /// so by default there is nothing left to print.
+    fn print_human(&self) {}
+}
+
+/// Print a handler result in the selected mode
+pub fn emit<T: CommandOutput>(result: &T) -> anyhow::Result<()> {
+    if is_json() {
+        println!("{}", serde_json::to_string_pretty(result)?);
+    } else {
+        result.print_human();
+    }
+    Ok(())
+}
@@ -0,0 +1,346 @@
+//! Server process control for headless use.
+//!
+//! The CLI runs standalone, so it launches ArkAscendedServer.exe itself instead of
+//! going through the desktop app. The command line comes from the same
+//! `build_launch_args` the app's ProcessManager uses, with values read from
+//! GameUserSettings.ini.
+//!
+//! The PID of a CLI-started server is written to `ShooterGame/Saved/asa_manager.pid`
+//! so later `stop`/`restart` calls (from any shell) can find it. Servers started by
+//! the desktop app are tracked there, not in this file, and should be stopped from the app.
+
+use crate::config::{get_game_user_settings_path, parse_active_mods};
+use crate::errors::AsaError;
+use crate::launch_args::{
+    build_launch_args, validate_platforms, LaunchOptions, DEFAULT_MOD_STRATEGY,
+};
+use crate::output::CommandOutput;
+use anyhow::{Context, Result};
+use colored::*;
+use ini::Ini;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use sysinfo::{Pid, System};
+
+/// PID file relative to server root
+const PID_FILE_PATH: &str = "ShooterGame/Saved/asa_manager.pid";
+
+/// Launch options not stored in GameUserSettings.ini
+#[derive(Debug, Clone, Default)]
+pub struct LaunchFlags {
+    pub map: String,
+    pub game_port: Option<u16>,
+    pub query_port: Option<u16>,
+    pub ip: Option<String>,
+    pub cluster_id: Option<String>,
+    pub cluster_dir: Option<String>,
+    pub custom_args: Option<String>,
+    pub no_mods: bool,
+    pub battleye: bool,
+    pub platforms: Vec<String>,
+}
+
+/// Result of a server subcommand
+#[derive(Debug, Serialize)]
+pub struct ServerOutput {
+    pub action: String,
+    pub running: bool,
+    pub pid: Option<u32>,
+}
+
+impl CommandOutput for ServerOutput {
+    fn print_human(&self) {
+        if self.action == "status" {
+            match self.pid {
+                Some(pid) if self.running => println!("  {} Running (PID {})", "●".green(), pid),
+                _ => println!("  {} Stopped", "●".red()),
+            }
+        }
+    }
+}
+
+fn pid_file(server_path: &Path) -> PathBuf {
+    server_path.join(PID_FILE_PATH)
+}
+
+/// PID from the pid file, if that process is still alive
+fn running_pid(server_path: &Path) -> Option<u32> {
+    let pid: u32 = fs::read_to_string(pid_file(server_path))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+
+    let sys = System::new_all();
+    sys.process(Pid::from_u32(pid)).map(|_| pid)
+}
+
+/// Arguments for ArkAscendedServer.exe: GameUserSettings.ini values, overridden by `flags`
+fn launch_command(server_path: &Path, flags: &LaunchFlags) -> Result<Vec<String>> {
+    let config_path = get_game_user_settings_path(server_path);
+    let ini = if config_path.exists() {
+        Ini::load_from_file(&config_path)
+            .with_context(|| format!("Failed to load {}", config_path.display()))?
+    } else {
+        Ini::new()
+    };
+    let get = |section: &str, key: &str| -> Option<String> {
+        ini.section(Some(section))
+            .and_then(|s| s.get(key))
+            .map(|v| v.trim_matches('"').to_string())
+    };
+
+    let session_name = get("SessionSettings", "SessionName")
+        .or_else(|| get("ServerSettings", "SessionName"))
+        .unwrap_or_else(|| "ASA Server".to_string());
+    let admin_password = get("ServerSettings", "ServerAdminPassword").ok_or_else(|| {
+        anyhow::anyhow!(
+            "ServerAdminPassword is not set in {}",
+            config_path.display()
+        )
+    })?;
+    let server_password = get("ServerSettings", "ServerPassword").filter(|p| !p.is_empty());
+    let max_players = get("ServerSettings", "MaxPlayers")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(70);
+    let rcon_port = get("ServerSettings", "RCONPort")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(27020);
+    let game_port = flags
+        .game_port
+        .or_else(|| get("URL", "Port").and_then(|v| v.parse().ok()))
+        .unwrap_or(7777);
+    let query_port = flags
+        .query_port
+        .or_else(|| get("URL", "QueryPort").and_then(|v| v.parse().ok()))
+        .unwrap_or(27015);
+
+    let mods: Vec<String> = if flags.no_mods {
+        Vec::new()
+    } else {
+        parse_active_mods(server_path)?
+            .iter()
+            .map(|id| id.to_string())
+            .collect()
+    };
+    let platforms = validate_platforms(&flags.platforms).map_err(|e| anyhow::anyhow!(e))?;
+
+    Ok(build_launch_args(&LaunchOptions {
+        map_name: &flags.map,
+        session_name: &session_name,
+        game_port,
+        query_port,
+        rcon_port,
+        max_players,
+        server_password: server_password.as_deref(),
+        admin_password: &admin_password,
+        ip_address: flags.ip.as_deref(),
+        cluster_id: flags.cluster_id.as_deref(),
+        cluster_dir: flags.cluster_dir.as_deref(),
+        mods: Some(&mods),
+        custom_args: flags.custom_args.as_deref(),
+        battleye: flags.battleye,
+        launch_flags: &[],
+        platforms: &platforms,
+        mod_strategy: DEFAULT_MOD_STRATEGY,
+    }))
+}
+
+/// Start the server in the background and record its PID
+pub fn start_server(server_path: &Path, flags: &LaunchFlags) -> Result<ServerOutput> {
+    say!("{}", "▶️ Starting server...".cyan());
+
+    if let Some(pid) = running_pid(server_path) {
+        say!("  {} Already running with PID {}", "⚠️".yellow(), pid);
+        return Err(AsaError::ServerRunning.into());
+    }
+
+    let executable = server_path.join("ShooterGame/Binaries/Win64/ArkAscendedServer.exe");
+    if !executable.exists() {
+        anyhow::bail!("Server executable not found at {}", executable.display());
+    }
+
+    let args = launch_command(server_path, flags)?;
+
+    say!("  🚀 {} {}", executable.display(), args.join(" ").dimmed());
+
+    let mut child = Command::new(&executable)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start server process")?;
+
+    // Catch immediate failures (bad map, missing DLLs) the same way the app does
+    std::thread::sleep(Duration::from_secs(5));
+    if let Some(status) = child.try_wait()? {
+        anyhow::bail!(
+            "Server process exited immediately with status: {}. Check ShooterGame/Saved/Logs/ShooterGame.log",
+            status
+        );
+    }
+
+    let pid = child.id();
+    fs::write(pid_file(server_path), pid.to_string()).context("Failed to write PID file")?;
+
+    say!("  {} Server started with PID {}", "✓".green(), pid);
+    Ok(ServerOutput {
+        action: "start".to_string(),
+        running: true,
+        pid: Some(pid),
+    })
+}
+
+/// Force stop the server recorded in the PID file
+pub fn stop_server(server_path: &Path) -> Result<ServerOutput> {
+    say!("{}", "⏹️ Stopping server...".cyan());
+
+    let pid = match running_pid(server_path) {
+        Some(pid) => pid,
+        None => {
+            let _ = fs::remove_file(pid_file(server_path));
+            say!("  {} No server started by the CLI is running", "ℹ️".blue());
+            return Ok(ServerOutput {
+                action: "stop".to_string(),
+                running: false,
+                pid: None,
+            });
+        }
+    };
+
+    #[cfg(target_os = "windows")]
+    {
+        // Kill the whole tree, the same as the app's stop_server
+        let _ = Command::new("taskkill")
+            .args(["/F", "/T", "/PID", &pid.to_string()])
+            .output();
+    }
+
+    let sys = System::new_all();
+    if let Some(process) = sys.process(Pid::from_u32(pid)) {
+        process.kill();
+    }
+
+    // Wait for the process to go away before reporting success
+    for _ in 0..15 {
+        if running_pid(server_path).is_none() {
+            break;
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    if running_pid(server_path).is_some() {
+        anyhow::bail!("Server process {} did not exit", pid);
+    }
+
+    let _ = fs::remove_file(pid_file(server_path));
+    say!("  {} Server stopped (PID {})", "✓".green(), pid);
+    Ok(ServerOutput {
+        action: "stop".to_string(),
+        running: false,
+        pid: Some(pid),
+    })
+}
+
+/// Stop (if running) and start again with the given flags
+pub fn restart_server(server_path: &Path, flags: &LaunchFlags) -> Result<ServerOutput> {
+    stop_server(server_path)?;
+    let mut result = start_server(server_path, flags)?;
+    result.action = "restart".to_string();
+    Ok(result)
+}
+
+/// Whether a CLI-started server is running
+pub fn server_status(server_path: &Path) -> Result<ServerOutput> {
+    let pid = running_pid(server_path);
+    Ok(ServerOutput {
+        action: "status".to_string(),
+        running: pid.is_some(),
+        pid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A server root with the given GameUserSettings.ini, removed by the caller
+    fn server_with_settings(name: &str, settings: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("asa_cli_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let config_path = get_game_user_settings_path(&root);
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, settings).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_launch_command_reads_game_user_settings() {
+        let root = server_with_settings(
+            "launch_ini",
+            "[SessionSettings]\nSessionName=Rag PvE\n\
+             [ServerSettings]\nServerAdminPassword=secret\nMaxPlayers=40\nRCONPort=27030\nActiveMods=928548,930404\n\
+             [URL]\nPort=7779\nQueryPort=27017\n",
+        );
+        let flags = LaunchFlags {
+            map: "Ragnarok_WP".to_string(),
+            ..Default::default()
+        };
+
+        let args = launch_command(&root, &flags).unwrap();
+        assert_eq!(
+            args[0],
+            "Ragnarok_WP?listen?SessionName=Rag PvE?Port=7779?QueryPort=27017?RCONPort=27030?MaxPlayers=40?ServerAdminPassword=secret"
+        );
+        assert!(args.contains(&"-NoBattlEye".to_string()));
+        assert!(args.contains(&"-mods=928548,930404".to_string()));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_launch_flags_override_the_ini() {
+        let root = server_with_settings(
+            "launch_flags",
+            "[ServerSettings]\nServerAdminPassword=secret\nActiveMods=928548\n[URL]\nPort=7779\n",
+        );
+        let flags = LaunchFlags {
+            map: "TheIsland_WP".to_string(),
+            game_port: Some(7790),
+            no_mods: true,
+            battleye: true,
+            platforms: vec!["pc".to_string(), "ps5".to_string()],
+            custom_args: Some("-ForceAllowCaveFlyers -NoTransferFromFiltering".to_string()),
+            ..Default::default()
+        };
+
+        let args = launch_command(&root, &flags).unwrap();
+        assert!(args[0].contains("?Port=7790?QueryPort=27015?"));
+        assert!(args.contains(&"-UseBattlEye".to_string()));
+        assert!(args.contains(&"-ServerPlatform=PC+PS5".to_string()));
+        assert!(!args.iter().any(|a| a.starts_with("-mods=")));
+        assert!(args.ends_with(&[
+            "-ForceAllowCaveFlyers".to_string(),
+            "-NoTransferFromFiltering".to_string()
+        ]));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_launch_command_needs_an_admin_password() {
+        let root = server_with_settings("launch_no_admin", "[ServerSettings]\nMaxPlayers=10\n");
+        let flags = LaunchFlags {
+            map: "TheIsland_WP".to_string(),
+            ..Default::default()
+        };
+
+        let err = launch_command(&root, &flags).unwrap_err();
+        assert!(err.to_string().contains("ServerAdminPassword"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -1,8 +1,29 @@
-use crate::models::{Backup, BackupOptions, BackupType, RestoreOptions};
+use crate::commands::rcon::{ensure_connected, RconState};
+use crate::models::{Backup, BackupOptions, BackupType, RestoreOptions, RestoreValidation};
 use crate::services::backup_service::BackupService;
+use crate::services::cloud_backup::{self, CloudConfig};
+use crate::services::notifications::{self, NotificationEvent};
+use crate::services::settings;
 use crate::AppState;
 use std::path::PathBuf;
-use tauri::State;
+use std::time::Duration;
+use tauri::{Manager, State};
+
+/// Time given to ARK to finish writing SavedArks after SaveWorld returns
+const QUIESCE_WAIT: Duration = Duration::from_secs(5);
+
+/// Flush the world to disk over RCON so the save files being copied are consistent
+async fn quiesce_world(state: &AppState, server_id: i64) -> Result<(), String> {
+    {
+        let rcon_state = state.app_handle.state::<RconState>();
+        let rcon = &rcon_state.0;
+        ensure_connected(state, rcon, server_id).await?;
+        rcon.save_world(server_id).await?;
+    }
+    println!("  💾 World saved, waiting for writes to settle");
+    tokio::time::sleep(QUIESCE_WAIT).await;
+    Ok(())
+}
 
 /// Create a real backup of the server
 #[tauri::command]
@@ -11,57 +32,99 @@ pub async fn create_backup(
     server_id: i64,
     backup_type: String,
     options: Option<BackupOptions>,
+) -> Result<Backup, String> {
+    let backup_type_enum = match backup_type.as_str() {
+        "auto" => BackupType::Auto,
+        "manual" => BackupType::Manual,
+        "pre-update" => BackupType::PreUpdate,
+        "pre-restart" => BackupType::PreRestart,
+        _ => return Err("Invalid backup type".to_string()),
+    };
+
+    run_backup(
+        &state,
+        server_id,
+        backup_type_enum,
+        options.unwrap_or_default(),
+    )
+    .await
+}
+
+/// Back up a server, record it and send the backup notification
+pub async fn run_backup(
+    state: &AppState,
+    server_id: i64,
+    backup_type_enum: BackupType,
+    backup_options: BackupOptions,
 ) -> Result<Backup, String> {
     println!(
         "💾 Creating {} backup for server {}",
-        backup_type, server_id
+        backup_type_enum.to_string(),
+        server_id
     );
 
     // Get server info from database
-    let (install_path, app_data_dir) = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+    let install_path: String = {
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
-        let install_path: String = conn
-            .query_row(
-                "SELECT install_path FROM servers WHERE id = ?1",
-                [server_id],
-                |row| row.get(0),
-            )
-            .map_err(|e| format!("Server not found: {}", e))?;
-
-        // Get app data dir for backups
-        let app_data_dir = PathBuf::from("C:/ASA_Backups");
-        (install_path, app_data_dir)
+        conn.query_row(
+            "SELECT install_path FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Server not found: {}", e))?
     };
 
-    let backup_type_enum = match backup_type.as_str() {
-        "auto" => BackupType::Auto,
-        "manual" => BackupType::Manual,
-        "pre-update" => BackupType::PreUpdate,
-        "pre-restart" => BackupType::PreRestart,
-        _ => return Err("Invalid backup type".to_string()),
-    };
+    let backup_dir = BackupService::get_backup_dir(&settings::backup_root(state), server_id);
+
+    if backup_options.quiesce
+        && backup_options.include_saves
+        && state.process_manager.is_running(server_id)
+    {
+        // A failed save still leaves the last autosave on disk, so carry on
+        if let Err(e) = quiesce_world(state, server_id).await {
+            println!("  ⚠️ Pre-backup SaveWorld failed, backing up as-is: {}", e);
+        }
+    }
 
-    let backup_options = options.unwrap_or_default();
-    let backup_dir = BackupService::get_backup_dir(&app_data_dir, server_id);
+    let encryption_key = if backup_options.encrypt {
+        Some(
+            settings::BACKUP_ENCRYPTION_KEY
+                .load(state)
+                .ok_or("Backup encryption is on but no encryption key is set in Settings")?,
+        )
+    } else {
+        None
+    };
 
-    let mut backup = BackupService::create_backup(
+    let mut backup = match BackupService::create_backup(
         &PathBuf::from(&install_path),
         &backup_dir,
         server_id,
         backup_type_enum,
         &backup_options,
-    )?;
+        encryption_key.as_deref(),
+    ) {
+        Ok(backup) => backup,
+        Err(e) => {
+            notifications::notify(
+                &state.app_handle,
+                server_id,
+                NotificationEvent::BackupFailed { error: e.clone() },
+            );
+            return Err(e);
+        }
+    };
 
     // Save backup to database
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         conn.execute(
-            "INSERT INTO backups (server_id, backup_type, file_path, size, includes_configs, includes_mods, includes_saves, includes_cluster, verified, created_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO backups (server_id, backup_type, file_path, size, includes_configs, includes_mods, includes_saves, includes_cluster, verified, encrypted, created_at) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             rusqlite::params![
                 backup.server_id,
                 backup.backup_type.to_string(),
@@ -72,6 +135,7 @@ pub async fn create_backup(
                 backup.includes_saves,
                 backup.includes_cluster,
                 backup.verified,
+                backup.encrypted,
                 backup.created_at,
             ],
         )
@@ -80,10 +144,154 @@ pub async fn create_backup(
         backup.id = conn.last_insert_rowid();
     }
 
+    notifications::notify(
+        &state.app_handle,
+        server_id,
+        NotificationEvent::BackupCompleted {
+            size_bytes: backup.size,
+        },
+    );
+
     println!("  ✅ Backup created: ID {}", backup.id);
+
+    // Off-site copy in the background; the local backup is already usable
+    if settings::CLOUD_AUTO_UPLOAD.load(state) {
+        let app_handle = state.app_handle.clone();
+        let backup_id = backup.id;
+        tauri::async_runtime::spawn(async move {
+            let state = app_handle.state::<AppState>();
+            if let Err(e) = upload_to_cloud(&state, backup_id).await {
+                println!("  ⚠️ Cloud upload of backup {} failed: {}", backup_id, e);
+            }
+        });
+    }
+
     Ok(backup)
 }
 
+/// Upload a backup archive to the configured bucket and record its URL
+async fn upload_to_cloud(state: &AppState, backup_id: i64) -> Result<String, String> {
+    let (file_path, server_id) = backup_file(state, backup_id)?;
+    let config = CloudConfig::load(state)?;
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Backup has no file name")?;
+
+    println!("  ☁️ Uploading backup {} to {}", backup_id, config.bucket);
+    let remote_url = cloud_backup::upload(
+        &config,
+        &file_path,
+        &config.object_key(server_id, &file_name),
+    )
+    .await?;
+
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE backups SET remote_url = ?1 WHERE id = ?2",
+        rusqlite::params![remote_url, backup_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    println!("  ✅ Backup {} uploaded: {}", backup_id, remote_url);
+    Ok(remote_url)
+}
+
+/// Archive path and owning server of a backup
+fn backup_file(state: &AppState, backup_id: i64) -> Result<(PathBuf, i64), String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    conn.query_row(
+        "SELECT file_path, server_id FROM backups WHERE id = ?1",
+        [backup_id],
+        |row| Ok((PathBuf::from(row.get::<_, String>(0)?), row.get(1)?)),
+    )
+    .map_err(|e| format!("Backup not found: {}", e))
+}
+
+/// Push a backup to cloud storage. Returns the s3:// URL.
+#[tauri::command]
+pub async fn upload_backup(state: State<'_, AppState>, backup_id: i64) -> Result<String, String> {
+    upload_to_cloud(&state, backup_id).await
+}
+
+/// Pull an uploaded backup back to its local path (e.g. after a disk failure) so it can be restored
+#[tauri::command]
+pub async fn download_backup_from_cloud(
+    state: State<'_, AppState>,
+    backup_id: i64,
+) -> Result<(), String> {
+    println!("☁️ Downloading backup {} from cloud storage", backup_id);
+
+    let (file_path, _) = backup_file(&state, backup_id)?;
+    let remote_url: Option<String> = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        conn.query_row(
+            "SELECT remote_url FROM backups WHERE id = ?1",
+            [backup_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Backup not found: {}", e))?
+    };
+    let remote_url = remote_url.ok_or("This backup was never uploaded")?;
+
+    let config = CloudConfig::load(&state)?;
+    cloud_backup::download(&config, &remote_url, &file_path).await?;
+
+    println!("  ✅ Backup {} downloaded to {:?}", backup_id, file_path);
+    Ok(())
+}
+
+/// Take an automatic safety backup unless a recent one already exists.
+/// Returns None when skipped.
+pub async fn backup_unless_recent(
+    state: &AppState,
+    server_id: i64,
+    backup_type: BackupType,
+) -> Result<Option<Backup>, String> {
+    let (window_minutes, latest) = {
+        let db = &state.db;
+        let window_minutes = settings::AUTO_BACKUP_SKIP_MINUTES.get(db) as i64;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        let latest: Option<String> = conn
+            .query_row(
+                "SELECT MAX(created_at) FROM backups WHERE server_id = ?1",
+                [server_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        (window_minutes, latest)
+    };
+
+    let recent = latest
+        .and_then(|at| chrono::DateTime::parse_from_rfc3339(&at).ok())
+        .map(|at| chrono::Local::now().signed_duration_since(at).num_minutes() < window_minutes)
+        .unwrap_or(false);
+    if recent {
+        println!(
+            "  ⏭️ Skipping {} backup for server {}: one was taken in the last {} minutes",
+            backup_type.to_string(),
+            server_id,
+            window_minutes
+        );
+        return Ok(None);
+    }
+
+    run_backup(state, server_id, backup_type, BackupOptions::default())
+        .await
+        .map(Some)
+}
+
+/// Password to open a backup: the one the user typed, else the configured encryption key.
+/// Plain archives ignore it.
+fn backup_password(state: &AppState, provided: Option<String>) -> Option<String> {
+    provided
+        .filter(|p| !p.is_empty())
+        .or_else(|| settings::BACKUP_ENCRYPTION_KEY.load(state))
+}
+
 /// Get all backups for a server from the database
 #[tauri::command]
 pub async fn get_backups(
@@ -93,13 +301,14 @@ pub async fn get_backups(
     println!("📋 Getting backups for server {}", server_id);
 
     let backups: Vec<Backup> = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         let mut stmt = conn
             .prepare(
                 "SELECT id, server_id, backup_type, file_path, size, includes_configs, includes_mods, 
-                        includes_saves, includes_cluster, verified, created_at 
+                        includes_saves, includes_cluster, verified, created_at,
+                        COALESCE(encrypted, 0), remote_url
                  FROM backups WHERE server_id = ?1 ORDER BY created_at DESC",
             )
             .map_err(|e| e.to_string())?;
@@ -126,6 +335,8 @@ pub async fn get_backups(
                     includes_saves: row.get(7)?,
                     includes_cluster: row.get(8)?,
                     verified: row.get(9)?,
+                    encrypted: row.get(11)?,
+                    remote_url: row.get(12)?,
                     created_at: row.get(10)?,
                 })
             })
@@ -144,12 +355,13 @@ pub async fn restore_backup(
     state: State<'_, AppState>,
     backup_id: i64,
     options: Option<RestoreOptions>,
-) -> Result<(), String> {
+    password: Option<String>,
+) -> Result<Option<RestoreValidation>, String> {
     println!("🔄 Restoring backup {}", backup_id);
 
     // Get backup and server info from database
-    let (backup_path, install_path) = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+    let (backup_path, install_path, map_name) = {
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         let result: (String, i64) = conn
@@ -160,27 +372,92 @@ pub async fn restore_backup(
             )
             .map_err(|e| format!("Backup not found: {}", e))?;
 
-        let install_path: String = conn
+        let (install_path, map_name): (String, String) = conn
             .query_row(
-                "SELECT install_path FROM servers WHERE id = ?1",
+                "SELECT install_path, map_name FROM servers WHERE id = ?1",
                 [result.1],
-                |row| row.get(0),
+                |row| Ok((row.get(0)?, row.get(1)?)),
             )
             .map_err(|e| format!("Server not found: {}", e))?;
 
-        (PathBuf::from(result.0), install_path)
+        (PathBuf::from(result.0), install_path, map_name)
     };
 
     let restore_options = options.unwrap_or_default();
+    let install_path = PathBuf::from(&install_path);
 
+    let password = backup_password(&state, password);
     BackupService::restore_backup(
         &backup_path,
-        &PathBuf::from(&install_path),
+        &install_path,
         &restore_options,
+        password.as_deref(),
     )?;
-
     println!("  ✅ Backup restored");
-    Ok(())
+
+    if !restore_options.restore_saves {
+        return Ok(None);
+    }
+
+    let validation = BackupService::validate_restored_saves(&install_path, &map_name);
+    for issue in &validation.issues {
+        println!("  ⚠️ Restore check: {}", issue);
+    }
+    Ok(Some(validation))
+}
+
+/// Restore one file from a backup (e.g. a griefed player's .arkprofile) without
+/// rolling back the rest of the world. Returns the path written.
+#[tauri::command]
+pub async fn restore_single_file(
+    state: State<'_, AppState>,
+    backup_id: i64,
+    archive_inner_path: String,
+    dest_override: Option<String>,
+    password: Option<String>,
+) -> Result<String, String> {
+    println!(
+        "🔄 Restoring '{}' from backup {}",
+        archive_inner_path, backup_id
+    );
+
+    let (backup_path, server_id, install_path) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let (file_path, server_id): (String, i64) = conn
+            .query_row(
+                "SELECT file_path, server_id FROM backups WHERE id = ?1",
+                [backup_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| format!("Backup not found: {}", e))?;
+
+        let install_path: String = conn
+            .query_row(
+                "SELECT install_path FROM servers WHERE id = ?1",
+                [server_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Server not found: {}", e))?;
+
+        (PathBuf::from(file_path), server_id, install_path)
+    };
+
+    // A running server keeps player/tribe data in memory and would overwrite the file on save
+    let dest_override = dest_override.filter(|d| !d.trim().is_empty());
+    if dest_override.is_none() && state.process_manager.is_running(server_id) {
+        return Err("Stop the server before restoring save files in place".to_string());
+    }
+
+    let target = BackupService::restore_single_file(
+        &backup_path,
+        &PathBuf::from(install_path),
+        &archive_inner_path,
+        dest_override.as_deref().map(std::path::Path::new),
+        backup_password(&state, password).as_deref(),
+    )?;
+    Ok(target.to_string_lossy().to_string())
 }
 
 /// Delete a backup
@@ -190,7 +467,7 @@ pub async fn delete_backup(state: State<'_, AppState>, backup_id: i64) -> Result
 
     // Get backup file path and delete from filesystem
     let file_path = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         let path: String = conn
@@ -211,7 +488,7 @@ pub async fn delete_backup(state: State<'_, AppState>, backup_id: i64) -> Result
 
     // Delete from database
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         conn.execute("DELETE FROM backups WHERE id = ?1", [backup_id])
@@ -224,11 +501,15 @@ pub async fn delete_backup(state: State<'_, AppState>, backup_id: i64) -> Result
 
 /// Verify backup integrity
 #[tauri::command]
-pub async fn verify_backup(state: State<'_, AppState>, backup_id: i64) -> Result<bool, String> {
+pub async fn verify_backup(
+    state: State<'_, AppState>,
+    backup_id: i64,
+    password: Option<String>,
+) -> Result<bool, String> {
     println!("🔍 Verifying backup {}", backup_id);
 
     let file_path = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         let path: String = conn
@@ -242,11 +523,12 @@ pub async fn verify_backup(state: State<'_, AppState>, backup_id: i64) -> Result
         PathBuf::from(path)
     };
 
-    let is_valid = BackupService::verify_backup(&file_path)?;
+    let password = backup_password(&state, password);
+    let is_valid = BackupService::verify_backup(&file_path, password.as_deref())?;
 
     // Update verified status in database
     if is_valid {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         conn.execute("UPDATE backups SET verified = 1 WHERE id = ?1", [backup_id])
@@ -266,7 +548,7 @@ pub async fn get_backup_contents(
     println!("📂 Getting backup contents for {}", backup_id);
 
     let file_path = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         let path: String = conn
@@ -289,7 +571,7 @@ pub async fn get_backup_contents(
 /// Cleanup old backups, keeping only the most recent N
 #[tauri::command]
 pub async fn cleanup_old_backups(
-    _state: State<'_, AppState>,
+    state: State<'_, AppState>,
     server_id: i64,
     keep_count: usize,
 ) -> Result<Vec<String>, String> {
@@ -298,7 +580,7 @@ pub async fn cleanup_old_backups(
         server_id, keep_count
     );
 
-    let backup_dir = BackupService::get_backup_dir(&PathBuf::from("C:/ASA_Backups"), server_id);
+    let backup_dir = BackupService::get_backup_dir(&settings::backup_root(&state), server_id);
     let deleted = BackupService::cleanup_old_backups(&backup_dir, server_id, keep_count)?;
 
     let deleted_paths: Vec<String> = deleted
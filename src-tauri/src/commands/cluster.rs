@@ -1,5 +1,10 @@
 use crate::models::{Cluster, ClusterStatus, ServerStatus, ServerStatusInfo};
+use crate::services::config_generator::{ConfigGenerator, ServerConfig};
+use crate::services::ini_parser::IniParser;
+use crate::services::server_state;
+use crate::services::settings;
 use crate::AppState;
+use serde::Serialize;
 use std::path::PathBuf;
 use tauri::State;
 
@@ -15,8 +20,11 @@ pub async fn create_cluster(
         server_ids.len()
     );
 
-    // Get app data dir for cluster path
-    let cluster_dir = format!("C:/ASA_Clusters/{}", name.replace(" ", "_"));
+    // Cluster folders live under the configured cluster root
+    let cluster_dir = settings::cluster_root(&state)
+        .join(name.replace(" ", "_"))
+        .to_string_lossy()
+        .to_string();
 
     // Create a cluster directory
     std::fs::create_dir_all(&cluster_dir)
@@ -24,7 +32,7 @@ pub async fn create_cluster(
 
     // Insert into database
     let cluster_id: i64 = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         // Serialize server_ids as JSON array
@@ -42,33 +50,9 @@ pub async fn create_cluster(
 
     // Link servers to cluster
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
-
-        for server_id in &server_ids {
-            // Insert into cluster_servers junction table
-            conn.execute(
-                "INSERT OR REPLACE INTO cluster_servers (cluster_id, server_id) VALUES (?1, ?2)",
-                rusqlite::params![cluster_id, server_id],
-            )
-            .map_err(|e| e.to_string())?;
-
-            // Set cluster_id on the server for startup arg lookup
-            conn.execute(
-                "UPDATE servers SET cluster_id = ?1 WHERE id = ?2",
-                rusqlite::params![cluster_id, server_id],
-            )
-            .map_err(|e| e.to_string())?;
-
-            // Update server's GameUserSettings.ini with ClusterDirOverride
-            if let Ok(install_path) = conn.query_row::<String, _, _>(
-                "SELECT install_path FROM servers WHERE id = ?1",
-                [server_id],
-                |row| row.get(0),
-            ) {
-                update_cluster_config(&install_path, &cluster_dir);
-            }
-        }
+        link_servers_to_cluster(&conn, cluster_id, &cluster_dir, &server_ids)?;
     }
 
     let cluster = Cluster {
@@ -83,12 +67,115 @@ pub async fn create_cluster(
     Ok(cluster)
 }
 
+/// Register an existing ClusterDirOverride folder (e.g. a hand-configured cluster)
+/// without recreating it, and link the given servers to it
+#[tauri::command]
+pub async fn import_cluster(
+    state: State<'_, AppState>,
+    name: String,
+    cluster_path: String,
+    server_ids: Vec<i64>,
+) -> Result<Cluster, String> {
+    println!(
+        "📥 Importing cluster: {} from {} with {} servers",
+        name,
+        cluster_path,
+        server_ids.len()
+    );
+
+    let path = PathBuf::from(&cluster_path);
+    if !path.is_dir() {
+        return Err(format!("Cluster directory not found: {}", cluster_path));
+    }
+
+    // Make sure servers will be able to write transfer data here
+    let probe = path.join(".asa_manager_write_test");
+    std::fs::write(&probe, b"ok")
+        .map_err(|e| format!("Cluster directory is not writable: {}", e))?;
+    let _ = std::fs::remove_file(&probe);
+
+    let cluster_id: i64 = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT name FROM clusters WHERE cluster_path = ?1",
+                [&cluster_path],
+                |row| row.get(0),
+            )
+            .ok();
+        if let Some(existing) = existing {
+            return Err(format!(
+                "This directory is already registered as cluster '{}'",
+                existing
+            ));
+        }
+
+        let server_ids_json = serde_json::to_string(&server_ids)
+            .map_err(|e| format!("Failed to serialize server_ids: {}", e))?;
+
+        conn.execute(
+            "INSERT INTO clusters (name, cluster_path, server_ids) VALUES (?1, ?2, ?3)",
+            rusqlite::params![name, cluster_path, server_ids_json],
+        )
+        .map_err(|e| e.to_string())?;
+
+        let cluster_id = conn.last_insert_rowid();
+        link_servers_to_cluster(&conn, cluster_id, &cluster_path, &server_ids)?;
+        cluster_id
+    };
+
+    println!("  ✅ Cluster imported: ID {}", cluster_id);
+    Ok(Cluster {
+        id: cluster_id,
+        name,
+        cluster_path: path,
+        server_ids,
+        created_at: chrono::Local::now().to_rfc3339(),
+    })
+}
+
+/// Link servers to a cluster: junction rows, servers.cluster_id and ClusterDirOverride in INI
+fn link_servers_to_cluster(
+    conn: &rusqlite::Connection,
+    cluster_id: i64,
+    cluster_dir: &str,
+    server_ids: &[i64],
+) -> Result<(), String> {
+    for server_id in server_ids {
+        // Insert into cluster_servers junction table
+        conn.execute(
+            "INSERT OR REPLACE INTO cluster_servers (cluster_id, server_id) VALUES (?1, ?2)",
+            rusqlite::params![cluster_id, server_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        // Set cluster_id on the server for startup arg lookup
+        conn.execute(
+            "UPDATE servers SET cluster_id = ?1 WHERE id = ?2",
+            rusqlite::params![cluster_id, server_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        // Update server's GameUserSettings.ini with ClusterDirOverride
+        if let Ok(install_path) = conn.query_row::<String, _, _>(
+            "SELECT install_path FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        ) {
+            update_cluster_config(&install_path, cluster_dir);
+        }
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_clusters(state: State<'_, AppState>) -> Result<Vec<Cluster>, String> {
     println!("📋 Getting all clusters");
 
     let clusters = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         let mut stmt = conn
@@ -139,7 +226,7 @@ pub async fn delete_cluster(state: State<'_, AppState>, cluster_id: i64) -> Resu
     println!("🗑️ Deleting cluster: {}", cluster_id);
 
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         // Remove cluster-server links
@@ -165,7 +252,7 @@ pub async fn add_server_to_cluster(
     cluster_id: i64,
     server_id: i64,
 ) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute(
@@ -193,7 +280,7 @@ pub async fn remove_server_from_cluster(
     cluster_id: i64,
     server_id: i64,
 ) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute(
@@ -206,7 +293,7 @@ pub async fn remove_server_from_cluster(
 }
 
 /// Update GameUserSettings.ini with ClusterDirOverride
-fn update_cluster_config(install_path: &str, cluster_path: &str) {
+pub(crate) fn update_cluster_config(install_path: &str, cluster_path: &str) {
     let config_path = PathBuf::from(install_path)
         .join("ShooterGame/Saved/Config/WindowsServer/GameUserSettings.ini");
 
@@ -240,11 +327,118 @@ fn update_cluster_config(install_path: &str, cluster_path: &str) {
             result
         };
 
-        let _ = std::fs::write(&config_path, new_content);
+        let _ = IniParser::write_atomic(&config_path, &new_content);
         println!("  📝 Updated cluster config for server at {}", install_path);
     }
 }
 
+/// Per-server outcome of a cluster-wide config change
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterApplyResult {
+    pub server_id: i64,
+    pub server_name: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// Write selected ServerConfig fields to every cluster member's INI files.
+/// Each server's configs are backed up first and unselected keys are left untouched,
+/// so per-map values like map name and ports stay unique.
+#[tauri::command]
+pub async fn apply_config_to_cluster(
+    state: State<'_, AppState>,
+    cluster_id: i64,
+    config: ServerConfig,
+    fields: Vec<String>,
+) -> Result<Vec<ClusterApplyResult>, String> {
+    println!(
+        "🔗 Applying {} fields to cluster {}",
+        fields.len(),
+        cluster_id
+    );
+
+    if fields.is_empty() {
+        return Err("No fields selected".to_string());
+    }
+
+    let (gus_update, game_update) = ConfigGenerator::generate_partial_configs(&config, &fields)?;
+
+    let members: Vec<(i64, String, String)> = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT s.id, s.name, s.install_path FROM servers s
+                 INNER JOIN cluster_servers cs ON s.id = cs.server_id
+                 WHERE cs.cluster_id = ?1",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map([cluster_id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .map_err(|e| e.to_string())?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    if members.is_empty() {
+        return Err("Cluster has no servers".to_string());
+    }
+
+    let mut results = Vec::new();
+    for (server_id, server_name, install_path) in members {
+        let outcome =
+            apply_partial_config(&PathBuf::from(&install_path), &gus_update, &game_update);
+        match &outcome {
+            Ok(_) => println!("  ✅ Updated {}", server_name),
+            Err(e) => println!("  ❌ Failed to update {}: {}", server_name, e),
+        }
+        results.push(ClusterApplyResult {
+            server_id,
+            server_name,
+            success: outcome.is_ok(),
+            message: outcome
+                .err()
+                .unwrap_or_else(|| "Config updated".to_string()),
+        });
+    }
+
+    Ok(results)
+}
+
+/// Back up a server's configs, then merge INI fragments into them
+fn apply_partial_config(
+    install_path: &PathBuf,
+    gus_update: &str,
+    game_update: &str,
+) -> Result<(), String> {
+    let config_dir = install_path.join("ShooterGame/Saved/Config/WindowsServer");
+    if config_dir.exists() {
+        ConfigGenerator::backup_configs(install_path)?;
+    } else {
+        std::fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+
+    for (file_name, update) in [
+        ("GameUserSettings.ini", gus_update),
+        ("Game.ini", game_update),
+    ] {
+        if update.is_empty() {
+            continue;
+        }
+        let path = config_dir.join(file_name);
+        let existing = std::fs::read_to_string(&path).unwrap_or_default();
+        IniParser::write_atomic(&path, &IniParser::merge(&existing, update))
+            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+    }
+
+    Ok(())
+}
+
 /// Get the status of all servers in a cluster
 #[tauri::command]
 pub async fn get_cluster_status(
@@ -253,7 +447,7 @@ pub async fn get_cluster_status(
 ) -> Result<ClusterStatus, String> {
     println!("📊 Getting cluster status for {}", cluster_id);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     // Get cluster info
@@ -279,15 +473,7 @@ pub async fn get_cluster_status(
             let id: i64 = row.get(0)?;
             let name: String = row.get(1)?;
             let status_str: String = row.get(2)?;
-            let status = match status_str.as_str() {
-                "running" => ServerStatus::Running,
-                "starting" => ServerStatus::Starting,
-                "stopped" => ServerStatus::Stopped,
-                "crashed" => ServerStatus::Crashed,
-                "updating" => ServerStatus::Updating,
-                "restarting" => ServerStatus::Restarting,
-                _ => ServerStatus::Stopped,
-            };
+            let status = ServerStatus::from_db(&status_str);
             Ok((id, name, status))
         })
         .map_err(|e| e.to_string())?;
@@ -298,7 +484,7 @@ pub async fn get_cluster_status(
 
     for server_result in server_iter {
         if let Ok((id, name, status)) = server_result {
-            if matches!(status, ServerStatus::Running) {
+            if matches!(status, ServerStatus::Running | ServerStatus::Online) {
                 running_servers += 1;
             }
             // For now, player count is 0 - would need RCON integration to get real count
@@ -334,7 +520,7 @@ pub async fn start_cluster(state: State<'_, AppState>, cluster_id: i64) -> Resul
 
     // Get cluster info first
     let (cluster_name, cluster_path): (String, String) = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         conn.query_row(
@@ -345,136 +531,56 @@ pub async fn start_cluster(state: State<'_, AppState>, cluster_id: i64) -> Resul
         .map_err(|e| format!("Cluster not found: {}", e))?
     };
 
-    // Get all server info for this cluster
-    let servers: Vec<(
-        i64,
-        String,
-        String,
-        String,
-        u16,
-        u16,
-        u16,
-        i32,
-        Option<String>,
-        String,
-        Option<String>,
-        Option<String>,
-    )> = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+    // Stopped servers in this cluster
+    let server_ids: Vec<i64> = {
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         let mut stmt = conn
             .prepare(
-                "SELECT s.id, s.install_path, s.map_name, s.session_name, s.game_port, 
-                        s.query_port, s.rcon_port, s.max_players, s.server_password, s.admin_password, s.ip_address, s.custom_args
-                 FROM servers s
+                "SELECT s.id FROM servers s
                  INNER JOIN cluster_servers cs ON s.id = cs.server_id
                  WHERE cs.cluster_id = ?1 AND s.status = 'stopped'",
             )
             .map_err(|e| e.to_string())?;
-
-        let mut result = Vec::new();
-        let mut rows = stmt.query([cluster_id]).map_err(|e| e.to_string())?;
-        while let Some(row) = rows.next().map_err(|e| e.to_string())? {
-            result.push((
-                row.get::<_, i64>(0).unwrap_or(0),
-                row.get::<_, String>(1).unwrap_or_default(),
-                row.get::<_, String>(2).unwrap_or_default(),
-                row.get::<_, String>(3).unwrap_or_default(),
-                row.get::<_, u16>(4).unwrap_or(7777),
-                row.get::<_, u16>(5).unwrap_or(27015),
-                row.get::<_, u16>(6).unwrap_or(27020),
-                row.get::<_, i32>(7).unwrap_or(70),
-                row.get::<_, Option<String>>(8).unwrap_or(None),
-                row.get::<_, String>(9).unwrap_or_default(),
-                row.get::<_, Option<String>>(10).unwrap_or(None),
-                row.get::<_, Option<String>>(11).unwrap_or(None),
-            ));
-        }
-        result
+        let ids = stmt
+            .query_map([cluster_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+        ids
     };
 
     // Start each server with cluster args
-    for (
-        server_id,
-        install_path,
-        map_name,
-        session_name,
-        game_port,
-        query_port,
-        rcon_port,
-        max_players,
-        server_password,
-        admin_password,
-        ip_address,
-        custom_args,
-    ) in servers
-    {
-        // Get enabled mods for this server
-        let enabled_mods: Vec<String> = {
-            let db = state.db.lock().map_err(|e| e.to_string())?;
-            let conn = db.get_connection().map_err(|e| e.to_string())?;
-
-            let mut stmt = conn.prepare(
-                "SELECT mod_id FROM mods WHERE server_id = ?1 AND enabled = 1 ORDER BY load_order ASC"
-            ).map_err(|e| e.to_string())?;
-
-            let mut rows = stmt.query([server_id]).map_err(|e| e.to_string())?;
-            let mut mods = Vec::new();
-            while let Some(row) = rows.next().map_err(|e| e.to_string())? {
-                if let Ok(mod_id) = row.get::<_, String>(0) {
-                    mods.push(mod_id);
-                }
+    for server_id in server_ids {
+        let mut launch = match crate::commands::server::server_launch(&state, server_id) {
+            Ok(launch) => launch,
+            Err(e) => {
+                println!("  ⚠️ Failed to start server {}: {}", server_id, e);
+                continue;
             }
-            mods
         };
+        launch.cluster_id = Some(cluster_name.clone());
+        launch.cluster_dir = Some(cluster_path.clone());
 
-        if !enabled_mods.is_empty() {
+        if !launch.mods.is_empty() {
             println!(
                 "  🧩 Found {} enabled mods for server {}",
-                enabled_mods.len(),
+                launch.mods.len(),
                 server_id
             );
         }
 
-        let install_path = PathBuf::from(&install_path);
-        let server_password_ref = server_password.as_deref();
-        let ip_address_ref = ip_address.as_deref();
-        let mods_option = if enabled_mods.is_empty() {
-            None
-        } else {
-            Some(enabled_mods.as_slice())
-        };
-
         if let Err(e) = state.process_manager.start_server(
             server_id,
-            "ASA",
-            &install_path,
-            &map_name,
-            &session_name,
-            game_port,
-            query_port,
-            rcon_port,
-            max_players,
-            server_password_ref,
-            &admin_password,
-            ip_address_ref,
-            Some(&cluster_name),
-            Some(&cluster_path),
-            mods_option,
-            custom_args.as_deref(),
+            &launch.install_path,
+            launch.use_asa_api,
+            &launch.options(),
         ) {
             println!("  ⚠️ Failed to start server {}: {}", server_id, e);
         } else {
             // Update status in database
-            if let Ok(db) = state.db.lock() {
-                if let Ok(conn) = db.get_connection() {
-                    let _ = conn.execute(
-                        "UPDATE servers SET status = 'starting' WHERE id = ?1",
-                        [server_id],
-                    );
-                }
-            }
+            server_state::update(&state, server_id, ServerStatus::Starting);
             println!("  ✅ Started server {}", server_id);
         }
         // Small delay between starts to prevent overwhelming the system
@@ -490,14 +596,14 @@ pub async fn stop_cluster(state: State<'_, AppState>, cluster_id: i64) -> Result
     println!("⏹️ Stopping all servers in cluster {}", cluster_id);
 
     let server_ids: Vec<i64> = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         let mut stmt = conn
             .prepare(
                 "SELECT s.id FROM servers s
                  INNER JOIN cluster_servers cs ON s.id = cs.server_id
-                 WHERE cs.cluster_id = ?1 AND s.status = 'running'",
+                 WHERE cs.cluster_id = ?1 AND s.status IN ('starting', 'running', 'online')",
             )
             .map_err(|e| e.to_string())?;
 
@@ -513,21 +619,53 @@ pub async fn stop_cluster(state: State<'_, AppState>, cluster_id: i64) -> Result
 
     // Stop each server
     for server_id in server_ids {
-        if let Err(e) = state.process_manager.stop_server(server_id) {
+        if let Err(e) = state.process_manager.stop_server(server_id).await {
             println!("  ⚠️ Failed to stop server {}: {}", server_id, e);
         } else {
             // Update status in database
-            if let Ok(db) = state.db.lock() {
-                if let Ok(conn) = db.get_connection() {
-                    let _ = conn.execute(
-                        "UPDATE servers SET status = 'stopped' WHERE id = ?1",
-                        [server_id],
-                    );
-                }
-            }
+            server_state::update(&state, server_id, ServerStatus::Stopped);
             println!("  ✅ Stopped server {}", server_id);
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pushing a partial config to a member must not collapse the repeated
+    /// override keys in its Game.ini
+    #[test]
+    fn test_apply_partial_config_keeps_repeated_keys() {
+        let dir = crate::db::test_support::TempDir::new("cluster_partial");
+        let install_path = dir.path().to_path_buf();
+        let config_dir = install_path.join("ShooterGame/Saved/Config/WindowsServer");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        let game_ini = "[/Script/ShooterGame.ShooterGameMode]\r\n\
+            OverrideNamedEngramEntries=(EngramClassName=\"EngramEntry_Campfire_C\",EngramLevelRequirement=1)\r\n\
+            MatingIntervalMultiplier=1.00\r\n\
+            OverrideNamedEngramEntries=(EngramClassName=\"EngramEntry_Forge_C\",EngramLevelRequirement=5)\r\n";
+        std::fs::write(config_dir.join("Game.ini"), game_ini).unwrap();
+
+        let config = ServerConfig {
+            mating_interval_multiplier: 0.25,
+            ..Default::default()
+        };
+        let (gus_update, game_update) = ConfigGenerator::generate_partial_configs(
+            &config,
+            &["matingIntervalMultiplier".to_string()],
+        )
+        .unwrap();
+        apply_partial_config(&install_path, &gus_update, &game_update).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(config_dir.join("Game.ini")).unwrap(),
+            game_ini.replace(
+                "MatingIntervalMultiplier=1.00",
+                "MatingIntervalMultiplier=0.25"
+            )
+        );
+    }
+}
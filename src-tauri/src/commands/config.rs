@@ -1,14 +1,18 @@
-use crate::services::config_generator::{ConfigGenerator, MapProfile, ServerConfig};
-use crate::services::ini_parser::IniParser;
+use crate::services::config_generator::{
+    ConfigGenerator, MapProfile, ServerConfig, GAME_INI_SECTION,
+};
+use crate::services::ini_parser::{IniParser, GLOBAL_SECTION};
 use crate::AppState;
-use chrono::Local;
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use tauri::State;
 
 /// Helper to get server install path from database
 fn get_server_install_path(state: &State<'_, AppState>, server_id: i64) -> Result<String, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
     conn.query_row(
         "SELECT install_path FROM servers WHERE id = ?1",
@@ -30,6 +34,184 @@ fn get_backup_dir(install_path: &str) -> PathBuf {
     PathBuf::from(install_path).join("ShooterGame/Saved/Config/WindowsServer/Backups")
 }
 
+/// GameUserSettings.ini values that start_server reads from the database
+#[derive(Default)]
+struct IniServerValues {
+    session_name: Option<String>,
+    map_name: Option<String>,
+    max_players: Option<i32>,
+    server_password: Option<String>,
+    admin_password: Option<String>,
+    rcon_enabled: Option<bool>,
+    rcon_port: Option<u16>,
+    game_port: Option<u16>,
+    query_port: Option<u16>,
+    /// Field name -> (section, key) it was read from
+    locations: HashMap<&'static str, (String, String)>,
+}
+
+fn parse_ini_server_values(content: &str) -> IniServerValues {
+    let mut values = IniServerValues::default();
+    let mut current_section = String::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        // Section header
+        if line.starts_with('[') && line.ends_with(']') {
+            current_section = line[1..line.len() - 1].to_string();
+            continue;
+        }
+
+        // Key=Value pair
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            let raw_value = value.trim();
+
+            // Remove surrounding quotes if present
+            let value =
+                if raw_value.starts_with('"') && raw_value.ends_with('"') && raw_value.len() >= 2 {
+                    &raw_value[1..raw_value.len() - 1]
+                } else {
+                    raw_value
+                };
+
+            let field = if current_section == "ServerSettings"
+                || current_section == "/Script/ShooterGame.ShooterGameMode"
+            {
+                match key {
+                    "SessionName" | "ServerName" => {
+                        values.session_name = Some(value.to_string());
+                        Some("sessionName")
+                    }
+                    "MapName" => {
+                        values.map_name = Some(value.to_string());
+                        Some("mapName")
+                    }
+                    "MaxPlayers" => {
+                        values.max_players = value.parse().ok();
+                        Some("maxPlayers")
+                    }
+                    "ServerPassword" => {
+                        values.server_password = Some(value.to_string());
+                        Some("serverPassword")
+                    }
+                    "ServerAdminPassword" => {
+                        values.admin_password = Some(value.to_string());
+                        Some("adminPassword")
+                    }
+                    "RCONEnabled" => {
+                        values.rcon_enabled = Some(value.to_uppercase() == "TRUE");
+                        Some("rconEnabled")
+                    }
+                    "RCONPort" => {
+                        values.rcon_port = value.parse().ok();
+                        Some("rconPort")
+                    }
+                    _ => None,
+                }
+            } else if current_section == "URL" || current_section == "/Script/Engine.GameSession" {
+                match key {
+                    "Port" => {
+                        values.game_port = value.parse().ok();
+                        Some("gamePort")
+                    }
+                    "QueryPort" => {
+                        values.query_port = value.parse().ok();
+                        Some("queryPort")
+                    }
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            if let Some(field) = field {
+                values
+                    .locations
+                    .insert(field, (current_section.clone(), key.to_string()));
+            }
+        }
+    }
+
+    values
+}
+
+/// Write the values found in GameUserSettings.ini back to the servers row
+fn sync_ini_values_to_db(
+    state: &AppState,
+    server_id: i64,
+    values: &IniServerValues,
+) -> Result<(), String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let mut query = "UPDATE servers SET ".to_string();
+    let mut updates = Vec::new();
+    let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(v) = &values.session_name {
+        updates.push("session_name = ?");
+        params.push(Box::new(v.clone()));
+    }
+    if let Some(v) = &values.map_name {
+        updates.push("map_name = ?");
+        params.push(Box::new(v.clone()));
+    }
+    if let Some(v) = values.max_players {
+        updates.push("max_players = ?");
+        params.push(Box::new(v));
+    }
+    // Handle password specially - empty string means remove it (set to null in DB context usually, but here we might wrap)
+    // But for strings we usually just overwrite.
+    if let Some(v) = &values.server_password {
+        updates.push("server_password = ?");
+        if v.is_empty() {
+            params.push(Box::new(None::<String>));
+        } else {
+            params.push(Box::new(Some(v.clone())));
+        }
+    }
+    if let Some(v) = &values.admin_password {
+        updates.push("admin_password = ?");
+        params.push(Box::new(v.clone()));
+    }
+    if let Some(v) = values.rcon_enabled {
+        updates.push("rcon_enabled = ?");
+        params.push(Box::new(v));
+    }
+    if let Some(v) = values.rcon_port {
+        updates.push("rcon_port = ?");
+        params.push(Box::new(v));
+    }
+    if let Some(v) = values.game_port {
+        updates.push("game_port = ?");
+        params.push(Box::new(v));
+    }
+    if let Some(v) = values.query_port {
+        updates.push("query_port = ?");
+        params.push(Box::new(v));
+    }
+
+    if !updates.is_empty() {
+        query.push_str(&updates.join(", "));
+        query.push_str(" WHERE id = ?");
+        params.push(Box::new(server_id));
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+
+        conn.execute(&query, params_refs.as_slice())
+            .map_err(|e| format!("Failed to update database: {}", e))?;
+
+        println!(
+            "✅ Synced settings from INI to Database for server {}",
+            server_id
+        );
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn read_config(
     state: State<'_, AppState>,
@@ -77,140 +259,13 @@ pub async fn save_config(
         content.clone()
     };
 
-    fs::write(&file_path, &final_content).map_err(|e| e.to_string())?;
+    IniParser::write_atomic(&file_path, &final_content).map_err(|e| e.to_string())?;
     println!("  ✅ Saved {} to {:?}", config_type, file_path);
 
     // If we're saving GameUserSettings.ini, we need to sync critical values to the database
     // because the start_server command reads from the DB, not the INI files
     if config_type == "GameUserSettings" {
-        let mut session_name: Option<String> = None;
-        let mut map_name: Option<String> = None;
-        let mut max_players: Option<i32> = None;
-        let mut server_password: Option<String> = None;
-        let mut admin_password: Option<String> = None;
-        let mut rcon_enabled: Option<bool> = None;
-        let mut rcon_port: Option<u16> = None;
-        let mut game_port: Option<u16> = None;
-        let mut query_port: Option<u16> = None;
-
-        let mut current_section = String::new();
-
-        for line in content.lines() {
-            let line = line.trim();
-
-            // Section header
-            if line.starts_with('[') && line.ends_with(']') {
-                current_section = line[1..line.len() - 1].to_string();
-                continue;
-            }
-
-            // Key=Value pair
-            if let Some((key, value)) = line.split_once('=') {
-                let key = key.trim();
-                let raw_value = value.trim();
-
-                // Remove surrounding quotes if present
-                let value = if raw_value.starts_with('"')
-                    && raw_value.ends_with('"')
-                    && raw_value.len() >= 2
-                {
-                    &raw_value[1..raw_value.len() - 1]
-                } else {
-                    raw_value
-                };
-
-                if current_section == "ServerSettings"
-                    || current_section == "/Script/ShooterGame.ShooterGameMode"
-                {
-                    match key {
-                        "SessionName" | "ServerName" => session_name = Some(value.to_string()),
-                        "MapName" => map_name = Some(value.to_string()),
-                        "MaxPlayers" => max_players = value.parse().ok(),
-                        "ServerPassword" => server_password = Some(value.to_string()),
-                        "ServerAdminPassword" => admin_password = Some(value.to_string()),
-                        "RCONEnabled" => rcon_enabled = Some(value.to_uppercase() == "TRUE"),
-                        "RCONPort" => rcon_port = value.parse().ok(),
-                        _ => {}
-                    }
-                }
-
-                if current_section == "URL" || current_section == "/Script/Engine.GameSession" {
-                    match key {
-                        "Port" => game_port = value.parse().ok(),
-                        "QueryPort" => query_port = value.parse().ok(),
-                        _ => {}
-                    }
-                }
-            }
-        }
-
-        // Perform the update
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.get_connection().map_err(|e| e.to_string())?;
-
-        let mut query = "UPDATE servers SET ".to_string();
-        let mut updates = Vec::new();
-        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-
-        if let Some(v) = session_name {
-            updates.push("session_name = ?");
-            params.push(Box::new(v));
-        }
-        if let Some(v) = map_name {
-            updates.push("map_name = ?");
-            params.push(Box::new(v));
-        }
-        if let Some(v) = max_players {
-            updates.push("max_players = ?");
-            params.push(Box::new(v));
-        }
-        // Handle password specially - empty string means remove it (set to null in DB context usually, but here we might wrap)
-        // But for strings we usually just overwrite.
-        if let Some(v) = server_password {
-            updates.push("server_password = ?");
-            if v.is_empty() {
-                params.push(Box::new(None::<String>));
-            } else {
-                params.push(Box::new(Some(v)));
-            }
-        }
-        if let Some(v) = admin_password {
-            updates.push("admin_password = ?");
-            params.push(Box::new(v));
-        }
-        if let Some(v) = rcon_enabled {
-            updates.push("rcon_enabled = ?");
-            params.push(Box::new(v));
-        }
-        if let Some(v) = rcon_port {
-            updates.push("rcon_port = ?");
-            params.push(Box::new(v));
-        }
-        if let Some(v) = game_port {
-            updates.push("game_port = ?");
-            params.push(Box::new(v));
-        }
-        if let Some(v) = query_port {
-            updates.push("query_port = ?");
-            params.push(Box::new(v));
-        }
-
-        if !updates.is_empty() {
-            query.push_str(&updates.join(", "));
-            query.push_str(" WHERE id = ?");
-            params.push(Box::new(server_id));
-
-            let params_refs: Vec<&dyn rusqlite::ToSql> =
-                params.iter().map(|p| p.as_ref()).collect();
-
-            conn.execute(&query, params_refs.as_slice())
-                .map_err(|e| format!("Failed to update database: {}", e))?;
-
-            println!(
-                "✅ Synced settings from INI to Database for server {}",
-                server_id
-            );
-        }
+        sync_ini_values_to_db(&state, server_id, &parse_ini_server_values(&content))?;
     }
 
     Ok(())
@@ -300,6 +355,519 @@ pub async fn list_config_backups(
     Ok(backups)
 }
 
+// ===============================================
+// Config Drift Commands
+// ===============================================
+
+/// A value that differs between the servers row and GameUserSettings.ini
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDriftMismatch {
+    pub field: String,
+    pub section: String,
+    pub key: String,
+    pub db_value: Option<String>,
+    pub ini_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDriftReport {
+    pub server_id: i64,
+    pub file_path: String,
+    pub mismatches: Vec<ConfigDriftMismatch>,
+    /// "pull" (INI -> DB) when the file was edited since the server last started,
+    /// otherwise "push" (DB -> INI). None when there is nothing to resolve.
+    pub suggested_direction: Option<String>,
+}
+
+/// Compare the key GameUserSettings.ini values to the servers row.
+/// Keys missing from the INI are not reported, start_server falls back to the DB for those.
+pub fn detect_config_drift(state: &AppState, server_id: i64) -> Result<ConfigDriftReport, String> {
+    let (install_path, db_values, last_started): (
+        String,
+        Vec<(&'static str, Option<String>)>,
+        Option<String>,
+    ) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        conn.query_row(
+            "SELECT install_path, session_name, map_name, max_players, server_password,
+                    admin_password, rcon_enabled, rcon_port, game_port, query_port, last_started
+             FROM servers WHERE id = ?1",
+            [server_id],
+            |row| {
+                let rcon_enabled: Option<bool> = row.get(6)?;
+                Ok((
+                    row.get(0)?,
+                    vec![
+                        ("sessionName", row.get::<_, Option<String>>(1)?),
+                        ("mapName", row.get::<_, Option<String>>(2)?),
+                        (
+                            "maxPlayers",
+                            row.get::<_, Option<i32>>(3)?.map(|v| v.to_string()),
+                        ),
+                        ("serverPassword", row.get::<_, Option<String>>(4)?),
+                        ("adminPassword", row.get::<_, Option<String>>(5)?),
+                        (
+                            "rconEnabled",
+                            rcon_enabled.map(|v| if v { "True" } else { "False" }.to_string()),
+                        ),
+                        (
+                            "rconPort",
+                            row.get::<_, Option<u16>>(7)?.map(|v| v.to_string()),
+                        ),
+                        (
+                            "gamePort",
+                            row.get::<_, Option<u16>>(8)?.map(|v| v.to_string()),
+                        ),
+                        (
+                            "queryPort",
+                            row.get::<_, Option<u16>>(9)?.map(|v| v.to_string()),
+                        ),
+                    ],
+                    row.get(10)?,
+                ))
+            },
+        )
+        .map_err(|e| format!("Server not found: {}", e))?
+    };
+
+    let path = get_config_path(&install_path, "GameUserSettings");
+    let mut report = ConfigDriftReport {
+        server_id,
+        file_path: path.to_string_lossy().to_string(),
+        mismatches: Vec::new(),
+        suggested_direction: None,
+    };
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return Ok(report),
+    };
+    let ini = parse_ini_server_values(&content);
+
+    for (field, db_value) in db_values {
+        let ini_value = match field {
+            "sessionName" => ini.session_name.clone(),
+            "mapName" => ini.map_name.clone(),
+            "maxPlayers" => ini.max_players.map(|v| v.to_string()),
+            "serverPassword" => ini.server_password.clone(),
+            "adminPassword" => ini.admin_password.clone(),
+            "rconEnabled" => ini
+                .rcon_enabled
+                .map(|v| if v { "True" } else { "False" }.to_string()),
+            "rconPort" => ini.rcon_port.map(|v| v.to_string()),
+            "gamePort" => ini.game_port.map(|v| v.to_string()),
+            "queryPort" => ini.query_port.map(|v| v.to_string()),
+            _ => None,
+        };
+        let (section, key) = match ini.locations.get(field) {
+            Some(location) => location.clone(),
+            None => continue,
+        };
+
+        // An empty password in the INI and a NULL one in the DB mean the same thing
+        let normalize = |v: &Option<String>| v.clone().filter(|v| !v.is_empty());
+        if normalize(&ini_value) != normalize(&db_value) {
+            report.mismatches.push(ConfigDriftMismatch {
+                field: field.to_string(),
+                section,
+                key,
+                db_value,
+                ini_value,
+            });
+        }
+    }
+
+    if !report.mismatches.is_empty() {
+        let modified: Option<DateTime<Utc>> = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+        let started = last_started
+            .and_then(|s| NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S").ok())
+            .map(|dt| dt.and_utc());
+        let edited_since_start = match (modified, started) {
+            (Some(modified), Some(started)) => modified > started,
+            _ => true,
+        };
+        report.suggested_direction =
+            Some(if edited_since_start { "pull" } else { "push" }.to_string());
+    }
+
+    Ok(report)
+}
+
+/// Check whether GameUserSettings.ini and the database disagree
+#[tauri::command]
+pub async fn check_config_drift(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<ConfigDriftReport, String> {
+    detect_config_drift(&state, server_id)
+}
+
+/// Resolve drift by pulling INI values into the DB ("pull") or writing DB values to the INI ("push")
+#[tauri::command]
+pub async fn resolve_config_drift(
+    state: State<'_, AppState>,
+    server_id: i64,
+    direction: String,
+) -> Result<ConfigDriftReport, String> {
+    let report = detect_config_drift(&state, server_id)?;
+    if report.mismatches.is_empty() {
+        return Ok(report);
+    }
+
+    let path = PathBuf::from(&report.file_path);
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+
+    match direction.as_str() {
+        "pull" => {
+            sync_ini_values_to_db(&state, server_id, &parse_ini_server_values(&content))?;
+        }
+        "push" => {
+            let mut updates = String::new();
+            for m in &report.mismatches {
+                updates.push_str(&format!(
+                    "[{}]\r\n{}={}\r\n",
+                    m.section,
+                    m.key,
+                    m.db_value.clone().unwrap_or_default()
+                ));
+            }
+            IniParser::write_atomic(&path, &IniParser::merge(&content, &updates))
+                .map_err(|e| format!("Failed to write GameUserSettings.ini: {}", e))?;
+            println!(
+                "✅ Pushed {} database value(s) to GameUserSettings.ini for server {}",
+                report.mismatches.len(),
+                server_id
+            );
+        }
+        other => return Err(format!("Unknown drift direction: {}", other)),
+    }
+
+    detect_config_drift(&state, server_id)
+}
+
+// ===============================================
+// Effective Config
+// ===============================================
+
+/// One setting as the server will actually see it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveSetting {
+    pub key: String,
+    pub value: String,
+    /// "GameUserSettings", "Game" or "LaunchArgs"
+    pub source: String,
+    /// INI section, None for launch options with no INI counterpart
+    pub section: Option<String>,
+    /// INI value that a launch option replaces
+    pub overridden_ini_value: Option<String>,
+    /// True when a launch option overrides an INI value with a different one
+    pub conflict: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveConfig {
+    pub server_id: i64,
+    pub launch_args: Vec<String>,
+    pub settings: Vec<EffectiveSetting>,
+}
+
+/// Launch options that replace an INI key with a different name
+const LAUNCH_OPTION_INI_KEYS: &[(&str, &str)] = &[("mods", "ActiveMods")];
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// `?Key=Value` options from the connection URL and `-Key=Value` / `-Flag` options
+fn parse_launch_options(args: &[String]) -> Vec<(String, String)> {
+    let mut options = Vec::new();
+    if let Some(url) = args.first() {
+        for part in url.split('?').skip(1) {
+            match part.split_once('=') {
+                Some((key, value)) => options.push((key.to_string(), value.to_string())),
+                None => options.push((part.to_string(), "True".to_string())),
+            }
+        }
+    }
+    for arg in args.iter().skip(1) {
+        let Some(option) = arg.strip_prefix('-') else {
+            continue;
+        };
+        match option.split_once('=') {
+            Some((key, value)) => options.push((key.to_string(), unquote(value).to_string())),
+            None => options.push((option.to_string(), "True".to_string())),
+        }
+    }
+    options
+}
+
+/// Merge both INI files with the launch options. A launch option wins over an INI
+/// key of the same name (case-insensitive) in either file.
+fn resolve_effective_settings(
+    ini_files: &[(&str, &str)],
+    launch_options: &[(String, String)],
+) -> Vec<EffectiveSetting> {
+    let mut settings = Vec::new();
+    for (source, content) in ini_files {
+        for (section, key, value) in IniParser::parse(content).entries() {
+            settings.push(EffectiveSetting {
+                key: key.to_string(),
+                value: unquote(value).to_string(),
+                source: source.to_string(),
+                section: (section != GLOBAL_SECTION).then(|| section.to_string()),
+                overridden_ini_value: None,
+                conflict: false,
+            });
+        }
+    }
+
+    for (option, value) in launch_options {
+        let ini_key = LAUNCH_OPTION_INI_KEYS
+            .iter()
+            .find(|(o, _)| o.eq_ignore_ascii_case(option))
+            .map(|(_, k)| *k)
+            .unwrap_or(option.as_str());
+
+        let mut overridden = false;
+        for setting in settings
+            .iter_mut()
+            .filter(|s| s.source != "LaunchArgs" && s.key.eq_ignore_ascii_case(ini_key))
+        {
+            overridden = true;
+            setting.conflict = !setting.value.eq_ignore_ascii_case(value);
+            setting.overridden_ini_value =
+                Some(std::mem::replace(&mut setting.value, value.clone()));
+            setting.source = "LaunchArgs".to_string();
+        }
+        if !overridden {
+            settings.push(EffectiveSetting {
+                key: option.clone(),
+                value: value.clone(),
+                source: "LaunchArgs".to_string(),
+                section: None,
+                overridden_ini_value: None,
+                conflict: false,
+            });
+        }
+    }
+
+    settings
+}
+
+/// Resolve what the server will actually run with: both INI files plus the
+/// launch arguments start_server would pass, flagging INI values a launch
+/// option overrides
+#[tauri::command]
+pub async fn get_effective_config(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<EffectiveConfig, String> {
+    let install_path = get_server_install_path(&state, server_id)?;
+    let game_user_settings =
+        fs::read_to_string(get_config_path(&install_path, "GameUserSettings")).unwrap_or_default();
+    let game_ini = fs::read_to_string(get_config_path(&install_path, "Game")).unwrap_or_default();
+    let launch_args = crate::commands::server::server_launch_args(&state, server_id)?;
+
+    let settings = resolve_effective_settings(
+        &[
+            ("GameUserSettings", game_user_settings.as_str()),
+            ("Game", game_ini.as_str()),
+        ],
+        &parse_launch_options(&launch_args),
+    );
+
+    Ok(EffectiveConfig {
+        server_id,
+        launch_args,
+        settings,
+    })
+}
+
+// ===============================================
+// Config Override Commands
+// ===============================================
+
+fn get_config_override_backup(state: &AppState, server_id: i64) -> Result<Option<String>, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    conn.query_row(
+        "SELECT config_override_backup FROM servers WHERE id = ?1",
+        [server_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| format!("Server not found: {}", e))
+}
+
+fn set_config_override_backup(
+    state: &AppState,
+    server_id: i64,
+    backup: Option<&str>,
+) -> Result<(), String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE servers SET config_override_backup = ?1 WHERE id = ?2",
+        rusqlite::params![backup, server_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Copy the backed up configs back over the overridden ones and re-sync the DB
+fn restore_override_backup(state: &AppState, server_id: i64, backup: &str) -> Result<(), String> {
+    let install_path = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        conn.query_row(
+            "SELECT install_path FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get::<_, String>(0),
+        )
+        .map_err(|e| e.to_string())?
+    };
+    let backup_dir = PathBuf::from(backup);
+    if !backup_dir.exists() {
+        return Err(format!("Override backup not found: {}", backup));
+    }
+
+    for config_type in ["GameUserSettings", "Game"] {
+        let file_name = format!("{}.ini", config_type);
+        let config_path = get_config_path(&install_path, config_type);
+        let backup_path = backup_dir.join(&file_name);
+        if backup_path.exists() {
+            fs::copy(&backup_path, &config_path)
+                .map_err(|e| format!("Failed to restore {}: {}", file_name, e))?;
+        } else if config_path.exists() {
+            // The file only exists because of the override
+            fs::remove_file(&config_path)
+                .map_err(|e| format!("Failed to remove {}: {}", file_name, e))?;
+        }
+    }
+
+    // start_server pulled the overridden values into the DB, put the originals back
+    let gus =
+        fs::read_to_string(get_config_path(&install_path, "GameUserSettings")).unwrap_or_default();
+    sync_ini_values_to_db(state, server_id, &parse_ini_server_values(&gus))?;
+
+    set_config_override_backup(state, server_id, None)
+}
+
+/// Start a server with temporary INI overrides (section -> key -> value).
+/// The current configs are backed up first and restored by revert_overrides.
+/// Overrides in the /Script/ShooterGame.ShooterGameMode section go to Game.ini.
+#[tauri::command]
+pub async fn start_server_with_overrides(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    server_id: i64,
+    config_overrides: HashMap<String, HashMap<String, String>>,
+) -> Result<String, String> {
+    if state.process_manager.is_running(server_id) {
+        return Err("Server is already running".to_string());
+    }
+    if let Some(backup) = get_config_override_backup(&state, server_id)? {
+        return Err(format!(
+            "Overrides are already active (backup: {}), revert them first",
+            backup
+        ));
+    }
+
+    let install_path = get_server_install_path(&state, server_id)?;
+    let config_dir = PathBuf::from(&install_path).join("ShooterGame/Saved/Config/WindowsServer");
+    fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+
+    let backup_dir = ConfigGenerator::backup_configs(&PathBuf::from(&install_path))?;
+    let backup = backup_dir.to_string_lossy().to_string();
+    set_config_override_backup(&state, server_id, Some(&backup))?;
+    println!(
+        "📦 Backed up configs for server {} before applying overrides: {}",
+        server_id, backup
+    );
+
+    let apply = || -> Result<(), String> {
+        for (config_type, game_ini) in [("GameUserSettings", false), ("Game", true)] {
+            let mut updates = String::new();
+            for (section, keys) in &config_overrides {
+                if (section == GAME_INI_SECTION) != game_ini || keys.is_empty() {
+                    continue;
+                }
+                updates.push_str(&format!("[{}]\r\n", section));
+                for (key, value) in keys {
+                    updates.push_str(&format!("{}={}\r\n", key, value));
+                }
+            }
+            if updates.is_empty() {
+                continue;
+            }
+
+            let path = get_config_path(&install_path, config_type);
+            let existing = fs::read_to_string(&path).unwrap_or_default();
+            IniParser::write_atomic(&path, &IniParser::merge(&existing, &updates))
+                .map_err(|e| format!("Failed to write {}.ini: {}", config_type, e))?;
+        }
+        Ok(())
+    };
+
+    let result = match apply() {
+        Ok(()) => crate::commands::server::start_server(app_handle, server_id)
+            .await
+            .map_err(String::from),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        println!(
+            "⚠️ Start with overrides failed for server {}, restoring configs",
+            server_id
+        );
+        restore_override_backup(&state, server_id, &backup)?;
+        return Err(e);
+    }
+
+    println!("✅ Server {} started with config overrides", server_id);
+    Ok(backup)
+}
+
+/// Restore the configs saved by start_server_with_overrides.
+/// Takes effect the next time the server starts. Refused while the server runs:
+/// ARK writes GameUserSettings.ini from memory on shutdown, which would put the
+/// overrides back after the backup reference is already gone.
+#[tauri::command]
+pub async fn revert_overrides(state: State<'_, AppState>, server_id: i64) -> Result<(), String> {
+    if state.process_manager.is_running(server_id) {
+        return Err("Stop the server before reverting its config overrides".to_string());
+    }
+    let backup = get_config_override_backup(&state, server_id)?
+        .ok_or("No config overrides are active for this server")?;
+
+    restore_override_backup(&state, server_id, &backup)?;
+
+    println!(
+        "🔄 Reverted config overrides for server {} from {}",
+        server_id, backup
+    );
+    Ok(())
+}
+
+/// Backup directory of the active overrides, if any
+#[tauri::command]
+pub async fn get_active_overrides(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Option<String>, String> {
+    get_config_override_backup(&state, server_id)
+}
+
 // ===============================================
 // Config Generator Commands
 // ===============================================
@@ -357,20 +925,25 @@ pub async fn write_server_configs(
     state: State<'_, AppState>,
     server_id: i64,
     install_path: String,
-    config: ServerConfig,
+    mut config: ServerConfig,
     backup: bool,
 ) -> Result<(), String> {
     let path = PathBuf::from(install_path);
-    ConfigGenerator::write_configs(&path, &config, backup)?;
-
-    // Sync config values to database so UI reflects the changes
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    // Set from the launch settings, not the generator form
+    config.crossplay_platforms = crate::commands::server::server_platforms(&state, server_id);
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
+    let previous_extra = stored_extra_settings(&conn, server_id);
+    ConfigGenerator::write_configs(&path, &config, &previous_extra, backup)?;
+
+    // Sync config values to database so UI reflects the changes
+    let extra_settings =
+        serde_json::to_string(&config.extra_settings).map_err(|e| e.to_string())?;
     conn.execute(
         "UPDATE servers SET max_players = ?1, map_name = ?2, session_name = ?3, 
          game_port = ?4, query_port = ?5, rcon_port = ?6, admin_password = ?7,
-         server_password = ?8, rcon_enabled = ?9 WHERE id = ?10",
+         server_password = ?8, rcon_enabled = ?9, extra_settings = ?10 WHERE id = ?11",
         rusqlite::params![
             config.max_players,
             config.map_name,
@@ -381,6 +954,7 @@ pub async fn write_server_configs(
             config.admin_password,
             config.server_password,
             config.rcon_enabled,
+            extra_settings,
             server_id,
         ],
     )
@@ -409,3 +983,142 @@ pub async fn backup_all_configs(
 pub async fn get_default_config() -> Result<ServerConfig, String> {
     Ok(ServerConfig::default())
 }
+
+/// Load a server's current settings into the config generator: gameplay values come from
+/// its GameUserSettings.ini/Game.ini, identity and network values from the database
+/// (which is what start_server launches with). Falls back to defaults when no files exist.
+#[tauri::command]
+pub async fn get_server_generator_config(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<ServerConfig, String> {
+    let install_path = get_server_install_path(&state, server_id)?;
+    let mut config =
+        ConfigGenerator::read_configs(&PathBuf::from(&install_path)).unwrap_or_default();
+
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    conn.query_row(
+        "SELECT session_name, map_name, max_players, game_port, query_port, rcon_port,
+         admin_password, server_password, rcon_enabled FROM servers WHERE id = ?1",
+        [server_id],
+        |row| {
+            config.session_name = row.get(0)?;
+            config.map_name = row.get(1)?;
+            config.max_players = row.get(2)?;
+            config.game_port = row.get(3)?;
+            config.query_port = row.get(4)?;
+            config.rcon_port = row.get(5)?;
+            config.admin_password = row.get(6)?;
+            config.server_password = row.get(7)?;
+            config.rcon_enabled = row.get(8)?;
+            Ok(())
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    config.extra_settings = stored_extra_settings(&conn, server_id);
+    config.crossplay_platforms = crate::commands::server::server_platforms(&state, server_id);
+
+    Ok(config)
+}
+
+/// The extra_settings saved by the last write_server_configs
+fn stored_extra_settings(
+    conn: &rusqlite::Connection,
+    server_id: i64,
+) -> HashMap<String, HashMap<String, String>> {
+    conn.query_row(
+        "SELECT extra_settings FROM servers WHERE id = ?1",
+        [server_id],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(options: &[(&str, &str)]) -> Vec<(String, String)> {
+        options
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_launch_options() {
+        let args: Vec<String> = [
+            "TheIsland_WP?listen?SessionName=My Island?Port=7777",
+            "-mods=928548,930404",
+            "-NoBattlEye",
+            r#"-ClusterDirOverride="C:\Clusters\main""#,
+            "stray",
+        ]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+
+        assert_eq!(
+            parse_launch_options(&args),
+            pairs(&[
+                ("listen", "True"),
+                ("SessionName", "My Island"),
+                ("Port", "7777"),
+                ("mods", "928548,930404"),
+                ("NoBattlEye", "True"),
+                ("ClusterDirOverride", r"C:\Clusters\main"),
+            ])
+        );
+        assert!(parse_launch_options(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_effective_settings() {
+        let game_user_settings = "[ServerSettings]\nSessionName=\"Old Name\"\nserveradminpassword=secret\nActiveMods=928548\n";
+        let game_ini =
+            "[/script/shootergame.shootergamemode]\nbDisableStructurePlacementCollision=True\n";
+        let settings = resolve_effective_settings(
+            &[("GameUserSettings", game_user_settings), ("Game", game_ini)],
+            &pairs(&[
+                ("SessionName", "My Island"),
+                ("ServerAdminPassword", "SECRET"),
+                ("mods", "928548,930404"),
+                ("NoBattlEye", "True"),
+            ]),
+        );
+        let find = |key: &str| settings.iter().find(|s| s.key == key).unwrap();
+
+        // A launch option replaces the INI value and says what it replaced
+        let session = find("SessionName");
+        assert_eq!(session.value, "My Island");
+        assert_eq!(session.source, "LaunchArgs");
+        assert_eq!(session.section.as_deref(), Some("ServerSettings"));
+        assert_eq!(session.overridden_ini_value.as_deref(), Some("Old Name"));
+        assert!(session.conflict);
+
+        // Keys match case-insensitively; an equal value isn't a conflict
+        let password = find("serveradminpassword");
+        assert_eq!(password.value, "SECRET");
+        assert!(!password.conflict);
+
+        // -mods is the launch side of ActiveMods
+        let mods = find("ActiveMods");
+        assert_eq!(mods.value, "928548,930404");
+        assert_eq!(mods.overridden_ini_value.as_deref(), Some("928548"));
+        assert!(mods.conflict);
+        assert!(settings.iter().all(|s| s.key != "mods"));
+
+        // Untouched INI keys keep their source, options with no INI key are added
+        let collision = find("bDisableStructurePlacementCollision");
+        assert_eq!(collision.source, "Game");
+        assert_eq!(collision.overridden_ini_value, None);
+        let battleye = find("NoBattlEye");
+        assert_eq!(battleye.source, "LaunchArgs");
+        assert_eq!(battleye.section, None);
+        assert_eq!(settings.len(), 5);
+    }
+}
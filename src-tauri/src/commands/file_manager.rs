@@ -1,3 +1,4 @@
+use crate::services::ini_parser::IniParser;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -84,7 +85,7 @@ pub fn read_file_content(path: String) -> Result<String, String> {
 
 #[tauri::command]
 pub fn write_file_content(path: String, content: String) -> Result<(), String> {
-    fs::write(&path, content).map_err(|e| e.to_string())
+    IniParser::write_atomic(Path::new(&path), &content).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -97,9 +98,187 @@ pub fn rename_item(old_path: String, new_path: String) -> Result<(), String> {
     fs::rename(&old_path, &new_path).map_err(|e| e.to_string())
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct FileOperationProgress {
+    pub operation: String,
+    pub source: String,
+    pub copied_files: u64,
+    pub total_files: u64,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/// Copy a file or directory tree from `src` to `dst` (the full destination path).
+/// Existing files are only replaced when `overwrite` is set. `on_file` is called
+/// after each file with (files copied, bytes copied).
+pub fn copy_path_recursive(
+    src: &Path,
+    dst: &Path,
+    overwrite: bool,
+    on_file: &mut dyn FnMut(u64, u64),
+) -> Result<(), String> {
+    fn copy_inner(
+        src: &Path,
+        dst: &Path,
+        overwrite: bool,
+        counters: &mut (u64, u64),
+        on_file: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), String> {
+        if src.is_dir() {
+            fs::create_dir_all(dst).map_err(|e| format!("Failed to create {:?}: {}", dst, e))?;
+            for entry in fs::read_dir(src).map_err(|e| e.to_string())? {
+                let entry = entry.map_err(|e| e.to_string())?;
+                copy_inner(
+                    &entry.path(),
+                    &dst.join(entry.file_name()),
+                    overwrite,
+                    counters,
+                    on_file,
+                )?;
+            }
+        } else {
+            if dst.exists() && !overwrite {
+                return Err(format!("Destination already exists: {:?}", dst));
+            }
+            let bytes =
+                fs::copy(src, dst).map_err(|e| format!("Failed to copy {:?}: {}", src, e))?;
+            counters.0 += 1;
+            counters.1 += bytes;
+            on_file(counters.0, counters.1);
+        }
+        Ok(())
+    }
+
+    if !src.exists() {
+        return Err(format!("Source does not exist: {:?}", src));
+    }
+    if dst.starts_with(src) && src.is_dir() {
+        return Err("Cannot copy a directory into itself".to_string());
+    }
+
+    copy_inner(src, dst, overwrite, &mut (0, 0), on_file)
+}
+
+/// Count files and bytes under a path for progress reporting
+fn measure_path(path: &Path) -> (u64, u64) {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .fold((0, 0), |(files, bytes), e| {
+            (
+                files + 1,
+                bytes + e.metadata().map(|m| m.len()).unwrap_or(0),
+            )
+        })
+}
+
+/// Copy with throttled "file-operation-progress" events
+fn copy_with_progress(
+    app: &tauri::AppHandle,
+    operation: &str,
+    src: &Path,
+    dst: &Path,
+    overwrite: bool,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    let (total_files, total_bytes) = measure_path(src);
+    let source = src.to_string_lossy().to_string();
+    let mut last_emit = std::time::Instant::now();
+
+    let emit = |copied_files: u64, copied_bytes: u64| {
+        let _ = app.emit(
+            "file-operation-progress",
+            FileOperationProgress {
+                operation: operation.to_string(),
+                source: source.clone(),
+                copied_files,
+                total_files,
+                copied_bytes,
+                total_bytes,
+            },
+        );
+    };
+
+    copy_path_recursive(src, dst, overwrite, &mut |files, bytes| {
+        if last_emit.elapsed() >= std::time::Duration::from_millis(250) {
+            last_emit = std::time::Instant::now();
+            emit(files, bytes);
+        }
+    })?;
+
+    emit(total_files, total_bytes);
+    Ok(())
+}
+
+/// Copy a file or directory to `dst` (full destination path)
+#[tauri::command]
+pub async fn copy_item(
+    app: tauri::AppHandle,
+    src: String,
+    dst: String,
+    overwrite: Option<bool>,
+) -> Result<(), String> {
+    let overwrite = overwrite.unwrap_or(false);
+    let (src, dst) = (PathBuf::from(src), PathBuf::from(dst));
+
+    if src.is_file() && dst.exists() && !overwrite {
+        return Err(format!("Destination already exists: {:?}", dst));
+    }
+
+    tokio::task::spawn_blocking(move || copy_with_progress(&app, "copy", &src, &dst, overwrite))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Move a file or directory to `dst` (full destination path).
+/// Uses a rename when possible and falls back to copy + delete across drives.
 #[tauri::command]
-pub fn delete_item(path: String) -> Result<(), String> {
+pub async fn move_item(
+    app: tauri::AppHandle,
+    src: String,
+    dst: String,
+    overwrite: Option<bool>,
+) -> Result<(), String> {
+    let overwrite = overwrite.unwrap_or(false);
+    let (src, dst) = (PathBuf::from(src), PathBuf::from(dst));
+
+    if !src.exists() {
+        return Err(format!("Source does not exist: {:?}", src));
+    }
+    if dst.exists() && !overwrite {
+        return Err(format!("Destination already exists: {:?}", dst));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        // Fast path: same volume, nothing to merge into
+        if !dst.exists() && fs::rename(&src, &dst).is_ok() {
+            return Ok(());
+        }
+
+        copy_with_progress(&app, "move", &src, &dst, overwrite)?;
+
+        if src.is_dir() {
+            fs::remove_dir_all(&src)
+        } else {
+            fs::remove_file(&src)
+        }
+        .map_err(|e| format!("Copied, but failed to remove source: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Delete a file or directory. With `to_trash` the item goes to the OS recycle bin
+/// instead of being removed permanently.
+#[tauri::command]
+pub fn delete_item(path: String, to_trash: Option<bool>) -> Result<(), String> {
     let path = Path::new(&path);
+    if to_trash.unwrap_or(false) {
+        return trash::delete(path).map_err(|e| format!("Failed to move to Recycle Bin: {}", e));
+    }
+
     if path.is_dir() {
         fs::remove_dir_all(&path).map_err(|e| e.to_string())
     } else {
@@ -107,6 +286,106 @@ pub fn delete_item(path: String) -> Result<(), String> {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileSearchMatch {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub line_number: Option<usize>,
+    pub line: Option<String>,
+}
+
+const SEARCH_MAX_DEPTH: usize = 12;
+const SEARCH_MAX_RESULTS: usize = 1000;
+const SEARCH_MAX_CONTENT_BYTES: u64 = 10 * 1024 * 1024;
+const SEARCH_TIME_BUDGET: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Recursively search `root` for names (and optionally text file contents) containing `query`.
+/// Bounded by depth, result count and a time budget so huge trees stay responsive.
+#[tauri::command]
+pub async fn search_files(
+    root: String,
+    query: String,
+    content: bool,
+    max_results: Option<usize>,
+) -> Result<Vec<FileSearchMatch>, String> {
+    let root_path = PathBuf::from(&root);
+    if !root_path.is_dir() {
+        return Err("Directory does not exist".to_string());
+    }
+    if query.trim().is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+
+    let limit = max_results.unwrap_or(200).clamp(1, SEARCH_MAX_RESULTS);
+    let needle = query.to_lowercase();
+
+    tokio::task::spawn_blocking(move || {
+        let started = std::time::Instant::now();
+        let mut results = Vec::new();
+
+        for entry in walkdir::WalkDir::new(&root_path)
+            .max_depth(SEARCH_MAX_DEPTH)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            if results.len() >= limit || started.elapsed() > SEARCH_TIME_BUDGET {
+                break;
+            }
+
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().is_dir();
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+            if name.to_lowercase().contains(&needle) {
+                results.push(FileSearchMatch {
+                    name,
+                    path: entry.path().to_string_lossy().to_string(),
+                    is_dir,
+                    size,
+                    line_number: None,
+                    line: None,
+                });
+                continue;
+            }
+
+            if content && !is_dir && size <= SEARCH_MAX_CONTENT_BYTES {
+                if let Some((line_number, line)) = find_in_text_file(entry.path(), &needle) {
+                    results.push(FileSearchMatch {
+                        name,
+                        path: entry.path().to_string_lossy().to_string(),
+                        is_dir,
+                        size,
+                        line_number: Some(line_number),
+                        line: Some(line),
+                    });
+                }
+            }
+        }
+
+        Ok(results)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// First line containing `needle` (lowercase) in a text file; binary files are skipped
+fn find_in_text_file(path: &Path, needle: &str) -> Option<(usize, String)> {
+    let bytes = fs::read(path).ok()?;
+
+    // Treat files with NUL bytes near the start as binary (saves, paks, exes)
+    if bytes.iter().take(8192).any(|b| *b == 0) {
+        return None;
+    }
+
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.to_lowercase().contains(needle))
+        .map(|(i, line)| (i + 1, line.trim().chars().take(300).collect()))
+}
+
 #[tauri::command]
 pub fn open_in_explorer(path: String) -> Result<(), String> {
     #[cfg(target_os = "windows")]
@@ -17,7 +17,7 @@ pub async fn import_non_dedicated_save(
     }
 
     let install_path_str = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
         let mut stmt = conn
             .prepare("SELECT install_path FROM servers WHERE id = ?1")
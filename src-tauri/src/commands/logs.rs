@@ -0,0 +1,280 @@
+use crate::services::{app_log, settings};
+use crate::AppState;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+const ACTIVE_LOG_NAME: &str = "ShooterGame.log";
+const DEFAULT_MAX_RESULTS: usize = 500;
+const MAX_RESULTS_LIMIT: usize = 5000;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogSearchResult {
+    pub file_name: String,
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// Resolve Saved/Logs for a server from its install path
+fn get_logs_dir(state: &State<'_, AppState>, server_id: i64) -> Result<PathBuf, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let install_path: String = conn
+        .query_row(
+            "SELECT install_path FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Server not found: {}", e))?;
+
+    Ok(PathBuf::from(install_path)
+        .join("ShooterGame")
+        .join("Saved")
+        .join("Logs"))
+}
+
+/// List .log files in the logs folder: active log first, then rotated logs newest first
+fn list_log_files(logs_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    if !logs_dir.exists() {
+        return Err(format!("Logs folder not found: {:?}", logs_dir));
+    }
+
+    let mut rotated = Vec::new();
+    let mut active = None;
+
+    for entry in fs::read_dir(logs_dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("log") {
+            continue;
+        }
+
+        if entry.file_name() == ACTIVE_LOG_NAME {
+            active = Some(path);
+        } else {
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            rotated.push((modified, path));
+        }
+    }
+
+    rotated.sort_by(|a, b| b.0.cmp(&a.0));
+
+    Ok(active
+        .into_iter()
+        .chain(rotated.into_iter().map(|(_, p)| p))
+        .collect())
+}
+
+/// Search a server's active and rotated logs.
+/// Case-insensitive substring by default, or a regex when `use_regex` is set.
+/// Files are streamed line by line and results are capped at `max_results`.
+#[tauri::command]
+pub async fn search_logs(
+    state: State<'_, AppState>,
+    server_id: i64,
+    query: String,
+    max_results: Option<usize>,
+    use_regex: Option<bool>,
+) -> Result<Vec<LogSearchResult>, String> {
+    if query.is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+
+    let logs_dir = get_logs_dir(&state, server_id)?;
+    let limit = max_results
+        .unwrap_or(DEFAULT_MAX_RESULTS)
+        .clamp(1, MAX_RESULTS_LIMIT);
+
+    let pattern = if use_regex.unwrap_or(false) {
+        Some(
+            regex::RegexBuilder::new(&query)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| format!("Invalid regex: {}", e))?,
+        )
+    } else {
+        None
+    };
+    let needle = query.to_lowercase();
+
+    println!(
+        "🔍 Searching logs for server {} (query: {:?}, limit: {})",
+        server_id, query, limit
+    );
+
+    tokio::task::spawn_blocking(move || -> Result<Vec<LogSearchResult>, String> {
+        let mut results = Vec::new();
+
+        for path in list_log_files(&logs_dir)? {
+            let file_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let file = match File::open(&path) {
+                Ok(f) => f,
+                Err(e) => {
+                    println!("  ⚠️ Skipping {}: {}", file_name, e);
+                    continue;
+                }
+            };
+
+            // Read raw bytes so a stray non-UTF8 byte doesn't abort the whole file
+            let mut reader = BufReader::new(file);
+            let mut buf = Vec::new();
+            let mut line_number = 0;
+
+            while reader
+                .read_until(b'\n', &mut buf)
+                .map_err(|e| e.to_string())?
+                > 0
+            {
+                line_number += 1;
+                let line = String::from_utf8_lossy(&buf);
+                let line = line.trim_end();
+
+                let matched = match &pattern {
+                    Some(re) => re.is_match(line),
+                    None => line.to_lowercase().contains(&needle),
+                };
+
+                if matched {
+                    results.push(LogSearchResult {
+                        file_name: file_name.clone(),
+                        line_number,
+                        line: line.to_string(),
+                    });
+                    if results.len() >= limit {
+                        println!("  ✅ Hit result limit ({})", limit);
+                        return Ok(results);
+                    }
+                }
+                buf.clear();
+            }
+        }
+
+        println!("  ✅ Found {} matching lines", results.len());
+        Ok(results)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCleanupResult {
+    pub deleted_files: Vec<String>,
+    pub freed_bytes: u64,
+    pub remaining_bytes: u64,
+}
+
+/// Delete rotated logs older than `keep_days`, then the oldest remaining ones until the
+/// Logs folder fits in `max_total_mb`. The active ShooterGame.log is never touched.
+#[tauri::command]
+pub async fn cleanup_logs(
+    state: State<'_, AppState>,
+    server_id: i64,
+    keep_days: u32,
+    max_total_mb: u64,
+) -> Result<LogCleanupResult, String> {
+    let logs_dir = get_logs_dir(&state, server_id)?;
+
+    println!(
+        "🧹 Cleaning up logs for server {} (keep {} days, max {} MB)",
+        server_id, keep_days, max_total_mb
+    );
+
+    let max_age = std::time::Duration::from_secs(keep_days as u64 * 24 * 60 * 60);
+    let budget = max_total_mb * 1024 * 1024;
+    let now = std::time::SystemTime::now();
+
+    let mut active_bytes = 0;
+    // Rotated logs, newest first (as returned by list_log_files)
+    let mut rotated = Vec::new();
+    for path in list_log_files(&logs_dir)? {
+        let meta = fs::metadata(&path).map_err(|e| e.to_string())?;
+        if path.file_name().is_some_and(|n| n == ACTIVE_LOG_NAME) {
+            active_bytes = meta.len();
+            continue;
+        }
+        let age = meta
+            .modified()
+            .ok()
+            .and_then(|m| now.duration_since(m).ok())
+            .unwrap_or_default();
+        rotated.push((path, meta.len(), age));
+    }
+
+    let mut result = LogCleanupResult {
+        deleted_files: Vec::new(),
+        freed_bytes: 0,
+        remaining_bytes: active_bytes + rotated.iter().map(|(_, size, _)| size).sum::<u64>(),
+    };
+
+    // Walk oldest first so the size budget evicts the oldest logs
+    for (path, size, age) in rotated.into_iter().rev() {
+        let too_old = age > max_age;
+        let over_budget = result.remaining_bytes > budget;
+        if !too_old && !over_budget {
+            continue;
+        }
+
+        match fs::remove_file(&path) {
+            Ok(_) => {
+                result.remaining_bytes -= size;
+                result.freed_bytes += size;
+                result.deleted_files.push(
+                    path.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                );
+            }
+            Err(e) => println!("  ⚠️ Failed to delete {:?}: {}", path, e),
+        }
+    }
+
+    println!(
+        "  ✅ Deleted {} log files, freed {} MB",
+        result.deleted_files.len(),
+        result.freed_bytes / 1024 / 1024
+    );
+    Ok(result)
+}
+
+/// Last lines of the manager's own log (app_data_dir/logs/manager.log)
+#[tauri::command]
+pub async fn get_recent_logs(lines: Option<usize>) -> Result<Vec<String>, String> {
+    app_log::recent_lines(
+        lines
+            .unwrap_or(DEFAULT_MAX_RESULTS)
+            .clamp(1, MAX_RESULTS_LIMIT),
+    )
+}
+
+/// Current manager log level, e.g. "INFO"
+#[tauri::command]
+pub async fn get_log_level() -> Result<String, String> {
+    Ok(app_log::current_level().to_string())
+}
+
+/// Change the manager log level now and for future launches
+#[tauri::command]
+pub async fn set_log_level(state: State<'_, AppState>, level: String) -> Result<(), String> {
+    let filter =
+        app_log::parse_level(&level).ok_or_else(|| format!("Unknown log level '{}'", level))?;
+    {
+        let db = &state.db;
+        settings::LOG_LEVEL.set(db, &Some(filter.to_string().to_lowercase()))?;
+    }
+    app_log::set_level(filter);
+    log::info!("📝 Log level set to {}", filter);
+    Ok(())
+}
@@ -3,10 +3,14 @@ pub mod cluster;
 pub mod config;
 pub mod file_manager;
 pub mod import;
+pub mod logs;
 pub mod mods;
+pub mod notifications;
 pub mod player;
 pub mod plugin;
 pub mod rcon;
+pub mod remote_api;
 pub mod scheduler;
 pub mod server;
 pub mod system;
+pub mod template;
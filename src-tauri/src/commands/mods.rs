@@ -1,5 +1,9 @@
 use crate::models::ModInfo;
+use crate::services::config_generator::ConfigGenerator;
+use crate::services::mod_conflicts::{self, ModConflictInput, ModConflictReport, ModLoadRules};
 use crate::services::mod_scraper;
+use crate::services::mod_sync;
+use crate::services::server_installer::{ModPredownloadResult, ServerInstaller};
 use crate::AppState;
 use std::path::PathBuf;
 use tauri::State;
@@ -9,12 +13,21 @@ pub async fn search_mods(
     state: State<'_, AppState>,
     query: String,
     server_type: String,
+    force_refresh: Option<bool>,
 ) -> Result<Vec<ModInfo>, String> {
     println!(
         "🔍 search_mods called: query='{}', type='{}'",
         query, server_type
     );
 
+    // Repeated searches within the TTL are served from memory to spare the API quota
+    if !force_refresh.unwrap_or(false) {
+        if let Some(mods) = mod_scraper::cached_search(&query) {
+            println!("  ♻️ Using cached results ({} mods)", mods.len());
+            return Ok(mods);
+        }
+    }
+
     // ASA-only: use CurseForge for mod search
     let api_key = crate::services::api_key_manager::ApiKeyManager::get_curseforge_key(&state);
     println!(
@@ -30,12 +43,23 @@ pub async fn search_mods(
         .await
         .map_err(|e| e.to_string());
     match &result {
-        Ok(mods) => println!("  ✅ Found {} CurseForge mods", mods.len()),
+        Ok(mods) => {
+            println!("  ✅ Found {} CurseForge mods", mods.len());
+            mod_scraper::cache_search(&query, mods);
+        }
         Err(e) => println!("  ❌ CurseForge search failed: {}", e),
     }
     result
 }
 
+/// Drop cached search results so the next search goes to CurseForge
+#[tauri::command]
+pub async fn clear_mod_search_cache() -> Result<(), String> {
+    mod_scraper::clear_search_cache();
+    println!("🧹 Cleared mod search cache");
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_mod_description(
     state: State<'_, AppState>,
@@ -63,23 +87,22 @@ pub async fn install_mod(
         mod_info.name, mod_info.id, server_id
     );
 
-    // Get highest load order
-    let max_order: i32 = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.get_connection().map_err(|e| e.to_string())?;
-        conn.query_row(
-            "SELECT COALESCE(MAX(load_order), 0) FROM mods WHERE server_id = ?1",
-            [server_id],
-            |row| row.get(0),
-        )
-        .unwrap_or(0)
-    };
-
-    // Insert mod into database
+    // Append after the highest load order. IMMEDIATE takes the write lock before
+    // the read, so two installs can't both pick the same slot.
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.get_connection().map_err(|e| e.to_string())?;
-        conn.execute(
+        let mut conn = state.db.get_connection().map_err(|e| e.to_string())?;
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(|e| e.to_string())?;
+        let max_order: i32 = tx
+            .query_row(
+                "SELECT COALESCE(MAX(load_order), 0) FROM mods WHERE server_id = ?1",
+                [server_id],
+                |row| row.get(0),
+            )
+            .unwrap_or(0);
+
+        tx.execute(
             "INSERT OR REPLACE INTO mods (server_id, mod_id, name, version, author, description, workshop_url, server_type, enabled, load_order)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'ASA', 1, ?8)",
             rusqlite::params![
@@ -93,6 +116,7 @@ pub async fn install_mod(
                 max_order + 1
             ],
         ).map_err(|e| e.to_string())?;
+        tx.commit().map_err(|e| e.to_string())?;
     }
 
     // Update GameUserSettings.ini with mod ID
@@ -102,6 +126,63 @@ pub async fn install_mod(
     Ok(())
 }
 
+/// Add a map profile's recommended mods to the server's mod list.
+/// Mods that are already installed are left alone; returns the ids that were added.
+#[tauri::command]
+pub async fn apply_profile_mods(
+    state: State<'_, AppState>,
+    server_id: i64,
+    map_id: String,
+) -> Result<Vec<String>, String> {
+    let profile = ConfigGenerator::get_profile_for_map(&map_id)
+        .ok_or_else(|| format!("No profile for map {}", map_id))?;
+    println!(
+        "📦 Applying {} recommended mods for {} to server {}",
+        profile.recommended_mods.len(),
+        profile.map_name,
+        server_id
+    );
+
+    let added = {
+        let mut conn = state.db.get_connection().map_err(|e| e.to_string())?;
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(|e| e.to_string())?;
+        let max_order: i32 = tx
+            .query_row(
+                "SELECT COALESCE(MAX(load_order), 0) FROM mods WHERE server_id = ?1",
+                [server_id],
+                |row| row.get(0),
+            )
+            .unwrap_or(0);
+
+        let mut added = Vec::new();
+        for mod_id in &profile.recommended_mods {
+            let name = ConfigGenerator::recommended_mod_name(mod_id)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| format!("Mod {}", mod_id));
+            let load_order = max_order + added.len() as i32 + 1;
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO mods (server_id, mod_id, name, server_type, enabled, load_order)
+                 VALUES (?1, ?2, ?3, 'ASA', 1, ?4)",
+                rusqlite::params![server_id, mod_id, name, load_order],
+            ).map_err(|e| e.to_string())?;
+            if inserted > 0 {
+                added.push(mod_id.clone());
+            }
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        added
+    };
+
+    if !added.is_empty() {
+        sync_mods_to_ini(&state, server_id).await?;
+    }
+
+    println!("  ✅ Added {} mods: {:?}", added.len(), added);
+    Ok(added)
+}
+
 #[tauri::command]
 pub async fn uninstall_mod(
     state: State<'_, AppState>,
@@ -111,7 +192,7 @@ pub async fn uninstall_mod(
     println!("🗑️ Uninstalling mod: {} from server {}", mod_id, server_id);
 
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
         conn.execute(
             "DELETE FROM mods WHERE server_id = ?1 AND mod_id = ?2",
@@ -134,10 +215,10 @@ pub async fn get_installed_mods(
     println!("📋 Getting installed mods for server {}", server_id);
 
     let mods = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
         let mut stmt = conn.prepare(
-            "SELECT mod_id, name, version, author, description, workshop_url, enabled, load_order 
+            "SELECT mod_id, name, version, author, description, workshop_url, enabled, load_order, file_date, game_version, latest_version
              FROM mods WHERE server_id = ?1 ORDER BY load_order ASC"
         ).map_err(|e| e.to_string())?;
 
@@ -155,7 +236,9 @@ pub async fn get_installed_mods(
                     curseforge_url: row.get::<_, Option<String>>(5).ok().flatten(),
                     enabled: row.get::<_, bool>(6).unwrap_or(true),
                     load_order: row.get::<_, i32>(7).unwrap_or(0),
-                    last_updated: None,
+                    last_updated: row.get::<_, Option<String>>(8).ok().flatten(),
+                    game_version: row.get::<_, Option<String>>(9).ok().flatten(),
+                    latest_version: row.get::<_, Option<String>>(10).ok().flatten(),
                 })
             })
             .map_err(|e| e.to_string())?;
@@ -167,6 +250,66 @@ pub async fn get_installed_mods(
     Ok(mods)
 }
 
+/// Pull each installed mod's newest file (name, game version, date) from CurseForge
+/// into the mods table. Returns the refreshed installed list.
+#[tauri::command]
+pub async fn refresh_mod_metadata(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Vec<ModInfo>, String> {
+    println!("🔄 Refreshing mod metadata for server {}", server_id);
+
+    let mod_ids: Vec<i64> = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare("SELECT mod_id FROM mods WHERE server_id = ?1")
+            .map_err(|e| e.to_string())?;
+        let ids = stmt
+            .query_map([server_id], |row| row.get::<_, String>(0))
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .filter_map(|id| id.trim().parse().ok())
+            .collect();
+        ids
+    };
+
+    if mod_ids.is_empty() {
+        return get_installed_mods(state, server_id).await;
+    }
+
+    let api_key = crate::services::api_key_manager::ApiKeyManager::get_curseforge_key(&state);
+    let files = mod_scraper::get_mod_file_info(&mod_ids, api_key)
+        .await
+        .map_err(|e| format!("Failed to fetch mod metadata: {}", e))?;
+
+    {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        for (mod_id, file) in &files {
+            store_latest_file(&conn, server_id, mod_id, file).map_err(|e| e.to_string())?;
+        }
+    }
+
+    println!("  ✅ Updated metadata for {}/{} mods", files.len(), mod_ids.len());
+    get_installed_mods(state, server_id).await
+}
+
+/// Record CurseForge's newest file for an installed mod. The installed `version` is left
+/// alone so the UI can tell an outdated mod from a current one.
+fn store_latest_file(
+    conn: &rusqlite::Connection,
+    server_id: i64,
+    mod_id: &str,
+    file: &mod_scraper::ModFileInfo,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE mods SET latest_version = ?1, game_version = ?2, file_date = ?3, metadata_refreshed_at = CURRENT_TIMESTAMP
+         WHERE server_id = ?4 AND mod_id = ?5",
+        rusqlite::params![file.version, file.game_version, file.file_date, server_id, mod_id],
+    )
+}
+
 #[tauri::command]
 pub async fn update_mod_order(
     state: State<'_, AppState>,
@@ -175,17 +318,18 @@ pub async fn update_mod_order(
 ) -> Result<(), String> {
     println!("🔄 Updating mod load order for server {}", server_id);
 
+    let generation = mod_sync::begin_reorder(server_id);
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
+        mod_sync::set_load_order(&conn, server_id, &mod_ids)?;
+    }
 
-        for (index, mod_id) in mod_ids.iter().enumerate() {
-            conn.execute(
-                "UPDATE mods SET load_order = ?1 WHERE server_id = ?2 AND mod_id = ?3",
-                rusqlite::params![index as i32, server_id, mod_id],
-            )
-            .map_err(|e| e.to_string())?;
-        }
+    // Drag-reorders arrive in bursts; only the last one in the window touches the INI
+    tokio::time::sleep(mod_sync::REORDER_DEBOUNCE).await;
+    if !mod_sync::is_latest_reorder(server_id, generation) {
+        println!("  ⏭️ Newer load order pending, leaving the INI to it");
+        return Ok(());
     }
 
     // Update GameUserSettings.ini with new order
@@ -208,13 +352,9 @@ pub async fn toggle_mod(
     );
 
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
-        conn.execute(
-            "UPDATE mods SET enabled = ?1 WHERE server_id = ?2 AND mod_id = ?3",
-            rusqlite::params![enabled, server_id, mod_id],
-        )
-        .map_err(|e| e.to_string())?;
+        mod_sync::set_mod_enabled(&conn, server_id, &mod_id, enabled)?;
     }
 
     // Update GameUserSettings.ini
@@ -232,7 +372,7 @@ pub async fn verify_mod_integrity(
 
     // Get server install path
     let install_path: String = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
         conn.query_row(
             "SELECT install_path FROM servers WHERE id = ?1",
@@ -298,84 +438,303 @@ pub struct ModIntegrityResult {
     pub utoc_exists: bool,
 }
 
-/// Sync installed mods to GameUserSettings.ini ActiveMods line
-async fn sync_mods_to_ini(state: &State<'_, AppState>, server_id: i64) -> Result<(), String> {
-    // Get server install path
-    let install_path: String = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+/// Check the server's mod list for duplicates, missing or disabled dependencies,
+/// incompatible pairs and load-first rules. Dependency data comes from CurseForge
+/// when an API key is set; the other checks work offline.
+#[tauri::command]
+pub async fn detect_mod_conflicts(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<ModConflictReport, String> {
+    println!("🧩 Checking mod conflicts for server {}", server_id);
+
+    let (install_path, custom_args): (String, Option<String>) = {
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
         conn.query_row(
-            "SELECT install_path FROM servers WHERE id = ?1",
+            "SELECT install_path, custom_args FROM servers WHERE id = ?1",
             [server_id],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
         )
-        .map_err(|e| e.to_string())?
-    };
-
-    // Get enabled mods in order
-    let mod_ids: Vec<String> = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.get_connection().map_err(|e| e.to_string())?;
-        let mut stmt = conn.prepare(
-            "SELECT mod_id FROM mods WHERE server_id = ?1 AND enabled = 1 ORDER BY load_order ASC"
-        ).map_err(|e| e.to_string())?;
-
-        let ids = stmt
-            .query_map([server_id], |row| row.get::<_, String>(0))
-            .map_err(|e| e.to_string())?
-            .filter_map(|r| r.ok())
-            .collect();
-        ids
+        .map_err(|e| format!("Server not found: {}", e))?
     };
+    let rules = load_mod_rules(&state);
+    let mods = get_installed_mods(state.clone(), server_id).await?;
 
-    // Update GameUserSettings.ini
     let config_path = PathBuf::from(&install_path)
         .join("ShooterGame/Saved/Config/WindowsServer/GameUserSettings.ini");
+    let ini_active_mods: Vec<String> = std::fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|content| {
+            content
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("ActiveMods=").map(str::to_string))
+        })
+        .map(|list| {
+            list.split(',')
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let custom_arg_mods = mod_conflicts::mods_from_args(custom_args.as_deref().unwrap_or(""));
+
+    let curseforge_ids: Vec<i64> = mods.iter().filter_map(|m| m.id.parse().ok()).collect();
+    let api_key = crate::services::api_key_manager::ApiKeyManager::get_curseforge_key(&state);
+    let relations = match mod_scraper::get_mod_relations(&curseforge_ids, api_key).await {
+        Ok(relations) => Some(relations),
+        Err(e) => {
+            println!("  ⚠️ Skipping dependency checks: {}", e);
+            None
+        }
+    };
 
-    if config_path.exists() {
-        let content = std::fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
-        let active_mods_line = format!("ActiveMods={}", mod_ids.join(","));
-
-        let new_content = if content.contains("ActiveMods=") {
-            // Replace existing line
-            let lines: Vec<&str> = content.lines().collect();
-            lines
-                .iter()
-                .map(|line| {
-                    if line.starts_with("ActiveMods=") {
-                        active_mods_line.as_str()
-                    } else {
-                        *line
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join("\n")
-        } else {
-            // Add to [ServerSettings] section
-            let mut result = String::new();
-            let mut added = false;
-            for line in content.lines() {
-                result.push_str(line);
-                result.push('\n');
-                if line.starts_with("[ServerSettings]") && !added {
-                    result.push_str(&active_mods_line);
-                    result.push('\n');
-                    added = true;
+    let report = mod_conflicts::detect_conflicts(&ModConflictInput {
+        mods: &mods,
+        ini_active_mods: &ini_active_mods,
+        custom_arg_mods: &custom_arg_mods,
+        relations: relations.as_ref(),
+        rules: &rules,
+    });
+
+    println!("  Found {} mod conflicts", report.conflicts.len());
+    Ok(report)
+}
+
+fn load_mod_rules(state: &State<'_, AppState>) -> ModLoadRules {
+    state
+        .db
+        .lock()
+        .ok()
+        .and_then(|db| db.get_setting(mod_conflicts::MOD_RULES_SETTING).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn get_mod_load_rules(state: State<'_, AppState>) -> Result<ModLoadRules, String> {
+    Ok(load_mod_rules(&state))
+}
+
+#[tauri::command]
+pub async fn save_mod_load_rules(
+    state: State<'_, AppState>,
+    rules: ModLoadRules,
+) -> Result<(), String> {
+    let json = serde_json::to_string(&rules).map_err(|e| e.to_string())?;
+    let db = &state.db;
+    db.set_setting(mod_conflicts::MOD_RULES_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// One mod in an exported JSON mod list
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModManifestEntry {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub curseforge_url: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModManifest {
+    /// In load order
+    pub mods: Vec<ModManifestEntry>,
+}
+
+/// Export a server's mods as "active_mods" (the ActiveMods= value, enabled mods only),
+/// "json" (every mod with name, state and load order) or "curseforge" (one line per mod
+/// with its page, for players to look up)
+#[tauri::command]
+pub async fn export_mod_list(
+    state: State<'_, AppState>,
+    server_id: i64,
+    format: String,
+) -> Result<String, String> {
+    let mods = get_installed_mods(state, server_id).await?;
+
+    match format.as_str() {
+        "active_mods" => Ok(mods
+            .iter()
+            .filter(|m| m.enabled)
+            .map(|m| m.id.as_str())
+            .collect::<Vec<_>>()
+            .join(",")),
+        "json" => {
+            let manifest = ModManifest {
+                mods: mods
+                    .into_iter()
+                    .map(|m| ModManifestEntry {
+                        id: m.id,
+                        name: Some(m.name),
+                        enabled: m.enabled,
+                        curseforge_url: m.curseforge_url.filter(|u| !u.is_empty()),
+                    })
+                    .collect(),
+            };
+            serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())
+        }
+        "curseforge" => Ok(mods
+            .iter()
+            .filter(|m| m.enabled)
+            .map(|m| {
+                let url = m
+                    .curseforge_url
+                    .clone()
+                    .filter(|u| !u.is_empty())
+                    .unwrap_or_else(|| format!("https://www.curseforge.com/projects/{}", m.id));
+                format!("{} ({}) - {}", m.name, m.id, url)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")),
+        _ => Err(format!("Unknown mod list format: {}", format)),
+    }
+}
+
+/// Parse any format produced by export_mod_list (or a bare list of ids)
+fn parse_mod_list(content: &str) -> Result<Vec<ModManifestEntry>, String> {
+    let content = content.trim();
+    if content.starts_with('{') {
+        let manifest: ModManifest =
+            serde_json::from_str(content).map_err(|e| format!("Invalid mod list JSON: {}", e))?;
+        return Ok(manifest.mods);
+    }
+
+    let mut entries: Vec<ModManifestEntry> = Vec::new();
+    let mut push = |id: &str, name: Option<String>| {
+        if !entries.iter().any(|e| e.id == id) {
+            entries.push(ModManifestEntry {
+                id: id.to_string(),
+                name,
+                enabled: true,
+                curseforge_url: None,
+            });
+        }
+    };
+
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        // CurseForge style: "Name (12345) - url". The id is the last parenthesised
+        // group before the url, names can have their own ("Structures Plus (S+)")
+        let head = match line.rsplit_once(" - ") {
+            Some((head, url)) if url.trim_start().starts_with("http") => head,
+            _ => line,
+        };
+        if let Some((name, rest)) = head.rsplit_once(" (") {
+            if let Some((id, _)) = rest.split_once(')') {
+                if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) {
+                    push(id, Some(name.trim().to_string()));
+                    continue;
                 }
             }
-            if !added {
-                result.push_str("\n[ServerSettings]\n");
-                result.push_str(&active_mods_line);
-                result.push('\n');
+        }
+        let ids = line.strip_prefix("ActiveMods=").unwrap_or(line);
+        for id in ids.split([',', ' ', '\t']).map(str::trim) {
+            if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) {
+                push(id, None);
             }
-            result
-        };
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Load a mod list from export_mod_list into the server's mods table, in the list's order.
+/// With `replace`, mods not in the list are removed; otherwise they're kept after the listed ones.
+/// Returns how many mods the list contained.
+#[tauri::command]
+pub async fn import_mod_list(
+    state: State<'_, AppState>,
+    server_id: i64,
+    content: String,
+    replace: Option<bool>,
+) -> Result<usize, String> {
+    let entries = parse_mod_list(&content)?;
+    if entries.is_empty() {
+        return Err("No mod ids found in the mod list".to_string());
+    }
+    println!(
+        "📥 Importing {} mods into server {}",
+        entries.len(),
+        server_id
+    );
+
+    {
+        let mut conn = state.db.get_connection().map_err(|e| e.to_string())?;
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(|e| e.to_string())?;
+
+        if replace.unwrap_or(false) {
+            let ids: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+            let mut existing = tx
+                .prepare("SELECT mod_id FROM mods WHERE server_id = ?1")
+                .map_err(|e| e.to_string())?;
+            let stale: Vec<String> = existing
+                .query_map([server_id], |row| row.get::<_, String>(0))
+                .map_err(|e| e.to_string())?
+                .filter_map(|r| r.ok())
+                .filter(|id| !ids.contains(&id.as_str()))
+                .collect();
+            drop(existing);
+            for mod_id in stale {
+                tx.execute(
+                    "DELETE FROM mods WHERE server_id = ?1 AND mod_id = ?2",
+                    rusqlite::params![server_id, mod_id],
+                ).map_err(|e| e.to_string())?;
+            }
+        }
+
+        // Mods that aren't in the list keep their relative order after the listed ones
+        tx.execute(
+            "UPDATE mods SET load_order = load_order + ?1 WHERE server_id = ?2",
+            rusqlite::params![entries.len() as i32, server_id],
+        ).map_err(|e| e.to_string())?;
 
-        std::fs::write(&config_path, new_content).map_err(|e| e.to_string())?;
-        println!("  📝 Updated ActiveMods in INI: {} mods", mod_ids.len());
+        for (index, entry) in entries.iter().enumerate() {
+            let name = entry
+                .name
+                .clone()
+                .or_else(|| {
+                    ConfigGenerator::recommended_mod_name(&entry.id).map(|n| n.to_string())
+                })
+                .unwrap_or_else(|| format!("Mod {}", entry.id));
+            tx.execute(
+                "INSERT INTO mods (server_id, mod_id, name, workshop_url, server_type, enabled, load_order)
+                 VALUES (?1, ?2, ?3, ?4, 'ASA', ?5, ?6)
+                 ON CONFLICT(server_id, mod_id) DO UPDATE SET enabled = ?5, load_order = ?6",
+                rusqlite::params![
+                    server_id,
+                    entry.id,
+                    name,
+                    entry.curseforge_url.clone().unwrap_or_default(),
+                    entry.enabled,
+                    index as i32
+                ],
+            ).map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())?;
     }
 
-    Ok(())
+    sync_mods_to_ini(&state, server_id).await?;
+
+    println!("  ✅ Imported mod list");
+    Ok(entries.len())
+}
+
+/// Sync installed mods to GameUserSettings.ini ActiveMods line.
+/// Does nothing until the server has written its config (fresh installs).
+/// Syncs for one server are serialized, see services::mod_sync.
+pub async fn sync_mods_to_ini(state: &State<'_, AppState>, server_id: i64) -> Result<(), String> {
+    mod_sync::sync_active_mods(&state.db, server_id).await
 }
 
 // =============================================================================
@@ -467,7 +826,7 @@ pub async fn generate_mod_config(
 
     // Single DB access to get all needed data
     let (install_path, session_name, map_name, game_port, query_port, mod_ids) = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
         
         // Get server info
@@ -557,6 +916,59 @@ pub async fn apply_mods_to_server(
     Ok(preview)
 }
 
+/// Pre-download a server's enabled mods with SteamCMD before launch.
+/// Progress is reported through the install-progress/install-console events.
+#[tauri::command]
+pub async fn predownload_mods(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Vec<ModPredownloadResult>, String> {
+    println!("📥 Pre-downloading mods for server {}", server_id);
+
+    let (install_path, mod_ids) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let install_path: String = conn
+            .query_row(
+                "SELECT install_path FROM servers WHERE id = ?1",
+                [server_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Server not found: {}", e))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT mod_id FROM mods WHERE server_id = ?1 AND enabled = 1 ORDER BY load_order ASC",
+            )
+            .map_err(|e| e.to_string())?;
+        let mod_ids: Vec<String> = stmt
+            .query_map([server_id], |row| row.get::<_, String>(0))
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        (install_path, mod_ids)
+    };
+
+    if mod_ids.is_empty() {
+        return Err("No enabled mods to download".to_string());
+    }
+
+    let installer = ServerInstaller::new(app_handle);
+    let results = installer
+        .predownload_mods(&PathBuf::from(&install_path), &mod_ids)
+        .await?;
+
+    println!(
+        "  ✅ Pre-downloaded {}/{} mods",
+        results.iter().filter(|r| r.success).count(),
+        results.len()
+    );
+    Ok(results)
+}
+
 /// Get post-install instructions for mod installation
 #[tauri::command]
 pub async fn get_mod_install_instructions() -> Result<Vec<String>, String> {
@@ -593,86 +1005,30 @@ pub async fn hardcore_retry_mods(
     println!("☢️ Hardcore Mod Retry initiated for server {}", server_id);
 
     // 1. Fetch Server Details & Config
-    let (install_path, session_name, map_name, game_port, query_port, rcon_port, max_players, server_password, admin_password, ip_address, cluster_id, cluster_dir, custom_args) = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.get_connection().map_err(|e| e.to_string())?;
-        
-        conn.query_row(
-            "SELECT install_path, session_name, map_name, game_port, query_port, rcon_port, max_players, server_password, admin_password, ip_address, cluster_id, cluster_dir, custom_args 
-             FROM servers WHERE id = ?1",
-            [server_id],
-            |row| Ok((
-                row.get::<_, String>(0)?, // install_path
-                row.get::<_, String>(1)?, // session_name
-                row.get::<_, String>(2)?, // map_name
-                row.get::<_, i32>(3)?,    // game_port
-                row.get::<_, i32>(4)?,    // query_port
-                row.get::<_, i32>(5)?,    // rcon_port
-                row.get::<_, i32>(6)?,    // max_players
-                row.get::<_, Option<String>>(7)?, // server_password
-                row.get::<_, String>(8)?, // admin_password
-                row.get::<_, Option<String>>(9)?, // ip_address
-                row.get::<_, Option<String>>(10)?, // cluster_id
-                row.get::<_, Option<String>>(11)?, // cluster_dir
-                row.get::<_, Option<String>>(12)?, // custom_args
-            )),
-        ).map_err(|e| e.to_string())?
-    };
-
-    let path_buf = PathBuf::from(&install_path);
+    let launch = crate::commands::server::server_launch(&state, server_id)?;
 
     // 2. Stop Server
     println!("  ⏹️ Stopping server...");
-    state.process_manager.stop_server(server_id).map_err(|e| e.to_string())?;
+    state.process_manager.stop_server(server_id).await.map_err(|e| e.to_string())?;
     
     // Wait a bit to ensure file handles are released
-    std::thread::sleep(std::time::Duration::from_secs(3));
+    tokio::time::sleep(state.process_manager.stop_settle_delay()).await;
 
     // 3. Delete Cache
     println!("  🧹 Clearing mod cache...");
-    delete_mod_cache(&path_buf)?;
-
-    // 4. Get Enabled Mods (for restart)
-    let enabled_mods: Vec<String> = {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
-        let conn = db.get_connection().map_err(|e| e.to_string())?;
-        let mut stmt = conn.prepare(
-            "SELECT mod_id FROM mods WHERE server_id = ?1 AND enabled = 1 ORDER BY load_order ASC"
-        ).map_err(|e| e.to_string())?;
-
-        let ids: Vec<String> = stmt.query_map([server_id], |row| row.get::<_, String>(0))
-            .map_err(|e| e.to_string())?
-            .filter_map(|r| r.ok())
-            .collect();
-        ids
-    };
+    delete_mod_cache(&launch.install_path)?;
 
-    let mods_option = if enabled_mods.is_empty() {
-        None
-    } else {
-        Some(enabled_mods.as_slice())
-    };
-
-    // 5. Start Server
+    // 4. Start Server
     println!("  🚀 Restarting server...");
-    state.process_manager.start_server(
-        server_id,
-        "ASA", // Assuming ASA for now as this is mod related
-        &path_buf,
-        &map_name,
-        &session_name,
-        game_port as u16,
-        query_port as u16,
-        rcon_port as u16,
-        max_players,
-        server_password.as_deref(),
-        &admin_password,
-        ip_address.as_deref(),
-        cluster_id.as_deref(),
-        cluster_dir.as_deref(),
-        mods_option,
-        custom_args.as_deref(),
-    ).map_err(|e| e.to_string())?;
+    state
+        .process_manager
+        .start_server(
+            server_id,
+            &launch.install_path,
+            launch.use_asa_api,
+            &launch.options(),
+        )
+        .map_err(|e| e.to_string())?;
 
     println!("  ✅ Hardcore retry complete!");
     Ok(())
@@ -692,7 +1048,7 @@ pub async fn copy_mods_to_server(
 
     // Scope DB operations to ensure MutexGuard is dropped before await
     {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let db = &state.db;
         let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         // 1. Get enabled mods from source server with load order
@@ -719,6 +1075,8 @@ pub async fn copy_mods_to_server(
                         enabled: true,
                         load_order: 0, 
                         last_updated: None, 
+                        game_version: None,
+                        latest_version: None,
                     })
                 })
                 .map_err(|e| e.to_string())?
@@ -733,7 +1091,7 @@ pub async fn copy_mods_to_server(
         }
 
         // 2. Clear existing mods on target server or Append
-        conn.execute("BEGIN TRANSACTION", []).map_err(|e| e.to_string())?;
+        conn.execute("BEGIN IMMEDIATE", []).map_err(|e| e.to_string())?;
 
         let mut max_order: i32 = conn
             .query_row(
@@ -795,3 +1153,78 @@ pub async fn copy_mods_to_server(
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids_and_names(content: &str) -> Vec<(String, Option<String>)> {
+        parse_mod_list(content)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.id, e.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_mod_list_keeps_parentheses_in_names() {
+        let list = "Structures Plus (S+) (731604991) - https://www.curseforge.com/ark-survival-ascended/mods/structures-plus\n\
+                    Awesome Spyglass! (928548)\n\
+                    Dino Storage - v2 (930404) - https://www.curseforge.com/ark-survival-ascended/mods/dino-storage";
+        assert_eq!(
+            ids_and_names(list),
+            vec![
+                (
+                    "731604991".to_string(),
+                    Some("Structures Plus (S+)".to_string())
+                ),
+                ("928548".to_string(), Some("Awesome Spyglass!".to_string())),
+                ("930404".to_string(), Some("Dino Storage - v2".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_refresh_keeps_installed_version() {
+        let (_dir, db, server_id) = crate::db::test_support::db_with_server("mods_latest");
+        let conn = db.get_connection().unwrap();
+        conn.execute(
+            "INSERT INTO mods (server_id, mod_id, name, version, load_order) VALUES (?1, '928548', 'Spyglass', '1.0.2', 0)",
+            [server_id],
+        )
+        .unwrap();
+
+        let file = mod_scraper::ModFileInfo {
+            version: "1.0.3".to_string(),
+            game_version: Some("55.12".to_string()),
+            file_date: Some("2026-09-30T12:00:00Z".to_string()),
+        };
+        assert_eq!(
+            store_latest_file(&conn, server_id, "928548", &file).unwrap(),
+            1
+        );
+
+        let (version, latest, game_version): (String, String, String) = conn
+            .query_row(
+                "SELECT version, latest_version, game_version FROM mods WHERE server_id = ?1 AND mod_id = '928548'",
+                [server_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(version, "1.0.2");
+        assert_eq!(latest, "1.0.3");
+        assert_eq!(game_version, "55.12");
+    }
+
+    #[test]
+    fn test_parse_mod_list_reads_plain_ids_and_active_mods() {
+        assert_eq!(
+            ids_and_names("ActiveMods=928548,930404\n931047 928548"),
+            vec![
+                ("928548".to_string(), None),
+                ("930404".to_string(), None),
+                ("931047".to_string(), None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::models::{ChatBridgeConfig, DiscordConfig};
+use crate::services::chat_bridge::{self, CHAT_BRIDGE_SETTING};
+use crate::services::notifications::{
+    load_channels, load_discord_config, send_test, ChannelKind, NotificationChannelConfig,
+    CHANNELS_SETTING, DISCORD_CONFIG_SETTING,
+};
+use crate::AppState;
+use tauri::State;
+
+/// Discord webhook URL and which events to post
+#[tauri::command]
+pub async fn get_discord_config(state: State<'_, AppState>) -> Result<DiscordConfig, String> {
+    Ok(load_discord_config(&state))
+}
+
+#[tauri::command]
+pub async fn save_discord_config(
+    state: State<'_, AppState>,
+    config: DiscordConfig,
+) -> Result<(), String> {
+    if let Some(url) = config
+        .webhook_url
+        .as_deref()
+        .filter(|u| !u.trim().is_empty())
+    {
+        if !url.starts_with("https://") {
+            return Err("Webhook URL must start with https://".to_string());
+        }
+    }
+
+    let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    let db = &state.db;
+    db.set_setting(DISCORD_CONFIG_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// Discord chat bridge: bot token and per-server channel mapping
+#[tauri::command]
+pub async fn get_chat_bridge_config(
+    state: State<'_, AppState>,
+) -> Result<ChatBridgeConfig, String> {
+    Ok(chat_bridge::load_config(&state))
+}
+
+#[tauri::command]
+pub async fn save_chat_bridge_config(
+    state: State<'_, AppState>,
+    config: ChatBridgeConfig,
+) -> Result<(), String> {
+    for link in &config.links {
+        if let Some(url) = link.webhook_url.as_deref().filter(|u| !u.trim().is_empty()) {
+            if !url.starts_with("https://") {
+                return Err(format!(
+                    "Server {}: webhook URL must start with https://",
+                    link.server_id
+                ));
+            }
+        }
+        if let Some(channel_id) = link.channel_id.as_deref().map(str::trim) {
+            if !channel_id.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!(
+                    "Server {}: channel ID must be the numeric Discord channel ID",
+                    link.server_id
+                ));
+            }
+        }
+    }
+
+    let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    let db = &state.db;
+    db.set_setting(CHAT_BRIDGE_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// Additional notification channels (webhook, email, extra Discord webhooks)
+#[tauri::command]
+pub async fn get_notification_channels(
+    state: State<'_, AppState>,
+) -> Result<Vec<NotificationChannelConfig>, String> {
+    Ok(load_channels(&state))
+}
+
+#[tauri::command]
+pub async fn save_notification_channels(
+    state: State<'_, AppState>,
+    channels: Vec<NotificationChannelConfig>,
+) -> Result<(), String> {
+    for channel in &channels {
+        validate_channel(channel)?;
+    }
+
+    let json = serde_json::to_string(&channels).map_err(|e| e.to_string())?;
+    let db = &state.db;
+    db.set_setting(CHANNELS_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// Send a test message through a channel (saved or not) and surface any delivery error
+#[tauri::command]
+pub async fn test_notification(channel: NotificationChannelConfig) -> Result<(), String> {
+    validate_channel(&channel)?;
+    send_test(&channel)
+        .await
+        .map_err(|e| format!("{}: {}", channel.name, e))
+}
+
+fn validate_channel(channel: &NotificationChannelConfig) -> Result<(), String> {
+    if channel.name.trim().is_empty() {
+        return Err("Channel name is required".to_string());
+    }
+    match &channel.kind {
+        ChannelKind::Discord { webhook_url } if !webhook_url.starts_with("https://") => Err(
+            format!("{}: webhook URL must start with https://", channel.name),
+        ),
+        ChannelKind::Webhook { url, .. }
+            if !url.starts_with("https://") && !url.starts_with("http://") =>
+        {
+            Err(format!("{}: URL must start with http(s)://", channel.name))
+        }
+        ChannelKind::Email { smtp_host, to, .. }
+            if smtp_host.trim().is_empty() || to.is_empty() =>
+        {
+            Err(format!(
+                "{}: SMTP host and at least one recipient are required",
+                channel.name
+            ))
+        }
+        _ => Ok(()),
+    }
+}
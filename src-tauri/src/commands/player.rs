@@ -1,14 +1,18 @@
 // Player Intelligence Commands for ASA Server Manager
 // Exposes player tracking and statistics functionality to the frontend
 
-use crate::models::{PlayerSession, PlayerStats};
+use crate::commands::rcon::RconState;
+use crate::db::Database;
+use crate::models::{PlayerEntry, PlayerSearchResult, PlayerSession, PlayerStats};
+use crate::services::ini_parser::IniParser;
 use crate::services::player_intelligence::PlayerIntelligenceService;
 use crate::AppState;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::Mutex;
 
-#[allow(dead_code)]
 pub struct PlayerIntelligenceState(pub Arc<Mutex<PlayerIntelligenceService>>);
 
 /// Get player statistics by Steam ID
@@ -19,7 +23,7 @@ pub async fn get_player_stats(
 ) -> Result<PlayerStats, String> {
     println!("📊 Getting player stats for {}", steam_id);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.query_row(
@@ -56,7 +60,7 @@ pub async fn get_all_players(
     let limit = limit.unwrap_or(100);
     let offset = offset.unwrap_or(0);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
@@ -99,13 +103,14 @@ pub async fn get_player_sessions(
 
     let limit = limit.unwrap_or(50);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, server_id, steam_id, player_name, joined_at, left_at 
-             FROM player_sessions WHERE steam_id = ?1 ORDER BY joined_at DESC LIMIT ?2",
+            "SELECT id, server_id, steam_id, player_name, joined_at, left_at, eos_id 
+             FROM player_sessions WHERE steam_id = ?1 OR eos_id = ?1 
+             ORDER BY joined_at DESC LIMIT ?2",
         )
         .map_err(|e| e.to_string())?;
 
@@ -122,6 +127,7 @@ pub async fn get_player_sessions(
             player_name: row.get(3).unwrap_or_default(),
             joined_at: row.get(4).unwrap_or_default(),
             left_at: row.get(5).unwrap_or(None),
+            eos_id: row.get(6).unwrap_or(None),
         });
     }
 
@@ -138,7 +144,7 @@ pub async fn update_player_notes(
 ) -> Result<(), String> {
     println!("📝 Updating notes for player {}", steam_id);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute(
@@ -162,7 +168,7 @@ pub async fn set_player_whitelist(
         steam_id, whitelisted
     );
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute(
@@ -183,7 +189,7 @@ pub async fn set_player_ban(
 ) -> Result<(), String> {
     println!("🚫 Setting ban for player {}: {}", steam_id, banned);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute(
@@ -195,6 +201,392 @@ pub async fn set_player_ban(
     Ok(())
 }
 
+/// Set whitelist status for several players at once, returning how many were updated
+#[tauri::command]
+pub async fn bulk_set_player_whitelist(
+    state: State<'_, AppState>,
+    steam_ids: Vec<String>,
+    whitelisted: bool,
+) -> Result<usize, String> {
+    println!(
+        "📋 Setting whitelist for {} players: {}",
+        steam_ids.len(),
+        whitelisted
+    );
+    bulk_update_flag(&state, "is_whitelisted", &steam_ids, whitelisted)
+}
+
+/// Set ban status for several players at once, returning how many were updated
+#[tauri::command]
+pub async fn bulk_set_player_ban(
+    state: State<'_, AppState>,
+    steam_ids: Vec<String>,
+    banned: bool,
+) -> Result<usize, String> {
+    println!("🚫 Setting ban for {} players: {}", steam_ids.len(), banned);
+    bulk_update_flag(&state, "is_banned", &steam_ids, banned)
+}
+
+fn bulk_update_flag(
+    state: &State<'_, AppState>,
+    column: &str,
+    steam_ids: &[String],
+    value: bool,
+) -> Result<usize, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let mut updated = 0;
+    {
+        let mut stmt = tx
+            .prepare(&format!(
+                "UPDATE players SET {} = ?1 WHERE steam_id = ?2",
+                column
+            ))
+            .map_err(|e| e.to_string())?;
+        for steam_id in steam_ids {
+            updated += stmt
+                .execute(rusqlite::params![value, steam_id.trim()])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    println!("  ✅ Updated {} players", updated);
+    Ok(updated)
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerListSyncResult {
+    pub file_path: String,
+    pub entries: usize,
+    pub rcon_applied: usize,
+    pub rcon_errors: Vec<String>,
+}
+
+/// Add every whitelisted player to the server's PlayerExclusiveJoinList.txt and,
+/// if RCON is connected, apply the changes to the running server.
+/// `prune` also removes ids the manager doesn't list as whitelisted.
+/// The list is only enforced when the server runs with -exclusivejoin.
+#[tauri::command]
+pub async fn sync_whitelist_to_server(
+    state: State<'_, AppState>,
+    rcon_state: State<'_, RconState>,
+    server_id: i64,
+    prune: Option<bool>,
+) -> Result<PlayerListSyncResult, String> {
+    println!("📋 Syncing whitelist to server {}", server_id);
+
+    let (install_path, steam_ids) = load_player_list(&state, server_id, "is_whitelisted")?;
+    let file_path = player_list_path(&install_path, "PlayerExclusiveJoinList.txt");
+    let PlayerListChange {
+        entries,
+        added,
+        removed,
+    } = write_player_list(&file_path, &steam_ids, prune.unwrap_or(false))?;
+
+    let rcon = &rcon_state.0;
+    let mut result = PlayerListSyncResult {
+        file_path: file_path.to_string_lossy().to_string(),
+        entries,
+        rcon_applied: 0,
+        rcon_errors: Vec::new(),
+    };
+
+    if rcon.is_connected(server_id).await {
+        for id in &added {
+            match rcon.allow_player_to_join(server_id, id).await {
+                Ok(_) => result.rcon_applied += 1,
+                Err(e) => result.rcon_errors.push(format!("{}: {}", id, e)),
+            }
+        }
+        for id in &removed {
+            match rcon.disallow_player_to_join(server_id, id).await {
+                Ok(_) => result.rcon_applied += 1,
+                Err(e) => result.rcon_errors.push(format!("{}: {}", id, e)),
+            }
+        }
+    }
+
+    println!(
+        "  ✅ Whitelist synced: {} entries, {} RCON changes",
+        result.entries, result.rcon_applied
+    );
+    Ok(result)
+}
+
+/// Add every banned player to the server's BanList.txt and, if RCON is connected,
+/// ban them on the running server so the change takes effect immediately.
+/// Bans made in-game or by hand are kept unless `prune` is set, which also
+/// removes (and unbans) every id the manager doesn't list as banned.
+#[tauri::command]
+pub async fn sync_banlist_to_server(
+    state: State<'_, AppState>,
+    rcon_state: State<'_, RconState>,
+    server_id: i64,
+    prune: Option<bool>,
+) -> Result<PlayerListSyncResult, String> {
+    println!("🚫 Syncing ban list to server {}", server_id);
+
+    let (install_path, steam_ids) = load_player_list(&state, server_id, "is_banned")?;
+    let file_path = player_list_path(&install_path, "BanList.txt");
+    let PlayerListChange {
+        entries,
+        added,
+        removed,
+    } = write_player_list(&file_path, &steam_ids, prune.unwrap_or(false))?;
+
+    let rcon = &rcon_state.0;
+    let mut result = PlayerListSyncResult {
+        file_path: file_path.to_string_lossy().to_string(),
+        entries,
+        rcon_applied: 0,
+        rcon_errors: Vec::new(),
+    };
+
+    if rcon.is_connected(server_id).await {
+        for id in &added {
+            match rcon.ban_player(server_id, id).await {
+                Ok(_) => result.rcon_applied += 1,
+                Err(e) => result.rcon_errors.push(format!("{}: {}", id, e)),
+            }
+        }
+        for id in &removed {
+            match rcon.unban_player(server_id, id).await {
+                Ok(_) => result.rcon_applied += 1,
+                Err(e) => result.rcon_errors.push(format!("{}: {}", id, e)),
+            }
+        }
+    }
+
+    println!(
+        "  ✅ Ban list synced: {} entries, {} RCON changes",
+        result.entries, result.rcon_applied
+    );
+    Ok(result)
+}
+
+/// Export banned players to `dest` in ARK's BanList.txt format, returning how many were written
+#[tauri::command]
+pub async fn export_banlist(
+    state: State<'_, AppState>,
+    server_id: i64,
+    dest: String,
+) -> Result<usize, String> {
+    println!("📤 Exporting ban list for server {} to {}", server_id, dest);
+    let (_, steam_ids) = load_player_list(&state, server_id, "is_banned")?;
+    write_player_list(Path::new(&dest), &steam_ids, true)?;
+    Ok(steam_ids.len())
+}
+
+/// Export whitelisted players to `dest` in ARK's PlayerExclusiveJoinList.txt format
+#[tauri::command]
+pub async fn export_whitelist(
+    state: State<'_, AppState>,
+    server_id: i64,
+    dest: String,
+) -> Result<usize, String> {
+    println!(
+        "📤 Exporting whitelist for server {} to {}",
+        server_id, dest
+    );
+    let (_, steam_ids) = load_player_list(&state, server_id, "is_whitelisted")?;
+    write_player_list(Path::new(&dest), &steam_ids, true)?;
+    Ok(steam_ids.len())
+}
+
+/// Mark every ID in an ARK ban list as banned. Reads the server's own BanList.txt
+/// unless `source` is given. Returns how many IDs were imported.
+#[tauri::command]
+pub async fn import_banlist(
+    state: State<'_, AppState>,
+    server_id: i64,
+    source: Option<String>,
+) -> Result<usize, String> {
+    import_player_list(&state, server_id, source, "BanList.txt", "is_banned")
+}
+
+/// Mark every ID in an ARK exclusive join list as whitelisted. Reads the server's own
+/// PlayerExclusiveJoinList.txt unless `source` is given.
+#[tauri::command]
+pub async fn import_whitelist(
+    state: State<'_, AppState>,
+    server_id: i64,
+    source: Option<String>,
+) -> Result<usize, String> {
+    import_player_list(
+        &state,
+        server_id,
+        source,
+        "PlayerExclusiveJoinList.txt",
+        "is_whitelisted",
+    )
+}
+
+fn import_player_list(
+    state: &State<'_, AppState>,
+    server_id: i64,
+    source: Option<String>,
+    file_name: &str,
+    column: &str,
+) -> Result<usize, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let path = match source {
+        Some(source) => PathBuf::from(source),
+        None => {
+            let install_path: String = conn
+                .query_row(
+                    "SELECT install_path FROM servers WHERE id = ?1",
+                    [server_id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| format!("Server not found: {}", e))?;
+            player_list_path(&install_path, file_name)
+        }
+    };
+    println!("📥 Importing {:?} into {}", path, column);
+
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let steam_ids = parse_player_list(&content);
+
+    // Unknown players get a placeholder row named after their ID until they join
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    {
+        let mut stmt = tx
+            .prepare(&format!(
+                "INSERT INTO players (steam_id, display_name, first_seen, last_seen, total_playtime_minutes, total_sessions, {column})
+                 VALUES (?1, ?1, datetime('now'), datetime('now'), 0, 0, 1)
+                 ON CONFLICT(steam_id) DO UPDATE SET {column} = 1"
+            ))
+            .map_err(|e| e.to_string())?;
+        for steam_id in &steam_ids {
+            stmt.execute([steam_id]).map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    println!("  ✅ Imported {} players", steam_ids.len());
+    Ok(steam_ids.len())
+}
+
+/// IDs from a one-per-line ARK list, skipping blanks and comment lines
+fn parse_player_list(content: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with("//"))
+        .filter_map(|l| l.split([' ', '\t', ',']).next())
+        .map(str::to_string)
+        .filter(|id| seen.insert(id.clone()))
+        .collect()
+}
+
+/// Load a server's install path and the Steam IDs of players with the given flag set
+fn load_player_list(
+    state: &State<'_, AppState>,
+    server_id: i64,
+    column: &str,
+) -> Result<(String, Vec<String>), String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let install_path: String = conn
+        .query_row(
+            "SELECT install_path FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Server not found: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT steam_id FROM players WHERE {} = 1 ORDER BY steam_id",
+            column
+        ))
+        .map_err(|e| e.to_string())?;
+    let steam_ids = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok((install_path, steam_ids))
+}
+
+/// ARK reads its allow/ban lists from next to the server executable
+fn player_list_path(install_path: &str, file_name: &str) -> PathBuf {
+    PathBuf::from(install_path)
+        .join("ShooterGame")
+        .join("Binaries")
+        .join("Win64")
+        .join(file_name)
+}
+
+/// Result of writing a one-id-per-line list file
+struct PlayerListChange {
+    entries: usize,
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+/// Write a one-id-per-line list file. Without `prune` the ids already in the file
+/// (bans made in-game or by hand) are kept and only missing ids are appended;
+/// with `prune` the file is replaced by exactly `steam_ids`.
+fn write_player_list(
+    path: &Path,
+    steam_ids: &[String],
+    prune: bool,
+) -> Result<PlayerListChange, String> {
+    let previous: Vec<String> = std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    let previous_set: HashSet<&String> = previous.iter().collect();
+    let current_set: HashSet<&String> = steam_ids.iter().collect();
+
+    let added: Vec<String> = steam_ids
+        .iter()
+        .filter(|id| !previous_set.contains(id))
+        .cloned()
+        .collect();
+    let (list, removed) = if prune {
+        let removed = previous
+            .iter()
+            .filter(|id| !current_set.contains(id))
+            .cloned()
+            .collect();
+        (steam_ids.to_vec(), removed)
+    } else {
+        let mut list = previous.clone();
+        list.extend(added.iter().cloned());
+        (list, Vec::new())
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut content = list.join("\r\n");
+    if !content.is_empty() {
+        content.push_str("\r\n");
+    }
+    IniParser::write_atomic(path, &content)
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    Ok(PlayerListChange {
+        entries: list.len(),
+        added,
+        removed,
+    })
+}
+
 /// Record a player session (usually called when player leaves)
 #[tauri::command]
 pub async fn record_player_session(
@@ -203,19 +595,95 @@ pub async fn record_player_session(
 ) -> Result<(), String> {
     println!("📥 Recording session for player {}", session.steam_id);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
+    save_player_session(&conn, &session)
+}
+
+/// Poll ListPlayers over RCON and reconcile tracked sessions for a server.
+/// Players who are no longer online get their session written to the database.
+#[tauri::command]
+pub async fn sync_player_sessions(
+    app_handle: AppHandle,
+    server_id: i64,
+) -> Result<Vec<PlayerEntry>, String> {
+    sync_sessions(&app_handle, server_id).await
+}
+
+/// Reconcile a server's tracked sessions against its current player list.
+/// Also run by the background online poll.
+pub(crate) async fn sync_sessions(
+    app_handle: &AppHandle,
+    server_id: i64,
+) -> Result<Vec<PlayerEntry>, String> {
+    let online = {
+        let rcon_state = app_handle.state::<RconState>();
+        rcon_state.0.get_player_entries(server_id).await?
+    };
+
+    let ended = {
+        let intel_state = app_handle.state::<PlayerIntelligenceState>();
+        let intel = intel_state.0.lock().await;
+        intel.sync_online_players(server_id, &online).await
+    };
+    save_ended_sessions(&app_handle.state::<AppState>().db, &ended);
+
+    Ok(online)
+}
+
+/// Close every open session on a server that stopped or crashed
+pub(crate) async fn end_sessions(app_handle: &AppHandle, server_id: i64) {
+    let Some(intel_state) = app_handle.try_state::<PlayerIntelligenceState>() else {
+        return;
+    };
+    let ended = {
+        let intel = intel_state.0.lock().await;
+        intel.clear_server_sessions(server_id).await
+    };
+    if let Some(state) = app_handle.try_state::<AppState>() {
+        save_ended_sessions(&state.db, &ended);
+    }
+}
+
+fn save_ended_sessions(db: &Database, ended: &[PlayerSession]) {
+    if ended.is_empty() {
+        return;
+    }
+    let conn = match db.get_connection() {
+        Ok(conn) => conn,
+        Err(e) => {
+            println!(
+                "  ⚠️ Failed to record {} player sessions: {}",
+                ended.len(),
+                e
+            );
+            return;
+        }
+    };
+    for session in ended {
+        if let Err(e) = save_player_session(&conn, session) {
+            println!(
+                "  ⚠️ Failed to record session for {}: {}",
+                session.steam_id, e
+            );
+        }
+    }
+}
+
+/// Insert a finished session and roll it into the player's aggregate stats
+fn save_player_session(conn: &rusqlite::Connection, session: &PlayerSession) -> Result<(), String> {
     // Insert session
     conn.execute(
-        "INSERT INTO player_sessions (server_id, steam_id, player_name, joined_at, left_at) 
-         VALUES (?1, ?2, ?3, ?4, ?5)",
+        "INSERT INTO player_sessions (server_id, steam_id, player_name, joined_at, left_at, eos_id) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
         rusqlite::params![
             session.server_id,
             session.steam_id,
             session.player_name,
             session.joined_at,
             session.left_at,
+            session.eos_id,
         ],
     )
     .map_err(|e| e.to_string())?;
@@ -256,31 +724,111 @@ pub async fn record_player_session(
     Ok(())
 }
 
-/// Search players by name or Steam ID
+/// Search players by a case-insensitive fragment of their name, Steam ID or notes.
+/// Each result includes the server the player was most recently seen on.
 #[tauri::command]
 pub async fn search_players(
     state: State<'_, AppState>,
     query: String,
-) -> Result<Vec<PlayerStats>, String> {
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> Result<Vec<PlayerSearchResult>, String> {
     println!("🔍 Searching players: {}", query);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let limit = limit.unwrap_or(50).clamp(1, 500);
+    let offset = offset.unwrap_or(0).max(0);
+
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
-    let search_pattern = format!("%{}%", query);
+    // Escape LIKE wildcards so "%" or "_" in a name fragment match literally
+    let escaped = query
+        .trim()
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    let search_pattern = format!("%{}%", escaped);
 
     let mut stmt = conn
         .prepare(
+            "SELECT p.steam_id, p.display_name, p.first_seen, p.last_seen, p.total_playtime_minutes, 
+                    p.total_sessions, p.notes, p.is_whitelisted, p.is_banned, 
+                    ls.server_id, srv.name 
+             FROM players p 
+             LEFT JOIN player_sessions ls ON ls.id = (
+                 SELECT s.id FROM player_sessions s WHERE s.steam_id = p.steam_id 
+                 ORDER BY COALESCE(s.left_at, s.joined_at) DESC LIMIT 1
+             ) 
+             LEFT JOIN servers srv ON srv.id = ls.server_id 
+             WHERE LOWER(p.steam_id) LIKE ?1 ESCAPE '\\' 
+                OR LOWER(p.display_name) LIKE ?1 ESCAPE '\\' 
+                OR LOWER(COALESCE(p.notes, '')) LIKE ?1 ESCAPE '\\' 
+             ORDER BY p.last_seen DESC LIMIT ?2 OFFSET ?3",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut result = Vec::new();
+    let mut rows = stmt
+        .query(rusqlite::params![search_pattern, limit, offset])
+        .map_err(|e| e.to_string())?;
+
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        result.push(PlayerSearchResult {
+            player: PlayerStats {
+                steam_id: row.get(0).unwrap_or_default(),
+                display_name: row.get(1).unwrap_or_default(),
+                first_seen: row.get(2).unwrap_or_default(),
+                last_seen: row.get(3).unwrap_or_default(),
+                total_playtime_minutes: row.get(4).unwrap_or(0),
+                total_sessions: row.get(5).unwrap_or(0),
+                notes: row.get(6).unwrap_or(None),
+                is_whitelisted: row.get(7).unwrap_or(false),
+                is_banned: row.get(8).unwrap_or(false),
+            },
+            last_server_id: row.get(9).unwrap_or(None),
+            last_server_name: row.get(10).unwrap_or(None),
+        });
+    }
+
+    println!("  Found {} players", result.len());
+    Ok(result)
+}
+
+/// Get the top players ranked by playtime, session count or most recent activity
+#[tauri::command]
+pub async fn get_player_leaderboard(
+    state: State<'_, AppState>,
+    limit: Option<i32>,
+    sort_by: Option<String>,
+) -> Result<Vec<PlayerStats>, String> {
+    let limit = limit.unwrap_or(10).clamp(1, 500);
+    let order_by = match sort_by.as_deref().unwrap_or("playtime") {
+        "playtime" => "total_playtime_minutes DESC, total_sessions DESC",
+        "sessions" => "total_sessions DESC, total_playtime_minutes DESC",
+        "recent" => "last_seen DESC",
+        other => return Err(format!("Unknown leaderboard sort: {}", other)),
+    };
+
+    println!(
+        "🏆 Getting player leaderboard (top {}, {})",
+        limit, order_by
+    );
+
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
             "SELECT steam_id, display_name, first_seen, last_seen, total_playtime_minutes, 
                     total_sessions, notes, is_whitelisted, is_banned 
-             FROM players 
-             WHERE steam_id LIKE ?1 OR display_name LIKE ?1 
-             ORDER BY last_seen DESC LIMIT 50",
-        )
+             FROM players ORDER BY {} LIMIT ?1",
+            order_by
+        ))
         .map_err(|e| e.to_string())?;
 
     let mut result = Vec::new();
-    let mut rows = stmt.query([&search_pattern]).map_err(|e| e.to_string())?;
+    let mut rows = stmt.query([limit]).map_err(|e| e.to_string())?;
 
     while let Some(row) = rows.next().map_err(|e| e.to_string())? {
         result.push(PlayerStats {
@@ -296,6 +844,204 @@ pub async fn search_players(
         });
     }
 
-    println!("  Found {} players", result.len());
     Ok(result)
 }
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerActivityDay {
+    pub date: String,
+    pub unique_players: usize,
+    pub peak_concurrent: usize,
+}
+
+/// Daily unique players and peak concurrent players for a server over the last `days` days.
+/// Sessions still open (no left_at) are counted as online until now.
+#[tauri::command]
+pub async fn get_server_activity(
+    state: State<'_, AppState>,
+    server_id: i64,
+    days: Option<i64>,
+) -> Result<Vec<ServerActivityDay>, String> {
+    use chrono::{DateTime, Duration, NaiveDate, Utc};
+    use std::collections::{BTreeMap, HashSet};
+
+    let days = days.unwrap_or(7).clamp(1, 365);
+    let now = Utc::now();
+    let first_day = (now - Duration::days(days - 1)).date_naive();
+    let window_start = first_day.and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+    println!(
+        "📈 Getting activity for server {} (last {} days)",
+        server_id, days
+    );
+
+    let sessions: Vec<(String, String, Option<String>)> = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT steam_id, joined_at, left_at FROM player_sessions 
+                 WHERE server_id = ?1 AND (left_at IS NULL OR left_at >= ?2)",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map(
+                rusqlite::params![server_id, window_start.to_rfc3339()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|e| e.to_string())?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    let parse = |s: &str| {
+        DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    };
+
+    // Clip every session to the window, then sweep join (+1) / leave (-1) events
+    let mut unique: BTreeMap<NaiveDate, HashSet<String>> = BTreeMap::new();
+    let mut events = Vec::new();
+    for (steam_id, joined_at, left_at) in sessions {
+        let Some(joined) = parse(&joined_at) else {
+            continue;
+        };
+        let left = left_at.as_deref().and_then(parse).unwrap_or(now);
+        let start = joined.max(window_start);
+        let end = left.min(now);
+        if end < start {
+            continue;
+        }
+
+        let mut day = start.date_naive();
+        while day <= end.date_naive() {
+            unique.entry(day).or_default().insert(steam_id.clone());
+            day = match day.succ_opt() {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        events.push((start, 1i32));
+        events.push((end, -1i32));
+    }
+
+    // Leaves sort before joins at the same instant so back-to-back sessions don't double count
+    events.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    let mut peaks: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    let mut current: i32 = 0;
+    let mut day = first_day;
+    for (time, delta) in events {
+        // Players still online carry over into every day we pass through
+        while day < time.date_naive() {
+            let Some(next) = day.succ_opt() else { break };
+            day = next;
+            let peak = peaks.entry(day).or_default();
+            *peak = (*peak).max(current.max(0) as usize);
+        }
+        current += delta;
+        let peak = peaks.entry(day).or_default();
+        *peak = (*peak).max(current.max(0) as usize);
+    }
+
+    let mut result = Vec::new();
+    let mut day = first_day;
+    while day <= now.date_naive() {
+        result.push(ServerActivityDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            unique_players: unique.get(&day).map(|s| s.len()).unwrap_or(0),
+            peak_concurrent: peaks.get(&day).copied().unwrap_or(0),
+        });
+        day = match day.succ_opt() {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_write_player_list_merges_unless_pruning() {
+        let dir = std::env::temp_dir().join(format!("asa_player_list_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("BanList.txt");
+        // Bans made in-game before the first sync
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "111\r\n222\r\n").unwrap();
+
+        let change = write_player_list(&path, &ids(&["222", "333"]), false).unwrap();
+        assert_eq!(change.entries, 3);
+        assert_eq!(change.added, ids(&["333"]));
+        assert!(change.removed.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "111\r\n222\r\n333\r\n"
+        );
+
+        let change = write_player_list(&path, &ids(&["333"]), true).unwrap();
+        assert_eq!(change.entries, 1);
+        assert!(change.added.is_empty());
+        assert_eq!(change.removed, ids(&["111", "222"]));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "333\r\n");
+
+        let change = write_player_list(&path, &[], true).unwrap();
+        assert_eq!(change.entries, 0);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_are_saved_when_players_leave_or_the_server_stops() {
+        let (_dir, db, server_id) = crate::db::test_support::db_with_server("player_sessions");
+        let intel = PlayerIntelligenceService::new();
+        let player = |name: &str, steam_id: &str| PlayerEntry {
+            index: 0,
+            name: name.to_string(),
+            net_id: steam_id.to_string(),
+            steam_id: Some(steam_id.to_string()),
+            eos_id: None,
+        };
+        let sessions = |steam_id: &str| -> i64 {
+            db.get_connection()
+                .unwrap()
+                .query_row(
+                    "SELECT total_sessions FROM players WHERE steam_id = ?1",
+                    [steam_id],
+                    |row| row.get(0),
+                )
+                .unwrap_or(0)
+        };
+
+        let online = [
+            player("Alice", "76561198000000001"),
+            player("Bob", "76561198000000002"),
+        ];
+        let ended = intel.sync_online_players(server_id, &online).await;
+        assert!(ended.is_empty());
+
+        // Bob logs off
+        let ended = intel.sync_online_players(server_id, &online[..1]).await;
+        save_ended_sessions(&db, &ended);
+        assert_eq!(sessions("76561198000000002"), 1);
+        assert_eq!(sessions("76561198000000001"), 0);
+
+        // The server stops with Alice still on
+        save_ended_sessions(&db, &intel.clear_server_sessions(server_id).await);
+        assert_eq!(sessions("76561198000000001"), 1);
+        assert!(intel.get_all_active_sessions().await.is_empty());
+    }
+}
@@ -1,8 +1,9 @@
 use crate::models::PluginInfo;
+use crate::services::ini_parser::IniParser;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::State;
 
 /// Plugin manifest structure (from plugin.json or PluginInfo.json inside plugin folder)
@@ -23,7 +24,7 @@ pub struct PluginManifest {
 
 /// Helper function to get server install path from database
 fn get_server_install_path(state: &State<'_, AppState>, server_id: i64) -> Result<PathBuf, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     let install_path: String = conn
@@ -54,6 +55,247 @@ pub async fn check_asa_api_installed(
     Ok(arkapi_path.exists())
 }
 
+const ASA_API_RELEASES_URL: &str =
+    "https://api.github.com/repos/ArkServerApi/AsaApi/releases/latest";
+pub const ASA_API_LOADER: &str = "AsaApiLoader.exe";
+const ASA_API_VERSION_FILE: &str = ".asaapi_version";
+
+fn get_win64_dir(install_path: &Path) -> PathBuf {
+    install_path
+        .join("ShooterGame")
+        .join("Binaries")
+        .join("Win64")
+}
+
+/// Download the latest AsaApi release and install it next to ArkAscendedServer.exe.
+/// Existing plugins and ArkApi/config.json are kept. Returns the installed version.
+#[tauri::command]
+pub async fn install_asa_api(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<String, String> {
+    use crate::services::server_installer::InstallProgress;
+    use tauri::Emitter;
+
+    if state.process_manager.is_running(server_id) {
+        return Err("Stop the server before installing AsaApi".to_string());
+    }
+
+    let install_path = get_server_install_path(&state, server_id)?;
+    let win64_dir = get_win64_dir(&install_path);
+    if !win64_dir.join("ArkAscendedServer.exe").exists() {
+        return Err(format!(
+            "ArkAscendedServer.exe not found in {:?}. Install the server first.",
+            win64_dir
+        ));
+    }
+
+    let emit = |stage: &str, progress: f32, message: String, is_complete: bool, is_error: bool| {
+        let _ = app_handle.emit(
+            "asa-api-install-progress",
+            InstallProgress {
+                stage: stage.to_string(),
+                progress,
+                message,
+                is_complete,
+                is_error,
+                steam_error: None,
+            },
+        );
+    };
+
+    let result = async {
+        emit(
+            "checking",
+            5.0,
+            "Looking up latest AsaApi release".to_string(),
+            false,
+            false,
+        );
+
+        let client = reqwest::Client::builder()
+            .user_agent("ASA-Server-Manager")
+            .build()
+            .map_err(|e| e.to_string())?;
+        let release: serde_json::Value = client
+            .get(ASA_API_RELEASES_URL)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to query AsaApi releases: {}", e))?
+            .error_for_status()
+            .map_err(|e| format!("Failed to query AsaApi releases: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid release response: {}", e))?;
+
+        let version = release["tag_name"]
+            .as_str()
+            .unwrap_or("unknown")
+            .to_string();
+        let download_url = release["assets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|a| {
+                let name = a["name"].as_str()?;
+                let url = a["browser_download_url"].as_str()?;
+                name.to_lowercase()
+                    .ends_with(".zip")
+                    .then(|| url.to_string())
+            })
+            .next()
+            .ok_or_else(|| format!("AsaApi release {} has no .zip asset", version))?;
+
+        emit(
+            "downloading",
+            20.0,
+            format!("Downloading AsaApi {}", version),
+            false,
+            false,
+        );
+        println!("⬇️ Downloading AsaApi {} from {}", version, download_url);
+
+        let bytes = client
+            .get(&download_url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Failed to download AsaApi: {}", e))?
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to download AsaApi: {}", e))?;
+
+        emit(
+            "extracting",
+            60.0,
+            "Extracting AsaApi".to_string(),
+            false,
+            false,
+        );
+
+        let target = win64_dir.clone();
+        let version_clone = version.clone();
+        tokio::task::spawn_blocking(move || extract_asa_api(&bytes, &target, &version_clone))
+            .await
+            .map_err(|e| e.to_string())??;
+
+        emit(
+            "verifying",
+            90.0,
+            "Verifying installation".to_string(),
+            false,
+            false,
+        );
+        if !win64_dir.join(ASA_API_LOADER).exists() || !win64_dir.join("ArkApi").is_dir() {
+            return Err(format!(
+                "AsaApi files were not found next to ArkAscendedServer.exe in {:?}",
+                win64_dir
+            ));
+        }
+
+        Ok::<String, String>(version)
+    }
+    .await;
+
+    match &result {
+        Ok(version) => {
+            println!("✅ AsaApi {} installed for server {}", version, server_id);
+            emit(
+                "complete",
+                100.0,
+                format!("AsaApi {} installed", version),
+                true,
+                false,
+            );
+        }
+        Err(e) => {
+            println!("❌ AsaApi install failed for server {}: {}", server_id, e);
+            emit("error", 0.0, e.clone(), true, true);
+        }
+    }
+
+    result
+}
+
+/// Extract an AsaApi release zip into Win64. The zip may wrap everything in a
+/// top-level folder, so the folder containing AsaApiLoader.exe is used as the root.
+fn extract_asa_api(bytes: &[u8], win64_dir: &Path, version: &str) -> Result<(), String> {
+    let temp_dir = win64_dir.join(".asaapi_temp");
+    if temp_dir.exists() {
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+    fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let result = (|| -> Result<(), String> {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+            .map_err(|e| format!("Invalid AsaApi archive: {}", e))?;
+        archive
+            .extract(&temp_dir)
+            .map_err(|e| format!("Failed to extract AsaApi: {}", e))?;
+
+        let root = walkdir::WalkDir::new(&temp_dir)
+            .max_depth(3)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .find(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case(ASA_API_LOADER)
+            })
+            .and_then(|e| e.path().parent().map(|p| p.to_path_buf()))
+            .ok_or_else(|| format!("{} not found in the AsaApi archive", ASA_API_LOADER))?;
+
+        // Keep the user's API config across upgrades
+        let config_path = win64_dir.join("ArkApi").join("config.json");
+        let saved_config = fs::read(&config_path).ok();
+
+        for entry in fs::read_dir(&root).map_err(|e| e.to_string())?.flatten() {
+            crate::commands::file_manager::copy_path_recursive(
+                &entry.path(),
+                &win64_dir.join(entry.file_name()),
+                true,
+                &mut |_, _| {},
+            )?;
+        }
+
+        if let Some(config) = saved_config {
+            fs::write(&config_path, config).map_err(|e| e.to_string())?;
+        }
+        fs::create_dir_all(win64_dir.join("ArkApi").join("Plugins")).map_err(|e| e.to_string())?;
+        fs::write(win64_dir.join("ArkApi").join(ASA_API_VERSION_FILE), version)
+            .map_err(|e| e.to_string())?;
+
+        Ok(())
+    })();
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    result
+}
+
+/// Get the installed AsaApi version, or None when AsaApi isn't installed.
+/// Installs made outside the manager report "unknown".
+#[tauri::command]
+pub async fn get_asa_api_version(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Option<String>, String> {
+    let install_path = get_server_install_path(&state, server_id)?;
+    let win64_dir = get_win64_dir(&install_path);
+
+    if !win64_dir.join(ASA_API_LOADER).exists() {
+        return Ok(None);
+    }
+
+    let version = fs::read_to_string(win64_dir.join("ArkApi").join(ASA_API_VERSION_FILE))
+        .map(|v| v.trim().to_string())
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    Ok(Some(version))
+}
+
 /// Get the plugin directory for a specific server
 #[tauri::command]
 pub async fn get_plugin_directory(
@@ -94,6 +336,145 @@ pub async fn import_plugin_archive(
     // Get server install path
     let install_path = get_server_install_path(&state, server_id)?;
 
+    // Determine plugin name from archive filename
+    let plugin_name = archive_path_buf
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Unknown Plugin".to_string());
+
+    install_plugin_archive(&install_path, &archive_path_buf, &plugin_name)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDownloadProgress {
+    pub server_id: i64,
+    pub url: String,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+}
+
+/// Download a plugin archive (e.g. a GitHub release asset) and install it.
+/// The archive is downloaded to a temp file which is removed afterwards.
+#[tauri::command]
+pub async fn install_plugin_from_url(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    server_id: i64,
+    url: String,
+) -> Result<PluginInfo, String> {
+    use tauri::Emitter;
+
+    let url = url.trim().to_string();
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err("Plugin URL must start with http:// or https://".to_string());
+    }
+
+    let install_path = get_server_install_path(&state, server_id)?;
+
+    println!("⬇️ Downloading plugin from {}", url);
+
+    let mut response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to download plugin: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Download failed with status {}", response.status()));
+    }
+
+    // Prefer the name in the requested URL, then the final URL after redirects
+    let file_name = [url.as_str(), response.url().as_str()]
+        .iter()
+        .map(|u| archive_name_from_url(u))
+        .find(|name| is_supported_archive(name))
+        .ok_or_else(|| {
+            "URL does not point to a .zip or .7z archive. Plugins must be downloaded as .zip or .7z"
+                .to_string()
+        })?;
+
+    let temp_path = std::env::temp_dir().join(format!("asa_plugin_{}_{}", server_id, file_name));
+    let total_bytes = response.content_length();
+
+    let download = async {
+        use std::io::Write;
+
+        let mut file = fs::File::create(&temp_path)
+            .map_err(|e| format!("Failed to create temp file: {}", e))?;
+        let mut downloaded_bytes = 0u64;
+        let mut last_emit = std::time::Instant::now();
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Download interrupted: {}", e))?
+        {
+            file.write_all(&chunk)
+                .map_err(|e| format!("Failed to write temp file: {}", e))?;
+            downloaded_bytes += chunk.len() as u64;
+
+            if last_emit.elapsed() >= std::time::Duration::from_millis(250) {
+                last_emit = std::time::Instant::now();
+                let _ = app_handle.emit(
+                    "plugin-download-progress",
+                    PluginDownloadProgress {
+                        server_id,
+                        url: url.clone(),
+                        downloaded_bytes,
+                        total_bytes,
+                    },
+                );
+            }
+        }
+
+        let _ = app_handle.emit(
+            "plugin-download-progress",
+            PluginDownloadProgress {
+                server_id,
+                url: url.clone(),
+                downloaded_bytes,
+                total_bytes,
+            },
+        );
+        Ok::<(), String>(())
+    };
+
+    let result = match download.await {
+        Ok(()) => {
+            let plugin_name = file_name
+                .rsplit_once('.')
+                .map(|(stem, _)| stem.to_string())
+                .unwrap_or_else(|| file_name.clone());
+            install_plugin_archive(&install_path, &temp_path, &plugin_name)
+        }
+        Err(e) => Err(e),
+    };
+
+    let _ = fs::remove_file(&temp_path);
+    result
+}
+
+/// Last path segment of a URL without query string or fragment, percent-decoding spaces
+fn archive_name_from_url(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .replace("%20", " ")
+        .chars()
+        .filter(|c| !matches!(c, '\\' | '/' | ':' | '*' | '"' | '<' | '>' | '|'))
+        .collect()
+}
+
+fn is_supported_archive(file_name: &str) -> bool {
+    let lower = file_name.to_lowercase();
+    lower.ends_with(".zip") || lower.ends_with(".7z")
+}
+
+/// Extract a plugin archive into ArkApi/Plugins, named after the plugin DLL
+fn install_plugin_archive(
+    install_path: &Path,
+    archive_path_buf: &PathBuf,
+    plugin_name: &str,
+) -> Result<PluginInfo, String> {
     let plugins_dir = install_path
         .join("ShooterGame")
         .join("Binaries")
@@ -107,12 +488,6 @@ pub async fn import_plugin_archive(
             .map_err(|e| format!("Failed to create plugins directory: {}", e))?;
     }
 
-    // Determine plugin name from archive filename
-    let plugin_name = archive_path_buf
-        .file_stem()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| "Unknown Plugin".to_string());
-
     let plugin_id = plugin_name
         .chars()
         .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
@@ -421,3 +796,105 @@ pub async fn toggle_plugin(
 
     Ok(())
 }
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginConfigFile {
+    pub file_path: String,
+    pub content: String,
+}
+
+/// Locate an ArkApi plugin's config: config.json in the plugin folder,
+/// otherwise the first .json file under configs/
+fn find_plugin_config(
+    state: &State<'_, AppState>,
+    server_id: i64,
+    plugin_id: &str,
+) -> Result<PathBuf, String> {
+    if plugin_id.is_empty() || plugin_id.contains(['/', '\\']) || plugin_id.contains("..") {
+        return Err(format!("Invalid plugin id '{}'", plugin_id));
+    }
+
+    let install_path = get_server_install_path(state, server_id)?;
+    let plugin_path = install_path
+        .join("ShooterGame")
+        .join("Binaries")
+        .join("Win64")
+        .join("ArkApi")
+        .join("Plugins")
+        .join(plugin_id);
+
+    if !plugin_path.exists() {
+        return Err(format!("Plugin '{}' not found", plugin_id));
+    }
+
+    let config_path = plugin_path.join("config.json");
+    if config_path.is_file() {
+        return Ok(config_path);
+    }
+
+    let configs_dir = plugin_path.join("configs");
+    if let Ok(entries) = fs::read_dir(&configs_dir) {
+        let mut configs: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.is_file()
+                    && p.extension()
+                        .map(|ext| ext.to_string_lossy().to_lowercase() == "json")
+                        .unwrap_or(false)
+            })
+            .collect();
+        configs.sort();
+        if let Some(path) = configs.into_iter().next() {
+            return Ok(path);
+        }
+    }
+
+    Err(format!(
+        "Plugin '{}' has no config.json or configs/*.json file",
+        plugin_id
+    ))
+}
+
+/// Read an ArkApi plugin's JSON config
+#[tauri::command]
+pub async fn read_plugin_config(
+    state: State<'_, AppState>,
+    server_id: i64,
+    plugin_id: String,
+) -> Result<PluginConfigFile, String> {
+    let config_path = find_plugin_config(&state, server_id, &plugin_id)?;
+
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read plugin config: {}", e))?;
+
+    Ok(PluginConfigFile {
+        file_path: config_path.to_string_lossy().to_string(),
+        content,
+    })
+}
+
+/// Validate and write an ArkApi plugin's JSON config
+#[tauri::command]
+pub async fn write_plugin_config(
+    state: State<'_, AppState>,
+    server_id: i64,
+    plugin_id: String,
+    content: String,
+) -> Result<(), String> {
+    serde_json::from_str::<serde_json::Value>(&content)
+        .map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let config_path = find_plugin_config(&state, server_id, &plugin_id)?;
+
+    IniParser::write_atomic(&config_path, &content)
+        .map_err(|e| format!("Failed to write plugin config: {}", e))?;
+
+    println!(
+        "💾 Plugin '{}' config saved for server {}",
+        plugin_id, server_id
+    );
+
+    Ok(())
+}
@@ -1,13 +1,61 @@
 // RCON Commands for ASA Server Manager
 // Exposes RCON functionality to the frontend
 
-use crate::models::{RconPlayer, RconResponse};
-use crate::services::rcon::RconService;
+use crate::error::{CommandResult, ManagerError};
+use crate::models::{
+    ChatMessage, RconFavorite, RconHistoryEntry, RconPlayer, RconResponse, RconTestResult,
+};
+use crate::services::rcon::{self as rcon_service, mask_sensitive, RconService};
+use crate::services::rcon_catalog::{RconCommandInfo, RCON_COMMANDS};
+use crate::services::rcon_protocol::{self, RawRconExchange};
+use crate::services::settings;
+use crate::services::{chat_bridge, chat_relay};
+use crate::AppState;
 use std::sync::Arc;
-use tauri::State;
-use tokio::sync::Mutex;
+use tauri::{Emitter, State};
 
-pub struct RconState(pub Arc<Mutex<RconService>>);
+/// Shared RCON service. It locks per server internally, so no outer lock is needed.
+pub struct RconState(pub Arc<RconService>);
+
+/// Address, RCON port and admin password stored for a server
+fn rcon_target(state: &AppState, server_id: i64) -> Result<(String, u16, String), String> {
+    let (rcon_port, admin_password, ip_address, rcon_enabled): (u16, String, Option<String>, bool) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        conn.query_row(
+            "SELECT rcon_port, admin_password, ip_address, COALESCE(rcon_enabled, 1) FROM servers WHERE id = ?1",
+            [server_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get::<_, i32>(3)? != 0)),
+        )
+        .map_err(|e| format!("Server not found: {}", e))?
+    };
+
+    if !rcon_enabled {
+        return Err("RCON is disabled for this server".to_string());
+    }
+
+    let address = ip_address
+        .filter(|ip| !ip.is_empty() && ip != "0.0.0.0")
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    Ok((address, rcon_port, admin_password))
+}
+
+/// Connect to a server's RCON with the port and admin password stored in the DB,
+/// unless a connection is already open
+pub async fn ensure_connected(
+    state: &AppState,
+    rcon: &RconService,
+    server_id: i64,
+) -> Result<(), String> {
+    if rcon.is_connected(server_id).await {
+        return Ok(());
+    }
+
+    let (address, rcon_port, admin_password) = rcon_target(state, server_id)?;
+    rcon.connect(server_id, &address, rcon_port, &admin_password)
+        .await?;
+    Ok(())
+}
 
 /// Connect to a server's RCON
 #[tauri::command]
@@ -17,9 +65,12 @@ pub async fn rcon_connect(
     address: String,
     port: u16,
     password: String,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.connect(server_id, &address, port, &password).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .connect(server_id, &address, port, &password)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Disconnect from a server's RCON
@@ -27,20 +78,31 @@ pub async fn rcon_connect(
 pub async fn rcon_disconnect(
     state: State<'_, RconState>,
     server_id: i64,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.disconnect(server_id).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .disconnect(server_id)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Send a raw RCON command
 #[tauri::command]
 pub async fn rcon_send_command(
+    app_state: State<'_, AppState>,
     state: State<'_, RconState>,
     server_id: i64,
     command: String,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.send_command(server_id, &command).await
+) -> CommandResult<RconResponse> {
+    let result = {
+        let service = &state.0;
+        service.send_command(server_id, &command).await
+    };
+
+    if let Err(e) = record_history(&app_state, server_id, &command, &result) {
+        println!("⚠️ Failed to record RCON history: {}", e);
+    }
+    result.map_err(ManagerError::Rcon)
 }
 
 /// Get list of online players
@@ -48,9 +110,12 @@ pub async fn rcon_send_command(
 pub async fn rcon_get_players(
     state: State<'_, RconState>,
     server_id: i64,
-) -> Result<Vec<RconPlayer>, String> {
-    let service = state.0.lock().await;
-    service.get_players(server_id).await
+) -> CommandResult<Vec<RconPlayer>> {
+    let service = &state.0;
+    service
+        .get_players(server_id)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Broadcast a message to all players
@@ -59,9 +124,43 @@ pub async fn rcon_broadcast(
     state: State<'_, RconState>,
     server_id: i64,
     message: String,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.broadcast(server_id, &message).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .broadcast(server_id, &message)
+        .await
+        .map_err(ManagerError::Rcon)
+}
+
+/// Post a chat message from the manager. The GetChat echo ("SERVER: ...") is skipped by
+/// the chat relay, which instead gets this message as a chat-message event straight away
+/// (and on Discord, if the server is bridged).
+#[tauri::command]
+pub async fn rcon_send_chat(
+    app_state: State<'_, AppState>,
+    state: State<'_, RconState>,
+    server_id: i64,
+    message: String,
+) -> CommandResult<ChatMessage> {
+    let message = message.trim().to_string();
+    if message.is_empty() {
+        return Err(ManagerError::Validation("Message is empty".to_string()));
+    }
+    let service = &state.0;
+    ensure_connected(&app_state, service, server_id)
+        .await
+        .map_err(ManagerError::Rcon)?;
+
+    chat_relay::expect_echo(server_id, &message);
+    service
+        .broadcast(server_id, &message)
+        .await
+        .map_err(ManagerError::Rcon)?;
+
+    let sent = chat_relay::parse_chat_line(server_id, &format!("SERVER: {}", message), true);
+    let _ = app_state.app_handle.emit(chat_relay::CHAT_EVENT, &sent);
+    chat_bridge::forward_to_discord(&app_state, server_id, std::slice::from_ref(&sent)).await;
+    Ok(sent)
 }
 
 /// Kick a player from the server
@@ -71,11 +170,12 @@ pub async fn rcon_kick_player(
     server_id: i64,
     steam_id: String,
     reason: Option<String>,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
     service
         .kick_player(server_id, &steam_id, reason.as_deref())
         .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Ban a player from the server
@@ -84,9 +184,12 @@ pub async fn rcon_ban_player(
     state: State<'_, RconState>,
     server_id: i64,
     steam_id: String,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.ban_player(server_id, &steam_id).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .ban_player(server_id, &steam_id)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Unban a player
@@ -95,9 +198,12 @@ pub async fn rcon_unban_player(
     state: State<'_, RconState>,
     server_id: i64,
     steam_id: String,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.unban_player(server_id, &steam_id).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .unban_player(server_id, &steam_id)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Save the world
@@ -105,9 +211,12 @@ pub async fn rcon_unban_player(
 pub async fn rcon_save_world(
     state: State<'_, RconState>,
     server_id: i64,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.save_world(server_id).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .save_world(server_id)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Destroy all wild dinos
@@ -115,9 +224,12 @@ pub async fn rcon_save_world(
 pub async fn rcon_destroy_wild_dinos(
     state: State<'_, RconState>,
     server_id: i64,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.destroy_wild_dinos(server_id).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .destroy_wild_dinos(server_id)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Set the time of day
@@ -127,9 +239,12 @@ pub async fn rcon_set_time(
     server_id: i64,
     hour: u8,
     minute: u8,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.set_time(server_id, hour, minute).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .set_time(server_id, hour, minute)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Send a private message to a player
@@ -139,17 +254,354 @@ pub async fn rcon_message_player(
     server_id: i64,
     steam_id: String,
     message: String,
-) -> Result<RconResponse, String> {
-    let service = state.0.lock().await;
-    service.message_player(server_id, &steam_id, &message).await
+) -> CommandResult<RconResponse> {
+    let service = &state.0;
+    service
+        .message_player(server_id, &steam_id, &message)
+        .await
+        .map_err(ManagerError::Rcon)
 }
 
 /// Check if RCON is connected to a server
 #[tauri::command]
-pub async fn rcon_is_connected(
+pub async fn rcon_is_connected(state: State<'_, RconState>, server_id: i64) -> CommandResult<bool> {
+    let service = &state.0;
+    Ok(service.is_connected(server_id).await)
+}
+
+/// Debug: run a command on a throwaway connection and return every packet
+/// exchanged, including how a long reply was split. Off unless rcon_debug_enabled is set.
+#[tauri::command]
+pub async fn rcon_send_raw(
+    app_state: State<'_, AppState>,
     state: State<'_, RconState>,
     server_id: i64,
-) -> Result<bool, String> {
-    let service = state.0.lock().await;
-    Ok(service.is_connected(server_id).await)
+    command: String,
+) -> CommandResult<RawRconExchange> {
+    if !settings::RCON_DEBUG_ENABLED.load(&app_state) {
+        return Err(ManagerError::validation(
+            "Raw RCON is a debugging tool, enable it in Settings first",
+        ));
+    }
+    let (address, port, password) =
+        rcon_target(&app_state, server_id).map_err(ManagerError::Rcon)?;
+
+    log::debug!(
+        "RCON raw exchange with server {}: {}",
+        server_id,
+        mask_sensitive(&command, &[&password])
+    );
+    let exchange = rcon_protocol::exchange(
+        &address,
+        port,
+        &password,
+        &command,
+        state.0.command_timeout(),
+    )
+    .await
+    .map_err(ManagerError::Rcon)?;
+    log::debug!(
+        "RCON raw exchange finished: {} packets, reply in {}, ended by {} after {}ms",
+        exchange.packets.len(),
+        exchange.response_packets,
+        exchange.ended_by,
+        exchange.elapsed_ms
+    );
+    Ok(exchange)
+}
+
+/// Try RCON credentials before saving them. Uses its own short-lived connection,
+/// so an existing console session for the server is left alone.
+#[tauri::command]
+pub async fn rcon_test(
+    address: String,
+    port: u16,
+    password: String,
+) -> CommandResult<RconTestResult> {
+    println!("🔌 Testing RCON credentials for {}:{}", address, port);
+    let result = rcon_service::test_connection(&address, port, &password).await;
+    if result.success {
+        println!("  ✅ {}", result.message);
+    } else {
+        println!("  ❌ {}", result.message);
+    }
+    Ok(result)
+}
+
+/// Per-server outcome of a command sent to every running server
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RconFanoutResult {
+    pub server_id: i64,
+    pub server_name: String,
+    pub success: bool,
+    /// Server was left out, e.g. because RCON is disabled for it
+    pub skipped: bool,
+    pub response: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Send a command to every running server, regardless of cluster.
+/// Servers with RCON disabled are skipped and reported as such.
+#[tauri::command]
+pub async fn rcon_send_all(
+    app_state: State<'_, AppState>,
+    state: State<'_, RconState>,
+    command: String,
+) -> CommandResult<Vec<RconFanoutResult>> {
+    let servers: Vec<(i64, String, bool)> = {
+        let db = &app_state.db;
+        let conn = db.get_connection().map_err(ManagerError::db)?;
+        let mut stmt = conn
+            .prepare("SELECT id, name, COALESCE(rcon_enabled, 1) FROM servers ORDER BY id")
+            .map_err(ManagerError::db)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get::<_, i32>(2)? != 0))
+            })
+            .map_err(ManagerError::db)?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    let service = &state.0;
+    let mut results = Vec::new();
+    for (server_id, server_name, rcon_enabled) in servers {
+        if !app_state.process_manager.is_running(server_id) {
+            continue;
+        }
+        let mut result = RconFanoutResult {
+            server_id,
+            server_name,
+            success: false,
+            skipped: false,
+            response: None,
+            error: None,
+        };
+        if !rcon_enabled {
+            result.skipped = true;
+            result.error = Some("RCON is disabled for this server".to_string());
+            results.push(result);
+            continue;
+        }
+
+        let response = match ensure_connected(&app_state, service, server_id).await {
+            Ok(()) => service.send_command(server_id, &command).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = record_history(&app_state, server_id, &command, &response) {
+            println!("⚠️ Failed to record RCON history: {}", e);
+        }
+        match response {
+            Ok(response) => {
+                result.success = response.success;
+                result.response = response.data.or(Some(response.message));
+            }
+            Err(e) => result.error = Some(e),
+        }
+        results.push(result);
+    }
+
+    println!(
+        "📡 Sent RCON command to {} running servers ({} succeeded)",
+        results.len(),
+        results.iter().filter(|r| r.success).count()
+    );
+    Ok(results)
+}
+
+/// Broadcast a chat message to every running server
+#[tauri::command]
+pub async fn rcon_broadcast_all(
+    app_state: State<'_, AppState>,
+    state: State<'_, RconState>,
+    message: String,
+) -> CommandResult<Vec<RconFanoutResult>> {
+    rcon_send_all(app_state, state, format!("ServerChat {}", message)).await
+}
+
+/// History rows kept per server; older entries are pruned on insert
+const HISTORY_LIMIT: i64 = 500;
+
+/// Longest response stored alongside a history entry
+const RESPONSE_SNIPPET_CHARS: usize = 200;
+
+fn record_history(
+    state: &AppState,
+    server_id: i64,
+    command: &str,
+    result: &Result<RconResponse, String>,
+) -> Result<(), String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let admin_password: String = conn
+        .query_row(
+            "SELECT COALESCE(admin_password, '') FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .unwrap_or_default();
+    let secrets = [admin_password.as_str()];
+
+    let (success, response) = match result {
+        Ok(response) => (true, response.data.clone().unwrap_or_default()),
+        Err(e) => (false, e.clone()),
+    };
+    let snippet: String = response
+        .trim()
+        .chars()
+        .take(RESPONSE_SNIPPET_CHARS)
+        .collect();
+
+    conn.execute(
+        "INSERT INTO rcon_command_history (server_id, command, response_snippet, success)
+         VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![
+            server_id,
+            mask_sensitive(command, &secrets),
+            mask_sensitive(&snippet, &secrets),
+            success
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "DELETE FROM rcon_command_history WHERE server_id = ?1 AND id NOT IN (
+            SELECT id FROM rcon_command_history WHERE server_id = ?1 ORDER BY id DESC LIMIT ?2
+        )",
+        rusqlite::params![server_id, HISTORY_LIMIT],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Most recent console commands for a server, newest first
+#[tauri::command]
+pub async fn get_rcon_history(
+    state: State<'_, AppState>,
+    server_id: i64,
+    limit: Option<i64>,
+) -> CommandResult<Vec<RconHistoryEntry>> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(ManagerError::db)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, server_id, command, response_snippet, success, created_at
+             FROM rcon_command_history WHERE server_id = ?1 ORDER BY id DESC LIMIT ?2",
+        )
+        .map_err(ManagerError::db)?;
+    let entries = stmt
+        .query_map(
+            rusqlite::params![server_id, limit.unwrap_or(50).clamp(1, HISTORY_LIMIT)],
+            |row| {
+                Ok(RconHistoryEntry {
+                    id: row.get(0)?,
+                    server_id: row.get(1)?,
+                    command: row.get(2)?,
+                    response_snippet: row.get(3)?,
+                    success: row.get(4)?,
+                    created_at: row.get(5)?,
+                })
+            },
+        )
+        .map_err(ManagerError::db)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn clear_rcon_history(state: State<'_, AppState>, server_id: i64) -> CommandResult<()> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(ManagerError::db)?;
+    conn.execute(
+        "DELETE FROM rcon_command_history WHERE server_id = ?1",
+        [server_id],
+    )
+    .map_err(ManagerError::db)?;
+    Ok(())
+}
+
+/// Save a one-click command; an existing favorite with the same label is replaced
+#[tauri::command]
+pub async fn save_rcon_favorite(
+    state: State<'_, AppState>,
+    server_id: i64,
+    label: String,
+    command: String,
+) -> CommandResult<RconFavorite> {
+    let label = label.trim().to_string();
+    let command = command.trim().to_string();
+    if label.is_empty() || command.is_empty() {
+        return Err(ManagerError::validation("Label and command are required"));
+    }
+
+    let db = &state.db;
+    let conn = db.get_connection().map_err(ManagerError::db)?;
+    conn.execute(
+        "INSERT INTO rcon_favorites (server_id, label, command) VALUES (?1, ?2, ?3)
+         ON CONFLICT(server_id, label) DO UPDATE SET command = excluded.command",
+        rusqlite::params![server_id, label, command],
+    )
+    .map_err(ManagerError::db)?;
+
+    let id = conn
+        .query_row(
+            "SELECT id FROM rcon_favorites WHERE server_id = ?1 AND label = ?2",
+            rusqlite::params![server_id, label],
+            |row| row.get(0),
+        )
+        .map_err(ManagerError::db)?;
+
+    Ok(RconFavorite {
+        id,
+        server_id,
+        label,
+        command,
+    })
+}
+
+#[tauri::command]
+pub async fn get_rcon_favorites(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> CommandResult<Vec<RconFavorite>> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(ManagerError::db)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, server_id, label, command FROM rcon_favorites
+             WHERE server_id = ?1 ORDER BY label COLLATE NOCASE",
+        )
+        .map_err(ManagerError::db)?;
+    let favorites = stmt
+        .query_map([server_id], |row| {
+            Ok(RconFavorite {
+                id: row.get(0)?,
+                server_id: row.get(1)?,
+                label: row.get(2)?,
+                command: row.get(3)?,
+            })
+        })
+        .map_err(ManagerError::db)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(favorites)
+}
+
+#[tauri::command]
+pub async fn delete_rcon_favorite(state: State<'_, AppState>, id: i64) -> CommandResult<()> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(ManagerError::db)?;
+    conn.execute("DELETE FROM rcon_favorites WHERE id = ?1", [id])
+        .map_err(ManagerError::db)?;
+    Ok(())
+}
+
+/// Static list of common admin commands for console autocomplete
+#[tauri::command]
+pub async fn get_rcon_command_catalog() -> CommandResult<Vec<RconCommandInfo>> {
+    Ok(RCON_COMMANDS.to_vec())
 }
@@ -0,0 +1,33 @@
+use crate::services::remote_api::{self, RemoteApiStatus};
+use crate::services::settings;
+use crate::AppState;
+use tauri::State;
+
+/// Whether the remote management API is running and where
+#[tauri::command]
+pub async fn get_remote_api_status(
+    app_handle: tauri::AppHandle,
+) -> Result<RemoteApiStatus, String> {
+    Ok(remote_api::status(&app_handle).await)
+}
+
+/// Re-read the remote API settings and (re)start or stop the server to match
+#[tauri::command]
+pub async fn restart_remote_api(app_handle: tauri::AppHandle) -> Result<RemoteApiStatus, String> {
+    remote_api::start(&app_handle).await?;
+    Ok(remote_api::status(&app_handle).await)
+}
+
+/// Generate and store a new access token. Takes effect after restart_remote_api.
+#[tauri::command]
+pub async fn regenerate_remote_api_token(state: State<'_, AppState>) -> Result<String, String> {
+    let db = &state.db;
+    let token: String = {
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        // SQLite's randomblob is backed by the OS random source
+        conn.query_row("SELECT lower(hex(randomblob(32)))", [], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+    };
+    settings::REMOTE_API_TOKEN.set(db, &Some(token.clone()))?;
+    Ok(token)
+}
@@ -18,6 +18,8 @@ pub struct ScheduledTask {
     pub enabled: bool,
     pub last_run: Option<String>,
     pub created_at: String,
+    /// Type-specific settings, see services::scheduler
+    pub options: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +31,45 @@ pub struct CreateTaskRequest {
     pub command: Option<String>,
     pub message: Option<String>,
     pub pre_warning_minutes: i32,
+    #[serde(default)]
+    pub options: Option<serde_json::Value>,
+}
+
+/// One execution of a scheduled task. `outcome` is None while it is still running,
+/// then "success", "failed" or "skipped"; `success` is None for skipped runs too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRun {
+    pub id: i64,
+    pub task_id: i64,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub success: Option<bool>,
+    pub outcome: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Column list shared by every task query; rows map through `task_from_row`
+pub const TASK_SELECT: &str = "SELECT id, server_id, task_type, cron_expression, command, message,
+        pre_warning_minutes, enabled, last_run, created_at, options
+     FROM scheduled_tasks";
+
+pub fn task_from_row(row: &rusqlite::Row) -> rusqlite::Result<ScheduledTask> {
+    Ok(ScheduledTask {
+        id: row.get(0)?,
+        server_id: row.get(1)?,
+        task_type: row.get(2)?,
+        cron_expression: row.get(3)?,
+        command: row.get(4)?,
+        message: row.get(5)?,
+        pre_warning_minutes: row.get(6)?,
+        enabled: row.get::<_, i32>(7)? == 1,
+        last_run: row.get(8)?,
+        created_at: row.get(9)?,
+        options: row
+            .get::<_, Option<String>>(10)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+    })
 }
 
 /// Get all scheduled tasks for a server
@@ -39,32 +80,18 @@ pub async fn get_scheduled_tasks(
 ) -> Result<Vec<ScheduledTask>, String> {
     println!("📅 Getting scheduled tasks for server {}", server_id);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, server_id, task_type, cron_expression, command, message, 
-                    pre_warning_minutes, enabled, last_run, created_at 
-             FROM scheduled_tasks WHERE server_id = ?1 ORDER BY created_at DESC",
-        )
+        .prepare(&format!(
+            "{} WHERE server_id = ?1 ORDER BY created_at DESC",
+            TASK_SELECT
+        ))
         .map_err(|e| e.to_string())?;
 
     let task_iter = stmt
-        .query_map([server_id], |row| {
-            Ok(ScheduledTask {
-                id: row.get(0)?,
-                server_id: row.get(1)?,
-                task_type: row.get(2)?,
-                cron_expression: row.get(3)?,
-                command: row.get(4)?,
-                message: row.get(5)?,
-                pre_warning_minutes: row.get(6)?,
-                enabled: row.get::<_, i32>(7)? == 1,
-                last_run: row.get(8)?,
-                created_at: row.get(9)?,
-            })
-        })
+        .query_map([server_id], task_from_row)
         .map_err(|e| e.to_string())?;
 
     let tasks: Vec<ScheduledTask> = task_iter.filter_map(|t| t.ok()).collect();
@@ -84,12 +111,18 @@ pub async fn create_scheduled_task(
         request.task_type, request.server_id
     );
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    crate::services::scheduler::parse_schedule(&request.cron_expression)?;
+    crate::services::scheduler::validate_task_options(
+        &request.task_type,
+        request.options.as_ref(),
+    )?;
+
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute(
-        "INSERT INTO scheduled_tasks (server_id, task_type, cron_expression, command, message, pre_warning_minutes, enabled)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1)",
+        "INSERT INTO scheduled_tasks (server_id, task_type, cron_expression, command, message, pre_warning_minutes, enabled, options)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7)",
         rusqlite::params![
             request.server_id,
             request.task_type,
@@ -97,6 +130,7 @@ pub async fn create_scheduled_task(
             request.command,
             request.message,
             request.pre_warning_minutes,
+            request.options.as_ref().map(|o| o.to_string()),
         ],
     )
     .map_err(|e| e.to_string())?;
@@ -114,6 +148,7 @@ pub async fn create_scheduled_task(
         enabled: true,
         last_run: None,
         created_at: chrono::Utc::now().to_rfc3339(),
+        options: request.options,
     };
 
     println!("  ✅ Created task with ID {}", id);
@@ -129,7 +164,7 @@ pub async fn toggle_scheduled_task(
 ) -> Result<(), String> {
     println!("🔄 Toggling task {} to {}", task_id, enabled);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute(
@@ -147,7 +182,7 @@ pub async fn toggle_scheduled_task(
 pub async fn delete_scheduled_task(state: State<'_, AppState>, task_id: i64) -> Result<(), String> {
     println!("🗑️ Deleting scheduled task {}", task_id);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute("DELETE FROM scheduled_tasks WHERE id = ?1", [task_id])
@@ -157,10 +192,20 @@ pub async fn delete_scheduled_task(state: State<'_, AppState>, task_id: i64) ->
     Ok(())
 }
 
+/// Run a task immediately to try it out; the outcome lands in its history.
+/// Returns false if the task skipped itself (e.g. restart while the server is offline).
+#[tauri::command]
+pub async fn run_scheduled_task_now(
+    app_handle: tauri::AppHandle,
+    task_id: i64,
+) -> Result<bool, String> {
+    crate::services::scheduler::run_now(&app_handle, task_id).await
+}
+
 /// Update task's last run time
 #[tauri::command]
 pub async fn update_task_last_run(state: State<'_, AppState>, task_id: i64) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     conn.execute(
@@ -171,3 +216,39 @@ pub async fn update_task_last_run(state: State<'_, AppState>, task_id: i64) -> R
 
     Ok(())
 }
+
+/// Most recent runs of a task, newest first
+#[tauri::command]
+pub async fn get_task_history(
+    state: State<'_, AppState>,
+    task_id: i64,
+    limit: Option<u32>,
+) -> Result<Vec<TaskRun>, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, task_id, started_at, finished_at, success, message, outcome
+             FROM scheduled_task_runs WHERE task_id = ?1 ORDER BY id DESC LIMIT ?2",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let runs = stmt
+        .query_map(rusqlite::params![task_id, limit.unwrap_or(20)], |row| {
+            Ok(TaskRun {
+                id: row.get(0)?,
+                task_id: row.get(1)?,
+                started_at: row.get(2)?,
+                finished_at: row.get(3)?,
+                success: row.get::<_, Option<i32>>(4)?.map(|s| s == 1),
+                message: row.get(5)?,
+                outcome: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(runs)
+}
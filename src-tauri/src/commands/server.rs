@@ -1,49 +1,106 @@
-use crate::models::{RconConfig, Server, ServerConfig, ServerPorts, ServerStatus};
+use crate::error::{CommandResult, ManagerError};
+use crate::models::{
+    AutoStopTrigger, BackupType, RconConfig, Server, ServerConfig, ServerFilter, ServerPorts,
+    ServerStatus, DEFAULT_ADMIN_PASSWORD,
+};
+use crate::services::diagnostics;
+use crate::services::file_watcher;
+use crate::services::ini_parser::{IniParser, GLOBAL_SECTION};
+use crate::services::launch_args;
 use crate::services::network;
-use crate::services::server_installer::ServerInstaller;
+use crate::services::notifications::{self, NotificationEvent};
+use crate::services::server_installer::{self, ServerInstaller};
+use crate::services::server_state;
+use crate::services::settings;
+use crate::services::uptime::{self, UptimeReport};
 use crate::AppState;
 use anyhow::Error as AnyhowError;
+use rusqlite::types::Value;
 use rusqlite::Row;
 use std::path::PathBuf;
 use tauri::{Manager, State};
 
+/// WHERE clause and parameters for a ServerFilter
+fn server_filter_sql(filter: &ServerFilter) -> (String, Vec<Value>) {
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+
+    if !filter.statuses.is_empty() {
+        conditions.push(format!(
+            "status IN ({})",
+            vec!["?"; filter.statuses.len()].join(", ")
+        ));
+        params.extend(filter.statuses.iter().cloned().map(Value::Text));
+    }
+    if let Some(cluster_id) = filter.cluster_id {
+        conditions.push(
+            "(cluster_id = ? OR id IN (SELECT server_id FROM cluster_servers WHERE cluster_id = ?))"
+                .to_string(),
+        );
+        params.push(Value::Integer(cluster_id));
+        params.push(Value::Integer(cluster_id));
+    }
+    if let Some(tag) = &filter.tag {
+        conditions.push("id IN (SELECT server_id FROM server_tags WHERE tag = ?)".to_string());
+        params.push(Value::Text(tag.trim().to_string()));
+    }
+
+    if conditions.is_empty() {
+        (String::new(), params)
+    } else {
+        (format!(" WHERE {}", conditions.join(" AND ")), params)
+    }
+}
+
+/// List servers, optionally filtered and paged (ordered by id)
 #[tauri::command]
-pub async fn get_all_servers(state: State<'_, AppState>) -> Result<Vec<Server>, String> {
-    let db = state
-        .db
-        .lock()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-    let conn = db
-        .get_connection()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+pub async fn get_all_servers(
+    state: State<'_, AppState>,
+    filter: Option<ServerFilter>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<Server>, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let (where_clause, mut params) = server_filter_sql(&filter.unwrap_or_default());
+    // LIMIT -1 is SQLite for "no limit"
+    params.push(Value::Integer(limit.map(i64::from).unwrap_or(-1)));
+    params.push(Value::Integer(offset.map(i64::from).unwrap_or(0)));
 
     let mut stmt = conn
-        .prepare(
+        .prepare(&format!(
             "SELECT id, name, install_path, status, game_port, query_port, rcon_port, max_players, 
          server_password, admin_password, ip_address, created_at, last_started, 
-         auto_start, auto_stop, intelligent_mode FROM servers",
-        )
+         auto_start, auto_stop, intelligent_mode, auto_port_forward, use_asa_api,
+         backup_before_restart, battleye_enabled, crossplay_platforms
+         FROM servers{} ORDER BY id LIMIT ? OFFSET ?",
+            where_clause
+        ))
         .map_err(|e: rusqlite::Error| e.to_string())?;
 
     let mut servers = Vec::new();
-    let mut rows = stmt.query([]).map_err(|e: rusqlite::Error| e.to_string())?;
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(params))
+        .map_err(|e: rusqlite::Error| e.to_string())?;
 
     while let Some(row) = rows.next().map_err(|e: rusqlite::Error| e.to_string())? {
         let status_str: String = row.get(3).unwrap_or_else(|_| "stopped".to_string());
-        let status = match status_str.as_str() {
-            "running" => ServerStatus::Running,
-            "starting" => ServerStatus::Starting,
-            "stopped" => ServerStatus::Stopped,
-            "crashed" => ServerStatus::Crashed,
-            "updating" => ServerStatus::Updating,
-            "restarting" => ServerStatus::Restarting,
-            "online" => ServerStatus::Online,
-            _ => ServerStatus::Stopped,
-        };
+        let status = ServerStatus::from_db(&status_str);
 
         let auto_start: i32 = row.get(13).unwrap_or(0);
         let auto_stop: i32 = row.get(14).unwrap_or(0);
         let intelligent_mode: i32 = row.get(15).unwrap_or(0);
+        let auto_port_forward: i32 = row.get(16).unwrap_or(0);
+        let use_asa_api: i32 = row.get(17).unwrap_or(0);
+        let backup_before_restart: i32 = row.get(18).unwrap_or(0);
+        let battleye_enabled: i32 = row.get(19).unwrap_or(0);
+        let crossplay_platforms: Vec<String> = row
+            .get::<_, Option<String>>(20)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
 
         servers.push(Server {
             id: row.get(0).map_err(|e| e.to_string())?,
@@ -62,8 +119,9 @@ pub async fn get_all_servers(state: State<'_, AppState>) -> Result<Vec<Server>,
                 map_name: "".to_string(),     // Not stored in this query
                 session_name: "".to_string(), // Not stored in this query
                 motd: None,
-                mods: vec![],
+                mods: enabled_mod_ids(&conn, row.get(0).map_err(|e| e.to_string())?)?,
                 custom_args: None,
+                crossplay_platforms,
             },
             rcon_config: RconConfig {
                 enabled: true,
@@ -75,12 +133,145 @@ pub async fn get_all_servers(state: State<'_, AppState>) -> Result<Vec<Server>,
             auto_start: auto_start != 0,
             auto_stop: auto_stop != 0,
             intelligent_mode: intelligent_mode != 0,
+            auto_port_forward: auto_port_forward != 0,
+            use_asa_api: use_asa_api != 0,
+            backup_before_restart: backup_before_restart != 0,
+            battleye_enabled: battleye_enabled != 0,
         });
     }
 
     Ok(servers)
 }
 
+/// Number of servers matching a filter, for paging
+#[tauri::command]
+pub async fn count_servers(
+    state: State<'_, AppState>,
+    filter: Option<ServerFilter>,
+) -> Result<i64, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let (where_clause, params) = server_filter_sql(&filter.unwrap_or_default());
+    conn.query_row(
+        &format!("SELECT COUNT(*) FROM servers{}", where_clause),
+        rusqlite::params_from_iter(params),
+        |row| row.get(0),
+    )
+    .map_err(|e: rusqlite::Error| e.to_string())
+}
+
+/// Tags are trimmed and stored as typed; matching ignores case
+fn normalize_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    if tag.len() > 64 {
+        return Err("Tag is too long (max 64 characters)".to_string());
+    }
+    Ok(tag.to_string())
+}
+
+#[tauri::command]
+pub async fn add_server_tag(
+    state: State<'_, AppState>,
+    server_id: i64,
+    tag: String,
+) -> Result<(), String> {
+    let tag = normalize_tag(&tag)?;
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO server_tags (server_id, tag) VALUES (?1, ?2)",
+        rusqlite::params![server_id, tag],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_server_tag(
+    state: State<'_, AppState>,
+    server_id: i64,
+    tag: String,
+) -> Result<(), String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "DELETE FROM server_tags WHERE server_id = ?1 AND tag = ?2",
+        rusqlite::params![server_id, tag.trim()],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_server_tags(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Vec<String>, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT tag FROM server_tags WHERE server_id = ?1 ORDER BY tag")
+        .map_err(|e| e.to_string())?;
+    let tags = stmt
+        .query_map([server_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(tags)
+}
+
+/// Every tag in use, for filter pickers
+#[tauri::command]
+pub async fn get_all_tags(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT DISTINCT tag FROM server_tags ORDER BY tag")
+        .map_err(|e| e.to_string())?;
+    let tags = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(tags)
+}
+
+#[tauri::command]
+pub async fn get_servers_by_tag(
+    state: State<'_, AppState>,
+    tag: String,
+) -> Result<Vec<Server>, String> {
+    let filter = ServerFilter {
+        tag: Some(tag),
+        ..Default::default()
+    };
+    get_all_servers(state, Some(filter), None, None).await
+}
+
+/// Enabled mod ids in load order. The mods table is the single source of truth
+/// for what a server launches with; servers.mods is deprecated and unused.
+pub fn enabled_mod_ids(conn: &rusqlite::Connection, server_id: i64) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT mod_id FROM mods WHERE server_id = ?1 AND enabled = 1 ORDER BY load_order ASC",
+        )
+        .map_err(|e: rusqlite::Error| e.to_string())?;
+    let mods = stmt
+        .query_map([server_id], |row| row.get::<usize, String>(0))
+        .map_err(|e: rusqlite::Error| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(mods)
+}
+
 #[tauri::command]
 pub async fn get_server_by_id(
     _state: State<'_, AppState>,
@@ -99,6 +290,111 @@ pub async fn show_server_console(state: State<'_, AppState>, server_id: i64) ->
         .map_err(|e| e.to_string())
 }
 
+/// Type a command into the server console without RCON. Requires the server to have
+/// been launched with console input enabled (settings::CONSOLE_STDIN_ENABLED).
+#[tauri::command]
+pub async fn send_console_command(
+    state: State<'_, AppState>,
+    server_id: i64,
+    command: String,
+) -> Result<(), String> {
+    state
+        .process_manager
+        .send_console_command(server_id, &command)
+        .map_err(|e| e.to_string())
+}
+
+/// Collect every port registered by existing servers, tagged with the owning server name.
+/// `except` leaves out one server, e.g. the one being checked.
+fn get_registered_ports(
+    conn: &rusqlite::Connection,
+    except: Option<i64>,
+) -> Result<Vec<(u16, String)>, String> {
+    let mut stmt = conn
+        .prepare("SELECT name, game_port, query_port, rcon_port FROM servers WHERE id IS NOT ?1")
+        .map_err(|e: rusqlite::Error| e.to_string())?;
+
+    let mut ports = Vec::new();
+    let mut rows = stmt
+        .query([except])
+        .map_err(|e: rusqlite::Error| e.to_string())?;
+    while let Some(row) = rows.next().map_err(|e: rusqlite::Error| e.to_string())? {
+        let name: String = row.get(0).unwrap_or_default();
+        for idx in 1..=3 {
+            if let Ok(port) = row.get::<_, u16>(idx) {
+                ports.push((port, name.clone()));
+            }
+        }
+    }
+
+    Ok(ports)
+}
+
+/// Describe every conflict for a port triplet (registered servers first, then other apps)
+fn find_port_conflicts(ports: &ServerPorts, registered: &[(u16, String)]) -> Vec<String> {
+    let mut conflicts = Vec::new();
+
+    for (label, port) in [
+        ("Game Port", ports.game_port),
+        ("Query Port", ports.query_port),
+        ("RCON Port", ports.rcon_port),
+    ] {
+        if let Some((_, owner)) = registered.iter().find(|(p, _)| *p == port) {
+            conflicts.push(format!("{} {} is used by server '{}'.", label, port, owner));
+        } else if network::is_port_in_use(port) {
+            conflicts.push(format!(
+                "{} {} is already in use by another application.",
+                label, port
+            ));
+        }
+    }
+
+    conflicts
+}
+
+/// Find the next free port triplet, offsetting all three ports by 10 (same as clone_server)
+fn find_free_ports(base: &ServerPorts, registered: &[(u16, String)]) -> Option<ServerPorts> {
+    let mut candidate = base.clone();
+
+    for _ in 0..100 {
+        if find_port_conflicts(&candidate, registered).is_empty() {
+            return Some(candidate);
+        }
+
+        candidate = ServerPorts {
+            game_port: candidate.game_port.checked_add(10)?,
+            query_port: candidate.query_port.checked_add(10)?,
+            rcon_port: candidate.rcon_port.checked_add(10)?,
+        };
+    }
+
+    None
+}
+
+/// Suggest the next free game/query/rcon ports starting from a base game port.
+/// Query and RCON ports keep the same distance from the ASA defaults as the game port.
+#[tauri::command]
+pub async fn suggest_free_ports(
+    state: State<'_, AppState>,
+    base_game_port: u16,
+) -> Result<ServerPorts, String> {
+    let registered = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        get_registered_ports(&conn, None)?
+    };
+
+    let shift = base_game_port.saturating_sub(7777);
+    let base = ServerPorts {
+        game_port: base_game_port,
+        query_port: 27015u16.saturating_add(shift),
+        rcon_port: 27020u16.saturating_add(shift),
+    };
+
+    find_free_ports(&base, &registered)
+        .ok_or_else(|| format!("No free port range found starting at {}", base_game_port))
+}
+
 #[tauri::command]
 pub async fn install_server(
     app_handle: tauri::AppHandle,
@@ -112,6 +408,32 @@ pub async fn install_server(
 ) -> Result<Server, String> {
     println!("🚀 Installing server: {} at {}", name, install_path);
 
+    // Check for port conflicts before downloading anything
+    {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        let registered = get_registered_ports(&conn, None)?;
+
+        let requested = ServerPorts {
+            game_port,
+            query_port,
+            rcon_port,
+        };
+        let conflicts = find_port_conflicts(&requested, &registered);
+
+        if !conflicts.is_empty() {
+            println!("  ⚠️ Port conflicts detected: {:?}", conflicts);
+            let suggestion = match find_free_ports(&requested, &registered) {
+                Some(free) => format!(
+                    " Suggested ports: game {}, query {}, rcon {}",
+                    free.game_port, free.query_port, free.rcon_port
+                ),
+                None => String::new(),
+            };
+            return Err(format!("{}{}", conflicts.join(" "), suggestion));
+        }
+    }
+
     let path = PathBuf::from(&install_path);
 
     // Create the installer and run the installation
@@ -119,13 +441,8 @@ pub async fn install_server(
     installer.install_asa_server(&path).await?;
 
     // Create database entry
-    let db = state
-        .db
-        .lock()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-    let conn = db
-        .get_connection()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     // Check if server name already exists and make it unique
     let mut unique_name = name.clone();
@@ -158,7 +475,7 @@ pub async fn install_server(
             query_port,
             rcon_port,
             70,
-            "admin123",
+            DEFAULT_ADMIN_PASSWORD,
             &map_name,
             &unique_name,
             "ASA", // Server type - ARK: Survival Ascended
@@ -182,16 +499,17 @@ pub async fn install_server(
         config: ServerConfig {
             max_players: 70,
             server_password: None,
-            admin_password: "admin123".to_string(),
+            admin_password: DEFAULT_ADMIN_PASSWORD.to_string(),
             map_name: map_name.clone(),
             session_name: unique_name,
             motd: None,
             mods: vec![],
             custom_args: None,
+            crossplay_platforms: Vec::new(),
         },
         rcon_config: RconConfig {
             enabled: true,
-            password: "admin123".to_string(),
+            password: DEFAULT_ADMIN_PASSWORD.to_string(),
         },
         ip_address: None,
         created_at: chrono::Utc::now().to_rfc3339(),
@@ -199,7 +517,92 @@ pub async fn install_server(
         auto_start: false, // Default: OFF
         auto_stop: false,  // Default: OFF
         intelligent_mode: false,
+        auto_port_forward: false,
+        use_asa_api: false,
+        backup_before_restart: false,
+        battleye_enabled: false,
+    })
+}
+
+/// Seed a new install folder with the game files of an already-installed server
+/// instead of downloading ~10 GB again. Saves, configs, mods and plugins are not
+/// copied. Installing a server at `new_path` afterwards finds the files in place
+/// and only runs a SteamCMD validate.
+#[tauri::command]
+pub async fn install_server_from_existing(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    source_server_id: i64,
+    new_path: String,
+) -> Result<server_installer::BinaryCopyReport, String> {
+    let (source_path, status) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        conn.query_row(
+            "SELECT install_path, status FROM servers WHERE id = ?1",
+            [source_server_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .map_err(|e| format!("Source server not found: {}", e))?
+    };
+
+    if ServerStatus::from_db(&status) == ServerStatus::Updating {
+        return Err("The source server is being updated, try again when it finishes".to_string());
+    }
+    let source = PathBuf::from(&source_path);
+    if !source
+        .join("ShooterGame/Binaries/Win64/ArkAscendedServer.exe")
+        .exists()
+    {
+        return Err(format!("No server installed at {}", source_path));
+    }
+    let dest = PathBuf::from(&new_path);
+    if dest
+        .read_dir()
+        .map(|mut d| d.next().is_some())
+        .unwrap_or(false)
+    {
+        return Err(format!("{} is not empty", new_path));
+    }
+
+    println!(
+        "📋 Copying server files from {} to {}",
+        source_path, new_path
+    );
+
+    let installer = ServerInstaller::new(app_handle);
+    let report = tokio::task::spawn_blocking(move || {
+        installer.emit_progress("copying", 0.0, "Copying server files...");
+        let result = server_installer::copy_server_binaries(&source, &dest, &mut |files, bytes| {
+            if files % 200 == 0 {
+                installer.emit_progress(
+                    "copying",
+                    50.0,
+                    &format!("Copied {} files ({} MB)", files, bytes / 1_048_576),
+                );
+            }
+        });
+        match &result {
+            Ok(report) => installer.emit_progress(
+                "copying",
+                100.0,
+                &format!("Copied and verified {} files", report.files),
+            ),
+            Err(_) => {
+                let _ = std::fs::remove_dir_all(&dest);
+            }
+        }
+        result
     })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    println!(
+        "  ✅ Copied {} files ({} MB)",
+        report.files,
+        report.bytes / 1_048_576
+    );
+    Ok(report)
 }
 
 /// Clone an existing server with offset ports
@@ -224,13 +627,8 @@ pub async fn clone_server(
         admin_password,
         ip_address,
     ) = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         conn.query_row(
             "SELECT name, install_path, map_name, session_name, game_port, query_port, rcon_port,
@@ -293,13 +691,8 @@ pub async fn clone_server(
 
     // Insert new server into database
     let new_id = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
 
         conn.execute(
             "INSERT INTO servers (name, install_path, status, game_port, query_port, rcon_port,
@@ -349,6 +742,7 @@ pub async fn clone_server(
             motd: None,
             mods: vec![],
             custom_args: None,
+            crossplay_platforms: Vec::new(),
         },
         rcon_config: RconConfig {
             enabled: true,
@@ -360,105 +754,523 @@ pub async fn clone_server(
         auto_start: false,
         auto_stop: false,
         intelligent_mode: false,
+        auto_port_forward: false,
+        use_asa_api: false,
+        backup_before_restart: false,
+        battleye_enabled: false,
     })
 }
 
-/// Transfer settings (INI files) from one server to another
-#[tauri::command]
-pub async fn transfer_settings(
-    state: State<'_, AppState>,
-    source_server_id: i64,
-    target_server_id: i64,
-) -> Result<(), String> {
-    println!(
-        "📋 Transferring settings from server {} to {}",
-        source_server_id, target_server_id
-    );
+/// Current format version of exported server definitions
+const SERVER_DEFINITION_VERSION: u32 = 1;
+
+/// Portable server configuration bundle produced by export_server
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerDefinition {
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: String,
+    pub name: String,
+    pub install_path: String,
+    pub map_name: String,
+    pub session_name: String,
+    pub game_port: u16,
+    pub query_port: u16,
+    pub rcon_port: u16,
+    pub max_players: i32,
+    pub server_password: Option<String>,
+    pub admin_password: String,
+    #[serde(default)]
+    pub motd: Option<String>,
+    #[serde(default)]
+    pub custom_args: Option<String>,
+    #[serde(default)]
+    pub rcon_enabled: bool,
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub auto_start: bool,
+    #[serde(default)]
+    pub auto_stop: bool,
+    #[serde(default)]
+    pub intelligent_mode: bool,
+    #[serde(default)]
+    pub auto_port_forward: bool,
+    #[serde(default)]
+    pub use_asa_api: bool,
+    #[serde(default)]
+    pub battleye_enabled: bool,
+    /// Keys from launch_args::LAUNCH_FLAGS
+    #[serde(default)]
+    pub launch_flags: Vec<String>,
+    #[serde(default)]
+    pub crossplay_platforms: Vec<String>,
+    /// Key from launch_args::MOD_STRATEGIES; None uses the default
+    #[serde(default)]
+    pub mod_strategy: Option<String>,
+    #[serde(default)]
+    pub mods: Vec<ServerDefinitionMod>,
+    /// INI file name -> contents (only when exported with configs)
+    #[serde(default)]
+    pub configs: std::collections::HashMap<String, String>,
+}
 
-    // Get both server paths
-    let (source_path, target_path) = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerDefinitionMod {
+    pub mod_id: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub workshop_url: Option<String>,
+    pub enabled: bool,
+    pub load_order: i32,
+}
 
-        let source: String = conn
-            .query_row(
-                "SELECT install_path FROM servers WHERE id = ?1",
-                [source_server_id],
-                |row| row.get(0),
-            )
-            .map_err(|e| format!("Source server not found: {}", e))?;
+/// Blank the value of every *Password= line (ServerAdminPassword, ServerPassword,
+/// SpectatorPassword), keeping line endings intact
+fn redact_ini_passwords(content: &str) -> String {
+    content
+        .split_inclusive('\n')
+        .map(|line| match line.split_once('=') {
+            Some((key, value)) if key.trim().to_lowercase().contains("password") => {
+                let ending = &value[value.trim_end_matches(['\r', '\n']).len()..];
+                format!("{}={}", key, ending)
+            }
+            _ => line.to_string(),
+        })
+        .collect()
+}
 
-        let target: String = conn
-            .query_row(
-                "SELECT install_path FROM servers WHERE id = ?1",
-                [target_server_id],
-                |row| row.get(0),
-            )
-            .map_err(|e| format!("Target server not found: {}", e))?;
+/// A server's DB row and mods as an export bundle, without configs and with secrets
+fn read_server_definition(
+    conn: &rusqlite::Connection,
+    server_id: i64,
+) -> Result<ServerDefinition, String> {
+    let mut definition = conn
+        .query_row(
+            "SELECT name, install_path, map_name, session_name, game_port, query_port, rcon_port,
+             max_players, server_password, admin_password, motd, custom_args, rcon_enabled,
+             ip_address, auto_start, auto_stop, intelligent_mode, auto_port_forward, use_asa_api,
+             battleye_enabled, launch_flags, crossplay_platforms, mod_strategy
+             FROM servers WHERE id = ?1",
+            [server_id],
+            |row| {
+                Ok(ServerDefinition {
+                    version: SERVER_DEFINITION_VERSION,
+                    exported_at: chrono::Utc::now().to_rfc3339(),
+                    name: row.get(0)?,
+                    install_path: row.get(1)?,
+                    map_name: row.get(2)?,
+                    session_name: row.get(3)?,
+                    game_port: row.get(4)?,
+                    query_port: row.get(5)?,
+                    rcon_port: row.get(6)?,
+                    max_players: row.get(7)?,
+                    server_password: row.get(8)?,
+                    admin_password: row.get(9)?,
+                    motd: row.get(10)?,
+                    custom_args: row.get(11)?,
+                    rcon_enabled: row.get::<_, Option<i32>>(12)?.unwrap_or(1) != 0,
+                    ip_address: row.get(13)?,
+                    auto_start: row.get::<_, Option<i32>>(14)?.unwrap_or(0) != 0,
+                    auto_stop: row.get::<_, Option<i32>>(15)?.unwrap_or(0) != 0,
+                    intelligent_mode: row.get::<_, Option<i32>>(16)?.unwrap_or(0) != 0,
+                    auto_port_forward: row.get::<_, Option<i32>>(17)?.unwrap_or(0) != 0,
+                    use_asa_api: row.get::<_, Option<i32>>(18)?.unwrap_or(0) != 0,
+                    battleye_enabled: row.get::<_, Option<i32>>(19)?.unwrap_or(0) != 0,
+                    launch_flags: json_list(row.get(20)?),
+                    crossplay_platforms: json_list(row.get(21)?),
+                    mod_strategy: row.get(22)?,
+                    mods: Vec::new(),
+                    configs: std::collections::HashMap::new(),
+                })
+            },
+        )
+        .map_err(|e| format!("Server not found: {}", e))?;
 
-        (PathBuf::from(source), PathBuf::from(target))
-    };
+    let mut stmt = conn
+        .prepare(
+            "SELECT mod_id, name, version, author, description, workshop_url, enabled, load_order
+             FROM mods WHERE server_id = ?1 ORDER BY load_order ASC",
+        )
+        .map_err(|e: rusqlite::Error| e.to_string())?;
+    definition.mods = stmt
+        .query_map([server_id], |row| {
+            Ok(ServerDefinitionMod {
+                mod_id: row.get(0)?,
+                name: row.get(1)?,
+                version: row.get(2)?,
+                author: row.get(3)?,
+                description: row.get(4)?,
+                workshop_url: row.get(5)?,
+                enabled: row.get::<_, Option<bool>>(6)?.unwrap_or(true),
+                load_order: row.get(7)?,
+            })
+        })
+        .map_err(|e: rusqlite::Error| e.to_string())?
+        .filter_map(|m| m.ok())
+        .collect();
+
+    Ok(definition)
+}
 
-    // Copy config files
-    let source_config = source_path.join("ShooterGame/Saved/Config/WindowsServer");
-    let target_config = target_path.join("ShooterGame/Saved/Config/WindowsServer");
+/// A JSON list column (launch_flags, crossplay_platforms); NULL or bad JSON is empty
+fn json_list(json: Option<String>) -> Vec<String> {
+    json.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
 
-    if !source_config.exists() {
-        return Err("Source server has no config files".to_string());
-    }
+/// Export a server's DB row, mods and optionally its INI files as a JSON bundle.
+/// Bundles are meant for sharing, so passwords are left out (also from the INI
+/// files and custom args) unless `include_secrets` is set.
+#[tauri::command]
+pub async fn export_server(
+    state: State<'_, AppState>,
+    server_id: i64,
+    include_configs: Option<bool>,
+    include_secrets: Option<bool>,
+) -> Result<String, String> {
+    println!("📤 Exporting server definition {}", server_id);
 
-    std::fs::create_dir_all(&target_config)
-        .map_err(|e| format!("Failed to create target config dir: {}", e))?;
+    let mut definition = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        read_server_definition(&conn, server_id)?
+    };
 
-    for file in ["GameUserSettings.ini", "Game.ini"] {
-        let src = source_config.join(file);
-        let dst = target_config.join(file);
-        if src.exists() {
-            std::fs::copy(&src, &dst).map_err(|e| format!("Failed to copy {}: {}", file, e))?;
-            println!("  ✅ Copied {}", file);
+    if include_configs.unwrap_or(false) {
+        let config_dir =
+            PathBuf::from(&definition.install_path).join("ShooterGame/Saved/Config/WindowsServer");
+        for file in ["GameUserSettings.ini", "Game.ini"] {
+            if let Ok(content) = std::fs::read_to_string(config_dir.join(file)) {
+                definition.configs.insert(file.to_string(), content);
+            }
         }
     }
 
-    println!("  ✅ Settings transferred successfully");
-    Ok(())
-}
+    if !include_secrets.unwrap_or(false) {
+        let secrets: Vec<String> = definition
+            .server_password
+            .take()
+            .into_iter()
+            .chain(Some(std::mem::take(&mut definition.admin_password)))
+            .collect();
+        definition.custom_args = definition
+            .custom_args
+            .as_deref()
+            .map(|args| diagnostics::redact_secrets(args, &secrets));
+        for content in definition.configs.values_mut() {
+            *content = redact_ini_passwords(content);
+        }
+    }
 
-/// Extract save data (world/player) from one server to another
-#[tauri::command]
-pub async fn extract_save_data(
-    state: State<'_, AppState>,
-    source_server_id: i64,
-    target_server_id: i64,
-) -> Result<(), String> {
     println!(
-        "📦 Extracting save data from server {} to {}",
-        source_server_id, target_server_id
+        "  ✅ Exported '{}' with {} mods and {} config files",
+        definition.name,
+        definition.mods.len(),
+        definition.configs.len()
     );
+    serde_json::to_string_pretty(&definition).map_err(|e| e.to_string())
+}
 
-    // Get both server paths
-    let (source_path, target_path) = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+/// Insert an imported bundle as a new server, renaming it and moving its ports
+/// off anything already taken. Returns the new id, name and ports.
+fn insert_server_definition(
+    conn: &rusqlite::Connection,
+    definition: &ServerDefinition,
+    install_path: &str,
+) -> Result<(i64, String, ServerPorts), String> {
+    // Resolve name conflicts: "Name", "Name (2)", "Name (3)", ...
+    let name_taken = |candidate: &str| -> bool {
+        conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM servers WHERE name = ?1)",
+            [candidate],
+            |row| row.get::<_, bool>(0),
+        )
+        .unwrap_or(false)
+    };
+    let mut name = definition.name.clone();
+    let mut suffix = 2;
+    while name_taken(&name) {
+        name = format!("{} ({})", definition.name, suffix);
+        suffix += 1;
+    }
 
-        let source: String = conn
-            .query_row(
-                "SELECT install_path FROM servers WHERE id = ?1",
-                [source_server_id],
-                |row| row.get(0),
-            )
-            .map_err(|e| format!("Source server not found: {}", e))?;
+    let registered = get_registered_ports(conn, None)?;
+    let requested = ServerPorts {
+        game_port: definition.game_port,
+        query_port: definition.query_port,
+        rcon_port: definition.rcon_port,
+    };
+    let ports = find_free_ports(&requested, &registered)
+        .ok_or_else(|| "No free port range found for imported server".to_string())?;
+
+    // Settings from another version may not exist here; keep only what this one knows
+    let launch_flags: Vec<&str> = definition
+        .launch_flags
+        .iter()
+        .filter(|key| launch_args::launch_flag(key).is_some())
+        .map(String::as_str)
+        .collect();
+    let launch_flags = serde_json::to_string(&launch_flags).map_err(|e| e.to_string())?;
+    let crossplay_platforms = launch_args::validate_platforms(&definition.crossplay_platforms)
+        .unwrap_or_else(|e| {
+            println!("  ⚠️ Ignoring crossplay platforms from the bundle: {}", e);
+            Vec::new()
+        });
+    let crossplay_platforms =
+        serde_json::to_string(&crossplay_platforms).map_err(|e| e.to_string())?;
+    let mod_strategy = definition
+        .mod_strategy
+        .as_deref()
+        .filter(|key| launch_args::mod_strategy(key).is_some());
 
-        let target: String = conn
+    conn.execute(
+        "INSERT INTO servers (name, install_path, status, game_port, query_port, rcon_port,
+         max_players, admin_password, map_name, session_name, server_password, motd,
+         custom_args, rcon_enabled, ip_address, auto_start, auto_stop, intelligent_mode,
+         auto_port_forward, use_asa_api, battleye_enabled, launch_flags, crossplay_platforms,
+         mod_strategy)
+         VALUES (?1, ?2, 'stopped', ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19,
+         ?20, ?21, ?22, ?23)",
+        rusqlite::params![
+            name,
+            install_path,
+            ports.game_port,
+            ports.query_port,
+            ports.rcon_port,
+            definition.max_players,
+            definition.admin_password,
+            definition.map_name,
+            definition.session_name,
+            definition.server_password,
+            definition.motd,
+            definition.custom_args,
+            definition.rcon_enabled,
+            definition.ip_address,
+            definition.auto_start,
+            definition.auto_stop,
+            definition.intelligent_mode,
+            definition.auto_port_forward,
+            definition.use_asa_api,
+            definition.battleye_enabled,
+            launch_flags,
+            crossplay_platforms,
+            mod_strategy,
+        ],
+    )
+    .map_err(|e: rusqlite::Error| e.to_string())?;
+
+    let new_id = conn.last_insert_rowid();
+
+    for m in &definition.mods {
+        conn.execute(
+            "INSERT OR REPLACE INTO mods (server_id, mod_id, name, version, author, description,
+             workshop_url, enabled, load_order)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            rusqlite::params![
+                new_id,
+                m.mod_id,
+                m.name,
+                m.version,
+                m.author,
+                m.description,
+                m.workshop_url,
+                m.enabled,
+                m.load_order,
+            ],
+        )
+        .map_err(|e: rusqlite::Error| e.to_string())?;
+    }
+
+    Ok((new_id, name, ports))
+}
+
+/// Recreate a server from an export_server bundle.
+/// A new id is generated, the name gets a numeric suffix if taken and ports move to
+/// the next free range on conflict. Bundled INI files are only written where none exist.
+/// The bundle comes from another machine, so the install path must be given and
+/// auto-start is left off; `admin_password` is required when the bundle has none.
+#[tauri::command]
+pub async fn import_server_definition(
+    state: State<'_, AppState>,
+    json: String,
+    install_path: String,
+    admin_password: Option<String>,
+) -> Result<Server, String> {
+    let mut definition: ServerDefinition =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid server definition: {}", e))?;
+
+    if definition.version > SERVER_DEFINITION_VERSION {
+        return Err(format!(
+            "Server definition version {} is newer than supported version {}. Please update the app.",
+            definition.version, SERVER_DEFINITION_VERSION
+        ));
+    }
+
+    println!("📥 Importing server definition '{}'", definition.name);
+
+    let install_path = install_path.trim().to_string();
+    if install_path.is_empty() {
+        return Err("Choose where the imported server is installed on this machine".to_string());
+    }
+    definition.auto_start = false;
+    if let Some(password) = admin_password.filter(|p| !p.trim().is_empty()) {
+        definition.admin_password = password;
+    }
+    if definition.admin_password.trim().is_empty() {
+        return Err("This export has no admin password; enter one to import it".to_string());
+    }
+
+    let (new_id, name, ports) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        insert_server_definition(&conn, &definition, &install_path)?
+    };
+
+    if !definition.configs.is_empty() {
+        let config_dir =
+            PathBuf::from(&install_path).join("ShooterGame/Saved/Config/WindowsServer");
+        std::fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+        for (file, content) in &definition.configs {
+            // Only known INI names, never paths from the bundle
+            if file != "GameUserSettings.ini" && file != "Game.ini" {
+                continue;
+            }
+            let path = config_dir.join(file);
+            if !path.exists() {
+                IniParser::write_atomic(&path, content)
+                    .map_err(|e| format!("Failed to write {}: {}", file, e))?;
+            }
+        }
+    }
+
+    println!("  ✅ Imported '{}' as server {}", name, new_id);
+
+    Ok(Server {
+        id: new_id,
+        name,
+        install_path: PathBuf::from(&install_path),
+        status: ServerStatus::Stopped,
+        ports,
+        config: ServerConfig {
+            max_players: definition.max_players,
+            server_password: definition.server_password,
+            admin_password: definition.admin_password.clone(),
+            map_name: definition.map_name,
+            session_name: definition.session_name,
+            motd: definition.motd,
+            mods: definition.mods.iter().map(|m| m.mod_id.clone()).collect(),
+            custom_args: definition.custom_args,
+            crossplay_platforms: definition.crossplay_platforms,
+        },
+        rcon_config: RconConfig {
+            enabled: definition.rcon_enabled,
+            password: definition.admin_password,
+        },
+        ip_address: definition.ip_address,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        last_started: None,
+        auto_start: definition.auto_start,
+        auto_stop: definition.auto_stop,
+        intelligent_mode: definition.intelligent_mode,
+        auto_port_forward: definition.auto_port_forward,
+        use_asa_api: definition.use_asa_api,
+        backup_before_restart: false,
+        battleye_enabled: definition.battleye_enabled,
+    })
+}
+
+/// Transfer settings (INI files) from one server to another
+#[tauri::command]
+pub async fn transfer_settings(
+    state: State<'_, AppState>,
+    source_server_id: i64,
+    target_server_id: i64,
+) -> Result<(), String> {
+    println!(
+        "📋 Transferring settings from server {} to {}",
+        source_server_id, target_server_id
+    );
+
+    // Get both server paths
+    let (source_path, target_path) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let source: String = conn
+            .query_row(
+                "SELECT install_path FROM servers WHERE id = ?1",
+                [source_server_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Source server not found: {}", e))?;
+
+        let target: String = conn
+            .query_row(
+                "SELECT install_path FROM servers WHERE id = ?1",
+                [target_server_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Target server not found: {}", e))?;
+
+        (PathBuf::from(source), PathBuf::from(target))
+    };
+
+    // Copy config files
+    let source_config = source_path.join("ShooterGame/Saved/Config/WindowsServer");
+    let target_config = target_path.join("ShooterGame/Saved/Config/WindowsServer");
+
+    if !source_config.exists() {
+        return Err("Source server has no config files".to_string());
+    }
+
+    std::fs::create_dir_all(&target_config)
+        .map_err(|e| format!("Failed to create target config dir: {}", e))?;
+
+    for file in ["GameUserSettings.ini", "Game.ini"] {
+        let src = source_config.join(file);
+        let dst = target_config.join(file);
+        if src.exists() {
+            std::fs::copy(&src, &dst).map_err(|e| format!("Failed to copy {}: {}", file, e))?;
+            println!("  ✅ Copied {}", file);
+        }
+    }
+
+    println!("  ✅ Settings transferred successfully");
+    Ok(())
+}
+
+/// Extract save data (world/player) from one server to another
+#[tauri::command]
+pub async fn extract_save_data(
+    state: State<'_, AppState>,
+    source_server_id: i64,
+    target_server_id: i64,
+) -> Result<(), String> {
+    println!(
+        "📦 Extracting save data from server {} to {}",
+        source_server_id, target_server_id
+    );
+
+    // Get both server paths
+    let (source_path, target_path) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let source: String = conn
+            .query_row(
+                "SELECT install_path FROM servers WHERE id = ?1",
+                [source_server_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Source server not found: {}", e))?;
+
+        let target: String = conn
             .query_row(
                 "SELECT install_path FROM servers WHERE id = ?1",
                 [target_server_id],
@@ -482,35 +1294,310 @@ pub async fn extract_save_data(
         .map_err(|e| format!("Failed to create target saves dir: {}", e))?;
 
     // Copy all files recursively
-    fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result<()> {
-        if src.is_dir() {
-            std::fs::create_dir_all(dst)?;
-            for entry in std::fs::read_dir(src)? {
-                let entry = entry?;
-                let src_path = entry.path();
-                let dst_path = dst.join(entry.file_name());
-                if src_path.is_dir() {
-                    copy_dir_recursive(&src_path, &dst_path)?;
+    crate::commands::file_manager::copy_path_recursive(
+        &source_saves,
+        &target_saves,
+        true,
+        &mut |_, _| {},
+    )
+    .map_err(|e| format!("Failed to copy save data: {}", e))?;
+
+    println!("  ✅ Save data extracted successfully");
+    Ok(())
+}
+
+/// Free space below which preflight_check flags the install drive. Updates and save
+/// backups need room to grow.
+const PREFLIGHT_MIN_FREE_GB: u64 = 10;
+
+/// One line of a preflight checklist. Failed "error" items will stop the server from
+/// starting; failed "warning" items are recoverable (e.g. ARK downloads missing mods).
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightItem {
+    pub id: String,
+    pub label: String,
+    pub passed: bool,
+    pub severity: String,
+    pub detail: Option<String>,
+}
+
+impl PreflightItem {
+    fn new(id: &str, label: &str, passed: bool, severity: &str, detail: Option<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            passed,
+            severity: severity.to_string(),
+            detail,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightReport {
+    pub server_id: i64,
+    /// No failed error items
+    pub ready: bool,
+    pub items: Vec<PreflightItem>,
+}
+
+/// Run every pre-launch check at once so the UI can show a checklist instead of the
+/// first error start_server happens to hit
+#[tauri::command]
+pub async fn preflight_check(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<PreflightReport, String> {
+    println!("🩺 Running preflight checks for server {}", server_id);
+
+    let (name, install_path, status, ports, admin_password, rcon_enabled, registered, enabled_mods) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        let (name, install_path, status, ports, admin_password, rcon_enabled) = conn
+            .query_row(
+                "SELECT name, install_path, status, game_port, query_port, rcon_port,
+                 admin_password, rcon_enabled FROM servers WHERE id = ?1",
+                [server_id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        ServerPorts {
+                            game_port: row.get(3)?,
+                            query_port: row.get(4)?,
+                            rcon_port: row.get(5)?,
+                        },
+                        row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+                        row.get::<_, Option<bool>>(7)?.unwrap_or(true),
+                    ))
+                },
+            )
+            .map_err(|e| format!("Server not found: {}", e))?;
+        let registered = get_registered_ports(&conn, Some(server_id))?;
+        let enabled_mods = enabled_mod_ids(&conn, server_id)?;
+        (
+            name,
+            install_path,
+            status,
+            ports,
+            admin_password,
+            rcon_enabled,
+            registered,
+            enabled_mods,
+        )
+    };
+    let install_path_buf = PathBuf::from(&install_path);
+    let mut items = Vec::new();
+
+    // Executable
+    let executable = install_path_buf
+        .join("ShooterGame")
+        .join("Binaries")
+        .join("Win64")
+        .join("ArkAscendedServer.exe");
+    items.push(if executable.is_file() {
+        PreflightItem::new(
+            "executable",
+            "Server executable present",
+            true,
+            "warning",
+            None,
+        )
+    } else {
+        PreflightItem::new(
+            "executable",
+            "Server executable present",
+            false,
+            "warning",
+            Some("ArkAscendedServer.exe not found; it will be downloaded on start".to_string()),
+        )
+    });
+
+    // Ports. A running server holds its own ports, so only other registered servers count.
+    let running = matches!(
+        ServerStatus::from_db(&status),
+        ServerStatus::Running | ServerStatus::Starting | ServerStatus::Online
+    );
+    let conflicts = if running {
+        let port_list = [ports.game_port, ports.query_port, ports.rcon_port];
+        registered
+            .iter()
+            .filter(|(port, _)| port_list.contains(port))
+            .map(|(port, owner)| format!("Port {} is used by server '{}'.", port, owner))
+            .collect()
+    } else {
+        find_port_conflicts(&ports, &registered)
+    };
+    items.push(PreflightItem::new(
+        "ports",
+        "Ports free",
+        conflicts.is_empty(),
+        "error",
+        (!conflicts.is_empty()).then(|| conflicts.join(" ")),
+    ));
+
+    // Config files
+    let config_dir = install_path_buf
+        .join("ShooterGame")
+        .join("Saved")
+        .join("Config")
+        .join("WindowsServer");
+    for file in ["GameUserSettings", "Game"] {
+        let path = config_dir.join(format!("{}.ini", file));
+        let label = format!("{}.ini present and readable", file);
+        let item = match std::fs::read_to_string(&path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => PreflightItem::new(
+                "config",
+                &label,
+                false,
+                "warning",
+                Some("Not created yet; ARK writes defaults on first start".to_string()),
+            ),
+            Err(e) => PreflightItem::new(
+                "config",
+                &label,
+                false,
+                "error",
+                Some(format!("Failed to read: {}", e)),
+            ),
+            Ok(content) => {
+                let has_section = IniParser::parse(&content)
+                    .sections()
+                    .iter()
+                    .any(|s| s != GLOBAL_SECTION);
+                if has_section || content.trim().is_empty() {
+                    PreflightItem::new("config", &label, true, "error", None)
                 } else {
-                    std::fs::copy(&src_path, &dst_path)?;
+                    PreflightItem::new(
+                        "config",
+                        &label,
+                        false,
+                        "error",
+                        Some("No [Section] headers found; the file is not valid INI".to_string()),
+                    )
                 }
             }
-        }
-        Ok(())
+        };
+        items.push(item);
+    }
+
+    // Enabled mods on disk
+    if !enabled_mods.is_empty() {
+        let integrity = crate::commands::mods::verify_mod_integrity(state.clone(), server_id)
+            .await
+            .unwrap_or_default();
+        let enabled: Vec<_> = integrity
+            .iter()
+            .filter(|m| enabled_mods.contains(&m.mod_id))
+            .collect();
+        let describe = |status: &str| -> Vec<String> {
+            enabled
+                .iter()
+                .filter(|m| m.status == status)
+                .map(|m| format!("{} ({})", m.mod_name, m.mod_id))
+                .collect()
+        };
+        let corrupted = describe("corrupted");
+        let missing = describe("missing");
+        items.push(PreflightItem::new(
+            "mods_corrupted",
+            "Enabled mods intact",
+            corrupted.is_empty(),
+            "error",
+            (!corrupted.is_empty())
+                .then(|| format!("Partially downloaded: {}", corrupted.join(", "))),
+        ));
+        items.push(PreflightItem::new(
+            "mods_missing",
+            "Enabled mods downloaded",
+            missing.is_empty(),
+            "warning",
+            (!missing.is_empty()).then(|| {
+                format!(
+                    "Will download on first start, expect a delay: {}",
+                    missing.join(", ")
+                )
+            }),
+        ));
     }
 
-    copy_dir_recursive(&source_saves, &target_saves)
-        .map_err(|e| format!("Failed to copy save data: {}", e))?;
+    // Disk space on the drive holding the install
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let free = disks
+        .list()
+        .iter()
+        .filter(|d| install_path_buf.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| d.available_space());
+    items.push(match free {
+        Some(bytes) => {
+            let free_gb = bytes / (1024 * 1024 * 1024);
+            PreflightItem::new(
+                "disk",
+                "Enough free disk space",
+                free_gb >= PREFLIGHT_MIN_FREE_GB,
+                "warning",
+                Some(format!("{} GB free", free_gb)),
+            )
+        }
+        None => PreflightItem::new(
+            "disk",
+            "Enough free disk space",
+            false,
+            "warning",
+            Some("Could not determine the drive of the install path".to_string()),
+        ),
+    });
 
-    println!("  ✅ Save data extracted successfully");
-    Ok(())
+    // RCON password
+    let default_password = admin_password.is_empty() || admin_password == DEFAULT_ADMIN_PASSWORD;
+    items.push(PreflightItem::new(
+        "rcon_password",
+        "Admin/RCON password changed from default",
+        !rcon_enabled || !default_password,
+        "warning",
+        (rcon_enabled && default_password).then(|| {
+            "Anyone who guesses the default password gets admin and RCON access".to_string()
+        }),
+    ));
+
+    let ready = items.iter().all(|i| i.passed || i.severity != "error");
+    let failed = items.iter().filter(|i| !i.passed).count();
+    println!(
+        "  {} Preflight for '{}': {} of {} checks passed",
+        if ready { "✅" } else { "❌" },
+        name,
+        items.len() - failed,
+        items.len()
+    );
+
+    Ok(PreflightReport {
+        server_id,
+        ready,
+        items,
+    })
 }
 
 #[tauri::command]
-pub async fn start_server(app_handle: tauri::AppHandle, server_id: i64) -> Result<(), String> {
+pub async fn start_server(app_handle: tauri::AppHandle, server_id: i64) -> CommandResult<()> {
     let state = app_handle.state::<AppState>();
     println!("▶️ Starting server {}", server_id);
 
+    ensure_install_present(&state, server_id)?;
+
+    // Warn about values that differ between the DB and the INI before they get pulled in
+    if let Ok(report) = crate::commands::config::detect_config_drift(&state, server_id) {
+        for m in &report.mismatches {
+            println!(
+                "⚠️ Config drift on server {}: {} differs between DB and INI, using INI value",
+                server_id, m.field
+            );
+        }
+    }
+
     // Sync critical settings from INI to DB before starting
     // This ensures that if the user manually edited INI files, the changes are respected
     // and passed correctly to the command line arguments
@@ -527,103 +1614,16 @@ pub async fn start_server(app_handle: tauri::AppHandle, server_id: i64) -> Resul
     )
     .await;
 
-    // Get server details including cluster info
-    let (
-        install_path,
-        map_name,
-        session_name,
-        game_port,
-        query_port,
-        rcon_port,
-        max_players,
-        server_password,
-        admin_password,
-        ip_address,
-        _cluster_id,
-        cluster_name,
-        cluster_path,
-        custom_args,
-    ): (
-        String,
-        String,
-        String,
-        u16,
-        u16,
-        u16,
-        i32,
-        Option<String>,
-        String,
-        Option<String>,
-        Option<i64>,
-        Option<String>,
-        Option<String>,
-        Option<String>,
-    ) = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-
-        conn.query_row(
-            "SELECT s.install_path, s.map_name, s.session_name, s.game_port, s.query_port, s.rcon_port, 
-             s.max_players, s.server_password, s.admin_password, s.ip_address, s.cluster_id,
-             c.name, c.cluster_path, s.custom_args
-             FROM servers s
-             LEFT JOIN clusters c ON s.cluster_id = c.id
-             WHERE s.id = ?1",
-            [server_id],
-            |row: &Row| {
-                Ok((
-                    row.get::<usize, String>(0)?,
-                    row.get::<usize, String>(1)?,
-                    row.get::<usize, String>(2)?,
-                    row.get::<usize, u16>(3)?,
-                    row.get::<usize, u16>(4)?,
-                    row.get::<usize, u16>(5)?,
-                    row.get::<usize, i32>(6)?,
-                    row.get::<usize, Option<String>>(7)?,
-                    row.get::<usize, String>(8)?,
-                    row.get::<usize, Option<String>>(9)?,
-                    row.get::<usize, Option<i64>>(10)?,
-                    row.get::<usize, Option<String>>(11)?,
-                    row.get::<usize, Option<String>>(12)?,
-                    row.get::<usize, Option<String>>(13)?,
-                ))
-            },
-        )
-        .map_err(|e| format!("Server not found: {}", e))?
-    };
+    // Get server details including cluster info and enabled mods
+    let launch = server_launch(&state, server_id).map_err(ManagerError::not_found)?;
+    let enabled_mods = &launch.mods;
 
-    // Get enabled mods for this server
-    let enabled_mods: Vec<String> = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-
-        let mut stmt = conn.prepare(
-            "SELECT mod_id FROM mods WHERE server_id = ?1 AND enabled = 1 ORDER BY load_order ASC"
-        ).map_err(|e: rusqlite::Error| e.to_string())?;
-
-        let mut rows = stmt
-            .query([server_id])
-            .map_err(|e: rusqlite::Error| e.to_string())?;
-        let mut mods = Vec::new();
-        while let Some(row) = rows.next().map_err(|e: rusqlite::Error| e.to_string())? {
-            if let Ok(mod_id) = row.get::<usize, String>(0) {
-                mods.push(mod_id);
-            }
-        }
-        mods
-    };
+    // Keep the INI's ActiveMods in line with the -mods we're about to launch with
+    if let Err(e) = crate::commands::mods::sync_mods_to_ini(&state, server_id).await {
+        println!("  ⚠️ Failed to sync ActiveMods to INI: {}", e);
+    }
 
-    let install_path_buf = PathBuf::from(&install_path);
+    let install_path_buf = &launch.install_path;
 
     // Pass all enabled mods to the server - ARK/CFCore will download any missing mods automatically
     if !enabled_mods.is_empty() {
@@ -632,7 +1632,7 @@ pub async fn start_server(app_handle: tauri::AppHandle, server_id: i64) -> Resul
             enabled_mods.len(),
             server_id
         );
-        for mod_id in &enabled_mods {
+        for mod_id in enabled_mods {
             println!("     - Mod: {}", mod_id);
         }
     }
@@ -650,72 +1650,43 @@ pub async fn start_server(app_handle: tauri::AppHandle, server_id: i64) -> Resul
 
         // Update status to 'updating' to show download progress
         {
-            let db = state
-                .db
-                .lock()
-                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            let conn = db
-                .get_connection()
-                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            conn.execute(
-                "UPDATE servers SET status = 'updating' WHERE id = ?1",
-                [server_id],
-            )
-            .map_err(|e: rusqlite::Error| e.to_string())?;
+            let db = &state.db;
+            let conn = db.get_connection().map_err(ManagerError::db)?;
+            server_state::set_status(&conn, server_id, ServerStatus::Updating)?;
         }
 
         // Run the installation via SteamCMD
         let installer = ServerInstaller::new(app_handle.clone());
-        installer.install_asa_server(&install_path_buf).await?;
+        if let Err(e) = installer.install_asa_server(install_path_buf).await {
+            let db = &state.db;
+            let conn = db.get_connection().map_err(ManagerError::db)?;
+            server_state::set_status(&conn, server_id, ServerStatus::Stopped)?;
+            return Err(ManagerError::Steam(e));
+        }
 
         println!("  ✅ Server download complete, now starting...");
     }
 
     // Start the server process with all enabled mods (ARK will download missing ones)
-    let mods_option = if enabled_mods.is_empty() {
-        None
-    } else {
-        Some(enabled_mods.as_slice())
-    };
-
     state
         .process_manager
         .start_server(
             server_id,
-            "ASA",
-            &install_path_buf,
-            &map_name,
-            &session_name,
-            game_port,
-            query_port,
-            rcon_port,
-            max_players,
-            server_password.as_deref() as Option<&str>,
-            &admin_password,
-            ip_address.as_deref() as Option<&str>,
-            cluster_name.as_deref() as Option<&str>,
-            cluster_path.as_deref() as Option<&str>,
-            mods_option,
-            custom_args.as_deref() as Option<&str>,
+            &launch.install_path,
+            launch.use_asa_api,
+            &launch.options(),
         )
         .map_err(|e: AnyhowError| e.to_string())?;
 
     // Update status in database
     {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        conn.execute(
-            "UPDATE servers SET status = 'running', last_started = datetime('now') WHERE id = ?1",
-            [server_id],
-        )
-        .map_err(|e: rusqlite::Error| e.to_string())?;
+        let db = &state.db;
+        let conn = db.get_connection().map_err(ManagerError::db)?;
+        server_state::set_status(&conn, server_id, ServerStatus::Running)?;
     }
 
+    spawn_auto_port_forward(app_handle.clone(), server_id, true);
+
     println!("  ✅ Server {} started", server_id);
     Ok(())
 }
@@ -726,78 +1697,13 @@ pub async fn start_server_no_mods(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
     server_id: i64,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     println!("▶️ Starting server {} (NO MODS MODE)", server_id);
 
     // Get server details including cluster info
-    let (
-        install_path,
-        map_name,
-        session_name,
-        game_port,
-        query_port,
-        rcon_port,
-        max_players,
-        server_password,
-        admin_password,
-        ip_address,
-        cluster_name,
-        cluster_path,
-        custom_args,
-    ): (
-        String,
-        String,
-        String,
-        u16,
-        u16,
-        u16,
-        i32,
-        Option<String>,
-        String,
-        Option<String>,
-        Option<String>,
-        Option<String>,
-        Option<String>,
-    ) = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-
-        // Join with clusters table to get cluster details if assigned
-        conn.query_row(
-            "SELECT s.install_path, s.map_name, s.session_name, s.game_port, s.query_port, s.rcon_port, 
-             s.max_players, s.server_password, s.admin_password, s.ip_address,
-             c.name, c.cluster_path, s.custom_args
-             FROM servers s
-             LEFT JOIN clusters c ON s.cluster_id = c.id
-             WHERE s.id = ?1",
-            [server_id],
-            |row: &Row| {
-                Ok((
-                    row.get::<usize, String>(0)?,
-                    row.get::<usize, String>(1)?,
-                    row.get::<usize, String>(2)?,
-                    row.get::<usize, u16>(3)?,
-                    row.get::<usize, u16>(4)?,
-                    row.get::<usize, u16>(5)?,
-                    row.get::<usize, i32>(6)?,
-                    row.get::<usize, Option<String>>(7)?,
-                    row.get::<usize, String>(8)?,
-                    row.get::<usize, Option<String>>(9)?,
-                    row.get::<usize, Option<String>>(10)?,
-                    row.get::<usize, Option<String>>(11)?,
-                    row.get::<usize, Option<String>>(12)?,
-                ))
-            },
-        )
-        .map_err(|e| format!("Server not found: {}", e))?
-    };
-
-    let install_path_buf = PathBuf::from(&install_path);
+    let mut launch = server_launch(&state, server_id).map_err(ManagerError::not_found)?;
+    launch.mods.clear();
+    let install_path_buf = &launch.install_path;
 
     // Check if server executable exists
     let executable = install_path_buf
@@ -810,23 +1716,19 @@ pub async fn start_server_no_mods(
         println!("  📥 Server executable not found, starting automatic download...");
         // Send a temporary "updating" status so UI shows something happening
         {
-            let db = state
-                .db
-                .lock()
-                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            let conn = db
-                .get_connection()
-                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            conn.execute(
-                "UPDATE servers SET status = 'updating' WHERE id = ?1",
-                [server_id],
-            )
-            .map_err(|e: rusqlite::Error| e.to_string())?;
+            let db = &state.db;
+            let conn = db.get_connection().map_err(ManagerError::db)?;
+            server_state::set_status(&conn, server_id, ServerStatus::Updating)?;
         }
 
         // Run the installation via SteamCMD
         let installer = ServerInstaller::new(app_handle.clone());
-        installer.install_asa_server(&install_path_buf).await?;
+        if let Err(e) = installer.install_asa_server(install_path_buf).await {
+            let db = &state.db;
+            let conn = db.get_connection().map_err(ManagerError::db)?;
+            server_state::set_status(&conn, server_id, ServerStatus::Stopped)?;
+            return Err(ManagerError::Steam(e));
+        }
 
         println!("  ✅ Server download complete, now starting...");
     }
@@ -836,153 +1738,60 @@ pub async fn start_server_no_mods(
         .process_manager
         .start_server(
             server_id,
-            "ASA",
-            &install_path_buf,
-            &map_name,
-            &session_name,
-            game_port,
-            query_port,
-            rcon_port,
-            max_players,
-            server_password.as_deref() as Option<&str>,
-            &admin_password,
-            ip_address.as_deref() as Option<&str>,
-            cluster_name.as_deref() as Option<&str>,
-            cluster_path.as_deref() as Option<&str>,
-            None, // No mods
-            custom_args.as_deref() as Option<&str>,
+            &launch.install_path,
+            launch.use_asa_api,
+            &launch.options(),
         )
         .map_err(|e: AnyhowError| e.to_string())?;
 
     // Update status in database
     {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        conn.execute(
-            "UPDATE servers SET status = 'running', last_started = datetime('now') WHERE id = ?1",
-            [server_id],
-        )
-        .map_err(|e: rusqlite::Error| e.to_string())?;
+        let db = &state.db;
+        let conn = db.get_connection().map_err(ManagerError::db)?;
+        server_state::set_status(&conn, server_id, ServerStatus::Running)?;
     }
 
+    spawn_auto_port_forward(app_handle.clone(), server_id, true);
+
     println!("  ✅ Server {} started (NO MODS)", server_id);
     Ok(())
 }
 
 #[tauri::command]
-pub async fn stop_server(state: State<'_, AppState>, server_id: i64) -> Result<(), String> {
+pub async fn stop_server(state: State<'_, AppState>, server_id: i64) -> CommandResult<()> {
     println!("⏹️ Stopping server {}", server_id);
 
-    state
-        .process_manager
-        .stop_server(server_id)
-        .map_err(|e: AnyhowError| e.to_string())?;
+    let previous = server_state::begin_stop(&state, server_id);
+    if let Err(e) = state.process_manager.stop_server(server_id).await {
+        if let Some(previous) = previous {
+            server_state::abort_stop(&state, server_id, previous);
+        }
+        return Err(e.into());
+    }
 
     // Update status in database
-    let db = state
-        .db
-        .lock()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-    let conn = db
-        .get_connection()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-    conn.execute(
-        "UPDATE servers SET status = 'stopped' WHERE id = ?1",
-        [server_id],
-    )
-    .map_err(|e: rusqlite::Error| e.to_string())?;
+    let db = &state.db;
+    let conn = db.get_connection().map_err(ManagerError::db)?;
+    server_state::set_status(&conn, server_id, ServerStatus::Stopped)?;
+
+    spawn_auto_port_forward(state.app_handle.clone(), server_id, false);
+    notifications::notify(
+        &state.app_handle,
+        server_id,
+        NotificationEvent::ServerOffline,
+    );
 
     println!("  ✅ Server {} stopped", server_id);
     Ok(())
 }
 
 #[tauri::command]
-pub async fn restart_server(state: State<'_, AppState>, server_id: i64) -> Result<(), String> {
+pub async fn restart_server(state: State<'_, AppState>, server_id: i64) -> CommandResult<()> {
     println!("🔄 Restarting server {}", server_id);
 
-    // Get server details including cluster info
-    let (
-        install_path,
-        map_name,
-        session_name,
-        game_port,
-        query_port,
-        rcon_port,
-        max_players,
-        server_password,
-        admin_password,
-        ip_address,
-        cluster_name,
-        cluster_path,
-        custom_args,
-    ) = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-
-        conn.query_row(
-            "SELECT s.install_path, s.map_name, s.session_name, s.game_port, s.query_port, s.rcon_port, 
-             s.max_players, s.server_password, s.admin_password, s.ip_address,
-             c.name, c.cluster_path, s.custom_args
-             FROM servers s
-             LEFT JOIN clusters c ON s.cluster_id = c.id
-             WHERE s.id = ?1",
-            [server_id],
-            |row: &Row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, String>(2)?,
-                    row.get::<_, u16>(3)?,
-                    row.get::<_, u16>(4)?,
-                    row.get::<_, u16>(5)?,
-                    row.get::<_, i32>(6)?,
-                    row.get::<_, Option<String>>(7)?,
-                    row.get::<_, String>(8)?,
-                    row.get::<_, Option<String>>(9)?,
-                    row.get::<_, Option<String>>(10)?,
-                    row.get::<_, Option<String>>(11)?,
-                    row.get::<_, Option<String>>(12)?,
-                ))
-            },
-        )
-        .map_err(|e| format!("Server not found: {}", e))?
-    };
-
-    // Get enabled mods for this server
-    let enabled_mods: Vec<String> = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-
-        let mut stmt = conn.prepare(
-            "SELECT mod_id FROM mods WHERE server_id = ?1 AND enabled = 1 ORDER BY load_order ASC"
-        ).map_err(|e: rusqlite::Error| e.to_string())?;
-
-        let mut rows = stmt
-            .query([server_id])
-            .map_err(|e: rusqlite::Error| e.to_string())?;
-        let mut mods = Vec::new();
-        while let Some(row) = rows.next().map_err(|e: rusqlite::Error| e.to_string())? {
-            if let Ok(mod_id) = row.get::<usize, String>(0) {
-                mods.push(mod_id);
-            }
-        }
-        mods
-    };
+    // Get server details including cluster info and enabled mods
+    let launch = server_launch(&state, server_id).map_err(ManagerError::not_found)?;
+    let enabled_mods = &launch.mods;
 
     if !enabled_mods.is_empty() {
         println!(
@@ -992,49 +1801,49 @@ pub async fn restart_server(state: State<'_, AppState>, server_id: i64) -> Resul
         );
     }
 
-    // Restart the server with mods
-    let mods_option = if enabled_mods.is_empty() {
-        None
-    } else {
-        Some(enabled_mods.as_slice())
-    };
+    if let Err(e) = crate::commands::mods::sync_mods_to_ini(&state, server_id).await {
+        println!("  ⚠️ Failed to sync ActiveMods to INI: {}", e);
+    }
 
-    state
+    // Safety backup of the pre-restart world; a failed backup is reported but doesn't block the restart
+    if server_backs_up_before_restart(&state, server_id)
+        && state.process_manager.is_running(server_id)
+    {
+        if let Err(e) =
+            crate::commands::backup::backup_unless_recent(&state, server_id, BackupType::PreRestart)
+                .await
+        {
+            println!("  ⚠️ Pre-restart backup failed: {}", e);
+        }
+    }
+
+    // Restart the server with mods
+    server_state::update(&state, server_id, ServerStatus::Restarting);
+    let restarted = state
         .process_manager
         .restart_server(
             server_id,
-            "ASA",
-            &PathBuf::from(&install_path),
-            &map_name,
-            &session_name,
-            game_port,
-            query_port,
-            rcon_port,
-            max_players,
-            server_password.as_deref() as Option<&str>,
-            &admin_password,
-            ip_address.as_deref() as Option<&str>,
-            cluster_name.as_deref() as Option<&str>,
-            cluster_path.as_deref() as Option<&str>,
-            mods_option,
-            custom_args.as_deref() as Option<&str>,
+            &launch.install_path,
+            launch.use_asa_api,
+            &launch.options(),
         )
-        .map_err(|e: AnyhowError| e.to_string())?;
+        .await;
+    if let Err(e) = restarted {
+        // Whatever is left of the process decides the status
+        let status = if state.process_manager.is_running(server_id) {
+            ServerStatus::Running
+        } else {
+            ServerStatus::Stopped
+        };
+        server_state::update(&state, server_id, status);
+        return Err(e.into());
+    }
 
     // Update status
     {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        conn.execute(
-            "UPDATE servers SET status = 'running', last_started = datetime('now') WHERE id = ?1",
-            [server_id],
-        )
-        .map_err(|e: rusqlite::Error| e.to_string())?;
+        let db = &state.db;
+        let conn = db.get_connection().map_err(ManagerError::db)?;
+        server_state::set_status(&conn, server_id, ServerStatus::Running)?;
     }
 
     println!("  ✅ Server {} restarted", server_id);
@@ -1042,19 +1851,14 @@ pub async fn restart_server(state: State<'_, AppState>, server_id: i64) -> Resul
 }
 
 #[tauri::command]
-pub async fn delete_server(state: State<'_, AppState>, server_id: i64) -> Result<(), String> {
+pub async fn delete_server(state: State<'_, AppState>, server_id: i64) -> CommandResult<()> {
     println!("🗑️ Deleting server {}", server_id);
 
-    let db = state
-        .db
-        .lock()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-    let conn = db
-        .get_connection()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+    let db = &state.db;
+    let conn = db.get_connection().map_err(ManagerError::db)?;
 
     conn.execute("DELETE FROM servers WHERE id = ?1", [server_id])
-        .map_err(|e: rusqlite::Error| e.to_string())?;
+        .map_err(ManagerError::db)?;
 
     println!("  ✅ Server {} deleted", server_id);
     Ok(())
@@ -1078,13 +1882,8 @@ pub async fn update_server_settings(
 ) -> Result<(), String> {
     println!("⚙️ Updating server settings for server {}", server_id);
 
-    let db = state
-        .db
-        .lock()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-    let conn = db
-        .get_connection()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     // Build dynamic update query
     let mut updates = Vec::new();
@@ -1146,112 +1945,986 @@ pub async fn update_server_settings(
     Ok(())
 }
 
+/// Stop a running SteamCMD install or update, given the server or (for a server
+/// still being installed) its install path. Returns false if nothing was running.
+#[tauri::command]
+pub async fn cancel_install(
+    state: State<'_, AppState>,
+    server_id: Option<i64>,
+    install_path: Option<String>,
+) -> Result<bool, String> {
+    let path = match (server_id, install_path) {
+        (_, Some(path)) => PathBuf::from(path),
+        (Some(id), None) => {
+            let db = &state.db;
+            let conn = db.get_connection().map_err(|e| e.to_string())?;
+            conn.query_row(
+                "SELECT install_path FROM servers WHERE id = ?1",
+                [id],
+                |row| row.get::<_, String>(0),
+            )
+            .map(PathBuf::from)
+            .map_err(|e| format!("Server not found: {}", e))?
+        }
+        (None, None) => return Err("Either server_id or install_path is required".to_string()),
+    };
+
+    println!("🛑 Cancelling install at {}", path.display());
+    Ok(crate::services::server_installer::cancel_install(&path))
+}
+
 #[tauri::command]
 pub async fn update_server(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
     server_id: i64,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     println!("📥 Updating server {}", server_id);
 
     // Get server install path
     let install_path = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+        let db = &state.db;
+        let conn = db.get_connection().map_err(ManagerError::db)?;
 
         conn.query_row(
             "SELECT install_path FROM servers WHERE id = ?1",
             [server_id],
             |row| row.get::<_, String>(0),
         )
-        .map_err(|e| format!("Server not found: {}", e))?
+        .map_err(|e| ManagerError::not_found(format!("Server not found: {}", e)))?
     };
 
+    let installer = ServerInstaller::new(app_handle);
+
+    // Snapshot the current install first; an update that breaks saves or mods can be rolled back
+    if settings::BACKUP_BEFORE_UPDATE.load(&state) {
+        installer.emit_progress("backup", 0.0, "Backing up before update...");
+        crate::commands::backup::backup_unless_recent(&state, server_id, BackupType::PreUpdate)
+            .await
+            .map_err(|e| format!("Pre-update backup failed, update cancelled: {}", e))?;
+    }
+
     // Update status to updating
     {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        conn.execute(
-            "UPDATE servers SET status = 'updating' WHERE id = ?1",
-            [server_id],
-        )
-        .map_err(|e: rusqlite::Error| e.to_string())?;
+        let db = &state.db;
+        let conn = db.get_connection().map_err(ManagerError::db)?;
+        server_state::set_status(&conn, server_id, ServerStatus::Updating)?;
     }
 
-    // Run the update
-    let installer = ServerInstaller::new(app_handle);
-    installer
-        .update_server(&PathBuf::from(&install_path))
-        .await?;
+    // Run the update; a cancelled or failed run leaves the server stopped, not stuck updating
+    if let Err(e) = installer.update_server(&PathBuf::from(&install_path)).await {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(ManagerError::db)?;
+        server_state::set_status(&conn, server_id, ServerStatus::Stopped)?;
+        return Err(ManagerError::Steam(e));
+    }
 
     // Update status back to stopped
     {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        conn.execute(
-            "UPDATE servers SET status = 'stopped' WHERE id = ?1",
+        let db = &state.db;
+        let conn = db.get_connection().map_err(ManagerError::db)?;
+        server_state::set_status(&conn, server_id, ServerStatus::Stopped)?;
+    }
+
+    notifications::notify(
+        &state.app_handle,
+        server_id,
+        NotificationEvent::ServerUpdated,
+    );
+
+    println!("  ✅ Server {} updated", server_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn check_server_reachability(
+    state: State<'_, AppState>,
+    server_id: i64,
+    port: u16,
+) -> Result<String, String> {
+    // 1. Get Public IP
+    let public_ip = match network::get_public_ip().await {
+        Ok(ip) => ip,
+        Err(_) => return Ok("LAN".to_string()), // If we can't get public IP, assume LAN or Offline
+    };
+
+    // 2. Always update IP in database if we found one
+    // This allows the UI to show the correct Public IP even if the port is currently closed (e.g. server updating)
+    {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let _ = conn.execute(
+            "UPDATE servers SET ip_address = ?1 WHERE id = ?2",
+            [&public_ip, &server_id.to_string()],
+        );
+    }
+
+    // 3. Check if port is open on that IP
+    // Note: This checks if the port is reachable from "externally" (or at least hairpinned)
+    let is_open = network::check_port_open(&public_ip, port);
+
+    if is_open {
+        Ok("Public".to_string())
+    } else {
+        Ok("LAN".to_string())
+    }
+}
+
+/// Load the name, ports and auto_port_forward flag used for UPnP mappings
+fn get_upnp_target(
+    state: &State<'_, AppState>,
+    server_id: i64,
+) -> Result<(String, u16, u16, u16, bool), String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        "SELECT name, game_port, query_port, rcon_port, auto_port_forward FROM servers WHERE id = ?1",
+        [server_id],
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u16>(1)?,
+                row.get::<_, u16>(2)?,
+                row.get::<_, u16>(3)?,
+                row.get::<_, Option<i32>>(4)?.unwrap_or(0) != 0,
+            ))
+        },
+    )
+    .map_err(|e| format!("Server not found: {}", e))
+}
+
+/// Forward the server's game/query/rcon ports on the router via UPnP
+#[tauri::command]
+pub async fn open_ports_upnp(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Vec<String>, String> {
+    let (name, game_port, query_port, rcon_port, _) = get_upnp_target(&state, server_id)?;
+    println!("🌐 UPnP: Opening ports for server {}", server_id);
+
+    let opened = network::upnp_open_ports(
+        game_port,
+        query_port,
+        rcon_port,
+        &format!("ASA Server Manager - {}", name),
+    )
+    .await?;
+
+    println!("  ✅ UPnP: Forwarded {} ports", opened.len());
+    Ok(opened)
+}
+
+/// Remove the server's UPnP port mappings from the router
+#[tauri::command]
+pub async fn close_ports_upnp(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Vec<String>, String> {
+    let (_, game_port, query_port, rcon_port, _) = get_upnp_target(&state, server_id)?;
+    println!("🌐 UPnP: Closing ports for server {}", server_id);
+
+    network::upnp_close_ports(game_port, query_port, rcon_port).await
+}
+
+/// Whether a server takes a backup before each restart (backup_before_restart)
+fn server_backs_up_before_restart(state: &AppState, server_id: i64) -> bool {
+    let db = &state.db;
+    let Ok(conn) = db.get_connection() else {
+        return false;
+    };
+    conn.query_row(
+        "SELECT COALESCE(backup_before_restart, 0) FROM servers WHERE id = ?1",
+        [server_id],
+        |row: &Row| row.get::<_, i32>(0),
+    )
+    .map(|v| v != 0)
+    .unwrap_or(false)
+}
+
+/// Launch flag keys switched on for a server (see launch_args::LAUNCH_FLAGS)
+pub fn server_launch_flags(state: &AppState, server_id: i64) -> Vec<String> {
+    let db = &state.db;
+    let Ok(conn) = db.get_connection() else {
+        return Vec::new();
+    };
+    conn.query_row(
+        "SELECT launch_flags FROM servers WHERE id = ?1",
+        [server_id],
+        |row: &Row| row.get::<_, Option<String>>(0),
+    )
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchFlagInfo {
+    pub key: String,
+    pub flag: String,
+    pub label: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchPresetInfo {
+    pub name: String,
+    pub flags: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchFlagCatalog {
+    pub flags: Vec<LaunchFlagInfo>,
+    pub presets: Vec<LaunchPresetInfo>,
+}
+
+/// Every toggleable launch flag and preset, for the server settings UI
+#[tauri::command]
+pub async fn get_launch_flag_catalog() -> Result<LaunchFlagCatalog, String> {
+    Ok(LaunchFlagCatalog {
+        flags: launch_args::LAUNCH_FLAGS
+            .iter()
+            .map(|f| LaunchFlagInfo {
+                key: f.key.to_string(),
+                flag: f.flag.to_string(),
+                label: f.label.to_string(),
+                description: f.description.to_string(),
+            })
+            .collect(),
+        presets: launch_args::LAUNCH_PRESETS
+            .iter()
+            .map(|(name, flags)| LaunchPresetInfo {
+                name: name.to_string(),
+                flags: flags.iter().map(|f| f.to_string()).collect(),
+            })
+            .collect(),
+    })
+}
+
+#[tauri::command]
+pub async fn get_server_launch_flags(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Vec<String>, String> {
+    Ok(server_launch_flags(&state, server_id))
+}
+
+/// Replace the launch flags a server starts with; applies on next start
+#[tauri::command]
+pub async fn set_server_launch_flags(
+    state: State<'_, AppState>,
+    server_id: i64,
+    flags: Vec<String>,
+) -> Result<(), String> {
+    let mut keys: Vec<String> = Vec::new();
+    for key in flags {
+        if launch_args::launch_flag(&key).is_none() {
+            return Err(format!("Unknown launch flag: {}", key));
+        }
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    println!(
+        "⚙️ Setting launch flags for server {}: {:?}",
+        server_id, keys
+    );
+
+    let json = serde_json::to_string(&keys).map_err(|e| e.to_string())?;
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE servers SET launch_flags = ?1 WHERE id = ?2",
+        rusqlite::params![json, server_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Switch on every flag in a preset, keeping the server's other flags; returns the new set
+#[tauri::command]
+pub async fn apply_launch_preset(
+    state: State<'_, AppState>,
+    server_id: i64,
+    preset: String,
+) -> Result<Vec<String>, String> {
+    let (_, preset_flags) = launch_args::LAUNCH_PRESETS
+        .iter()
+        .find(|(name, _)| *name == preset)
+        .ok_or_else(|| format!("Unknown launch preset: {}", preset))?;
+
+    let mut flags = server_launch_flags(&state, server_id);
+    for key in preset_flags.iter() {
+        if !flags.iter().any(|f| f == key) {
+            flags.push(key.to_string());
+        }
+    }
+    set_server_launch_flags(state, server_id, flags.clone()).await?;
+    Ok(flags)
+}
+
+/// Everything a server is launched with: its row, cluster, enabled mods and
+/// launch settings, read in one place for every start path
+pub struct ServerLaunch {
+    pub install_path: PathBuf,
+    /// Launch through the AsaApi loader instead of ArkAscendedServer.exe
+    pub use_asa_api: bool,
+    pub map_name: String,
+    pub session_name: String,
+    pub game_port: u16,
+    pub query_port: u16,
+    pub rcon_port: u16,
+    pub max_players: i32,
+    pub server_password: Option<String>,
+    pub admin_password: String,
+    pub ip_address: Option<String>,
+    pub cluster_id: Option<String>,
+    pub cluster_dir: Option<String>,
+    pub custom_args: Option<String>,
+    pub battleye: bool,
+    pub launch_flags: Vec<String>,
+    pub platforms: Vec<String>,
+    pub mod_strategy: String,
+    /// Enabled mods in load order; clear it to launch without mods
+    pub mods: Vec<String>,
+}
+
+impl ServerLaunch {
+    pub fn load(conn: &rusqlite::Connection, server_id: i64) -> Result<Self, String> {
+        let mut launch = conn
+            .query_row(
+                "SELECT s.install_path, COALESCE(s.use_asa_api, 0), s.map_name, s.session_name,
+                 s.game_port, s.query_port, s.rcon_port, s.max_players, s.server_password,
+                 s.admin_password, s.ip_address, c.name, c.cluster_path, s.custom_args,
+                 COALESCE(s.battleye_enabled, 0), s.launch_flags, s.crossplay_platforms,
+                 s.mod_strategy
+                 FROM servers s
+                 LEFT JOIN clusters c ON s.cluster_id = c.id
+                 WHERE s.id = ?1",
+                [server_id],
+                |row: &Row| {
+                    Ok(ServerLaunch {
+                        install_path: PathBuf::from(row.get::<_, String>(0)?),
+                        use_asa_api: row.get::<_, i32>(1)? != 0,
+                        map_name: row.get(2)?,
+                        session_name: row.get(3)?,
+                        game_port: row.get(4)?,
+                        query_port: row.get(5)?,
+                        rcon_port: row.get(6)?,
+                        max_players: row.get(7)?,
+                        server_password: row.get(8)?,
+                        admin_password: row.get(9)?,
+                        ip_address: row.get(10)?,
+                        cluster_id: row.get(11)?,
+                        cluster_dir: row.get(12)?,
+                        custom_args: row.get(13)?,
+                        battleye: row.get::<_, i32>(14)? != 0,
+                        launch_flags: json_list(row.get(15)?),
+                        platforms: json_list(row.get(16)?),
+                        mod_strategy: row
+                            .get::<_, Option<String>>(17)?
+                            .filter(|key| launch_args::mod_strategy(key).is_some())
+                            .unwrap_or_else(|| launch_args::DEFAULT_MOD_STRATEGY.to_string()),
+                        mods: Vec::new(),
+                    })
+                },
+            )
+            .map_err(|e| format!("Server not found: {}", e))?;
+        launch.mods = enabled_mod_ids(conn, server_id)?;
+        Ok(launch)
+    }
+
+    pub fn options(&self) -> launch_args::LaunchOptions<'_> {
+        launch_args::LaunchOptions {
+            map_name: &self.map_name,
+            session_name: &self.session_name,
+            game_port: self.game_port,
+            query_port: self.query_port,
+            rcon_port: self.rcon_port,
+            max_players: self.max_players,
+            server_password: self.server_password.as_deref(),
+            admin_password: &self.admin_password,
+            ip_address: self.ip_address.as_deref(),
+            cluster_id: self.cluster_id.as_deref(),
+            cluster_dir: self.cluster_dir.as_deref(),
+            mods: (!self.mods.is_empty()).then_some(self.mods.as_slice()),
+            custom_args: self.custom_args.as_deref(),
+            battleye: self.battleye,
+            launch_flags: &self.launch_flags,
+            platforms: &self.platforms,
+            mod_strategy: &self.mod_strategy,
+        }
+    }
+}
+
+/// Load a server's ServerLaunch through the shared database
+pub fn server_launch(state: &AppState, server_id: i64) -> Result<ServerLaunch, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    ServerLaunch::load(&conn, server_id)
+}
+
+/// The command line start_server would use for a server right now, built from
+/// the database without syncing the INI first
+pub fn server_launch_args(state: &AppState, server_id: i64) -> Result<Vec<String>, String> {
+    let launch = server_launch(state, server_id)?;
+    Ok(launch_args::build_launch_args(&launch.options()))
+}
+
+/// Crossplay platforms a server launches for (see launch_args::SERVER_PLATFORMS)
+pub fn server_platforms(state: &AppState, server_id: i64) -> Vec<String> {
+    let db = &state.db;
+    let Ok(conn) = db.get_connection() else {
+        return Vec::new();
+    };
+    conn.query_row(
+        "SELECT crossplay_platforms FROM servers WHERE id = ?1",
+        [server_id],
+        |row: &Row| row.get::<_, Option<String>>(0),
+    )
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+/// Set which platforms can join a server; an empty list drops -ServerPlatform.
+/// Applies on next start. Returns the normalised list that was saved.
+#[tauri::command]
+pub async fn set_crossplay_platforms(
+    state: State<'_, AppState>,
+    server_id: i64,
+    platforms: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let platforms = launch_args::validate_platforms(&platforms)?;
+    println!(
+        "🎮 Setting crossplay platforms for server {}: {:?}",
+        server_id, platforms
+    );
+
+    let json = if platforms.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&platforms).map_err(|e| e.to_string())?)
+    };
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE servers SET crossplay_platforms = ?1 WHERE id = ?2",
+        rusqlite::params![json, server_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(platforms)
+}
+
+/// Mod management strategy a server launches with (see launch_args::MOD_STRATEGIES)
+pub fn server_mod_strategy(state: &AppState, server_id: i64) -> String {
+    let stored = state.db.get_connection().ok().and_then(|conn| {
+        conn.query_row(
+            "SELECT mod_strategy FROM servers WHERE id = ?1",
             [server_id],
+            |row: &Row| row.get::<_, Option<String>>(0),
         )
-        .map_err(|e: rusqlite::Error| e.to_string())?;
+        .ok()
+        .flatten()
+    });
+    stored
+        .filter(|key| launch_args::mod_strategy(key).is_some())
+        .unwrap_or_else(|| launch_args::DEFAULT_MOD_STRATEGY.to_string())
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModStrategyInfo {
+    pub key: String,
+    pub flag: Option<String>,
+    pub label: String,
+    pub description: String,
+}
+
+/// Every mod management strategy with its tradeoffs, for the server settings UI
+#[tauri::command]
+pub async fn get_mod_strategies() -> Result<Vec<ModStrategyInfo>, String> {
+    Ok(launch_args::MOD_STRATEGIES
+        .iter()
+        .map(|s| ModStrategyInfo {
+            key: s.key.to_string(),
+            flag: s.flag.map(str::to_string),
+            label: s.label.to_string(),
+            description: s.description.to_string(),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_mod_strategy(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<String, String> {
+    Ok(server_mod_strategy(&state, server_id))
+}
+
+/// Choose how a server gets its mods; applies on next start
+#[tauri::command]
+pub async fn set_mod_strategy(
+    state: State<'_, AppState>,
+    server_id: i64,
+    strategy: String,
+) -> Result<(), String> {
+    if launch_args::mod_strategy(&strategy).is_none() {
+        return Err(format!("Unknown mod strategy: {}", strategy));
     }
+    println!(
+        "🧩 Setting mod strategy for server {}: {}",
+        server_id, strategy
+    );
 
-    println!("  ✅ Server {} updated", server_id);
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE servers SET mod_strategy = ?1 WHERE id = ?2",
+        rusqlite::params![strategy, server_id],
+    )
+    .map_err(|e| e.to_string())?;
     Ok(())
 }
 
-#[tauri::command]
-pub async fn check_server_reachability(
-    state: State<'_, AppState>,
-    server_id: i64,
-    port: u16,
-) -> Result<String, String> {
-    // 1. Get Public IP
-    let public_ip = match network::get_public_ip().await {
-        Ok(ip) => ip,
-        Err(_) => return Ok("LAN".to_string()), // If we can't get public IP, assume LAN or Offline
-    };
+/// Open or close UPnP mappings in the background if auto_port_forward is enabled.
+/// Failures (e.g. no UPnP gateway) are reported to the console and never fail the caller.
+fn spawn_auto_port_forward(app_handle: tauri::AppHandle, server_id: i64, open: bool) {
+    use crate::services::process_manager::ServerLogEvent;
+    use tauri::Emitter;
+
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let enabled = matches!(get_upnp_target(&state, server_id), Ok((_, _, _, _, true)));
+        if !enabled {
+            return;
+        }
+
+        let result = if open {
+            open_ports_upnp(state.clone(), server_id).await
+        } else {
+            close_ports_upnp(state.clone(), server_id).await
+        };
+
+        let is_stderr = result.is_err();
+        let line = match result {
+            Ok(ports) if open => format!("[Manager] UPnP: Forwarded {}", ports.join(", ")),
+            Ok(ports) => format!("[Manager] UPnP: Removed {}", ports.join(", ")),
+            Err(e) => format!("[Manager] UPnP: {}", e),
+        };
+        println!("  🌐 {}", line);
+
+        let _ = app_handle.emit(
+            "server_log",
+            ServerLogEvent {
+                server_id,
+                line,
+                is_stderr,
+            },
+        );
+    });
+}
+
+/// Default gap between servers in a bulk action, same as start_cluster
+const BULK_STAGGER_SECS: u64 = 5;
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkActionResult {
+    pub server_id: i64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkActionProgress {
+    pub action: String,
+    pub server_id: i64,
+    /// 1-based position in the batch
+    pub index: usize,
+    pub total: usize,
+    /// "running", "done" or "failed"
+    pub stage: String,
+    pub error: Option<String>,
+}
+
+/// Start, stop, restart or update several servers one after another, pausing
+/// `stagger_seconds` (default 5) between them so the host isn't hit all at once.
+/// `tag` selects every server with that tag in addition to `server_ids`.
+/// A failure on one server doesn't stop the rest; progress goes out as "bulk-action-progress".
+#[tauri::command]
+pub async fn bulk_server_action(
+    app_handle: tauri::AppHandle,
+    server_ids: Vec<i64>,
+    action: String,
+    stagger_seconds: Option<u64>,
+    tag: Option<String>,
+) -> Result<Vec<BulkActionResult>, String> {
+    use tauri::Emitter;
+
+    if !matches!(action.as_str(), "start" | "stop" | "restart" | "update") {
+        return Err(format!("Unknown bulk action: {}", action));
+    }
+
+    // Servers carrying `tag` are added to the explicit ids
+    let mut server_ids = server_ids;
+    if let Some(tag) = tag {
+        let tagged = get_servers_by_tag(app_handle.state(), tag).await?;
+        for server in tagged {
+            if !server_ids.contains(&server.id) {
+                server_ids.push(server.id);
+            }
+        }
+    }
+    let stagger = std::time::Duration::from_secs(stagger_seconds.unwrap_or(BULK_STAGGER_SECS));
+    let total = server_ids.len();
+    println!("📦 Bulk {} for {} servers", action, total);
+
+    let emit = |server_id: i64, index: usize, stage: &str, error: Option<String>| {
+        let _ = app_handle.emit(
+            "bulk-action-progress",
+            BulkActionProgress {
+                action: action.clone(),
+                server_id,
+                index,
+                total,
+                stage: stage.to_string(),
+                error,
+            },
+        );
+    };
+
+    let mut results = Vec::with_capacity(total);
+    for (i, &server_id) in server_ids.iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(stagger).await;
+        }
+
+        emit(server_id, i + 1, "running", None);
+        let outcome = run_bulk_action(&app_handle, server_id, &action).await;
+        match &outcome {
+            Ok(()) => emit(server_id, i + 1, "done", None),
+            Err(e) => {
+                println!(
+                    "  ⚠️ Bulk {} failed for server {}: {}",
+                    action, server_id, e
+                );
+                emit(server_id, i + 1, "failed", Some(e.clone()));
+            }
+        }
+        results.push(BulkActionResult {
+            server_id,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+
+    println!(
+        "  ✅ Bulk {} finished: {}/{} succeeded",
+        action,
+        results.iter().filter(|r| r.success).count(),
+        total
+    );
+    Ok(results)
+}
+
+async fn run_bulk_action(
+    app_handle: &tauri::AppHandle,
+    server_id: i64,
+    action: &str,
+) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    match action {
+        "start" => start_server(app_handle.clone(), server_id)
+            .await
+            .map_err(String::from),
+        "stop" => stop_server(state, server_id).await.map_err(String::from),
+        "restart" => restart_server(state, server_id).await.map_err(String::from),
+        "update" => {
+            // Same sequence as a scheduled update, minus the player warning
+            let was_running = state.process_manager.is_running(server_id);
+            crate::services::scheduler::stop_and_update(app_handle, server_id, was_running).await
+        }
+        _ => Err(format!("Unknown bulk action: {}", action)),
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopAllResult {
+    pub results: Vec<BulkActionResult>,
+    /// Servers whose auto-stop watcher was switched off. Watchers come back when
+    /// automation is toggled again or on the next launch of the manager.
+    pub unwatched_server_ids: Vec<i64>,
+}
+
+/// Emergency stop for every server the process manager is running, all at once.
+/// File watchers are dropped first so auto-stop handlers can't race the shutdown.
+/// `force` skips the close request and kills the process trees immediately.
+/// The outcome is also emitted as "stop-all-complete".
+#[tauri::command]
+pub async fn stop_all_servers(
+    state: State<'_, AppState>,
+    force: bool,
+) -> CommandResult<StopAllResult> {
+    use tauri::Emitter;
+
+    let unwatched_server_ids = state.file_watcher.stop_all();
+    let server_ids = state.process_manager.running_server_ids();
+    println!(
+        "🛑 Emergency stop of {} server(s){}",
+        server_ids.len(),
+        if force { " (force)" } else { "" }
+    );
+
+    let tasks: Vec<_> = server_ids
+        .iter()
+        .map(|&server_id| {
+            let app_handle = state.app_handle.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                let state = app_handle.state::<AppState>();
+                let previous = server_state::begin_stop(&state, server_id);
+                let stopped = if force {
+                    state.process_manager.kill_server(server_id)
+                } else {
+                    state.process_manager.stop_server_blocking(server_id)
+                };
+                match stopped {
+                    Ok(()) => {
+                        server_state::update(&state, server_id, ServerStatus::Stopped);
+                        Ok(())
+                    }
+                    Err(e) => {
+                        if let Some(previous) = previous {
+                            server_state::abort_stop(&state, server_id, previous);
+                        }
+                        Err(e.to_string())
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for (&server_id, task) in server_ids.iter().zip(tasks) {
+        let outcome = task.await.map_err(|e| e.to_string()).and_then(|r| r);
+        match &outcome {
+            Ok(()) => {
+                spawn_auto_port_forward(state.app_handle.clone(), server_id, false);
+                notifications::notify(
+                    &state.app_handle,
+                    server_id,
+                    NotificationEvent::ServerOffline,
+                );
+            }
+            Err(e) => println!("  ⚠️ Failed to stop server {}: {}", server_id, e),
+        }
+        results.push(BulkActionResult {
+            server_id,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+
+    println!(
+        "  ✅ Emergency stop finished: {}/{} stopped",
+        results.iter().filter(|r| r.success).count(),
+        results.len()
+    );
+    let result = StopAllResult {
+        results,
+        unwatched_server_ids,
+    };
+    let _ = state.app_handle.emit("stop-all-complete", &result);
+    Ok(result)
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceProgress {
+    pub server_id: i64,
+    /// "countdown", "saving", "stopping", "stopped" or "failed"
+    pub stage: String,
+    pub message: String,
+    pub remaining_seconds: u64,
+}
+
+/// Broadcast points (seconds remaining) for a maintenance countdown
+fn maintenance_checkpoints(total_secs: u64) -> Vec<u64> {
+    let mut points: Vec<u64> = [60, 30, 15, 10, 5, 4, 3, 2, 1]
+        .iter()
+        .map(|m| m * 60)
+        .chain([30, 10])
+        .filter(|s| *s <= total_secs)
+        .collect();
+    points.push(total_secs);
+    points.sort_unstable_by(|a, b| b.cmp(a));
+    points.dedup();
+    points
+}
+
+fn format_remaining(secs: u64) -> String {
+    match secs {
+        s if s >= 120 => format!("{} minutes", s / 60),
+        s if s >= 60 => "1 minute".to_string(),
+        s => format!("{} seconds", s),
+    }
+}
+
+/// Broadcast a countdown over RCON, save the world a minute before the end,
+/// then shut the server down gracefully. Runs in the background and reports
+/// through "maintenance-progress" events.
+#[tauri::command]
+pub async fn begin_maintenance(
+    state: State<'_, AppState>,
+    rcon_state: State<'_, crate::commands::rcon::RconState>,
+    server_id: i64,
+    countdown_minutes: u32,
+    message: Option<String>,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    if !state.process_manager.is_running(server_id) {
+        return Err("Server is not running".to_string());
+    }
+
+    let (rcon_port, admin_password, ip_address, rcon_enabled): (u16, String, Option<String>, bool) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        conn.query_row(
+            "SELECT rcon_port, admin_password, ip_address, COALESCE(rcon_enabled, 1) FROM servers WHERE id = ?1",
+            [server_id],
+            |row: &Row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get::<_, i32>(3)? != 0)),
+        )
+        .map_err(|e: rusqlite::Error| format!("Server not found: {}", e))?
+    };
+
+    if !rcon_enabled {
+        return Err("RCON is disabled for this server".to_string());
+    }
+
+    let address = ip_address
+        .filter(|ip| !ip.is_empty() && ip != "0.0.0.0")
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+
+    // Make sure RCON works before committing to a countdown
+    {
+        let rcon = &rcon_state.0;
+        if !rcon.is_connected(server_id).await {
+            rcon.connect(server_id, &address, rcon_port, &admin_password)
+                .await?;
+        }
+    }
+
+    let message = message
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| "Server maintenance.".to_string());
+    let total_secs = countdown_minutes as u64 * 60;
+
+    println!(
+        "🛠️ Beginning maintenance for server {} ({} minute countdown)",
+        server_id, countdown_minutes
+    );
+
+    let app_handle = state.app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let rcon_state = app_handle.state::<crate::commands::rcon::RconState>();
+        let emit = |stage: &str, message: String, remaining_seconds: u64| {
+            let _ = app_handle.emit(
+                "maintenance-progress",
+                MaintenanceProgress {
+                    server_id,
+                    stage: stage.to_string(),
+                    message,
+                    remaining_seconds,
+                },
+            );
+        };
+
+        let started = std::time::Instant::now();
+        let mut saved = false;
+
+        for remaining in maintenance_checkpoints(total_secs) {
+            let due = std::time::Duration::from_secs(total_secs - remaining);
+            if let Some(wait) = due.checked_sub(started.elapsed()) {
+                tokio::time::sleep(wait).await;
+            }
+
+            if !state.process_manager.is_running(server_id) {
+                emit(
+                    "failed",
+                    "Server stopped during countdown".to_string(),
+                    remaining,
+                );
+                return;
+            }
+
+            if remaining <= 60 && !saved {
+                emit("saving", "Saving world".to_string(), remaining);
+                if let Err(e) = rcon_state.0.save_world(server_id).await {
+                    println!("  ⚠️ Maintenance save failed: {}", e);
+                }
+                saved = true;
+            }
+
+            let broadcast = format!(
+                "{} Server shutting down in {}.",
+                message,
+                format_remaining(remaining)
+            );
+            if let Err(e) = rcon_state.0.broadcast(server_id, &broadcast).await {
+                println!("  ⚠️ Maintenance broadcast failed: {}", e);
+            }
+            emit("countdown", broadcast, remaining);
+        }
+
+        if let Some(wait) =
+            std::time::Duration::from_secs(total_secs).checked_sub(started.elapsed())
+        {
+            tokio::time::sleep(wait).await;
+        }
+
+        emit("stopping", "Shutting down server".to_string(), 0);
+        let previous = server_state::begin_stop(&state, server_id);
+        let result = {
+            let rcon = &rcon_state.0;
+            state
+                .process_manager
+                .shutdown_server(server_id, rcon, &address, rcon_port, &admin_password)
+                .await
+        };
 
-    // 2. Always update IP in database if we found one
-    // This allows the UI to show the correct Public IP even if the port is currently closed (e.g. server updating)
-    {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        let conn = db
-            .get_connection()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+        if let Err(e) = result {
+            if let Some(previous) = previous.filter(|_| state.process_manager.is_running(server_id))
+            {
+                server_state::abort_stop(&state, server_id, previous);
+            }
+            emit("failed", format!("Shutdown failed: {}", e), 0);
+            return;
+        }
 
-        let _ = conn.execute(
-            "UPDATE servers SET ip_address = ?1 WHERE id = ?2",
-            [&public_ip, &server_id.to_string()],
-        );
-    }
+        server_state::update(&state, server_id, ServerStatus::Stopped);
+        spawn_auto_port_forward(app_handle.clone(), server_id, false);
+        notifications::notify(&app_handle, server_id, NotificationEvent::ServerOffline);
 
-    // 3. Check if port is open on that IP
-    // Note: This checks if the port is reachable from "externally" (or at least hairpinned)
-    let is_open = network::check_port_open(&public_ip, port);
+        println!(
+            "  ✅ Maintenance shutdown complete for server {}",
+            server_id
+        );
+        emit("stopped", "Server stopped for maintenance".to_string(), 0);
+    });
 
-    if is_open {
-        Ok("Public".to_string())
-    } else {
-        Ok("LAN".to_string())
-    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -1260,6 +2933,7 @@ pub async fn start_log_watcher(
     install_path: String,
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
+    use crate::services::log_parser;
     use crate::services::process_manager::ServerLogEvent;
     use std::fs::File;
     use std::io::{BufRead, BufReader, Seek, SeekFrom};
@@ -1339,6 +3013,7 @@ pub async fn start_log_watcher(
         }
 
         // Second pass: tail new lines as they appear
+        // (structured events only for live lines, history would replay old joins)
         loop {
             let mut line = String::new();
             match reader.read_line(&mut line) {
@@ -1348,6 +3023,7 @@ pub async fn start_log_watcher(
                 Ok(_) => {
                     let line = line.trim_end().to_string();
                     if !line.is_empty() {
+                        log_parser::emit_parsed(&app_handle, server_id, &line);
                         let _ = app_handle.emit(
                             "server_log",
                             ServerLogEvent {
@@ -1368,6 +3044,420 @@ pub async fn start_log_watcher(
     Ok(())
 }
 
+/// Settings recovered from an existing install's GameUserSettings.ini
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedSettings {
+    pub max_players: i32,
+    pub map_name: String,
+    pub session_name: String,
+    pub server_password: Option<String>,
+    pub admin_password: String,
+    pub game_port: u16,
+    pub query_port: u16,
+    pub rcon_port: u16,
+    pub rcon_enabled: bool,
+}
+
+/// Parse GameUserSettings.ini under `path` and detect the map from the world save or
+/// leftover launch scripts, falling back to defaults for anything missing
+fn read_import_settings(path: &std::path::Path, name: &str) -> ImportedSettings {
+    let config_path = path
+        .join("ShooterGame")
+        .join("Saved")
+        .join("Config")
+        .join("WindowsServer")
+        .join("GameUserSettings.ini");
+
+    let mut settings = ImportedSettings {
+        max_players: 70,
+        map_name: "TheIsland_WP".to_string(),
+        session_name: name.to_string(),
+        server_password: None,
+        admin_password: DEFAULT_ADMIN_PASSWORD.to_string(),
+        game_port: 7777,
+        query_port: 27015,
+        rcon_port: 27020,
+        rcon_enabled: true,
+    };
+
+    let content = std::fs::read_to_string(&config_path).unwrap_or_default();
+
+    let mut current_section = String::new();
+    for line in content.lines() {
+        let line = line.trim();
+
+        // Section header
+        if line.starts_with('[') && line.ends_with(']') {
+            current_section = line[1..line.len() - 1].to_string();
+            continue;
+        }
+
+        // Key=Value pair
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            let value = value.trim();
+
+            if current_section == "ServerSettings"
+                || current_section == "/Script/ShooterGame.ShooterGameMode"
+            {
+                match key {
+                    "MaxPlayers" => settings.max_players = value.parse().unwrap_or(70),
+                    "ServerPassword" if !value.is_empty() => {
+                        settings.server_password = Some(value.to_string())
+                    }
+                    "ServerAdminPassword" if !value.is_empty() => {
+                        settings.admin_password = value.to_string()
+                    }
+                    "SessionName" if !value.is_empty() => settings.session_name = value.to_string(),
+                    "RCONEnabled" => settings.rcon_enabled = value.to_lowercase() == "true",
+                    "RCONPort" => settings.rcon_port = value.parse().unwrap_or(27020),
+                    "MapName" if !value.is_empty() => settings.map_name = value.to_string(),
+                    _ => {}
+                }
+            }
+
+            if current_section == "URL" || current_section == "/Script/Engine.GameSession" {
+                match key {
+                    "Port" => settings.game_port = value.parse().unwrap_or(7777),
+                    "QueryPort" => settings.query_port = value.parse().unwrap_or(27015),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // The map is a launch argument, not an INI key. ARK names the world save after
+    // the map it's running, so an existing save is the most reliable witness, then a
+    // launch script that started this install, then whatever GameUserSettings.ini says
+    if let Some(map) = detect_map_from_saves(path).or_else(|| detect_map_from_launch_scripts(path))
+    {
+        settings.map_name = map;
+    }
+
+    settings
+}
+
+/// Map of the most recently written world save under SavedArks. ASA keeps each map in
+/// its own folder (SavedArks/<Map>/<Map>.ark); older layouts put the .ark directly in
+/// SavedArks. Timestamped backups (<Map>_dd.mm.yyyy_hh.mm.ss.ark) are skipped.
+fn detect_map_from_saves(path: &std::path::Path) -> Option<String> {
+    let saved_arks = path.join("ShooterGame").join("Saved").join("SavedArks");
+
+    let (map, _) = walkdir::WalkDir::new(&saved_arks)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let path = e.path();
+            let is_ark = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("ark"));
+            let stem = path.file_stem()?.to_string_lossy().to_string();
+            if !is_ark || stem.contains('.') {
+                return None;
+            }
+            // In the per-map layout the world file is named after its folder
+            if e.depth() == 2 && path.parent()?.file_name()? != stem.as_str() {
+                return None;
+            }
+            let modified = e.metadata().ok()?.modified().ok()?;
+            Some((stem, modified))
+        })
+        .max_by_key(|(_, modified)| *modified)?;
+
+    println!("   🗺️  Map {} found in SavedArks", map);
+    Some(map)
+}
+
+/// Look through batch/PowerShell scripts left next to an install for the map the
+/// server was launched with (e.g. `ArkAscendedServer.exe Ragnarok_WP?listen`)
+fn detect_map_from_launch_scripts(path: &std::path::Path) -> Option<String> {
+    let pattern =
+        regex::Regex::new(r#"(?i)ArkAscendedServer(?:\.exe)?"?\s+"?([A-Za-z0-9_]+)\?"#).ok()?;
+
+    let script_dirs = [
+        path.to_path_buf(),
+        path.join("ShooterGame").join("Binaries").join("Win64"),
+    ];
+    for dir in &script_dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut scripts: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| matches!(e.to_lowercase().as_str(), "bat" | "cmd" | "ps1"))
+            })
+            .collect();
+        scripts.sort();
+
+        for script in scripts {
+            let Ok(content) = std::fs::read_to_string(&script) else {
+                continue;
+            };
+            let found = content
+                .lines()
+                .filter(|line| {
+                    let line = line.trim_start().to_lowercase();
+                    !line.starts_with("rem ") && !line.starts_with("::") && !line.starts_with('#')
+                })
+                .find_map(|line| pattern.captures(line).map(|c| c[1].to_string()));
+            if let Some(map) = found {
+                println!("   🗺️  Map {} found in launch script {:?}", map, script);
+                return Some(map);
+            }
+        }
+    }
+
+    None
+}
+
+/// How deep scan_for_servers descends below the chosen root
+const SCAN_MAX_DEPTH: usize = 5;
+
+/// Folders that never contain a server install and are expensive to walk.
+/// `steamapps` is walked: SteamCMD installs land in `steamapps/common/...`.
+const SCAN_SKIP_DIRS: &[&str] = &[
+    "windows",
+    "$recycle.bin",
+    "system volume information",
+    "programdata",
+    "appdata",
+    "node_modules",
+];
+
+/// An existing install found by scan_for_servers
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCandidate {
+    pub install_path: String,
+    pub has_executable: bool,
+    pub has_config: bool,
+    /// Already registered in the database; importing it again would fail
+    pub already_registered: bool,
+    pub settings: ImportedSettings,
+}
+
+fn is_server_install(dir: &std::path::Path) -> (bool, bool) {
+    let shooter_game = dir.join("ShooterGame");
+    let has_executable = shooter_game
+        .join("Binaries")
+        .join("Win64")
+        .join("ArkAscendedServer.exe")
+        .is_file();
+    let has_config = shooter_game.join("Saved").join("Config").is_dir();
+    (has_executable, has_config)
+}
+
+fn scan_dir(dir: &std::path::Path, depth: usize, found: &mut Vec<(PathBuf, bool, bool)>) {
+    let (has_executable, has_config) = is_server_install(dir);
+    if has_executable || has_config {
+        // An install's own subfolders never hold another install
+        found.push((dir.to_path_buf(), has_executable, has_config));
+        return;
+    }
+    if depth >= SCAN_MAX_DEPTH {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        // Don't follow symlinks/junctions, they can loop
+        if !file_type.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        if name.starts_with('.') || SCAN_SKIP_DIRS.contains(&name.as_str()) {
+            continue;
+        }
+        scan_dir(&entry.path(), depth + 1, found);
+    }
+}
+
+/// Fail early (and flag the server as missing) when its install folder is gone,
+/// instead of erroring somewhere deep in config sync or SteamCMD
+fn ensure_install_present(state: &AppState, server_id: i64) -> CommandResult<()> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(ManagerError::db)?;
+    let install_path: String = conn
+        .query_row(
+            "SELECT install_path FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| ManagerError::not_found(format!("Server not found: {}", e)))?;
+
+    if PathBuf::from(&install_path).is_dir() {
+        // A folder that went missing and came back (drive reconnected) is usable again
+        if let Err(e) = server_state::clear_missing(&conn, server_id) {
+            log::warn!(
+                "⚠️ Could not clear missing flag on server {}: {}",
+                server_id,
+                e
+            );
+        }
+        return Ok(());
+    }
+    // The relocate hint matters more than the flag; don't let a failed write replace it
+    if let Err(e) = server_state::set_status(&conn, server_id, ServerStatus::Missing) {
+        log::warn!("⚠️ Could not flag server {} as missing: {}", server_id, e);
+    }
+    Err(ManagerError::Io(format!(
+        "Install folder not found: {}. If the server was moved, relocate it to the new folder.",
+        install_path
+    )))
+}
+
+/// Point a server at its install folder's new location after it was moved.
+/// Updates the DB, the cluster override in its INI and any file watcher.
+#[tauri::command]
+pub async fn relocate_server(
+    state: State<'_, AppState>,
+    server_id: i64,
+    new_install_path: String,
+) -> Result<(), String> {
+    println!("📦 Relocating server {} to {}", server_id, new_install_path);
+
+    let new_path = PathBuf::from(&new_install_path);
+    if !new_path.is_dir() {
+        return Err(format!("Not a folder: {}", new_install_path));
+    }
+    let (has_executable, has_config) = is_server_install(&new_path);
+    if !has_executable && !has_config {
+        return Err(format!(
+            "{} doesn't look like an ARK server install (no ShooterGame folder with a server executable or config)",
+            new_install_path
+        ));
+    }
+    if state.process_manager.is_running(server_id) {
+        return Err("Stop the server before relocating it".to_string());
+    }
+
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let taken: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM servers WHERE install_path = ?1 AND id != ?2)",
+            rusqlite::params![&new_install_path, server_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if taken {
+        return Err("Another server is already registered at this path".to_string());
+    }
+
+    let changed = conn
+        .execute(
+            "UPDATE servers SET install_path = ?1 WHERE id = ?2",
+            rusqlite::params![&new_install_path, server_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if changed == 0 {
+        return Err(format!("Server {} not found", server_id));
+    }
+    server_state::set_status(&conn, server_id, ServerStatus::Stopped)?;
+
+    // The cluster dir override lives in the install's own GameUserSettings.ini
+    let cluster_path: Option<String> = conn
+        .query_row(
+            "SELECT c.cluster_path FROM clusters c
+             JOIN servers s ON s.cluster_id = c.id OR c.id IN
+                (SELECT cluster_id FROM cluster_servers WHERE server_id = s.id)
+             WHERE s.id = ?1 LIMIT 1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .ok();
+    if let Some(cluster_path) = cluster_path {
+        crate::commands::cluster::update_cluster_config(&new_install_path, &cluster_path);
+    }
+
+    let watched: bool = conn
+        .query_row(
+            "SELECT auto_stop = 1 OR intelligent_mode = 1 FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .unwrap_or(false);
+    if watched {
+        let triggers = file_watcher::load_triggers(&conn, server_id);
+        state.file_watcher.stop_watching(server_id);
+        state
+            .file_watcher
+            .start_watching(server_id, new_path, triggers)?;
+    }
+
+    println!("✅ Server {} relocated", server_id);
+    Ok(())
+}
+
+/// Look for existing ASA server installs under `root_path` so they can be imported in one go
+#[tauri::command]
+pub async fn scan_for_servers(
+    state: State<'_, AppState>,
+    root_path: String,
+) -> Result<Vec<ServerCandidate>, String> {
+    let root = PathBuf::from(&root_path);
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {}", root_path));
+    }
+    println!("🔍 Scanning for server installs under {}", root_path);
+
+    let found = tokio::task::spawn_blocking(move || {
+        let mut found = Vec::new();
+        scan_dir(&root, 0, &mut found);
+        found
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let registered: Vec<String> = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare("SELECT install_path FROM servers")
+            .map_err(|e| e.to_string())?;
+        let paths = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .filter_map(|r| r.ok())
+            .collect();
+        paths
+    };
+
+    let candidates: Vec<ServerCandidate> = found
+        .into_iter()
+        .map(|(path, has_executable, has_config)| {
+            let install_path = path.to_string_lossy().to_string();
+            let default_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Imported Server".to_string());
+            ServerCandidate {
+                already_registered: registered.iter().any(|p| PathBuf::from(p) == path),
+                settings: read_import_settings(&path, &default_name),
+                install_path,
+                has_executable,
+                has_config,
+            }
+        })
+        .collect();
+
+    println!("   Found {} candidate install(s)", candidates.len());
+    Ok(candidates)
+}
+
 /// Import an existing server installation
 /// Reads settings from GameUserSettings.ini and creates a database entry
 #[tauri::command]
@@ -1376,8 +3466,6 @@ pub async fn import_server(
     install_path: String,
     name: String,
 ) -> Result<Server, String> {
-    use std::fs;
-
     println!("📥 Importing server from: {}", install_path);
 
     let path = PathBuf::from(&install_path);
@@ -1405,71 +3493,17 @@ pub async fn import_server(
         println!("   ⚠️  Empty folder - server will be downloaded on first start");
     }
 
-    // Read GameUserSettings.ini to extract settings
-    let config_path = path
-        .join("ShooterGame")
-        .join("Saved")
-        .join("Config")
-        .join("WindowsServer")
-        .join("GameUserSettings.ini");
-
-    let mut max_players = 70;
-    let map_name = "TheIsland_WP".to_string();
-    let mut session_name = name.clone();
-    let mut server_password: Option<String> = None;
-    let mut admin_password = "admin123".to_string();
-    let mut game_port: u16 = 7777;
-    let mut query_port: u16 = 27015;
-    let mut rcon_port: u16 = 27020;
-    let mut rcon_enabled = true;
-
-    if config_path.exists() {
-        if let Ok(content) = fs::read_to_string(&config_path) {
-            let mut current_section = String::new();
-
-            for line in content.lines() {
-                let line = line.trim();
-
-                // Section header
-                if line.starts_with('[') && line.ends_with(']') {
-                    current_section = line[1..line.len() - 1].to_string();
-                    continue;
-                }
-
-                // Key=Value pair
-                if let Some((key, value)) = line.split_once('=') {
-                    let key = key.trim();
-                    let value = value.trim();
-
-                    if current_section == "ServerSettings"
-                        || current_section == "/Script/ShooterGame.ShooterGameMode"
-                    {
-                        match key {
-                            "MaxPlayers" => max_players = value.parse().unwrap_or(70),
-                            "ServerPassword" if !value.is_empty() => {
-                                server_password = Some(value.to_string())
-                            }
-                            "ServerAdminPassword" if !value.is_empty() => {
-                                admin_password = value.to_string()
-                            }
-                            "SessionName" if !value.is_empty() => session_name = value.to_string(),
-                            "RCONEnabled" => rcon_enabled = value.to_lowercase() == "true",
-                            "RCONPort" => rcon_port = value.parse().unwrap_or(27020),
-                            _ => {}
-                        }
-                    }
-
-                    if current_section == "URL" || current_section == "/Script/Engine.GameSession" {
-                        match key {
-                            "Port" => game_port = value.parse().unwrap_or(7777),
-                            "QueryPort" => query_port = value.parse().unwrap_or(27015),
-                            _ => {}
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let ImportedSettings {
+        max_players,
+        map_name,
+        session_name,
+        server_password,
+        admin_password,
+        game_port,
+        query_port,
+        rcon_port,
+        rcon_enabled,
+    } = read_import_settings(&path, &name);
 
     println!(
         "   Detected settings: Session={}, Map={}, MaxPlayers={}",
@@ -1477,13 +3511,8 @@ pub async fn import_server(
     );
 
     // Create database entry
-    let db = state
-        .db
-        .lock()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-    let conn = db
-        .get_connection()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
 
     // Check if this path is already registered
     let exists: bool = conn
@@ -1562,6 +3591,7 @@ pub async fn import_server(
             motd: None,
             mods: vec![],
             custom_args: None,
+            crossplay_platforms: Vec::new(),
         },
         rcon_config: RconConfig {
             enabled: rcon_enabled,
@@ -1573,6 +3603,10 @@ pub async fn import_server(
         auto_start: false,
         auto_stop: false,
         intelligent_mode: false,
+        auto_port_forward: false,
+        use_asa_api: false,
+        backup_before_restart: false,
+        battleye_enabled: false,
     })
 }
 
@@ -1580,7 +3614,7 @@ pub async fn import_server(
 pub async fn toggle_automation(
     state: State<'_, AppState>,
     server_id: i64,
-    toggle_type: String, // "auto_start" or "auto_stop"
+    toggle_type: String, // "auto_start", "auto_stop", "intelligent_mode", "auto_port_forward", "use_asa_api", "backup_before_restart" or "battleye_enabled"
     enabled: bool,
 ) -> Result<(), String> {
     println!(
@@ -1589,10 +3623,7 @@ pub async fn toggle_automation(
     );
 
     let (db, _app_handle) = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+        let db = &state.db;
         (db, state.app_handle.clone())
     };
 
@@ -1603,6 +3634,10 @@ pub async fn toggle_automation(
         "auto_start" => "auto_start",
         "auto_stop" => "auto_stop",
         "intelligent_mode" => "intelligent_mode",
+        "auto_port_forward" => "auto_port_forward",
+        "use_asa_api" => "use_asa_api",
+        "backup_before_restart" => "backup_before_restart",
+        "battleye_enabled" => "battleye_enabled",
         _ => return Err("Invalid toggle type".to_string()),
     };
 
@@ -1622,9 +3657,10 @@ pub async fn toggle_automation(
                 )
                 .map_err(|e| format!("Server not found: {}", e))?;
 
+            let triggers = file_watcher::load_triggers(&conn, server_id);
             state
                 .file_watcher
-                .start_watching(server_id, PathBuf::from(install_path))?;
+                .start_watching(server_id, PathBuf::from(install_path), triggers)?;
         } else {
             state.file_watcher.stop_watching(server_id);
         }
@@ -1632,3 +3668,256 @@ pub async fn toggle_automation(
 
     Ok(())
 }
+
+/// What auto-stops a server; the defaults when it has none of its own
+#[tauri::command]
+pub async fn get_auto_stop_triggers(
+    state: State<'_, AppState>,
+    server_id: i64,
+) -> Result<Vec<AutoStopTrigger>, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    Ok(file_watcher::load_triggers(&conn, server_id))
+}
+
+/// Replace a server's auto-stop triggers; an empty list goes back to the defaults.
+/// A running watcher is restarted so the change applies right away.
+#[tauri::command]
+pub async fn set_auto_stop_triggers(
+    state: State<'_, AppState>,
+    server_id: i64,
+    triggers: Vec<AutoStopTrigger>,
+) -> Result<Vec<AutoStopTrigger>, String> {
+    for trigger in &triggers {
+        file_watcher::validate_trigger(trigger)?;
+    }
+    println!(
+        "⚙️ Setting {} auto-stop trigger(s) for server {}",
+        triggers.len(),
+        server_id
+    );
+
+    let json = if triggers.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&triggers).map_err(|e| e.to_string())?)
+    };
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    let install_path: String = conn
+        .query_row(
+            "SELECT install_path FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Server not found: {}", e))?;
+    conn.execute(
+        "UPDATE servers SET auto_stop_triggers = ?1 WHERE id = ?2",
+        rusqlite::params![json, server_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let triggers = file_watcher::load_triggers(&conn, server_id);
+    if state.file_watcher.is_watching(server_id) {
+        state.file_watcher.stop_watching(server_id);
+        state.file_watcher.start_watching(
+            server_id,
+            PathBuf::from(install_path),
+            triggers.clone(),
+        )?;
+    }
+    Ok(triggers)
+}
+
+/// Uptime percentage and downtime windows over the last `days` days
+#[tauri::command]
+pub async fn get_server_uptime(
+    state: State<'_, AppState>,
+    server_id: i64,
+    days: u32,
+) -> Result<UptimeReport, String> {
+    if days == 0 {
+        return Err("Days must be at least 1".to_string());
+    }
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+    uptime::get_uptime(&conn, server_id, days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_ini_passwords() {
+        let ini = "[ServerSettings]\r\nServerAdminPassword=hunter22\r\nServerPassword = join\r\nSessionName=Password Island\r\nSpectatorPassword=spec";
+        assert_eq!(
+            redact_ini_passwords(ini),
+            "[ServerSettings]\r\nServerAdminPassword=\r\nServerPassword =\r\nSessionName=Password Island\r\nSpectatorPassword="
+        );
+    }
+
+    #[test]
+    fn test_server_definition_round_trips_launch_settings() {
+        let (_dir, db, server_id) = crate::db::test_support::db_with_server("definition");
+        let conn = db.get_connection().unwrap();
+        conn.execute(
+            "UPDATE servers SET battleye_enabled = 1, launch_flags = ?1,
+             crossplay_platforms = ?2, mod_strategy = 'runtime' WHERE id = ?3",
+            rusqlite::params![
+                r#"["no_crash_dialog","server_game_log"]"#,
+                r#"["PC","XSX"]"#,
+                server_id
+            ],
+        )
+        .unwrap();
+
+        let json =
+            serde_json::to_string(&read_server_definition(&conn, server_id).unwrap()).unwrap();
+        let definition: ServerDefinition = serde_json::from_str(&json).unwrap();
+        let (new_id, name, ports) =
+            insert_server_definition(&conn, &definition, "/srv/imported").unwrap();
+        assert_ne!(new_id, server_id);
+        assert_eq!(name, "definition (2)");
+        assert_ne!(ports.game_port, definition.game_port);
+
+        let imported = read_server_definition(&conn, new_id).unwrap();
+        assert!(imported.battleye_enabled);
+        assert_eq!(
+            imported.launch_flags,
+            ["no_crash_dialog", "server_game_log"]
+        );
+        assert_eq!(imported.crossplay_platforms, ["PC", "XSX"]);
+        assert_eq!(imported.mod_strategy.as_deref(), Some("runtime"));
+    }
+
+    #[test]
+    fn test_import_drops_launch_settings_this_version_does_not_know() {
+        let (_dir, db, server_id) = crate::db::test_support::db_with_server("definition_old");
+        let conn = db.get_connection().unwrap();
+        let mut definition = read_server_definition(&conn, server_id).unwrap();
+        definition.launch_flags =
+            vec!["no_crash_dialog".to_string(), "from_the_future".to_string()];
+        definition.crossplay_platforms = vec!["ALL".to_string(), "PC".to_string()];
+        definition.mod_strategy = Some("from_the_future".to_string());
+
+        let (new_id, _, _) = insert_server_definition(&conn, &definition, "/srv/imported").unwrap();
+        let imported = read_server_definition(&conn, new_id).unwrap();
+        assert_eq!(imported.launch_flags, ["no_crash_dialog"]);
+        assert!(imported.crossplay_platforms.is_empty());
+        assert_eq!(imported.mod_strategy, None);
+    }
+
+    fn make_install(dir: &std::path::Path, with_executable: bool) {
+        let shooter_game = dir.join("ShooterGame");
+        std::fs::create_dir_all(shooter_game.join("Saved").join("Config")).unwrap();
+        if with_executable {
+            let binaries = shooter_game.join("Binaries").join("Win64");
+            std::fs::create_dir_all(&binaries).unwrap();
+            std::fs::write(binaries.join("ArkAscendedServer.exe"), b"").unwrap();
+        }
+    }
+
+    #[test]
+    fn test_is_server_install() {
+        let dir = crate::db::test_support::TempDir::new("is_server_install");
+        assert_eq!(is_server_install(dir.path()), (false, false));
+
+        make_install(dir.path(), false);
+        assert_eq!(is_server_install(dir.path()), (false, true));
+
+        make_install(dir.path(), true);
+        assert_eq!(is_server_install(dir.path()), (true, true));
+    }
+
+    #[test]
+    fn test_scan_dir_finds_installs_under_steamapps() {
+        let dir = crate::db::test_support::TempDir::new("scan_dir");
+        let steam = dir
+            .path()
+            .join("SteamLibrary")
+            .join("steamapps")
+            .join("common")
+            .join("ARK Survival Ascended Dedicated Server");
+        make_install(&steam, true);
+        let plain = dir.path().join("servers").join("island");
+        make_install(&plain, false);
+        // Skipped: hidden folders, known system folders and anything inside an install
+        make_install(&dir.path().join(".cache").join("server"), true);
+        make_install(&dir.path().join("node_modules").join("server"), true);
+        make_install(&plain.join("ShooterGame").join("nested"), true);
+        // Deeper than SCAN_MAX_DEPTH
+        let deep =
+            (0..=SCAN_MAX_DEPTH).fold(dir.path().to_path_buf(), |p, i| p.join(format!("d{}", i)));
+        make_install(&deep, true);
+
+        let mut found = Vec::new();
+        scan_dir(dir.path(), 0, &mut found);
+        found.sort();
+        assert_eq!(found, vec![(steam, true, true), (plain, false, true)]);
+    }
+
+    #[test]
+    fn test_detect_map_from_launch_scripts() {
+        let dir = crate::db::test_support::TempDir::new("launch_scripts");
+        assert_eq!(detect_map_from_launch_scripts(dir.path()), None);
+
+        let win64 = dir.path().join("ShooterGame/Binaries/Win64");
+        std::fs::create_dir_all(&win64).unwrap();
+        std::fs::write(
+            win64.join("start.bat"),
+            "@echo off\r\nrem ArkAscendedServer.exe TheIsland_WP?listen\r\n\
+             start ArkAscendedServer.exe ScorchedEarth_WP?listen?SessionName=Scorched -NoBattlEye\r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_map_from_launch_scripts(dir.path()),
+            Some("ScorchedEarth_WP".to_string())
+        );
+
+        // Scripts at the install root are checked before Win64, quoted paths included
+        std::fs::write(
+            dir.path().join("Run.ps1"),
+            "# Launch\n& \"C:\\ark\\ShooterGame\\Binaries\\Win64\\ArkAscendedServer.exe\" \"Ragnarok_WP?listen\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_map_from_launch_scripts(dir.path()),
+            Some("Ragnarok_WP".to_string())
+        );
+    }
+
+    #[test]
+    fn test_registered_ports_leave_out_the_server_by_id() {
+        let (_dir, db, server_id) = crate::db::test_support::db_with_server("registered_ports");
+        let conn = db.get_connection().unwrap();
+        let twin = crate::db::test_support::insert_server(&conn, "Twin", "D:/twin");
+        conn.execute(
+            "UPDATE servers SET game_port = 7787, query_port = 27025, rcon_port = 27030 WHERE id = ?1",
+            [twin],
+        )
+        .unwrap();
+
+        assert_eq!(get_registered_ports(&conn, None).unwrap().len(), 6);
+        let mut others: Vec<u16> = get_registered_ports(&conn, Some(server_id))
+            .unwrap()
+            .into_iter()
+            .map(|(port, _)| port)
+            .collect();
+        others.sort();
+        assert_eq!(others, vec![7787, 27025, 27030]);
+        assert!(get_registered_ports(&conn, Some(server_id))
+            .unwrap()
+            .iter()
+            .all(|(_, owner)| owner == "Twin"));
+    }
+
+    #[test]
+    fn test_import_without_settings_gets_the_default_admin_password() {
+        let dir = crate::db::test_support::TempDir::new("import_defaults");
+        let settings = read_import_settings(dir.path(), "Imported");
+        assert_eq!(settings.admin_password, DEFAULT_ADMIN_PASSWORD);
+        assert_eq!(settings.session_name, "Imported");
+        assert!(settings.rcon_enabled);
+    }
+}
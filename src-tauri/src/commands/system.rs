@@ -1,6 +1,13 @@
+use crate::commands::rcon::RconState;
 use crate::models::SystemInfo;
+use crate::services::api_key_manager::{ApiKeyManager, KeyValidation};
+use crate::services::guardian::GuardianState;
+use crate::services::settings::{self, AppSettings};
+use crate::services::{app_log, diagnostics, mod_scraper};
 use crate::AppState;
 use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
 use sysinfo::Disks;
 use tauri::Manager;
 use tauri::State;
@@ -81,18 +88,132 @@ pub async fn get_setting(
     state: State<'_, AppState>,
     key: String,
 ) -> Result<Option<String>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let db = &state.db;
     db.get_setting(&key).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn set_setting(
     state: State<'_, AppState>,
+    rcon: State<'_, RconState>,
     key: String,
     value: String,
 ) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.set_setting(&key, &value).map_err(|e| e.to_string())
+    settings::validate_raw(&key, &value)?;
+    {
+        let db = &state.db;
+        db.set_setting(&key, &value).map_err(|e| e.to_string())?;
+    }
+
+    // Results fetched with the old key (or none) shouldn't outlive it
+    if key == settings::CURSEFORGE_API_KEY.key {
+        mod_scraper::clear_search_cache();
+    }
+    if key == settings::RCON_TIMEOUT_SECS.key {
+        let secs = settings::RCON_TIMEOUT_SECS.load(&state);
+        rcon.0.set_command_timeout(Duration::from_secs(secs));
+    }
+    Ok(())
+}
+
+/// Check a CurseForge API key before (or after) saving it
+#[tauri::command]
+pub async fn validate_curseforge_key(key: String) -> Result<KeyValidation, String> {
+    let result = ApiKeyManager::validate_curseforge_key(&key).await;
+    match &result.reason {
+        None => println!("🔑 CurseForge API key is valid"),
+        Some(reason) => println!("🔑 CurseForge API key check failed: {}", reason),
+    }
+    Ok(result)
+}
+
+/// Every known setting with defaults applied, for the settings page
+#[tauri::command]
+pub async fn get_all_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
+    let db = &state.db;
+    Ok(AppSettings::load(db))
+}
+
+#[tauri::command]
+pub async fn save_all_settings(
+    state: State<'_, AppState>,
+    rcon: State<'_, RconState>,
+    settings: AppSettings,
+) -> Result<(), String> {
+    let key_changed = {
+        let db = &state.db;
+        let key_changed = settings::CURSEFORGE_API_KEY.get(db) != settings.curseforge_api_key;
+        settings.save(db)?;
+        key_changed
+    };
+    if key_changed {
+        mod_scraper::clear_search_cache();
+    }
+    let secs = settings::RCON_TIMEOUT_SECS.load(&state);
+    rcon.0.set_command_timeout(Duration::from_secs(secs));
+    Ok(())
+}
+
+/// Export a consistent copy of the manager database to a user-chosen file
+#[tauri::command]
+pub async fn export_database(state: State<'_, AppState>, dest_path: String) -> Result<(), String> {
+    let dest = std::path::PathBuf::from(&dest_path);
+    if dest.exists() {
+        return Err(format!(
+            "{} already exists. Choose a new file name.",
+            dest_path
+        ));
+    }
+
+    println!("💾 Exporting database to {}", dest_path);
+    let db = &state.db;
+    db.export_to(&dest)
+        .map_err(|e| format!("Database export failed: {}", e))?;
+
+    println!("  ✅ Database exported");
+    Ok(())
+}
+
+/// Replace the manager database with an exported copy.
+/// Requires `confirm` and saves a safety copy of the current database first.
+#[tauri::command]
+pub async fn import_database(
+    state: State<'_, AppState>,
+    src_path: String,
+    confirm: bool,
+) -> Result<String, String> {
+    if !confirm {
+        return Err(
+            "Importing replaces all servers, mods and player data. Confirm to continue."
+                .to_string(),
+        );
+    }
+
+    let src = std::path::PathBuf::from(&src_path);
+    crate::db::validate_database_file(&src)
+        .map_err(|e| format!("Not a valid ASA Server Manager database: {}", e))?;
+
+    println!("📥 Importing database from {}", src_path);
+    let db = &state.db;
+
+    let safety_copy = db
+        .path()
+        .unwrap_or_else(|| std::path::PathBuf::from("asa_manager.db"))
+        .with_file_name(format!(
+            "asa_manager_before_import_{}.db",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ));
+    db.export_to(&safety_copy)
+        .map_err(|e| format!("Failed to save a copy of the current database: {}", e))?;
+
+    db.import_from(&src)
+        .map_err(|e| format!("Database import failed: {}", e))?;
+
+    println!(
+        "  ✅ Database imported (previous database saved to {:?})",
+        safety_copy
+    );
+    Ok(safety_copy.to_string_lossy().to_string())
 }
 
 #[derive(Serialize)]
@@ -219,3 +340,114 @@ pub async fn install_steamcmd(app: tauri::AppHandle) -> Result<String, String> {
 
     Ok("SteamCMD installed successfully.".to_string())
 }
+
+/// Zip up what a bug report needs: the manager log, DB schema, sanitized server rows
+/// and settings, Guardian crash events, recent ARK crash reports, SteamCMD details and
+/// OS/hardware info. Passwords, API keys and webhook URLs are redacted.
+#[tauri::command]
+pub async fn export_diagnostics_bundle(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    guardian: State<'_, GuardianState>,
+    dest: String,
+) -> Result<String, String> {
+    let dest_path = PathBuf::from(&dest);
+    if dest_path.exists() {
+        return Err(format!("{} already exists. Choose a new file name.", dest));
+    }
+    log::info!("🩺 Exporting diagnostics bundle to {}", dest);
+
+    let mut redactor = diagnostics::Redactor::default();
+    let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+
+    // Database: schema, sanitized servers and settings
+    let installs: Vec<(i64, String)> = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        entries.push((
+            "database/schema.sql".to_string(),
+            diagnostics::schema_sql(&conn)?.into_bytes(),
+        ));
+        for table in ["servers", "clusters"] {
+            let rows = diagnostics::table_rows(&conn, table, &mut redactor)?;
+            entries.push((
+                format!("database/{}.json", table),
+                serde_json::to_vec_pretty(&rows).map_err(|e| e.to_string())?,
+            ));
+        }
+        let settings_json = diagnostics::settings_rows(&conn, &mut redactor)?;
+        entries.push((
+            "database/settings.json".to_string(),
+            serde_json::to_vec_pretty(&settings_json).map_err(|e| e.to_string())?,
+        ));
+
+        let mut stmt = conn
+            .prepare("SELECT id, install_path FROM servers ORDER BY id")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    // System and SteamCMD
+    let system = {
+        let mut sys = state
+            .sys
+            .lock()
+            .map_err(|_| "Failed to lock system info".to_string())?;
+        sys.refresh_all();
+        diagnostics::system_report(&sys)
+    };
+    let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let report = serde_json::json!({
+        "appVersion": app.package_info().version.to_string(),
+        "createdAt": chrono::Local::now().to_rfc3339(),
+        "system": system,
+        "steamcmd": diagnostics::steamcmd_report(&app_dir.join("steamcmd")),
+        "runningServers": installs
+            .iter()
+            .filter(|(id, _)| state.process_manager.is_running(*id))
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>(),
+    });
+    entries.push((
+        "system.json".to_string(),
+        serde_json::to_vec_pretty(&report).map_err(|e| e.to_string())?,
+    ));
+
+    // Crashes seen by the Guardian, plus ARK's own crash reports
+    let crash_events = guardian.0.lock().await.get_crash_log().await;
+    entries.push((
+        "crashes/guardian_events.json".to_string(),
+        serde_json::to_vec_pretty(&crash_events).map_err(|e| e.to_string())?,
+    ));
+    for (server_id, install_path) in &installs {
+        let bundle_dir = format!("crashes/server_{}", server_id);
+        for (name, path) in
+            diagnostics::crash_files(std::path::Path::new(install_path), &bundle_dir)
+        {
+            if let Ok(data) = std::fs::read(&path) {
+                let text = String::from_utf8_lossy(&data);
+                entries.push((name, redactor.scrub(&text).into_bytes()));
+            }
+        }
+    }
+
+    // Manager log, current and previous file
+    if let Some(log_path) = app_log::log_path() {
+        app_log::flush();
+        let previous = log_path.with_file_name("manager.1.log");
+        for path in [previous, log_path] {
+            if let Ok(text) = std::fs::read_to_string(&path) {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                entries.push((format!("logs/{}", name), redactor.scrub(&text).into_bytes()));
+            }
+        }
+    }
+
+    diagnostics::write_bundle(&dest_path, &entries)?;
+    log::info!("  ✅ Diagnostics bundle written ({} files)", entries.len());
+    Ok(dest)
+}
@@ -0,0 +1,356 @@
+// Config Template Commands for ASA Server Manager
+// INI templates with {{placeholder}} substitution for deploying many similar servers
+
+use crate::services::config_generator::ConfigGenerator;
+use crate::services::ini_parser::IniParser;
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigTemplate {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub game_user_settings: String,
+    pub game_ini: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigTemplateRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub game_user_settings: String,
+    pub game_ini: String,
+}
+
+/// Placeholders available in templates, filled from the server's DB row
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "server_id",
+    "server_name",
+    "session_name",
+    "map_name",
+    "game_port",
+    "query_port",
+    "rcon_port",
+    "max_players",
+    "server_password",
+    "admin_password",
+    "ip_address",
+    "motd",
+    "cluster_id",
+    "cluster_dir",
+];
+
+/// Replace every {{name}} in `template` with its value. Fails on unknown placeholders
+/// so a typo doesn't end up in the INI as a literal "{{sesion_name}}".
+fn render_template(template: &str, values: &HashMap<&str, String>) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut unknown = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                match values.get(name) {
+                    Some(value) => out.push_str(value),
+                    None => {
+                        unknown.push(name.to_string());
+                        out.push_str(&rest[start..start + 2 + end + 2]);
+                    }
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+
+    if unknown.is_empty() {
+        Ok(out)
+    } else {
+        unknown.sort();
+        unknown.dedup();
+        Err(format!(
+            "Unknown template placeholders: {}. Available: {}",
+            unknown.join(", "),
+            TEMPLATE_PLACEHOLDERS.join(", ")
+        ))
+    }
+}
+
+fn row_to_template(row: &rusqlite::Row) -> rusqlite::Result<ConfigTemplate> {
+    Ok(ConfigTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        game_user_settings: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+        game_ini: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+/// List all config templates
+#[tauri::command]
+pub async fn get_config_templates(
+    state: State<'_, AppState>,
+) -> Result<Vec<ConfigTemplate>, String> {
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, description, game_user_settings, game_ini, created_at, updated_at 
+             FROM config_templates ORDER BY name COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let templates = stmt
+        .query_map([], row_to_template)
+        .map_err(|e| e.to_string())?
+        .filter_map(|t| t.ok())
+        .collect();
+
+    Ok(templates)
+}
+
+/// Create a new config template
+#[tauri::command]
+pub async fn create_config_template(
+    state: State<'_, AppState>,
+    request: ConfigTemplateRequest,
+) -> Result<ConfigTemplate, String> {
+    println!("➕ Creating config template '{}'", request.name);
+
+    if request.name.trim().is_empty() {
+        return Err("Template name cannot be empty".to_string());
+    }
+
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO config_templates (name, description, game_user_settings, game_ini) 
+         VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![
+            request.name.trim(),
+            request.description,
+            request.game_user_settings,
+            request.game_ini,
+        ],
+    )
+    .map_err(|e| format!("Failed to create template: {}", e))?;
+
+    let id = conn.last_insert_rowid();
+    conn.query_row(
+        "SELECT id, name, description, game_user_settings, game_ini, created_at, updated_at 
+         FROM config_templates WHERE id = ?1",
+        [id],
+        row_to_template,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Update an existing config template
+#[tauri::command]
+pub async fn update_config_template(
+    state: State<'_, AppState>,
+    template_id: i64,
+    request: ConfigTemplateRequest,
+) -> Result<(), String> {
+    println!("✏️ Updating config template {}", template_id);
+
+    if request.name.trim().is_empty() {
+        return Err("Template name cannot be empty".to_string());
+    }
+
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    let updated = conn
+        .execute(
+            "UPDATE config_templates SET name = ?1, description = ?2, game_user_settings = ?3, 
+             game_ini = ?4, updated_at = CURRENT_TIMESTAMP WHERE id = ?5",
+            rusqlite::params![
+                request.name.trim(),
+                request.description,
+                request.game_user_settings,
+                request.game_ini,
+                template_id,
+            ],
+        )
+        .map_err(|e| format!("Failed to update template: {}", e))?;
+
+    if updated == 0 {
+        return Err(format!("Template {} not found", template_id));
+    }
+    Ok(())
+}
+
+/// Delete a config template
+#[tauri::command]
+pub async fn delete_config_template(
+    state: State<'_, AppState>,
+    template_id: i64,
+) -> Result<(), String> {
+    println!("🗑️ Deleting config template {}", template_id);
+
+    let db = &state.db;
+    let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM config_templates WHERE id = ?1", [template_id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Render a template with a server's values and write GameUserSettings.ini / Game.ini.
+/// Existing files are backed up first; an empty template section leaves that file untouched.
+#[tauri::command]
+pub async fn apply_template(
+    state: State<'_, AppState>,
+    server_id: i64,
+    template_id: i64,
+) -> Result<(), String> {
+    println!(
+        "📋 Applying config template {} to server {}",
+        template_id, server_id
+    );
+
+    let (template, install_path, values) = {
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+
+        let template = conn
+            .query_row(
+                "SELECT id, name, description, game_user_settings, game_ini, created_at, updated_at 
+                 FROM config_templates WHERE id = ?1",
+                [template_id],
+                row_to_template,
+            )
+            .map_err(|e| format!("Template not found: {}", e))?;
+
+        let (install_path, values) = conn
+            .query_row(
+                "SELECT s.install_path, s.name, s.session_name, s.map_name, s.game_port, s.query_port, 
+                        s.rcon_port, s.max_players, s.server_password, s.admin_password, s.ip_address, 
+                        s.motd, c.name, c.cluster_path 
+                 FROM servers s LEFT JOIN clusters c ON s.cluster_id = c.id 
+                 WHERE s.id = ?1",
+                [server_id],
+                |row| {
+                    let mut values: HashMap<&str, String> = HashMap::new();
+                    values.insert("server_id", server_id.to_string());
+                    values.insert("server_name", row.get(1)?);
+                    values.insert("session_name", row.get(2)?);
+                    values.insert("map_name", row.get(3)?);
+                    values.insert("game_port", row.get::<_, i64>(4)?.to_string());
+                    values.insert("query_port", row.get::<_, i64>(5)?.to_string());
+                    values.insert("rcon_port", row.get::<_, i64>(6)?.to_string());
+                    values.insert("max_players", row.get::<_, i64>(7)?.to_string());
+                    values.insert(
+                        "server_password",
+                        row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+                    );
+                    values.insert("admin_password", row.get(9)?);
+                    values.insert(
+                        "ip_address",
+                        row.get::<_, Option<String>>(10)?.unwrap_or_default(),
+                    );
+                    values.insert("motd", row.get::<_, Option<String>>(11)?.unwrap_or_default());
+                    values.insert(
+                        "cluster_id",
+                        row.get::<_, Option<String>>(12)?.unwrap_or_default(),
+                    );
+                    values.insert(
+                        "cluster_dir",
+                        row.get::<_, Option<String>>(13)?.unwrap_or_default(),
+                    );
+                    Ok((row.get::<_, String>(0)?, values))
+                },
+            )
+            .map_err(|e| format!("Server not found: {}", e))?;
+
+        (template, PathBuf::from(install_path), values)
+    };
+
+    // Render both files before touching disk so a bad placeholder writes nothing
+    let game_user_settings = render_template(&template.game_user_settings, &values)?;
+    let game_ini = render_template(&template.game_ini, &values)?;
+
+    let config_dir = install_path.join("ShooterGame/Saved/Config/WindowsServer");
+    fs::create_dir_all(&config_dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    let _ = ConfigGenerator::backup_configs(&install_path);
+
+    for (file_name, content) in [
+        ("GameUserSettings.ini", game_user_settings),
+        ("Game.ini", game_ini),
+    ] {
+        if content.trim().is_empty() {
+            continue;
+        }
+        IniParser::write_atomic(&config_dir.join(file_name), &content)
+            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+        println!("  📝 Wrote {} from template '{}'", file_name, template.name);
+    }
+
+    println!("  ✅ Template applied to server {}", server_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("session_name", "Rag PvE".to_string()),
+            ("game_port", "7779".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_render_template_fills_placeholders() {
+        assert_eq!(
+            render_template(
+                "[SessionSettings]\nSessionName={{session_name}}\nPort={{ game_port }}\n",
+                &values()
+            )
+            .unwrap(),
+            "[SessionSettings]\nSessionName=Rag PvE\nPort=7779\n"
+        );
+        // An unclosed brace pair is left as written
+        assert_eq!(
+            render_template("Message=Hi {{session_name", &values()).unwrap(),
+            "Message=Hi {{session_name"
+        );
+    }
+
+    #[test]
+    fn test_render_template_reports_each_unknown_placeholder_once() {
+        let err = render_template(
+            "SessionName={{sesion_name}}\nMessage={{motdd}} on {{sesion_name}}\n",
+            &values(),
+        )
+        .unwrap_err();
+        assert!(
+            err.starts_with("Unknown template placeholders: motdd, sesion_name."),
+            "{}",
+            err
+        );
+    }
+}
@@ -1,20 +1,93 @@
-use rusqlite::{Connection, Result};
-use std::path::PathBuf;
-use std::sync::Mutex;
+use rusqlite::{Connection, DatabaseName, OpenFlags, Result};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
 
+#[cfg(test)]
+pub mod test_support;
+
+/// Idle connections kept open for reuse
+const MAX_IDLE_CONNECTIONS: usize = 4;
+
+/// Lock a mutex, taking the guard back if an earlier holder panicked.
+/// A panic mid-command must not brick every later command until restart.
+pub fn lock_or_recover<'a, T>(mutex: &'a Mutex<T>, name: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        println!("⚠️ {} lock was poisoned by a panic, recovering", name);
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+/// Small connection pool over one SQLite file in WAL mode.
+/// Readers no longer queue behind a long query (e.g. the session poller
+/// scanning player_sessions); SQLite itself serializes writers, with
+/// busy_timeout absorbing short waits.
 pub struct Database {
-    conn: Mutex<Connection>,
+    path: PathBuf,
+    idle: Mutex<Vec<Connection>>,
+}
+
+/// A pooled connection; goes back to the pool when dropped
+pub struct DbConnection<'a> {
+    conn: Option<Connection>,
+    db: &'a Database,
+}
+
+impl Deref for DbConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection taken")
+    }
+}
+
+impl DerefMut for DbConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection taken")
+    }
+}
+
+impl Drop for DbConnection<'_> {
+    fn drop(&mut self) {
+        let Some(conn) = self.conn.take() else {
+            return;
+        };
+        // A connection left inside a transaction (e.g. by a panic) is closed, not reused
+        if !conn.is_autocommit() {
+            return;
+        }
+        let mut idle = lock_or_recover(&self.db.idle, "Database pool");
+        if idle.len() < MAX_IDLE_CONNECTIONS {
+            idle.push(conn);
+        }
+    }
 }
 
 impl Database {
     pub fn new(db_path: PathBuf) -> Result<Self> {
-        let conn = Connection::open(db_path)?;
+        let db = Database {
+            path: db_path,
+            idle: Mutex::new(Vec::new()),
+        };
+        let conn = db.get_connection()?;
 
-        // Enable Write-Ahead Logging (WAL) for concurrency
+        // Enable Write-Ahead Logging (WAL) for concurrency; the mode is stored in the file.
         // Note: PRAGMA journal_mode returns the new mode (e.g. "wal"), so execute() fails.
         // We use pragma_update or query_row to handle this.
         let _mode: String = conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))?;
 
+        // Initialize schema
+        Self::init_schema(&conn)?;
+        drop(conn);
+
+        Ok(db)
+    }
+
+    /// Open a connection with the per-connection settings every caller relies on
+    fn open(&self) -> Result<Connection> {
+        let conn = Connection::open(&self.path)?;
+
         // Set synchronous mode to NORMAL (faster in WAL mode)
         conn.pragma_update(None, "synchronous", "NORMAL")?;
 
@@ -24,12 +97,7 @@ impl Database {
         // Enable foreign keys
         conn.execute("PRAGMA foreign_keys = ON", [])?;
 
-        // Initialize schema
-        Self::init_schema(&conn)?;
-
-        Ok(Database {
-            conn: Mutex::new(conn),
-        })
+        Ok(conn)
     }
 
     fn init_schema(conn: &Connection) -> Result<()> {
@@ -42,85 +110,56 @@ impl Database {
         Ok(())
     }
 
+    /// Apply every migration newer than the version stored in schema_version.
+    /// Foreign keys are off meanwhile so a table rebuild doesn't cascade-delete
+    /// the rows that reference it (the pragma is a no-op inside a transaction).
     fn run_migrations(conn: &Connection) -> Result<()> {
-        // Add missing columns to servers table (if they don't exist)
-        // SQLite doesn't have IF NOT EXISTS for ALTER TABLE, so we use a table info check
-
-        let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
-        let columns: Vec<String> = stmt
-            .query_map([], |row| row.get::<_, String>(1))?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        // Add mods column if missing
-        if !columns.contains(&"mods".to_string()) {
-            println!("📦 Migration: Adding 'mods' column to servers table");
-            conn.execute("ALTER TABLE servers ADD COLUMN mods TEXT", [])?;
-        }
-
-        // Add custom_args column if missing
-        if !columns.contains(&"custom_args".to_string()) {
-            println!("📦 Migration: Adding 'custom_args' column to servers table");
-            conn.execute("ALTER TABLE servers ADD COLUMN custom_args TEXT", [])?;
-        }
-
-        // Add rcon_enabled column if missing
-        if !columns.contains(&"rcon_enabled".to_string()) {
-            println!("📦 Migration: Adding 'rcon_enabled' column to servers table");
-            conn.execute(
-                "ALTER TABLE servers ADD COLUMN rcon_enabled INTEGER DEFAULT 1",
-                [],
-            )?;
-        }
-
-        // Add ip_address column if missing
-        if !columns.contains(&"ip_address".to_string()) {
-            println!("📦 Migration: Adding 'ip_address' column to servers table");
-            conn.execute("ALTER TABLE servers ADD COLUMN ip_address TEXT", [])?;
-        }
-
-        // Add cluster_id column if missing
-        if !columns.contains(&"cluster_id".to_string()) {
-            println!("📦 Migration: Adding 'cluster_id' column to servers table");
-            conn.execute("ALTER TABLE servers ADD COLUMN cluster_id INTEGER REFERENCES clusters(id) ON DELETE SET NULL", [])?;
-        }
+        let current: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+            [],
+            |row| row.get(0),
+        )?;
 
-        // Add auto_start column if missing
-        if !columns.contains(&"auto_start".to_string()) {
-            println!("📦 Migration: Adding 'auto_start' column to servers table");
-            conn.execute(
-                "ALTER TABLE servers ADD COLUMN auto_start INTEGER DEFAULT 0",
-                [],
-            )?;
-        }
+        conn.execute("PRAGMA foreign_keys = OFF", [])?;
+        let result = Self::apply_migrations(conn, current);
+        conn.execute("PRAGMA foreign_keys = ON", [])?;
+        result
+    }
 
-        // Add auto_stop column if missing
-        if !columns.contains(&"auto_stop".to_string()) {
-            println!("📦 Migration: Adding 'auto_stop' column to servers table");
-            conn.execute(
-                "ALTER TABLE servers ADD COLUMN auto_stop INTEGER DEFAULT 0",
-                [],
-            )?;
-        }
+    fn apply_migrations(conn: &Connection, current: i64) -> Result<()> {
+        for (version, description, migrate) in MIGRATIONS {
+            if *version <= current {
+                continue;
+            }
 
-        // Add intelligent_mode column if missing
-        if !columns.contains(&"intelligent_mode".to_string()) {
-            println!("📦 Migration: Adding 'intelligent_mode' column to servers table");
-            conn.execute(
-                "ALTER TABLE servers ADD COLUMN intelligent_mode INTEGER DEFAULT 0",
-                [],
+            println!("📦 Migration {}: {}", version, description);
+            let tx = conn.unchecked_transaction()?;
+            migrate(&tx)?;
+            tx.execute(
+                "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+                rusqlite::params![version, description],
             )?;
+            tx.commit()?;
         }
 
         Ok(())
     }
 
-    pub fn get_connection(&self) -> std::sync::LockResult<std::sync::MutexGuard<'_, Connection>> {
-        self.conn.lock()
+    /// An idle pooled connection, or a freshly opened one
+    pub fn get_connection(&self) -> Result<DbConnection<'_>> {
+        let reused = lock_or_recover(&self.idle, "Database pool").pop();
+        let conn = match reused {
+            Some(conn) => conn,
+            None => self.open()?,
+        };
+        Ok(DbConnection {
+            conn: Some(conn),
+            db: self,
+        })
     }
 
     pub fn get_setting(&self, key: &str) -> Result<Option<String>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.get_connection()?;
         let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?1")?;
         let mut rows = stmt.query([key])?;
 
@@ -132,7 +171,7 @@ impl Database {
     }
 
     pub fn set_setting(&self, key: &str, value: &str) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.get_connection()?;
         conn.execute(
             "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, CURRENT_TIMESTAMP) 
              ON CONFLICT(key) DO UPDATE SET value = ?2, updated_at = CURRENT_TIMESTAMP",
@@ -140,4 +179,728 @@ impl Database {
         )?;
         Ok(())
     }
+
+    /// Write a consistent snapshot of the database to `dest` (includes WAL contents)
+    pub fn export_to(&self, dest: &Path) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute("VACUUM INTO ?1", [dest.to_string_lossy()])?;
+        Ok(())
+    }
+
+    /// Replace the live database with the contents of `src` using the SQLite backup API,
+    /// then bring the imported schema up to date
+    pub fn import_from(&self, src: &Path) -> Result<()> {
+        // Idle connections are closed so nothing keeps a stale view of the old file
+        lock_or_recover(&self.idle, "Database pool").clear();
+        let mut conn = self.get_connection()?;
+        conn.restore(
+            DatabaseName::Main,
+            src,
+            None::<fn(rusqlite::backup::Progress)>,
+        )?;
+        conn.execute_batch(include_str!("schema.sql"))?;
+        Self::run_migrations(&conn)?;
+        Ok(())
+    }
+
+    /// Path of the open database file
+    pub fn path(&self) -> Option<PathBuf> {
+        Some(self.path.clone())
+    }
+}
+
+/// Check that `path` is a SQLite database created by this app (has a servers table)
+pub fn validate_database_file(path: &Path) -> Result<()> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.query_row(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'servers'",
+        [],
+        |row| row.get::<_, String>(0),
+    )?;
+    Ok(())
+}
+
+type Migration = (i64, &'static str, fn(&Connection) -> Result<()>);
+
+/// Ordered schema migrations. Append new steps with the next version number;
+/// never edit or reorder steps that have shipped.
+const MIGRATIONS: &[Migration] = &[
+    (1, "Baseline servers columns", migrate_v1_legacy_columns),
+    (
+        2,
+        "Allow new scheduled task types",
+        migrate_v2_relax_task_type_check,
+    ),
+    (
+        3,
+        "Store EOS ids on player sessions",
+        migrate_v3_session_eos_id,
+    ),
+    (4, "Add AsaApi launch flag", migrate_v4_use_asa_api),
+    (5, "Add config templates", migrate_v5_config_templates),
+    (
+        6,
+        "Track active config overrides",
+        migrate_v6_config_override_backup,
+    ),
+    (7, "Record server status history", migrate_v7_status_history),
+    (8, "Add RCON history and favorites", migrate_v8_rcon_history),
+    (9, "Add scheduled task options", migrate_v9_task_options),
+    (
+        10,
+        "Add pre-restart backup flag",
+        migrate_v10_backup_before_restart,
+    ),
+    (11, "Track encrypted backups", migrate_v11_backup_encryption),
+    (
+        12,
+        "Track cloud backup copies",
+        migrate_v12_backup_remote_url,
+    ),
+    (
+        13,
+        "Move legacy servers.mods into the mods table",
+        migrate_v13_backfill_mods_table,
+    ),
+    (14, "Add server tags", migrate_v14_server_tags),
+    (15, "Record scheduled task runs", migrate_v15_task_runs),
+    (16, "Add BattlEye launch flag", migrate_v16_battleye),
+    (17, "Add toggleable launch flags", migrate_v17_launch_flags),
+    (
+        18,
+        "Add crossplay platforms",
+        migrate_v18_crossplay_platforms,
+    ),
+    (19, "Add mod management strategy", migrate_v19_mod_strategy),
+    (20, "Store mod file metadata", migrate_v20_mod_metadata),
+    (
+        21,
+        "Add auto-stop trigger patterns",
+        migrate_v21_auto_stop_triggers,
+    ),
+    (
+        22,
+        "Allow online, stopping and missing server statuses",
+        migrate_v22_widen_status_check,
+    ),
+    (
+        23,
+        "Remember extra config settings written to the INI files",
+        migrate_v23_extra_settings,
+    ),
+    (
+        24,
+        "Record skipped scheduled task runs apart from failures",
+        migrate_v24_task_run_outcome,
+    ),
+    (
+        25,
+        "Keep CurseForge's newest mod file apart from the installed version",
+        migrate_v25_mod_latest_version,
+    ),
+];
+
+/// Version 1: columns added to `servers` before migrations were versioned.
+/// Checks table_info so it is safe on both fresh and pre-versioning databases.
+fn migrate_v1_legacy_columns(conn: &Connection) -> Result<()> {
+    // Add missing columns to servers table (if they don't exist)
+    // SQLite doesn't have IF NOT EXISTS for ALTER TABLE, so we use a table info check
+
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // Add mods column if missing
+    if !columns.contains(&"mods".to_string()) {
+        println!("📦 Migration: Adding 'mods' column to servers table");
+        conn.execute("ALTER TABLE servers ADD COLUMN mods TEXT", [])?;
+    }
+
+    // Add custom_args column if missing
+    if !columns.contains(&"custom_args".to_string()) {
+        println!("📦 Migration: Adding 'custom_args' column to servers table");
+        conn.execute("ALTER TABLE servers ADD COLUMN custom_args TEXT", [])?;
+    }
+
+    // Add rcon_enabled column if missing
+    if !columns.contains(&"rcon_enabled".to_string()) {
+        println!("📦 Migration: Adding 'rcon_enabled' column to servers table");
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN rcon_enabled INTEGER DEFAULT 1",
+            [],
+        )?;
+    }
+
+    // Add ip_address column if missing
+    if !columns.contains(&"ip_address".to_string()) {
+        println!("📦 Migration: Adding 'ip_address' column to servers table");
+        conn.execute("ALTER TABLE servers ADD COLUMN ip_address TEXT", [])?;
+    }
+
+    // Add cluster_id column if missing
+    if !columns.contains(&"cluster_id".to_string()) {
+        println!("📦 Migration: Adding 'cluster_id' column to servers table");
+        conn.execute("ALTER TABLE servers ADD COLUMN cluster_id INTEGER REFERENCES clusters(id) ON DELETE SET NULL", [])?;
+    }
+
+    // Add auto_start column if missing
+    if !columns.contains(&"auto_start".to_string()) {
+        println!("📦 Migration: Adding 'auto_start' column to servers table");
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN auto_start INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+
+    // Add auto_stop column if missing
+    if !columns.contains(&"auto_stop".to_string()) {
+        println!("📦 Migration: Adding 'auto_stop' column to servers table");
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN auto_stop INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+
+    // Add intelligent_mode column if missing
+    if !columns.contains(&"intelligent_mode".to_string()) {
+        println!("📦 Migration: Adding 'intelligent_mode' column to servers table");
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN intelligent_mode INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+
+    // Add auto_port_forward column if missing
+    if !columns.contains(&"auto_port_forward".to_string()) {
+        println!("📦 Migration: Adding 'auto_port_forward' column to servers table");
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN auto_port_forward INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Version 2: drop the task_type CHECK on scheduled_tasks (types are validated in code)
+fn migrate_v2_relax_task_type_check(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE scheduled_tasks_new (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id INTEGER NOT NULL,
+            task_type TEXT NOT NULL,
+            cron_expression TEXT NOT NULL,
+            command TEXT,
+            message TEXT,
+            pre_warning_minutes INTEGER DEFAULT 5,
+            enabled INTEGER DEFAULT 1,
+            last_run TIMESTAMP,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (server_id) REFERENCES servers (id) ON DELETE CASCADE
+        );
+        INSERT INTO scheduled_tasks_new
+            SELECT id, server_id, task_type, cron_expression, command, message,
+                   pre_warning_minutes, enabled, last_run, created_at
+            FROM scheduled_tasks;
+        DROP TABLE scheduled_tasks;
+        ALTER TABLE scheduled_tasks_new RENAME TO scheduled_tasks;",
+    )
+}
+
+/// Version 3: ASA players are identified by EOS ids, keep them alongside the Steam ID
+fn migrate_v3_session_eos_id(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(player_sessions)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"eos_id".to_string()) {
+        conn.execute("ALTER TABLE player_sessions ADD COLUMN eos_id TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Version 4: per-server flag to launch through the AsaApi loader
+fn migrate_v4_use_asa_api(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"use_asa_api".to_string()) {
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN use_asa_api INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Version 5: INI templates with {{placeholder}} substitution
+fn migrate_v5_config_templates(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS config_templates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            description TEXT,
+            game_user_settings TEXT,
+            game_ini TEXT,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );",
+    )
+}
+
+/// Version 6: backup directory of the configs replaced by start_server_with_overrides
+fn migrate_v6_config_override_backup(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"config_override_backup".to_string()) {
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN config_override_backup TEXT",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Version 7: every change of servers.status, written by a trigger so no
+/// code path that updates the status can forget to record it
+fn migrate_v7_status_history(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS server_status_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id INTEGER NOT NULL REFERENCES servers(id) ON DELETE CASCADE,
+            status TEXT NOT NULL,
+            changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE INDEX IF NOT EXISTS idx_status_history_server
+            ON server_status_history(server_id, changed_at);
+        CREATE TRIGGER IF NOT EXISTS trg_servers_status_history
+            AFTER UPDATE OF status ON servers
+            WHEN NEW.status IS NOT OLD.status
+        BEGIN
+            INSERT INTO server_status_history (server_id, status)
+            VALUES (NEW.id, NEW.status);
+        END;
+        INSERT INTO server_status_history (server_id, status)
+            SELECT id, status FROM servers WHERE status IS NOT NULL;",
+    )
+}
+
+/// Version 8: console history (passwords masked before insert) and saved commands
+fn migrate_v8_rcon_history(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS rcon_command_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id INTEGER NOT NULL REFERENCES servers(id) ON DELETE CASCADE,
+            command TEXT NOT NULL,
+            response_snippet TEXT,
+            success INTEGER NOT NULL DEFAULT 1,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE INDEX IF NOT EXISTS idx_rcon_history_server
+            ON rcon_command_history(server_id, id);
+        CREATE TABLE IF NOT EXISTS rcon_favorites (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id INTEGER NOT NULL REFERENCES servers(id) ON DELETE CASCADE,
+            label TEXT NOT NULL,
+            command TEXT NOT NULL,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(server_id, label)
+        );",
+    )
+}
+
+/// Version 9: per-type JSON options for scheduled tasks (e.g. save after a dino wipe)
+fn migrate_v9_task_options(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(scheduled_tasks)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"options".to_string()) {
+        conn.execute("ALTER TABLE scheduled_tasks ADD COLUMN options TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Version 10: per-server opt-in for a safety backup before every restart
+fn migrate_v10_backup_before_restart(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"backup_before_restart".to_string()) {
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN backup_before_restart INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Version 11: mark backups whose archive is password-encrypted
+fn migrate_v11_backup_encryption(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(backups)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"encrypted".to_string()) {
+        conn.execute(
+            "ALTER TABLE backups ADD COLUMN encrypted BOOLEAN DEFAULT 0",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Version 12: where a backup was uploaded (s3://bucket/key), if anywhere
+fn migrate_v12_backup_remote_url(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(backups)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"remote_url".to_string()) {
+        conn.execute("ALTER TABLE backups ADD COLUMN remote_url TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Version 13: the mods table is the only source of truth for a server's mods.
+/// Copies any comma-separated ids left in the deprecated servers.mods column
+/// (enabled, appended after existing mods) and clears the column.
+fn migrate_v13_backfill_mods_table(conn: &Connection) -> Result<()> {
+    let legacy: Vec<(i64, String)> = {
+        let mut stmt = conn
+            .prepare("SELECT id, mods FROM servers WHERE mods IS NOT NULL AND TRIM(mods) != ''")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    for (server_id, mods) in legacy {
+        let mut load_order: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(load_order), 0) FROM mods WHERE server_id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )?;
+        for mod_id in mods.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+            load_order += 1;
+            conn.execute(
+                "INSERT OR IGNORE INTO mods (server_id, mod_id, name, server_type, enabled, load_order)
+                 VALUES (?1, ?2, ?3, 'ASA', 1, ?4)",
+                rusqlite::params![server_id, mod_id, format!("Mod {}", mod_id), load_order],
+            )?;
+        }
+    }
+
+    conn.execute("UPDATE servers SET mods = NULL", [])?;
+    Ok(())
+}
+
+/// Version 14: free-form tags for grouping servers independently of clusters
+fn migrate_v14_server_tags(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS server_tags (
+            server_id INTEGER NOT NULL REFERENCES servers(id) ON DELETE CASCADE,
+            tag TEXT NOT NULL COLLATE NOCASE,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (server_id, tag)
+        );
+        CREATE INDEX IF NOT EXISTS idx_server_tags_tag ON server_tags(tag);",
+    )
+}
+
+/// Version 15: outcome of each scheduled task execution, not just the last run time
+fn migrate_v15_task_runs(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS scheduled_task_runs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL REFERENCES scheduled_tasks(id) ON DELETE CASCADE,
+            started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            finished_at TIMESTAMP,
+            success INTEGER,
+            message TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_task_runs_task ON scheduled_task_runs(task_id, id);",
+    )
+}
+
+/// Version 16: per-server BattlEye toggle; off keeps the old hardcoded -NoBattlEye
+fn migrate_v16_battleye(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"battleye_enabled".to_string()) {
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN battleye_enabled INTEGER DEFAULT 0",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Version 17: JSON array of launch_args::LAUNCH_FLAGS keys per server
+fn migrate_v17_launch_flags(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"launch_flags".to_string()) {
+        conn.execute("ALTER TABLE servers ADD COLUMN launch_flags TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Version 18: JSON array of -ServerPlatform names; NULL launches with the game default
+fn migrate_v18_crossplay_platforms(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"crossplay_platforms".to_string()) {
+        conn.execute(
+            "ALTER TABLE servers ADD COLUMN crossplay_platforms TEXT",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Version 19: launch_args::MOD_STRATEGIES key; NULL reads as the default
+fn migrate_v19_mod_strategy(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"mod_strategy".to_string()) {
+        conn.execute("ALTER TABLE servers ADD COLUMN mod_strategy TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Version 20: newest CurseForge file details per installed mod, filled by refresh_mod_metadata
+fn migrate_v20_mod_metadata(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(mods)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    for (column, definition) in [
+        ("game_version", "TEXT"),
+        ("file_date", "TEXT"),
+        ("metadata_refreshed_at", "TIMESTAMP"),
+    ] {
+        if !columns.contains(&column.to_string()) {
+            conn.execute(
+                &format!("ALTER TABLE mods ADD COLUMN {} {}", column, definition),
+                [],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Version 21: JSON array of AutoStopTrigger; NULL uses file_watcher::default_triggers
+fn migrate_v21_auto_stop_triggers(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"auto_stop_triggers".to_string()) {
+        conn.execute("ALTER TABLE servers ADD COLUMN auto_stop_triggers TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Version 22: the state machine (services::server_state) stores online, stopping and
+/// missing, which the original status CHECK rejected. SQLite can't alter a CHECK, so the
+/// table is rebuilt from its own CREATE statement, keeping columns added by earlier steps.
+fn migrate_v22_widen_status_check(conn: &Connection) -> Result<()> {
+    const OLD_CHECK: &str =
+        "CHECK(status IN ('stopped', 'starting', 'running', 'crashed', 'updating', 'restarting'))";
+    const NEW_CHECK: &str = "CHECK(status IN ('stopped', 'starting', 'running', 'online', 'stopping', 'crashed', 'updating', 'restarting', 'missing'))";
+
+    let create_sql: String = conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'servers'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !create_sql.contains(OLD_CHECK) {
+        // Created from the current schema.sql
+        return Ok(());
+    }
+
+    let create_new = create_sql
+        .replacen("servers", "servers_new", 1)
+        .replace(OLD_CHECK, NEW_CHECK);
+    conn.execute_batch(&format!(
+        "{};
+        INSERT INTO servers_new SELECT * FROM servers;
+        DROP TABLE servers;
+        ALTER TABLE servers_new RENAME TO servers;
+        CREATE INDEX IF NOT EXISTS idx_servers_status ON servers(status);
+        CREATE TRIGGER IF NOT EXISTS trg_servers_status_history
+            AFTER UPDATE OF status ON servers
+            WHEN NEW.status IS NOT OLD.status
+        BEGIN
+            INSERT INTO server_status_history (server_id, status)
+            VALUES (NEW.id, NEW.status);
+        END;",
+        create_new
+    ))?;
+    Ok(())
+}
+
+/// Version 23: the extra_settings last written by the config generator, so a setting
+/// removed from the config can be removed from the INI files as well
+fn migrate_v23_extra_settings(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(servers)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"extra_settings".to_string()) {
+        conn.execute("ALTER TABLE servers ADD COLUMN extra_settings TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Version 24: a finished task run is a success, a failure or skipped (e.g. the
+/// server was offline). Skipped runs used to be stored as failures with the
+/// message "Skipped"; they keep no success value now.
+fn migrate_v24_task_run_outcome(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(scheduled_task_runs)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"outcome".to_string()) {
+        conn.execute(
+            "ALTER TABLE scheduled_task_runs ADD COLUMN outcome TEXT",
+            [],
+        )?;
+    }
+    conn.execute_batch(
+        "UPDATE scheduled_task_runs SET outcome = CASE
+            WHEN success = 1 THEN 'success'
+            WHEN message = 'Skipped' THEN 'skipped'
+            ELSE 'failed'
+         END
+         WHERE finished_at IS NOT NULL AND outcome IS NULL;
+         UPDATE scheduled_task_runs SET success = NULL, message = NULL WHERE outcome = 'skipped';",
+    )
+}
+
+/// Version 25: refresh_mod_metadata writes the newest CurseForge file name here so
+/// `version` stays what was installed
+fn migrate_v25_mod_latest_version(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(mods)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !columns.contains(&"latest_version".to_string()) {
+        conn.execute("ALTER TABLE mods ADD COLUMN latest_version TEXT", [])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_server, TempDir};
+
+    /// A database created before version 22 keeps its rows, child rows and
+    /// status history trigger, and accepts the new statuses afterwards
+    #[test]
+    fn test_widen_status_check_migration() {
+        let dir = TempDir::new("db_v22");
+        let path = dir.path().join("asa_manager.db");
+
+        // Roll a fresh database back to the pre-22 servers table
+        {
+            let db = Database::new(path.clone()).unwrap();
+            let conn = db.get_connection().unwrap();
+            let create_sql: String = conn
+                .query_row(
+                    "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'servers'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            let old_sql = create_sql.replacen("servers", "servers_old", 1).replace(
+                "'running', 'online', 'stopping', 'crashed', 'updating', 'restarting', 'missing'",
+                "'running', 'crashed', 'updating', 'restarting'",
+            );
+            conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+            conn.execute_batch(&format!(
+                "{};
+                DROP TABLE servers;
+                ALTER TABLE servers_old RENAME TO servers;
+                DELETE FROM schema_version WHERE version >= 22;",
+                old_sql
+            ))
+            .unwrap();
+            insert_server(&conn, "Old", "/srv/old");
+            conn.execute(
+                "INSERT INTO mods (server_id, mod_id, name, load_order) VALUES (1, '928793', 'Cybers', 0)",
+                [],
+            )
+            .unwrap();
+            conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+            assert!(conn
+                .execute("UPDATE servers SET status = 'online' WHERE id = 1", [])
+                .is_err());
+        }
+
+        let db = Database::new(path).unwrap();
+        let conn = db.get_connection().unwrap();
+        conn.execute("UPDATE servers SET status = 'online' WHERE id = 1", [])
+            .unwrap();
+        let mods: i64 = conn
+            .query_row("SELECT COUNT(*) FROM mods WHERE server_id = 1", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(mods, 1);
+        let last: String = conn
+            .query_row(
+                "SELECT status FROM server_status_history WHERE server_id = 1 ORDER BY id DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(last, "online");
+    }
 }
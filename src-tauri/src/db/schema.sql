@@ -6,7 +6,7 @@ CREATE TABLE IF NOT EXISTS servers (
     name TEXT NOT NULL,
     server_type TEXT NOT NULL DEFAULT 'ASA' CHECK(server_type IN ('ASA')),
     install_path TEXT NOT NULL,
-    status TEXT NOT NULL DEFAULT 'stopped' CHECK(status IN ('stopped', 'starting', 'running', 'crashed', 'updating', 'restarting')),
+    status TEXT NOT NULL DEFAULT 'stopped' CHECK(status IN ('stopped', 'starting', 'running', 'online', 'stopping', 'crashed', 'updating', 'restarting', 'missing')),
     game_port INTEGER NOT NULL,
     query_port INTEGER NOT NULL,
     rcon_port INTEGER NOT NULL,
@@ -16,7 +16,7 @@ CREATE TABLE IF NOT EXISTS servers (
     map_name TEXT NOT NULL,
     session_name TEXT NOT NULL,
     motd TEXT,
-    mods TEXT,
+    mods TEXT, -- deprecated: the mods table is canonical (cleared by migration 13)
     custom_args TEXT,
     rcon_enabled INTEGER DEFAULT 1,
     ip_address TEXT,
@@ -98,6 +98,7 @@ CREATE TABLE IF NOT EXISTS player_sessions (
     player_name TEXT NOT NULL,
     joined_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
     left_at TIMESTAMP,
+    eos_id TEXT,
     FOREIGN KEY (server_id) REFERENCES servers (id) ON DELETE CASCADE
 );
 
@@ -118,7 +119,7 @@ CREATE TABLE IF NOT EXISTS player_stats (
 CREATE TABLE IF NOT EXISTS scheduled_tasks (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     server_id INTEGER NOT NULL,
-    task_type TEXT NOT NULL CHECK(task_type IN ('restart', 'backup', 'rcon-command', 'announcement', 'save-world', 'destroy-wild-dinos')),
+    task_type TEXT NOT NULL,
     cron_expression TEXT NOT NULL,
     command TEXT,
     message TEXT,
@@ -129,6 +130,24 @@ CREATE TABLE IF NOT EXISTS scheduled_tasks (
     FOREIGN KEY (server_id) REFERENCES servers (id) ON DELETE CASCADE
 );
 
+-- Config templates with {{placeholder}} substitution
+CREATE TABLE IF NOT EXISTS config_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    game_user_settings TEXT,
+    game_ini TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Applied schema migrations (see MIGRATIONS in db/mod.rs)
+CREATE TABLE IF NOT EXISTS schema_version (
+    version INTEGER PRIMARY KEY,
+    description TEXT NOT NULL,
+    applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
 -- Create indexes for performance
 CREATE INDEX IF NOT EXISTS idx_mods_server_id ON mods(server_id);
 CREATE INDEX IF NOT EXISTS idx_backups_server_id ON backups(server_id);
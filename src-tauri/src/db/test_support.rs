@@ -0,0 +1,54 @@
+// Fixtures for tests that need a real database file
+
+use super::Database;
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+
+/// A scratch directory under the system temp dir, removed when dropped
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// `name` keeps parallel tests apart; the process id keeps parallel runs apart
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("asa_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Insert a server with the usual default ports and return its id
+pub fn insert_server(conn: &Connection, name: &str, install_path: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO servers (name, install_path, game_port, query_port, rcon_port, admin_password, map_name, session_name)
+         VALUES (?1, ?2, 7777, 27015, 27020, 'admin', 'TheIsland_WP', ?1)",
+        [name, install_path],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+/// A fresh migrated database holding one server installed at `<dir>/server`
+/// (the folder itself isn't created). Bind the result as `(dir, db, id)` so
+/// the database closes before its directory is removed.
+pub fn db_with_server(name: &str) -> (TempDir, Database, i64) {
+    let dir = TempDir::new(name);
+    let db = Database::new(dir.path().join("asa_manager.db")).unwrap();
+    let install_path = dir.path().join("server");
+    let server_id = insert_server(
+        &db.get_connection().unwrap(),
+        name,
+        &install_path.to_string_lossy(),
+    );
+    (dir, db, server_id)
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use std::fmt::Display;
+use thiserror::Error;
+
+// Command errors for the frontend
+//
+// Serialized as { "kind": "notFound", "message": "..." } so the UI can react to the
+// category while still having a readable message. Plain String errors convert into
+// `Other`, which lets commands move over to ManagerError one at a time.
+
+#[derive(Debug, Clone, PartialEq, Error, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum ManagerError {
+    /// A server, mod, backup, ... that isn't in the DB
+    #[error("{0}")]
+    NotFound(String),
+    /// Filesystem failures (missing install, disk full, permissions)
+    #[error("{0}")]
+    Io(String),
+    /// SQLite failures
+    #[error("{0}")]
+    Db(String),
+    /// RCON disabled, unreachable, bad password or timed out
+    #[error("{0}")]
+    Rcon(String),
+    /// SteamCMD install/update failures
+    #[error("{0}")]
+    Steam(String),
+    /// Bad input or a request that doesn't make sense in the current state
+    #[error("{0}")]
+    Validation(String),
+    /// Anything not categorized yet
+    #[error("{0}")]
+    Other(String),
+}
+
+pub type CommandResult<T> = Result<T, ManagerError>;
+
+impl ManagerError {
+    pub fn not_found(e: impl Display) -> Self {
+        Self::NotFound(e.to_string())
+    }
+
+    pub fn io(e: impl Display) -> Self {
+        Self::Io(e.to_string())
+    }
+
+    pub fn db(e: impl Display) -> Self {
+        Self::Db(e.to_string())
+    }
+
+    pub fn rcon(e: impl Display) -> Self {
+        Self::Rcon(e.to_string())
+    }
+
+    pub fn steam(e: impl Display) -> Self {
+        Self::Steam(e.to_string())
+    }
+
+    pub fn validation(e: impl Display) -> Self {
+        Self::Validation(e.to_string())
+    }
+
+    /// The serialized kind, e.g. "notFound"
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "notFound",
+            Self::Io(_) => "io",
+            Self::Db(_) => "db",
+            Self::Rcon(_) => "rcon",
+            Self::Steam(_) => "steam",
+            Self::Validation(_) => "validation",
+            Self::Other(_) => "other",
+        }
+    }
+}
+
+impl From<String> for ManagerError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<&str> for ManagerError {
+    fn from(message: &str) -> Self {
+        Self::Other(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for ManagerError {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::NotFound => Self::NotFound(e.to_string()),
+            _ => Self::Io(e.to_string()),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for ManagerError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::QueryReturnedNoRows => Self::NotFound(e.to_string()),
+            _ => Self::Db(e.to_string()),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ManagerError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Other(e.to_string())
+    }
+}
+
+/// For callers that still deal in String errors (scheduler, bulk actions, ...)
+impl From<ManagerError> for String {
+    fn from(e: ManagerError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_kind_and_message() {
+        let json = serde_json::to_value(ManagerError::not_found("Server 3 not found")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "notFound", "message": "Server 3 not found" })
+        );
+        assert_eq!(ManagerError::not_found("x").kind(), "notFound");
+    }
+
+    #[test]
+    fn string_errors_convert_both_ways() {
+        let e: ManagerError = "boom".to_string().into();
+        assert_eq!(e, ManagerError::Other("boom".to_string()));
+        assert_eq!(String::from(ManagerError::rcon("timed out")), "timed out");
+    }
+
+    #[test]
+    fn missing_rows_are_not_found() {
+        let e: ManagerError = rusqlite::Error::QueryReturnedNoRows.into();
+        assert_eq!(e.kind(), "notFound");
+    }
+}
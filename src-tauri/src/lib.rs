@@ -1,5 +1,6 @@
 pub mod commands;
 mod db;
+mod error;
 mod models;
 mod services;
 
@@ -14,7 +15,7 @@ use sysinfo::System;
 use tauri::Manager;
 
 pub struct AppState {
-    pub db: Mutex<Database>,
+    pub db: Database,
     pub process_manager: ProcessManager,
     pub sys: Mutex<System>,
     pub app_handle: tauri::AppHandle,
@@ -62,15 +63,25 @@ pub fn run() {
             println!("   Database exists: {}", db_path.exists());
             let db = Database::new(db_path).expect("failed to initialize database");
 
+            // Manager log file, at the level saved in settings
+            let log_level = services::settings::LOG_LEVEL
+                .get(&db)
+                .and_then(|level| services::app_log::parse_level(&level))
+                .unwrap_or(services::app_log::DEFAULT_LEVEL);
+            match services::app_log::init(&app_dir.join("logs"), log_level) {
+                Ok(path) => log::info!("📝 Manager log: {:?} (level {})", path, log_level),
+                Err(e) => println!("⚠️ Failed to open manager log: {}", e),
+            }
+            let rcon_timeout = services::settings::RCON_TIMEOUT_SECS.get(&db);
+
             // RESET SERVER STATUS ON STARTUP
             // Since we lose process handles on restart, we must assume all servers are stopped
             // to prevent "Ghost" online statuses.
             if let Ok(conn) = db.get_connection() {
-                let _ = conn.execute(
-                    "UPDATE servers SET status = 'stopped' WHERE status IN ('running', 'starting', 'restarting', 'updating', 'stopping')",
-                    [],
-                );
+                let _ = services::server_state::reset_stale(&conn);
                 println!("🔄 Reset all server statuses to 'stopped' on startup.");
+                // Servers whose folder was moved show as "missing" until relocated
+                let _ = services::server_state::flag_missing_installs(&conn);
             }
 
             let mut sys = System::new_all();
@@ -83,7 +94,7 @@ pub fn run() {
             // Spawn Auto-Start and Watcher Logic
             
             app.manage(AppState {
-                db: Mutex::new(db),
+                db,
                 process_manager: ProcessManager::new(app_handle.clone()),
                 sys: Mutex::new(sys),
                 app_handle: app_handle.clone(), // Fix duplicate let app_handle
@@ -97,52 +108,79 @@ pub fn run() {
                  let state = app_handle_clone.state::<AppState>();
                  
                  // Access DB to get servers with automation enabled
-                 if let Ok(db) = state.db.lock() {
-                    if let Ok(conn) = db.get_connection() {
-                        // 1. Check for Auto-Start Servers
-                        let mut stmt = conn.prepare("SELECT id, install_path FROM servers WHERE auto_start = 1").unwrap();
-                        let rows = stmt.query_map([], |row| {
-                             Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
-                        }).unwrap();
-                        
-                        for row in rows {
-                            if let Ok((id, _path)) = row {
-                                println!("🚀 Auto-starting server {}", id);
-                                
-                                // Invoke the start_server logic via command logic wrapper
-                                let app_handle_clone_2 = app_handle_clone.clone();
-                                
-                                tauri::async_runtime::spawn(async move {
-                                     let _ = commands::server::start_server(app_handle_clone_2, id).await;
-                                });
-                            }
+                 if let Ok(conn) = state.db.get_connection() {
+                    // 1. Check for Auto-Start Servers
+                    let mut stmt = conn.prepare("SELECT id, install_path FROM servers WHERE auto_start = 1").unwrap();
+                    let rows = stmt.query_map([], |row| {
+                         Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+                    }).unwrap();
+                    
+                    for row in rows {
+                        if let Ok((id, _path)) = row {
+                            println!("🚀 Auto-starting server {}", id);
+                            
+                            // Invoke the start_server logic via command logic wrapper
+                            let app_handle_clone_2 = app_handle_clone.clone();
+                            
+                            tauri::async_runtime::spawn(async move {
+                                 let _ = commands::server::start_server(app_handle_clone_2, id).await;
+                            });
                         }
+                    }
 
-                        // 2. Initialize File Watchers for Auto-Stop
-                        let mut stmt_stop = conn.prepare("SELECT id, install_path FROM servers WHERE auto_stop = 1").unwrap();
-                        let rows_stop = stmt_stop.query_map([], |row| {
-                             Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
-                        }).unwrap();
+                    // 2. Initialize File Watchers for Auto-Stop
+                    let mut stmt_stop = conn.prepare("SELECT id, install_path FROM servers WHERE auto_stop = 1").unwrap();
+                    let rows_stop = stmt_stop.query_map([], |row| {
+                         Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+                    }).unwrap();
 
-                        for row in rows_stop {
-                            if let Ok((id, path)) = row {
-                                let _ = state.file_watcher.start_watching(id, std::path::PathBuf::from(path));
-                            }
+                    for row in rows_stop {
+                        if let Ok((id, path)) = row {
+                            let triggers = services::file_watcher::load_triggers(&conn, id);
+                            let _ = state.file_watcher.start_watching(id, std::path::PathBuf::from(path), triggers);
                         }
                     }
-                };
+                 };
             });
 
             // Initialize RCON state
-            app.manage(RconState(Arc::new(tokio::sync::Mutex::new(
-                RconService::new(),
+            app.manage(RconState(Arc::new(RconService::with_timeout(
+                std::time::Duration::from_secs(rcon_timeout),
             ))));
 
+            // Initialize player session tracking
+            app.manage(commands::player::PlayerIntelligenceState(Arc::new(
+                tokio::sync::Mutex::new(
+                    services::player_intelligence::PlayerIntelligenceService::new(),
+                ),
+            )));
+
             // Initialize Guardian state
             app.manage(services::guardian::GuardianState(Arc::new(
                 tokio::sync::Mutex::new(services::guardian::GuardianService::new()),
             )));
 
+            // Repeat suppression for crash/backup failure notifications
+            app.manage(services::notifications::NotificationThrottleState::default());
+
+            // Remote management API (off unless enabled in settings)
+            app.manage(services::remote_api::RemoteApiState(
+                tokio::sync::Mutex::new(None),
+            ));
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = services::remote_api::start(&app_handle).await {
+                    println!("❌ Remote API not started: {}", e);
+                }
+            });
+
+            // Scheduled task runner
+            services::scheduler::start(app.handle().clone());
+
+            // In-game chat relay
+            services::chat_relay::start(app.handle().clone());
+            services::chat_bridge::start(app.handle().clone());
+
             // Check and install SteamCMD
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
@@ -167,42 +205,94 @@ pub fn run() {
             commands::system::select_plugin_zip,
             commands::system::get_setting,
             commands::system::set_setting,
+            commands::system::validate_curseforge_key,
+            commands::system::get_all_settings,
+            commands::system::save_all_settings,
+            commands::system::export_database,
+            commands::system::import_database,
             commands::system::run_diagnostics,
+            commands::system::export_diagnostics_bundle,
             commands::system::install_steamcmd, // <-- New Command
             // Server commands
             commands::server::get_all_servers,
+            commands::server::count_servers,
+            commands::server::add_server_tag,
+            commands::server::remove_server_tag,
+            commands::server::get_server_tags,
+            commands::server::get_all_tags,
+            commands::server::get_servers_by_tag,
             commands::server::get_server_by_id,
             commands::server::install_server,
+            commands::server::suggest_free_ports,
+            commands::server::preflight_check,
             commands::server::start_server,
             commands::server::start_server_no_mods,
             commands::server::stop_server,
             commands::server::restart_server,
+            commands::server::bulk_server_action,
+            commands::server::stop_all_servers,
+            commands::server::begin_maintenance,
             commands::server::delete_server,
             commands::server::update_server,
+            commands::server::cancel_install,
             commands::server::update_server_settings,
             commands::server::clone_server,
+            commands::server::install_server_from_existing,
+            commands::server::export_server,
+            commands::server::import_server_definition,
             commands::server::transfer_settings,
             commands::server::extract_save_data,
             commands::server::check_server_reachability,
+            commands::server::open_ports_upnp,
+            commands::server::close_ports_upnp,
             commands::server::start_log_watcher,
             commands::server::import_server,
+            commands::server::scan_for_servers,
+            commands::server::relocate_server,
             commands::server::show_server_console,
+            commands::server::send_console_command,
             commands::server::toggle_automation,
+            commands::server::get_auto_stop_triggers,
+            commands::server::set_auto_stop_triggers,
+            commands::server::get_launch_flag_catalog,
+            commands::server::get_server_launch_flags,
+            commands::server::set_server_launch_flags,
+            commands::server::set_crossplay_platforms,
+            commands::server::get_mod_strategies,
+            commands::server::get_mod_strategy,
+            commands::server::set_mod_strategy,
+            commands::server::apply_launch_preset,
+            commands::server::get_server_uptime,
             commands::import::import_non_dedicated_save, // <-- New Command
+            // Log commands
+            commands::logs::search_logs,
+            commands::logs::cleanup_logs,
+            commands::logs::get_recent_logs,
+            commands::logs::get_log_level,
+            commands::logs::set_log_level,
             // Mod commands
             commands::mods::search_mods,
+            commands::mods::clear_mod_search_cache,
             commands::mods::get_mod_description,
             commands::mods::install_mod,
             commands::mods::uninstall_mod,
             commands::mods::get_installed_mods,
+            commands::mods::refresh_mod_metadata,
             commands::mods::update_mod_order,
             commands::mods::toggle_mod,
             commands::mods::verify_mod_integrity,
+            commands::mods::detect_mod_conflicts,
+            commands::mods::get_mod_load_rules,
+            commands::mods::save_mod_load_rules,
+            commands::mods::apply_profile_mods,
+            commands::mods::export_mod_list,
+            commands::mods::import_mod_list,
             commands::mods::validate_mod_ids,
             commands::mods::generate_mod_config,
             commands::mods::apply_mods_to_server,
             commands::mods::get_mod_install_instructions,
             commands::mods::hardcore_retry_mods,
+            commands::mods::predownload_mods,
             commands::mods::copy_mods_to_server,
 
             // Config commands
@@ -211,6 +301,12 @@ pub fn run() {
             commands::config::backup_config,
             commands::config::restore_config,
             commands::config::list_config_backups,
+            commands::config::check_config_drift,
+            commands::config::resolve_config_drift,
+            commands::config::get_effective_config,
+            commands::config::start_server_with_overrides,
+            commands::config::revert_overrides,
+            commands::config::get_active_overrides,
             // Config generator commands
             commands::config::get_map_profiles,
             commands::config::get_map_profile,
@@ -221,8 +317,17 @@ pub fn run() {
             commands::config::write_server_configs,
             commands::config::backup_all_configs,
             commands::config::get_default_config,
+            commands::config::get_server_generator_config,
+            // Config template commands
+            commands::template::get_config_templates,
+            commands::template::create_config_template,
+            commands::template::update_config_template,
+            commands::template::delete_config_template,
+            commands::template::apply_template,
             // Cluster commands
             commands::cluster::create_cluster,
+            commands::cluster::import_cluster,
+            commands::cluster::apply_config_to_cluster,
             commands::cluster::get_clusters,
             commands::cluster::delete_cluster,
             commands::cluster::get_cluster_status,
@@ -232,6 +337,9 @@ pub fn run() {
             commands::backup::create_backup,
             commands::backup::get_backups,
             commands::backup::restore_backup,
+            commands::backup::restore_single_file,
+            commands::backup::upload_backup,
+            commands::backup::download_backup_from_cloud,
             commands::backup::delete_backup,
             commands::backup::verify_backup,
             commands::backup::get_backup_contents,
@@ -242,12 +350,17 @@ pub fn run() {
             commands::scheduler::toggle_scheduled_task,
             commands::scheduler::delete_scheduled_task,
             commands::scheduler::update_task_last_run,
+            commands::scheduler::get_task_history,
+            commands::scheduler::run_scheduled_task_now,
             // RCON commands
             commands::rcon::rcon_connect,
             commands::rcon::rcon_disconnect,
             commands::rcon::rcon_send_command,
             commands::rcon::rcon_get_players,
             commands::rcon::rcon_broadcast,
+            commands::rcon::rcon_send_all,
+            commands::rcon::rcon_broadcast_all,
+            commands::rcon::rcon_send_chat,
             commands::rcon::rcon_kick_player,
             commands::rcon::rcon_ban_player,
             commands::rcon::rcon_unban_player,
@@ -256,6 +369,26 @@ pub fn run() {
             commands::rcon::rcon_set_time,
             commands::rcon::rcon_message_player,
             commands::rcon::rcon_is_connected,
+            commands::rcon::rcon_send_raw,
+            commands::rcon::rcon_test,
+            commands::rcon::get_rcon_history,
+            commands::rcon::clear_rcon_history,
+            commands::rcon::save_rcon_favorite,
+            commands::rcon::get_rcon_favorites,
+            commands::rcon::delete_rcon_favorite,
+            commands::rcon::get_rcon_command_catalog,
+            // Notification commands
+            commands::notifications::get_discord_config,
+            commands::notifications::save_discord_config,
+            commands::notifications::get_chat_bridge_config,
+            commands::notifications::save_chat_bridge_config,
+            commands::notifications::get_notification_channels,
+            commands::notifications::save_notification_channels,
+            commands::notifications::test_notification,
+            // Remote API commands
+            commands::remote_api::get_remote_api_status,
+            commands::remote_api::restart_remote_api,
+            commands::remote_api::regenerate_remote_api_token,
             // Guardian commands
             services::guardian::get_server_health,
             services::guardian::get_all_server_health,
@@ -269,15 +402,31 @@ pub fn run() {
             commands::player::update_player_notes,
             commands::player::set_player_whitelist,
             commands::player::set_player_ban,
+            commands::player::bulk_set_player_whitelist,
+            commands::player::bulk_set_player_ban,
+            commands::player::sync_whitelist_to_server,
+            commands::player::sync_banlist_to_server,
+            commands::player::export_banlist,
+            commands::player::export_whitelist,
+            commands::player::import_banlist,
+            commands::player::import_whitelist,
             commands::player::record_player_session,
+            commands::player::sync_player_sessions,
             commands::player::search_players,
+            commands::player::get_player_leaderboard,
+            commands::player::get_server_activity,
             // Plugin commands
             commands::plugin::check_asa_api_installed,
+            commands::plugin::install_asa_api,
+            commands::plugin::get_asa_api_version,
             commands::plugin::get_plugin_directory,
             commands::plugin::import_plugin_archive,
+            commands::plugin::install_plugin_from_url,
             commands::plugin::get_installed_plugins,
             commands::plugin::uninstall_plugin,
             commands::plugin::toggle_plugin,
+            commands::plugin::read_plugin_config,
+            commands::plugin::write_plugin_config,
             // File Manager commands
             commands::file_manager::read_directory,
             commands::file_manager::read_file_content,
@@ -288,6 +437,9 @@ pub fn run() {
             commands::file_manager::rename_item,
             commands::file_manager::delete_item,
             commands::file_manager::open_in_explorer,
+            commands::file_manager::search_files,
+            commands::file_manager::copy_item,
+            commands::file_manager::move_item,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 // ARK Server Manager 2.0 - ASA and ASE Models
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ServerStatus {
     Stopped,
@@ -13,6 +13,26 @@ pub enum ServerStatus {
     Updating,
     Restarting,
     Online,
+    Stopping,
+    /// The registered install_path no longer exists on disk
+    Missing,
+}
+
+impl ServerStatus {
+    /// Parse the servers.status column; unknown values read as stopped
+    pub fn from_db(status: &str) -> Self {
+        match status {
+            "starting" => ServerStatus::Starting,
+            "running" => ServerStatus::Running,
+            "crashed" => ServerStatus::Crashed,
+            "updating" => ServerStatus::Updating,
+            "restarting" => ServerStatus::Restarting,
+            "online" => ServerStatus::Online,
+            "stopping" => ServerStatus::Stopping,
+            "missing" => ServerStatus::Missing,
+            _ => ServerStatus::Stopped,
+        }
+    }
 }
 
 impl ToString for ServerStatus {
@@ -25,6 +45,8 @@ impl ToString for ServerStatus {
             ServerStatus::Updating => "updating".to_string(),
             ServerStatus::Restarting => "restarting".to_string(),
             ServerStatus::Online => "online".to_string(),
+            ServerStatus::Stopping => "stopping".to_string(),
+            ServerStatus::Missing => "missing".to_string(),
         }
     }
 }
@@ -46,6 +68,25 @@ pub struct Server {
     pub auto_start: bool,
     pub auto_stop: bool,
     pub intelligent_mode: bool,
+    pub auto_port_forward: bool,
+    #[serde(default)]
+    pub use_asa_api: bool,
+    #[serde(default)]
+    pub backup_before_restart: bool,
+    /// Launch with -UseBattlEye instead of -NoBattlEye
+    #[serde(default)]
+    pub battleye_enabled: bool,
+}
+
+/// Optional narrowing for server listings; empty fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerFilter {
+    /// Any of these statuses (e.g. ["running", "online"])
+    pub statuses: Vec<String>,
+    pub cluster_id: Option<i64>,
+    /// Servers carrying this tag (case-insensitive)
+    pub tag: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +97,9 @@ pub struct ServerPorts {
     pub rcon_port: u16,
 }
 
+/// Admin/RCON password a new or imported server gets until the user sets one
+pub const DEFAULT_ADMIN_PASSWORD: &str = "admin123";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerConfig {
@@ -67,6 +111,9 @@ pub struct ServerConfig {
     pub motd: Option<String>,
     pub mods: Vec<String>,
     pub custom_args: Option<String>,
+    /// -ServerPlatform names (PC, XSX, PS5, WINGDK or ALL); empty means no crossplay flag
+    #[serde(default)]
+    pub crossplay_platforms: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +139,12 @@ pub struct ModInfo {
     pub enabled: bool,
     pub load_order: i32,
     pub last_updated: Option<String>,
+    /// Newest ASA version the installed file targets, from refresh_mod_metadata
+    #[serde(default)]
+    pub game_version: Option<String>,
+    /// Newest CurseForge file name, from refresh_mod_metadata; `version` stays what was installed
+    #[serde(default)]
+    pub latest_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +160,11 @@ pub struct Backup {
     pub includes_saves: bool,
     pub includes_cluster: bool,
     pub verified: bool,
+    #[serde(default)]
+    pub encrypted: bool,
+    /// s3://bucket/key once uploaded to cloud storage
+    #[serde(default)]
+    pub remote_url: Option<String>,
     pub created_at: String,
 }
 
@@ -192,6 +250,27 @@ pub struct RconPlayer {
     pub steam_id: String,
 }
 
+/// A ListPlayers row with the raw UE net id split into its Steam/EOS form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerEntry {
+    pub index: i64,
+    pub name: String,
+    pub net_id: String,
+    pub steam_id: Option<String>,
+    pub eos_id: Option<String>,
+}
+
+impl PlayerEntry {
+    /// The id sessions are keyed on: the Steam ID when known, otherwise the EOS id
+    pub fn player_id(&self) -> &str {
+        self.steam_id
+            .as_deref()
+            .or(self.eos_id.as_deref())
+            .unwrap_or(&self.net_id)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RconResponse {
@@ -200,6 +279,50 @@ pub struct RconResponse {
     pub data: Option<String>,
 }
 
+/// Outcome of a one-off RCON credentials check. `failure` is one of
+/// "refused", "auth_failed", "timeout", "unreachable" or "command_failed".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RconTestResult {
+    pub success: bool,
+    pub failure: Option<String>,
+    pub message: String,
+    pub response: Option<String>,
+}
+
+/// A console command as persisted (passwords already masked)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RconHistoryEntry {
+    pub id: i64,
+    pub server_id: i64,
+    pub command: String,
+    pub response_snippet: Option<String>,
+    pub success: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RconFavorite {
+    pub id: i64,
+    pub server_id: i64,
+    pub label: String,
+    pub command: String,
+}
+
+/// One in-game chat line relayed from GetChat, or a message sent from the manager
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessage {
+    pub server_id: i64,
+    pub sender: Option<String>,
+    pub message: String,
+    pub raw: String,
+    pub timestamp: String,
+    pub from_manager: bool,
+}
+
 // Player Intelligence Models
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -215,6 +338,15 @@ pub struct PlayerStats {
     pub is_banned: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSearchResult {
+    #[serde(flatten)]
+    pub player: PlayerStats,
+    pub last_server_id: Option<i64>,
+    pub last_server_name: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerSession {
@@ -224,6 +356,8 @@ pub struct PlayerSession {
     pub player_name: String,
     pub joined_at: String,
     pub left_at: Option<String>,
+    #[serde(default)]
+    pub eos_id: Option<String>,
 }
 
 // Backup Options
@@ -235,6 +369,16 @@ pub struct BackupOptions {
     pub include_saves: bool,
     pub include_cluster: bool,
     pub compression_level: i32, // 0-9
+    /// SaveWorld over RCON and let the write settle before copying saves of a running server
+    #[serde(default = "default_true")]
+    pub quiesce: bool,
+    /// AES-encrypt the archive with the backup_encryption_key setting
+    #[serde(default)]
+    pub encrypt: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for BackupOptions {
@@ -245,6 +389,8 @@ impl Default for BackupOptions {
             include_saves: true,
             include_cluster: false,
             compression_level: 6,
+            quiesce: true,
+            encrypt: false,
         }
     }
 }
@@ -269,6 +415,23 @@ impl Default for RestoreOptions {
     }
 }
 
+/// Post-restore sanity check of the world save against the server's configured map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreValidation {
+    /// False when any issue was found; warnings alone don't fail it
+    pub ok: bool,
+    pub map_name: String,
+    pub world_file: Option<String>,
+    pub world_size: u64,
+    pub profile_count: usize,
+    pub tribe_count: usize,
+    /// Maps that have a .ark world in SavedArks after the restore
+    pub found_maps: Vec<String>,
+    pub issues: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
 // Scheduled Tasks
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -298,6 +461,7 @@ pub enum TaskType {
     Update,
     SaveWorld,
     DestroyWildDinos,
+    CleanupLogs,
 }
 
 impl ToString for TaskType {
@@ -310,14 +474,14 @@ impl ToString for TaskType {
             TaskType::Update => "update".to_string(),
             TaskType::SaveWorld => "save-world".to_string(),
             TaskType::DestroyWildDinos => "destroy-wild-dinos".to_string(),
+            TaskType::CleanupLogs => "cleanup-logs".to_string(),
         }
     }
 }
 
 // Discord Webhook Config
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
+#[serde(rename_all = "camelCase", default)]
 pub struct DiscordConfig {
     pub webhook_url: Option<String>,
     pub notify_server_start: bool,
@@ -326,6 +490,8 @@ pub struct DiscordConfig {
     pub notify_player_join: bool,
     pub notify_player_leave: bool,
     pub notify_scheduled_task: bool,
+    pub notify_backup: bool,
+    pub notify_server_update: bool,
 }
 
 impl Default for DiscordConfig {
@@ -338,10 +504,70 @@ impl Default for DiscordConfig {
             notify_player_join: false,
             notify_player_leave: false,
             notify_scheduled_task: true,
+            notify_backup: true,
+            notify_server_update: true,
         }
     }
 }
 
+/// One condition that auto-stops a watched server. `pattern` is relative to the
+/// install root, with '/' separators.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoStopTrigger {
+    pub match_type: TriggerMatch,
+    pub pattern: String,
+    /// Filesystem changes that count; create and modify when empty
+    #[serde(default)]
+    pub events: Vec<TriggerEvent>,
+    /// Skip while the server is booting (ARK rewrites its own INIs then)
+    #[serde(default)]
+    pub ignore_during_startup: bool,
+    /// Delete the matched file once acted on, for one-shot marker files
+    #[serde(default)]
+    pub consume: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerMatch {
+    /// Exactly this file
+    File,
+    /// `*` and `?` within a path segment, `**` across segments
+    Glob,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerEvent {
+    Create,
+    Modify,
+    Remove,
+    /// Renamed into or out of place, which is how ARK finishes writing a save
+    Rename,
+}
+
+/// Two-way chat bridge between in-game chat and Discord. In-game chat is posted
+/// through each link's webhook; with a bot token, messages in the linked channel
+/// are relayed back into the game.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChatBridgeConfig {
+    pub bot_token: Option<String>,
+    pub links: Vec<ChatBridgeLink>,
+}
+
+/// Channel mapping for one server. Servers without a link are not bridged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChatBridgeLink {
+    pub server_id: i64,
+    pub enabled: bool,
+    pub webhook_url: Option<String>,
+    /// Discord channel read for Discord -> game relaying; needs the bot token
+    pub channel_id: Option<String>,
+}
+
 // Plugin Info for ASA Server API Plugins
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
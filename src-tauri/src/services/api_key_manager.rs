@@ -1,17 +1,33 @@
+use crate::services::mod_scraper::{ASA_GAME_ID, CURSEFORGE_API_URL};
+use crate::services::settings;
 use crate::AppState;
+use serde::Serialize;
 use tauri::State;
 
 pub struct ApiKeyManager;
 
+/// Outcome of checking an API key against its service
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyValidation {
+    pub valid: bool,
+    pub reason: Option<String>,
+}
+
+impl KeyValidation {
+    fn invalid(reason: impl Into<String>) -> Self {
+        Self {
+            valid: false,
+            reason: Some(reason.into()),
+        }
+    }
+}
+
 impl ApiKeyManager {
     pub fn get_curseforge_key(state: &State<'_, AppState>) -> Option<String> {
         // 1. Try to get from Database
-        if let Ok(db) = state.db.lock() {
-            if let Ok(Some(key)) = db.get_setting("curseforge_api_key") {
-                if !key.trim().is_empty() {
-                    return Some(key.trim().to_string());
-                }
-            }
+        if let Some(key) = settings::CURSEFORGE_API_KEY.load(state) {
+            return Some(key);
         }
 
         // 2. Fallback to Environment Variable
@@ -23,4 +39,48 @@ impl ApiKeyManager {
 
         None
     }
+
+    /// Check a CurseForge key with one small authenticated request (the ASA game entry)
+    pub async fn validate_curseforge_key(key: &str) -> KeyValidation {
+        let key = key.trim();
+        if key.is_empty() {
+            return KeyValidation::invalid("No API key entered");
+        }
+
+        let client = match reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => return KeyValidation::invalid(format!("HTTP client error: {}", e)),
+        };
+
+        let url = format!("{}/games/{}", CURSEFORGE_API_URL, ASA_GAME_ID);
+        match client.get(&url).header("x-api-key", key).send().await {
+            Ok(resp) if resp.status().is_success() => KeyValidation {
+                valid: true,
+                reason: None,
+            },
+            Ok(resp) if matches!(resp.status().as_u16(), 401 | 403) => {
+                KeyValidation::invalid("CurseForge rejected the key. Check it was copied in full.")
+            }
+            Ok(resp) => KeyValidation::invalid(format!(
+                "CurseForge returned HTTP {}; try again later",
+                resp.status().as_u16()
+            )),
+            Err(e) => KeyValidation::invalid(format!("Could not reach CurseForge: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn empty_key_is_invalid_without_a_request() {
+        let result = ApiKeyManager::validate_curseforge_key("   ").await;
+        assert!(!result.valid);
+        assert_eq!(result.reason.as_deref(), Some("No API key entered"));
+    }
 }
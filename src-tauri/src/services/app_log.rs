@@ -0,0 +1,240 @@
+// Manager log: leveled records written to a size-rotated file in app_data_dir/logs
+// Records are echoed to stdout as well, so the dev console reads the same as the old
+// println! output. Other crates' records are only kept at warn and above.
+
+use chrono::Local;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+pub const LOG_FILE_NAME: &str = "manager.log";
+pub const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+/// Size at which manager.log is rotated to manager.1.log
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the active one
+const KEEP_ROTATED: usize = 5;
+
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+struct AppLogger {
+    file: Mutex<Option<LogFile>>,
+}
+
+static LOGGER: AppLogger = AppLogger {
+    file: Mutex::new(None),
+};
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let ours = metadata.target().starts_with(env!("CARGO_CRATE_NAME"));
+        metadata.level() <= log::max_level() && (ours || metadata.level() <= Level::Warn)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        println!("{}", record.args());
+
+        let line = format!(
+            "{} {:<5} [{}] {}\n",
+            Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        let Ok(mut guard) = self.file.lock() else {
+            return;
+        };
+        let Some(log_file) = guard.as_mut() else {
+            return;
+        };
+
+        if log_file.size + line.len() as u64 > MAX_FILE_BYTES {
+            rotate(&log_file.path, KEEP_ROTATED);
+            match open_append(&log_file.path) {
+                Ok(file) => {
+                    log_file.file = file;
+                    log_file.size = 0;
+                }
+                Err(_) => return,
+            }
+        }
+        if log_file.file.write_all(line.as_bytes()).is_ok() {
+            log_file.size += line.len() as u64;
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut guard) = self.file.lock() {
+            if let Some(log_file) = guard.as_mut() {
+                let _ = log_file.file.flush();
+            }
+        }
+    }
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// manager.log -> manager.1.log -> ... -> manager.{keep}.log; the oldest is dropped
+fn rotate(path: &Path, keep: usize) {
+    let _ = fs::remove_file(rotated_path(path, keep));
+    for n in (1..keep).rev() {
+        let _ = fs::rename(rotated_path(path, n), rotated_path(path, n + 1));
+    }
+    let _ = fs::rename(path, rotated_path(path, 1));
+}
+
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("manager");
+    path.with_file_name(format!("{}.{}.log", stem, n))
+}
+
+/// Install the logger, writing to `log_dir`/manager.log. Safe to call more than once;
+/// later calls only switch the file and level.
+pub fn init(log_dir: &Path, level: LevelFilter) -> Result<PathBuf, String> {
+    fs::create_dir_all(log_dir).map_err(|e| format!("Failed to create log folder: {}", e))?;
+    let path = log_dir.join(LOG_FILE_NAME);
+    let file = open_append(&path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+    if let Ok(mut guard) = LOGGER.file.lock() {
+        *guard = Some(LogFile {
+            path: path.clone(),
+            file,
+            size,
+        });
+    }
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+    Ok(path)
+}
+
+/// "error", "warn", "info", "debug", "trace" or "off" (any case)
+pub fn parse_level(level: &str) -> Option<LevelFilter> {
+    LevelFilter::from_str(level.trim()).ok()
+}
+
+pub fn set_level(level: LevelFilter) {
+    log::set_max_level(level);
+}
+
+pub fn current_level() -> LevelFilter {
+    log::max_level()
+}
+
+/// Push buffered records to disk before the file is read elsewhere
+pub fn flush() {
+    LOGGER.flush();
+}
+
+/// Path of the active log file, once init has run
+pub fn log_path() -> Option<PathBuf> {
+    LOGGER
+        .file
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(|f| f.path.clone()))
+}
+
+/// Last `lines` lines of the manager log, reaching into manager.1.log when the
+/// active file was rotated recently
+pub fn recent_lines(lines: usize) -> Result<Vec<String>, String> {
+    let path = log_path().ok_or("Manager log is not initialized")?;
+    flush();
+
+    let mut tail = tail_lines(&path, lines)?;
+    if tail.len() < lines {
+        let previous = rotated_path(&path, 1);
+        if previous.exists() {
+            let mut older = tail_lines(&previous, lines - tail.len())?;
+            older.append(&mut tail);
+            tail = older;
+        }
+    }
+    Ok(tail)
+}
+
+fn tail_lines(path: &Path, lines: usize) -> Result<Vec<String>, String> {
+    if lines == 0 {
+        return Ok(Vec::new());
+    }
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {:?}: {}", path, e)),
+    };
+
+    let mut tail = VecDeque::with_capacity(lines.min(4096));
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        if tail.len() == lines {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+    Ok(tail.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("asa_app_log_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rotate_shifts_files_and_drops_the_oldest() {
+        let dir = temp_dir("rotate");
+        let path = dir.join(LOG_FILE_NAME);
+        fs::write(&path, "current").unwrap();
+        fs::write(rotated_path(&path, 1), "one").unwrap();
+        fs::write(rotated_path(&path, 2), "two").unwrap();
+
+        rotate(&path, 2);
+
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+            "current"
+        );
+        assert_eq!(fs::read_to_string(rotated_path(&path, 2)).unwrap(), "one");
+        assert!(!rotated_path(&path, 3).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn tail_keeps_the_last_lines() {
+        let dir = temp_dir("tail");
+        let path = dir.join(LOG_FILE_NAME);
+        fs::write(&path, "a\nb\nc\nd\n").unwrap();
+
+        assert_eq!(tail_lines(&path, 2).unwrap(), vec!["c", "d"]);
+        assert_eq!(tail_lines(&path, 10).unwrap().len(), 4);
+        assert!(tail_lines(&dir.join("missing.log"), 5).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parses_levels_case_insensitively() {
+        assert_eq!(parse_level("DEBUG"), Some(LevelFilter::Debug));
+        assert_eq!(parse_level(" warn "), Some(LevelFilter::Warn));
+        assert_eq!(parse_level("loud"), None);
+    }
+}
@@ -1,24 +1,28 @@
 // Backup Service for ASA Server Manager
 // Handles real backup creation, restoration, and management
 
-use crate::models::{Backup, BackupOptions, BackupType, RestoreOptions};
+use crate::models::{Backup, BackupOptions, BackupType, RestoreOptions, RestoreValidation};
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use zip::result::ZipError;
 use zip::write::FileOptions;
-use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use zip::{AesMode, CompressionMethod, ZipArchive, ZipWriter};
 
 /// Backup service for managing server backups
 pub struct BackupService;
 
 impl BackupService {
-    /// Create a full backup of the server's saved data
+    /// Create a full backup of the server's saved data.
+    /// With a password every entry is AES-256 encrypted; without that password
+    /// the archive cannot be read back by anyone, including us.
     pub fn create_backup(
         server_path: &Path,
         backup_dir: &Path,
         server_id: i64,
         backup_type: BackupType,
         options: &BackupOptions,
+        password: Option<&str>,
     ) -> Result<Backup, String> {
         // Create backup directory if it doesn't exist
         fs::create_dir_all(backup_dir)
@@ -40,9 +44,12 @@ impl BackupService {
         };
 
         #[allow(deprecated)]
-        let file_options = FileOptions::default()
+        let mut file_options = FileOptions::default()
             .compression_method(compression)
             .unix_permissions(0o644);
+        if let Some(password) = password {
+            file_options = file_options.with_aes_encryption(AesMode::Aes256, password);
+        }
 
         let mut total_size: u64 = 0;
         let mut includes_configs = false;
@@ -107,6 +114,8 @@ impl BackupService {
             includes_saves,
             includes_cluster,
             verified: false,
+            encrypted: password.is_some(),
+            remote_url: None,
             created_at: chrono::Local::now().to_rfc3339(),
         };
 
@@ -160,8 +169,19 @@ impl BackupService {
         Ok(total_size)
     }
 
+    /// Explain a failed entry read; encrypted archives fail here without the right password
+    fn entry_error(e: ZipError) -> String {
+        match e {
+            ZipError::InvalidPassword => "Wrong backup password".to_string(),
+            ZipError::UnsupportedArchive(msg) if msg == ZipError::PASSWORD_REQUIRED => {
+                "This backup is encrypted; a password is required".to_string()
+            }
+            e => format!("Failed to read archive entry: {}", e),
+        }
+    }
+
     /// Verify backup integrity
-    pub fn verify_backup(backup_path: &Path) -> Result<bool, String> {
+    pub fn verify_backup(backup_path: &Path, password: Option<&str>) -> Result<bool, String> {
         let file =
             File::open(backup_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
 
@@ -170,9 +190,11 @@ impl BackupService {
 
         // Try to read each file in the archive
         for i in 0..archive.len() {
-            let mut file = archive
-                .by_index(i)
-                .map_err(|e| format!("Failed to read archive entry {}: {}", i, e))?;
+            let mut file = match password {
+                Some(password) => archive.by_index_decrypt(i, password.as_bytes()),
+                None => archive.by_index(i),
+            }
+            .map_err(Self::entry_error)?;
 
             // Read file contents to verify
             let mut buffer = Vec::new();
@@ -189,6 +211,7 @@ impl BackupService {
         backup_path: &Path,
         server_path: &Path,
         options: &RestoreOptions,
+        password: Option<&str>,
     ) -> Result<(), String> {
         let file =
             File::open(backup_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
@@ -197,9 +220,11 @@ impl BackupService {
             ZipArchive::new(file).map_err(|e| format!("Invalid backup archive: {}", e))?;
 
         for i in 0..archive.len() {
-            let mut file = archive
-                .by_index(i)
-                .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+            let mut file = match password {
+                Some(password) => archive.by_index_decrypt(i, password.as_bytes()),
+                None => archive.by_index(i),
+            }
+            .map_err(Self::entry_error)?;
 
             let out_path = match file.enclosed_name() {
                 Some(path) => path.to_owned(),
@@ -207,21 +232,11 @@ impl BackupService {
             };
 
             // Determine the target path based on the backup structure
-            let target_path = if out_path.starts_with("SavedArks") {
-                if !options.restore_saves {
-                    continue;
-                }
-                server_path.join("ShooterGame/Saved").join(&out_path)
-            } else if out_path.starts_with("Config") {
-                if !options.restore_configs {
-                    continue;
-                }
-                let relative = out_path.strip_prefix("Config").unwrap();
-                server_path
-                    .join("ShooterGame/Saved/Config/WindowsServer")
-                    .join(relative)
-            } else {
-                continue;
+            let wanted = (out_path.starts_with("SavedArks") && options.restore_saves)
+                || (out_path.starts_with("Config") && options.restore_configs);
+            let target_path = match Self::restore_target(server_path, &out_path) {
+                Some(target) if wanted => target,
+                _ => continue,
             };
 
             if file.name().ends_with('/') {
@@ -245,6 +260,163 @@ impl BackupService {
         Ok(())
     }
 
+    /// Where an archive entry lives in the server install, by its top-level folder
+    fn restore_target(server_path: &Path, entry: &Path) -> Option<PathBuf> {
+        if entry.starts_with("SavedArks") {
+            Some(server_path.join("ShooterGame/Saved").join(entry))
+        } else if let Ok(relative) = entry.strip_prefix("Config") {
+            Some(
+                server_path
+                    .join("ShooterGame/Saved/Config/WindowsServer")
+                    .join(relative),
+            )
+        } else if entry.starts_with("clusters") {
+            Some(server_path.join("ShooterGame/Saved").join(entry))
+        } else {
+            None
+        }
+    }
+
+    /// Extract one file (e.g. a single .arkprofile or .arktribe) from a backup.
+    /// `dest_override` may be a directory (file keeps its name) or a full file path.
+    pub fn restore_single_file(
+        backup_path: &Path,
+        server_path: &Path,
+        inner_path: &str,
+        dest_override: Option<&Path>,
+        password: Option<&str>,
+    ) -> Result<PathBuf, String> {
+        let file =
+            File::open(backup_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
+        let mut archive =
+            ZipArchive::new(file).map_err(|e| format!("Invalid backup archive: {}", e))?;
+
+        let inner_path = inner_path.replace('\\', "/");
+        let mut entry = match password {
+            Some(password) => archive.by_name_decrypt(&inner_path, password.as_bytes()),
+            None => archive.by_name(&inner_path),
+        }
+        .map_err(|e| match e {
+            ZipError::FileNotFound => format!("'{}' is not in this backup", inner_path),
+            e => Self::entry_error(e),
+        })?;
+        if entry.is_dir() {
+            return Err(format!("'{}' is a folder, pick a file", inner_path));
+        }
+        let entry_path = entry
+            .enclosed_name()
+            .ok_or_else(|| format!("Unsafe path in archive: {}", inner_path))?;
+
+        let target_path = match dest_override {
+            Some(dest) if dest.is_dir() => dest.join(entry_path.file_name().unwrap_or_default()),
+            Some(dest) => dest.to_path_buf(),
+            None => Self::restore_target(server_path, &entry_path)
+                .ok_or_else(|| format!("Don't know where '{}' belongs", inner_path))?,
+        };
+
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+        }
+        let mut out_file =
+            File::create(&target_path).map_err(|e| format!("Failed to create file: {}", e))?;
+        std::io::copy(&mut entry, &mut out_file)
+            .map_err(|e| format!("Failed to extract file: {}", e))?;
+
+        println!("✅ Restored {} to {:?}", inner_path, target_path);
+        Ok(target_path)
+    }
+
+    /// Check that a restored SavedArks will load: the configured map's .ark is
+    /// present and non-empty, and player/tribe files aren't truncated
+    pub fn validate_restored_saves(server_path: &Path, map_name: &str) -> RestoreValidation {
+        let saved_arks = server_path.join("ShooterGame/Saved/SavedArks");
+        let mut issues = Vec::new();
+        let mut warnings = Vec::new();
+
+        // ASA keeps each map in its own folder: SavedArks/<Map>/<Map>.ark
+        let mut found_maps = Vec::new();
+        let mut profile_count = 0;
+        let mut tribe_count = 0;
+        for entry in walkdir::WalkDir::new(&saved_arks)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let ext = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let name = path
+                .strip_prefix(&saved_arks)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string();
+
+            match ext.as_str() {
+                "ark" => {
+                    if let Some(stem) = path.file_stem() {
+                        found_maps.push(stem.to_string_lossy().to_string());
+                    }
+                }
+                "arkprofile" => profile_count += 1,
+                "arktribe" => tribe_count += 1,
+                _ => continue,
+            }
+            if size == 0 {
+                issues.push(format!("{} is empty (truncated archive?)", name));
+            }
+        }
+        found_maps.sort();
+        found_maps.dedup();
+
+        let world_file = [
+            saved_arks.join(map_name).join(format!("{}.ark", map_name)),
+            saved_arks.join(format!("{}.ark", map_name)),
+        ]
+        .into_iter()
+        .find(|p| p.is_file());
+        let world_size = world_file
+            .as_ref()
+            .and_then(|p| fs::metadata(p).ok())
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        // An empty world file was already reported above
+        if world_file.is_none() {
+            if found_maps.is_empty() {
+                issues.push(format!("No world save found for {}", map_name));
+            } else {
+                issues.push(format!(
+                    "Backup has saves for {} but the server is configured for {}",
+                    found_maps.join(", "),
+                    map_name
+                ));
+            }
+        }
+
+        if profile_count == 0 {
+            warnings.push("No player profiles (.arkprofile) found".to_string());
+        }
+        if tribe_count == 0 {
+            warnings.push("No tribe files (.arktribe) found".to_string());
+        }
+
+        RestoreValidation {
+            ok: issues.is_empty(),
+            map_name: map_name.to_string(),
+            world_file: world_file.map(|p| p.to_string_lossy().to_string()),
+            world_size,
+            profile_count,
+            tribe_count,
+            found_maps,
+            issues,
+            warnings,
+        }
+    }
+
     /// Get backup preview (list of files in backup)
     pub fn get_backup_contents(backup_path: &Path) -> Result<Vec<String>, String> {
         let file =
@@ -253,9 +425,10 @@ impl BackupService {
         let mut archive =
             ZipArchive::new(file).map_err(|e| format!("Invalid backup archive: {}", e))?;
 
+        // Raw access lists names without decrypting, so encrypted backups can be browsed
         let mut contents: Vec<String> = Vec::new();
         for i in 0..archive.len() {
-            if let Ok(f) = archive.by_index(i) {
+            if let Ok(f) = archive.by_index_raw(i) {
                 if let Some(name) = f.enclosed_name() {
                     contents.push(name.to_string_lossy().to_string());
                 }
@@ -0,0 +1,392 @@
+// Discord chat bridge
+// In-game chat picked up by the chat relay is posted to each linked server's
+// Discord webhook. With a bot token, new messages in the linked channel are read
+// back and sent into the game with ServerChat.
+//
+// Echo loops are avoided on both sides: webhook and bot posts are never read back
+// from Discord, and "[Discord] ..." lines are never forwarded to Discord.
+
+use crate::commands::rcon::{ensure_connected, RconState};
+use crate::models::{ChatBridgeConfig, ChatBridgeLink, ChatMessage};
+use crate::services::chat_relay;
+use crate::services::discord::DiscordService;
+use crate::services::settings;
+use crate::AppState;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Settings key holding the JSON-encoded ChatBridgeConfig
+pub const CHAT_BRIDGE_SETTING: &str = "chat_bridge_config";
+
+/// Prefix of Discord messages sent into the game
+pub const DISCORD_PREFIX: &str = "[Discord]";
+
+const DISCORD_API: &str = "https://discord.com/api/v10";
+/// Discord rejects message content longer than this
+const DISCORD_MAX_CHARS: usize = 2000;
+/// Longest Discord message passed on to ServerChat
+const GAME_MAX_CHARS: usize = 200;
+/// Re-check interval while the chat relay is switched off
+const DISABLED_RECHECK: Duration = Duration::from_secs(30);
+
+/// Newest Discord message id seen per channel
+static LAST_SEEN: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+
+pub fn load_config(state: &AppState) -> ChatBridgeConfig {
+    state
+        .db
+        .lock()
+        .ok()
+        .and_then(|db| db.get_setting(CHAT_BRIDGE_SETTING).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+impl ChatBridgeConfig {
+    /// The enabled link for a server, if any
+    pub fn link_for(&self, server_id: i64) -> Option<&ChatBridgeLink> {
+        self.links
+            .iter()
+            .find(|link| link.enabled && link.server_id == server_id)
+    }
+
+    fn bot_token(&self) -> Option<&str> {
+        self.bot_token
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+    }
+}
+
+/// Escape Discord markdown so player text shows up literally
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    // Headings and list markers only count at the start of the line
+    if text.starts_with(['#', '-']) {
+        escaped.push('\\');
+    }
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '~' | '`' | '|' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped.chars().take(DISCORD_MAX_CHARS).collect()
+}
+
+/// Webhook display name for a chat sender. Discord allows 1-80 characters and
+/// refuses names containing "discord" or "clyde".
+pub fn webhook_username(sender: Option<&str>, fallback: &str) -> String {
+    let name = sender
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(fallback);
+    let lower = name.to_lowercase();
+    let name = if lower.contains("discord") || lower.contains("clyde") || name.is_empty() {
+        "In-game chat"
+    } else {
+        name
+    };
+    name.chars().take(80).collect()
+}
+
+/// ServerChat text for a Discord message: one line, printable, length-capped.
+/// None when nothing is left to send (attachments only, embeds, ...).
+pub fn game_text(author: &str, content: &str) -> Option<String> {
+    let content = content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(GAME_MAX_CHARS)
+        .collect::<String>();
+    if content.is_empty() {
+        return None;
+    }
+    let author: String = author
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(32)
+        .collect();
+    Some(format!("{} {}: {}", DISCORD_PREFIX, author.trim(), content))
+}
+
+/// ServerChat lines that came from Discord in the first place
+pub fn is_bridge_echo(message: &ChatMessage) -> bool {
+    message.sender.as_deref() == Some("SERVER") && message.message.starts_with(DISCORD_PREFIX)
+}
+
+/// Post relayed chat to the server's webhook, if the server is bridged
+pub async fn forward_to_discord(state: &AppState, server_id: i64, messages: &[ChatMessage]) {
+    let config = load_config(state);
+    let Some(webhook_url) = config
+        .link_for(server_id)
+        .and_then(|link| link.webhook_url.as_deref())
+        .filter(|url| !url.trim().is_empty())
+    else {
+        return;
+    };
+
+    let server_name = server_name(state, server_id).unwrap_or_else(|| "Server".to_string());
+    let discord = DiscordService::new();
+    for message in messages.iter().filter(|m| !is_bridge_echo(m)) {
+        let username = webhook_username(message.sender.as_deref(), &server_name);
+        let content = escape_markdown(&message.message);
+        if let Err(e) = discord.send_chat(webhook_url, &username, &content).await {
+            log::warn!(
+                "💬 Chat bridge: Discord post for server {} failed: {}",
+                server_id,
+                e
+            );
+            break;
+        }
+    }
+}
+
+fn server_name(state: &AppState, server_id: i64) -> Option<String> {
+    let db = &state.db;
+    let conn = db.get_connection().ok()?;
+    conn.query_row(
+        "SELECT name FROM servers WHERE id = ?1",
+        [server_id],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiscordAuthor {
+    pub username: String,
+    pub global_name: Option<String>,
+    #[serde(default)]
+    pub bot: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiscordMessage {
+    pub id: String,
+    #[serde(default)]
+    pub content: String,
+    pub webhook_id: Option<String>,
+    pub author: DiscordAuthor,
+}
+
+impl DiscordMessage {
+    /// Webhook and bot posts include our own relayed chat, so they are never sent back
+    pub fn is_relayable(&self) -> bool {
+        self.webhook_id.is_none() && !self.author.bot
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.author
+            .global_name
+            .as_deref()
+            .unwrap_or(&self.author.username)
+    }
+}
+
+/// Snowflake ids grow over time; Discord lists newest first
+pub fn oldest_first(mut messages: Vec<DiscordMessage>) -> Vec<DiscordMessage> {
+    messages.sort_by_key(|m| m.id.parse::<u64>().unwrap_or(0));
+    messages
+}
+
+async fn fetch_channel_messages(
+    client: &Client,
+    bot_token: &str,
+    channel_id: &str,
+    after: Option<&str>,
+) -> Result<Vec<DiscordMessage>, String> {
+    let url = match after {
+        Some(id) => format!(
+            "{}/channels/{}/messages?after={}&limit=50",
+            DISCORD_API, channel_id, id
+        ),
+        None => format!("{}/channels/{}/messages?limit=1", DISCORD_API, channel_id),
+    };
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bot {}", bot_token))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to read Discord channel: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Discord returned HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected Discord response: {}", e))
+}
+
+/// Relay new messages from one linked channel into the game
+async fn relay_channel(
+    app_handle: &AppHandle,
+    client: &Client,
+    bot_token: &str,
+    server_id: i64,
+    channel_id: &str,
+) -> Result<(), String> {
+    let after = crate::db::lock_or_recover(&LAST_SEEN, "chat bridge")
+        .get_or_insert_with(HashMap::new)
+        .get(channel_id)
+        .cloned();
+    let messages = oldest_first(
+        fetch_channel_messages(client, bot_token, channel_id, after.as_deref()).await?,
+    );
+
+    if let Some(newest) = messages.last() {
+        crate::db::lock_or_recover(&LAST_SEEN, "chat bridge")
+            .get_or_insert_with(HashMap::new)
+            .insert(channel_id.to_string(), newest.id.clone());
+    }
+    // The first read only marks where the channel is, so old history isn't replayed
+    if after.is_none() {
+        return Ok(());
+    }
+
+    let lines: Vec<String> = messages
+        .iter()
+        .filter(|m| m.is_relayable())
+        .filter_map(|m| game_text(m.display_name(), &m.content))
+        .collect();
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let state = app_handle.state::<AppState>();
+    let rcon_state = app_handle.state::<RconState>();
+    let rcon = &rcon_state.0;
+    ensure_connected(&state, rcon, server_id).await?;
+    for line in lines {
+        chat_relay::expect_echo(server_id, &line);
+        rcon.broadcast(server_id, &line).await?;
+        let message = chat_relay::parse_chat_line(server_id, &line, true);
+        let _ = app_handle.emit(chat_relay::CHAT_EVENT, &message);
+    }
+    Ok(())
+}
+
+/// Start the Discord -> game half of the bridge. It polls on the chat relay's
+/// interval and only for online servers with a channel id and a bot token.
+pub fn start(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let client = Client::new();
+
+        loop {
+            let state = app_handle.state::<AppState>();
+            let interval = settings::CHAT_POLL_SECS.load(&state);
+            if interval == 0 {
+                tokio::time::sleep(DISABLED_RECHECK).await;
+                continue;
+            }
+
+            let config = load_config(&state);
+            if let Some(bot_token) = config.bot_token() {
+                let online = chat_relay::online_servers(&state);
+                for link in config
+                    .links
+                    .iter()
+                    .filter(|link| link.enabled && online.contains(&link.server_id))
+                {
+                    let Some(channel_id) = link
+                        .channel_id
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|id| !id.is_empty())
+                    else {
+                        continue;
+                    };
+                    if let Err(e) =
+                        relay_channel(&app_handle, &client, bot_token, link.server_id, channel_id)
+                            .await
+                    {
+                        log::debug!(
+                            "💬 Chat bridge: Discord relay for server {} failed: {}",
+                            link.server_id,
+                            e
+                        );
+                    }
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discord_message(id: &str, webhook: bool, bot: bool) -> DiscordMessage {
+        DiscordMessage {
+            id: id.to_string(),
+            content: "hi".to_string(),
+            webhook_id: webhook.then(|| "1".to_string()),
+            author: DiscordAuthor {
+                username: "rex".to_string(),
+                global_name: Some("Rex".to_string()),
+                bot,
+            },
+        }
+    }
+
+    #[test]
+    fn escapes_markdown_for_discord() {
+        assert_eq!(escape_markdown("**bold** _x_"), "\\*\\*bold\\*\\* \\_x\\_");
+        assert_eq!(escape_markdown("# not a heading"), "\\# not a heading");
+        assert_eq!(escape_markdown(&"a".repeat(3000)).len(), DISCORD_MAX_CHARS);
+    }
+
+    #[test]
+    fn webhook_usernames_follow_discord_rules() {
+        assert_eq!(webhook_username(Some("Bob (Rex)"), "Island"), "Bob (Rex)");
+        assert_eq!(webhook_username(None, "Island"), "Island");
+        assert_eq!(
+            webhook_username(Some("DiscordFan"), "Island"),
+            "In-game chat"
+        );
+        assert_eq!(webhook_username(Some(&"x".repeat(100)), "Island").len(), 80);
+    }
+
+    #[test]
+    fn game_text_is_one_prefixed_line() {
+        assert_eq!(
+            game_text("Rex", "hello\n  there").as_deref(),
+            Some("[Discord] Rex: hello there")
+        );
+        assert_eq!(game_text("Rex", "  \n "), None);
+        let long = game_text("Rex", &"y".repeat(500)).unwrap();
+        assert!(long.ends_with(&"y".repeat(GAME_MAX_CHARS)));
+    }
+
+    #[test]
+    fn bridged_lines_are_not_sent_back_to_discord() {
+        let echoed = chat_relay::parse_chat_line(1, "SERVER: [Discord] Rex: hi", false);
+        assert!(is_bridge_echo(&echoed));
+        let player = chat_relay::parse_chat_line(1, "Bob: [Discord] is down", false);
+        assert!(!is_bridge_echo(&player));
+    }
+
+    #[test]
+    fn only_human_channel_messages_are_relayed_oldest_first() {
+        assert!(discord_message("1", false, false).is_relayable());
+        assert!(!discord_message("1", true, false).is_relayable());
+        assert!(!discord_message("1", false, true).is_relayable());
+
+        let ordered = oldest_first(vec![
+            discord_message("30", false, false),
+            discord_message("100", false, false),
+            discord_message("4", false, false),
+        ]);
+        let ids: Vec<&str> = ordered.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["4", "30", "100"]);
+    }
+}
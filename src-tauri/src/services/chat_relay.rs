@@ -0,0 +1,312 @@
+// In-game chat relay
+// Polls GetChat over RCON for every online server and emits each new line as a
+// "chat-message" event. GetChat may hand back lines from the previous poll, so
+// only the part after the overlap with the last batch is new.
+
+use crate::commands::player;
+use crate::commands::rcon::{ensure_connected, RconState};
+use crate::models::ChatMessage;
+use crate::services::{chat_bridge, settings};
+use crate::AppState;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const CHAT_EVENT: &str = "chat-message";
+/// Re-check interval while polling is switched off (chat_poll_secs = 0)
+const DISABLED_RECHECK: Duration = Duration::from_secs(30);
+/// How often the poll also reconciles player sessions with ListPlayers
+const PLAYER_SYNC_INTERVAL: Duration = Duration::from_secs(60);
+/// Lines remembered per server for overlap detection
+const MAX_REMEMBERED: usize = 200;
+
+#[derive(Default)]
+struct ChatState {
+    /// Lines of the last batch, oldest first
+    previous: Vec<String>,
+    /// Messages posted from the manager whose echo should not show up twice
+    pending_echo: Vec<String>,
+}
+
+static CHAT_STATE: Mutex<Option<HashMap<i64, ChatState>>> = Mutex::new(None);
+
+fn with_state<R>(server_id: i64, f: impl FnOnce(&mut ChatState) -> R) -> R {
+    let mut guard = crate::db::lock_or_recover(&CHAT_STATE, "chat relay");
+    f(guard
+        .get_or_insert_with(HashMap::new)
+        .entry(server_id)
+        .or_default())
+}
+
+/// GetChat output as lines, without blanks and the "Server received, But no response!!" filler
+pub fn chat_lines(raw: &str) -> Vec<String> {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("Server received, But no response"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Lines of `current` not already delivered. Until it is cleared the server's buffer
+/// only grows or slides forward, so a re-sent batch is at least as long as the last one
+/// and starts where that one started or later; the overlap is the rest of the last batch
+/// from that position. A shorter batch is a fresh buffer even when its lines read the
+/// same as the end of the last one, e.g. a player typing "gg" twice.
+pub fn new_lines<'a>(previous: &[String], current: &'a [String]) -> &'a [String] {
+    if current.len() < previous.len() {
+        return current;
+    }
+    let overlap = (0..previous.len())
+        .find(|&start| current.starts_with(&previous[start..]))
+        .map_or(0, |start| previous.len() - start);
+    &current[overlap..]
+}
+
+/// "Sender: message" or "Sender (Character): message"; anything else has no sender
+pub fn parse_chat_line(server_id: i64, line: &str, from_manager: bool) -> ChatMessage {
+    let (sender, message) = match line.split_once(": ") {
+        Some((sender, message)) if !sender.trim().is_empty() => {
+            (Some(sender.trim().to_string()), message.to_string())
+        }
+        _ => (None, line.to_string()),
+    };
+    ChatMessage {
+        server_id,
+        sender,
+        message,
+        raw: line.to_string(),
+        timestamp: chrono::Local::now().to_rfc3339(),
+        from_manager,
+    }
+}
+
+/// Record a message just posted with ServerChat so its echo in GetChat is skipped
+pub fn expect_echo(server_id: i64, message: &str) {
+    with_state(server_id, |state| {
+        state.pending_echo.push(message.to_string());
+        let excess = state.pending_echo.len().saturating_sub(20);
+        state.pending_echo.drain(..excess);
+    });
+}
+
+/// Feed one GetChat reply through the dedupe and return the messages to emit
+pub fn process_batch(server_id: i64, raw: &str) -> Vec<ChatMessage> {
+    let current = chat_lines(raw);
+    with_state(server_id, |state| {
+        let fresh: Vec<String> = new_lines(&state.previous, &current).to_vec();
+        if !current.is_empty() {
+            state.previous = current;
+            let excess = state.previous.len().saturating_sub(MAX_REMEMBERED);
+            state.previous.drain(..excess);
+        }
+
+        fresh
+            .into_iter()
+            .filter(|line| {
+                let echo = state.pending_echo.iter().position(|sent| {
+                    line.strip_prefix("SERVER: ")
+                        .is_some_and(|text| text.trim() == sent.trim())
+                });
+                match echo {
+                    Some(i) => {
+                        state.pending_echo.remove(i);
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .map(|line| parse_chat_line(server_id, &line, false))
+            .collect()
+    })
+}
+
+/// Forget a server's chat history once it is no longer polled
+fn forget(server_id: i64) {
+    let mut guard = crate::db::lock_or_recover(&CHAT_STATE, "chat relay");
+    if let Some(states) = guard.as_mut() {
+        states.remove(&server_id);
+    }
+}
+
+/// Servers whose log reported them ready and that accept RCON
+fn relayed_server_ids(conn: &Connection) -> rusqlite::Result<Vec<i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM servers WHERE status = 'online' AND COALESCE(rcon_enabled, 1) = 1",
+    )?;
+    let ids = stmt
+        .query_map([], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(ids)
+}
+
+pub(crate) fn online_servers(state: &AppState) -> Vec<i64> {
+    let db = &state.db;
+    let Ok(conn) = db.get_connection() else {
+        return Vec::new();
+    };
+    relayed_server_ids(&conn).unwrap_or_default()
+}
+
+async fn poll_server(app_handle: &AppHandle, server_id: i64) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let rcon_state = app_handle.state::<RconState>();
+    let rcon = &rcon_state.0;
+    ensure_connected(&state, rcon, server_id).await?;
+
+    let response = rcon.send_command(server_id, "GetChat").await?;
+    let messages = process_batch(server_id, response.data.as_deref().unwrap_or(""));
+    for message in &messages {
+        let _ = app_handle.emit(CHAT_EVENT, message);
+    }
+    if !messages.is_empty() {
+        chat_bridge::forward_to_discord(&state, server_id, &messages).await;
+    }
+    Ok(())
+}
+
+/// Start the background poller. The interval (settings::CHAT_POLL_SECS) is read
+/// every round so changes apply without a restart; 0 pauses polling.
+/// Player sessions are reconciled on the same loop every PLAYER_SYNC_INTERVAL,
+/// and closed when a server drops out of the online list.
+pub fn start(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut polled: Vec<i64> = Vec::new();
+        let mut last_player_sync: Option<Instant> = None;
+
+        loop {
+            let state = app_handle.state::<AppState>();
+            let interval = settings::CHAT_POLL_SECS.load(&state);
+            if interval == 0 {
+                for server_id in polled.drain(..) {
+                    forget(server_id);
+                }
+                tokio::time::sleep(DISABLED_RECHECK).await;
+                continue;
+            }
+
+            let online = online_servers(&state);
+            for &server_id in polled.iter().filter(|id| !online.contains(id)) {
+                forget(server_id);
+                player::end_sessions(&app_handle, server_id).await;
+            }
+            polled = online;
+
+            for &server_id in &polled {
+                if let Err(e) = poll_server(&app_handle, server_id).await {
+                    log::debug!("💬 Chat poll failed for server {}: {}", server_id, e);
+                }
+            }
+
+            if last_player_sync.is_none_or(|at| at.elapsed() >= PLAYER_SYNC_INTERVAL) {
+                last_player_sync = Some(Instant::now());
+                for &server_id in &polled {
+                    if let Err(e) = player::sync_sessions(&app_handle, server_id).await {
+                        log::debug!("👥 Player sync failed for server {}: {}", server_id, e);
+                    }
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn new_lines_skips_the_overlap_with_the_last_batch() {
+        let previous = lines(&["a: 1", "b: 2", "c: 3"]);
+        assert_eq!(
+            new_lines(&previous, &lines(&["b: 2", "c: 3", "d: 4"])),
+            lines(&["d: 4"])
+        );
+        assert_eq!(
+            new_lines(&previous, &lines(&["a: 1", "b: 2", "c: 3", "d: 4"])),
+            lines(&["d: 4"])
+        );
+        assert!(new_lines(&previous, &previous).is_empty());
+        assert_eq!(new_lines(&[], &lines(&["x"])), lines(&["x"]));
+    }
+
+    #[test]
+    fn new_lines_keeps_repeated_lines_after_the_buffer_was_cleared() {
+        let previous = lines(&["Alice: gl", "Bob: gg"]);
+        assert_eq!(
+            new_lines(&previous, &lines(&["Bob: gg"])),
+            lines(&["Bob: gg"])
+        );
+        assert_eq!(new_lines(&previous, &lines(&["d: 4"])), lines(&["d: 4"]));
+
+        // Repeats inside a batch that re-sends the last one are new too
+        let previous = lines(&["Bob: gg"]);
+        assert_eq!(
+            new_lines(&previous, &lines(&["Bob: gg", "Bob: gg", "Bob: gg"])),
+            lines(&["Bob: gg", "Bob: gg"])
+        );
+    }
+
+    #[test]
+    fn parses_sender_and_message() {
+        let msg = parse_chat_line(1, "Rex Rider (Bob): hello: world", false);
+        assert_eq!(msg.sender.as_deref(), Some("Rex Rider (Bob)"));
+        assert_eq!(msg.message, "hello: world");
+
+        let msg = parse_chat_line(1, "Tribe Alpha was destroyed", false);
+        assert_eq!(msg.sender, None);
+        assert_eq!(msg.message, "Tribe Alpha was destroyed");
+    }
+
+    #[test]
+    fn batches_are_deduplicated_and_echoes_suppressed() {
+        let server_id = -633;
+        let first = process_batch(server_id, "Alice: hi\nServer received, But no response!!\n");
+        assert_eq!(first.len(), 1);
+        assert!(process_batch(server_id, "Alice: hi\n").is_empty());
+
+        expect_echo(server_id, "restart in 5");
+        let next = process_batch(server_id, "Alice: hi\nSERVER: restart in 5\nBob: ok\n");
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].sender.as_deref(), Some("Bob"));
+        forget(server_id);
+    }
+
+    /// Status written the way the log watcher does it, so the relay sees
+    /// exactly the servers that reached "online" in the real schema
+    #[test]
+    fn polls_servers_that_went_online() {
+        use crate::db::test_support::{db_with_server, insert_server};
+        use crate::models::ServerStatus;
+        use crate::services::server_state::set_status;
+
+        let (_dir, db, _) = db_with_server("chat_relay");
+        let conn = db.get_connection().unwrap();
+        insert_server(&conn, "Starting", "/srv/relay");
+        let no_rcon = insert_server(&conn, "No RCON", "/srv/relay");
+        conn.execute(
+            "UPDATE servers SET rcon_enabled = 0 WHERE id = ?1",
+            [no_rcon],
+        )
+        .unwrap();
+
+        for server_id in [1, 2, 3] {
+            assert!(set_status(&conn, server_id, ServerStatus::Starting).unwrap());
+        }
+        for server_id in [1, 3] {
+            assert!(set_status(&conn, server_id, ServerStatus::Running).unwrap());
+            assert!(set_status(&conn, server_id, ServerStatus::Online).unwrap());
+        }
+        assert_eq!(relayed_server_ids(&conn).unwrap(), vec![1]);
+
+        assert!(set_status(&conn, 1, ServerStatus::Stopping).unwrap());
+        assert!(relayed_server_ids(&conn).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+// Off-site copies of backup archives on S3-compatible storage
+// (AWS S3, Backblaze B2, Wasabi, MinIO, Cloudflare R2, ...).
+// Uploaded archives are tracked in backups.remote_url as s3://bucket/key.
+
+use crate::services::settings;
+use crate::AppState;
+use s3::creds::Credentials;
+use s3::{Bucket, Region};
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+
+/// Bucket connection details from settings
+pub struct CloudConfig {
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+    pub prefix: String,
+}
+
+impl CloudConfig {
+    pub fn load(state: &AppState) -> Result<Self, String> {
+        let required = |setting: &settings::Setting<Option<String>>, label: &str| {
+            setting
+                .load(state)
+                .ok_or_else(|| format!("Cloud backup {} is not set in Settings", label))
+        };
+
+        Ok(Self {
+            endpoint: required(&settings::CLOUD_ENDPOINT, "endpoint")?,
+            region: settings::CLOUD_REGION
+                .load(state)
+                .unwrap_or_else(|| "us-east-1".to_string()),
+            bucket: required(&settings::CLOUD_BUCKET, "bucket")?,
+            access_key: required(&settings::CLOUD_ACCESS_KEY, "access key")?,
+            secret_key: required(&settings::CLOUD_SECRET_KEY, "secret key")?,
+            prefix: settings::CLOUD_PREFIX
+                .load(state)
+                .map(|p| p.trim_matches('/').to_string())
+                .unwrap_or_default(),
+        })
+    }
+
+    fn bucket(&self, name: &str) -> Result<Box<Bucket>, String> {
+        let region = Region::Custom {
+            region: self.region.clone(),
+            endpoint: self.endpoint.trim_end_matches('/').to_string(),
+        };
+        let credentials = Credentials::new(
+            Some(&self.access_key),
+            Some(&self.secret_key),
+            None,
+            None,
+            None,
+        )
+        .map_err(|e| format!("Invalid cloud credentials: {}", e))?;
+
+        // Path-style addressing works with every S3-compatible provider
+        Bucket::new(name, region, credentials)
+            .map(|bucket| bucket.with_path_style())
+            .map_err(|e| format!("Invalid bucket '{}': {}", name, e))
+    }
+
+    /// Object key for a backup: <prefix>/server_<id>/<file name>
+    pub fn object_key(&self, server_id: i64, file_name: &str) -> String {
+        let key = format!("server_{}/{}", server_id, file_name);
+        if self.prefix.is_empty() {
+            key
+        } else {
+            format!("{}/{}", self.prefix, key)
+        }
+    }
+}
+
+/// Split "s3://bucket/key" into its parts
+pub fn parse_remote_url(url: &str) -> Option<(&str, &str)> {
+    url.strip_prefix("s3://")?
+        .split_once('/')
+        .filter(|(bucket, key)| !bucket.is_empty() && !key.is_empty())
+}
+
+/// Upload a local archive and return its s3:// URL
+pub async fn upload(config: &CloudConfig, local_path: &Path, key: &str) -> Result<String, String> {
+    let bucket = config.bucket(&config.bucket)?;
+    let mut file = tokio::fs::File::open(local_path)
+        .await
+        .map_err(|e| format!("Failed to open backup file: {}", e))?;
+
+    let response = bucket
+        .put_object_stream(&mut file, key)
+        .await
+        .map_err(|e| format!("Upload failed: {}", e))?;
+    if !(200..300).contains(&response.status_code()) {
+        return Err(format!(
+            "Upload failed: storage returned HTTP {}",
+            response.status_code()
+        ));
+    }
+
+    Ok(format!("s3://{}/{}", config.bucket, key))
+}
+
+/// Download an uploaded archive to `local_path`, replacing any existing file
+pub async fn download(
+    config: &CloudConfig,
+    remote_url: &str,
+    local_path: &Path,
+) -> Result<(), String> {
+    let (bucket_name, key) = parse_remote_url(remote_url)
+        .ok_or_else(|| format!("Not a cloud backup URL: {}", remote_url))?;
+    let bucket = config.bucket(bucket_name)?;
+
+    if let Some(parent) = local_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    }
+
+    // Download next to the target first so a failed transfer doesn't clobber a good local copy
+    let partial = local_path.with_extension("zip.part");
+    let mut file = tokio::fs::File::create(&partial)
+        .await
+        .map_err(|e| format!("Failed to create backup file: {}", e))?;
+    let status = bucket
+        .get_object_to_writer(key, &mut file)
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?;
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to write backup file: {}", e))?;
+    drop(file);
+
+    if !(200..300).contains(&status) {
+        let _ = tokio::fs::remove_file(&partial).await;
+        return Err(format!("Download failed: storage returned HTTP {}", status));
+    }
+    tokio::fs::rename(&partial, local_path)
+        .await
+        .map_err(|e| format!("Failed to move downloaded backup into place: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_url() {
+        assert_eq!(
+            parse_remote_url("s3://asa-backups/main/server_1/backup_1.zip"),
+            Some(("asa-backups", "main/server_1/backup_1.zip"))
+        );
+        assert_eq!(parse_remote_url("s3://asa-backups/"), None);
+        assert_eq!(parse_remote_url("https://example.com/a"), None);
+    }
+}
@@ -1,6 +1,7 @@
 // Config Generator Service for ASA Server Configuration
 // Handles INI file generation, parsing, and per-map profiles
 
+use crate::services::ini_parser::IniParser;
 use chrono::Local;
 use std::collections::HashMap;
 use std::fs;
@@ -30,6 +31,13 @@ pub struct IniConfig {
     pub sections: Vec<ConfigSection>,
 }
 
+/// CurseForge mods suggested by the built-in map profiles, with display names
+/// (used when they are added to a server before CurseForge has been queried)
+pub const RECOMMENDED_MODS: &[(&str, &str)] = &[
+    ("928793", "Cybers Structures QoL+"),
+    ("929420", "Super Spyglass Plus"),
+];
+
 /// Per-map profile with recommended settings
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -113,6 +121,43 @@ pub struct ServerConfig {
     pub per_level_stats_multiplier_player: Vec<f32>,
     pub per_level_stats_multiplier_dino_tamed: Vec<f32>,
     pub per_level_stats_multiplier_dino_wild: Vec<f32>,
+
+    // Cluster Transfers (what may move between maps)
+    #[serde(default)]
+    pub prevent_download_survivors: bool,
+    #[serde(default)]
+    pub prevent_download_items: bool,
+    #[serde(default)]
+    pub prevent_download_dinos: bool,
+    #[serde(default)]
+    pub prevent_upload_survivors: bool,
+    #[serde(default)]
+    pub prevent_upload_items: bool,
+    #[serde(default)]
+    pub prevent_upload_dinos: bool,
+    #[serde(default)]
+    pub no_tribute_downloads: bool,
+    #[serde(default)]
+    pub cross_ark_allow_foreign_dino_downloads: bool,
+    #[serde(default)]
+    pub tribute_item_expiration_seconds: Option<u32>,
+    #[serde(default)]
+    pub tribute_dino_expiration_seconds: Option<u32>,
+    #[serde(default)]
+    pub tribute_character_expiration_seconds: Option<u32>,
+    #[serde(default)]
+    pub max_tribute_items: Option<u32>,
+    #[serde(default)]
+    pub max_tribute_dinos: Option<u32>,
+
+    // Crossplay, as saved with set_crossplay_platforms; empty leaves the game default
+    #[serde(default)]
+    pub crossplay_platforms: Vec<String>,
+
+    // Any other INI keys, keyed by section then key. Sections named
+    // GAME_INI_SECTION go to Game.ini, everything else to GameUserSettings.ini.
+    #[serde(default)]
+    pub extra_settings: HashMap<String, HashMap<String, String>>,
 }
 
 impl Default for ServerConfig {
@@ -120,7 +165,7 @@ impl Default for ServerConfig {
         Self {
             session_name: "My ASA Server".to_string(),
             server_password: None,
-            admin_password: "admin123".to_string(),
+            admin_password: crate::models::DEFAULT_ADMIN_PASSWORD.to_string(),
             max_players: 70,
             map_name: "TheIsland_WP".to_string(),
             game_port: 7777,
@@ -160,10 +205,48 @@ impl Default for ServerConfig {
             per_level_stats_multiplier_player: vec![1.0; 12],
             per_level_stats_multiplier_dino_tamed: vec![1.0; 12],
             per_level_stats_multiplier_dino_wild: vec![1.0; 12],
+            prevent_download_survivors: false,
+            prevent_download_items: false,
+            prevent_download_dinos: false,
+            prevent_upload_survivors: false,
+            prevent_upload_items: false,
+            prevent_upload_dinos: false,
+            no_tribute_downloads: false,
+            cross_ark_allow_foreign_dino_downloads: false,
+            tribute_item_expiration_seconds: None,
+            tribute_dino_expiration_seconds: None,
+            tribute_character_expiration_seconds: None,
+            max_tribute_items: None,
+            max_tribute_dinos: None,
+            crossplay_platforms: Vec::new(),
+            extra_settings: HashMap::new(),
         }
     }
 }
 
+/// The Game.ini section; extra_settings under any other section belong in GameUserSettings.ini
+pub const GAME_INI_SECTION: &str = "/Script/ShooterGame.ShooterGameMode";
+
+/// GameUserSettings.ini section holding the generator's settings
+const GUS_SECTION: &str = "ServerSettings";
+/// Sections ASA itself writes the session name, ports and player cap to
+const SESSION_SECTION: &str = "SessionSettings";
+const GAME_SESSION_SECTION: &str = "/Script/Engine.GameSession";
+
+/// Keys the generator only writes conditionally. They're cleared from existing files before
+/// merging so e.g. removing a server password doesn't leave the old one behind.
+const CONDITIONAL_KEYS: &[&str] = &[
+    "ServerPassword",
+    "ActiveMods",
+    "TributeItemExpirationSeconds",
+    "TributeDinoExpirationSeconds",
+    "TributeCharacterExpirationSeconds",
+    "MaxTributeItems",
+    "MaxTributeDinos",
+    "ServerPlatform",
+    "PerLevelStatsMultiplier_",
+];
+
 pub struct ConfigGenerator;
 
 impl ConfigGenerator {
@@ -177,7 +260,7 @@ impl ConfigGenerator {
                 xp_multiplier: 1.0,
                 harvest_multiplier: 1.0,
                 taming_multiplier: 1.0,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
             MapProfile {
@@ -187,7 +270,7 @@ impl ConfigGenerator {
                 xp_multiplier: 1.2,
                 harvest_multiplier: 1.2,
                 taming_multiplier: 1.5,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
             MapProfile {
@@ -197,7 +280,7 @@ impl ConfigGenerator {
                 xp_multiplier: 1.2,
                 harvest_multiplier: 1.0,
                 taming_multiplier: 1.5,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
             MapProfile {
@@ -207,7 +290,7 @@ impl ConfigGenerator {
                 xp_multiplier: 1.5,
                 harvest_multiplier: 1.5,
                 taming_multiplier: 2.0,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
             MapProfile {
@@ -217,7 +300,7 @@ impl ConfigGenerator {
                 xp_multiplier: 1.0,
                 harvest_multiplier: 1.0,
                 taming_multiplier: 1.0,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
             MapProfile {
@@ -227,7 +310,7 @@ impl ConfigGenerator {
                 xp_multiplier: 1.0,
                 harvest_multiplier: 1.0,
                 taming_multiplier: 1.0,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
             MapProfile {
@@ -237,7 +320,7 @@ impl ConfigGenerator {
                 xp_multiplier: 1.5,
                 harvest_multiplier: 1.2,
                 taming_multiplier: 1.5,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
             MapProfile {
@@ -247,7 +330,7 @@ impl ConfigGenerator {
                 xp_multiplier: 1.5,
                 harvest_multiplier: 1.2,
                 taming_multiplier: 2.0,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
             MapProfile {
@@ -257,12 +340,28 @@ impl ConfigGenerator {
                 xp_multiplier: 1.5,
                 harvest_multiplier: 1.2,
                 taming_multiplier: 2.0,
-                recommended_mods: vec![],
+                recommended_mods: Self::recommended_mods(),
                 custom_settings: HashMap::new(),
             },
         ]
     }
 
+    /// Official maps all share the same quality-of-life recommendations
+    fn recommended_mods() -> Vec<String> {
+        RECOMMENDED_MODS
+            .iter()
+            .map(|(id, _)| id.to_string())
+            .collect()
+    }
+
+    /// Display name of a recommended mod
+    pub fn recommended_mod_name(mod_id: &str) -> Option<&'static str> {
+        RECOMMENDED_MODS
+            .iter()
+            .find(|(id, _)| *id == mod_id)
+            .map(|(_, name)| *name)
+    }
+
     /// Get profile for a specific map
     pub fn get_profile_for_map(map_id: &str) -> Option<MapProfile> {
         Self::get_map_profiles()
@@ -303,6 +402,13 @@ impl ConfigGenerator {
         content.push_str(&format!("MapName={}\r\n", config.map_name));
         content.push_str(&format!("RCONEnabled={}\r\n", config.rcon_enabled));
         content.push_str(&format!("RCONPort={}\r\n", config.rcon_port));
+        // Same value as -ServerPlatform on the command line
+        if !config.crossplay_platforms.is_empty() {
+            content.push_str(&format!(
+                "ServerPlatform={}\r\n",
+                config.crossplay_platforms.join("+")
+            ));
+        }
 
         // Rates
         content.push_str(&format!("XPMultiplier={:.2}\r\n", config.xp_multiplier));
@@ -399,6 +505,55 @@ impl ConfigGenerator {
             !config.friendly_fire
         ));
 
+        // Cluster Transfers
+        content.push_str(&format!(
+            "PreventDownloadSurvivors={}\r\n",
+            config.prevent_download_survivors
+        ));
+        content.push_str(&format!(
+            "PreventDownloadItems={}\r\n",
+            config.prevent_download_items
+        ));
+        content.push_str(&format!(
+            "PreventDownloadDinos={}\r\n",
+            config.prevent_download_dinos
+        ));
+        content.push_str(&format!(
+            "PreventUploadSurvivors={}\r\n",
+            config.prevent_upload_survivors
+        ));
+        content.push_str(&format!(
+            "PreventUploadItems={}\r\n",
+            config.prevent_upload_items
+        ));
+        content.push_str(&format!(
+            "PreventUploadDinos={}\r\n",
+            config.prevent_upload_dinos
+        ));
+        content.push_str(&format!(
+            "noTributeDownloads={}\r\n",
+            config.no_tribute_downloads
+        ));
+        content.push_str(&format!(
+            "CrossARKAllowForeignDinoDownloads={}\r\n",
+            config.cross_ark_allow_foreign_dino_downloads
+        ));
+        if let Some(secs) = config.tribute_item_expiration_seconds {
+            content.push_str(&format!("TributeItemExpirationSeconds={}\r\n", secs));
+        }
+        if let Some(secs) = config.tribute_dino_expiration_seconds {
+            content.push_str(&format!("TributeDinoExpirationSeconds={}\r\n", secs));
+        }
+        if let Some(secs) = config.tribute_character_expiration_seconds {
+            content.push_str(&format!("TributeCharacterExpirationSeconds={}\r\n", secs));
+        }
+        if let Some(max) = config.max_tribute_items {
+            content.push_str(&format!("MaxTributeItems={}\r\n", max));
+        }
+        if let Some(max) = config.max_tribute_dinos {
+            content.push_str(&format!("MaxTributeDinos={}\r\n", max));
+        }
+
         // Mods
         if !config.active_mods.is_empty() {
             content.push_str(&format!("ActiveMods={}\r\n", config.active_mods.join(",")));
@@ -412,6 +567,8 @@ impl ConfigGenerator {
         content.push_str("Duration=20\r\n");
         content.push_str("\r\n");
 
+        Self::append_extra_settings(&mut content, config, false, "\r\n");
+
         content
     }
 
@@ -490,9 +647,106 @@ impl ConfigGenerator {
 
         content.push_str("\n");
 
+        Self::append_extra_settings(&mut content, config, true, "\n");
+
         content
     }
 
+    /// Write extra_settings for one file verbatim. Keys go into the matching generated
+    /// section (replacing a generated line with the same key), unknown sections are appended.
+    fn append_extra_settings(
+        content: &mut String,
+        config: &ServerConfig,
+        game_ini: bool,
+        nl: &str,
+    ) {
+        let mut sections: Vec<(&String, &HashMap<String, String>)> = config
+            .extra_settings
+            .iter()
+            .filter(|(section, keys)| {
+                (section.as_str() == GAME_INI_SECTION) == game_ini && !keys.is_empty()
+            })
+            .collect();
+        sections.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (section, keys) in sections {
+            let mut keys: Vec<(&String, &String)> = keys.iter().collect();
+            keys.sort();
+            let lines: String = keys
+                .iter()
+                .map(|(key, value)| format!("{}={}{}", key, value, nl))
+                .collect();
+
+            let header = format!("[{}]{}", section, nl);
+            match content.find(&header) {
+                Some(pos) => {
+                    let start = pos + header.len();
+                    let end = content[start..]
+                        .find(&format!("{}{}", nl, nl))
+                        .map(|i| start + i + nl.len())
+                        .unwrap_or(content.len());
+                    let mut body: String = content[start..end]
+                        .split_inclusive(nl)
+                        .filter(|line| {
+                            let key = line.split('=').next().unwrap_or_default().trim();
+                            !keys.iter().any(|(k, _)| k.as_str() == key)
+                        })
+                        .collect();
+                    body.push_str(&lines);
+                    content.replace_range(start..end, &body);
+                }
+                None => {
+                    content.push_str(&header);
+                    content.push_str(&lines);
+                    content.push_str(nl);
+                }
+            }
+        }
+    }
+
+    /// Merge generated content into an existing file: keys the generator writes win,
+    /// everything else the user added by hand is kept (comments, order and repeated
+    /// keys included). `dropped` are extra settings to take out of the file.
+    fn merge_with_existing(
+        existing: &str,
+        generated: &str,
+        dropped: &[(String, String)],
+    ) -> String {
+        let mut document = IniParser::parse(existing);
+        document.retain(|section, key| {
+            let conditional = CONDITIONAL_KEYS.iter().any(|k| {
+                if k.ends_with('_') {
+                    key.starts_with(k)
+                } else {
+                    key == *k
+                }
+            });
+            let was_dropped = dropped
+                .iter()
+                .any(|(s, k)| s.eq_ignore_ascii_case(section) && k.eq_ignore_ascii_case(key));
+            !conditional && !was_dropped
+        });
+        document.merge(&IniParser::parse(generated));
+        document.serialize()
+    }
+
+    /// Extra settings an earlier write put in the files that `config` no longer has
+    fn dropped_extra_settings(
+        previous: &HashMap<String, HashMap<String, String>>,
+        config: &ServerConfig,
+    ) -> Vec<(String, String)> {
+        previous
+            .iter()
+            .flat_map(|(section, keys)| keys.keys().map(move |key| (section.clone(), key.clone())))
+            .filter(|(section, key)| {
+                !config
+                    .extra_settings
+                    .get(section)
+                    .is_some_and(|keys| keys.contains_key(key))
+            })
+            .collect()
+    }
+
     /// Generate server startup command
     pub fn generate_startup_command(config: &ServerConfig, install_path: &PathBuf) -> String {
         let exe_path = install_path
@@ -530,6 +784,129 @@ impl ConfigGenerator {
         cmd
     }
 
+    /// INI keys written for a ServerConfig field (camelCase, as sent by the UI).
+    /// Keys ending in '[' match every index of an array setting.
+    /// Identity and network fields are intentionally absent so they stay per-server.
+    pub fn ini_keys_for_field(field: &str) -> Option<&'static [&'static str]> {
+        let keys: &'static [&'static str] = match field {
+            "maxPlayers" => &["MaxPlayers"],
+            "xpMultiplier" => &["XPMultiplier"],
+            "harvestAmountMultiplier" => &["HarvestAmountMultiplier"],
+            "tamingSpeedMultiplier" => &["TamingSpeedMultiplier"],
+            "difficultyOffset" => &["DifficultyOffset"],
+            "overrideOfficialDifficulty" => &["OverrideOfficialDifficulty"],
+            "dayCycleSpeedScale" => &["DayCycleSpeedScale"],
+            "dayTimeSpeedScale" => &["DayTimeSpeedScale"],
+            "nightTimeSpeedScale" => &["NightTimeSpeedScale"],
+            "playerDamageMultiplier" => &["PlayerDamageMultiplier"],
+            "playerResistanceMultiplier" => &["PlayerResistanceMultiplier"],
+            "playerFoodDrainMultiplier" => &["PlayerCharacterFoodDrainMultiplier"],
+            "playerWaterDrainMultiplier" => &["PlayerCharacterWaterDrainMultiplier"],
+            "playerStaminaDrainMultiplier" => &["PlayerCharacterStaminaDrainMultiplier"],
+            "dinoDamageMultiplier" => &["DinoDamageMultiplier"],
+            "dinoResistanceMultiplier" => &["DinoResistanceMultiplier"],
+            "dinoFoodDrainMultiplier" => &["DinoCharacterFoodDrainMultiplier"],
+            "wildDinoCountMultiplier" => &["DinoCountMultiplier"],
+            "eggHatchSpeedMultiplier" => &["EggHatchSpeedMultiplier"],
+            "babyMatureSpeedMultiplier" => &["BabyMatureSpeedMultiplier"],
+            "babyFoodConsumptionMultiplier" => &["BabyFoodConsumptionSpeedMultiplier"],
+            "matingIntervalMultiplier" => &["MatingIntervalMultiplier"],
+            "structureDamageMultiplier" => &["StructureDamageMultiplier"],
+            "structureResistanceMultiplier" => &["StructureResistanceMultiplier"],
+            "structureDecayMultiplier" => &["PvEStructureDecayPeriodMultiplier"],
+            "pveMode" => &["ServerPVE"],
+            "pvpGamma" => &["EnablePvPGamma"],
+            "friendlyFire" => &["DisableFriendlyFire"],
+            "activeMods" => &["ActiveMods"],
+            "allowFlyerSpeedLeveling" => &["bAllowFlyerSpeedLeveling"],
+            "allowSpeedLeveling" => &["bAllowSpeedLeveling"],
+            "perLevelStatsMultiplierPlayer" => &["PerLevelStatsMultiplier_Player["],
+            "perLevelStatsMultiplierDinoTamed" => &["PerLevelStatsMultiplier_DinoTamed["],
+            "perLevelStatsMultiplierDinoWild" => &["PerLevelStatsMultiplier_DinoWild["],
+            "preventDownloadSurvivors" => &["PreventDownloadSurvivors"],
+            "preventDownloadItems" => &["PreventDownloadItems"],
+            "preventDownloadDinos" => &["PreventDownloadDinos"],
+            "preventUploadSurvivors" => &["PreventUploadSurvivors"],
+            "preventUploadItems" => &["PreventUploadItems"],
+            "preventUploadDinos" => &["PreventUploadDinos"],
+            "noTributeDownloads" => &["noTributeDownloads"],
+            "crossArkAllowForeignDinoDownloads" => &["CrossARKAllowForeignDinoDownloads"],
+            "tributeItemExpirationSeconds" => &["TributeItemExpirationSeconds"],
+            "tributeDinoExpirationSeconds" => &["TributeDinoExpirationSeconds"],
+            "tributeCharacterExpirationSeconds" => &["TributeCharacterExpirationSeconds"],
+            "maxTributeItems" => &["MaxTributeItems"],
+            "maxTributeDinos" => &["MaxTributeDinos"],
+            // Shorthand for every transfer setting at once
+            "clusterTransfers" => &[
+                "PreventDownloadSurvivors",
+                "PreventDownloadItems",
+                "PreventDownloadDinos",
+                "PreventUploadSurvivors",
+                "PreventUploadItems",
+                "PreventUploadDinos",
+                "noTributeDownloads",
+                "CrossARKAllowForeignDinoDownloads",
+                "TributeItemExpirationSeconds",
+                "TributeDinoExpirationSeconds",
+                "TributeCharacterExpirationSeconds",
+                "MaxTributeItems",
+                "MaxTributeDinos",
+            ],
+            _ => return None,
+        };
+        Some(keys)
+    }
+
+    /// Generate GameUserSettings.ini and Game.ini fragments containing only the given fields,
+    /// suitable for IniParser::merge into an existing server's files
+    pub fn generate_partial_configs(
+        config: &ServerConfig,
+        fields: &[String],
+    ) -> Result<(String, String), String> {
+        let mut keys: Vec<&str> = Vec::new();
+        for field in fields {
+            let field_keys = Self::ini_keys_for_field(field)
+                .ok_or_else(|| format!("Field '{}' cannot be applied across servers", field))?;
+            keys.extend_from_slice(field_keys);
+        }
+
+        let matches = |key: &str| {
+            keys.iter().any(|k| {
+                if k.ends_with('[') {
+                    key.starts_with(k)
+                } else {
+                    key == *k
+                }
+            })
+        };
+
+        let filter = |content: String| {
+            let nl = if content.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            };
+            let mut fragment = String::new();
+            let mut current = None;
+            for (section, key, value) in IniParser::parse(&content).entries() {
+                if !matches(key) {
+                    continue;
+                }
+                if current != Some(section) {
+                    fragment.push_str(&format!("[{}]{}", section, nl));
+                    current = Some(section);
+                }
+                fragment.push_str(&format!("{}={}{}", key, value, nl));
+            }
+            fragment
+        };
+
+        Ok((
+            filter(Self::generate_game_user_settings(config)),
+            filter(Self::generate_game_ini(config)),
+        ))
+    }
+
     /// Backup existing config files
     pub fn backup_configs(install_path: &PathBuf) -> Result<PathBuf, String> {
         let config_dir = install_path
@@ -565,10 +942,12 @@ impl ConfigGenerator {
         Ok(backup_dir)
     }
 
-    /// Write config files to disk
+    /// Write config files to disk. `previous_extra` is the extra_settings of the last
+    /// write, so settings removed from the config since are removed from the files too.
     pub fn write_configs(
         install_path: &PathBuf,
         config: &ServerConfig,
+        previous_extra: &HashMap<String, HashMap<String, String>>,
         backup: bool,
     ) -> Result<(), String> {
         let config_dir = install_path
@@ -586,20 +965,525 @@ impl ConfigGenerator {
             let _ = Self::backup_configs(install_path);
         }
 
-        // Write GameUserSettings.ini
-        let gus_content = Self::generate_game_user_settings(config);
+        let dropped = Self::dropped_extra_settings(previous_extra, config);
+
+        // Write GameUserSettings.ini, merged with any keys already in the file
+        let mut gus_content = Self::generate_game_user_settings(config);
         let gus_path = config_dir.join("GameUserSettings.ini");
+        if let Ok(existing) = fs::read_to_string(&gus_path) {
+            if !existing.trim().is_empty() {
+                gus_content = Self::merge_with_existing(&existing, &gus_content, &dropped);
+            }
+        }
         println!("  📝 Writing GameUserSettings.ini to: {:?}", gus_path);
-        fs::write(&gus_path, gus_content)
+        IniParser::write_atomic(&gus_path, &gus_content)
             .map_err(|e| format!("Failed to write GameUserSettings.ini: {}", e))?;
 
         // Write Game.ini
-        let game_content = Self::generate_game_ini(config);
+        let mut game_content = Self::generate_game_ini(config);
         let game_path = config_dir.join("Game.ini");
+        if let Ok(existing) = fs::read_to_string(&game_path) {
+            if !existing.trim().is_empty() {
+                game_content = Self::merge_with_existing(&existing, &game_content, &dropped);
+            }
+        }
         println!("  📝 Writing Game.ini to: {:?}", game_path);
-        fs::write(&game_path, game_content)
+        IniParser::write_atomic(&game_path, &game_content)
             .map_err(|e| format!("Failed to write Game.ini: {}", e))?;
 
         Ok(())
     }
+
+    /// Rebuild a ServerConfig from existing GameUserSettings.ini and Game.ini contents.
+    /// Keys are matched case-insensitively since ARK accepts any casing; anything missing
+    /// keeps its default, and keys the generator doesn't know are left to the files.
+    pub fn parse_configs(gus: &str, game: &str) -> ServerConfig {
+        let mut values: HashMap<(String, String), String> = HashMap::new();
+        for content in [gus, game] {
+            for (section, key, value) in IniParser::parse(content).entries() {
+                values.insert(
+                    (section.to_lowercase(), key.to_lowercase()),
+                    value.to_string(),
+                );
+            }
+        }
+
+        let get = |section: &str, key: &str| {
+            values
+                .get(&(section.to_lowercase(), key.to_lowercase()))
+                .map(|v| v.trim_matches('"').to_string())
+        };
+        let number = |section: &str, key: &str, target: &mut f32| {
+            if let Some(v) = get(section, key).and_then(|v| v.parse().ok()) {
+                *target = v;
+            }
+        };
+        let flag = |section: &str, key: &str, target: &mut bool| {
+            if let Some(v) = get(section, key) {
+                *target = v.eq_ignore_ascii_case("true");
+            }
+        };
+
+        let mut config = ServerConfig::default();
+
+        // Identity & network
+        if let Some(v) = get(SESSION_SECTION, "SessionName")
+            .or_else(|| get(GUS_SECTION, "SessionName"))
+            .filter(|v| !v.is_empty())
+        {
+            config.session_name = v;
+        }
+        config.server_password = get(GUS_SECTION, "ServerPassword").filter(|v| !v.is_empty());
+        if let Some(v) = get(GUS_SECTION, "ServerAdminPassword").filter(|v| !v.is_empty()) {
+            config.admin_password = v;
+        }
+        if let Some(v) = get(GUS_SECTION, "MapName").filter(|v| !v.is_empty()) {
+            config.map_name = v;
+        }
+        if let Some(v) = get(GAME_SESSION_SECTION, "MaxPlayers")
+            .or_else(|| get(GUS_SECTION, "MaxPlayers"))
+            .and_then(|v| v.parse().ok())
+        {
+            config.max_players = v;
+        }
+        if let Some(v) = get(SESSION_SECTION, "Port").and_then(|v| v.parse().ok()) {
+            config.game_port = v;
+        }
+        if let Some(v) = get(SESSION_SECTION, "QueryPort").and_then(|v| v.parse().ok()) {
+            config.query_port = v;
+        }
+        if let Some(v) = get(GUS_SECTION, "RCONPort").and_then(|v| v.parse().ok()) {
+            config.rcon_port = v;
+        }
+        flag(GUS_SECTION, "RCONEnabled", &mut config.rcon_enabled);
+        if let Some(v) = get(GUS_SECTION, "ServerPlatform") {
+            config.crossplay_platforms = v
+                .split('+')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        // Rates
+        number(GUS_SECTION, "XPMultiplier", &mut config.xp_multiplier);
+        number(
+            GUS_SECTION,
+            "HarvestAmountMultiplier",
+            &mut config.harvest_amount_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "TamingSpeedMultiplier",
+            &mut config.taming_speed_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "DifficultyOffset",
+            &mut config.difficulty_offset,
+        );
+        number(
+            GUS_SECTION,
+            "OverrideOfficialDifficulty",
+            &mut config.override_official_difficulty,
+        );
+
+        // Day/Night
+        number(
+            GUS_SECTION,
+            "DayCycleSpeedScale",
+            &mut config.day_cycle_speed_scale,
+        );
+        number(
+            GUS_SECTION,
+            "DayTimeSpeedScale",
+            &mut config.day_time_speed_scale,
+        );
+        number(
+            GUS_SECTION,
+            "NightTimeSpeedScale",
+            &mut config.night_time_speed_scale,
+        );
+
+        // Player Stats
+        number(
+            GUS_SECTION,
+            "PlayerDamageMultiplier",
+            &mut config.player_damage_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "PlayerResistanceMultiplier",
+            &mut config.player_resistance_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "PlayerCharacterFoodDrainMultiplier",
+            &mut config.player_food_drain_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "PlayerCharacterWaterDrainMultiplier",
+            &mut config.player_water_drain_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "PlayerCharacterStaminaDrainMultiplier",
+            &mut config.player_stamina_drain_multiplier,
+        );
+
+        // Dino Stats
+        number(
+            GUS_SECTION,
+            "DinoDamageMultiplier",
+            &mut config.dino_damage_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "DinoResistanceMultiplier",
+            &mut config.dino_resistance_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "DinoCharacterFoodDrainMultiplier",
+            &mut config.dino_food_drain_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "DinoCountMultiplier",
+            &mut config.wild_dino_count_multiplier,
+        );
+
+        // Breeding
+        number(
+            GAME_INI_SECTION,
+            "EggHatchSpeedMultiplier",
+            &mut config.egg_hatch_speed_multiplier,
+        );
+        number(
+            GAME_INI_SECTION,
+            "BabyMatureSpeedMultiplier",
+            &mut config.baby_mature_speed_multiplier,
+        );
+        number(
+            GAME_INI_SECTION,
+            "BabyFoodConsumptionSpeedMultiplier",
+            &mut config.baby_food_consumption_multiplier,
+        );
+        number(
+            GAME_INI_SECTION,
+            "MatingIntervalMultiplier",
+            &mut config.mating_interval_multiplier,
+        );
+
+        // Structure
+        number(
+            GUS_SECTION,
+            "StructureDamageMultiplier",
+            &mut config.structure_damage_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "StructureResistanceMultiplier",
+            &mut config.structure_resistance_multiplier,
+        );
+        number(
+            GUS_SECTION,
+            "PvEStructureDecayPeriodMultiplier",
+            &mut config.structure_decay_multiplier,
+        );
+
+        // PvP/PvE
+        flag(GUS_SECTION, "ServerPVE", &mut config.pve_mode);
+        flag(GUS_SECTION, "EnablePvPGamma", &mut config.pvp_gamma);
+        let mut disable_friendly_fire = !config.friendly_fire;
+        flag(
+            GUS_SECTION,
+            "DisableFriendlyFire",
+            &mut disable_friendly_fire,
+        );
+        config.friendly_fire = !disable_friendly_fire;
+
+        // Mods
+        if let Some(v) = get(GUS_SECTION, "ActiveMods") {
+            config.active_mods = v
+                .split(',')
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty())
+                .collect();
+        }
+
+        // Advanced Gameplay
+        flag(
+            GAME_INI_SECTION,
+            "bAllowFlyerSpeedLeveling",
+            &mut config.allow_flyer_speed_leveling,
+        );
+        flag(
+            GAME_INI_SECTION,
+            "bAllowSpeedLeveling",
+            &mut config.allow_speed_leveling,
+        );
+
+        // Per-Level Stat Multipliers
+        for (prefix, stats) in [
+            (
+                "perlevelstatsmultiplier_player[",
+                &mut config.per_level_stats_multiplier_player,
+            ),
+            (
+                "perlevelstatsmultiplier_dinotamed[",
+                &mut config.per_level_stats_multiplier_dino_tamed,
+            ),
+            (
+                "perlevelstatsmultiplier_dinowild[",
+                &mut config.per_level_stats_multiplier_dino_wild,
+            ),
+        ] {
+            for ((section, key), value) in &values {
+                if *section != GAME_INI_SECTION.to_lowercase() {
+                    continue;
+                }
+                let Some(index) = key
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(']'))
+                    .and_then(|i| i.parse::<usize>().ok())
+                else {
+                    continue;
+                };
+                if let (Some(slot), Ok(v)) = (stats.get_mut(index), value.parse()) {
+                    *slot = v;
+                }
+            }
+        }
+
+        // Cluster Transfers
+        flag(
+            GUS_SECTION,
+            "PreventDownloadSurvivors",
+            &mut config.prevent_download_survivors,
+        );
+        flag(
+            GUS_SECTION,
+            "PreventDownloadItems",
+            &mut config.prevent_download_items,
+        );
+        flag(
+            GUS_SECTION,
+            "PreventDownloadDinos",
+            &mut config.prevent_download_dinos,
+        );
+        flag(
+            GUS_SECTION,
+            "PreventUploadSurvivors",
+            &mut config.prevent_upload_survivors,
+        );
+        flag(
+            GUS_SECTION,
+            "PreventUploadItems",
+            &mut config.prevent_upload_items,
+        );
+        flag(
+            GUS_SECTION,
+            "PreventUploadDinos",
+            &mut config.prevent_upload_dinos,
+        );
+        flag(
+            GUS_SECTION,
+            "noTributeDownloads",
+            &mut config.no_tribute_downloads,
+        );
+        flag(
+            GUS_SECTION,
+            "CrossARKAllowForeignDinoDownloads",
+            &mut config.cross_ark_allow_foreign_dino_downloads,
+        );
+        let seconds = |key: &str| get(GUS_SECTION, key).and_then(|v| v.parse().ok());
+        config.tribute_item_expiration_seconds = seconds("TributeItemExpirationSeconds");
+        config.tribute_dino_expiration_seconds = seconds("TributeDinoExpirationSeconds");
+        config.tribute_character_expiration_seconds = seconds("TributeCharacterExpirationSeconds");
+        config.max_tribute_items = seconds("MaxTributeItems");
+        config.max_tribute_dinos = seconds("MaxTributeDinos");
+
+        config
+    }
+
+    /// Read the config files of an existing install back into a ServerConfig.
+    /// Returns None when neither file exists yet.
+    pub fn read_configs(install_path: &PathBuf) -> Option<ServerConfig> {
+        let config_dir = install_path
+            .join("ShooterGame")
+            .join("Saved")
+            .join("Config")
+            .join("WindowsServer");
+
+        let gus = fs::read_to_string(config_dir.join("GameUserSettings.ini")).ok();
+        let game = fs::read_to_string(config_dir.join("Game.ini")).ok();
+        if gus.is_none() && game.is_none() {
+            return None;
+        }
+
+        Some(Self::parse_configs(
+            gus.as_deref().unwrap_or_default(),
+            game.as_deref().unwrap_or_default(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_configs_reads_both_files() {
+        let gus = "[ServerSettings]\r\nSessionName=Rag PvE\r\nxpmultiplier=2.5\r\nServerPVE=True\r\nDisableFriendlyFire=False\r\nActiveMods=928793, 929420\r\n";
+        let game = "[/Script/ShooterGame.ShooterGameMode]\nBabyMatureSpeedMultiplier=10\nPerLevelStatsMultiplier_Player[7]=3.0\nbAllowSpeedLeveling=true\n";
+
+        let config = ConfigGenerator::parse_configs(gus, game);
+        assert_eq!(config.session_name, "Rag PvE");
+        assert_eq!(config.xp_multiplier, 2.5);
+        assert!(config.pve_mode);
+        assert!(config.friendly_fire);
+        assert_eq!(config.active_mods, vec!["928793", "929420"]);
+        assert_eq!(config.baby_mature_speed_multiplier, 10.0);
+        assert_eq!(config.per_level_stats_multiplier_player[7], 3.0);
+        assert!(config.allow_speed_leveling);
+        // Missing keys keep their defaults
+        assert_eq!(config.taming_speed_multiplier, 1.0);
+        assert_eq!(config.map_name, "TheIsland_WP");
+    }
+
+    /// Same-named keys in other sections (ASA's own or a mod's) don't overwrite the ones read
+    #[test]
+    fn parse_configs_keys_by_section() {
+        let gus = "[SessionSettings]\r\nSessionName=Scorched PvP\r\nPort=7779\r\n\
+                   [ServerSettings]\r\nDifficultyOffset=0.8\r\nServerPVE=True\r\n\
+                   [/Script/Engine.GameSession]\r\nMaxPlayers=32\r\n\
+                   [StructuresPlus]\r\nDifficultyOffset=0.2\r\nServerPVE=False\r\n";
+        let game = "[/Script/ShooterGame.ShooterGameMode]\nMatingIntervalMultiplier=0.5\n\
+                    [SomeMod]\nMatingIntervalMultiplier=9\nPerLevelStatsMultiplier_Player[7]=50\n";
+
+        let config = ConfigGenerator::parse_configs(gus, game);
+        assert_eq!(config.session_name, "Scorched PvP");
+        assert_eq!(config.game_port, 7779);
+        assert_eq!(config.max_players, 32);
+        assert_eq!(config.difficulty_offset, 0.8);
+        assert!(config.pve_mode);
+        assert_eq!(config.mating_interval_multiplier, 0.5);
+        assert_eq!(config.per_level_stats_multiplier_player[7], 1.0);
+    }
+
+    #[test]
+    fn parse_configs_round_trips_generated_files() {
+        let mut config = ServerConfig {
+            map_name: "Ragnarok_WP".to_string(),
+            taming_speed_multiplier: 5.0,
+            egg_hatch_speed_multiplier: 20.0,
+            max_tribute_dinos: Some(40),
+            ..Default::default()
+        };
+        config.per_level_stats_multiplier_dino_wild[0] = 0.5;
+
+        let parsed = ConfigGenerator::parse_configs(
+            &ConfigGenerator::generate_game_user_settings(&config),
+            &ConfigGenerator::generate_game_ini(&config),
+        );
+        assert_eq!(parsed.map_name, "Ragnarok_WP");
+        assert_eq!(parsed.taming_speed_multiplier, 5.0);
+        assert_eq!(parsed.egg_hatch_speed_multiplier, 20.0);
+        assert_eq!(parsed.per_level_stats_multiplier_dino_wild[0], 0.5);
+        assert_eq!(parsed.max_tribute_dinos, Some(40));
+        assert_eq!(parsed.friendly_fire, config.friendly_fire);
+    }
+
+    #[test]
+    fn crossplay_platforms_are_written_to_server_settings() {
+        let config = ServerConfig {
+            crossplay_platforms: vec!["PC".to_string(), "XSX".to_string(), "PS5".to_string()],
+            ..Default::default()
+        };
+        let gus = ConfigGenerator::generate_game_user_settings(&config);
+        assert_eq!(
+            IniParser::parse(&gus).get("ServerSettings", "ServerPlatform"),
+            Some("PC+XSX+PS5")
+        );
+        let parsed = ConfigGenerator::parse_configs(&gus, "");
+        assert_eq!(parsed.crossplay_platforms, config.crossplay_platforms);
+
+        // No selection leaves the key out, so the game default applies
+        let gus = ConfigGenerator::generate_game_user_settings(&ServerConfig::default());
+        assert!(!gus.contains("ServerPlatform"));
+        assert!(ConfigGenerator::parse_configs(&gus, "")
+            .crossplay_platforms
+            .is_empty());
+    }
+
+    /// Saving from the generator keeps hand-written Game.ini content: repeated
+    /// override keys, comments and their order
+    #[test]
+    fn write_configs_keeps_repeated_game_ini_keys() {
+        let dir = crate::db::test_support::TempDir::new("write_configs");
+        let install_path = dir.path().to_path_buf();
+        let config_dir = install_path.join("ShooterGame/Saved/Config/WindowsServer");
+        fs::create_dir_all(&config_dir).unwrap();
+        let overrides = [
+            "; stack sizes",
+            "ConfigOverrideItemMaxQuantity=(ItemClassString=\"PrimalItemResource_Stone_C\",Quantity=(MaxItemQuantity=1000,bIgnoreMultiplier=true))",
+            "ConfigOverrideItemMaxQuantity=(ItemClassString=\"PrimalItemResource_Wood_C\",Quantity=(MaxItemQuantity=1000,bIgnoreMultiplier=true))",
+            "ConfigOverrideItemMaxQuantity=(ItemClassString=\"PrimalItemResource_Metal_C\",Quantity=(MaxItemQuantity=500,bIgnoreMultiplier=true))",
+        ];
+        fs::write(
+            config_dir.join("Game.ini"),
+            format!(
+                "[/Script/ShooterGame.ShooterGameMode]\nEggHatchSpeedMultiplier=5.00\n{}\n",
+                overrides.join("\n")
+            ),
+        )
+        .unwrap();
+
+        let config = ServerConfig {
+            egg_hatch_speed_multiplier: 20.0,
+            ..Default::default()
+        };
+        for _ in 0..2 {
+            ConfigGenerator::write_configs(&install_path, &config, &HashMap::new(), false).unwrap();
+        }
+
+        let game = fs::read_to_string(config_dir.join("Game.ini")).unwrap();
+        assert!(game.contains(&format!(
+            "EggHatchSpeedMultiplier=20.00\n{}\n",
+            overrides.join("\n")
+        )));
+        assert_eq!(game.matches("ConfigOverrideItemMaxQuantity=").count(), 3);
+    }
+
+    #[test]
+    fn write_configs_removes_dropped_extra_settings() {
+        let dir = crate::db::test_support::TempDir::new("write_configs_extra");
+        let install_path = dir.path().to_path_buf();
+        let mut config = ServerConfig::default();
+        config.extra_settings.insert(
+            "ServerSettings".to_string(),
+            HashMap::from([
+                ("AllowFlyerCarryPvE".to_string(), "True".to_string()),
+                ("ShowMapPlayerLocation".to_string(), "True".to_string()),
+            ]),
+        );
+        ConfigGenerator::write_configs(&install_path, &config, &HashMap::new(), false).unwrap();
+
+        let previous = config.extra_settings.clone();
+        config
+            .extra_settings
+            .get_mut("ServerSettings")
+            .unwrap()
+            .remove("AllowFlyerCarryPvE");
+        ConfigGenerator::write_configs(&install_path, &config, &previous, false).unwrap();
+
+        let gus = fs::read_to_string(
+            install_path.join("ShooterGame/Saved/Config/WindowsServer/GameUserSettings.ini"),
+        )
+        .unwrap();
+        assert!(!gus.contains("AllowFlyerCarryPvE"));
+        assert!(gus.contains("ShowMapPlayerLocation=True"));
+    }
 }
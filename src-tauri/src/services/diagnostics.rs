@@ -0,0 +1,369 @@
+// Diagnostics bundle: everything a bug report needs, zipped into one file
+// Secrets never leave the machine: sensitive DB columns and settings are replaced
+// with a marker, and every redacted value is also scrubbed from the copied logs.
+
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+use serde_json::{json, Map, Value};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use sysinfo::{Disks, System};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+pub const REDACTED: &str = "<redacted>";
+
+/// Crash report folders taken per server, newest first
+const CRASH_FOLDERS_PER_SERVER: usize = 3;
+/// Larger crash files (minidumps) are left out
+const MAX_CRASH_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+const SENSITIVE_NAME_PARTS: &[&str] = &[
+    "password",
+    "token",
+    "secret",
+    "key",
+    "webhook",
+    "credential",
+    "smtp",
+];
+
+/// Column or settings key that holds a secret
+pub fn is_sensitive(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SENSITIVE_NAME_PARTS.iter().any(|part| name.contains(part))
+}
+
+/// Replace every known secret value in `text`. Very short values are skipped so a
+/// one-character password doesn't mangle the whole file.
+pub fn redact_secrets(text: &str, secrets: &[String]) -> String {
+    let mut out = text.to_string();
+    for secret in secrets.iter().filter(|s| s.len() >= 4) {
+        out = out.replace(secret.as_str(), REDACTED);
+    }
+    out
+}
+
+/// Collects redacted values while rows are read, so they can be scrubbed from logs
+#[derive(Default)]
+pub struct Redactor {
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    fn redact(&mut self, value: String) -> Value {
+        if value.is_empty() {
+            return Value::String(value);
+        }
+        if !self.secrets.contains(&value) {
+            self.secrets.push(value);
+        }
+        Value::String(REDACTED.to_string())
+    }
+
+    pub fn scrub(&self, text: &str) -> String {
+        redact_secrets(text, &self.secrets)
+    }
+}
+
+fn value_to_json(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => json!(i),
+        ValueRef::Real(f) => json!(f),
+        ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).to_string()),
+        ValueRef::Blob(b) => Value::String(format!("<{} byte blob>", b.len())),
+    }
+}
+
+/// All rows of `table` as JSON objects, with sensitive columns redacted
+pub fn table_rows(
+    conn: &Connection,
+    table: &str,
+    redactor: &mut Redactor,
+) -> Result<Value, String> {
+    let mut stmt = conn
+        .prepare(&format!("SELECT * FROM {}", table))
+        .map_err(|e| e.to_string())?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+    let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        let mut object = Map::new();
+        for (i, column) in columns.iter().enumerate() {
+            let value = value_to_json(row.get_ref(i).map_err(|e| e.to_string())?);
+            let value = match value {
+                Value::String(s) if is_sensitive(column) => redactor.redact(s),
+                other => other,
+            };
+            object.insert(column.clone(), value);
+        }
+        out.push(Value::Object(object));
+    }
+    Ok(Value::Array(out))
+}
+
+/// Redact the strings of a JSON setting that sit under a sensitive key at any depth,
+/// and every URL (webhooks carry their token in the URL)
+fn redact_json(value: Value, sensitive: bool, redactor: &mut Redactor) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut out = Map::new();
+            for (key, value) in object {
+                let sensitive = sensitive || is_sensitive(&key);
+                out.insert(key, redact_json(value, sensitive, redactor));
+            }
+            Value::Object(out)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| redact_json(item, sensitive, redactor))
+                .collect(),
+        ),
+        Value::String(s) if sensitive || s.contains("://") => redactor.redact(s),
+        other => other,
+    }
+}
+
+/// The settings table as a key -> value map. Sensitive keys and anything holding a
+/// URL are redacted; JSON values (notification channels, the chat bridge) are
+/// redacted field by field.
+pub fn settings_rows(conn: &Connection, redactor: &mut Redactor) -> Result<Value, String> {
+    let mut stmt = conn
+        .prepare("SELECT key, value FROM settings ORDER BY key")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut out = Map::new();
+    for (key, value) in rows.filter_map(|r| r.ok()) {
+        let value = if is_sensitive(&key) {
+            redactor.redact(value)
+        } else {
+            match serde_json::from_str::<Value>(&value) {
+                Ok(json @ (Value::Object(_) | Value::Array(_))) => {
+                    redact_json(json, false, redactor)
+                }
+                _ if value.contains("://") => redactor.redact(value),
+                _ => Value::String(value),
+            }
+        };
+        out.insert(key, value);
+    }
+    Ok(Value::Object(out))
+}
+
+/// CREATE statements for every table and index
+pub fn schema_sql(conn: &Connection) -> Result<String, String> {
+    let mut stmt = conn
+        .prepare("SELECT sql FROM sqlite_master WHERE sql IS NOT NULL ORDER BY type DESC, name")
+        .map_err(|e| e.to_string())?;
+    let statements: Vec<String> = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(statements.join(";\n\n") + ";\n")
+}
+
+/// OS, CPU, memory and disk summary
+pub fn system_report(sys: &System) -> Value {
+    let disks = Disks::new_with_refreshed_list();
+    json!({
+        "os": System::name(),
+        "osVersion": System::long_os_version(),
+        "kernelVersion": System::kernel_version(),
+        "cpu": sys.cpus().first().map(|c| c.brand().trim().to_string()),
+        "cpuCores": sys.cpus().len(),
+        "physicalCores": sys.physical_core_count(),
+        "memoryTotalMb": sys.total_memory() / 1024 / 1024,
+        "memoryUsedMb": sys.used_memory() / 1024 / 1024,
+        "disks": disks.list().iter().map(|d| json!({
+            "mountPoint": d.mount_point().to_string_lossy(),
+            "fileSystem": d.file_system().to_string_lossy(),
+            "totalGb": d.total_space() / 1024 / 1024 / 1024,
+            "availableGb": d.available_space() / 1024 / 1024 / 1024,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// Where SteamCMD is and what its install looks like. SteamCMD has no version flag,
+/// so the version reported is the one in its package manifest when present.
+pub fn steamcmd_report(steamcmd_dir: &Path) -> Value {
+    let exe = steamcmd_dir.join("steamcmd.exe");
+    let modified = fs::metadata(&exe)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339());
+    let version = fs::read_to_string(
+        steamcmd_dir
+            .join("package")
+            .join("steam_cmd_win32.manifest"),
+    )
+    .ok()
+    .and_then(|manifest| {
+        manifest.lines().find_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("\"version\"")
+                .map(|v| v.trim().trim_matches('"').to_string())
+        })
+    });
+    json!({
+        "path": exe.to_string_lossy(),
+        "installed": exe.exists(),
+        "modified": modified,
+        "version": version,
+    })
+}
+
+/// Small files from the newest crash report folders under ShooterGame/Saved/Crashes,
+/// as (path inside the bundle, file on disk)
+pub fn crash_files(install_path: &Path, bundle_dir: &str) -> Vec<(String, PathBuf)> {
+    let crashes_dir = install_path
+        .join("ShooterGame")
+        .join("Saved")
+        .join("Crashes");
+    let Ok(entries) = fs::read_dir(&crashes_dir) else {
+        return Vec::new();
+    };
+
+    let mut folders: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .collect();
+    folders.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut files = Vec::new();
+    for (_, folder) in folders.into_iter().take(CRASH_FOLDERS_PER_SERVER) {
+        let folder_name = folder
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        for entry in WalkDir::new(&folder)
+            .max_depth(1)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let small = entry
+                .metadata()
+                .map(|m| m.is_file() && m.len() <= MAX_CRASH_FILE_BYTES)
+                .unwrap_or(false);
+            if small {
+                files.push((
+                    format!(
+                        "{}/{}/{}",
+                        bundle_dir,
+                        folder_name,
+                        entry.file_name().to_string_lossy()
+                    ),
+                    entry.path().to_path_buf(),
+                ));
+            }
+        }
+    }
+    files
+}
+
+/// Write the named entries into a new zip at `dest`
+pub fn write_bundle(dest: &Path, entries: &[(String, Vec<u8>)]) -> Result<(), String> {
+    let file = File::create(dest).map_err(|e| format!("Failed to create {:?}: {}", dest, e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+
+    for (name, data) in entries {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+        zip.write_all(data)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish diagnostics bundle: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE servers (id INTEGER, name TEXT, admin_password TEXT, server_password TEXT);
+             INSERT INTO servers VALUES (1, 'Island', 'hunter22', NULL);
+             CREATE TABLE settings (key TEXT, value TEXT);
+             INSERT INTO settings VALUES ('curseforge_api_key', 'cf-abcdef');
+             INSERT INTO settings VALUES ('discord_config', '{\"url\":\"https://discord.com/api/webhooks/1/x\"}');
+             INSERT INTO settings VALUES ('startup_timeout_minutes', '15');
+             INSERT INTO settings VALUES ('notification_channels', '[{\"name\":\"Admins\",\"type\":\"email\",\"smtpHost\":\"smtp.example.com\",\"smtpPort\":587,\"username\":\"ark@example.com\",\"password\":\"mail-pass-1\",\"from\":\"ark@example.com\",\"to\":[\"admin@example.com\"]}]');
+             INSERT INTO settings VALUES ('chat_bridge_config', '{\"botToken\":\"bot-token-1\",\"links\":[{\"serverId\":1,\"enabled\":true,\"channelId\":\"123\"}]}');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn redacts_sensitive_columns() {
+        let conn = test_db();
+        let mut redactor = Redactor::default();
+        let rows = table_rows(&conn, "servers", &mut redactor).unwrap();
+
+        assert_eq!(rows[0]["name"], "Island");
+        assert_eq!(rows[0]["admin_password"], REDACTED);
+        assert_eq!(rows[0]["server_password"], Value::Null);
+        assert_eq!(
+            redactor.scrub("login with hunter22 ok"),
+            format!("login with {} ok", REDACTED)
+        );
+    }
+
+    #[test]
+    fn redacts_keys_and_urls_in_settings() {
+        let conn = test_db();
+        let mut redactor = Redactor::default();
+        let settings = settings_rows(&conn, &mut redactor).unwrap();
+
+        assert_eq!(settings["curseforge_api_key"], REDACTED);
+        assert_eq!(settings["discord_config"]["url"], REDACTED);
+        assert_eq!(settings["startup_timeout_minutes"], "15");
+    }
+
+    /// Secrets inside JSON settings: an email channel's SMTP password and a bridge
+    /// configured with only a bot token (so no webhook URL gives it away)
+    #[test]
+    fn redacts_secrets_inside_json_settings() {
+        let conn = test_db();
+        let mut redactor = Redactor::default();
+        let settings = settings_rows(&conn, &mut redactor).unwrap();
+
+        let email = &settings["notification_channels"][0];
+        assert_eq!(email["password"], REDACTED);
+        assert_eq!(email["smtpHost"], REDACTED);
+        assert_eq!(email["name"], "Admins");
+        assert_eq!(email["to"][0], "admin@example.com");
+        assert_eq!(settings["chat_bridge_config"]["botToken"], REDACTED);
+        assert_eq!(
+            settings["chat_bridge_config"]["links"][0]["channelId"],
+            "123"
+        );
+
+        let log = "smtp login mail-pass-1 failed; bot bot-token-1";
+        assert!(!redactor.scrub(log).contains("mail-pass-1"));
+        assert!(!redactor.scrub(log).contains("bot-token-1"));
+    }
+
+    #[test]
+    fn short_secrets_are_not_scrubbed() {
+        assert_eq!(redact_secrets("a b c", &["a".to_string()]), "a b c");
+    }
+}
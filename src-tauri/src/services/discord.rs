@@ -24,13 +24,47 @@ impl DiscordService {
             "embeds": [embed.to_json()]
         });
 
-        self.client
+        let response = self
+            .client
             .post(webhook_url)
             .json(&payload)
             .send()
             .await
             .map_err(|e| format!("Failed to send webhook: {}", e))?;
 
+        if !response.status().is_success() {
+            return Err(format!("Webhook returned HTTP {}", response.status()));
+        }
+
+        Ok(())
+    }
+
+    /// Post a plain chat line under a custom name. Mentions are never resolved, so
+    /// in-game text can't ping @everyone or roles.
+    pub async fn send_chat(
+        &self,
+        webhook_url: &str,
+        username: &str,
+        content: &str,
+    ) -> Result<(), String> {
+        let payload = json!({
+            "username": username,
+            "content": content,
+            "allowed_mentions": { "parse": [] },
+        });
+
+        let response = self
+            .client
+            .post(webhook_url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send webhook: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Webhook returned HTTP {}", response.status()));
+        }
+
         Ok(())
     }
 
@@ -196,6 +230,79 @@ impl DiscordService {
         self.send_webhook(webhook_url, embed).await
     }
 
+    /// Notify backup result, with the archive size on success
+    pub async fn notify_backup(
+        &self,
+        config: &DiscordConfig,
+        server_name: &str,
+        result: Result<i64, &str>,
+    ) -> Result<(), String> {
+        if !config.notify_backup {
+            return Ok(());
+        }
+
+        let webhook_url = config
+            .webhook_url
+            .as_ref()
+            .ok_or("No webhook URL configured")?;
+
+        let embed = match result {
+            Ok(size) => DiscordEmbed {
+                title: "💾 Backup Completed".to_string(),
+                description: format!("Backup of **{}** finished.", server_name),
+                color: 0x22C55E, // Green
+                fields: vec![EmbedField {
+                    name: "Size".to_string(),
+                    value: format!("{:.1} MB", size as f64 / 1_048_576.0),
+                    inline: true,
+                }],
+                footer: Some("ASA Server Manager 2.0".to_string()),
+                timestamp: Some(chrono::Utc::now().to_rfc3339()),
+            },
+            Err(error) => DiscordEmbed {
+                title: "⚠️ Backup Failed".to_string(),
+                description: format!("Backup of **{}** failed.", server_name),
+                color: 0xF59E0B, // Amber
+                fields: vec![EmbedField {
+                    name: "Error".to_string(),
+                    value: format!("```{}```", error),
+                    inline: false,
+                }],
+                footer: Some("ASA Server Manager 2.0".to_string()),
+                timestamp: Some(chrono::Utc::now().to_rfc3339()),
+            },
+        };
+
+        self.send_webhook(webhook_url, embed).await
+    }
+
+    /// Notify server files updated via SteamCMD
+    pub async fn notify_server_updated(
+        &self,
+        config: &DiscordConfig,
+        server_name: &str,
+    ) -> Result<(), String> {
+        if !config.notify_server_update {
+            return Ok(());
+        }
+
+        let webhook_url = config
+            .webhook_url
+            .as_ref()
+            .ok_or("No webhook URL configured")?;
+
+        let embed = DiscordEmbed {
+            title: "📥 Server Updated".to_string(),
+            description: format!("**{}** was updated to the latest build.", server_name),
+            color: 0x3B82F6, // Blue
+            fields: vec![],
+            footer: Some("ASA Server Manager 2.0".to_string()),
+            timestamp: Some(chrono::Utc::now().to_rfc3339()),
+        };
+
+        self.send_webhook(webhook_url, embed).await
+    }
+
     /// Notify scheduled task execution
     pub async fn notify_scheduled_task(
         &self,
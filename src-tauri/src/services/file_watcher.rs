@@ -1,8 +1,12 @@
+use crate::models::{AutoStopTrigger, ServerStatus, TriggerEvent, TriggerMatch};
+use crate::services::server_state;
 use crate::AppState;
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
-use rusqlite::Row;
+use notify::event::ModifyKind;
+use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rusqlite::{Connection, Row};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::RecvTimeoutError;
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -22,7 +26,13 @@ impl FileWatcherService {
         }
     }
 
-    pub fn start_watching(&self, server_id: i64, path: PathBuf) -> Result<(), String> {
+    /// Watch a server's install folder for its auto-stop triggers (see load_triggers)
+    pub fn start_watching(
+        &self,
+        server_id: i64,
+        path: PathBuf,
+        triggers: Vec<AutoStopTrigger>,
+    ) -> Result<(), String> {
         let app_handle = self.app_handle.clone();
 
         // Channel for watcher events
@@ -31,183 +41,524 @@ impl FileWatcherService {
         let mut watcher = RecommendedWatcher::new(tx, Config::default())
             .map_err(|e| format!("Failed to create watcher: {}", e))?;
 
-        // Watch the specific directories
-        let config_path = path.join("ShooterGame/Saved/Config/WindowsServer");
-        let saves_path = path.join("ShooterGame/Saved/SavedArks");
-
-        if config_path.exists() {
-            let _ = watcher.watch(&config_path, RecursiveMode::NonRecursive);
-            println!("🛡️ Automation: Watching config dir: {:?}", config_path);
+        // Only the folders the triggers point at are watched, so busy folders like
+        // SavedArks stay out of it unless a trigger asks for them
+        let mut targets: Vec<(String, bool)> = Vec::new();
+        for target in triggers.iter().map(AutoStopTrigger::watch_target) {
+            match targets.iter_mut().find(|(dir, _)| *dir == target.0) {
+                Some(existing) => existing.1 |= target.1,
+                None => targets.push(target),
+            }
         }
-
-        if saves_path.exists() {
-            let _ = watcher.watch(&saves_path, RecursiveMode::NonRecursive);
-            println!("🛡️ Automation: Watching saves dir: {:?}", saves_path);
+        let mut watched = 0;
+        for (dir, recursive) in &targets {
+            let dir_path = path.join(dir);
+            if !dir_path.is_dir() {
+                log::warn!("🛡️ Automation: Trigger folder missing: {:?}", dir_path);
+                continue;
+            }
+            let mode = if *recursive {
+                RecursiveMode::Recursive
+            } else {
+                RecursiveMode::NonRecursive
+            };
+            match watcher.watch(&dir_path, mode) {
+                Ok(()) => {
+                    watched += 1;
+                    log::info!("🛡️ Automation: Watching {:?} ({:?})", dir_path, mode);
+                }
+                Err(e) => log::warn!("🛡️ Automation: Failed to watch {:?}: {}", dir_path, e),
+            }
+        }
+        if watched == 0 {
+            return Err("None of the auto-stop trigger folders could be watched".to_string());
         }
-
-        // Always watch the root path as well (for general updates)
-        watcher
-            .watch(&path, RecursiveMode::NonRecursive)
-            .map_err(|e| format!("Failed to watch root path: {}", e))?;
 
         // Start a thread to handle events
         let server_id_clone = server_id;
         let app_handle_clone = app_handle.clone();
+        // Set while an auto-stop runs; ARK rewrites its INIs on the way down
+        let stopping = Arc::new(AtomicBool::new(false));
 
         thread::spawn(move || {
             loop {
-                match rx.recv() {
-                    Ok(event) => {
-                        if let Ok(e) = event {
-                            // Ignore Access events (too noisy), focus on Modify/Create/Remove
-                            // notify 6.x: Access, Create, Modify, Remove, Rename, Other.
-                            if matches!(e.kind, notify::EventKind::Access(_)) {
-                                continue;
-                            }
-
-                            println!(
-                                "🛡️ Automation: Detected file change for server {} ({:?})",
-                                server_id_clone, e.kind
-                            );
-
-                            // Debounce: Wait for 2 seconds of silence
-                            let mut quiet = false;
-                            while !quiet {
-                                match rx.recv_timeout(Duration::from_secs(2)) {
-                                    Ok(next_event) => {
-                                        if let Ok(next_e) = next_event {
-                                            if matches!(next_e.kind, notify::EventKind::Access(_)) {
-                                                // Ignore access events even during debounce
-                                                continue;
-                                            }
-                                            println!("   ... Debouncing (more changes detected)");
-                                        }
-                                    }
-                                    Err(RecvTimeoutError::Timeout) => {
-                                        quiet = true;
-                                    }
-                                    Err(_) => return, // Channel closed
-                                }
-                            }
-
-                            println!(
-                                "🛡️ Automation: Triggering Auto-Stop for server {}...",
-                                server_id_clone
-                            );
-
-                            // Trigger Stop Command
-                            let app_handle_bg = app_handle_clone.clone();
-
-                            tauri::async_runtime::spawn(async move {
-                                let state = app_handle_bg.state::<AppState>();
-
-                                // Fetch server details for Intelligent Mode check
-                                let server_details = {
-                                    if let Ok(db) = state.db.lock() {
-                                        if let Ok(conn) = db.get_connection() {
-                                            conn.query_row(
-                                                "SELECT intelligent_mode, rcon_enabled, admin_password, query_port, ip_address FROM servers WHERE id = ?1",
-                                                [server_id_clone],
-                                                |row: &Row| {
-                                                    Ok((
-                                                        row.get::<usize, i32>(0)? != 0, // intelligent_mode
-                                                        row.get::<usize, i32>(1)? != 0, // rcon_enabled
-                                                        row.get::<usize, String>(2)?,   // admin_password
-                                                        row.get::<usize, u16>(3)?,      // query_port
-                                                        row.get::<usize, Option<String>>(4)?, // ip_address
-                                                    ))
-                                                }
-                                            ).ok()
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    }
-                                };
-
-                                if let Some((intel_mode, rcon_on, pass, port, ip)) = server_details
-                                {
-                                    println!("🛡️ Automation: Stopping server {} (Intelligent Mode: {})...", server_id_clone, intel_mode);
-
-                                    if intel_mode && rcon_on {
-                                        // 1. Graceful shutdown
-                                        let addr = ip.unwrap_or_else(|| "127.0.0.1".to_string());
-                                        let rcon_state = state
-                                            .app_handle
-                                            .state::<crate::commands::rcon::RconState>(
-                                        );
-                                        let rcon = rcon_state.0.lock().await;
-
-                                        if let Err(e) = state
-                                            .process_manager
-                                            .shutdown_server(
-                                                server_id_clone,
-                                                &*rcon,
-                                                &addr,
-                                                port,
-                                                &pass,
-                                            )
-                                            .await
-                                        {
-                                            println!(
-                                                "❌ Automation Error: Graceful shutdown failed: {}",
-                                                e
-                                            );
-                                        }
-                                    } else {
-                                        // 1. Force stop (fallback or if intel mode off)
-                                        if let Err(e) =
-                                            state.process_manager.stop_server(server_id_clone)
-                                        {
-                                            println!(
-                                                "❌ Automation Error: Failed to stop server: {}",
-                                                e
-                                            );
-                                        }
-                                    }
-
-                                    // 2. Update DB status
-                                    if let Ok(db) = state.db.lock() {
-                                        if let Ok(conn) = db.get_connection() {
-                                            let _ = conn.execute(
-                                                "UPDATE servers SET status = 'stopped' WHERE id = ?1",
-                                                [server_id_clone],
-                                            );
-                                        }
-                                    };
-
-                                    // 3. Optional: Restart if Auto-Start is on?
-                                    // Maybe wait a bit more for the file operation to fully complete.
-                                }
-                            });
-
-                            // Prevent rapid re-triggering? The stop_server takes time.
-                            // We loop back to recv(), but likely files will change during stop?
-                            // If server stops, we might want to keep watching or not?
-                            // Logic: stop_server updates status.
-                            // If we detect changes WHILE stopping, we might re-trigger stop?
-                            // Ideally, stop_server is idempotent.
+                let (trigger, changed) = match rx.recv() {
+                    Ok(Ok(event)) => match matching_trigger(&event, &path, &triggers) {
+                        Some((trigger, changed)) => (trigger.clone(), changed),
+                        None => continue,
+                    },
+                    Ok(Err(_)) => continue,
+                    Err(_) => break, // Channel closed
+                };
+
+                log::info!(
+                    "🛡️ Automation: Trigger '{}' matched {:?} for server {}",
+                    trigger.pattern,
+                    changed,
+                    server_id_clone
+                );
+
+                // Debounce: wait for 2 seconds without further matching changes
+                loop {
+                    match rx.recv_timeout(Duration::from_secs(2)) {
+                        Ok(Ok(next)) if matching_trigger(&next, &path, &triggers).is_some() => {
+                            log::info!("   ... Debouncing (more changes detected)");
                         }
+                        Ok(_) => {}
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(_) => return, // Channel closed
                     }
-                    Err(_) => {
-                        break;
-                    }
                 }
+
+                if trigger.consume {
+                    // One-shot marker, removed so it doesn't fire again on the next start
+                    let _ = std::fs::remove_file(&changed);
+                }
+
+                if stopping.load(Ordering::SeqCst) {
+                    log::info!(
+                        "🛡️ Automation: Auto-stop for server {} already in progress",
+                        server_id_clone
+                    );
+                    continue;
+                }
+                let state = app_handle_clone.state::<AppState>();
+                if !state.process_manager.is_running(server_id_clone) {
+                    log::info!(
+                        "🛡️ Automation: Server {} is not running, nothing to stop",
+                        server_id_clone
+                    );
+                    continue;
+                }
+                // ARK writes its own INIs while booting
+                if trigger.ignore_during_startup
+                    && state
+                        .process_manager
+                        .in_startup_window(server_id_clone, STARTUP_GRACE)
+                {
+                    log::info!(
+                        "🛡️ Automation: Ignoring '{}' for server {} during startup",
+                        trigger.pattern,
+                        server_id_clone
+                    );
+                    continue;
+                }
+
+                log::info!(
+                    "🛡️ Automation: Triggering Auto-Stop for server {}...",
+                    server_id_clone
+                );
+                let app_handle_bg = app_handle_clone.clone();
+                let stopping_bg = stopping.clone();
+                stopping.store(true, Ordering::SeqCst);
+                tauri::async_runtime::spawn(async move {
+                    auto_stop(&app_handle_bg, server_id_clone).await;
+                    stopping_bg.store(false, Ordering::SeqCst);
+                });
             }
         });
 
         let mut watchers = self.watchers.lock().unwrap();
         watchers.insert(server_id, watcher);
 
-        println!("🛡️ Automation: Started watching server {}", server_id);
+        log::info!("🛡️ Automation: Started watching server {}", server_id);
         Ok(())
     }
 
+    pub fn is_watching(&self, server_id: i64) -> bool {
+        self.watchers.lock().unwrap().contains_key(&server_id)
+    }
+
     pub fn stop_watching(&self, server_id: i64) {
         let mut watchers = self.watchers.lock().unwrap();
         if watchers.remove(&server_id).is_some() {
-            println!("🛡️ Automation: Stopped watching server {}", server_id);
+            log::info!("🛡️ Automation: Stopped watching server {}", server_id);
+        }
+    }
+
+    /// Drop every watcher; returns the ids that were being watched
+    pub fn stop_all(&self) -> Vec<i64> {
+        let mut watchers = self.watchers.lock().unwrap();
+        let mut ids: Vec<i64> = watchers.drain().map(|(id, _)| id).collect();
+        ids.sort_unstable();
+        if !ids.is_empty() {
+            log::info!("🛡️ Automation: Stopped watching {} server(s)", ids.len());
         }
+        ids
+    }
+}
+
+/// Stop a watched server: gracefully over RCON in Intelligent Mode, otherwise by
+/// terminating the process
+async fn auto_stop(app_handle: &tauri::AppHandle, server_id: i64) {
+    let state = app_handle.state::<AppState>();
+
+    // Fetch server details for Intelligent Mode check
+    let server_details = {
+        if let Ok(conn) = state.db.get_connection() {
+            conn.query_row(
+                "SELECT intelligent_mode, rcon_enabled, admin_password, query_port, ip_address FROM servers WHERE id = ?1",
+                [server_id],
+                |row: &Row| {
+                    Ok((
+                        row.get::<usize, i32>(0)? != 0,       // intelligent_mode
+                        row.get::<usize, i32>(1)? != 0,       // rcon_enabled
+                        row.get::<usize, String>(2)?,         // admin_password
+                        row.get::<usize, u16>(3)?,            // query_port
+                        row.get::<usize, Option<String>>(4)?, // ip_address
+                    ))
+                },
+            )
+            .ok()
+        } else {
+            None
+        }
+    };
+
+    let Some((intel_mode, rcon_on, pass, port, ip)) = server_details else {
+        return;
+    };
+    log::info!(
+        "🛡️ Automation: Stopping server {} (Intelligent Mode: {})...",
+        server_id,
+        intel_mode
+    );
+
+    if intel_mode && rcon_on {
+        // 1. Graceful shutdown
+        let addr = ip.unwrap_or_else(|| "127.0.0.1".to_string());
+        let rcon_state = state.app_handle.state::<crate::commands::rcon::RconState>();
+        let rcon = &rcon_state.0;
+
+        if let Err(e) = state
+            .process_manager
+            .shutdown_server(server_id, rcon, &addr, port, &pass)
+            .await
+        {
+            log::error!("❌ Automation Error: Graceful shutdown failed: {}", e);
+        }
+    } else {
+        // 1. Force stop (fallback or if intel mode off)
+        if let Err(e) = state.process_manager.stop_server(server_id).await {
+            log::error!("❌ Automation Error: Failed to stop server: {}", e);
+        }
+    }
+
+    // 2. Update DB status
+    server_state::update(&state, server_id, ServerStatus::Stopped);
+}
+
+/// Dropping this file into the install root asks for an auto-stop (e.g. from a
+/// deploy script). It is deleted once acted on.
+pub const STOP_REQUEST_FILE: &str = "asm-stop.request";
+
+/// Triggers marked ignore_during_startup wait until the server has been up this long
+const STARTUP_GRACE: Duration = Duration::from_secs(120);
+
+/// Used when a server has no triggers of its own: edits to GameUserSettings.ini or
+/// Game.ini once the server is up, or the stop request file at any time.
+pub fn default_triggers() -> Vec<AutoStopTrigger> {
+    let ini = |name: &str| AutoStopTrigger {
+        match_type: TriggerMatch::File,
+        pattern: format!("ShooterGame/Saved/Config/WindowsServer/{}", name),
+        events: vec![TriggerEvent::Create, TriggerEvent::Modify],
+        ignore_during_startup: true,
+        consume: false,
+    };
+    vec![
+        ini("GameUserSettings.ini"),
+        ini("Game.ini"),
+        AutoStopTrigger {
+            match_type: TriggerMatch::File,
+            pattern: STOP_REQUEST_FILE.to_string(),
+            events: vec![TriggerEvent::Create],
+            ignore_during_startup: false,
+            consume: true,
+        },
+    ]
+}
+
+/// A server's configured triggers, or the defaults when none are stored
+pub fn load_triggers(conn: &Connection, server_id: i64) -> Vec<AutoStopTrigger> {
+    conn.query_row(
+        "SELECT auto_stop_triggers FROM servers WHERE id = ?1",
+        [server_id],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str::<Vec<AutoStopTrigger>>(&json).ok())
+    .filter(|triggers| !triggers.is_empty())
+    .unwrap_or_else(default_triggers)
+}
+
+pub fn validate_trigger(trigger: &AutoStopTrigger) -> Result<(), String> {
+    let pattern = trigger.pattern.trim();
+    if pattern.is_empty() {
+        return Err("Trigger pattern is empty".to_string());
+    }
+    let path = Path::new(pattern);
+    if path.is_absolute() || pattern.starts_with('/') || pattern.starts_with('\\') {
+        return Err(format!(
+            "Trigger pattern must be relative to the install folder: {}",
+            pattern
+        ));
+    }
+    if pattern.split(['/', '\\']).any(|part| part == "..") {
+        return Err(format!(
+            "Trigger pattern can't leave the install folder: {}",
+            pattern
+        ));
+    }
+    if trigger.match_type == TriggerMatch::File && pattern.contains(['*', '?']) {
+        return Err(format!("Wildcards need the glob match type: {}", pattern));
+    }
+    Ok(())
+}
+
+/// The trigger event a filesystem event corresponds to. Reads and metadata-only
+/// changes (timestamps, attributes) never count.
+fn trigger_event(kind: &EventKind) -> Option<TriggerEvent> {
+    match kind {
+        EventKind::Create(_) => Some(TriggerEvent::Create),
+        EventKind::Modify(ModifyKind::Name(_)) => Some(TriggerEvent::Rename),
+        EventKind::Modify(ModifyKind::Metadata(_)) => None,
+        EventKind::Modify(_) => Some(TriggerEvent::Modify),
+        EventKind::Remove(_) => Some(TriggerEvent::Remove),
+        _ => None,
+    }
+}
+
+/// `path` relative to `root` with '/' separators
+fn relative_path(path: &Path, root: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    Some(parts.join("/"))
+}
+
+/// Case-insensitive glob over '/'-separated paths: `*` and `?` stay within a
+/// segment, a `**` segment matches any number of segments
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    fn segments(text: &str) -> Vec<String> {
+        text.split(['/', '\\'])
+            .filter(|s| !s.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    }
+    fn match_segment(pattern: &[char], text: &[char]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some('*'), _) => {
+                match_segment(&pattern[1..], text)
+                    || (!text.is_empty() && match_segment(pattern, &text[1..]))
+            }
+            (Some('?'), Some(_)) => match_segment(&pattern[1..], &text[1..]),
+            (Some(p), Some(t)) if p == t => match_segment(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+    fn match_path(pattern: &[String], path: &[String]) -> bool {
+        match pattern.first() {
+            None => path.is_empty(),
+            Some(p) if p == "**" => {
+                match_path(&pattern[1..], path)
+                    || (!path.is_empty() && match_path(pattern, &path[1..]))
+            }
+            Some(p) => {
+                !path.is_empty()
+                    && match_segment(
+                        &p.chars().collect::<Vec<_>>(),
+                        &path[0].chars().collect::<Vec<_>>(),
+                    )
+                    && match_path(&pattern[1..], &path[1..])
+            }
+        }
+    }
+    match_path(&segments(pattern), &segments(path))
+}
+
+impl AutoStopTrigger {
+    fn accepts(&self, event: TriggerEvent) -> bool {
+        if self.events.is_empty() {
+            matches!(event, TriggerEvent::Create | TriggerEvent::Modify)
+        } else {
+            self.events.contains(&event)
+        }
+    }
+
+    fn matches_path(&self, relative: &str) -> bool {
+        match self.match_type {
+            TriggerMatch::File => {
+                let normalized = self.pattern.trim().replace('\\', "/");
+                normalized.trim_matches('/').eq_ignore_ascii_case(relative)
+            }
+            TriggerMatch::Glob => glob_match(self.pattern.trim(), relative),
+        }
+    }
+
+    /// Folder to watch (relative, '/'-separated) and whether it needs recursion:
+    /// the file's parent, or a glob's fixed leading segments
+    pub fn watch_target(&self) -> (String, bool) {
+        let normalized = self.pattern.trim().replace('\\', "/");
+        let segments: Vec<&str> = normalized.split('/').filter(|s| !s.is_empty()).collect();
+        let fixed = match self.match_type {
+            TriggerMatch::File => segments.len().saturating_sub(1),
+            TriggerMatch::Glob => segments
+                .iter()
+                .position(|s| s.contains(['*', '?']))
+                .unwrap_or(segments.len().saturating_sub(1)),
+        };
+        let recursive = segments.len() - fixed > 1;
+        (segments[..fixed].join("/"), recursive)
+    }
+}
+
+/// The first trigger a filesystem event satisfies, with the path that matched
+pub fn matching_trigger<'a>(
+    event: &notify::Event,
+    install_root: &Path,
+    triggers: &'a [AutoStopTrigger],
+) -> Option<(&'a AutoStopTrigger, PathBuf)> {
+    let kind = trigger_event(&event.kind)?;
+    event.paths.iter().find_map(|changed| {
+        let relative = relative_path(changed, install_root)?;
+        triggers
+            .iter()
+            .find(|t| t.accepts(kind) && t.matches_path(&relative))
+            .map(|t| (t, changed.clone()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, DataChange, MetadataKind, RenameMode};
+
+    fn event(kind: EventKind, path: PathBuf) -> notify::Event {
+        notify::Event::new(kind).add_path(path)
+    }
+
+    fn modified(path: PathBuf) -> notify::Event {
+        event(
+            EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+            path,
+        )
+    }
+
+    fn matched(event: &notify::Event, root: &Path, triggers: &[AutoStopTrigger]) -> Option<String> {
+        matching_trigger(event, root, triggers).map(|(t, _)| t.pattern.clone())
+    }
+
+    #[test]
+    fn default_triggers_only_react_to_the_ini_files_and_stop_request() {
+        let root = PathBuf::from("/servers/island");
+        let config = root.join("ShooterGame/Saved/Config/WindowsServer");
+        let defaults = default_triggers();
+
+        assert!(matched(
+            &modified(config.join("GameUserSettings.ini")),
+            &root,
+            &defaults
+        )
+        .is_some());
+        assert!(matched(&modified(config.join("game.ini")), &root, &defaults).is_some());
+        assert!(matched(&modified(config.join("Engine.ini")), &root, &defaults).is_none());
+        assert!(matched(
+            &modified(root.join("ShooterGame/Saved/SavedArks/TheIsland_WP.ark")),
+            &root,
+            &defaults
+        )
+        .is_none());
+        assert!(matched(&modified(root.join("Game.ini")), &root, &defaults).is_none());
+
+        let request = event(
+            EventKind::Create(CreateKind::File),
+            root.join(STOP_REQUEST_FILE),
+        );
+        let (trigger, path) = matching_trigger(&request, &root, &defaults).unwrap();
+        assert!(trigger.consume && !trigger.ignore_during_startup);
+        assert_eq!(path, root.join(STOP_REQUEST_FILE));
+    }
+
+    #[test]
+    fn reads_and_metadata_changes_never_trigger() {
+        let root = PathBuf::from("/servers/island");
+        let ini = root.join("ShooterGame/Saved/Config/WindowsServer/Game.ini");
+        let defaults = default_triggers();
+        for kind in [
+            EventKind::Access(AccessKind::Any),
+            EventKind::Modify(ModifyKind::Metadata(MetadataKind::WriteTime)),
+            EventKind::Remove(notify::event::RemoveKind::File),
+        ] {
+            assert!(matched(&event(kind, ini.clone()), &root, &defaults).is_none());
+        }
+    }
+
+    #[test]
+    fn glob_triggers_match_by_segment() {
+        assert!(glob_match(
+            "ShooterGame/Saved/SavedArks/*.ark",
+            "ShooterGame/Saved/SavedArks/TheIsland_WP.ark"
+        ));
+        assert!(!glob_match(
+            "ShooterGame/Saved/SavedArks/*.ark",
+            "ShooterGame/Saved/SavedArks/Backup/x.ark"
+        ));
+        assert!(glob_match(
+            "**/*.ark",
+            "ShooterGame/Saved/SavedArks/Backup/x.ark"
+        ));
+        assert!(glob_match("markers/stop-??.flag", "Markers/STOP-01.flag"));
+        assert!(!glob_match("markers/stop-??.flag", "markers/stop-1.flag"));
+    }
+
+    #[test]
+    fn save_completion_trigger_fires_on_rename_only() {
+        let root = PathBuf::from("/servers/island");
+        let save = AutoStopTrigger {
+            match_type: TriggerMatch::Glob,
+            pattern: "ShooterGame/Saved/SavedArks/*.ark".to_string(),
+            events: vec![TriggerEvent::Rename],
+            ignore_during_startup: false,
+            consume: false,
+        };
+        let path = root.join("ShooterGame/Saved/SavedArks/TheIsland_WP.ark");
+        let triggers = [save.clone()];
+
+        assert!(matched(&modified(path.clone()), &root, &triggers).is_none());
+        let renamed = event(EventKind::Modify(ModifyKind::Name(RenameMode::To)), path);
+        assert!(matched(&renamed, &root, &triggers).is_some());
+        assert_eq!(
+            save.watch_target(),
+            ("ShooterGame/Saved/SavedArks".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn watch_targets_and_validation() {
+        let glob = |pattern: &str| AutoStopTrigger {
+            match_type: TriggerMatch::Glob,
+            pattern: pattern.to_string(),
+            events: Vec::new(),
+            ignore_during_startup: false,
+            consume: false,
+        };
+        assert_eq!(glob("**/*.flag").watch_target(), (String::new(), true));
+        assert_eq!(
+            glob("a/b/*/c.ini").watch_target(),
+            ("a/b".to_string(), true)
+        );
+        assert_eq!(default_triggers()[2].watch_target(), (String::new(), false));
+
+        assert!(validate_trigger(&glob("markers/*.flag")).is_ok());
+        assert!(validate_trigger(&glob("../outside/*.flag")).is_err());
+        assert!(validate_trigger(&glob("/abs/*.flag")).is_err());
+        assert!(validate_trigger(&glob("  ")).is_err());
+        let mut file = glob("markers/*.flag");
+        file.match_type = TriggerMatch::File;
+        assert!(validate_trigger(&file).is_err());
     }
 }
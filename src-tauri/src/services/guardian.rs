@@ -61,9 +61,10 @@ impl GuardianService {
     pub async fn register_server(&self, server_id: i64, pid: u32) {
         let mut pids = self.server_pids.lock().await;
         pids.insert(server_id, pid);
-        println!(
+        log::info!(
             "🛡️ Guardian: Registered server {} with PID {}",
-            server_id, pid
+            server_id,
+            pid
         );
     }
 
@@ -72,16 +73,17 @@ impl GuardianService {
     pub async fn unregister_server(&self, server_id: i64) {
         let mut pids = self.server_pids.lock().await;
         pids.remove(&server_id);
-        println!("🛡️ Guardian: Unregistered server {}", server_id);
+        log::info!("🛡️ Guardian: Unregistered server {}", server_id);
     }
 
     /// Enable/disable auto-restart for a server
     pub async fn set_auto_restart(&self, server_id: i64, enabled: bool) {
         let mut settings = self.auto_restart_enabled.lock().await;
         settings.insert(server_id, enabled);
-        println!(
+        log::info!(
             "🛡️ Guardian: Auto-restart for server {} set to {}",
-            server_id, enabled
+            server_id,
+            enabled
         );
     }
 
@@ -134,7 +136,6 @@ impl GuardianService {
     }
 
     /// Log a crash event
-    #[allow(dead_code)]
     pub async fn log_crash(
         &self,
         server_id: i64,
@@ -159,9 +160,10 @@ impl GuardianService {
         let mut counts = self.crash_counts.lock().await;
         *counts.entry(server_id).or_insert(0) += 1;
 
-        println!(
+        log::warn!(
             "⚠️ Guardian: Crash detected for server {} - {}",
-            server_id, reason
+            server_id,
+            reason
         );
     }
 
@@ -1,141 +1,302 @@
 // INI Parser and Merger Utility
-// Handles parsing, merging, and serializing INI files while preserving unknown keys
+// Handles parsing, merging, and serializing INI files while preserving unknown keys.
+// Parsing is line based: comments, blank lines, key order and repeated keys
+// (ConfigOverrideItemMaxQuantity, OverrideNamedEngramEntries, ...) all survive a
+// round trip, and lines nobody changed are written back byte for byte.
 
-use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
 
-/// Represents a parsed INI file with sections and their key-value pairs
-pub struct IniParser;
-
-impl IniParser {
-    /// Parse INI content into a structured format
-    /// Returns (sections, section_order) where section_order preserves original ordering
-    pub fn parse(content: &str) -> (BTreeMap<String, BTreeMap<String, String>>, Vec<String>) {
-        let mut sections: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
-        let mut section_order: Vec<String> = Vec::new();
-        let mut current_section = String::from("__global__");
-
-        sections.insert(current_section.clone(), BTreeMap::new());
-        section_order.push(current_section.clone());
-
-        for line in content.lines() {
-            let line = line.trim();
-
-            // Skip empty lines and comments
-            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
-                continue;
-            }
+/// Section name for keys that appear before the first [Section] header
+pub const GLOBAL_SECTION: &str = "__global__";
 
-            // Section header
-            if line.starts_with('[') && line.ends_with(']') {
-                current_section = line[1..line.len() - 1].to_string();
-                if !sections.contains_key(&current_section) {
-                    sections.insert(current_section.clone(), BTreeMap::new());
-                    section_order.push(current_section.clone());
-                }
-                continue;
-            }
+#[derive(Debug, Clone, PartialEq)]
+enum LineKind {
+    Section(String),
+    Entry {
+        key: String,
+        value: String,
+    },
+    /// Blank lines, comments and anything else that isn't key=value
+    Other,
+}
 
-            // Key=Value pair
-            if let Some((key, value)) = line.split_once('=') {
-                let key = key.trim().to_string();
-                let value = value.trim().to_string();
+#[derive(Debug, Clone, PartialEq)]
+struct Line {
+    raw: String,
+    kind: LineKind,
+}
 
-                if let Some(section_map) = sections.get_mut(&current_section) {
-                    section_map.insert(key, value);
-                }
+impl Line {
+    fn parse(raw: &str) -> Self {
+        let line = raw.trim();
+        let kind = if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            LineKind::Other
+        } else if line.starts_with('[') && line.ends_with(']') {
+            LineKind::Section(line[1..line.len() - 1].to_string())
+        } else if let Some((key, value)) = line.split_once('=') {
+            LineKind::Entry {
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
             }
+        } else {
+            LineKind::Other
+        };
+        Self {
+            raw: raw.to_string(),
+            kind,
         }
+    }
 
-        (sections, section_order)
+    fn entry(key: &str, value: &str) -> Self {
+        Self::parse(&format!("{}={}", key, value))
     }
 
-    /// Merge two INI contents, updates take precedence over base
-    /// This preserves all keys from base that aren't in updates
-    pub fn merge(base: &str, updates: &str) -> String {
-        let (mut base_sections, mut section_order) = Self::parse(base);
-        let (update_sections, update_order) = Self::parse(updates);
+    fn is_blank(&self) -> bool {
+        self.raw.trim().is_empty()
+    }
+}
+
+/// A parsed INI file. Sections and keys are matched case-insensitively, like ARK does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IniDocument {
+    lines: Vec<Line>,
+    newline: &'static str,
+}
+
+impl IniDocument {
+    pub fn parse(content: &str) -> Self {
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let lines = content.lines().map(Line::parse).collect();
+        Self { lines, newline }
+    }
 
-        // Add any new sections from updates to the order
-        for section in &update_order {
-            if !section_order.contains(section) {
-                section_order.push(section.clone());
+    /// Section names in file order, including GLOBAL_SECTION when keys precede the first header
+    pub fn sections(&self) -> Vec<String> {
+        let mut sections: Vec<String> = Vec::new();
+        let mut current = GLOBAL_SECTION;
+        for line in &self.lines {
+            match &line.kind {
+                LineKind::Section(name) => current = name.as_str(),
+                LineKind::Entry { .. } => {}
+                LineKind::Other => continue,
+            }
+            if !sections.iter().any(|s| s.eq_ignore_ascii_case(current)) {
+                sections.push(current.to_string());
             }
         }
+        sections
+    }
 
-        // Merge update sections into base
-        for (section_name, section_values) in update_sections {
-            if let Some(base_section) = base_sections.get_mut(&section_name) {
-                // Merge values - updates win on conflicts
-                for (key, value) in section_values {
-                    base_section.insert(key, value);
+    /// Every (section, key, value) in file order, repeated keys included
+    pub fn entries(&self) -> Vec<(&str, &str, &str)> {
+        let mut section = GLOBAL_SECTION;
+        let mut entries = Vec::new();
+        for line in &self.lines {
+            match &line.kind {
+                LineKind::Section(name) => section = name.as_str(),
+                LineKind::Entry { key, value } => {
+                    entries.push((section, key.as_str(), value.as_str()))
                 }
-            } else {
-                // New section from updates
-                base_sections.insert(section_name, section_values);
+                LineKind::Other => {}
             }
         }
+        entries
+    }
 
-        Self::serialize(&base_sections, &section_order)
+    /// The last value of a key, which is the one ARK uses for single-valued settings
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.get_all(section, key).pop()
     }
 
-    /// Serialize sections back to INI format
-    pub fn serialize(
-        sections: &BTreeMap<String, BTreeMap<String, String>>,
-        section_order: &[String],
-    ) -> String {
-        let mut result = String::new();
+    /// All values of a key that may repeat
+    pub fn get_all(&self, section: &str, key: &str) -> Vec<&str> {
+        self.entries()
+            .into_iter()
+            .filter(|(s, k, _)| s.eq_ignore_ascii_case(section) && k.eq_ignore_ascii_case(key))
+            .map(|(_, _, value)| value)
+            .collect()
+    }
 
-        for section_name in section_order {
-            if let Some(section_values) = sections.get(section_name) {
-                if section_values.is_empty() {
-                    continue;
-                }
+    /// Set a single-valued key, replacing every existing line for it
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        self.set_all(section, key, &[value]);
+    }
 
-                // Skip global section header
-                if section_name != "__global__" {
-                    if !result.is_empty() {
-                        result.push_str("\r\n");
-                    }
-                    result.push_str(&format!("[{}]\r\n", section_name));
-                }
+    /// Replace every line of `key` with one line per value. The new lines take the place
+    /// of the first existing one; a key the section doesn't have yet goes at its end.
+    pub fn set_all(&mut self, section: &str, key: &str, values: &[&str]) {
+        let positions = self.positions(section, key);
+        let new_lines: Vec<Line> = values.iter().map(|value| Line::entry(key, value)).collect();
 
-                for (key, value) in section_values {
-                    result.push_str(&format!("{}={}\r\n", key, value));
-                }
+        let Some(&first) = positions.first() else {
+            if !new_lines.is_empty() {
+                let at = self.section_end(section);
+                self.lines.splice(at..at, new_lines);
             }
+            return;
+        };
+
+        for &index in positions.iter().rev() {
+            self.lines.remove(index);
         }
+        self.lines.splice(first..first, new_lines);
+    }
 
-        result
+    /// Remove every line of a key
+    pub fn remove(&mut self, section: &str, key: &str) {
+        self.set_all(section, key, &[]);
     }
 
-    /// Update a specific key in a section, preserving all other content
-    #[allow(dead_code)]
-    pub fn update_key(content: &str, section: &str, key: &str, value: &str) -> String {
-        let (mut sections, section_order) = Self::parse(content);
+    /// Keep only the entries for which `keep(section, key)` holds; comments are untouched
+    pub fn retain(&mut self, mut keep: impl FnMut(&str, &str) -> bool) {
+        let mut section = GLOBAL_SECTION.to_string();
+        self.lines.retain(|line| match &line.kind {
+            LineKind::Section(name) => {
+                section = name.clone();
+                true
+            }
+            LineKind::Entry { key, .. } => keep(&section, key),
+            LineKind::Other => true,
+        });
+    }
 
-        // Ensure section exists
-        if !sections.contains_key(section) {
-            sections.insert(section.to_string(), BTreeMap::new());
+    /// Apply `updates` on top of this file: each key the update sets replaces all of
+    /// this file's lines for that key (so a repeated key is replaced as a group),
+    /// everything else is kept as it is
+    pub fn merge(&mut self, updates: &IniDocument) {
+        if self.lines.is_empty() {
+            self.newline = updates.newline;
         }
-
-        if let Some(section_map) = sections.get_mut(section) {
-            section_map.insert(key.to_string(), value.to_string());
+        let mut groups: Vec<(&str, &str, Vec<&str>)> = Vec::new();
+        for (section, key, value) in updates.entries() {
+            match groups
+                .iter_mut()
+                .find(|(s, k, _)| s.eq_ignore_ascii_case(section) && k.eq_ignore_ascii_case(key))
+            {
+                Some((_, _, values)) => values.push(value),
+                None => groups.push((section, key, vec![value])),
+            }
         }
+        for (section, key, values) in groups {
+            self.set_all(section, key, &values);
+        }
+    }
 
-        // Rebuild section order if needed
-        let mut order = section_order;
-        if !order.contains(&section.to_string()) {
-            order.push(section.to_string());
+    /// Indices of the lines holding `key` in `section`
+    fn positions(&self, section: &str, key: &str) -> Vec<usize> {
+        let mut current = GLOBAL_SECTION;
+        let mut positions = Vec::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            match &line.kind {
+                LineKind::Section(name) => current = name.as_str(),
+                LineKind::Entry { key: k, .. }
+                    if current.eq_ignore_ascii_case(section) && k.eq_ignore_ascii_case(key) =>
+                {
+                    positions.push(index)
+                }
+                _ => {}
+            }
         }
+        positions
+    }
+
+    /// Where a new key for `section` goes: after its last non-blank line.
+    /// Creates the section at the end of the file if it doesn't exist.
+    fn section_end(&mut self, section: &str) -> usize {
+        let start = if section == GLOBAL_SECTION {
+            Some(0)
+        } else {
+            self.lines
+                .iter()
+                .position(|line| {
+                    matches!(&line.kind, LineKind::Section(name) if name.eq_ignore_ascii_case(section))
+                })
+                .map(|header| header + 1)
+        };
+
+        let Some(start) = start else {
+            if self.lines.last().is_some_and(|line| !line.is_blank()) {
+                self.lines.push(Line::parse(""));
+            }
+            self.lines.push(Line::parse(&format!("[{}]", section)));
+            return self.lines.len();
+        };
+
+        let end = self.lines[start..]
+            .iter()
+            .position(|line| matches!(line.kind, LineKind::Section(_)))
+            .map_or(self.lines.len(), |offset| start + offset);
+        (start..end)
+            .rev()
+            .find(|&index| !self.lines[index].is_blank())
+            .map_or(start, |index| index + 1)
+    }
+
+    /// Write the file back out with its original line endings
+    pub fn serialize(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| format!("{}{}", line.raw, self.newline))
+            .collect()
+    }
+}
+
+pub struct IniParser;
+
+impl IniParser {
+    /// Parse INI content, see IniDocument
+    pub fn parse(content: &str) -> IniDocument {
+        IniDocument::parse(content)
+    }
 
-        Self::serialize(&sections, &order)
+    /// Merge two INI contents, updates take precedence over base
+    /// This preserves all keys from base that aren't in updates
+    pub fn merge(base: &str, updates: &str) -> String {
+        let mut document = IniDocument::parse(base);
+        document.merge(&IniDocument::parse(updates));
+        document.serialize()
+    }
+
+    /// Update a specific key in a section, preserving all other content
+    #[allow(dead_code)]
+    pub fn update_key(content: &str, section: &str, key: &str, value: &str) -> String {
+        let mut document = IniDocument::parse(content);
+        document.set(section, key, value);
+        document.serialize()
     }
 
     /// Get a value from parsed INI content
     #[allow(dead_code)]
     pub fn get_value(content: &str, section: &str, key: &str) -> Option<String> {
-        let (sections, _) = Self::parse(content);
-        sections.get(section).and_then(|s| s.get(key)).cloned()
+        IniDocument::parse(content)
+            .get(section, key)
+            .map(str::to_string)
+    }
+
+    /// Write a config file without ever leaving it half-written.
+    /// The content goes to a temp file in the same directory, which is then
+    /// renamed over the target; if anything fails the original is untouched.
+    pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Path has no file name"))?;
+        let temp_path = path.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+
+        let written = fs::File::create(&temp_path).and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        });
+        if let Err(e) = written.and_then(|_| fs::rename(&temp_path, path)) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+        Ok(())
     }
 }
 
@@ -150,11 +311,82 @@ mod tests {
 MaxPlayers=70
 SessionName=Test Server
 "#;
-        let (sections, _) = IniParser::parse(content);
-        assert!(sections.contains_key("ServerSettings"));
+        let document = IniParser::parse(content);
+        assert_eq!(document.sections(), vec!["ServerSettings"]);
+        assert_eq!(document.get("ServerSettings", "MaxPlayers"), Some("70"));
+    }
+
+    const GAME_INI: &str = "; Item stack overrides\r
+[/Script/ShooterGame.ShooterGameMode]\r
+ConfigOverrideItemMaxQuantity=(ItemClassString=\"PrimalItemResource_Stone_C\",Quantity=(MaxItemQuantity=1000,bIgnoreMultiplier=true))\r
+bAllowSpeedLeveling=True\r
+ConfigOverrideItemMaxQuantity=(ItemClassString=\"PrimalItemResource_Wood_C\",Quantity=(MaxItemQuantity=1000,bIgnoreMultiplier=true))\r
+; keep metal last\r
+ConfigOverrideItemMaxQuantity=(ItemClassString=\"PrimalItemResource_Metal_C\",Quantity=(MaxItemQuantity=500,bIgnoreMultiplier=true))\r
+\r
+[ShooterGameMode_TEMPOverrides]\r
+bUseCorpseLocator=True\r
+";
+
+    #[test]
+    fn test_round_trip_keeps_repeated_keys_and_comments() {
+        let document = IniParser::parse(GAME_INI);
+        assert_eq!(document.serialize(), GAME_INI);
+        assert_eq!(
+            document
+                .get_all(
+                    "/Script/ShooterGame.ShooterGameMode",
+                    "ConfigOverrideItemMaxQuantity"
+                )
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_repeated_keys_it_does_not_set() {
+        let merged = IniParser::merge(
+            GAME_INI,
+            "[/Script/ShooterGame.ShooterGameMode]\nbAllowSpeedLeveling=False\nMatingIntervalMultiplier=0.5\n",
+        );
+        let expected = GAME_INI
+            .replace("bAllowSpeedLeveling=True", "bAllowSpeedLeveling=False")
+            .replace(
+                "MaxItemQuantity=500,bIgnoreMultiplier=true))\r\n",
+                "MaxItemQuantity=500,bIgnoreMultiplier=true))\r\nMatingIntervalMultiplier=0.5\r\n",
+            );
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn test_merge_replaces_a_repeated_key_as_a_group() {
+        let merged = IniParser::merge(
+            GAME_INI,
+            "[/Script/ShooterGame.ShooterGameMode]\nConfigOverrideItemMaxQuantity=A\nConfigOverrideItemMaxQuantity=B\n",
+        );
+        let document = IniParser::parse(&merged);
+        assert_eq!(
+            document.get_all(
+                "/Script/ShooterGame.ShooterGameMode",
+                "ConfigOverrideItemMaxQuantity"
+            ),
+            vec!["A", "B"]
+        );
+        // The group takes the place of the first old line
+        assert!(merged.starts_with(
+            "; Item stack overrides\r\n[/Script/ShooterGame.ShooterGameMode]\r\nConfigOverrideItemMaxQuantity=A\r\nConfigOverrideItemMaxQuantity=B\r\nbAllowSpeedLeveling=True\r\n; keep metal last\r\n"
+        ));
+    }
+
+    #[test]
+    fn test_merge_adds_new_sections() {
+        let merged = IniParser::merge(
+            "[ServerSettings]\nMaxPlayers=70\n",
+            "[MessageOfTheDay]\nMessage=Hi\n",
+        );
         assert_eq!(
-            sections.get("ServerSettings").unwrap().get("MaxPlayers"),
-            Some(&"70".to_string())
+            merged,
+            "[ServerSettings]\nMaxPlayers=70\n\n[MessageOfTheDay]\nMessage=Hi\n"
         );
     }
 
@@ -194,4 +426,28 @@ MaxPlayers=70
         let updated = IniParser::update_key(content, "ServerSettings", "MaxPlayers", "100");
         assert!(updated.contains("MaxPlayers=100"));
     }
+
+    #[test]
+    fn test_write_atomic_keeps_original_on_failure() {
+        let dir = std::env::temp_dir().join(format!("asa_ini_atomic_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("GameUserSettings.ini");
+
+        IniParser::write_atomic(&path, "[ServerSettings]\nMaxPlayers=70\n").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[ServerSettings]\nMaxPlayers=70\n"
+        );
+
+        // A directory squatting on the temp path makes the temp write fail
+        fs::create_dir_all(dir.join(".GameUserSettings.ini.tmp")).unwrap();
+        assert!(IniParser::write_atomic(&path, "[ServerSettings]\nMaxPlayers=").is_err());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[ServerSettings]\nMaxPlayers=70\n"
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
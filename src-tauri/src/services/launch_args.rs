@@ -0,0 +1,366 @@
+//! Server command line construction.
+//!
+//! Kept free of crate imports so the standalone asa-cli can include this file
+//! with `#[path]` and launch servers with exactly the same arguments.
+
+/// Everything that ends up on the ArkAscendedServer.exe command line
+pub struct LaunchOptions<'a> {
+    pub map_name: &'a str,
+    pub session_name: &'a str,
+    pub game_port: u16,
+    pub query_port: u16,
+    pub rcon_port: u16,
+    pub max_players: i32,
+    pub server_password: Option<&'a str>,
+    pub admin_password: &'a str,
+    pub ip_address: Option<&'a str>,
+    pub cluster_id: Option<&'a str>,
+    pub cluster_dir: Option<&'a str>,
+    pub mods: Option<&'a [String]>,
+    pub custom_args: Option<&'a str>,
+    /// -UseBattlEye when set, otherwise -NoBattlEye
+    pub battleye: bool,
+    /// Keys from LAUNCH_FLAGS; unknown keys are ignored
+    pub launch_flags: &'a [String],
+    /// Crossplay platforms, already checked by validate_platforms; empty leaves the game default
+    pub platforms: &'a [String],
+    /// Key from MOD_STRATEGIES; its flag is only added when mods are passed
+    pub mod_strategy: &'a str,
+}
+
+/// A launch option that can be switched on in server settings
+pub struct LaunchFlag {
+    pub key: &'static str,
+    pub flag: &'static str,
+    pub label: &'static str,
+    pub description: &'static str,
+}
+
+pub const LAUNCH_FLAGS: &[LaunchFlag] = &[
+    LaunchFlag {
+        key: "force_allow_cave_flyers",
+        flag: "-ForceAllowCaveFlyers",
+        label: "Allow cave flyers",
+        description: "Let flying creatures enter caves",
+    },
+    LaunchFlag {
+        key: "server_game_log",
+        flag: "-servergamelog",
+        label: "Server game log",
+        description: "Write admin commands and game events to the server log",
+    },
+    LaunchFlag {
+        key: "server_game_log_tribe_logs",
+        flag: "-servergamelogincludetribelogs",
+        label: "Tribe logs in game log",
+        description: "Include tribe logs in the server game log",
+    },
+    LaunchFlag {
+        key: "rcon_tribe_logs",
+        flag: "-ServerRCONOutputTribeLogs",
+        label: "Tribe logs over RCON",
+        description: "Send tribe log lines to RCON chat output",
+    },
+    LaunchFlag {
+        key: "no_crash_dialog",
+        flag: "-NoCrashDialog",
+        label: "No crash dialog",
+        description: "Exit on a crash instead of waiting on a dialog, so auto-restart can kick in",
+    },
+    LaunchFlag {
+        key: "no_hang_detection",
+        flag: "-NoHangDetection",
+        label: "No hang detection",
+        description: "Don't abort the server when a long save or load stalls the game thread",
+    },
+    LaunchFlag {
+        key: "use_structure_stasis_grid",
+        flag: "-UseStructureStasisGrid",
+        label: "Structure stasis grid",
+        description: "Cheaper structure stasis checks, helps servers with large bases",
+    },
+    LaunchFlag {
+        key: "disable_custom_cosmetics",
+        flag: "-DisableCustomCosmetics",
+        label: "Disable custom cosmetics",
+        description: "Block cosmetic mods players bring in",
+    },
+    LaunchFlag {
+        key: "no_transfer_from_filtering",
+        flag: "-NoTransferFromFiltering",
+        label: "No transfer filtering",
+        description: "Allow transfers from any server, not just this cluster",
+    },
+    LaunchFlag {
+        key: "force_respawn_dinos",
+        flag: "-ForceRespawnDinos",
+        label: "Respawn wild dinos on start",
+        description: "Wipe and respawn all wild creatures every time the server starts",
+    },
+    LaunchFlag {
+        key: "exclusive_join",
+        flag: "-exclusivejoin",
+        label: "Whitelist only",
+        description: "Only players on PlayerExclusiveJoinList.txt can join",
+    },
+];
+
+/// Named sets of LAUNCH_FLAGS keys that can be applied in one go
+pub const LAUNCH_PRESETS: &[(&str, &[&str])] = &[(
+    "performance",
+    &[
+        "use_structure_stasis_grid",
+        "no_hang_detection",
+        "no_crash_dialog",
+    ],
+)];
+
+pub fn launch_flag(key: &str) -> Option<&'static LaunchFlag> {
+    LAUNCH_FLAGS.iter().find(|f| f.key == key)
+}
+
+/// How a server gets hold of its mods
+pub struct ModStrategy {
+    pub key: &'static str,
+    pub flag: Option<&'static str>,
+    pub label: &'static str,
+    pub description: &'static str,
+}
+
+pub const DEFAULT_MOD_STRATEGY: &str = "runtime";
+
+/// ASA only fetches mods itself at startup: -automanagedmods is ASE-only and ASA
+/// mods aren't published on the Steam Workshop, so there is nothing to pre-download with.
+pub const MOD_STRATEGIES: &[ModStrategy] = &[ModStrategy {
+    key: "runtime",
+    flag: None,
+    label: "Download at startup",
+    description: "Pass -mods and let the server fetch anything missing while it boots. \
+            Nothing to manage, but the first start after adding or updating a mod can take \
+            several minutes, and it fails if CurseForge can't be reached.",
+}];
+
+pub fn mod_strategy(key: &str) -> Option<&'static ModStrategy> {
+    MOD_STRATEGIES.iter().find(|s| s.key == key)
+}
+
+/// Values accepted by -ServerPlatform. WINGDK is the Microsoft Store PC build,
+/// ALL lets every platform join.
+pub const SERVER_PLATFORMS: &[&str] = &["PC", "XSX", "PS5", "WINGDK", "ALL"];
+
+/// Normalise a crossplay selection to the upper-case names -ServerPlatform takes.
+/// Rejects names the server doesn't know and ALL mixed with specific platforms.
+pub fn validate_platforms(platforms: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for platform in platforms {
+        let name = platform.trim().to_uppercase();
+        if !SERVER_PLATFORMS.contains(&name.as_str()) {
+            return Err(format!(
+                "Unknown platform '{}', expected one of {}",
+                platform.trim(),
+                SERVER_PLATFORMS.join(", ")
+            ));
+        }
+        if !normalized.contains(&name) {
+            normalized.push(name);
+        }
+    }
+
+    if normalized.len() > 1 && normalized.iter().any(|p| p == "ALL") {
+        return Err("ALL already includes every platform and can't be combined".to_string());
+    }
+    Ok(normalized)
+}
+
+/// Build launch arguments: the connection URL followed by the dash options
+pub fn build_launch_args(options: &LaunchOptions) -> Vec<String> {
+    let mut connection_url = format!("{}?listen", options.map_name);
+    connection_url.push_str(&format!("?SessionName={}", options.session_name));
+    connection_url.push_str(&format!("?Port={}", options.game_port));
+    connection_url.push_str(&format!("?QueryPort={}", options.query_port));
+    connection_url.push_str(&format!("?RCONPort={}", options.rcon_port));
+    connection_url.push_str(&format!("?MaxPlayers={}", options.max_players));
+    connection_url.push_str(&format!("?ServerAdminPassword={}", options.admin_password));
+
+    if let Some(password) = options.server_password {
+        connection_url.push_str(&format!("?ServerPassword={}", password));
+    }
+
+    let mut args = vec![connection_url];
+
+    args.push("-log".to_string());
+    args.push(if options.battleye {
+        "-UseBattlEye".to_string()
+    } else {
+        "-NoBattlEye".to_string()
+    });
+    args.extend(
+        options
+            .launch_flags
+            .iter()
+            .filter_map(|key| launch_flag(key))
+            .map(|f| f.flag.to_string()),
+    );
+
+    if !options.platforms.is_empty() {
+        args.push(format!("-ServerPlatform={}", options.platforms.join("+")));
+    }
+
+    // Add MultiHome for IP binding
+    if let Some(ip) = options.ip_address {
+        if !ip.is_empty() {
+            args.push(format!("-MultiHome={}", ip));
+        }
+    }
+
+    // Add cluster configuration for cross-ARK travel
+    if let (Some(cid), Some(cdir)) = (options.cluster_id, options.cluster_dir) {
+        if !cid.is_empty() && !cdir.is_empty() {
+            args.push(format!("-clusterid={}", cid));
+            args.push(format!("-ClusterDirOverride=\"{}\"", cdir));
+        }
+    }
+
+    // Add mods if any are enabled
+    if let Some(mod_list) = options.mods {
+        if !mod_list.is_empty() {
+            args.push(format!("-mods={}", mod_list.join(",")));
+            if let Some(flag) = mod_strategy(options.mod_strategy).and_then(|s| s.flag) {
+                args.push(flag.to_string());
+            }
+        }
+    }
+
+    // Add custom launch arguments
+    if let Some(custom) = options.custom_args {
+        if !custom.is_empty() {
+            // Split by whitespace but respect basic quoting if possible,
+            // for now simple split is safer than nothing.
+            args.extend(custom.split_whitespace().map(|s| s.to_string()));
+        }
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn options<'a>(mods: Option<&'a [String]>) -> LaunchOptions<'a> {
+        LaunchOptions {
+            map_name: "TheIsland_WP",
+            session_name: "Test",
+            game_port: 7777,
+            query_port: 27015,
+            rcon_port: 27020,
+            max_players: 70,
+            server_password: None,
+            admin_password: "admin",
+            ip_address: None,
+            cluster_id: None,
+            cluster_dir: None,
+            mods,
+            custom_args: None,
+            battleye: false,
+            launch_flags: &[],
+            platforms: &[],
+            mod_strategy: DEFAULT_MOD_STRATEGY,
+        }
+    }
+
+    #[test]
+    fn battleye_is_off_unless_enabled() {
+        let args = build_launch_args(&options(None));
+        assert!(args.contains(&"-NoBattlEye".to_string()));
+        assert!(!args.contains(&"-UseBattlEye".to_string()));
+
+        let args = build_launch_args(&LaunchOptions {
+            battleye: true,
+            ..options(None)
+        });
+        assert!(args.contains(&"-UseBattlEye".to_string()));
+        assert!(!args.contains(&"-NoBattlEye".to_string()));
+    }
+
+    #[test]
+    fn crossplay_platforms_become_one_server_platform_arg() {
+        let args = build_launch_args(&options(None));
+        assert!(!args.iter().any(|a| a.starts_with("-ServerPlatform")));
+
+        let platforms = strings(&["PC", "XSX", "PS5"]);
+        let args = build_launch_args(&LaunchOptions {
+            platforms: &platforms,
+            ..options(None)
+        });
+        assert!(args.contains(&"-ServerPlatform=PC+XSX+PS5".to_string()));
+    }
+
+    #[test]
+    fn launch_flags_map_to_their_switches_and_skip_unknown_keys() {
+        let flags = strings(&["no_crash_dialog", "not_a_flag", "exclusive_join"]);
+        let args = build_launch_args(&LaunchOptions {
+            launch_flags: &flags,
+            ..options(None)
+        });
+        let switches: Vec<&str> = args[1..]
+            .iter()
+            .map(String::as_str)
+            .filter(|a| LAUNCH_FLAGS.iter().any(|f| f.flag == *a))
+            .collect();
+        assert_eq!(switches, ["-NoCrashDialog", "-exclusivejoin"]);
+        assert!(!args.iter().any(|a| a.contains("not_a_flag")));
+    }
+
+    #[test]
+    fn every_mod_strategy_passes_mods_and_its_own_flag() {
+        let mods = strings(&["928793", "929420"]);
+        for strategy in MOD_STRATEGIES {
+            let args = build_launch_args(&LaunchOptions {
+                mod_strategy: strategy.key,
+                ..options(Some(&mods))
+            });
+            let mods_at = args.iter().position(|a| a == "-mods=928793,929420");
+            assert!(mods_at.is_some(), "{}", strategy.key);
+            assert_eq!(
+                args.get(mods_at.unwrap() + 1).map(String::as_str),
+                strategy.flag,
+                "{}",
+                strategy.key
+            );
+
+            // No mods, no strategy flag
+            let args = build_launch_args(&LaunchOptions {
+                mod_strategy: strategy.key,
+                ..options(None)
+            });
+            assert!(!args.iter().any(|a| a.starts_with("-mods")));
+            if let Some(flag) = strategy.flag {
+                assert!(!args.contains(&flag.to_string()));
+            }
+        }
+        assert!(mod_strategy(DEFAULT_MOD_STRATEGY).is_some());
+        assert!(mod_strategy("automanaged").is_none());
+    }
+
+    #[test]
+    fn validate_platforms_normalises_and_rejects_bad_combinations() {
+        assert_eq!(
+            validate_platforms(&strings(&[" pc", "xsx", "PC"])),
+            Ok(strings(&["PC", "XSX"]))
+        );
+        assert_eq!(validate_platforms(&[]), Ok(Vec::new()));
+        assert!(validate_platforms(&strings(&["Switch"]))
+            .unwrap_err()
+            .contains("Unknown platform 'Switch'"));
+        assert!(validate_platforms(&strings(&["ALL", "PC"])).is_err());
+        assert_eq!(
+            validate_platforms(&strings(&["all"])),
+            Ok(strings(&["ALL"]))
+        );
+    }
+}
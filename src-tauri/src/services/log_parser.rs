@@ -0,0 +1,418 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// A structured event recognized in a ShooterGame.log line
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LogEvent {
+    #[serde(rename_all = "camelCase")]
+    PlayerJoined {
+        player_name: String,
+        unique_id: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    PlayerLeft {
+        player_name: String,
+        unique_id: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    TribeLog {
+        tribe_name: String,
+        tribe_id: Option<String>,
+        message: String,
+    },
+    Error {
+        message: String,
+    },
+    Warning {
+        message: String,
+    },
+    ServerStarted,
+    #[serde(rename_all = "camelCase")]
+    ModDownload {
+        mod_id: Option<String>,
+        state: ModDownloadState,
+        progress: Option<f32>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ModDownloadStalled {
+        mod_id: Option<String>,
+        idle_secs: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModDownloadState {
+    Downloading,
+    Installed,
+    Failed,
+}
+
+/// Payload for the "server_event" channel, emitted alongside the raw "server_log" lines
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEvent {
+    pub server_id: i64,
+    pub timestamp: Option<String>,
+    pub event: LogEvent,
+}
+
+/// Parse a single log line into a structured event, if it matches a known pattern
+pub fn parse_line(line: &str) -> Option<LogEvent> {
+    let (_, body) = split_prefix(line);
+    let body = body.trim();
+
+    if body.contains("has successfully started") || body.starts_with("Full Startup:") {
+        return Some(LogEvent::ServerStarted);
+    }
+
+    if let Some(rest) = body.strip_suffix(" joined this ARK!") {
+        let (player_name, unique_id) = split_player(rest);
+        return Some(LogEvent::PlayerJoined {
+            player_name,
+            unique_id,
+        });
+    }
+
+    if let Some(rest) = body.strip_suffix(" left this ARK!") {
+        let (player_name, unique_id) = split_player(rest);
+        return Some(LogEvent::PlayerLeft {
+            player_name,
+            unique_id,
+        });
+    }
+
+    // Tribe TribeName, ID 123456789: Day 12, 10:00:00: <RichColor ...>Message</>)
+    if let Some(rest) = body.strip_prefix("Tribe ") {
+        if let Some((header, message)) = rest.split_once(": ") {
+            let (tribe_name, tribe_id) = match header.rsplit_once(", ID ") {
+                Some((name, id)) => (name.to_string(), Some(id.trim().to_string())),
+                None => (header.to_string(), None),
+            };
+            return Some(LogEvent::TribeLog {
+                tribe_name,
+                tribe_id,
+                message: strip_rich_text(message),
+            });
+        }
+    }
+
+    // Unreal categories: "LogFoo: Error: ..." / "LogFoo: Warning: ...". These are
+    // classified before the mod download lines so an engine error that merely
+    // mentions a mod path isn't reported as download progress.
+    if let Some((category, message)) = body.split_once(": ") {
+        if !category.contains(' ') {
+            if let Some(msg) = message.strip_prefix("Error: ") {
+                // A failed download is still reported as a mod event so the
+                // monitor can react to it
+                if let Some(mod_id) = mod_id_in(msg) {
+                    if is_download_line(&msg.to_lowercase()) {
+                        return Some(LogEvent::ModDownload {
+                            mod_id: Some(mod_id),
+                            state: ModDownloadState::Failed,
+                            progress: None,
+                        });
+                    }
+                }
+                return Some(LogEvent::Error {
+                    message: format!("{}: {}", category, msg),
+                });
+            }
+            if let Some(msg) = message.strip_prefix("Warning: ") {
+                return Some(LogEvent::Warning {
+                    message: format!("{}: {}", category, msg),
+                });
+            }
+        }
+    }
+
+    parse_mod_download(body)
+}
+
+/// Recognize the mod download/install lines ASA writes while it fetches mods on launch:
+///
+/// ```text
+/// Mod 928793 is not installed. Downloading...
+/// Downloading mod 928793 (45%)
+/// Mod 928793 installed successfully
+/// Failed to download mod 928793
+/// ```
+///
+/// A line only counts when "mod" is followed by the numeric mod id, so paths
+/// like "/Game/Mods/..." or words like "model" don't match.
+fn parse_mod_download(body: &str) -> Option<LogEvent> {
+    let mod_id = mod_id_in(body)?;
+    let lower = body.to_lowercase();
+    if !is_download_line(&lower) {
+        return None;
+    }
+
+    let state = if lower.contains("fail") || lower.contains("error") {
+        ModDownloadState::Failed
+    } else if (lower.contains("installed") && !lower.contains("not installed"))
+        || lower.contains("downloaded")
+        || lower.contains("complete")
+        || lower.contains("finished")
+    {
+        ModDownloadState::Installed
+    } else {
+        ModDownloadState::Downloading
+    };
+
+    let progress = body
+        .split_whitespace()
+        .filter_map(|token| token.trim_matches(['(', ')', ',']).strip_suffix('%'))
+        .find_map(|pct| pct.parse::<f32>().ok());
+
+    Some(LogEvent::ModDownload {
+        mod_id: Some(mod_id),
+        state,
+        progress,
+    })
+}
+
+fn is_download_line(lower: &str) -> bool {
+    lower.contains("download") || lower.contains("install")
+}
+
+/// The id following the word "mod" ("Mod 928793", "mod [928793]", "Mod: 928793"),
+/// if there is one. CurseForge ids are 5+ digits.
+fn mod_id_in(body: &str) -> Option<String> {
+    let mut words = body.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        let word = word.trim_end_matches(':');
+        if !word.eq_ignore_ascii_case("mod") {
+            continue;
+        }
+        let id = words.peek()?.trim_matches(|c: char| !c.is_ascii_digit());
+        if id.len() >= 5 && id.chars().all(|c| c.is_ascii_digit()) {
+            return Some(id.to_string());
+        }
+    }
+    None
+}
+
+/// Emit an event on the "server_event" channel
+pub fn emit_event(
+    app_handle: &AppHandle,
+    server_id: i64,
+    timestamp: Option<String>,
+    event: LogEvent,
+) {
+    let _ = app_handle.emit(
+        "server_event",
+        ServerEvent {
+            server_id,
+            timestamp,
+            event,
+        },
+    );
+}
+
+/// Parse a line and emit it on the "server_event" channel if it's a known event
+pub fn emit_parsed(app_handle: &AppHandle, server_id: i64, line: &str) -> Option<LogEvent> {
+    let event = parse_line(line)?;
+    let (timestamp, _) = split_prefix(line);
+
+    emit_event(app_handle, server_id, timestamp, event.clone());
+
+    Some(event)
+}
+
+/// Strip the "[2024.01.01-12.00.00:000][ 12]" engine prefix and the
+/// "2024.01.01_12.00.00: " game timestamp, returning the first timestamp found
+fn split_prefix(line: &str) -> (Option<String>, &str) {
+    let mut timestamp = None;
+    let mut rest = line;
+
+    // Engine prefix: up to two bracketed groups
+    for _ in 0..2 {
+        if rest.starts_with('[') {
+            if let Some(end) = rest.find(']') {
+                let inner = &rest[1..end];
+                if timestamp.is_none() && inner.contains('.') {
+                    timestamp = Some(inner.to_string());
+                }
+                rest = &rest[end + 1..];
+            }
+        }
+    }
+
+    // Game timestamp: "YYYY.MM.DD_HH.MM.SS: "
+    if let Some((head, tail)) = rest.split_once(": ") {
+        let is_timestamp = head.len() == 19
+            && head.as_bytes()[10] == b'_'
+            && head
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '.' || c == '_');
+        if is_timestamp {
+            if timestamp.is_none() {
+                timestamp = Some(head.to_string());
+            }
+            rest = tail;
+        }
+    }
+
+    (timestamp, rest)
+}
+
+/// Split "Name [UniqueNetId:0002abc Platform:None]" into name and unique id
+fn split_player(raw: &str) -> (String, Option<String>) {
+    let raw = raw.trim();
+    match raw.find(" [UniqueNetId:") {
+        Some(idx) => {
+            let name = raw[..idx].trim().to_string();
+            let id = raw[idx + " [UniqueNetId:".len()..]
+                .split(|c: char| c.is_whitespace() || c == ']')
+                .next()
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string());
+            (name, id)
+        }
+        None => (raw.to_string(), None),
+    }
+}
+
+/// Remove <RichColor ...> tags and the trailing "</>)" ARK adds to tribe logs
+fn strip_rich_text(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut in_tag = false;
+    for c in message.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.trim().trim_end_matches(')').trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_join_and_leave() {
+        let line = "[2024.01.05-12.34.56:789][ 42]2024.01.05_12.34.56: Survivor [UniqueNetId:0002abcdef Platform:None] joined this ARK!";
+        assert_eq!(
+            parse_line(line),
+            Some(LogEvent::PlayerJoined {
+                player_name: "Survivor".to_string(),
+                unique_id: Some("0002abcdef".to_string()),
+            })
+        );
+
+        let line = "2024.01.05_12.40.00: Survivor left this ARK!";
+        assert_eq!(
+            parse_line(line),
+            Some(LogEvent::PlayerLeft {
+                player_name: "Survivor".to_string(),
+                unique_id: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_tribe_log() {
+        let line = "2024.01.05_12.34.56: Tribe Raptors, ID 1234567: Day 12, 10:00:00: <RichColor Color=\"1, 0, 0, 1\">Bob was killed!</>)";
+        assert_eq!(
+            parse_line(line),
+            Some(LogEvent::TribeLog {
+                tribe_name: "Raptors".to_string(),
+                tribe_id: Some("1234567".to_string()),
+                message: "Day 12, 10:00:00: Bob was killed!".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_markers() {
+        assert_eq!(
+            parse_line("[2024.01.05-12.34.56:789][  0]LogStreaming: Warning: Failed to read file"),
+            Some(LogEvent::Warning {
+                message: "LogStreaming: Failed to read file".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_line("LogNet: Error: Connection lost"),
+            Some(LogEvent::Error {
+                message: "LogNet: Connection lost".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_line("2024.01.05_12.34.56: Server: \"My Server\" has successfully started!"),
+            Some(LogEvent::ServerStarted)
+        );
+        assert_eq!(parse_line("LogTemp: Loading map"), None);
+    }
+
+    fn mod_event(mod_id: &str, state: ModDownloadState, progress: Option<f32>) -> LogEvent {
+        LogEvent::ModDownload {
+            mod_id: Some(mod_id.to_string()),
+            state,
+            progress,
+        }
+    }
+
+    #[test]
+    fn test_parse_mod_download() {
+        // Excerpt from a ShooterGame.log of a server fetching a mod on first launch
+        assert_eq!(
+            parse_line("[2024.03.02-18.04.11:532][  0]Mod 928793 is not installed. Downloading..."),
+            Some(mod_event("928793", ModDownloadState::Downloading, None))
+        );
+        assert_eq!(
+            parse_line("[2024.03.02-18.04.15:104][  0]Downloading mod 928793 (45%)"),
+            Some(mod_event(
+                "928793",
+                ModDownloadState::Downloading,
+                Some(45.0)
+            ))
+        );
+        assert_eq!(
+            parse_line("[2024.03.02-18.04.31:870][  0]Mod 928793 installed successfully"),
+            Some(mod_event("928793", ModDownloadState::Installed, None))
+        );
+        assert_eq!(
+            parse_line("[2024.03.02-18.04.32:011][  0]Failed to download mod 931047"),
+            Some(mod_event("931047", ModDownloadState::Failed, None))
+        );
+        assert_eq!(
+            parse_line("LogModManager: Error: Failed to download mod 931047"),
+            Some(mod_event("931047", ModDownloadState::Failed, None))
+        );
+    }
+
+    #[test]
+    fn test_engine_errors_mentioning_mods_stay_errors() {
+        assert_eq!(
+            parse_line("[2024.03.02-18.05.01:220][  3]LogPakFile: Error: Failed to install pak /Game/Mods/928793/Content.pak"),
+            Some(LogEvent::Error {
+                message: "LogPakFile: Failed to install pak /Game/Mods/928793/Content.pak".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_line(
+                "[2024.03.02-18.05.02:001][  3]LogStreaming: Warning: Mod 928793 download is slow"
+            ),
+            Some(LogEvent::Warning {
+                message: "LogStreaming: Mod 928793 download is slow".to_string(),
+            })
+        );
+        // "model"/"modified" and mod paths without an id aren't download lines
+        assert_eq!(
+            parse_line("LogTemp: Installing model cache, download size 12000"),
+            None
+        );
+        assert_eq!(parse_line("LogTemp: Mod list downloaded"), None);
+    }
+
+    #[test]
+    fn test_timestamp_extraction() {
+        let (ts, rest) = split_prefix("[2024.01.05-12.34.56:789][ 42]LogNet: hello");
+        assert_eq!(ts.as_deref(), Some("2024.01.05-12.34.56:789"));
+        assert_eq!(rest, "LogNet: hello");
+    }
+}
@@ -1,16 +1,33 @@
 pub mod api_key_manager;
+pub mod app_log;
 pub mod backup_service;
+pub mod chat_bridge;
+pub mod chat_relay;
+pub mod cloud_backup;
 pub mod config_generator;
+pub mod diagnostics;
 pub mod discord;
 pub mod file_watcher;
 pub mod guardian;
 pub mod health_checker;
 pub mod ini_parser;
+pub mod launch_args;
+pub mod log_parser;
+pub mod mod_conflicts;
 pub mod mod_scraper;
+pub mod mod_sync;
 pub mod network;
+pub mod notifications;
 pub mod performance_tracker;
 pub mod player_intelligence;
 pub mod process_manager;
 pub mod rcon;
+pub mod rcon_catalog;
+pub mod rcon_protocol;
+pub mod remote_api;
+pub mod scheduler;
 pub mod server_installer;
+pub mod server_state;
+pub mod settings;
 pub mod steamcmd;
+pub mod uptime;
@@ -0,0 +1,291 @@
+// Mod load-order and compatibility checks
+// ASA mod metadata is thin, so the checks combine what CurseForge reports
+// (required / incompatible relations) with rules the admin configures
+// (mods that must load first, pairs known to clash).
+
+use crate::models::ModInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Settings key holding the JSON-encoded ModLoadRules
+pub const MOD_RULES_SETTING: &str = "mod_load_rules";
+
+/// Admin-configured rules, shared by every server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModLoadRules {
+    /// Core/framework mods that must be at the top of the list, in this order
+    pub load_first: Vec<String>,
+    /// Pairs that must not be enabled together
+    pub incompatible: Vec<(String, String)>,
+}
+
+/// CurseForge relation types we act on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Required,
+    Incompatible,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictKind {
+    DuplicateId,
+    MissingDependency,
+    DisabledDependency,
+    Incompatible,
+    LoadOrder,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModConflict {
+    pub kind: ConflictKind,
+    pub mod_ids: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModConflictReport {
+    pub conflicts: Vec<ModConflict>,
+    /// Full mod list in a better order, when the current one breaks a load-first rule
+    pub suggested_order: Option<Vec<String>>,
+    /// False when CurseForge couldn't be asked, so dependency checks were skipped
+    pub metadata_checked: bool,
+}
+
+/// Everything the checks look at for one server
+pub struct ModConflictInput<'a> {
+    /// Installed mods, in load order
+    pub mods: &'a [ModInfo],
+    /// ActiveMods as currently written in GameUserSettings.ini
+    pub ini_active_mods: &'a [String],
+    /// Mod ids passed by hand through custom launch args (-mods=...)
+    pub custom_arg_mods: &'a [String],
+    /// CurseForge relations per mod id, if fetched
+    pub relations: Option<&'a HashMap<String, Vec<(String, Relation)>>>,
+    pub rules: &'a ModLoadRules,
+}
+
+/// Ids from a "-mods=1,2,3" launch argument
+pub fn mods_from_args(args: &str) -> Vec<String> {
+    args.split_whitespace()
+        .filter_map(|arg| arg.trim_start_matches('?').strip_prefix("-mods="))
+        .flat_map(|list| list.split(','))
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+pub fn detect_conflicts(input: &ModConflictInput) -> ModConflictReport {
+    let mut conflicts = Vec::new();
+    let name_of = |id: &str| {
+        input
+            .mods
+            .iter()
+            .find(|m| m.id == id)
+            .map(|m| m.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let enabled: HashSet<&str> = input
+        .mods
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.id.as_str())
+        .collect();
+    let installed: HashSet<&str> = input.mods.iter().map(|m| m.id.as_str()).collect();
+
+    // Duplicates: the same id twice in the INI, or passed again through custom args
+    let mut seen = HashSet::new();
+    for id in input.ini_active_mods {
+        if !seen.insert(id.as_str()) {
+            conflicts.push(ModConflict {
+                kind: ConflictKind::DuplicateId,
+                mod_ids: vec![id.clone()],
+                message: format!("{} is listed more than once in ActiveMods", name_of(id)),
+            });
+        }
+    }
+    for id in input.custom_arg_mods {
+        if enabled.contains(id.as_str()) {
+            conflicts.push(ModConflict {
+                kind: ConflictKind::DuplicateId,
+                mod_ids: vec![id.clone()],
+                message: format!(
+                    "{} is in the mod list and also in the custom -mods= argument",
+                    name_of(id)
+                ),
+            });
+        }
+    }
+
+    // CurseForge relations: required dependencies of enabled mods, and
+    // incompatible pairs that are checked together with the admin's own
+    let mut pairs: Vec<(String, String)> = input.rules.incompatible.clone();
+    if let Some(relations) = input.relations {
+        for m in input.mods.iter().filter(|m| m.enabled) {
+            for (other, relation) in relations.get(&m.id).into_iter().flatten() {
+                match relation {
+                    Relation::Required if !installed.contains(other.as_str()) => {
+                        conflicts.push(ModConflict {
+                            kind: ConflictKind::MissingDependency,
+                            mod_ids: vec![m.id.clone(), other.clone()],
+                            message: format!(
+                                "{} requires mod {} which is not installed",
+                                m.name, other
+                            ),
+                        })
+                    }
+                    Relation::Required if !enabled.contains(other.as_str()) => {
+                        conflicts.push(ModConflict {
+                            kind: ConflictKind::DisabledDependency,
+                            mod_ids: vec![m.id.clone(), other.clone()],
+                            message: format!(
+                                "{} requires {}, which is disabled",
+                                m.name,
+                                name_of(other)
+                            ),
+                        })
+                    }
+                    Relation::Incompatible => pairs.push((m.id.clone(), other.clone())),
+                    Relation::Required => {}
+                }
+            }
+        }
+    }
+    let mut reported = HashSet::new();
+    for (a, b) in &pairs {
+        let key = if a < b { (a, b) } else { (b, a) };
+        if enabled.contains(a.as_str()) && enabled.contains(b.as_str()) && reported.insert(key) {
+            conflicts.push(ModConflict {
+                kind: ConflictKind::Incompatible,
+                mod_ids: vec![a.clone(), b.clone()],
+                message: format!("{} and {} are incompatible", name_of(a), name_of(b)),
+            });
+        }
+    }
+
+    // Load-first mods must come before every other enabled mod
+    let enabled_order: Vec<&str> = input
+        .mods
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.id.as_str())
+        .collect();
+    let first: Vec<&str> = input
+        .rules
+        .load_first
+        .iter()
+        .map(|id| id.as_str())
+        .filter(|id| enabled.contains(id))
+        .collect();
+    let misplaced: Vec<&str> = enabled_order
+        .iter()
+        .enumerate()
+        .filter(|&(index, &id)| first.contains(&id) && first.get(index) != Some(&id))
+        .map(|(_, &id)| id)
+        .collect();
+    for id in &misplaced {
+        conflicts.push(ModConflict {
+            kind: ConflictKind::LoadOrder,
+            mod_ids: vec![id.to_string()],
+            message: format!("{} should load before other mods", name_of(id)),
+        });
+    }
+
+    let suggested_order = (!misplaced.is_empty()).then(|| {
+        let mut order: Vec<String> = first.iter().map(|id| id.to_string()).collect();
+        order.extend(
+            input
+                .mods
+                .iter()
+                .filter(|m| !first.contains(&m.id.as_str()))
+                .map(|m| m.id.clone()),
+        );
+        order
+    });
+
+    ModConflictReport {
+        conflicts,
+        suggested_order,
+        metadata_checked: input.relations.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(id: &str, enabled: bool, load_order: i32) -> ModInfo {
+        ModInfo {
+            id: id.to_string(),
+            curseforge_id: None,
+            name: format!("Mod {}", id),
+            version: None,
+            author: None,
+            description: None,
+            thumbnail_url: None,
+            downloads: None,
+            curseforge_url: None,
+            enabled,
+            load_order,
+            last_updated: None,
+            game_version: None,
+            latest_version: None,
+        }
+    }
+
+    #[test]
+    fn test_detects_duplicates_dependencies_and_order() {
+        let mods = vec![
+            installed("100", true, 0),
+            installed("200", true, 1),
+            installed("300", false, 2),
+        ];
+        let ini = vec!["100".to_string(), "200".to_string(), "100".to_string()];
+        let relations = HashMap::from([(
+            "100".to_string(),
+            vec![
+                ("300".to_string(), Relation::Required),
+                ("999".to_string(), Relation::Required),
+            ],
+        )]);
+        let rules = ModLoadRules {
+            load_first: vec!["200".to_string()],
+            incompatible: vec![("100".to_string(), "200".to_string())],
+        };
+
+        let report = detect_conflicts(&ModConflictInput {
+            mods: &mods,
+            ini_active_mods: &ini,
+            custom_arg_mods: &[],
+            relations: Some(&relations),
+            rules: &rules,
+        });
+
+        let kinds: Vec<&ConflictKind> = report.conflicts.iter().map(|c| &c.kind).collect();
+        assert!(kinds.contains(&&ConflictKind::DuplicateId));
+        assert!(kinds.contains(&&ConflictKind::DisabledDependency));
+        assert!(kinds.contains(&&ConflictKind::MissingDependency));
+        assert!(kinds.contains(&&ConflictKind::Incompatible));
+        assert!(kinds.contains(&&ConflictKind::LoadOrder));
+        assert_eq!(
+            report.suggested_order,
+            Some(vec![
+                "200".to_string(),
+                "100".to_string(),
+                "300".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_mods_from_args() {
+        assert_eq!(
+            mods_from_args("-NoBattlEye -mods=1, 2 -log"),
+            vec!["1".to_string()]
+        );
+        assert_eq!(mods_from_args("-mods=10,20"), vec!["10", "20"]);
+    }
+}
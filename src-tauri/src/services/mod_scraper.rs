@@ -1,10 +1,67 @@
 use crate::models::ModInfo;
+use crate::services::mod_conflicts::Relation;
 use reqwest::Client;
 use scraper::{Html, Selector};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::error::Error;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const CURSEFORGE_API_URL: &str = "https://api.curseforge.com/v1";
+/// ARK: Survival Ascended on CurseForge
+pub const ASA_GAME_ID: i64 = 83374;
+
+/// How long a search result is served from memory before CurseForge is asked again
+const SEARCH_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+/// Oldest entries are dropped past this many distinct queries
+const SEARCH_CACHE_MAX_ENTRIES: usize = 50;
+
+struct CachedSearch {
+    query: String,
+    fetched_at: Instant,
+    mods: Vec<ModInfo>,
+}
+
+static SEARCH_CACHE: Mutex<Vec<CachedSearch>> = Mutex::new(Vec::new());
+
+fn cache_key(query: &str) -> String {
+    query.trim().to_lowercase()
+}
+
+/// Results of an earlier search for the same query, if still within the TTL
+pub fn cached_search(query: &str) -> Option<Vec<ModInfo>> {
+    let key = cache_key(query);
+    let mut cache = SEARCH_CACHE.lock().ok()?;
+    cache.retain(|entry| entry.fetched_at.elapsed() < SEARCH_CACHE_TTL);
+    cache
+        .iter()
+        .find(|entry| entry.query == key)
+        .map(|entry| entry.mods.clone())
+}
 
-const CURSEFORGE_API_URL: &str = "https://api.curseforge.com/v1";
+pub fn cache_search(query: &str, mods: &[ModInfo]) {
+    let key = cache_key(query);
+    let Ok(mut cache) = SEARCH_CACHE.lock() else {
+        return;
+    };
+    cache.retain(|entry| entry.query != key);
+    if cache.len() >= SEARCH_CACHE_MAX_ENTRIES {
+        cache.remove(0);
+    }
+    cache.push(CachedSearch {
+        query: key,
+        fetched_at: Instant::now(),
+        mods: mods.to_vec(),
+    });
+}
+
+/// Forget every cached search, e.g. after the API key changed
+pub fn clear_search_cache() {
+    if let Ok(mut cache) = SEARCH_CACHE.lock() {
+        cache.clear();
+    }
+}
 
 #[derive(Debug, Deserialize)]
 struct CurseForgeSearchResponse {
@@ -43,7 +100,8 @@ struct CurseForgeLinks {
 }
 
 /// Search CurseForge for ASA mods
-/// This is the primary mod source for ARK: Survival Ascended
+/// This is the primary mod source for ARK: Survival Ascended.
+/// A missing or rejected API key is an error, not a placeholder result.
 pub async fn search_curseforge(
     query: &str,
     api_key: Option<String>,
@@ -54,22 +112,7 @@ pub async fn search_curseforge(
     let api_key = api_key.trim();
 
     if api_key.is_empty() {
-        return Ok(vec![ModInfo {
-            id: "0".to_string(),
-            curseforge_id: None,
-            name: "API Key Missing".to_string(),
-            author: Some("System".to_string()),
-            version: None,
-            downloads: None,
-            description: Some(
-                "Please add your CurseForge API Key in Settings to search ASA mods.".to_string(),
-            ),
-            thumbnail_url: None,
-            curseforge_url: None,
-            enabled: false,
-            load_order: 0,
-            last_updated: None,
-        }]);
+        return Err("CurseForge API key missing. Add it in Settings to search ASA mods.".into());
     }
 
     let client = Client::builder()
@@ -112,13 +155,13 @@ pub async fn search_curseforge(
                 enabled: false,
                 load_order: 0,
                 last_updated: None,
+                game_version: None,
+                latest_version: None,
             })
             .collect());
     }
 
-    // ARK Survival Ascended Game ID on CurseForge
-    // Primary ID: 83374 (ARK: Survival Ascended)
-    let game_id = 83374;
+    let game_id = ASA_GAME_ID;
 
     let url = if query.is_empty() || query.len() < 2 {
         // If no search query, get popular mods
@@ -154,33 +197,30 @@ pub async fn search_curseforge(
                         Ok(body_text) => {
                             match serde_json::from_str::<CurseForgeSearchResponse>(&body_text) {
                                 Ok(search_results) => {
-                                    if !search_results.data.is_empty() {
-                                        println!("  ✅ Found {} mods", search_results.data.len());
-
-                                        let mods = search_results
-                                            .data
-                                            .into_iter()
-                                            .map(|cf_mod| ModInfo {
-                                                id: cf_mod.id.to_string(),
-                                                curseforge_id: Some(cf_mod.id as i64),
-                                                name: cf_mod.name,
-                                                author: cf_mod
-                                                    .authors
-                                                    .first()
-                                                    .map(|a| a.name.clone()),
-                                                version: None,
-                                                downloads: Some(cf_mod.download_count as i64),
-                                                description: Some(cf_mod.summary),
-                                                thumbnail_url: cf_mod.logo.map(|l| l.thumbnail_url),
-                                                curseforge_url: Some(cf_mod.links.website_url),
-                                                enabled: false,
-                                                load_order: 0,
-                                                last_updated: cf_mod.date_modified,
-                                            })
-                                            .collect();
-
-                                        return Ok(mods);
-                                    }
+                                    println!("  ✅ Found {} mods", search_results.data.len());
+
+                                    let mods = search_results
+                                        .data
+                                        .into_iter()
+                                        .map(|cf_mod| ModInfo {
+                                            id: cf_mod.id.to_string(),
+                                            curseforge_id: Some(cf_mod.id as i64),
+                                            name: cf_mod.name,
+                                            author: cf_mod.authors.first().map(|a| a.name.clone()),
+                                            version: None,
+                                            downloads: Some(cf_mod.download_count as i64),
+                                            description: Some(cf_mod.summary),
+                                            thumbnail_url: cf_mod.logo.map(|l| l.thumbnail_url),
+                                            curseforge_url: Some(cf_mod.links.website_url),
+                                            enabled: false,
+                                            load_order: 0,
+                                            last_updated: cf_mod.date_modified,
+                                            game_version: None,
+                                            latest_version: None,
+                                        })
+                                        .collect();
+
+                                    return Ok(mods);
                                 }
                                 Err(e) => {
                                     last_error = format!("Failed to parse response: {}", e);
@@ -195,22 +235,11 @@ pub async fn search_curseforge(
                     }
                 } else if status.as_u16() == 401 || status.as_u16() == 403 {
                     // Invalid API key - don't retry
-                    return Ok(vec![ModInfo {
-                        id: "0".to_string(),
-                        curseforge_id: None,
-                        name: "Invalid API Key".to_string(),
-                        author: Some("System".to_string()),
-                        version: None,
-                        downloads: None,
-                        description: Some(
-                            "Your CurseForge API Key is invalid or expired. Please update it in Settings.".to_string(),
-                        ),
-                        thumbnail_url: None,
-                        curseforge_url: Some("https://console.curseforge.com/".to_string()),
-                        enabled: false,
-                        load_order: 0,
-                        last_updated: None,
-                    }]);
+                    return Err(format!(
+                        "CurseForge rejected the API key (HTTP {}). Update it in Settings.",
+                        status.as_u16()
+                    )
+                    .into());
                 } else if status.as_u16() == 429 {
                     // Rate limited - wait longer before retry
                     last_error = "Rate limited by CurseForge API".to_string();
@@ -230,23 +259,11 @@ pub async fn search_curseforge(
 
     // All retries failed
     println!("  ❌ All retries failed: {}", last_error);
-    Ok(vec![ModInfo {
-        id: "0".to_string(),
-        curseforge_id: None,
-        name: "Search Failed".to_string(),
-        author: Some("System".to_string()),
-        version: None,
-        downloads: None,
-        description: Some(format!(
-            "Could not search CurseForge after {} attempts. Error: {}. Check your internet connection and API key.",
-            max_retries, last_error
-        )),
-        thumbnail_url: None,
-        curseforge_url: Some("https://www.curseforge.com/ark-survival-ascended".to_string()),
-        enabled: false,
-        load_order: 0,
-        last_updated: None,
-    }])
+    Err(format!(
+        "Could not search CurseForge after {} attempts: {}. Check your internet connection.",
+        max_retries, last_error
+    )
+    .into())
 }
 
 #[derive(Debug, Deserialize)]
@@ -278,6 +295,148 @@ pub async fn get_mod_description(
     Ok(body.data)
 }
 
+#[derive(Debug, Deserialize)]
+struct CurseForgeModsResponse {
+    data: Vec<CurseForgeModFiles>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeModFiles {
+    id: i64,
+    #[serde(rename = "latestFiles", default)]
+    latest_files: Vec<CurseForgeFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeFile {
+    id: i64,
+    #[serde(rename = "displayName", default)]
+    display_name: String,
+    #[serde(rename = "fileDate", default)]
+    file_date: Option<String>,
+    #[serde(rename = "gameVersions", default)]
+    game_versions: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<CurseForgeDependency>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeDependency {
+    #[serde(rename = "modId")]
+    mod_id: i64,
+    /// 3 = required dependency, 5 = incompatible
+    #[serde(rename = "relationType")]
+    relation_type: i32,
+}
+
+/// Newest file of an installed mod, as stored alongside it in the mods table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModFileInfo {
+    pub version: String,
+    pub game_version: Option<String>,
+    pub file_date: Option<String>,
+}
+
+/// Compare dotted game versions ("1.2.10" > "1.2.9"); non-numeric parts count as 0
+fn compare_game_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parts = |v: &str| -> Vec<u64> {
+        v.split('.')
+            .map(|p| p.trim().parse::<u64>().unwrap_or(0))
+            .collect()
+    };
+    let (a, b) = (parts(a), parts(b));
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| o.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Highest game version a file declares support for
+pub fn newest_game_version(versions: &[String]) -> Option<String> {
+    versions
+        .iter()
+        .filter(|v| !v.trim().is_empty())
+        .max_by(|a, b| compare_game_versions(a, b))
+        .cloned()
+}
+
+fn latest_file_info(files: &[CurseForgeFile]) -> Option<ModFileInfo> {
+    let file = files.iter().max_by_key(|f| f.id)?;
+    Some(ModFileInfo {
+        version: file.display_name.clone(),
+        game_version: newest_game_version(&file.game_versions),
+        file_date: file.file_date.clone(),
+    })
+}
+
+/// POST /mods for several ids at once
+async fn fetch_mods_files(
+    mod_ids: &[i64],
+    api_key: Option<String>,
+) -> Result<Vec<CurseForgeModFiles>, Box<dyn Error>> {
+    let api_key = api_key
+        .or_else(|| std::env::var("CURSEFORGE_API_KEY").ok())
+        .filter(|k| !k.is_empty())
+        .ok_or("CurseForge API key missing")?;
+
+    let url = format!("{}/mods", CURSEFORGE_API_URL);
+    let resp = Client::new()
+        .post(&url)
+        .header("x-api-key", api_key)
+        .json(&serde_json::json!({ "modIds": mod_ids }))
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        return Err(format!("CurseForge returned {}", resp.status()).into());
+    }
+
+    let body: CurseForgeModsResponse = resp.json().await?;
+    Ok(body.data)
+}
+
+/// Version, game version and date of each mod's newest file, keyed by mod id
+pub async fn get_mod_file_info(
+    mod_ids: &[i64],
+    api_key: Option<String>,
+) -> Result<HashMap<String, ModFileInfo>, Box<dyn Error>> {
+    let mods = fetch_mods_files(mod_ids, api_key).await?;
+    Ok(mods
+        .into_iter()
+        .filter_map(|m| Some((m.id.to_string(), latest_file_info(&m.latest_files)?)))
+        .collect())
+}
+
+/// Required/incompatible relations declared by each mod's newest file, keyed by mod id
+pub async fn get_mod_relations(
+    mod_ids: &[i64],
+    api_key: Option<String>,
+) -> Result<HashMap<String, Vec<(String, Relation)>>, Box<dyn Error>> {
+    let mods = fetch_mods_files(mod_ids, api_key).await?;
+    Ok(mods
+        .into_iter()
+        .map(|m| {
+            let relations = m
+                .latest_files
+                .into_iter()
+                .max_by_key(|f| f.id)
+                .map(|f| f.dependencies)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|d| {
+                    let relation = match d.relation_type {
+                        3 => Relation::Required,
+                        5 => Relation::Incompatible,
+                        _ => return None,
+                    };
+                    Some((d.mod_id.to_string(), relation))
+                })
+                .collect();
+            (m.id.to_string(), relations)
+        })
+        .collect())
+}
+
 /// Deprecated: Steam Workshop search (kept for reference only, ASA uses CurseForge)
 #[allow(dead_code)]
 pub async fn search_steam_workshop(query: &str) -> Result<Vec<ModInfo>, Box<dyn Error>> {
@@ -349,6 +508,8 @@ pub async fn search_steam_workshop(query: &str) -> Result<Vec<ModInfo>, Box<dyn
                 enabled: false,
                 load_order: 0,
                 last_updated: None,
+                game_version: None,
+                latest_version: None,
             });
         }
     }
@@ -365,9 +526,59 @@ mod tests {
         // Ensure env var is unset
         std::env::remove_var("CURSEFORGE_API_KEY");
         let results = search_curseforge("dino", None).await;
-        assert!(results.is_ok());
-        let mods = results.unwrap();
-        assert_eq!(mods.len(), 1);
-        assert_eq!(mods[0].name, "API Key Missing");
+        let error = results.unwrap_err().to_string();
+        assert!(error.contains("API key missing"));
+    }
+
+    fn sample_mod(id: &str) -> ModInfo {
+        ModInfo {
+            id: id.to_string(),
+            curseforge_id: None,
+            name: format!("Mod {}", id),
+            author: None,
+            version: None,
+            downloads: None,
+            description: None,
+            thumbnail_url: None,
+            curseforge_url: None,
+            enabled: false,
+            load_order: 0,
+            last_updated: None,
+            game_version: None,
+            latest_version: None,
+        }
+    }
+
+    #[test]
+    fn search_cache_matches_normalized_queries() {
+        cache_search("  Cache Test Structures ", &[sample_mod("1")]);
+        let cached = cached_search("cache test structures").unwrap();
+        assert_eq!(cached[0].id, "1");
+
+        cache_search("cache test structures", &[sample_mod("2")]);
+        assert_eq!(cached_search("CACHE TEST STRUCTURES").unwrap()[0].id, "2");
+        assert!(cached_search("cache test never searched").is_none());
+    }
+
+    #[test]
+    fn newest_game_version_compares_numerically() {
+        let versions = vec!["1.2.9".to_string(), "1.2.10".to_string(), "".to_string()];
+        assert_eq!(newest_game_version(&versions), Some("1.2.10".to_string()));
+        assert_eq!(newest_game_version(&[]), None);
+    }
+
+    #[test]
+    fn latest_file_info_uses_the_newest_file() {
+        let files: Vec<CurseForgeFile> = serde_json::from_value(serde_json::json!([
+            { "id": 10, "displayName": "v1.0", "fileDate": "2024-01-01T00:00:00Z", "gameVersions": ["1.0"] },
+            { "id": 12, "displayName": "v1.1", "fileDate": "2024-03-01T00:00:00Z", "gameVersions": ["1.0", "1.1"] }
+        ]))
+        .unwrap();
+
+        let info = latest_file_info(&files).unwrap();
+        assert_eq!(info.version, "v1.1");
+        assert_eq!(info.game_version.as_deref(), Some("1.1"));
+        assert_eq!(info.file_date.as_deref(), Some("2024-03-01T00:00:00Z"));
+        assert!(latest_file_info(&[]).is_none());
     }
 }
@@ -0,0 +1,249 @@
+// ActiveMods sync
+// Every mod change (install, toggle, reorder, ...) rewrites the ActiveMods line of
+// GameUserSettings.ini from the mods table. Syncs for one server run one at a time
+// and read the DB only once they hold the lock, so whichever sync runs last writes
+// the latest DB state, however the calls interleave.
+
+use crate::commands::server::enabled_mod_ids;
+use crate::db::Database;
+use crate::services::ini_parser::IniParser;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Quiet period before a reorder syncs; a newer reorder in that window takes over
+pub const REORDER_DEBOUNCE: Duration = Duration::from_millis(300);
+
+static SYNC_LOCKS: Mutex<Option<HashMap<i64, Arc<tokio::sync::Mutex<()>>>>> = Mutex::new(None);
+static REORDER_GENERATIONS: Mutex<Option<HashMap<i64, u64>>> = Mutex::new(None);
+
+fn server_lock(server_id: i64) -> Arc<tokio::sync::Mutex<()>> {
+    let mut guard = crate::db::lock_or_recover(&SYNC_LOCKS, "mod sync");
+    guard
+        .get_or_insert_with(HashMap::new)
+        .entry(server_id)
+        .or_default()
+        .clone()
+}
+
+/// Register a reorder for the server and return its generation
+pub fn begin_reorder(server_id: i64) -> u64 {
+    let mut guard = crate::db::lock_or_recover(&REORDER_GENERATIONS, "mod reorder");
+    let generation = guard
+        .get_or_insert_with(HashMap::new)
+        .entry(server_id)
+        .or_default();
+    *generation += 1;
+    *generation
+}
+
+/// False once a newer reorder has been registered for the server
+pub fn is_latest_reorder(server_id: i64, generation: u64) -> bool {
+    let guard = crate::db::lock_or_recover(&REORDER_GENERATIONS, "mod reorder");
+    guard
+        .as_ref()
+        .and_then(|generations| generations.get(&server_id))
+        .map_or(true, |latest| *latest == generation)
+}
+
+pub fn set_mod_enabled(
+    conn: &rusqlite::Connection,
+    server_id: i64,
+    mod_id: &str,
+    enabled: bool,
+) -> Result<(), String> {
+    conn.execute(
+        "UPDATE mods SET enabled = ?1 WHERE server_id = ?2 AND mod_id = ?3",
+        rusqlite::params![enabled, server_id, mod_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Store a full load order in one transaction so a sync never sees half of it
+pub fn set_load_order(
+    conn: &rusqlite::Connection,
+    server_id: i64,
+    mod_ids: &[String],
+) -> Result<(), String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    for (index, mod_id) in mod_ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE mods SET load_order = ?1 WHERE server_id = ?2 AND mod_id = ?3",
+            rusqlite::params![index as i32, server_id, mod_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+pub fn config_path(install_path: &str) -> PathBuf {
+    PathBuf::from(install_path).join("ShooterGame/Saved/Config/WindowsServer/GameUserSettings.ini")
+}
+
+/// `content` with its ActiveMods line set to `mod_ids`, adding the line under
+/// [ServerSettings] (or a new [ServerSettings]) when there is none
+pub fn with_active_mods(content: &str, mod_ids: &[String]) -> String {
+    let active_mods_line = format!("ActiveMods={}", mod_ids.join(","));
+
+    if content.contains("ActiveMods=") {
+        // Replace existing line
+        return content
+            .lines()
+            .map(|line| {
+                if line.starts_with("ActiveMods=") {
+                    active_mods_line.as_str()
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    // Add to [ServerSettings] section
+    let mut result = String::new();
+    let mut added = false;
+    for line in content.lines() {
+        result.push_str(line);
+        result.push('\n');
+        if line.starts_with("[ServerSettings]") && !added {
+            result.push_str(&active_mods_line);
+            result.push('\n');
+            added = true;
+        }
+    }
+    if !added {
+        result.push_str("\n[ServerSettings]\n");
+        result.push_str(&active_mods_line);
+        result.push('\n');
+    }
+    result
+}
+
+/// Write the server's enabled mods to the ActiveMods line of GameUserSettings.ini.
+/// Does nothing until the server has written its config (fresh installs).
+pub async fn sync_active_mods(db: &Database, server_id: i64) -> Result<(), String> {
+    let lock = server_lock(server_id);
+    let _guard = lock.lock().await;
+
+    let (install_path, mod_ids) = {
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        let install_path: String = conn
+            .query_row(
+                "SELECT install_path FROM servers WHERE id = ?1",
+                [server_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        (install_path, enabled_mod_ids(&conn, server_id)?)
+    };
+
+    let config_path = config_path(&install_path);
+    if !config_path.exists() {
+        log::info!("  ℹ️ No GameUserSettings.ini yet, skipping ActiveMods sync");
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
+    IniParser::write_atomic(&config_path, &with_active_mods(&content, &mod_ids))
+        .map_err(|e| e.to_string())?;
+    log::info!("  📝 Updated ActiveMods in INI: {} mods", mod_ids.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::db_with_server;
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn active_mods(path: &PathBuf) -> String {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .find_map(|line| line.strip_prefix("ActiveMods="))
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn active_mods_line_is_replaced_or_added() {
+        let replaced = with_active_mods("[ServerSettings]\nActiveMods=1\nFoo=2", &ids(&["3", "4"]));
+        assert_eq!(replaced, "[ServerSettings]\nActiveMods=3,4\nFoo=2");
+
+        let added = with_active_mods("[ServerSettings]\nFoo=2\n", &ids(&["5"]));
+        assert_eq!(added, "[ServerSettings]\nActiveMods=5\nFoo=2\n");
+
+        let new_section = with_active_mods("[Other]\n", &[]);
+        assert!(new_section.ends_with("[ServerSettings]\nActiveMods=\n"));
+    }
+
+    #[test]
+    fn reorders_supersede_each_other() {
+        let server_id = -640;
+        let first = begin_reorder(server_id);
+        assert!(is_latest_reorder(server_id, first));
+        let second = begin_reorder(server_id);
+        assert!(!is_latest_reorder(server_id, first));
+        assert!(is_latest_reorder(server_id, second));
+    }
+
+    /// Toggles and reorders fired concurrently, each followed by its sync the way
+    /// the commands do it; the INI must end up matching the DB
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_toggle_and_reorder_leave_ini_matching_db() {
+        let (dir, db, server_id) = db_with_server("mod_sync");
+        let db = Arc::new(db);
+
+        let install_path = dir.path().join("server").to_string_lossy().to_string();
+        let ini = config_path(&install_path);
+        std::fs::create_dir_all(ini.parent().unwrap()).unwrap();
+        std::fs::write(&ini, "[ServerSettings]\nActiveMods=\nSessionName=Test\n").unwrap();
+
+        let mods = ids(&["100", "200", "300", "400", "500"]);
+        {
+            let conn = db.get_connection().unwrap();
+            for (index, mod_id) in mods.iter().enumerate() {
+                conn.execute(
+                    "INSERT INTO mods (server_id, mod_id, name, load_order) VALUES (?1, ?2, ?2, ?3)",
+                    rusqlite::params![server_id, mod_id, index as i32],
+                )
+                .unwrap();
+            }
+        }
+
+        let mut tasks = Vec::new();
+        for round in 0..40usize {
+            let db = db.clone();
+            let mods = mods.clone();
+            tasks.push(tokio::spawn(async move {
+                {
+                    let conn = db.get_connection().unwrap();
+                    if round % 2 == 0 {
+                        let mod_id = &mods[round % mods.len()];
+                        set_mod_enabled(&conn, server_id, mod_id, round % 4 == 0).unwrap();
+                    } else {
+                        let mut order = mods.clone();
+                        order.rotate_left(round % mods.len());
+                        set_load_order(&conn, server_id, &order).unwrap();
+                    }
+                }
+                sync_active_mods(&db, server_id).await.unwrap();
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let expected = {
+            let conn = db.get_connection().unwrap();
+            enabled_mod_ids(&conn, server_id).unwrap().join(",")
+        };
+        assert_eq!(active_mods(&ini), expected);
+    }
+}
@@ -53,3 +53,102 @@ pub fn is_port_in_use(port: u16) -> bool {
 
     false
 }
+
+/// Discover a UPnP (IGD) gateway on the local network.
+/// Returns the gateway and the LAN address of this machine as seen by it.
+async fn find_upnp_gateway() -> Result<
+    (
+        igd_next::aio::Gateway<igd_next::aio::tokio::Tokio>,
+        std::net::IpAddr,
+    ),
+    String,
+> {
+    let options = igd_next::SearchOptions {
+        timeout: Some(Duration::from_secs(3)),
+        ..Default::default()
+    };
+
+    let gateway = igd_next::aio::tokio::search_gateway(options)
+        .await
+        .map_err(|e| {
+            format!(
+                "No UPnP-capable gateway found ({}). Enable UPnP on your router or forward the ports manually.",
+                e
+            )
+        })?;
+
+    // Find which local interface routes to the gateway
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).map_err(|e| e.to_string())?;
+    socket
+        .connect(gateway.addr)
+        .map_err(|e| format!("Failed to reach gateway {}: {}", gateway.addr, e))?;
+    let local_ip = socket.local_addr().map_err(|e| e.to_string())?.ip();
+
+    Ok((gateway, local_ip))
+}
+
+/// Ports an ASA server needs forwarded: game/query over UDP, RCON over TCP
+fn upnp_port_list(
+    game_port: u16,
+    query_port: u16,
+    rcon_port: u16,
+) -> [(igd_next::PortMappingProtocol, u16, &'static str); 3] {
+    use igd_next::PortMappingProtocol;
+    [
+        (PortMappingProtocol::UDP, game_port, "Game"),
+        (PortMappingProtocol::UDP, query_port, "Query"),
+        (PortMappingProtocol::TCP, rcon_port, "RCON"),
+    ]
+}
+
+/// Open the server's ports on the router via UPnP.
+/// Returns a human readable line per mapped port.
+pub async fn upnp_open_ports(
+    game_port: u16,
+    query_port: u16,
+    rcon_port: u16,
+    description: &str,
+) -> Result<Vec<String>, String> {
+    let (gateway, local_ip) = find_upnp_gateway().await?;
+    let mut opened = Vec::new();
+
+    for (protocol, port, label) in upnp_port_list(game_port, query_port, rcon_port) {
+        let local_addr = std::net::SocketAddr::new(local_ip, port);
+        gateway
+            .add_port(
+                protocol,
+                port,
+                local_addr,
+                0, // Permanent lease, removed again when the server stops
+                &format!("{} ({})", description, label),
+            )
+            .await
+            .map_err(|e| format!("Failed to forward {} port {}: {}", label, port, e))?;
+        opened.push(format!(
+            "{} port {} ({:?}) -> {}",
+            label, port, protocol, local_addr
+        ));
+    }
+
+    Ok(opened)
+}
+
+/// Remove the server's UPnP port mappings from the router
+pub async fn upnp_close_ports(
+    game_port: u16,
+    query_port: u16,
+    rcon_port: u16,
+) -> Result<Vec<String>, String> {
+    let (gateway, _) = find_upnp_gateway().await?;
+    let mut closed = Vec::new();
+
+    for (protocol, port, label) in upnp_port_list(game_port, query_port, rcon_port) {
+        // A missing mapping is not an error - it may have expired or never been created
+        match gateway.remove_port(protocol, port).await {
+            Ok(()) => closed.push(format!("{} port {} ({:?})", label, port, protocol)),
+            Err(e) => println!("  ⚠️ UPnP: could not remove {} port {}: {}", label, port, e),
+        }
+    }
+
+    Ok(closed)
+}
@@ -0,0 +1,707 @@
+// Notification dispatch for ASA Server Manager
+// Server lifecycle and backup events are fanned out to every enabled channel
+// (Discord, generic JSON webhook, SMTP email) configured in settings.
+
+use crate::models::DiscordConfig;
+use crate::services::discord::{DiscordEmbed, DiscordService};
+use crate::services::settings;
+use crate::AppState;
+use chrono::{DateTime, Utc};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Settings key holding the JSON-encoded DiscordConfig (single webhook, pre-channels)
+pub const DISCORD_CONFIG_SETTING: &str = "discord_config";
+
+/// Settings key holding the JSON-encoded Vec<NotificationChannelConfig>
+pub const CHANNELS_SETTING: &str = "notification_channels";
+
+#[derive(Debug, Clone)]
+pub enum NotificationEvent {
+    ServerOnline,
+    ServerOffline,
+    ServerCrashed {
+        error: Option<String>,
+    },
+    BackupCompleted {
+        size_bytes: i64,
+    },
+    BackupFailed {
+        error: String,
+    },
+    ServerUpdated,
+    /// Summary of failure events that were suppressed during a throttle window
+    Repeated {
+        of: Box<NotificationEvent>,
+        occurrences: u32,
+        window_secs: u64,
+    },
+    Test,
+}
+
+impl NotificationEvent {
+    /// Stable key used in channel event filters
+    pub fn kind(&self) -> &'static str {
+        match self {
+            NotificationEvent::ServerOnline => "server_online",
+            NotificationEvent::ServerOffline => "server_offline",
+            NotificationEvent::ServerCrashed { .. } => "server_crashed",
+            NotificationEvent::BackupCompleted { .. } => "backup_completed",
+            NotificationEvent::BackupFailed { .. } => "backup_failed",
+            NotificationEvent::ServerUpdated => "server_updated",
+            NotificationEvent::Repeated { of, .. } => of.kind(),
+            NotificationEvent::Test => "test",
+        }
+    }
+
+    /// Failure events that a crash loop or broken backup job can fire over and over
+    fn is_throttled(&self) -> bool {
+        matches!(
+            self,
+            NotificationEvent::ServerCrashed { .. } | NotificationEvent::BackupFailed { .. }
+        )
+    }
+}
+
+/// A rendered event, ready for any channel
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub server_id: Option<i64>,
+    pub server_name: String,
+    pub map_name: String,
+    pub event: NotificationEvent,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn title(&self) -> String {
+        match &self.event {
+            NotificationEvent::ServerOnline => "🟢 Server Online",
+            NotificationEvent::ServerOffline => "🔴 Server Stopped",
+            NotificationEvent::ServerCrashed { .. } => "💥 Server Crashed",
+            NotificationEvent::BackupCompleted { .. } => "💾 Backup Completed",
+            NotificationEvent::BackupFailed { .. } => "⚠️ Backup Failed",
+            NotificationEvent::ServerUpdated => "📥 Server Updated",
+            NotificationEvent::Repeated { of, .. } => match of.as_ref() {
+                NotificationEvent::BackupFailed { .. } => "🔁 Backups Still Failing",
+                _ => "🔁 Server Still Down",
+            },
+            NotificationEvent::Test => "🔔 Test Notification",
+        }
+        .to_string()
+    }
+
+    pub fn message(&self) -> String {
+        let name = &self.server_name;
+        match &self.event {
+            NotificationEvent::ServerOnline => format!("{} is now online!", name),
+            NotificationEvent::ServerOffline => format!("{} has been shut down.", name),
+            NotificationEvent::ServerCrashed { error } => match error {
+                Some(error) => format!("{} has crashed unexpectedly! ({})", name, error),
+                None => format!("{} has crashed unexpectedly!", name),
+            },
+            NotificationEvent::BackupCompleted { size_bytes } => format!(
+                "Backup of {} finished ({:.1} MB).",
+                name,
+                *size_bytes as f64 / 1_048_576.0
+            ),
+            NotificationEvent::BackupFailed { error } => {
+                format!("Backup of {} failed: {}", name, error)
+            }
+            NotificationEvent::ServerUpdated => {
+                format!("{} was updated to the latest build.", name)
+            }
+            NotificationEvent::Repeated {
+                of,
+                occurrences,
+                window_secs,
+            } => {
+                let minutes = (window_secs / 60).max(1);
+                match of.as_ref() {
+                    NotificationEvent::BackupFailed { .. } => format!(
+                        "Backups of {} are still failing: {} more failures in the last {} minutes.",
+                        name, occurrences, minutes
+                    ),
+                    _ => format!(
+                        "{} is still down: {} more crashes in the last {} minutes.",
+                        name, occurrences, minutes
+                    ),
+                }
+            }
+            NotificationEvent::Test => {
+                "Notifications from ASA Server Manager are working.".to_string()
+            }
+        }
+    }
+}
+
+type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
+
+/// A delivery channel for notifications
+pub trait Notifier: Send + Sync {
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a>;
+}
+
+/// Reuses the embeds from DiscordService so every Discord channel looks the same
+pub struct DiscordNotifier {
+    pub webhook_url: String,
+}
+
+impl Notifier for DiscordNotifier {
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a> {
+        Box::pin(async move {
+            let discord = DiscordService::new();
+            // Event filtering is done per channel, so enable everything here
+            let config = DiscordConfig {
+                webhook_url: Some(self.webhook_url.clone()),
+                notify_server_start: true,
+                notify_server_stop: true,
+                notify_server_crash: true,
+                notify_backup: true,
+                notify_server_update: true,
+                ..Default::default()
+            };
+            let server_name = notification.server_name.as_str();
+
+            match &notification.event {
+                NotificationEvent::ServerOnline => {
+                    discord
+                        .notify_server_start(&config, server_name, &notification.map_name)
+                        .await
+                }
+                NotificationEvent::ServerOffline => {
+                    discord.notify_server_stop(&config, server_name).await
+                }
+                NotificationEvent::ServerCrashed { error } => {
+                    discord
+                        .notify_server_crash(&config, server_name, error.as_deref())
+                        .await
+                }
+                NotificationEvent::BackupCompleted { size_bytes } => {
+                    discord
+                        .notify_backup(&config, server_name, Ok(*size_bytes))
+                        .await
+                }
+                NotificationEvent::BackupFailed { error } => {
+                    discord
+                        .notify_backup(&config, server_name, Err(error.as_str()))
+                        .await
+                }
+                NotificationEvent::ServerUpdated => {
+                    discord.notify_server_updated(&config, server_name).await
+                }
+                NotificationEvent::Repeated { .. } => {
+                    let embed = DiscordEmbed {
+                        title: notification.title(),
+                        description: notification.message(),
+                        color: 0xDC2626, // Dark Red
+                        fields: vec![],
+                        footer: Some("ASA Server Manager 2.0".to_string()),
+                        timestamp: Some(notification.timestamp.to_rfc3339()),
+                    };
+                    discord.send_webhook(&self.webhook_url, embed).await
+                }
+                NotificationEvent::Test => {
+                    let embed = DiscordEmbed {
+                        title: notification.title(),
+                        description: notification.message(),
+                        color: 0x3B82F6, // Blue
+                        fields: vec![],
+                        footer: Some("ASA Server Manager 2.0".to_string()),
+                        timestamp: Some(notification.timestamp.to_rfc3339()),
+                    };
+                    discord.send_webhook(&self.webhook_url, embed).await
+                }
+            }
+        })
+    }
+}
+
+/// POSTs a flat JSON document, for Slack-style relays, n8n, Home Assistant, etc.
+pub struct WebhookNotifier {
+    pub url: String,
+    pub headers: HashMap<String, String>,
+}
+
+impl Notifier for WebhookNotifier {
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a> {
+        Box::pin(async move {
+            let payload = json!({
+                "event": notification.event.kind(),
+                "serverId": notification.server_id,
+                "serverName": notification.server_name,
+                "title": notification.title(),
+                "message": notification.message(),
+                // Slack incoming webhooks only read `text`
+                "text": format!("{} - {}", notification.title(), notification.message()),
+                "timestamp": notification.timestamp.to_rfc3339(),
+            });
+
+            let mut request = reqwest::Client::new().post(&self.url).json(&payload);
+            for (key, value) in &self.headers {
+                request = request.header(key.as_str(), value.as_str());
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| format!("Failed to send webhook: {}", e))?;
+            if !response.status().is_success() {
+                return Err(format!("Webhook returned HTTP {}", response.status()));
+            }
+            Ok(())
+        })
+    }
+}
+
+pub struct EmailNotifier {
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub username: String,
+    pub password: String,
+    pub from: String,
+    pub to: Vec<String>,
+    pub starttls: bool,
+}
+
+impl Notifier for EmailNotifier {
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a> {
+        Box::pin(async move {
+            let from: Mailbox = self
+                .from
+                .parse()
+                .map_err(|e| format!("Invalid sender address: {}", e))?;
+            let mut builder = Message::builder()
+                .from(from)
+                .subject(format!("[ASA] {}", notification.title()));
+            for to in &self.to {
+                let mailbox: Mailbox = to
+                    .parse()
+                    .map_err(|e| format!("Invalid recipient '{}': {}", to, e))?;
+                builder = builder.to(mailbox);
+            }
+            let email = builder
+                .body(format!(
+                    "{}\n\n{}",
+                    notification.message(),
+                    notification.timestamp.to_rfc3339()
+                ))
+                .map_err(|e| format!("Failed to build email: {}", e))?;
+
+            // STARTTLS (587) or implicit TLS (465)
+            let transport = if self.starttls {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host)
+            } else {
+                AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)
+            }
+            .map_err(|e| format!("Invalid SMTP host: {}", e))?
+            .port(self.smtp_port)
+            .credentials(Credentials::new(
+                self.username.clone(),
+                self.password.clone(),
+            ))
+            .build();
+
+            transport
+                .send(email)
+                .await
+                .map_err(|e| format!("Failed to send email: {}", e))?;
+            Ok(())
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ChannelKind {
+    #[serde(rename_all = "camelCase")]
+    Discord { webhook_url: String },
+    #[serde(rename_all = "camelCase")]
+    Webhook {
+        url: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Email {
+        smtp_host: String,
+        smtp_port: u16,
+        username: String,
+        password: String,
+        from: String,
+        to: Vec<String>,
+        #[serde(default = "default_starttls")]
+        starttls: bool,
+    },
+}
+
+fn default_starttls() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationChannelConfig {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Event kinds to deliver (see NotificationEvent::kind); empty means all
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(flatten)]
+    pub kind: ChannelKind,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl NotificationChannelConfig {
+    pub fn wants(&self, event: &NotificationEvent) -> bool {
+        matches!(event, NotificationEvent::Test)
+            || self.events.is_empty()
+            || self.events.iter().any(|e| e == event.kind())
+    }
+
+    pub fn notifier(&self) -> Box<dyn Notifier> {
+        match &self.kind {
+            ChannelKind::Discord { webhook_url } => Box::new(DiscordNotifier {
+                webhook_url: webhook_url.clone(),
+            }),
+            ChannelKind::Webhook { url, headers } => Box::new(WebhookNotifier {
+                url: url.clone(),
+                headers: headers.clone(),
+            }),
+            ChannelKind::Email {
+                smtp_host,
+                smtp_port,
+                username,
+                password,
+                from,
+                to,
+                starttls,
+            } => Box::new(EmailNotifier {
+                smtp_host: smtp_host.clone(),
+                smtp_port: *smtp_port,
+                username: username.clone(),
+                password: password.clone(),
+                from: from.clone(),
+                to: to.clone(),
+                starttls: *starttls,
+            }),
+        }
+    }
+}
+
+pub fn load_discord_config(state: &AppState) -> DiscordConfig {
+    state
+        .db
+        .lock()
+        .ok()
+        .and_then(|db| db.get_setting(DISCORD_CONFIG_SETTING).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// The single Discord webhook from DiscordConfig, expressed as a channel
+fn legacy_discord_channel(config: &DiscordConfig) -> Option<NotificationChannelConfig> {
+    let webhook_url = config
+        .webhook_url
+        .clone()
+        .filter(|url| !url.trim().is_empty())?;
+
+    let mut events = Vec::new();
+    for (enabled, kinds) in [
+        (config.notify_server_start, &["server_online"][..]),
+        (config.notify_server_stop, &["server_offline"][..]),
+        (config.notify_server_crash, &["server_crashed"][..]),
+        (
+            config.notify_backup,
+            &["backup_completed", "backup_failed"][..],
+        ),
+        (config.notify_server_update, &["server_updated"][..]),
+    ] {
+        if enabled {
+            events.extend(kinds.iter().map(|k| k.to_string()));
+        }
+    }
+    if events.is_empty() {
+        return None;
+    }
+
+    Some(NotificationChannelConfig {
+        name: "Discord".to_string(),
+        enabled: true,
+        events,
+        kind: ChannelKind::Discord { webhook_url },
+    })
+}
+
+pub fn load_channels(state: &AppState) -> Vec<NotificationChannelConfig> {
+    state
+        .db
+        .lock()
+        .ok()
+        .and_then(|db| db.get_setting(CHANNELS_SETTING).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Every enabled channel, including the Discord webhook from DiscordConfig
+fn active_channels(state: &AppState) -> Vec<NotificationChannelConfig> {
+    let mut channels: Vec<NotificationChannelConfig> = load_channels(state)
+        .into_iter()
+        .filter(|c| c.enabled)
+        .collect();
+    if let Some(discord) = legacy_discord_channel(&load_discord_config(state)) {
+        channels.push(discord);
+    }
+    channels
+}
+
+fn build_notification(state: &AppState, server_id: i64, event: NotificationEvent) -> Notification {
+    let (server_name, map_name) = state
+        .db
+        .lock()
+        .ok()
+        .and_then(|db| {
+            let conn = db.get_connection().ok()?;
+            conn.query_row(
+                "SELECT name, map_name FROM servers WHERE id = ?1",
+                [server_id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .ok()
+        })
+        .unwrap_or_else(|| (format!("Server {}", server_id), String::new()));
+
+    Notification {
+        server_id: Some(server_id),
+        server_name,
+        map_name,
+        event,
+        timestamp: Utc::now(),
+    }
+}
+
+struct ThrottleEntry {
+    window_start: Instant,
+    suppressed: u32,
+    /// Bumped on every reset so a pending flush can tell it is stale
+    generation: u64,
+}
+
+/// Per (server, event kind) suppression of repeated failure notifications
+#[derive(Default)]
+pub struct NotificationThrottleState {
+    entries: Mutex<HashMap<(i64, &'static str), ThrottleEntry>>,
+    next_generation: AtomicU64,
+}
+
+enum ThrottleDecision {
+    Send,
+    /// First suppression in this window: schedule the summary for when it closes
+    SuppressAndFlush {
+        generation: u64,
+        remaining: Duration,
+    },
+    Suppress,
+}
+
+impl NotificationThrottleState {
+    fn check(&self, server_id: i64, kind: &'static str, window: Duration) -> ThrottleDecision {
+        let mut entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return ThrottleDecision::Send,
+        };
+        let now = Instant::now();
+
+        if let Some(entry) = entries.get_mut(&(server_id, kind)) {
+            let elapsed = now.duration_since(entry.window_start);
+            if elapsed < window {
+                entry.suppressed += 1;
+                return if entry.suppressed == 1 {
+                    ThrottleDecision::SuppressAndFlush {
+                        generation: entry.generation,
+                        remaining: window - elapsed,
+                    }
+                } else {
+                    ThrottleDecision::Suppress
+                };
+            }
+        }
+
+        let generation = self.bump_generation();
+        entries.insert(
+            (server_id, kind),
+            ThrottleEntry {
+                window_start: now,
+                suppressed: 0,
+                generation,
+            },
+        );
+        ThrottleDecision::Send
+    }
+
+    /// Take the suppressed count for a window that just closed and start a fresh one
+    fn flush(&self, server_id: i64, kind: &'static str, generation: u64) -> u32 {
+        let mut entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return 0,
+        };
+        let next = self.bump_generation();
+        match entries.get_mut(&(server_id, kind)) {
+            Some(entry) if entry.generation == generation => {
+                let suppressed = entry.suppressed;
+                entry.window_start = Instant::now();
+                entry.suppressed = 0;
+                entry.generation = next;
+                suppressed
+            }
+            _ => 0,
+        }
+    }
+
+    /// Forget all throttling for a server once it is healthy again
+    pub fn reset(&self, server_id: i64) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|(id, _), _| *id != server_id);
+        }
+    }
+
+    fn bump_generation(&self) -> u64 {
+        self.next_generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
+fn throttle_window(state: &AppState) -> Duration {
+    Duration::from_secs(settings::NOTIFICATION_THROTTLE_SECS.load(state))
+}
+
+/// Fire-and-forget: each channel is sent on a background task and failures are only logged,
+/// so callers (monitor thread, commands) never wait on or fail because of a channel.
+/// Repeated crashes/backup failures for the same server inside the throttle window are
+/// collapsed into one "still down" summary sent when the window closes.
+pub fn notify(app_handle: &AppHandle, server_id: i64, event: NotificationEvent) {
+    if let Some(throttle) = app_handle.try_state::<NotificationThrottleState>() {
+        if matches!(event, NotificationEvent::ServerOnline) {
+            throttle.reset(server_id);
+        } else if event.is_throttled() {
+            let window = match app_handle.try_state::<AppState>() {
+                Some(state) => throttle_window(&state),
+                None => return,
+            };
+            match throttle.check(server_id, event.kind(), window) {
+                ThrottleDecision::Send => {}
+                ThrottleDecision::Suppress => {
+                    log::info!(
+                        "🔕 Suppressed repeated '{}' notification for server {}",
+                        event.kind(),
+                        server_id
+                    );
+                    return;
+                }
+                ThrottleDecision::SuppressAndFlush {
+                    generation,
+                    remaining,
+                } => {
+                    log::info!(
+                        "🔕 Suppressed repeated '{}' notification for server {}",
+                        event.kind(),
+                        server_id
+                    );
+                    schedule_flush(
+                        app_handle.clone(),
+                        server_id,
+                        event,
+                        generation,
+                        remaining,
+                        window,
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    dispatch(app_handle, server_id, event);
+}
+
+fn schedule_flush(
+    app_handle: AppHandle,
+    server_id: i64,
+    event: NotificationEvent,
+    generation: u64,
+    remaining: Duration,
+    window: Duration,
+) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(remaining).await;
+        let throttle = app_handle.state::<NotificationThrottleState>();
+        // Zero when the server recovered (reset) before the window closed
+        let occurrences = throttle.flush(server_id, event.kind(), generation);
+        if occurrences > 0 {
+            dispatch(
+                &app_handle,
+                server_id,
+                NotificationEvent::Repeated {
+                    of: Box::new(event),
+                    occurrences,
+                    window_secs: window.as_secs(),
+                },
+            );
+        }
+    });
+}
+
+fn dispatch(app_handle: &AppHandle, server_id: i64, event: NotificationEvent) {
+    let state = match app_handle.try_state::<AppState>() {
+        Some(state) => state,
+        None => return,
+    };
+
+    let channels: Vec<NotificationChannelConfig> = active_channels(&state)
+        .into_iter()
+        .filter(|c| c.wants(&event))
+        .collect();
+    if channels.is_empty() {
+        return;
+    }
+
+    let notification = build_notification(&state, server_id, event);
+    for channel in channels {
+        let notification = notification.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = channel.notifier().send(&notification).await {
+                log::warn!(
+                    "⚠️ Notification '{}' via {} failed: {}",
+                    notification.event.kind(),
+                    channel.name,
+                    e
+                );
+            }
+        });
+    }
+}
+
+/// Send a test message through one channel and report the result
+pub async fn send_test(channel: &NotificationChannelConfig) -> Result<(), String> {
+    let notification = Notification {
+        server_id: None,
+        server_name: "ASA Server Manager".to_string(),
+        map_name: String::new(),
+        event: NotificationEvent::Test,
+        timestamp: Utc::now(),
+    };
+    channel.notifier().send(&notification).await
+}
@@ -1,16 +1,18 @@
 // Player Intelligence Service for ASA Server Manager
 // Tracks player sessions, playtime, and provides analytics
 
-use crate::models::{PlayerSession, PlayerStats};
+use crate::models::{PlayerEntry, PlayerSession, PlayerStats};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+type ActiveSession = (i64, String, chrono::DateTime<chrono::Local>, Option<String>);
+
 /// Service for tracking player activity and statistics
 #[allow(dead_code)]
 pub struct PlayerIntelligenceService {
-    /// Active sessions: steam_id -> (server_id, player_name, join_time)
-    active_sessions: Arc<Mutex<HashMap<String, (i64, String, chrono::DateTime<chrono::Local>)>>>,
+    /// Active sessions: steam_id -> (server_id, player_name, join_time, eos_id)
+    active_sessions: Arc<Mutex<HashMap<String, ActiveSession>>>,
 }
 
 #[allow(dead_code)]
@@ -22,13 +24,24 @@ impl PlayerIntelligenceService {
     }
 
     /// Record a player joining a server
-    pub async fn player_joined(&self, server_id: i64, steam_id: &str, player_name: &str) {
+    pub async fn player_joined(
+        &self,
+        server_id: i64,
+        steam_id: &str,
+        player_name: &str,
+        eos_id: Option<&str>,
+    ) {
         let mut sessions = self.active_sessions.lock().await;
         let now = chrono::Local::now();
 
         sessions.insert(
             steam_id.to_string(),
-            (server_id, player_name.to_string(), now),
+            (
+                server_id,
+                player_name.to_string(),
+                now,
+                eos_id.map(|id| id.to_string()),
+            ),
         );
 
         println!(
@@ -41,7 +54,7 @@ impl PlayerIntelligenceService {
     pub async fn player_left(&self, steam_id: &str) -> Option<PlayerSession> {
         let mut sessions = self.active_sessions.lock().await;
 
-        if let Some((server_id, player_name, join_time)) = sessions.remove(steam_id) {
+        if let Some((server_id, player_name, join_time, eos_id)) = sessions.remove(steam_id) {
             let now = chrono::Local::now();
             let duration = now.signed_duration_since(join_time);
 
@@ -59,6 +72,7 @@ impl PlayerIntelligenceService {
                 player_name,
                 joined_at: join_time.to_rfc3339(),
                 left_at: Some(now.to_rfc3339()),
+                eos_id,
             })
         } else {
             None
@@ -70,7 +84,7 @@ impl PlayerIntelligenceService {
         let sessions = self.active_sessions.lock().await;
         sessions
             .get(steam_id)
-            .map(|(server_id, name, _)| (*server_id, name.clone()))
+            .map(|(server_id, name, _, _)| (*server_id, name.clone()))
     }
 
     /// Get all active sessions
@@ -78,7 +92,7 @@ impl PlayerIntelligenceService {
         let sessions = self.active_sessions.lock().await;
         sessions
             .iter()
-            .map(|(steam_id, (server_id, name, _))| (steam_id.clone(), *server_id, name.clone()))
+            .map(|(steam_id, (server_id, name, _, _))| (steam_id.clone(), *server_id, name.clone()))
             .collect()
     }
 
@@ -87,13 +101,55 @@ impl PlayerIntelligenceService {
         let sessions = self.active_sessions.lock().await;
         let mut counts: HashMap<i64, i32> = HashMap::new();
 
-        for (_, (server_id, _, _)) in sessions.iter() {
+        for (_, (server_id, _, _, _)) in sessions.iter() {
             *counts.entry(*server_id).or_insert(0) += 1;
         }
 
         counts
     }
 
+    /// Reconcile active sessions for a server against a fresh ListPlayers result.
+    /// Starts sessions for newly seen players and returns the sessions of players who left.
+    pub async fn sync_online_players(
+        &self,
+        server_id: i64,
+        online: &[PlayerEntry],
+    ) -> Vec<PlayerSession> {
+        let online_ids: Vec<&str> = online.iter().map(|p| p.player_id()).collect();
+
+        let departed: Vec<String> = {
+            let sessions = self.active_sessions.lock().await;
+            sessions
+                .iter()
+                .filter(|(id, (sid, _, _, _))| {
+                    *sid == server_id && !online_ids.contains(&id.as_str())
+                })
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+
+        let mut ended = Vec::new();
+        for steam_id in departed {
+            if let Some(session) = self.player_left(&steam_id).await {
+                ended.push(session);
+            }
+        }
+
+        for player in online {
+            if self.get_active_session(player.player_id()).await.is_none() {
+                self.player_joined(
+                    server_id,
+                    player.player_id(),
+                    &player.name,
+                    player.eos_id.as_deref(),
+                )
+                .await;
+            }
+        }
+
+        ended
+    }
+
     /// Clear all sessions for a server (e.g., when server stops)
     pub async fn clear_server_sessions(&self, server_id: i64) -> Vec<PlayerSession> {
         let mut sessions = self.active_sessions.lock().await;
@@ -102,12 +158,12 @@ impl PlayerIntelligenceService {
         let mut ended_sessions = Vec::new();
         let to_remove: Vec<String> = sessions
             .iter()
-            .filter(|(_, (sid, _, _))| *sid == server_id)
+            .filter(|(_, (sid, _, _, _))| *sid == server_id)
             .map(|(steam_id, _)| steam_id.clone())
             .collect();
 
         for steam_id in to_remove {
-            if let Some((server_id, player_name, join_time)) = sessions.remove(&steam_id) {
+            if let Some((server_id, player_name, join_time, eos_id)) = sessions.remove(&steam_id) {
                 ended_sessions.push(PlayerSession {
                     id: 0,
                     server_id,
@@ -115,6 +171,7 @@ impl PlayerIntelligenceService {
                     player_name,
                     joined_at: join_time.to_rfc3339(),
                     left_at: Some(now.to_rfc3339()),
+                    eos_id,
                 });
             }
         }
@@ -2,11 +2,13 @@ use anyhow::{Context, Result};
 use serde::Serialize;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Seek, SeekFrom};
-use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
 #[cfg(target_os = "windows")]
@@ -15,7 +17,12 @@ use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-use crate::services::network;
+use crate::models::ServerStatus;
+use crate::services::guardian::GuardianState;
+use crate::services::notifications::{self, NotificationEvent};
+use crate::services::server_state;
+use crate::services::settings;
+use crate::services::{launch_args, log_parser, network};
 use crate::AppState;
 use tauri::Manager;
 
@@ -78,9 +85,43 @@ pub struct ServerLogEvent {
 
 struct ServerProcess {
     child: Child,
+    /// Present when the server was launched with settings::CONSOLE_STDIN_ENABLED
+    stdin: Option<ConsoleInput>,
     stop_flag: Arc<AtomicBool>,
+    online_flag: Arc<AtomicBool>,
+    started_at: Instant,
+    log_file_path: PathBuf,
+    stuck_reported: bool,
+    /// Set by terminate; the stopping caller reaps the process, not the monitor
+    stopping: bool,
 }
 
+/// Queue to the thread that owns a server's stdin. Writes happen on that thread so a
+/// server that never reads its console (the pipe fills up) can't block anyone else.
+struct ConsoleInput {
+    sender: SyncSender<String>,
+    closed: Arc<AtomicBool>,
+}
+
+/// Console commands waiting for the writer thread before sends are refused
+const CONSOLE_QUEUE_LEN: usize = 32;
+
+/// Console output lines kept for the "exited immediately" error
+const EARLY_OUTPUT_LINES: usize = 20;
+
+/// How long a mod download may go without log progress before a stall warning
+const MOD_DOWNLOAD_STALL_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupStuckEvent {
+    pub server_id: i64,
+    pub elapsed_secs: u64,
+    pub last_lines: Vec<String>,
+}
+
+/// Cheap to clone: every clone shares the same process map
+#[derive(Clone)]
 pub struct ProcessManager {
     processes: Arc<Mutex<HashMap<i64, ServerProcess>>>,
     app_handle: AppHandle,
@@ -108,18 +149,24 @@ impl ProcessManager {
             loop {
                 std::thread::sleep(std::time::Duration::from_secs(2));
 
+                let startup_timeout = Self::startup_timeout(&monitor_handle);
+
                 let mut p_lock = monitor_processes.lock().unwrap();
                 let mut crashed_servers = Vec::new();
 
                 for (id, proc) in p_lock.iter_mut() {
+                    if proc.stopping {
+                        continue;
+                    }
                     match proc.child.try_wait() {
                         Ok(Some(status)) => {
                             // Process has exited
-                            println!(
+                            log::warn!(
                                 "  ⚠️ Monitor detected server {} exit with status: {:?}",
-                                id, status
+                                id,
+                                status
                             );
-                            crashed_servers.push(*id);
+                            crashed_servers.push((*id, format!("{:?}", status)));
 
                             // Signal log watcher to stop
                             proc.stop_flag.store(true, Ordering::SeqCst);
@@ -128,13 +175,13 @@ impl ProcessManager {
                             // Still running
                         }
                         Err(e) => {
-                            println!("  ❌ Monitor failed to check server {}: {}", id, e);
+                            log::error!("  ❌ Monitor failed to check server {}: {}", id, e);
                         }
                     }
                 }
 
                 // Remove crashed servers and emit events
-                for id in crashed_servers {
+                for (id, exit_status) in crashed_servers {
                     p_lock.remove(&id);
                     let _ = monitor_handle.emit(
                         "server-status-change",
@@ -143,16 +190,159 @@ impl ProcessManager {
                             status: "stopped".to_string(), // Or "crashed"
                         },
                     );
+                    // Keep the DB (and so the status history) in step with the event
+                    if let Some(state) = monitor_handle.try_state::<AppState>() {
+                        server_state::update(&state, id, ServerStatus::Stopped);
+                    }
+                    Self::end_player_sessions(&monitor_handle, id);
+                    // Planned stops are marked stopping and skipped, so anything seen here is unexpected
+                    Self::record_crash(&monitor_handle, id, &exit_status);
+                    notifications::notify(
+                        &monitor_handle,
+                        id,
+                        NotificationEvent::ServerCrashed {
+                            error: Some(format!("Process exited: {}", exit_status)),
+                        },
+                    );
                 }
 
-                // Check for stuck servers (Running but not online for > 15 mins)
-                // TODO: Implement this using a timestamp check if needed
+                // Check for stuck servers (Running but never reached online within the timeout)
+                for (id, proc) in p_lock.iter_mut() {
+                    if proc.stuck_reported
+                        || proc.stopping
+                        || proc.online_flag.load(Ordering::SeqCst)
+                        || proc.started_at.elapsed() < startup_timeout
+                    {
+                        continue;
+                    }
+
+                    proc.stuck_reported = true;
+                    let elapsed_secs = proc.started_at.elapsed().as_secs();
+                    log::info!(
+                        "  ⏳ Server {} has not come online after {} minutes (possible mod download loop or missing map)",
+                        id,
+                        elapsed_secs / 60
+                    );
+
+                    let _ = monitor_handle.emit(
+                        "server-status-change",
+                        ServerStatusEvent {
+                            server_id: *id,
+                            status: "startup-stuck".to_string(),
+                        },
+                    );
+                    let _ = monitor_handle.emit(
+                        "server-startup-stuck",
+                        StartupStuckEvent {
+                            server_id: *id,
+                            elapsed_secs,
+                            last_lines: read_last_lines(&proc.log_file_path, 20),
+                        },
+                    );
+                }
             }
         });
 
         pm
     }
 
+    /// Startup timeout from settings; 0 falls back to the default
+    fn startup_timeout(app_handle: &AppHandle) -> Duration {
+        let minutes = app_handle
+            .try_state::<AppState>()
+            .map(|state| settings::STARTUP_TIMEOUT_MINUTES.load(&state))
+            .filter(|m| *m > 0)
+            .unwrap_or(settings::STARTUP_TIMEOUT_MINUTES.default);
+
+        Duration::from_secs(minutes * 60)
+    }
+
+    /// A seconds setting, or its default when the app state isn't managed yet
+    fn setting_duration(app_handle: &AppHandle, setting: &settings::Setting<u64>) -> Duration {
+        let secs = app_handle
+            .try_state::<AppState>()
+            .map(|state| setting.load(&state))
+            .unwrap_or(setting.default);
+        Duration::from_secs(secs)
+    }
+
+    /// Pause after a stop before relaunching or touching the server's files
+    pub fn stop_settle_delay(&self) -> Duration {
+        Self::setting_duration(&self.app_handle, &settings::STOP_SETTLE_SECS)
+    }
+
+    /// Ask the process tree to close without /F and wait up to `timeout` for `exited`.
+    /// Returns false when the request couldn't be delivered or the process outlived it.
+    fn request_close(pid: u32, timeout: Duration, exited: impl Fn() -> bool) -> bool {
+        if timeout.is_zero() {
+            return false;
+        }
+        let pid = pid.to_string();
+
+        #[cfg(target_os = "windows")]
+        let delivered = Command::new("taskkill")
+            .args(["/T", "/PID", &pid])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        #[cfg(not(target_os = "windows"))]
+        let delivered = Command::new("kill")
+            .args(["-TERM", &pid])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+
+        if !delivered {
+            return false;
+        }
+
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if exited() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        false
+    }
+
+    /// Feed an unexpected exit into the Guardian crash log / crash-loop counter
+    fn record_crash(app_handle: &AppHandle, server_id: i64, exit_status: &str) {
+        let server_name = app_handle
+            .try_state::<AppState>()
+            .and_then(|state| {
+                let db = &state.db;
+                let conn = db.get_connection().ok()?;
+                conn.query_row(
+                    "SELECT name FROM servers WHERE id = ?1",
+                    [server_id],
+                    |row| row.get::<_, String>(0),
+                )
+                .ok()
+            })
+            .unwrap_or_else(|| format!("Server {}", server_id));
+
+        let app_handle = app_handle.clone();
+        let reason = format!("Process exited: {}", exit_status);
+        tauri::async_runtime::spawn(async move {
+            if let Some(guardian) = app_handle.try_state::<GuardianState>() {
+                let guardian = guardian.0.lock().await;
+                guardian
+                    .log_crash(server_id, &server_name, &reason, false)
+                    .await;
+            }
+        });
+    }
+
+    /// Close the open player sessions of a server that is no longer running
+    fn end_player_sessions(app_handle: &AppHandle, server_id: i64) {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            crate::commands::player::end_sessions(&app_handle, server_id).await;
+        });
+    }
+
     fn emit_status_change(&self, server_id: i64, status: &str) {
         let _ = self.app_handle.emit(
             "server-status-change",
@@ -163,39 +353,51 @@ impl ProcessManager {
         );
     }
 
-    /// Start ARK server
+    /// Start ARK server. `use_asa_api` launches the AsaApi loader in place of the server binary.
     pub fn start_server(
         &self,
         server_id: i64,
-        _server_type: &str,
-        install_path: &PathBuf,
-        map_name: &str,
-        session_name: &str,
-        game_port: u16,
-        query_port: u16,
-        rcon_port: u16,
-        max_players: i32,
-        server_password: Option<&str>,
-        admin_password: &str,
-        ip_address: Option<&str>,
-        cluster_id: Option<&str>,
-        cluster_dir: Option<&str>,
-        mods: Option<&[String]>,
-        custom_args: Option<&str>,
+        install_path: &Path,
+        use_asa_api: bool,
+        options: &launch_args::LaunchOptions<'_>,
     ) -> Result<()> {
-        let executable = install_path
+        let win64_dir = install_path
             .join("ShooterGame")
             .join("Binaries")
-            .join("Win64")
-            .join("ArkAscendedServer.exe");
+            .join("Win64");
+        let server_executable = win64_dir.join("ArkAscendedServer.exe");
 
-        if !executable.exists() {
+        if !server_executable.exists() {
             return Err(anyhow::anyhow!(
                 "Server executable not found at {:?}",
-                executable
+                server_executable
             ));
         }
 
+        // With AsaApi the loader is launched instead of ArkAscendedServer.exe. It takes the
+        // same command line, starts the server as a child process and injects ArkApi so
+        // plugins in ArkApi/Plugins load. Stopping still works since taskkill /T kills the tree.
+        let executable = if use_asa_api {
+            let loader = win64_dir.join(crate::commands::plugin::ASA_API_LOADER);
+            if !loader.exists() || !win64_dir.join("ArkApi").is_dir() {
+                return Err(anyhow::anyhow!(
+                    "AsaApi is enabled for this server but {} was not found in {:?}. Install AsaApi or turn off 'Use AsaApi'.",
+                    crate::commands::plugin::ASA_API_LOADER,
+                    win64_dir
+                ));
+            }
+            log::info!(
+                "  🔌 Server {} launching through the AsaApi loader",
+                server_id
+            );
+            loader
+        } else {
+            server_executable
+        };
+
+        let (game_port, query_port, rcon_port) =
+            (options.game_port, options.query_port, options.rcon_port);
+
         // Check ports before starting
         if network::is_port_in_use(game_port) {
             return Err(anyhow::anyhow!(
@@ -224,87 +426,97 @@ impl ProcessManager {
             .join("ShooterGame.log");
 
         // Build launch arguments
-        let mut connection_url = format!("{}?listen", map_name);
-        connection_url.push_str(&format!("?SessionName={}", session_name));
-        connection_url.push_str(&format!("?Port={}", game_port));
-        connection_url.push_str(&format!("?QueryPort={}", query_port));
-        connection_url.push_str(&format!("?RCONPort={}", rcon_port));
-        connection_url.push_str(&format!("?MaxPlayers={}", max_players));
-        connection_url.push_str(&format!("?ServerAdminPassword={}", admin_password));
-
-        if let Some(password) = server_password {
-            connection_url.push_str(&format!("?ServerPassword={}", password));
-        }
-
-        let mut args = vec![connection_url];
-
-        args.push("-log".to_string());
-        args.push("-NoBattlEye".to_string());
+        let args = launch_args::build_launch_args(options);
 
-        // Add MultiHome for IP binding
-        if let Some(ip) = ip_address {
-            if !ip.is_empty() {
-                args.push(format!("-MultiHome={}", ip));
-            }
-        }
-
-        // Add cluster configuration for cross-ARK travel
-        if let (Some(cid), Some(cdir)) = (cluster_id, cluster_dir) {
+        if let (Some(cid), Some(cdir)) = (options.cluster_id, options.cluster_dir) {
             if !cid.is_empty() && !cdir.is_empty() {
-                args.push(format!("-clusterid={}", cid));
-                args.push(format!("-ClusterDirOverride=\"{}\"", cdir));
-                println!(
+                log::info!(
                     "  🔗 Server {} joining cluster: {} at {}",
-                    server_id, cid, cdir
-                );
-            }
-        }
-
-        // Add mods if any are enabled
-        if let Some(mod_list) = mods {
-            if !mod_list.is_empty() {
-                let mods_string = mod_list.join(",");
-                args.push(format!("-mods={}", mods_string));
-                println!(
-                    "  🧩 Server {} loading {} mods: {}",
                     server_id,
-                    mod_list.len(),
-                    mods_string
+                    cid,
+                    cdir
                 );
             }
         }
-
-        // Add custom launch arguments
-        if let Some(custom) = custom_args {
-            if !custom.is_empty() {
-                // Split by whitespace but respect basic quoting if possible,
-                // for now simple split is safer than nothing.
-                let custom_parts: Vec<String> =
-                    custom.split_whitespace().map(|s| s.to_string()).collect();
-                args.extend(custom_parts);
-            }
+        if let Some(mod_list) = options.mods.filter(|m| !m.is_empty()) {
+            log::info!(
+                "  🧩 Server {} loading {} mods: {}",
+                server_id,
+                mod_list.len(),
+                mod_list.join(",")
+            );
         }
 
-        println!("  🚀 Executing Command: {:?} {:?}", executable, args);
+        log::info!("  🚀 Executing Command: {:?} {:?}", executable, args);
+
+        // Opt-in: a piped stdin is what lets send_console_command reach the console
+        let pipe_stdin = self
+            .app_handle
+            .try_state::<AppState>()
+            .is_some_and(|state| settings::CONSOLE_STDIN_ENABLED.load(&state));
 
         let mut command = Command::new(&executable);
         command
             .args(&args)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null());
+            .stdin(if pipe_stdin {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
 
         let mut child = command.spawn().context("Failed to start server process")?;
+        let stdin = child
+            .stdin
+            .take()
+            .map(|stdin| spawn_console_writer(server_id, stdin));
         let child_pid = child.id();
 
+        // Console output shows up before ShooterGame.log exists, so it is the only
+        // trace of launch failures like missing DLLs
+        let early_output = Arc::new(Mutex::new(Vec::new()));
+        // Set once the ShooterGame.log tail is running; stdout echoes the same lines
+        let tail_following = Arc::new(AtomicBool::new(false));
+        if let Some(stdout) = child.stdout.take() {
+            pipe_output(
+                &self.app_handle,
+                server_id,
+                stdout,
+                false,
+                &early_output,
+                &tail_following,
+            );
+        }
+        if let Some(stderr) = child.stderr.take() {
+            pipe_output(
+                &self.app_handle,
+                server_id,
+                stderr,
+                true,
+                &early_output,
+                &tail_following,
+            );
+        }
+
         // Wait a longer moment to check for immediate startup failures (e.g. missing DLLs, bad path)
         std::thread::sleep(std::time::Duration::from_secs(5));
 
         match child.try_wait() {
             Ok(Some(status)) => {
-                // It crashed immediately
+                // It crashed immediately. The pipes are closed now; give the readers a
+                // moment to hand over what was left in them.
+                std::thread::sleep(Duration::from_millis(200));
+                let output = crate::db::lock_or_recover(&early_output, "early output").join("\n");
+                let output = if output.is_empty() {
+                    String::new()
+                } else {
+                    format!("\nLast console output:\n{}", output)
+                };
                 return Err(anyhow::anyhow!(
-                    "Server process exited immediately with status: {:?}. This often means an invalid configuration, missing map, or corrupt installation. Check server logs.",
-                    status
+                    "Server process exited immediately with status: {:?}. This often means an invalid configuration, missing map, or corrupt installation. Check server logs.{}",
+                    status,
+                    output
                 ));
             }
             Ok(None) => {
@@ -315,7 +527,7 @@ impl ProcessManager {
             }
         }
 
-        println!("  ✅ Server {} started with PID: {} ", server_id, child_pid);
+        log::info!("  ✅ Server {} started with PID: {} ", server_id, child_pid);
 
         // Emit 'running' event (This now means process started, but not yet ready)
         self.emit_status_change(server_id, "running");
@@ -331,12 +543,25 @@ impl ProcessManager {
         // Store process
         {
             let mut processes = self.processes.lock().unwrap();
-            processes.insert(server_id, ServerProcess { child, stop_flag });
+            processes.insert(
+                server_id,
+                ServerProcess {
+                    child,
+                    stdin,
+                    stop_flag,
+                    online_flag,
+                    started_at: Instant::now(),
+                    log_file_path: log_file_path.clone(),
+                    stuck_reported: false,
+                    stopping: false,
+                },
+            );
         }
 
         // Start log file watcher (Unchanged block omitted for brevity, keeping existing logic)
         let app_handle = self.app_handle.clone();
         let app_handle_status = self.app_handle.clone(); // Clone for status updates inside thread
+        let tail_following_clone = tail_following.clone();
 
         std::thread::spawn(move || {
             // Wait for log file to be created
@@ -376,14 +601,51 @@ impl ProcessManager {
 
             let mut reader = BufReader::new(file);
 
+            // From here on the file tail joins the piped console output in the same stream
+            let _ = app_handle.emit(
+                "server_log",
+                ServerLogEvent {
+                    server_id,
+                    line: "[Manager] Following ShooterGame.log".to_string(),
+                    is_stderr: false,
+                },
+            );
+            tail_following_clone.store(true, Ordering::SeqCst);
+
             // Seek to end to only read new lines
             let _ = reader.seek(SeekFrom::End(0));
 
+            // Mod download tracking: (mod id, last progress time, stall already reported)
+            let mut mod_download: Option<(Option<String>, Instant, bool)> = None;
+
             // Read new lines as they appear
             while !stop_flag_clone.load(Ordering::SeqCst) {
                 let mut line = String::new();
                 match reader.read_line(&mut line) {
                     Ok(0) => {
+                        // Warn once if a mod download has gone quiet
+                        if let Some((mod_id, last_progress, reported)) = mod_download.as_mut() {
+                            let idle = last_progress.elapsed();
+                            if !*reported && idle >= MOD_DOWNLOAD_STALL_TIMEOUT {
+                                *reported = true;
+                                log::warn!(
+                                    "  ⚠️ Server {} mod download stalled ({:?}) for {}s",
+                                    server_id,
+                                    mod_id,
+                                    idle.as_secs()
+                                );
+                                log_parser::emit_event(
+                                    &app_handle,
+                                    server_id,
+                                    None,
+                                    log_parser::LogEvent::ModDownloadStalled {
+                                        mod_id: mod_id.clone(),
+                                        idle_secs: idle.as_secs(),
+                                    },
+                                );
+                            }
+                        }
+
                         // No new data, wait a bit
                         std::thread::sleep(std::time::Duration::from_millis(100));
                     }
@@ -399,6 +661,41 @@ impl ProcessManager {
                                 },
                             );
 
+                            // Structured events (joins, tribe logs, errors) are additive
+                            let event = log_parser::emit_parsed(&app_handle, server_id, &line);
+
+                            match event {
+                                Some(log_parser::LogEvent::ModDownload {
+                                    mod_id, state, ..
+                                }) => {
+                                    if mod_download.is_none() {
+                                        log::info!("  📥 Server {} is downloading mods", server_id);
+                                        let _ = app_handle_status.emit(
+                                            "server-status-change",
+                                            ServerStatusEvent {
+                                                server_id,
+                                                status: "downloading-mods".to_string(),
+                                            },
+                                        );
+                                    }
+                                    if state == log_parser::ModDownloadState::Failed {
+                                        log::error!(
+                                            "  ❌ Server {} failed to download mod {:?}",
+                                            server_id,
+                                            mod_id
+                                        );
+                                    }
+                                    // Only an in-flight download can stall
+                                    let settled =
+                                        state != log_parser::ModDownloadState::Downloading;
+                                    mod_download = Some((mod_id, Instant::now(), settled));
+                                }
+                                Some(log_parser::LogEvent::ServerStarted) => {
+                                    mod_download = None;
+                                }
+                                _ => {}
+                            }
+
                             // CHECK FOR SERVER READY STATE
                             if !online_flag_clone.load(Ordering::SeqCst) {
                                 if line.contains("server has successfully started")
@@ -406,26 +703,33 @@ impl ProcessManager {
                                     || line.contains("Number of cores")
                                 // Sometimes appears late
                                 {
-                                    println!("  🎉 Server {} is ONLINE!", server_id);
+                                    log::info!("  🎉 Server {} is ONLINE!", server_id);
                                     online_flag_clone.store(true, Ordering::SeqCst);
-                                    let _ = app_handle_status.emit(
-                                        "server-status-change",
-                                        ServerStatusEvent {
+
+                                    // A stop issued while the server was booting wins over a late ready line
+                                    let became_online = app_handle_status
+                                        .try_state::<AppState>()
+                                        .map(|state| {
+                                            server_state::update(
+                                                &state,
+                                                server_id,
+                                                ServerStatus::Online,
+                                            )
+                                        })
+                                        .unwrap_or(false);
+                                    if became_online {
+                                        notifications::notify(
+                                            &app_handle_status,
                                             server_id,
-                                            status: "online".to_string(),
-                                        },
-                                    );
-
-                                    // Update database status to 'online'
-                                    if let Some(state) = app_handle_status.try_state::<AppState>() {
-                                        if let Ok(db) = state.db.lock() {
-                                            if let Ok(conn) = db.get_connection() {
-                                                let _ = conn.execute(
-                                                    "UPDATE servers SET status = 'online' WHERE id = ?1",
-                                                    [server_id],
-                                                );
-                                            }
-                                        }
+                                            NotificationEvent::ServerOnline,
+                                        );
+                                        let _ = app_handle_status.emit(
+                                            "server-status-change",
+                                            ServerStatusEvent {
+                                                server_id,
+                                                status: "online".to_string(),
+                                            },
+                                        );
                                     }
                                 }
                             }
@@ -452,30 +756,112 @@ impl ProcessManager {
         Ok(())
     }
 
-    /// Stop ARK server (Force)
-    pub fn stop_server(&self, server_id: i64) -> Result<()> {
+    /// Stop ARK server without RCON: ask the process tree to close, then force kill it
+    /// once the terminate timeout (settings::TERMINATE_TIMEOUT_SECS) runs out.
+    /// Waits on a blocking thread so async callers don't hold up a runtime worker.
+    pub async fn stop_server(&self, server_id: i64) -> Result<()> {
+        let pm = self.clone();
+        tauri::async_runtime::spawn_blocking(move || pm.stop_server_blocking(server_id))
+            .await
+            .map_err(|e| anyhow::anyhow!("Stop task failed: {}", e))?
+    }
+
+    /// `stop_server` for callers already on a blocking thread
+    pub fn stop_server_blocking(&self, server_id: i64) -> Result<()> {
+        let terminate_timeout =
+            Self::setting_duration(&self.app_handle, &settings::TERMINATE_TIMEOUT_SECS);
+        self.terminate(server_id, terminate_timeout)
+    }
+
+    /// Force kill the process tree right away, without asking it to close first
+    pub fn kill_server(&self, server_id: i64) -> Result<()> {
+        self.terminate(server_id, Duration::ZERO)
+    }
+
+    /// True while a tracked server is still booting or came up less than `grace` ago.
+    /// ARK rewrites its INIs during startup, so automation ignores that window.
+    pub fn in_startup_window(&self, server_id: i64, grace: Duration) -> bool {
+        self.processes
+            .lock()
+            .unwrap()
+            .get(&server_id)
+            .is_some_and(|proc| {
+                !proc.online_flag.load(Ordering::SeqCst) || proc.started_at.elapsed() < grace
+            })
+    }
+
+    /// Ids of every server process currently tracked
+    pub fn running_server_ids(&self) -> Vec<i64> {
+        let mut ids: Vec<i64> = self.processes.lock().unwrap().keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// True once the process started as `pid` is gone (or was reaped by is_running)
+    fn has_exited(&self, server_id: i64, pid: u32) -> bool {
         let mut processes = self.processes.lock().unwrap();
+        match processes.get_mut(&server_id) {
+            Some(proc) if proc.child.id() == pid => !matches!(proc.child.try_wait(), Ok(None)),
+            _ => true,
+        }
+    }
 
-        if let Some(mut server_proc) = processes.remove(&server_id) {
+    fn terminate(&self, server_id: i64, terminate_timeout: Duration) -> Result<()> {
+        // The process stays in the map until it has exited, so is_running keeps
+        // reporting it and a concurrent start can't launch a second instance on its
+        // ports. The lock is only held for short checks, never across the wait.
+        let pid = {
+            let mut processes = self.processes.lock().unwrap();
+            let Some(server_proc) = processes.get_mut(&server_id) else {
+                return Ok(());
+            };
             // Signal log watcher to stop
             server_proc.stop_flag.store(true, Ordering::SeqCst);
+            server_proc.stopping = true;
+            server_proc.child.id()
+        };
 
+        let closed =
+            Self::request_close(pid, terminate_timeout, || self.has_exited(server_id, pid));
+        if closed {
+            log::info!("  ✅ Server {} closed on request", server_id);
+        } else if !terminate_timeout.is_zero() {
+            log::warn!(
+                "  ⚠️ Server {} still running after {}s, force killing...",
+                server_id,
+                terminate_timeout.as_secs()
+            );
+        }
+
+        if !closed {
             // Force kill the process tree on Windows
             #[cfg(target_os = "windows")]
             {
-                let pid = server_proc.child.id();
                 let _ = Command::new("taskkill")
                     .args(["/F", "/T", "/PID", &pid.to_string()])
                     .creation_flags(CREATE_NO_WINDOW)
                     .output();
             }
+        }
 
-            // Fallback
-            let _ = server_proc.child.kill();
+        // Reap it, unless is_running already did (or a new instance took the slot)
+        let server_proc = {
+            let mut processes = self.processes.lock().unwrap();
+            match processes.get(&server_id) {
+                Some(proc) if proc.child.id() == pid => processes.remove(&server_id),
+                _ => None,
+            }
+        };
+        if let Some(mut server_proc) = server_proc {
+            if !closed {
+                // Fallback
+                let _ = server_proc.child.kill();
+            }
             let _ = server_proc.child.wait();
 
             // Emit stopped status
             self.emit_status_change(server_id, "stopped");
+            Self::end_player_sessions(&self.app_handle, server_id);
         }
         Ok(())
     }
@@ -489,7 +875,7 @@ impl ProcessManager {
         port: u16,
         password: &str,
     ) -> Result<()> {
-        println!(
+        log::info!(
             "🛡️ Intelligent Mode: Attempting graceful shutdown for server {}...",
             server_id
         );
@@ -497,27 +883,30 @@ impl ProcessManager {
         // 1. Connect and send RCON commands
         if let Ok(resp) = rcon.connect(server_id, address, port, password).await {
             if resp.success {
-                println!("  📡 RCON connected, sending SaveWorld...");
+                log::info!("  📡 RCON connected, sending SaveWorld...");
                 let _ = rcon.save_world(server_id).await;
 
-                std::thread::sleep(std::time::Duration::from_secs(2));
+                tokio::time::sleep(Duration::from_secs(2)).await;
 
-                println!("  📡 Sending DoExit/Quit...");
+                log::info!("  📡 Sending DoExit/Quit...");
                 let _ = rcon.send_command(server_id, "DoExit").await;
 
                 // Wait for process to exit naturally
-                let mut attempts = 0;
-                while self.is_running(server_id) && attempts < 15 {
-                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-                    attempts += 1;
+                let timeout = Self::setting_duration(
+                    &self.app_handle,
+                    &settings::GRACEFUL_SHUTDOWN_TIMEOUT_SECS,
+                );
+                let deadline = Instant::now() + timeout;
+                while self.is_running(server_id) && Instant::now() < deadline {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
                 }
             }
         }
 
-        // 2. If still running, force stop
+        // 2. If still running, escalate: terminate request, then force kill
         if self.is_running(server_id) {
-            println!("  ⚠️ Graceful shutdown timed out or failed, force stopping...");
-            self.stop_server(server_id)?;
+            log::warn!("  ⚠️ Graceful shutdown timed out or failed, stopping the process...");
+            self.stop_server(server_id).await?;
         }
 
         Ok(())
@@ -530,18 +919,19 @@ impl ProcessManager {
         if let Some(server_proc) = processes.get_mut(&server_id) {
             match server_proc.child.try_wait() {
                 Ok(Some(status)) => {
-                    println!("  ⚠️ Server {} exited with status: {:?}", server_id, status);
+                    log::warn!("  ⚠️ Server {} exited with status: {:?}", server_id, status);
                     server_proc.stop_flag.store(true, Ordering::SeqCst);
                     processes.remove(&server_id);
 
                     // Emit crash/stop event
                     self.emit_status_change(server_id, "stopped"); // or 'crashed' if non-zero?
+                    Self::end_player_sessions(&self.app_handle, server_id);
 
                     false
                 }
                 Ok(None) => true,
                 Err(e) => {
-                    println!("  ❌ Server {} error checking status: {:?}", server_id, e);
+                    log::error!("  ❌ Server {} error checking status: {:?}", server_id, e);
                     false
                 }
             }
@@ -550,50 +940,64 @@ impl ProcessManager {
         }
     }
 
-    /// Restart server
-    pub fn restart_server(
+    /// Restart server, waiting for the stop and settle delay without blocking a runtime worker
+    pub async fn restart_server(
         &self,
         server_id: i64,
-        server_type: &str,
-        install_path: &PathBuf,
-        map_name: &str,
-        session_name: &str,
-        game_port: u16,
-        query_port: u16,
-        rcon_port: u16,
-        max_players: i32,
-        server_password: Option<&str>,
-        admin_password: &str,
-        ip_address: Option<&str>,
-        cluster_id: Option<&str>,
-        cluster_dir: Option<&str>,
-        mods: Option<&[String]>,
-        custom_args: Option<&str>,
+        install_path: &Path,
+        use_asa_api: bool,
+        options: &launch_args::LaunchOptions<'_>,
     ) -> Result<()> {
         if self.is_running(server_id) {
-            self.stop_server(server_id)?;
-        }
-
-        std::thread::sleep(std::time::Duration::from_secs(2));
-
-        self.start_server(
-            server_id,
-            server_type,
-            install_path,
-            map_name,
-            session_name,
-            game_port,
-            query_port,
-            rcon_port,
-            max_players,
-            server_password,
-            admin_password,
-            ip_address,
-            cluster_id,
-            cluster_dir,
-            mods,
-            custom_args,
-        )
+            self.stop_server(server_id).await?;
+        }
+
+        tokio::time::sleep(self.stop_settle_delay()).await;
+
+        self.start_server(server_id, install_path, use_asa_api, options)
+    }
+
+    /// Queue one line for the server's console input. Only works for servers launched
+    /// while settings::CONSOLE_STDIN_ENABLED was on; everything else has a null stdin.
+    /// With AsaApi the pipe belongs to the loader, which may not forward it.
+    pub fn send_console_command(&self, server_id: i64, command: &str) -> Result<()> {
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(anyhow::anyhow!("Command is empty"));
+        }
+        if command.contains(['\r', '\n']) {
+            return Err(anyhow::anyhow!("Send one command per line"));
+        }
+
+        let mut processes = self.processes.lock().unwrap();
+        let server_proc = processes
+            .get_mut(&server_id)
+            .ok_or_else(|| anyhow::anyhow!("Server is not running"))?;
+        let input = server_proc.stdin.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Server was started without console input. Enable console input in Settings and restart the server."
+            )
+        })?;
+
+        if input.closed.load(Ordering::SeqCst) {
+            // The pipe is gone (process closed it or exited); stop offering it
+            server_proc.stdin = None;
+            return Err(anyhow::anyhow!("Server console input is closed"));
+        }
+        match input.sender.try_send(command.to_string()) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                return Err(anyhow::anyhow!(
+                    "Server is not reading its console input; try again later or use RCON"
+                ));
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                server_proc.stdin = None;
+                return Err(anyhow::anyhow!("Server console input is closed"));
+            }
+        }
+        log::info!("  ⌨️ Server {} console: {}", server_id, command);
+        Ok(())
     }
 
     /// Show the hidden server window
@@ -611,3 +1015,201 @@ impl ProcessManager {
         }
     }
 }
+
+/// Hand `stdin` to a writer thread. The thread exits when the queue is dropped
+/// (process removed from the map) or a write fails (process gone).
+fn spawn_console_writer(server_id: i64, mut stdin: ChildStdin) -> ConsoleInput {
+    let (sender, receiver) = mpsc::sync_channel::<String>(CONSOLE_QUEUE_LEN);
+    let closed = Arc::new(AtomicBool::new(false));
+    let closed_flag = closed.clone();
+
+    std::thread::spawn(move || {
+        for command in receiver {
+            if let Err(e) = writeln!(stdin, "{}\r", command).and_then(|_| stdin.flush()) {
+                log::warn!(
+                    "  ⚠️ Failed to write to server {} console: {}",
+                    server_id,
+                    e
+                );
+                closed_flag.store(true, Ordering::SeqCst);
+                break;
+            }
+        }
+    });
+
+    ConsoleInput { sender, closed }
+}
+
+/// Forward one of the server's piped console streams into the "server_log" stream,
+/// tagged [stdout]/[stderr] so it can be told apart from the ShooterGame.log tail.
+/// Reads until the pipe closes, which also keeps it drained so the server never
+/// blocks on a full pipe. The newest lines are kept in `early_output`.
+fn pipe_output(
+    app_handle: &AppHandle,
+    server_id: i64,
+    stream: impl Read + Send + 'static,
+    is_stderr: bool,
+    early_output: &Arc<Mutex<Vec<String>>>,
+    tail_following: &Arc<AtomicBool>,
+) {
+    let app_handle = app_handle.clone();
+    let early_output = early_output.clone();
+    let tail_following = tail_following.clone();
+    std::thread::spawn(move || {
+        forward_console(stream, is_stderr, &early_output, &tail_following, |line| {
+            let _ = app_handle.emit(
+                "server_log",
+                ServerLogEvent {
+                    server_id,
+                    line,
+                    is_stderr,
+                },
+            );
+        });
+    });
+}
+
+/// The read loop behind pipe_output. Once `tail_following` is set, stdout only
+/// repeats what the ShooterGame.log tail already shows, so its lines are read
+/// (and kept in `early_output`) but no longer emitted. stderr is always emitted.
+fn forward_console(
+    stream: impl Read,
+    is_stderr: bool,
+    early_output: &Mutex<Vec<String>>,
+    tail_following: &AtomicBool,
+    mut emit: impl FnMut(String),
+) {
+    let tag = if is_stderr { "[stderr]" } else { "[stdout]" };
+    let mut reader = BufReader::new(stream);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let Some(line) = console_line(&buf) else {
+            continue;
+        };
+        let line = format!("{} {}", tag, line);
+
+        {
+            let mut early = crate::db::lock_or_recover(early_output, "early output");
+            early.push(line.clone());
+            let excess = early.len().saturating_sub(EARLY_OUTPUT_LINES);
+            early.drain(..excess);
+        }
+        if is_stderr || !tail_following.load(Ordering::SeqCst) {
+            emit(line);
+        }
+    }
+}
+
+/// One raw line of console output as text, or None when it is blank. Windows tools
+/// sometimes write UTF-16 to a redirected handle, so NULs are dropped.
+fn console_line(raw: &[u8]) -> Option<String> {
+    let text: String = String::from_utf8_lossy(raw)
+        .chars()
+        .filter(|c| *c != '\0')
+        .collect();
+    let text = text.trim_end();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Read the last `count` non-empty lines of a log file (from its final 64KB)
+fn read_last_lines(path: &PathBuf, count: usize) -> Vec<String> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let mut reader = BufReader::new(file);
+    let size = reader.seek(SeekFrom::End(0)).unwrap_or(0);
+    let _ = reader.seek(SeekFrom::Start(size.saturating_sub(65536)));
+
+    let lines: Vec<String> = reader
+        .lines()
+        .map_while(|l| l.ok())
+        .filter(|l| !l.trim().is_empty())
+        .collect();
+
+    lines[lines.len().saturating_sub(count)..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn console_line_trims_and_drops_utf16_nuls() {
+        assert_eq!(
+            console_line(b"Missing DLL\r\n").as_deref(),
+            Some("Missing DLL")
+        );
+        assert_eq!(console_line(b"O\0K\0\r\0\n").as_deref(), Some("OK"));
+        assert_eq!(console_line(b"  \r\n"), None);
+    }
+
+    #[test]
+    fn stdout_goes_quiet_once_the_log_tail_follows() {
+        let output = b"Starting\r\nLoading map\r\n".repeat(200);
+        let early_output = Mutex::new(Vec::new());
+        let tail_following = AtomicBool::new(false);
+
+        let mut emitted = Vec::new();
+        forward_console(
+            &b"Starting\n"[..],
+            false,
+            &early_output,
+            &tail_following,
+            |l| emitted.push(l),
+        );
+        assert_eq!(emitted, ["[stdout] Starting"]);
+
+        tail_following.store(true, Ordering::SeqCst);
+        emitted.clear();
+        forward_console(&output[..], false, &early_output, &tail_following, |l| {
+            emitted.push(l)
+        });
+        assert!(emitted.is_empty());
+        // The pipe was still read to the end
+        let early = early_output.lock().unwrap();
+        assert_eq!(early.len(), EARLY_OUTPUT_LINES);
+        assert_eq!(early.last().unwrap(), "[stdout] Loading map");
+        drop(early);
+
+        forward_console(
+            &b"Missing DLL\n"[..],
+            true,
+            &early_output,
+            &tail_following,
+            |l| emitted.push(l),
+        );
+        assert_eq!(emitted, ["[stderr] Missing DLL"]);
+    }
+
+    /// A server that never reads stdin fills the pipe; sends must fail fast
+    /// instead of blocking the caller (and the process map) on the write
+    #[cfg(unix)]
+    #[test]
+    fn console_writer_never_blocks_the_sender() {
+        let mut child = Command::new("sleep")
+            .arg("5")
+            .stdin(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let input = spawn_console_writer(-638, child.stdin.take().unwrap());
+
+        let line = "x".repeat(64 * 1024);
+        let started = Instant::now();
+        let refused = (0..CONSOLE_QUEUE_LEN + 16).any(|_| {
+            matches!(
+                input.sender.try_send(line.clone()),
+                Err(TrySendError::Full(_))
+            )
+        });
+        assert!(refused);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
@@ -1,24 +1,63 @@
 // RCON Service for ASA Server Manager
 // Handles remote console connections to ARK: Survival Ascended servers
 
-use crate::models::{RconPlayer, RconResponse};
-use rcon::Connection;
+use crate::models::{PlayerEntry, RconPlayer, RconResponse, RconTestResult};
+use crate::services::rcon_protocol::{ConnectError, RconClient};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::net::TcpStream;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
+/// How long a credentials test waits for the connect and for the test command
+const TEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Default wait for a connect or a command reply; see settings::RCON_TIMEOUT_SECS
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+type SharedConnection = Arc<Mutex<RconClient>>;
+
 pub struct RconService {
-    connections: Arc<Mutex<HashMap<i64, Connection<TcpStream>>>>,
+    /// One lock per server, so a command to a laggy server only queues commands
+    /// to that same server. The map lock is held just long enough to look up,
+    /// add or remove an entry, never across a network call.
+    connections: std::sync::Mutex<HashMap<i64, SharedConnection>>,
+    /// A server that accepts the socket but never answers would otherwise block
+    /// its callers forever (milliseconds, adjustable while running)
+    command_timeout_ms: AtomicU64,
 }
 
 impl RconService {
     pub fn new() -> Self {
+        Self::with_timeout(DEFAULT_COMMAND_TIMEOUT)
+    }
+
+    pub fn with_timeout(command_timeout: Duration) -> Self {
         Self {
-            connections: Arc::new(Mutex::new(HashMap::new())),
+            connections: std::sync::Mutex::new(HashMap::new()),
+            command_timeout_ms: AtomicU64::new(command_timeout.as_millis() as u64),
         }
     }
 
+    pub fn set_command_timeout(&self, command_timeout: Duration) {
+        self.command_timeout_ms
+            .store(command_timeout.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn command_timeout(&self) -> Duration {
+        Duration::from_millis(self.command_timeout_ms.load(Ordering::Relaxed))
+    }
+
+    fn slots(&self) -> std::sync::MutexGuard<'_, HashMap<i64, SharedConnection>> {
+        // The map is always left consistent, so a panic elsewhere doesn't matter
+        self.connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn connection(&self, server_id: i64) -> Option<SharedConnection> {
+        self.slots().get(&server_id).cloned()
+    }
+
     /// Connect to a server's RCON
     pub async fn connect(
         &self,
@@ -28,28 +67,29 @@ impl RconService {
         password: &str,
     ) -> Result<RconResponse, String> {
         let addr = format!("{}:{}", address, port);
+        let timeout = self.command_timeout();
 
-        match Connection::<TcpStream>::builder()
-            .connect(&addr, password)
-            .await
-        {
-            Ok(conn) => {
-                let mut connections = self.connections.lock().await;
-                connections.insert(server_id, conn);
+        match tokio::time::timeout(timeout, RconClient::connect(&addr, password)).await {
+            Err(_) => Err(format!(
+                "Timed out connecting to RCON at {} after {}s",
+                addr,
+                timeout.as_secs_f32()
+            )),
+            Ok(Err(e)) => Err(format!("Failed to connect to RCON: {}", e)),
+            Ok(Ok(conn)) => {
+                self.slots().insert(server_id, Arc::new(Mutex::new(conn)));
                 Ok(RconResponse {
                     success: true,
                     message: format!("Connected to RCON at {}", addr),
                     data: None,
                 })
             }
-            Err(e) => Err(format!("Failed to connect to RCON: {}", e)),
         }
     }
 
     /// Disconnect from a server's RCON
     pub async fn disconnect(&self, server_id: i64) -> Result<RconResponse, String> {
-        let mut connections = self.connections.lock().await;
-        if connections.remove(&server_id).is_some() {
+        if self.slots().remove(&server_id).is_some() {
             Ok(RconResponse {
                 success: true,
                 message: "Disconnected from RCON".to_string(),
@@ -60,38 +100,77 @@ impl RconService {
         }
     }
 
-    /// Send an RCON command
+    /// Send an RCON command. Only commands to the same server wait on each other.
     pub async fn send_command(
         &self,
         server_id: i64,
         command: &str,
     ) -> Result<RconResponse, String> {
-        let mut connections = self.connections.lock().await;
+        let Some(shared) = self.connection(server_id) else {
+            return Err("No active RCON connection for this server".to_string());
+        };
+        let timeout = self.command_timeout();
+        let result = {
+            let mut conn = shared.lock().await;
+            tokio::time::timeout(timeout, conn.cmd(command)).await
+        };
 
-        if let Some(conn) = connections.get_mut(&server_id) {
-            match conn.cmd(command).await {
-                Ok(response) => Ok(RconResponse {
-                    success: true,
-                    message: "Command executed".to_string(),
-                    data: Some(response),
-                }),
-                Err(e) => Err(format!("Failed to execute command: {}", e)),
+        match result {
+            Ok(Ok(response)) => Ok(RconResponse {
+                success: true,
+                message: "Command executed".to_string(),
+                data: Some(response),
+            }),
+            Ok(Err(e)) => {
+                // Read/write failures leave the socket unusable
+                self.forget(server_id, &shared);
+                Err(format!("Failed to execute command: {}", e))
+            }
+            Err(_) => {
+                // The server is stuck and the stream may be mid-packet, so the
+                // next ensure_connected starts over
+                self.forget(server_id, &shared);
+                Err(format!(
+                    "RCON command timed out after {}s, the connection was closed",
+                    timeout.as_secs_f32()
+                ))
             }
-        } else {
-            Err("No active RCON connection for this server".to_string())
+        }
+    }
+
+    /// Remove `shared` from the map unless it was already replaced by a newer connection
+    fn forget(&self, server_id: i64, shared: &SharedConnection) {
+        let mut slots = self.slots();
+        if slots
+            .get(&server_id)
+            .is_some_and(|current| Arc::ptr_eq(current, shared))
+        {
+            slots.remove(&server_id);
         }
     }
 
     /// Get list of online players
     pub async fn get_players(&self, server_id: i64) -> Result<Vec<RconPlayer>, String> {
+        let players = self.get_player_entries(server_id).await?;
+
+        Ok(players
+            .into_iter()
+            .map(|p| RconPlayer {
+                id: p.index,
+                name: p.name,
+                steam_id: p.net_id,
+            })
+            .collect())
+    }
+
+    /// Get online players with their Steam and/or EOS ids resolved
+    pub async fn get_player_entries(&self, server_id: i64) -> Result<Vec<PlayerEntry>, String> {
         let response = self.send_command(server_id, "ListPlayers").await?;
 
-        if let Some(data) = response.data {
-            let players = parse_player_list(&data);
-            Ok(players)
-        } else {
-            Ok(vec![])
-        }
+        Ok(response
+            .data
+            .map(|data| parse_player_entries(&data))
+            .unwrap_or_default())
     }
 
     /// Broadcast a message to all players
@@ -130,6 +209,26 @@ impl RconService {
         self.send_command(server_id, &command).await
     }
 
+    /// Add a player to the exclusive join list (whitelist)
+    pub async fn allow_player_to_join(
+        &self,
+        server_id: i64,
+        steam_id: &str,
+    ) -> Result<RconResponse, String> {
+        let command = format!("AllowPlayerToJoinNoCheck {}", steam_id);
+        self.send_command(server_id, &command).await
+    }
+
+    /// Remove a player from the exclusive join list (whitelist)
+    pub async fn disallow_player_to_join(
+        &self,
+        server_id: i64,
+        steam_id: &str,
+    ) -> Result<RconResponse, String> {
+        let command = format!("DisallowPlayerNoCheck {}", steam_id);
+        self.send_command(server_id, &command).await
+    }
+
     /// Save the world
     pub async fn save_world(&self, server_id: i64) -> Result<RconResponse, String> {
         self.send_command(server_id, "SaveWorld").await
@@ -140,13 +239,14 @@ impl RconService {
         self.send_command(server_id, "DestroyWildDinos").await
     }
 
-    /// Set time of day
+    /// Set time of day (24h clock)
     pub async fn set_time(
         &self,
         server_id: i64,
         hour: u8,
         minute: u8,
     ) -> Result<RconResponse, String> {
+        validate_time_of_day(hour, minute)?;
         let command = format!("SetTimeOfDay {:02}:{:02}", hour, minute);
         self.send_command(server_id, &command).await
     }
@@ -164,41 +264,194 @@ impl RconService {
 
     /// Check if connected to a server
     pub async fn is_connected(&self, server_id: i64) -> bool {
-        let connections = self.connections.lock().await;
-        connections.contains_key(&server_id)
+        self.slots().contains_key(&server_id)
+    }
+}
+
+/// Check RCON credentials with a throwaway connection: connect, run ListPlayers,
+/// then drop the connection. Nothing is added to any RconService.
+pub async fn test_connection(address: &str, port: u16, password: &str) -> RconTestResult {
+    let addr = format!("{}:{}", address, port);
+    let failed = |failure: &str, message: String| RconTestResult {
+        success: false,
+        failure: Some(failure.to_string()),
+        message,
+        response: None,
+    };
+
+    let mut conn = match tokio::time::timeout(TEST_TIMEOUT, RconClient::connect(&addr, password))
+        .await
+    {
+        Err(_) => {
+            return failed(
+                "timeout",
+                format!(
+                    "No answer from {} within {}s, check the address, port and firewall",
+                    addr,
+                    TEST_TIMEOUT.as_secs()
+                ),
+            )
+        }
+        Ok(Err(e)) => {
+            let failure = classify_rcon_error(&e);
+            let message = match failure {
+                "refused" => format!(
+                    "Connection refused by {}, is the server running with RCON enabled on this port?",
+                    addr
+                ),
+                "auth_failed" => "Connected, but the admin password was rejected".to_string(),
+                "timeout" => format!("Connection to {} timed out", addr),
+                _ => format!("Could not reach {}: {}", addr, e),
+            };
+            return failed(failure, message);
+        }
+        Ok(Ok(conn)) => conn,
+    };
+
+    match tokio::time::timeout(TEST_TIMEOUT, conn.cmd("ListPlayers")).await {
+        Err(_) => failed(
+            "timeout",
+            "Authenticated, but the server did not answer ListPlayers".to_string(),
+        ),
+        Ok(Err(e)) => failed(
+            "command_failed",
+            format!("Authenticated, but ListPlayers failed: {}", e),
+        ),
+        Ok(Ok(response)) => RconTestResult {
+            success: true,
+            failure: None,
+            message: format!("RCON at {} is working", addr),
+            response: Some(response),
+        },
     }
 }
 
-/// Parse the ListPlayers response into player objects
-fn parse_player_list(data: &str) -> Vec<RconPlayer> {
+/// Map a connect error onto the failure kinds reported by test_connection
+fn classify_rcon_error(error: &ConnectError) -> &'static str {
+    match error {
+        ConnectError::Auth => "auth_failed",
+        ConnectError::Io(e) => classify_io_error(e.kind()),
+        ConnectError::Protocol(_) => "unreachable",
+    }
+}
+
+fn classify_io_error(kind: std::io::ErrorKind) -> &'static str {
+    match kind {
+        std::io::ErrorKind::ConnectionRefused => "refused",
+        std::io::ErrorKind::TimedOut => "timeout",
+        _ => "unreachable",
+    }
+}
+
+/// Parse the ListPlayers response into player entries.
+/// Format: "0. PlayerName, <net id>" where the net id is an EOS id on ASA
+/// or a 17-digit Steam ID on legacy/Steam-auth servers.
+fn parse_player_entries(data: &str) -> Vec<PlayerEntry> {
     let mut players = Vec::new();
 
-    // Format: "0. PlayerName, SteamID"
     for line in data.lines() {
         let line = line.trim();
         if line.is_empty() || line == "No Players Connected" {
             continue;
         }
 
-        // Try to parse the player line
-        if let Some(dot_pos) = line.find('.') {
-            let id_str = &line[..dot_pos];
-            let rest = &line[dot_pos + 1..].trim();
+        let Some((index, rest)) = line.split_once('.') else {
+            continue;
+        };
+        let Ok(index) = index.trim().parse::<i64>() else {
+            continue;
+        };
+
+        // Names may contain commas, the id never does
+        let Some((name, net_id)) = rest.rsplit_once(',') else {
+            continue;
+        };
+        let net_id = net_id.trim().to_string();
+        if net_id.is_empty() {
+            continue;
+        }
 
-            if let Ok(id) = id_str.trim().parse::<i64>() {
-                // Split by comma to get name and steam id
-                let parts: Vec<&str> = rest.splitn(2, ',').collect();
-                if parts.len() >= 2 {
-                    let name = parts[0].trim().to_string();
-                    let steam_id = parts[1].trim().to_string();
+        let (steam_id, eos_id) = classify_net_id(&net_id);
+        players.push(PlayerEntry {
+            index,
+            name: name.trim().to_string(),
+            net_id,
+            steam_id,
+            eos_id,
+        });
+    }
 
-                    players.push(RconPlayer { id, name, steam_id });
-                }
+    players
+}
+
+/// Split a UE net id into (steam_id, eos_id). Steam IDs are 17-digit numbers
+/// starting with 7656; EOS product user ids are 32 hex characters.
+fn classify_net_id(net_id: &str) -> (Option<String>, Option<String>) {
+    let mut steam_id = None;
+    let mut eos_id = None;
+
+    // Some servers report "eos|steam" pairs, so check each part
+    for part in net_id.split(['|', ' ']).filter(|p| !p.is_empty()) {
+        if part.len() == 17 && part.starts_with("7656") && part.bytes().all(|b| b.is_ascii_digit())
+        {
+            steam_id = Some(part.to_string());
+        } else if part.len() == 32 && part.bytes().all(|b| b.is_ascii_hexdigit()) {
+            eos_id = Some(part.to_lowercase());
+        }
+    }
+
+    (steam_id, eos_id)
+}
+
+pub fn validate_time_of_day(hour: u8, minute: u8) -> Result<(), String> {
+    if hour > 23 || minute > 59 {
+        return Err(format!(
+            "Invalid time {:02}:{:02}, expected 00:00-23:59",
+            hour, minute
+        ));
+    }
+    Ok(())
+}
+
+/// Commands whose arguments are a password ("cheat"/"admincheat" prefixes allowed)
+const PASSWORD_COMMANDS: &[&str] = &["enablecheats", "setcheatpassword"];
+
+/// Mask passwords before a command is persisted or logged: arguments of
+/// password-taking commands, values of `*password*=...` pairs, and any of the
+/// known `secrets` (e.g. the server's admin password) appearing verbatim.
+pub fn mask_sensitive(command: &str, secrets: &[&str]) -> String {
+    const MASK: &str = "********";
+
+    let mut words: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+    let mut name_index = 0;
+    while name_index < words.len()
+        && matches!(
+            words[name_index].to_lowercase().as_str(),
+            "cheat" | "admincheat"
+        )
+    {
+        name_index += 1;
+    }
+    let masks_args = words
+        .get(name_index)
+        .map(|w| PASSWORD_COMMANDS.contains(&w.to_lowercase().as_str()))
+        .unwrap_or(false);
+
+    for (i, word) in words.iter_mut().enumerate() {
+        if masks_args && i > name_index {
+            *word = MASK.to_string();
+        } else if let Some((key, _)) = word.split_once('=') {
+            if key.to_lowercase().contains("password") {
+                *word = format!("{}={}", key, MASK);
             }
         }
     }
 
-    players
+    let mut masked = words.join(" ");
+    for secret in secrets.iter().filter(|s| !s.is_empty()) {
+        masked = masked.replace(secret, MASK);
+    }
+    masked
 }
 
 impl Default for RconService {
@@ -206,3 +459,243 @@ impl Default for RconService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_player_entries() {
+        let data = "0. Survivor, 0002a1b2c3d4e5f6a7b8c9d0e1f2a3b4\n\
+                    1. Smith, John, 76561198000000001\n\
+                    \n";
+        let players = parse_player_entries(data);
+        assert_eq!(players.len(), 2);
+
+        assert_eq!(players[0].name, "Survivor");
+        assert_eq!(
+            players[0].eos_id.as_deref(),
+            Some("0002a1b2c3d4e5f6a7b8c9d0e1f2a3b4")
+        );
+        assert_eq!(players[0].steam_id, None);
+        assert_eq!(players[0].player_id(), "0002a1b2c3d4e5f6a7b8c9d0e1f2a3b4");
+
+        assert_eq!(players[1].index, 1);
+        assert_eq!(players[1].name, "Smith, John");
+        assert_eq!(players[1].steam_id.as_deref(), Some("76561198000000001"));
+        assert_eq!(players[1].eos_id, None);
+    }
+
+    #[test]
+    fn test_no_players() {
+        assert!(parse_player_entries("No Players Connected").is_empty());
+    }
+
+    #[test]
+    fn test_classify_rcon_error() {
+        assert_eq!(classify_rcon_error(&ConnectError::Auth), "auth_failed");
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert_eq!(classify_rcon_error(&ConnectError::Io(refused)), "refused");
+        assert_eq!(
+            classify_rcon_error(&ConnectError::Protocol("closed".to_string())),
+            "unreachable"
+        );
+        assert_eq!(classify_io_error(std::io::ErrorKind::TimedOut), "timeout");
+        assert_eq!(
+            classify_io_error(std::io::ErrorKind::AddrNotAvailable),
+            "unreachable"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connection_refused_is_reported() {
+        // Bind then drop to get a local port nothing is listening on
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let result = test_connection("127.0.0.1", port, "secret").await;
+        assert!(!result.success);
+        assert_eq!(result.failure.as_deref(), Some("refused"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_password_is_reported() {
+        use crate::services::rcon_protocol::encode_packet;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut len = [0u8; 4];
+            socket.read_exact(&mut len).await.unwrap();
+            let mut packet = vec![0u8; i32::from_le_bytes(len) as usize];
+            socket.read_exact(&mut packet).await.unwrap();
+            // SERVERDATA_AUTH_RESPONSE with id -1 means a wrong password
+            socket.write_all(&encode_packet(-1, 2, "")).await.unwrap();
+        });
+
+        let result = test_connection("127.0.0.1", port, "wrong").await;
+        assert!(!result.success);
+        assert_eq!(result.failure.as_deref(), Some("auth_failed"));
+    }
+
+    /// Fake server that accepts the socket, answers the auth packet when
+    /// `answer_auth` is set, and then never replies to anything
+    async fn silent_server(answer_auth: bool) -> u16 {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            if answer_auth {
+                let mut len = [0u8; 4];
+                socket.read_exact(&mut len).await.unwrap();
+                let mut packet = vec![0u8; i32::from_le_bytes(len) as usize];
+                socket.read_exact(&mut packet).await.unwrap();
+
+                // SERVERDATA_AUTH_RESPONSE echoing the request id, empty body
+                let mut reply = 10i32.to_le_bytes().to_vec();
+                reply.extend_from_slice(&packet[0..4]);
+                reply.extend_from_slice(&2i32.to_le_bytes());
+                reply.extend_from_slice(&[0, 0]);
+                socket.write_all(&reply).await.unwrap();
+            }
+            let mut sink = [0u8; 1024];
+            while matches!(socket.read(&mut sink).await, Ok(n) if n > 0) {}
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_connect_times_out_when_auth_is_never_answered() {
+        let port = silent_server(false).await;
+        let service = RconService::with_timeout(Duration::from_millis(300));
+
+        let error = service
+            .connect(1, "127.0.0.1", port, "secret")
+            .await
+            .unwrap_err();
+        assert!(error.contains("Timed out"), "{}", error);
+        assert!(!service.is_connected(1).await);
+    }
+
+    #[tokio::test]
+    async fn test_command_times_out_when_server_stops_answering() {
+        let port = silent_server(true).await;
+        let service = RconService::with_timeout(Duration::from_millis(300));
+        service
+            .connect(1, "127.0.0.1", port, "secret")
+            .await
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        let error = service.send_command(1, "ListPlayers").await.unwrap_err();
+        assert!(error.contains("timed out"), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!service.is_connected(1).await);
+    }
+
+    /// ListPlayers on a full server arrives in several packets
+    #[tokio::test]
+    async fn test_split_player_list_is_not_truncated() {
+        use crate::services::rcon_protocol::{encode_packet, SERVERDATA_RESPONSE_VALUE};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        async fn read_packet(socket: &mut tokio::net::TcpStream) -> Vec<u8> {
+            let mut len = [0u8; 4];
+            socket.read_exact(&mut len).await.unwrap();
+            let mut packet = vec![0u8; i32::from_le_bytes(len) as usize];
+            socket.read_exact(&mut packet).await.unwrap();
+            packet
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let auth = read_packet(&mut socket).await;
+            let auth_id = i32::from_le_bytes(auth[0..4].try_into().unwrap());
+            socket
+                .write_all(&encode_packet(auth_id, 2, ""))
+                .await
+                .unwrap();
+
+            let command = read_packet(&mut socket).await;
+            let marker = read_packet(&mut socket).await;
+            let command_id = i32::from_le_bytes(command[0..4].try_into().unwrap());
+            let marker_id = i32::from_le_bytes(marker[0..4].try_into().unwrap());
+            let mut out = Vec::new();
+            for chunk in (0..70).collect::<Vec<_>>().chunks(30) {
+                let body: String = chunk
+                    .iter()
+                    .map(|i| format!("{}. Survivor {}, 7656119800000{:04}\n", i, i, i))
+                    .collect();
+                out.extend(encode_packet(command_id, SERVERDATA_RESPONSE_VALUE, &body));
+            }
+            out.extend(encode_packet(marker_id, SERVERDATA_RESPONSE_VALUE, ""));
+            socket.write_all(&out).await.unwrap();
+            let mut sink = [0u8; 1024];
+            while matches!(socket.read(&mut sink).await, Ok(n) if n > 0) {}
+        });
+
+        let service = RconService::with_timeout(Duration::from_secs(2));
+        service
+            .connect(1, "127.0.0.1", port, "secret")
+            .await
+            .unwrap();
+        let players = service.get_player_entries(1).await.unwrap();
+        assert_eq!(players.len(), 70);
+        assert_eq!(players[69].name, "Survivor 69");
+    }
+
+    #[tokio::test]
+    async fn test_slow_server_does_not_block_other_servers() {
+        let silent_port = silent_server(true).await;
+        let other_port = silent_server(true).await;
+        let service = Arc::new(RconService::with_timeout(Duration::from_secs(2)));
+        service
+            .connect(1, "127.0.0.1", silent_port, "secret")
+            .await
+            .unwrap();
+        service
+            .connect(2, "127.0.0.1", other_port, "secret")
+            .await
+            .unwrap();
+
+        // Server 1's command holds its connection until the timeout
+        let stuck = {
+            let service = service.clone();
+            tokio::spawn(async move { service.send_command(1, "ListPlayers").await })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let started = std::time::Instant::now();
+        service.disconnect(2).await.unwrap();
+        assert!(!service.is_connected(2).await);
+        assert!(service.is_connected(1).await);
+        assert!(started.elapsed() < Duration::from_millis(500));
+
+        assert!(stuck.await.unwrap().is_err());
+    }
+
+    #[test]
+    fn test_mask_sensitive() {
+        assert_eq!(
+            mask_sensitive("cheat EnableCheats hunter2", &[]),
+            "cheat EnableCheats ********"
+        );
+        assert_eq!(
+            mask_sensitive("SetServerOption ServerAdminPassword=hunter2", &[]),
+            "SetServerOption ServerAdminPassword=********"
+        );
+        assert_eq!(
+            mask_sensitive("Broadcast my pass is hunter2", &["hunter2"]),
+            "Broadcast my pass is ********"
+        );
+        assert_eq!(mask_sensitive("SaveWorld", &["hunter2"]), "SaveWorld");
+    }
+}
@@ -0,0 +1,207 @@
+// Curated ASA admin commands for console autocomplete and tooltips
+// Static data only; nothing here talks to a server.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RconCommandCategory {
+    Player,
+    World,
+    Dino,
+    Server,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RconCommandInfo {
+    pub name: &'static str,
+    /// Argument hint shown after the name, e.g. "<hh:mm>"
+    pub args: &'static str,
+    pub description: &'static str,
+    pub category: RconCommandCategory,
+}
+
+const fn cmd(
+    category: RconCommandCategory,
+    name: &'static str,
+    args: &'static str,
+    description: &'static str,
+) -> RconCommandInfo {
+    RconCommandInfo {
+        name,
+        args,
+        description,
+        category,
+    }
+}
+
+use RconCommandCategory::{Dino, Player, Server, World};
+
+pub const RCON_COMMANDS: &[RconCommandInfo] = &[
+    // Player
+    cmd(
+        Player,
+        "ListPlayers",
+        "",
+        "List connected players with their ids",
+    ),
+    cmd(
+        Player,
+        "KickPlayer",
+        "<id>",
+        "Kick a player by EOS/Steam id",
+    ),
+    cmd(Player, "BanPlayer", "<id>", "Ban a player by EOS/Steam id"),
+    cmd(Player, "UnbanPlayer", "<id>", "Remove a ban"),
+    cmd(
+        Player,
+        "AllowPlayerToJoinNoCheck",
+        "<id>",
+        "Add a player to the whitelist",
+    ),
+    cmd(
+        Player,
+        "DisallowPlayerToJoinNoCheck",
+        "<id>",
+        "Remove a player from the whitelist",
+    ),
+    cmd(
+        Player,
+        "ServerChatTo",
+        "<id> <message>",
+        "Send a private message to a player",
+    ),
+    cmd(
+        Player,
+        "ServerChatToPlayer",
+        "<name> <message>",
+        "Send a private message by player name",
+    ),
+    cmd(
+        Player,
+        "GiveExpToPlayer",
+        "<playerId> <amount> <fromTribeShare> <preventSharing>",
+        "Grant experience to a player",
+    ),
+    cmd(
+        Player,
+        "GiveItemToPlayer",
+        "<playerId> <blueprintPath> <quantity> <quality> <forceBlueprint>",
+        "Give an item to a player",
+    ),
+    cmd(
+        Player,
+        "KillPlayer",
+        "<playerId>",
+        "Kill a player's character",
+    ),
+    cmd(
+        Player,
+        "TeleportPlayerIDToMe",
+        "<playerId>",
+        "Teleport a player to the admin",
+    ),
+    cmd(
+        Player,
+        "GetPlayerIDForSteamID",
+        "<steamId>",
+        "Look up the in-game player id",
+    ),
+    // World
+    cmd(World, "SaveWorld", "", "Save the world to disk"),
+    cmd(World, "SetTimeOfDay", "<hh:mm>", "Set the in-game time"),
+    cmd(
+        World,
+        "Slomo",
+        "<multiplier>",
+        "Change game speed (1 is normal)",
+    ),
+    cmd(
+        World,
+        "DestroyStructures",
+        "",
+        "Destroy all player structures",
+    ),
+    cmd(
+        World,
+        "DestroyTribeStructures",
+        "<tribeId>",
+        "Destroy a tribe's structures",
+    ),
+    cmd(
+        World,
+        "DestroyAllEnemies",
+        "",
+        "Kill every non-player creature, including tames",
+    ),
+    // Dino
+    cmd(
+        Dino,
+        "DestroyWildDinos",
+        "",
+        "Wipe all wild dinos so they respawn",
+    ),
+    cmd(
+        Dino,
+        "DestroyTribeDinos",
+        "<tribeId>",
+        "Destroy a tribe's tamed dinos",
+    ),
+    cmd(Dino, "ForceTame", "", "Tame the dino you are looking at"),
+    cmd(
+        Dino,
+        "SpawnDino",
+        "<blueprintPath> <spawnDistance> <spawnYOffset> <zOffset> <level>",
+        "Spawn a dino near the admin",
+    ),
+    cmd(
+        Dino,
+        "GMSummon",
+        "<className> <level>",
+        "Summon a tamed dino",
+    ),
+    // Server
+    cmd(
+        Server,
+        "Broadcast",
+        "<message>",
+        "Show a message to every player",
+    ),
+    cmd(
+        Server,
+        "ServerChat",
+        "<message>",
+        "Send a global chat message as the server",
+    ),
+    cmd(
+        Server,
+        "GetChat",
+        "",
+        "Fetch chat messages since the last call",
+    ),
+    cmd(
+        Server,
+        "GetGameLog",
+        "",
+        "Fetch game log entries since the last call",
+    ),
+    cmd(
+        Server,
+        "SetMessageOfTheDay",
+        "<message>",
+        "Change the message of the day",
+    ),
+    cmd(
+        Server,
+        "ShowMessageOfTheDay",
+        "",
+        "Display the message of the day",
+    ),
+    cmd(
+        Server,
+        "DoExit",
+        "",
+        "Shut the server down immediately (save first)",
+    ),
+];
@@ -0,0 +1,544 @@
+// RCON wire protocol: packet encoding, the client RconService keeps per server,
+// and a raw exchange that reports every packet for troubleshooting.
+// Long replies (ListPlayers on a full server, GetChat) are split over several
+// packets. After each command an empty SERVERDATA_RESPONSE_VALUE is sent as an end
+// marker; the server answers in order, so its echo means the reply is complete.
+// Servers that don't echo it are handled by ending the reply after a quiet gap.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+pub const SERVERDATA_RESPONSE_VALUE: i32 = 0;
+pub const SERVERDATA_EXECCOMMAND: i32 = 2;
+pub const SERVERDATA_AUTH_RESPONSE: i32 = 2;
+pub const SERVERDATA_AUTH: i32 = 3;
+
+/// Packets larger than this are treated as a corrupt stream
+const MAX_PACKET_SIZE: i32 = 64 * 1024;
+/// Once a reply has started, this much silence ends it if no end marker arrives
+const IDLE_GAP: Duration = Duration::from_millis(500);
+
+const AUTH_ID: i32 = 1;
+const COMMAND_ID: i32 = 2;
+const MARKER_ID: i32 = 3;
+
+/// One packet as it went over the wire
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawRconPacket {
+    /// "sent" or "received"
+    pub direction: String,
+    pub id: i32,
+    pub packet_type: i32,
+    /// Value of the length prefix
+    pub size: i32,
+    pub body: String,
+    /// The whole packet, length prefix included, as space separated hex
+    pub hex: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawRconExchange {
+    pub command: String,
+    /// Bodies of the reply packets to the command, joined in order
+    pub response: String,
+    /// Number of packets the command's reply was split into
+    pub response_packets: usize,
+    pub packets: Vec<RawRconPacket>,
+    /// "marker" (end marker echoed), "idle", "timeout" or "closed"
+    pub ended_by: String,
+    pub elapsed_ms: u64,
+}
+
+/// Length-prefixed packet: size, id, type, body, two NUL bytes
+pub fn encode_packet(id: i32, packet_type: i32, body: &str) -> Vec<u8> {
+    let size = 4 + 4 + body.len() as i32 + 2;
+    let mut bytes = Vec::with_capacity(size as usize + 4);
+    bytes.extend_from_slice(&size.to_le_bytes());
+    bytes.extend_from_slice(&id.to_le_bytes());
+    bytes.extend_from_slice(&packet_type.to_le_bytes());
+    bytes.extend_from_slice(body.as_bytes());
+    bytes.extend_from_slice(&[0, 0]);
+    bytes
+}
+
+/// Parse one packet from the front of `buf`. Returns the packet and the number of
+/// bytes it used, or None when more bytes are needed.
+pub fn decode_packet(buf: &[u8]) -> Result<Option<(RawRconPacket, usize)>, String> {
+    if buf.len() < 4 {
+        return Ok(None);
+    }
+    let size = i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+    if !(10..=MAX_PACKET_SIZE).contains(&size) {
+        return Err(format!("Invalid packet size {}", size));
+    }
+    let total = 4 + size as usize;
+    if buf.len() < total {
+        return Ok(None);
+    }
+
+    let id = i32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]);
+    let packet_type = i32::from_le_bytes([buf[8], buf[9], buf[10], buf[11]]);
+    let body = &buf[12..total];
+    let body = body.strip_suffix(&[0, 0]).unwrap_or(body);
+    Ok(Some((
+        RawRconPacket {
+            direction: "received".to_string(),
+            id,
+            packet_type,
+            size,
+            body: String::from_utf8_lossy(body).to_string(),
+            hex: to_hex(&buf[..total]),
+        },
+        total,
+    )))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn sent_packet(id: i32, packet_type: i32, body: &str, bytes: &[u8]) -> RawRconPacket {
+    RawRconPacket {
+        direction: "sent".to_string(),
+        id,
+        packet_type,
+        size: bytes.len() as i32 - 4,
+        body: body.to_string(),
+        hex: to_hex(bytes),
+    }
+}
+
+struct PacketReader {
+    stream: TcpStream,
+    buf: Vec<u8>,
+    /// Debug for raw exchanges; trace for the regular client, which polls often
+    log_level: log::Level,
+}
+
+impl PacketReader {
+    /// Next packet, or None if the server closed the connection
+    async fn next(&mut self) -> Result<Option<RawRconPacket>, String> {
+        loop {
+            if let Some((packet, used)) = decode_packet(&self.buf)? {
+                self.buf.drain(..used);
+                log::log!(
+                    self.log_level,
+                    "RCON raw <- id={} type={} size={} [{}]",
+                    packet.id,
+                    packet.packet_type,
+                    packet.size,
+                    packet.hex
+                );
+                return Ok(Some(packet));
+            }
+            let mut chunk = [0u8; 4096];
+            let n = self
+                .stream
+                .read(&mut chunk)
+                .await
+                .map_err(|e| format!("Read failed: {}", e))?;
+            if n == 0 {
+                return Ok(None);
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    async fn send(
+        &mut self,
+        id: i32,
+        packet_type: i32,
+        body: &str,
+        logged_body: &str,
+    ) -> Result<RawRconPacket, String> {
+        let bytes = encode_packet(id, packet_type, body);
+        let mut packet = sent_packet(id, packet_type, logged_body, &bytes);
+        if body != logged_body {
+            // Don't echo the password back as hex either
+            packet.hex = String::new();
+        }
+        log::log!(
+            self.log_level,
+            "RCON raw -> id={} type={} size={} body={:?} [{}]",
+            id,
+            packet_type,
+            packet.size,
+            logged_body,
+            packet.hex
+        );
+        self.stream
+            .write_all(&bytes)
+            .await
+            .map_err(|e| format!("Write failed: {}", e))?;
+        Ok(packet)
+    }
+}
+
+/// A command's reply as read off the wire
+struct Reply {
+    response: String,
+    packets: usize,
+    /// "marker", "idle", "timeout" or "closed"
+    ended_by: &'static str,
+}
+
+/// Why RconClient::connect failed; test_connection reports each cause differently
+#[derive(Debug)]
+pub enum ConnectError {
+    /// The TCP connect itself failed
+    Io(std::io::Error),
+    /// Connected, but the admin password was rejected
+    Auth,
+    /// Connected, but the auth exchange broke off
+    Protocol(String),
+}
+
+impl std::fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectError::Io(e) => write!(f, "{}", e),
+            ConnectError::Auth => write!(f, "Admin password was rejected"),
+            ConnectError::Protocol(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+async fn open(addr: &str, log_level: log::Level) -> std::io::Result<PacketReader> {
+    let stream = TcpStream::connect(addr).await?;
+    Ok(PacketReader {
+        stream,
+        buf: Vec::new(),
+        log_level,
+    })
+}
+
+/// Send the auth packet and wait for its AUTH_RESPONSE. Servers may send an empty
+/// RESPONSE_VALUE first.
+async fn authenticate(
+    reader: &mut PacketReader,
+    id: i32,
+    password: &str,
+    mut record: Option<&mut Vec<RawRconPacket>>,
+) -> Result<(), ConnectError> {
+    let sent = reader
+        .send(id, SERVERDATA_AUTH, password, "<password>")
+        .await
+        .map_err(ConnectError::Protocol)?;
+    if let Some(packets) = record.as_deref_mut() {
+        packets.push(sent);
+    }
+    loop {
+        let Some(packet) = reader.next().await.map_err(ConnectError::Protocol)? else {
+            return Err(ConnectError::Protocol(
+                "Server closed the connection during auth".to_string(),
+            ));
+        };
+        let is_auth = packet.packet_type == SERVERDATA_AUTH_RESPONSE;
+        let rejected = packet.id == -1;
+        if let Some(packets) = record.as_deref_mut() {
+            packets.push(packet);
+        }
+        if is_auth {
+            return if rejected {
+                Err(ConnectError::Auth)
+            } else {
+                Ok(())
+            };
+        }
+    }
+}
+
+/// Send `command` followed by the end marker, then collect reply packets until
+/// the marker is echoed, the reply goes quiet, or `deadline` passes. Packets with
+/// other ids (a late echo from an earlier command) are skipped.
+async fn run_command(
+    reader: &mut PacketReader,
+    command_id: i32,
+    marker_id: i32,
+    command: &str,
+    logged_command: &str,
+    deadline: Option<Instant>,
+    mut record: Option<&mut Vec<RawRconPacket>>,
+) -> Result<Reply, String> {
+    let sent = reader
+        .send(command_id, SERVERDATA_EXECCOMMAND, command, logged_command)
+        .await?;
+    let marker = reader
+        .send(marker_id, SERVERDATA_RESPONSE_VALUE, "", "")
+        .await?;
+    if let Some(packets) = record.as_deref_mut() {
+        packets.push(sent);
+        packets.push(marker);
+    }
+
+    let mut reply = Reply {
+        response: String::new(),
+        packets: 0,
+        ended_by: "timeout",
+    };
+    loop {
+        let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+        if remaining.is_some_and(|r| r.is_zero()) {
+            reply.ended_by = "timeout";
+            break;
+        }
+        let idle_wait = reply.packets > 0 && !remaining.is_some_and(|r| r <= IDLE_GAP);
+        let next = if idle_wait {
+            tokio::time::timeout(IDLE_GAP, reader.next()).await
+        } else if let Some(remaining) = remaining {
+            tokio::time::timeout(remaining, reader.next()).await
+        } else {
+            Ok(reader.next().await)
+        };
+
+        match next {
+            Err(_) => {
+                reply.ended_by = if idle_wait { "idle" } else { "timeout" };
+                break;
+            }
+            Ok(Err(e)) => return Err(e),
+            Ok(Ok(None)) => {
+                reply.ended_by = "closed";
+                break;
+            }
+            Ok(Ok(Some(packet))) => {
+                let id = packet.id;
+                if id == command_id {
+                    reply.response.push_str(&packet.body);
+                    reply.packets += 1;
+                }
+                if let Some(packets) = record.as_deref_mut() {
+                    packets.push(packet);
+                }
+                if id == marker_id {
+                    reply.ended_by = "marker";
+                    break;
+                }
+            }
+        }
+    }
+    Ok(reply)
+}
+
+/// Source RCON client for one server. Not shared between tasks; RconService
+/// keeps one per server behind its own lock.
+pub struct RconClient {
+    reader: PacketReader,
+    last_id: i32,
+}
+
+impl RconClient {
+    /// Connect to `addr` ("host:port") and authenticate
+    pub async fn connect(addr: &str, password: &str) -> Result<Self, ConnectError> {
+        let mut client = Self {
+            reader: open(addr, log::Level::Trace)
+                .await
+                .map_err(ConnectError::Io)?,
+            last_id: 0,
+        };
+        let id = client.next_id();
+        authenticate(&mut client.reader, id, password, None).await?;
+        Ok(client)
+    }
+
+    /// Positive ids only; -1 is the auth failure id
+    fn next_id(&mut self) -> i32 {
+        self.last_id = if self.last_id >= i32::MAX - 1 {
+            1
+        } else {
+            self.last_id + 1
+        };
+        self.last_id
+    }
+
+    /// Run a command and return its whole reply, however many packets it took.
+    /// Callers bound the wait; RconService applies its command timeout.
+    pub async fn cmd(&mut self, command: &str) -> Result<String, String> {
+        let command_id = self.next_id();
+        let marker_id = self.next_id();
+        let logged = crate::services::rcon::mask_sensitive(command, &[]);
+        let reply = run_command(
+            &mut self.reader,
+            command_id,
+            marker_id,
+            command,
+            &logged,
+            None,
+            None,
+        )
+        .await?;
+        if reply.ended_by == "closed" && reply.packets == 0 {
+            return Err("Server closed the RCON connection".to_string());
+        }
+        Ok(reply.response)
+    }
+}
+
+/// Connect, authenticate and run `command` on a throwaway connection, recording
+/// every packet. `timeout` bounds each phase (connect, auth, reply).
+pub async fn exchange(
+    address: &str,
+    port: u16,
+    password: &str,
+    command: &str,
+    timeout: Duration,
+) -> Result<RawRconExchange, String> {
+    let started = Instant::now();
+    let addr = format!("{}:{}", address, port);
+    let mut reader = tokio::time::timeout(timeout, open(&addr, log::Level::Debug))
+        .await
+        .map_err(|_| format!("Timed out connecting to {}", addr))?
+        .map_err(|e| format!("Could not reach {}: {}", addr, e))?;
+    let mut packets = Vec::new();
+
+    tokio::time::timeout(
+        timeout,
+        authenticate(&mut reader, AUTH_ID, password, Some(&mut packets)),
+    )
+    .await
+    .map_err(|_| "Timed out waiting for the auth response".to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let reply = run_command(
+        &mut reader,
+        COMMAND_ID,
+        MARKER_ID,
+        command,
+        command,
+        Some(Instant::now() + timeout),
+        Some(&mut packets),
+    )
+    .await?;
+
+    Ok(RawRconExchange {
+        command: command.to_string(),
+        response: reply.response,
+        response_packets: reply.packets,
+        packets,
+        ended_by: reply.ended_by.to_string(),
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packets_round_trip() {
+        let bytes = encode_packet(7, SERVERDATA_EXECCOMMAND, "ListPlayers");
+        assert_eq!(&bytes[..4], &(4 + 4 + 11 + 2i32).to_le_bytes());
+
+        let (packet, used) = decode_packet(&bytes).unwrap().unwrap();
+        assert_eq!(used, bytes.len());
+        assert_eq!(packet.id, 7);
+        assert_eq!(packet.packet_type, SERVERDATA_EXECCOMMAND);
+        assert_eq!(packet.body, "ListPlayers");
+        assert!(packet.hex.starts_with("17 00 00 00 07 00 00 00"));
+    }
+
+    #[test]
+    fn partial_and_corrupt_packets() {
+        let bytes = encode_packet(1, SERVERDATA_RESPONSE_VALUE, "abc");
+        assert!(decode_packet(&bytes[..bytes.len() - 1]).unwrap().is_none());
+        assert!(decode_packet(&[1, 0]).unwrap().is_none());
+        assert!(decode_packet(&[0xff, 0xff, 0xff, 0x7f]).is_err());
+    }
+
+    /// Fake server: accepts auth, then answers every command with `parts` as
+    /// separate packets, echoing the end marker when `echo_marker` is set
+    async fn fake_server(parts: Vec<&'static str>, echo_marker: bool) -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut reader = PacketReader {
+                stream,
+                buf: Vec::new(),
+                log_level: log::Level::Trace,
+            };
+            let auth = reader.next().await.unwrap().unwrap();
+            let mut out = encode_packet(auth.id, SERVERDATA_RESPONSE_VALUE, "");
+            out.extend(encode_packet(auth.id, SERVERDATA_AUTH_RESPONSE, ""));
+            reader.stream.write_all(&out).await.unwrap();
+
+            loop {
+                let Ok(Some(command)) = reader.next().await else {
+                    break;
+                };
+                let marker = reader.next().await.unwrap().unwrap();
+                let mut out = Vec::new();
+                for part in &parts {
+                    out.extend(encode_packet(command.id, SERVERDATA_RESPONSE_VALUE, part));
+                }
+                if echo_marker {
+                    out.extend(encode_packet(marker.id, SERVERDATA_RESPONSE_VALUE, ""));
+                    // Source servers follow the echo with a second, odd packet
+                    out.extend(encode_packet(marker.id, SERVERDATA_RESPONSE_VALUE, "\u{1}"));
+                }
+                reader.stream.write_all(&out).await.unwrap();
+            }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn exchange_reassembles_split_replies() {
+        let port = fake_server(vec!["0. Alice, 1\n", "1. Bob, 2\n"], true).await;
+
+        let result = exchange(
+            "127.0.0.1",
+            port,
+            "secret",
+            "ListPlayers",
+            Duration::from_secs(2),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.ended_by, "marker");
+        assert_eq!(result.response_packets, 2);
+        assert_eq!(result.response, "0. Alice, 1\n1. Bob, 2\n");
+        assert_eq!(result.packets[0].body, "<password>");
+        assert!(result.packets[0].hex.is_empty());
+    }
+
+    #[tokio::test]
+    async fn client_reassembles_split_replies_across_commands() {
+        let port = fake_server(vec!["0. Alice, 1\n", "1. Bob, 2\n"], true).await;
+        let mut client = RconClient::connect(&format!("127.0.0.1:{}", port), "secret")
+            .await
+            .unwrap();
+
+        // The second command must skip the leftover marker packet of the first
+        for _ in 0..2 {
+            let reply = tokio::time::timeout(Duration::from_secs(2), client.cmd("ListPlayers"))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(reply, "0. Alice, 1\n1. Bob, 2\n");
+        }
+    }
+
+    #[tokio::test]
+    async fn client_ends_reply_after_quiet_gap_without_marker_echo() {
+        let port = fake_server(vec!["part one ", "part two"], false).await;
+        let mut client = RconClient::connect(&format!("127.0.0.1:{}", port), "secret")
+            .await
+            .unwrap();
+
+        let reply = tokio::time::timeout(Duration::from_secs(3), client.cmd("GetChat"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reply, "part one part two");
+    }
+}
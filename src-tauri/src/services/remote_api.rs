@@ -0,0 +1,469 @@
+// Remote management API
+// Optional local HTTP server so a headless install can be driven from a phone or script.
+// Every request needs `Authorization: Bearer <token>` (or `?token=` for the WebSocket),
+// and each client IP is rate limited before the token is checked.
+
+use crate::commands;
+use crate::error::ManagerError;
+use crate::services::settings;
+use crate::AppState;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, Path, Request, State as AxumState};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, EventId, Listener, Manager};
+use tokio::sync::{broadcast, oneshot};
+
+/// Loopback only unless the user opts into something wider
+pub const DEFAULT_BIND: &str = "127.0.0.1:8787";
+
+/// Requests allowed per client IP per window
+const RATE_LIMIT_REQUESTS: u32 = 60;
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// App events forwarded to WebSocket clients
+const FORWARDED_EVENTS: &[&str] = &["server-status-change", "server_event"];
+
+pub struct RunningApi {
+    pub bind_address: String,
+    shutdown: oneshot::Sender<()>,
+    listeners: Vec<EventId>,
+}
+
+/// Handle to the running API server, if any
+pub struct RemoteApiState(pub tokio::sync::Mutex<Option<RunningApi>>);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteApiStatus {
+    pub running: bool,
+    pub bind_address: Option<String>,
+}
+
+#[derive(Clone)]
+struct ApiContext {
+    app_handle: AppHandle,
+    events: broadcast::Sender<String>,
+}
+
+/// What `authorize` checks before any route runs
+#[derive(Clone)]
+struct ApiAuth {
+    token: Arc<String>,
+    limiter: Arc<RateLimiter>,
+}
+
+#[derive(Default)]
+struct RateLimiter {
+    requests: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    /// Fixed window counter per client IP. Expired windows are dropped on every
+    /// call so the map only holds clients seen in the last window.
+    fn allow(&self, ip: IpAddr, now: Instant) -> bool {
+        let mut requests = match self.requests.lock() {
+            Ok(requests) => requests,
+            Err(_) => return false,
+        };
+        requests.retain(|_, (start, _)| now.duration_since(*start) < RATE_LIMIT_WINDOW);
+        let entry = requests.entry(ip).or_insert((now, 0));
+        entry.1 += 1;
+        entry.1 <= RATE_LIMIT_REQUESTS
+    }
+}
+
+#[derive(Deserialize)]
+struct RconRequest {
+    command: String,
+}
+
+/// Compare without bailing out on the first differing byte
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+/// Map a command result onto a JSON response; NotFound errors become a 404
+fn respond<T: Serialize, E: Into<ManagerError>>(result: Result<T, E>) -> Response {
+    match result.map_err(Into::<ManagerError>::into) {
+        Ok(value) => Json(value).into_response(),
+        Err(e @ ManagerError::NotFound(_)) => error_response(StatusCode::NOT_FOUND, &e.to_string()),
+        Err(e) => error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+    }
+}
+
+async fn authorize(
+    AxumState(auth): AxumState<ApiAuth>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // Rate limit first so the token can't be brute forced
+    if !auth.limiter.allow(addr.ip(), Instant::now()) {
+        return error_response(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded");
+    }
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| v.trim().to_string())
+        .or_else(|| {
+            // Browsers can't set headers on a WebSocket handshake
+            request.uri().query().and_then(|q| {
+                q.split('&')
+                    .find_map(|pair| pair.strip_prefix("token="))
+                    .map(|v| v.to_string())
+            })
+        });
+
+    match provided {
+        Some(token) if tokens_match(&token, &auth.token) => next.run(request).await,
+        _ => error_response(StatusCode::UNAUTHORIZED, "Missing or invalid token"),
+    }
+}
+
+async fn list_servers(AxumState(ctx): AxumState<ApiContext>) -> Response {
+    respond(
+        commands::server::get_all_servers(ctx.app_handle.state::<AppState>(), None, None, None)
+            .await,
+    )
+}
+
+async fn server_status(
+    AxumState(ctx): AxumState<ApiContext>,
+    Path(server_id): Path<i64>,
+) -> Response {
+    let state = ctx.app_handle.state::<AppState>();
+    let servers = match commands::server::get_all_servers(state.clone(), None, None, None).await {
+        Ok(servers) => servers,
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, &e),
+    };
+
+    match servers.into_iter().find(|s| s.id == server_id) {
+        Some(server) => Json(serde_json::json!({
+            "id": server.id,
+            "name": server.name,
+            "status": server.status,
+            "running": state.process_manager.is_running(server_id),
+        }))
+        .into_response(),
+        None => error_response(StatusCode::NOT_FOUND, "Server not found"),
+    }
+}
+
+async fn start_server(
+    AxumState(ctx): AxumState<ApiContext>,
+    Path(server_id): Path<i64>,
+) -> Response {
+    println!("🌐 Remote API: start server {}", server_id);
+    respond(
+        commands::server::start_server(ctx.app_handle.clone(), server_id)
+            .await
+            .map(|_| serde_json::json!({ "ok": true })),
+    )
+}
+
+async fn stop_server(
+    AxumState(ctx): AxumState<ApiContext>,
+    Path(server_id): Path<i64>,
+) -> Response {
+    println!("🌐 Remote API: stop server {}", server_id);
+    respond(
+        commands::server::stop_server(ctx.app_handle.state::<AppState>(), server_id)
+            .await
+            .map(|_| serde_json::json!({ "ok": true })),
+    )
+}
+
+async fn restart_server(
+    AxumState(ctx): AxumState<ApiContext>,
+    Path(server_id): Path<i64>,
+) -> Response {
+    println!("🌐 Remote API: restart server {}", server_id);
+    respond(
+        commands::server::restart_server(ctx.app_handle.state::<AppState>(), server_id)
+            .await
+            .map(|_| serde_json::json!({ "ok": true })),
+    )
+}
+
+async fn send_rcon(
+    AxumState(ctx): AxumState<ApiContext>,
+    Path(server_id): Path<i64>,
+    Json(body): Json<RconRequest>,
+) -> Response {
+    println!(
+        "🌐 Remote API: RCON '{}' on server {}",
+        body.command, server_id
+    );
+    let state = ctx.app_handle.state::<AppState>();
+    let rcon_state = ctx.app_handle.state::<commands::rcon::RconState>();
+    let rcon = &rcon_state.0;
+
+    if let Err(e) = commands::rcon::ensure_connected(&state, rcon, server_id).await {
+        return error_response(StatusCode::BAD_REQUEST, &e);
+    }
+    respond(rcon.send_command(server_id, &body.command).await)
+}
+
+async fn events(ws: WebSocketUpgrade, AxumState(ctx): AxumState<ApiContext>) -> Response {
+    let receiver = ctx.events.subscribe();
+    ws.on_upgrade(move |socket| forward_events(socket, receiver))
+}
+
+async fn forward_events(mut socket: WebSocket, mut receiver: broadcast::Receiver<String>) {
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                Ok(text) => {
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(_) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Start the API if it is enabled in settings. Returns the bound address, or None when disabled.
+pub async fn start(app_handle: &AppHandle) -> Result<Option<String>, String> {
+    stop(app_handle).await;
+
+    let state = app_handle.state::<AppState>();
+    if !settings::REMOTE_API_ENABLED.load(&state) {
+        return Ok(None);
+    }
+
+    let token = settings::REMOTE_API_TOKEN
+        .load(&state)
+        .ok_or("Remote API is enabled but no access token is set")?;
+    let bind_address = settings::REMOTE_API_BIND
+        .load(&state)
+        .unwrap_or_else(|| DEFAULT_BIND.to_string());
+    let addr: SocketAddr = bind_address
+        .parse()
+        .map_err(|e| format!("Invalid remote API address '{}': {}", bind_address, e))?;
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| format!("Failed to bind remote API to {}: {}", addr, e))?;
+
+    let (events, _) = broadcast::channel(64);
+    let listeners = FORWARDED_EVENTS
+        .iter()
+        .map(|name| {
+            let events = events.clone();
+            let name = name.to_string();
+            app_handle.listen_any(name.clone(), move |event| {
+                let _ = events.send(format!(
+                    "{{\"event\":\"{}\",\"payload\":{}}}",
+                    name,
+                    event.payload()
+                ));
+            })
+        })
+        .collect();
+
+    let ctx = ApiContext {
+        app_handle: app_handle.clone(),
+        events,
+    };
+    let auth = ApiAuth {
+        token: Arc::new(token),
+        limiter: Arc::new(RateLimiter::default()),
+    };
+
+    let router = Router::new()
+        .route("/api/servers", get(list_servers))
+        .route("/api/servers/:id", get(server_status))
+        .route("/api/servers/:id/start", post(start_server))
+        .route("/api/servers/:id/stop", post(stop_server))
+        .route("/api/servers/:id/restart", post(restart_server))
+        .route("/api/servers/:id/rcon", post(send_rcon))
+        .route("/api/events", get(events))
+        .layer(middleware::from_fn_with_state(auth, authorize))
+        .with_state(ctx);
+
+    let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+    tauri::async_runtime::spawn(async move {
+        let result = axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        })
+        .await;
+        if let Err(e) = result {
+            println!("❌ Remote API stopped with error: {}", e);
+        }
+    });
+
+    println!("🌐 Remote API listening on http://{}", addr);
+
+    let state = app_handle.state::<RemoteApiState>();
+    *state.0.lock().await = Some(RunningApi {
+        bind_address: addr.to_string(),
+        shutdown,
+        listeners,
+    });
+
+    Ok(Some(addr.to_string()))
+}
+
+/// Stop the API server if it is running
+pub async fn stop(app_handle: &AppHandle) {
+    let state = app_handle.state::<RemoteApiState>();
+    let running = state.0.lock().await.take();
+    if let Some(api) = running {
+        for id in api.listeners {
+            app_handle.unlisten(id);
+        }
+        let _ = api.shutdown.send(());
+        println!("🌐 Remote API on {} stopped", api.bind_address);
+    }
+}
+
+pub async fn status(app_handle: &AppHandle) -> RemoteApiStatus {
+    let state = app_handle.state::<RemoteApiState>();
+    let running = state.0.lock().await;
+    RemoteApiStatus {
+        running: running.is_some(),
+        bind_address: running.as_ref().map(|api| api.bind_address.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("s3cret-token", "s3cret-token"));
+        assert!(!tokens_match("s3cret-tokeN", "s3cret-token"));
+        assert!(!tokens_match("s3cret", "s3cret-token"));
+        assert!(!tokens_match("", "s3cret-token"));
+    }
+
+    #[test]
+    fn test_rate_limit_window() {
+        let limiter = RateLimiter::default();
+        let client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let start = Instant::now();
+
+        for _ in 0..RATE_LIMIT_REQUESTS {
+            assert!(limiter.allow(client, start));
+        }
+        assert!(!limiter.allow(client, start));
+        assert!(limiter.allow(other, start + Duration::from_secs(30)));
+
+        // A new window resets the count and forgets clients that went quiet
+        assert!(limiter.allow(client, start + RATE_LIMIT_WINDOW));
+        assert!(limiter.allow(client, start + Duration::from_secs(100)));
+        assert_eq!(limiter.requests.lock().unwrap().len(), 1);
+    }
+
+    /// Serve a stub route behind `authorize` and return its base URL
+    async fn serve_stub(auth: ApiAuth) -> String {
+        let router = Router::new()
+            .route("/api/ping", get(|| async { "pong" }))
+            .layer(middleware::from_fn_with_state(auth, authorize));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await;
+        });
+        format!("http://{}/api/ping", addr)
+    }
+
+    #[tokio::test]
+    async fn test_authorize_middleware() {
+        let auth = ApiAuth {
+            token: Arc::new("s3cret-token".to_string()),
+            limiter: Arc::new(RateLimiter::default()),
+        };
+        let url = serve_stub(auth.clone()).await;
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+
+        let status = |response: reqwest::Response| response.status().as_u16();
+        assert_eq!(status(client.get(&url).send().await.unwrap()), 401);
+        assert_eq!(
+            status(
+                client
+                    .get(&url)
+                    .bearer_auth("wrong-token!")
+                    .send()
+                    .await
+                    .unwrap()
+            ),
+            401
+        );
+        assert_eq!(
+            status(
+                client
+                    .get(&url)
+                    .bearer_auth("s3cret-token")
+                    .send()
+                    .await
+                    .unwrap()
+            ),
+            200
+        );
+        assert_eq!(
+            status(
+                client
+                    .get(format!("{}?token=s3cret-token", url))
+                    .send()
+                    .await
+                    .unwrap()
+            ),
+            200
+        );
+
+        // Past the limit even a valid token is turned away
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        while auth.limiter.allow(localhost, Instant::now()) {}
+        assert_eq!(
+            status(
+                client
+                    .get(&url)
+                    .bearer_auth("s3cret-token")
+                    .send()
+                    .await
+                    .unwrap()
+            ),
+            429
+        );
+    }
+}
@@ -0,0 +1,588 @@
+// Scheduled task runner for ASA Server Manager
+// Polls the scheduled_tasks table and runs each enabled task whose cron
+// expression fired since the previous tick. Task types without a runner here
+// are left alone.
+
+use crate::commands::logs::cleanup_logs;
+use crate::commands::rcon::{ensure_connected, RconState};
+use crate::commands::scheduler::{task_from_row, ScheduledTask, TASK_SELECT};
+use crate::commands::server::{restart_server, start_server, stop_server, update_server};
+use crate::services::rcon::validate_time_of_day;
+use crate::AppState;
+use chrono::{DateTime, Local};
+use cron::Schedule;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// How often the task table is checked
+const TICK: Duration = Duration::from_secs(30);
+/// How long a scheduled update waits for the server process to exit before giving up
+const STOP_WAIT: Duration = Duration::from_secs(120);
+
+/// Ids of tasks currently executing (including their warning delay), whether
+/// started by the schedule or by run_now, so a task never runs twice at once
+static RUNNING: Mutex<Vec<i64>> = Mutex::new(Vec::new());
+
+/// Runs kept per task in scheduled_task_runs; older ones are pruned
+const TASK_HISTORY_LIMIT: i64 = 50;
+
+/// scheduled_task_runs.outcome of a finished run
+pub const RUN_SUCCESS: &str = "success";
+pub const RUN_FAILED: &str = "failed";
+pub const RUN_SKIPPED: &str = "skipped";
+
+pub const TASK_RESTART: &str = "restart";
+pub const TASK_UPDATE: &str = "update";
+pub const TASK_WILD_DINO_WIPE: &str = "wild-dino-wipe";
+pub const TASK_SET_TIME_OF_DAY: &str = "set-time-of-day";
+pub const TASK_CLEANUP_LOGS: &str = "cleanup-logs";
+
+const DEFAULT_RESTART_WARNING: &str = "Server restarting in {minutes} minute(s)!";
+const DEFAULT_UPDATE_WARNING: &str = "Server going down for an update in {minutes} minute(s)!";
+const DEFAULT_WIPE_WARNING: &str = "Wild dinos will be wiped in {minutes} minute(s)!";
+
+/// Options for TASK_WILD_DINO_WIPE
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct WildDinoWipeOptions {
+    save_world: bool,
+}
+
+/// Options for TASK_CLEANUP_LOGS; see commands::logs::cleanup_logs
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct CleanupLogsOptions {
+    keep_days: u32,
+    max_total_mb: u64,
+}
+
+impl Default for CleanupLogsOptions {
+    fn default() -> Self {
+        Self {
+            keep_days: 14,
+            max_total_mb: 1024,
+        }
+    }
+}
+
+/// Options for TASK_SET_TIME_OF_DAY
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetTimeOfDayOptions {
+    hour: u8,
+    minute: u8,
+}
+
+impl SetTimeOfDayOptions {
+    fn from_task(options: Option<&serde_json::Value>) -> Result<Self, String> {
+        let options: Self = options
+            .cloned()
+            .ok_or("Set time of day needs an hour and minute")
+            .and_then(|o| {
+                serde_json::from_value(o).map_err(|_| "Set time of day needs an hour and minute")
+            })?;
+        validate_time_of_day(options.hour, options.minute)?;
+        Ok(options)
+    }
+}
+
+/// Check type-specific options when a task is created
+pub fn validate_task_options(
+    task_type: &str,
+    options: Option<&serde_json::Value>,
+) -> Result<(), String> {
+    if task_type == TASK_SET_TIME_OF_DAY {
+        SetTimeOfDayOptions::from_task(options)?;
+    }
+    Ok(())
+}
+
+/// Parse a cron expression. The UI uses classic 5-field expressions; the
+/// `cron` crate wants a leading seconds field, so one is added when missing.
+pub fn parse_schedule(expression: &str) -> Result<Schedule, String> {
+    let expression = expression.trim();
+    let full = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+    Schedule::from_str(&full)
+        .map_err(|e| format!("Invalid cron expression '{}': {}", expression, e))
+}
+
+/// True if the schedule has an occurrence in (from, to]
+fn fired_between(schedule: &Schedule, from: DateTime<Local>, to: DateTime<Local>) -> bool {
+    schedule.after(&from).next().is_some_and(|next| next <= to)
+}
+
+/// Start the background runner. Tasks that fired while the app was closed are not caught up.
+pub fn start(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_tick = Local::now();
+
+        loop {
+            tokio::time::sleep(TICK).await;
+            let now = Local::now();
+
+            for task in due_tasks(&app_handle, last_tick, now) {
+                // A task still in its warning delay is not started again
+                if !claim(task.id) {
+                    continue;
+                }
+
+                let app_handle = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    log::info!(
+                        "⏰ Running scheduled task {} ({}) for server {}",
+                        task.id,
+                        task.task_type,
+                        task.server_id
+                    );
+                    let _ = execute_task(&app_handle, &task).await;
+                    release(task.id);
+                });
+            }
+
+            last_tick = now;
+        }
+    });
+}
+
+fn claim(task_id: i64) -> bool {
+    let mut running = crate::db::lock_or_recover(&RUNNING, "scheduler");
+    if running.contains(&task_id) {
+        return false;
+    }
+    running.push(task_id);
+    true
+}
+
+fn release(task_id: i64) {
+    crate::db::lock_or_recover(&RUNNING, "scheduler").retain(|id| *id != task_id);
+}
+
+/// Run a task once, out of band. Its schedule is untouched, so the next regular
+/// run still happens as planned. Ok(false) means the task skipped itself.
+pub async fn run_now(app_handle: &AppHandle, task_id: i64) -> Result<bool, String> {
+    let task = {
+        let state = app_handle.state::<AppState>();
+        let db = &state.db;
+        let conn = db.get_connection().map_err(|e| e.to_string())?;
+        conn.query_row(
+            &format!("{} WHERE id = ?1", TASK_SELECT),
+            [task_id],
+            task_from_row,
+        )
+        .map_err(|e| format!("Task not found: {}", e))?
+    };
+
+    if !claim(task.id) {
+        return Err("Task is already running".to_string());
+    }
+    log::info!(
+        "▶️ Running task {} ({}) now for server {}",
+        task.id,
+        task.task_type,
+        task.server_id
+    );
+    let result = execute_task(app_handle, &task).await;
+    release(task.id);
+    result
+}
+
+fn due_tasks(
+    app_handle: &AppHandle,
+    from: DateTime<Local>,
+    to: DateTime<Local>,
+) -> Vec<ScheduledTask> {
+    let state = app_handle.state::<AppState>();
+    let tasks: Vec<ScheduledTask> = {
+        let conn = match state.db.get_connection() {
+            Ok(conn) => conn,
+            Err(_) => return Vec::new(),
+        };
+        let mut stmt = match conn.prepare(&format!("{} WHERE enabled = 1", TASK_SELECT)) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                log::error!("❌ Scheduler failed to load tasks: {}", e);
+                return Vec::new();
+            }
+        };
+        let tasks = match stmt.query_map([], task_from_row) {
+            Ok(rows) => rows.filter_map(|r| r.ok()).collect(),
+            Err(_) => Vec::new(),
+        };
+        tasks
+    };
+
+    tasks
+        .into_iter()
+        .filter(|task| match parse_schedule(&task.cron_expression) {
+            Ok(schedule) => fired_between(&schedule, from, to),
+            Err(e) => {
+                log::warn!("⚠️ Skipping task {}: {}", task.id, e);
+                false
+            }
+        })
+        .collect()
+}
+
+/// Run a task and record the outcome in its history (and last_run when it actually ran)
+pub async fn execute_task(app_handle: &AppHandle, task: &ScheduledTask) -> Result<bool, String> {
+    let run_id = begin_run(app_handle, task.id);
+    let result = run_task(app_handle, task).await;
+
+    let (outcome, message) = match &result {
+        Ok(true) => (RUN_SUCCESS, None),
+        Ok(false) => (RUN_SKIPPED, None),
+        Err(e) => {
+            log::error!("❌ Scheduled task {} failed: {}", task.id, e);
+            (RUN_FAILED, Some(e.clone()))
+        }
+    };
+    let state = app_handle.state::<AppState>();
+    if let Ok(conn) = state.db.get_connection() {
+        finish_run(&conn, task.id, run_id, outcome, message);
+    }
+    result
+}
+
+fn begin_run(app_handle: &AppHandle, task_id: i64) -> Option<i64> {
+    let state = app_handle.state::<AppState>();
+    let db = &state.db;
+    let conn = db.get_connection().ok()?;
+    conn.execute(
+        "INSERT INTO scheduled_task_runs (task_id) VALUES (?1)",
+        [task_id],
+    )
+    .ok()?;
+    Some(conn.last_insert_rowid())
+}
+
+/// Record how a run ended. Only a successful run moves last_run; a skipped run
+/// has no success value, so it never reads as a failure.
+fn finish_run(
+    conn: &rusqlite::Connection,
+    task_id: i64,
+    run_id: Option<i64>,
+    outcome: &str,
+    message: Option<String>,
+) {
+    if let Some(run_id) = run_id {
+        let success = match outcome {
+            RUN_SUCCESS => Some(true),
+            RUN_FAILED => Some(false),
+            _ => None,
+        };
+        let _ = conn.execute(
+            "UPDATE scheduled_task_runs SET finished_at = CURRENT_TIMESTAMP, outcome = ?1, success = ?2, message = ?3 WHERE id = ?4",
+            rusqlite::params![outcome, success, message, run_id],
+        );
+    }
+    if outcome == RUN_SUCCESS {
+        let _ = conn.execute(
+            "UPDATE scheduled_tasks SET last_run = CURRENT_TIMESTAMP WHERE id = ?1",
+            [task_id],
+        );
+    }
+    let _ = conn.execute(
+        "DELETE FROM scheduled_task_runs WHERE task_id = ?1 AND id NOT IN
+            (SELECT id FROM scheduled_task_runs WHERE task_id = ?1 ORDER BY id DESC LIMIT ?2)",
+        rusqlite::params![task_id, TASK_HISTORY_LIMIT],
+    );
+}
+
+/// Run one task. Ok(false) means it was skipped (e.g. server offline) and did not count as a run.
+async fn run_task(app_handle: &AppHandle, task: &ScheduledTask) -> Result<bool, String> {
+    match task.task_type.as_str() {
+        TASK_RESTART => run_restart(app_handle, task).await,
+        TASK_UPDATE => run_update(app_handle, task).await,
+        TASK_WILD_DINO_WIPE => run_wild_dino_wipe(app_handle, task).await,
+        TASK_SET_TIME_OF_DAY => run_set_time_of_day(app_handle, task).await,
+        TASK_CLEANUP_LOGS => run_cleanup_logs(app_handle, task).await,
+        _ => Ok(false),
+    }
+}
+
+fn server_is_running(app_handle: &AppHandle, server_id: i64) -> bool {
+    app_handle
+        .state::<AppState>()
+        .process_manager
+        .is_running(server_id)
+}
+
+/// Broadcast the task's warning (or `default_warning`) and wait pre_warning_minutes.
+/// Returns false if the server stopped during the wait.
+async fn warn_and_wait(
+    app_handle: &AppHandle,
+    task: &ScheduledTask,
+    default_warning: &str,
+) -> Result<bool, String> {
+    let delay_minutes = task.pre_warning_minutes.max(0);
+    if delay_minutes == 0 {
+        return Ok(true);
+    }
+
+    let warning = task
+        .message
+        .clone()
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| default_warning.to_string())
+        .replace("{minutes}", &delay_minutes.to_string());
+    {
+        let state = app_handle.state::<AppState>();
+        let rcon_state = app_handle.state::<RconState>();
+        let rcon = &rcon_state.0;
+        ensure_connected(&state, rcon, task.server_id).await?;
+        rcon.broadcast(task.server_id, &warning).await?;
+    }
+    tokio::time::sleep(Duration::from_secs(delay_minutes as u64 * 60)).await;
+
+    Ok(server_is_running(app_handle, task.server_id))
+}
+
+/// Warn players, then restart through the same path as the UI (including any pre-restart backup)
+async fn run_restart(app_handle: &AppHandle, task: &ScheduledTask) -> Result<bool, String> {
+    if !server_is_running(app_handle, task.server_id) {
+        log::info!(
+            "⏭️ Skipping scheduled restart for server {}: server is offline",
+            task.server_id
+        );
+        return Ok(false);
+    }
+
+    if !warn_and_wait(app_handle, task, DEFAULT_RESTART_WARNING).await? {
+        log::info!(
+            "⏭️ Server {} went offline before the scheduled restart",
+            task.server_id
+        );
+        return Ok(false);
+    }
+
+    restart_server(app_handle.state::<AppState>(), task.server_id).await?;
+    Ok(true)
+}
+
+/// Update through SteamCMD (with the pre-update backup, if enabled). A running server is
+/// warned, stopped for the update and started again afterwards.
+async fn run_update(app_handle: &AppHandle, task: &ScheduledTask) -> Result<bool, String> {
+    let was_running = server_is_running(app_handle, task.server_id);
+
+    if was_running && !warn_and_wait(app_handle, task, DEFAULT_UPDATE_WARNING).await? {
+        log::info!(
+            "  ℹ️ Server {} stopped during the update warning",
+            task.server_id
+        );
+    }
+
+    stop_and_update(app_handle, task.server_id, was_running).await?;
+    Ok(true)
+}
+
+/// Stop the server if it is still running and wait for it to exit, update it,
+/// then start it again when `restart` is set. Also behind the "update" bulk action.
+pub(crate) async fn stop_and_update(
+    app_handle: &AppHandle,
+    server_id: i64,
+    restart: bool,
+) -> Result<(), String> {
+    if server_is_running(app_handle, server_id) {
+        stop_server(app_handle.state::<AppState>(), server_id).await?;
+
+        let deadline = tokio::time::Instant::now() + STOP_WAIT;
+        while server_is_running(app_handle, server_id) {
+            if tokio::time::Instant::now() >= deadline {
+                return Err("Server did not stop in time for the update".to_string());
+            }
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+    }
+
+    update_server(
+        app_handle.clone(),
+        app_handle.state::<AppState>(),
+        server_id,
+    )
+    .await?;
+
+    if restart {
+        start_server(app_handle.clone(), server_id).await?;
+    }
+    Ok(())
+}
+
+/// Broadcast a warning, wait pre_warning_minutes, DestroyWildDinos, then optionally SaveWorld
+async fn run_wild_dino_wipe(app_handle: &AppHandle, task: &ScheduledTask) -> Result<bool, String> {
+    if !server_is_running(app_handle, task.server_id) {
+        log::info!(
+            "⏭️ Skipping wild dino wipe for server {}: server is offline",
+            task.server_id
+        );
+        return Ok(false);
+    }
+
+    let options: WildDinoWipeOptions = task
+        .options
+        .clone()
+        .and_then(|o| serde_json::from_value(o).ok())
+        .unwrap_or_default();
+
+    if !warn_and_wait(app_handle, task, DEFAULT_WIPE_WARNING).await? {
+        log::info!(
+            "⏭️ Server {} went offline before the wild dino wipe",
+            task.server_id
+        );
+        return Ok(false);
+    }
+
+    let state = app_handle.state::<AppState>();
+    let rcon_state = app_handle.state::<RconState>();
+    let rcon = &rcon_state.0;
+    ensure_connected(&state, rcon, task.server_id).await?;
+    rcon.destroy_wild_dinos(task.server_id).await?;
+    log::info!("🦖 Wild dinos wiped on server {}", task.server_id);
+
+    if options.save_world {
+        rcon.save_world(task.server_id).await?;
+        log::info!("💾 World saved on server {}", task.server_id);
+    }
+
+    Ok(true)
+}
+
+/// Force the in-game clock, e.g. every 30 minutes for a permanent-day event
+async fn run_set_time_of_day(app_handle: &AppHandle, task: &ScheduledTask) -> Result<bool, String> {
+    if !server_is_running(app_handle, task.server_id) {
+        return Ok(false);
+    }
+    let options = SetTimeOfDayOptions::from_task(task.options.as_ref())?;
+
+    let state = app_handle.state::<AppState>();
+    let rcon_state = app_handle.state::<RconState>();
+    let rcon = &rcon_state.0;
+    ensure_connected(&state, rcon, task.server_id).await?;
+    rcon.set_time(task.server_id, options.hour, options.minute)
+        .await?;
+    log::info!(
+        "🕒 Set time of day to {:02}:{:02} on server {}",
+        options.hour,
+        options.minute,
+        task.server_id
+    );
+    Ok(true)
+}
+
+/// Prune rotated ShooterGame logs; runs whether or not the server is up
+async fn run_cleanup_logs(app_handle: &AppHandle, task: &ScheduledTask) -> Result<bool, String> {
+    let options: CleanupLogsOptions = task
+        .options
+        .clone()
+        .and_then(|o| serde_json::from_value(o).ok())
+        .unwrap_or_default();
+
+    let result = cleanup_logs(
+        app_handle.state::<AppState>(),
+        task.server_id,
+        options.keep_days,
+        options.max_total_mb,
+    )
+    .await?;
+    log::info!(
+        "🧹 Removed {} old log files for server {}",
+        result.deleted_files.len(),
+        task.server_id
+    );
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_five_field_expression() {
+        let schedule = parse_schedule("0 */6 * * *").unwrap();
+        let from = Local.with_ymd_and_hms(2024, 1, 1, 5, 59, 40).unwrap();
+        let to = Local.with_ymd_and_hms(2024, 1, 1, 6, 0, 10).unwrap();
+        assert!(fired_between(&schedule, from, to));
+
+        let later = Local.with_ymd_and_hms(2024, 1, 1, 6, 0, 40).unwrap();
+        assert!(!fired_between(&schedule, to, later));
+    }
+
+    #[test]
+    fn test_invalid_expression() {
+        assert!(parse_schedule("every tuesday").is_err());
+    }
+
+    #[test]
+    fn test_skipped_runs_are_not_failures() {
+        let (_dir, db, server_id) = crate::db::test_support::db_with_server("task_runs");
+        let conn = db.get_connection().unwrap();
+        conn.execute(
+            "INSERT INTO scheduled_tasks (server_id, task_type, cron_expression) VALUES (?1, ?2, '0 4 * * *')",
+            rusqlite::params![server_id, TASK_RESTART],
+        )
+        .unwrap();
+        let task_id = conn.last_insert_rowid();
+        let begin = || {
+            conn.execute(
+                "INSERT INTO scheduled_task_runs (task_id) VALUES (?1)",
+                [task_id],
+            )
+            .unwrap();
+            Some(conn.last_insert_rowid())
+        };
+
+        let skipped = begin();
+        finish_run(&conn, task_id, skipped, RUN_SKIPPED, None);
+        let failed = begin();
+        finish_run(&conn, task_id, failed, RUN_FAILED, Some("boom".to_string()));
+
+        let runs: Vec<(Option<String>, Option<bool>)> = conn
+            .prepare("SELECT outcome, success FROM scheduled_task_runs ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                (Some(RUN_SKIPPED.to_string()), None),
+                (Some(RUN_FAILED.to_string()), Some(false)),
+            ]
+        );
+        let last_run: Option<String> = conn
+            .query_row(
+                "SELECT last_run FROM scheduled_tasks WHERE id = ?1",
+                [task_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(last_run, None);
+
+        let succeeded = begin();
+        finish_run(&conn, task_id, succeeded, RUN_SUCCESS, None);
+        let last_run: Option<String> = conn
+            .query_row(
+                "SELECT last_run FROM scheduled_tasks WHERE id = ?1",
+                [task_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(last_run.is_some());
+    }
+
+    #[test]
+    fn test_cleanup_logs_options() {
+        let options: CleanupLogsOptions =
+            serde_json::from_value(serde_json::json!({ "keepDays": 3 })).unwrap();
+        assert_eq!(options.keep_days, 3);
+        assert_eq!(options.max_total_mb, 1024);
+    }
+}
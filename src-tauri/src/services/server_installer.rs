@@ -1,11 +1,159 @@
 // Server Installation Service with Real-time Progress Events
 // Handles SteamCMD-based server installation with progress reporting and console output
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use tokio::process::{Child, Command};
+
+/// Give up on a SteamCMD install/update that runs longer than this
+const INSTALL_TIMEOUT: Duration = Duration::from_secs(4 * 60 * 60);
+
+/// SteamCMD prints progress at least every few seconds while it works;
+/// this long without a line means it is stuck
+const INSTALL_STALL_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Cancel flags of running SteamCMD installs, keyed by install path
+static ACTIVE_INSTALLS: Mutex<Vec<(PathBuf, Arc<AtomicBool>)>> = Mutex::new(Vec::new());
+
+/// Ask the SteamCMD install into `install_path` to stop. Returns false if none is running.
+pub fn cancel_install(install_path: &Path) -> bool {
+    let installs = crate::db::lock_or_recover(&ACTIVE_INSTALLS, "installs");
+    match installs.iter().find(|(path, _)| path == install_path) {
+        Some((_, cancel)) => {
+            cancel.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Kill SteamCMD together with the steamservice/steamerrorreporter children it spawns
+async fn kill_process_tree(child: &mut Child) {
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(pid) = child.id() {
+            let _ = Command::new("taskkill")
+                .args(["/F", "/T", "/PID", &pid.to_string()])
+                .creation_flags(0x08000000) // CREATE_NO_WINDOW
+                .output()
+                .await;
+        }
+    }
+    let _ = child.kill().await;
+}
+
+/// Why a SteamCMD run was stopped before it exited on its own
+enum Aborted {
+    Cancelled,
+    TimedOut,
+    Stalled,
+}
+
+/// Paths under an install that belong to one server and are never shared:
+/// configs and saves, the mods it downloaded, its ASA API plugins and SteamCMD staging
+const SERVER_OWN_PATHS: &[&str] = &[
+    "ShooterGame/Saved",
+    "ShooterGame/Binaries/Win64/ShooterGame/Mods",
+    "ShooterGame/Binaries/Win64/ArkApi",
+    "steamapps/downloading",
+    "steamapps/temp",
+];
+
+/// Result of seeding a new install from an existing server's files
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryCopyReport {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// Files in a server install that can be shared with another install, relative to `root`
+fn shared_install_files(root: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            !SERVER_OWN_PATHS
+                .iter()
+                .any(|own| relative == Path::new(own))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| Some(entry.path().strip_prefix(root).ok()?.to_path_buf()))
+        .collect()
+}
+
+/// Byte-for-byte comparison, reading both files in chunks so large paks stay out of memory
+fn same_contents(a: &Path, b: &Path) -> std::io::Result<bool> {
+    use std::io::Read;
+
+    let mut a = std::fs::File::open(a)?;
+    let mut b = std::fs::File::open(b)?;
+    if a.metadata()?.len() != b.metadata()?.len() {
+        return Ok(false);
+    }
+
+    let mut buf_a = vec![0u8; 64 * 1024];
+    let mut buf_b = vec![0u8; 64 * 1024];
+    loop {
+        let read = a.read(&mut buf_a)?;
+        if read == 0 {
+            return Ok(true);
+        }
+        b.read_exact(&mut buf_b[..read])?;
+        if buf_a[..read] != buf_b[..read] {
+            return Ok(false);
+        }
+    }
+}
+
+/// Copy the game files of an installed server into `dest`, leaving out SERVER_OWN_PATHS,
+/// then check every copied file against the source contents. `on_file` gets (files, bytes).
+pub fn copy_server_binaries(
+    source: &Path,
+    dest: &Path,
+    on_file: &mut dyn FnMut(u64, u64),
+) -> Result<BinaryCopyReport, String> {
+    if dest.starts_with(source) {
+        return Err("The new install cannot be inside the source install".to_string());
+    }
+
+    let files = shared_install_files(source);
+    let mut report = BinaryCopyReport { files: 0, bytes: 0 };
+    for relative in &files {
+        let target = dest.join(relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        report.bytes += std::fs::copy(source.join(relative), &target)
+            .map_err(|e| format!("Failed to copy {}: {}", relative.display(), e))?;
+        report.files += 1;
+        on_file(report.files, report.bytes);
+    }
+
+    let mismatched: Vec<String> = files
+        .iter()
+        .filter(|relative| {
+            !same_contents(&source.join(relative), &dest.join(relative)).unwrap_or(false)
+        })
+        .map(|relative| relative.display().to_string())
+        .collect();
+    if !mismatched.is_empty() {
+        return Err(format!(
+            "{} copied files do not match the source, e.g. {}",
+            mismatched.len(),
+            mismatched[0]
+        ));
+    }
+
+    Ok(report)
+}
 
 /// Progress event payload for frontend
 #[derive(Clone, serde::Serialize)]
@@ -16,6 +164,127 @@ pub struct InstallProgress {
     pub message: String,
     pub is_complete: bool,
     pub is_error: bool,
+    /// Set when an error line from SteamCMD was recognised
+    pub steam_error: Option<SteamCmdError>,
+}
+
+/// A recognised SteamCMD failure with what the user can do about it
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SteamCmdError {
+    /// App state code such as "0x202", when the line carried one
+    pub code: Option<String>,
+    /// "disk_space", "disk_write", "rate_limited", "login_failed", "network",
+    /// "no_subscription", "invalid_platform" or "unknown"
+    pub kind: String,
+    pub hint: String,
+    /// The SteamCMD line it was parsed from
+    pub line: String,
+}
+
+/// Known SteamCMD failure markers: (text in the line, kind, hint).
+/// Checked before the app state codes because the text is more specific.
+const STEAMCMD_ERROR_TEXT: &[(&str, &str, &str)] = &[
+    (
+        "rate limit exceeded",
+        "rate_limited",
+        "Steam is rate-limiting logins from this machine. Wait 15-30 minutes and retry.",
+    ),
+    (
+        "not enough disk space",
+        "disk_space",
+        "Out of disk space. ASA needs about 15 GB free on the install drive.",
+    ),
+    (
+        "disk write failure",
+        "disk_write",
+        "Steam could not write to the install folder. Check free space, antivirus and folder permissions.",
+    ),
+    (
+        "login failure",
+        "login_failed",
+        "Steam login failed. Check your connection and that Steam is not down, then retry.",
+    ),
+    (
+        "no subscription",
+        "no_subscription",
+        "Steam refused the download for this account. Make sure the anonymous login is used for the dedicated server.",
+    ),
+    (
+        "invalid platform",
+        "invalid_platform",
+        "The ASA server is Windows-only; SteamCMD must run as the Windows build.",
+    ),
+    (
+        "no connection",
+        "network",
+        "SteamCMD could not reach Steam. Check your internet connection and firewall.",
+    ),
+];
+
+/// App state codes from "Error! App '2430930' state is 0x202 after update job."
+const STEAMCMD_STATE_CODES: &[(&str, &str, &str)] = &[
+    (
+        "0x202",
+        "disk_space",
+        "Out of disk space. ASA needs about 15 GB free on the install drive.",
+    ),
+    (
+        "0x402",
+        "network",
+        "The download was interrupted by a network problem. Retry; it resumes where it stopped.",
+    ),
+    (
+        "0x602",
+        "disk_write",
+        "The update could not be written. Close anything using the server files (including a running server) and retry.",
+    ),
+    (
+        "0x606",
+        "disk_write",
+        "Disk write failure. Check free space, antivirus and folder permissions, then retry.",
+    ),
+];
+
+/// Recognise a SteamCMD error line. Returns None for ordinary output.
+pub fn parse_steamcmd_error(line: &str) -> Option<SteamCmdError> {
+    let lower = line.to_lowercase();
+    let is_error_line =
+        lower.contains("error") || lower.contains("failed") || lower.contains("failure");
+    if !is_error_line {
+        return None;
+    }
+
+    let code = lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|word| word.len() > 2 && word.starts_with("0x"))
+        .map(str::to_string);
+
+    let known = STEAMCMD_ERROR_TEXT
+        .iter()
+        .find(|(text, _, _)| lower.contains(text))
+        .or_else(|| {
+            let code = code.as_deref()?;
+            STEAMCMD_STATE_CODES.iter().find(|(c, _, _)| *c == code)
+        });
+
+    let (kind, hint) = match known {
+        Some((_, kind, hint)) => (kind.to_string(), hint.to_string()),
+        // An app state error we have no advice for is still worth surfacing
+        None if code.is_some() => (
+            "unknown".to_string(),
+            "SteamCMD failed to update the server. Retry, and run a validate if it keeps failing."
+                .to_string(),
+        ),
+        None => return None,
+    };
+
+    Some(SteamCmdError {
+        code,
+        kind,
+        hint,
+        line: line.trim().to_string(),
+    })
 }
 
 /// Console output event for realtime log display
@@ -27,6 +296,15 @@ pub struct ConsoleOutput {
     pub timestamp: String,
 }
 
+/// Outcome of pre-downloading a single mod through SteamCMD
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModPredownloadResult {
+    pub mod_id: String,
+    pub success: bool,
+    pub message: String,
+}
+
 pub struct ServerInstaller {
     app_handle: AppHandle,
 }
@@ -36,7 +314,7 @@ impl ServerInstaller {
         Self { app_handle }
     }
 
-    fn emit_progress(&self, stage: &str, progress: f32, message: &str) {
+    pub fn emit_progress(&self, stage: &str, progress: f32, message: &str) {
         let _ = self.app_handle.emit(
             "install-progress",
             InstallProgress {
@@ -45,6 +323,7 @@ impl ServerInstaller {
                 message: message.to_string(),
                 is_complete: false,
                 is_error: false,
+                steam_error: None,
             },
         );
     }
@@ -70,12 +349,32 @@ impl ServerInstaller {
                 message: message.to_string(),
                 is_complete: true,
                 is_error: false,
+                steam_error: None,
             },
         );
         self.emit_console("✓ Installation completed successfully!", "success");
     }
 
+    fn emit_cancelled(&self, message: &str) {
+        let _ = self.app_handle.emit(
+            "install-progress",
+            InstallProgress {
+                stage: "cancelled".to_string(),
+                progress: 0.0,
+                message: message.to_string(),
+                is_complete: false,
+                is_error: true,
+                steam_error: None,
+            },
+        );
+        self.emit_console(&format!("✗ {}", message), "warning");
+    }
+
     fn emit_error(&self, message: &str) {
+        self.emit_steam_error(message, None);
+    }
+
+    fn emit_steam_error(&self, message: &str, steam_error: Option<SteamCmdError>) {
         let _ = self.app_handle.emit(
             "install-progress",
             InstallProgress {
@@ -84,21 +383,43 @@ impl ServerInstaller {
                 message: message.to_string(),
                 is_complete: false,
                 is_error: true,
+                steam_error,
             },
         );
         self.emit_console(&format!("✗ Error: {}", message), "error");
     }
 
-    /// Install ARK: Survival Ascended server via SteamCMD
+    /// Install ARK: Survival Ascended server via SteamCMD.
+    /// Can be stopped with cancel_install; gives up after INSTALL_TIMEOUT.
     pub async fn install_asa_server(&self, install_path: &PathBuf) -> Result<(), String> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        {
+            let mut installs = crate::db::lock_or_recover(&ACTIVE_INSTALLS, "installs");
+            if installs.iter().any(|(path, _)| path == install_path) {
+                return Err(format!(
+                    "An install is already running for {}",
+                    install_path.display()
+                ));
+            }
+            installs.push((install_path.clone(), cancel.clone()));
+        }
+
+        let result = self.run_install(install_path, &cancel).await;
+        crate::db::lock_or_recover(&ACTIVE_INSTALLS, "installs")
+            .retain(|(path, _)| path != install_path);
+        result
+    }
+
+    async fn run_install(&self, install_path: &PathBuf, cancel: &AtomicBool) -> Result<(), String> {
         self.emit_progress("preparing", 5.0, "Preparing installation...");
         self.emit_console(
             "Starting ARK: Survival Ascended server installation...",
             "info",
         );
 
-        // Create install directory if it doesn't exist
-        if !install_path.exists() {
+        // Create install directory if it doesn't exist; a cancelled fresh install removes it again
+        let created_dir = !install_path.exists();
+        if created_dir {
             self.emit_console(
                 &format!("Creating directory: {}", install_path.display()),
                 "info",
@@ -223,20 +544,50 @@ impl ServerInstaller {
         self.emit_console("SteamCMD process started", "success");
         self.emit_console("Connecting to Steam servers...", "info");
 
-        // Read stdout and parse progress
+        // Read stdout and parse progress, watching for cancel, timeout and stalls
+        let started = Instant::now();
+        let mut last_output = Instant::now();
+        let mut aborted = None;
+        // Last recognised error; SteamCMD's exit code alone often says nothing useful
+        let mut steam_error: Option<SteamCmdError> = None;
+
         if let Some(stdout) = child.stdout.take() {
             let reader = BufReader::new(stdout);
             let mut lines = reader.lines();
 
-            while let Ok(Some(line)) = lines.next_line().await {
+            loop {
+                let line = tokio::select! {
+                    next = lines.next_line() => match next {
+                        Ok(Some(line)) => line,
+                        _ => break,
+                    },
+                    _ = tokio::time::sleep(Duration::from_millis(500)) => {
+                        if cancel.load(Ordering::SeqCst) {
+                            aborted = Some(Aborted::Cancelled);
+                        } else if started.elapsed() > INSTALL_TIMEOUT {
+                            aborted = Some(Aborted::TimedOut);
+                        } else if last_output.elapsed() > INSTALL_STALL_TIMEOUT {
+                            aborted = Some(Aborted::Stalled);
+                        }
+                        if aborted.is_some() {
+                            break;
+                        }
+                        continue;
+                    }
+                };
+                last_output = Instant::now();
+
                 // Skip empty lines and clean up the output
                 let trimmed = line.trim();
                 if trimmed.is_empty() {
                     continue;
                 }
 
+                let parsed = parse_steamcmd_error(trimmed);
+
                 // Determine line type and emit to console
-                let line_type = if line.contains("Error")
+                let line_type = if parsed.is_some()
+                    || line.contains("Error")
                     || line.contains("ERROR")
                     || line.contains("Failed")
                 {
@@ -256,6 +607,10 @@ impl ServerInstaller {
 
                 // Emit console line
                 self.emit_console(trimmed, line_type);
+                if let Some(error) = parsed {
+                    self.emit_console(&format!("  → {}", error.hint), "warning");
+                    steam_error = Some(error);
+                }
 
                 // Parse SteamCMD output for progress updates
                 if line.contains("Update state") {
@@ -286,6 +641,31 @@ impl ServerInstaller {
             }
         }
 
+        if let Some(reason) = aborted {
+            kill_process_tree(&mut child).await;
+            let message = match reason {
+                Aborted::Cancelled => "Installation cancelled".to_string(),
+                Aborted::TimedOut => format!(
+                    "SteamCMD did not finish within {} hours and was stopped",
+                    INSTALL_TIMEOUT.as_secs() / 3600
+                ),
+                Aborted::Stalled => format!(
+                    "SteamCMD produced no output for {} minutes and was stopped",
+                    INSTALL_STALL_TIMEOUT.as_secs() / 60
+                ),
+            };
+            if let Aborted::Cancelled = reason {
+                if created_dir {
+                    self.emit_console(&format!("Removing {}", install_path.display()), "info");
+                    let _ = std::fs::remove_dir_all(install_path);
+                }
+                self.emit_cancelled(&message);
+            } else {
+                self.emit_error(&format!("{}. Retrying resumes the download.", message));
+            }
+            return Err(message);
+        }
+
         // Also read stderr for any errors
         if let Some(stderr) = child.stderr.take() {
             let reader = BufReader::new(stderr);
@@ -294,6 +674,9 @@ impl ServerInstaller {
                 let trimmed = line.trim();
                 if !trimmed.is_empty() {
                     self.emit_console(trimmed, "error");
+                    if let Some(error) = parse_steamcmd_error(trimmed) {
+                        steam_error = Some(error);
+                    }
                     println!("[SteamCMD ERROR] {}", line);
                 }
             }
@@ -306,7 +689,7 @@ impl ServerInstaller {
             .map_err(|e| format!("SteamCMD process failed: {}", e))?;
 
         self.emit_console("", "info");
-        if status.success() {
+        if status.success() && steam_error.is_none() {
             self.emit_console(
                 "═══════════════════════════════════════════════════════════",
                 "success",
@@ -319,8 +702,11 @@ impl ServerInstaller {
             self.emit_complete("Server installed successfully!");
             Ok(())
         } else {
-            let error_msg = format!("SteamCMD exited with code: {:?}", status.code());
-            self.emit_error(&error_msg);
+            let error_msg = match &steam_error {
+                Some(error) => format!("{} (SteamCMD: {})", error.hint, error.line),
+                None => format!("SteamCMD exited with code: {:?}", status.code()),
+            };
+            self.emit_steam_error(&error_msg, steam_error);
             Err(error_msg)
         }
     }
@@ -333,4 +719,211 @@ impl ServerInstaller {
         // Use the same installation logic - SteamCMD handles updates
         self.install_asa_server(install_path).await
     }
+
+    /// Pre-download mods through SteamCMD's workshop_download_item and copy them into
+    /// the server's Mods folder so they exist before launch.
+    /// ASA mods are published on CurseForge, so many ids will not be on the Workshop;
+    /// those are reported per mod instead of failing the whole run.
+    pub async fn predownload_mods(
+        &self,
+        install_path: &PathBuf,
+        mod_ids: &[String],
+    ) -> Result<Vec<ModPredownloadResult>, String> {
+        // ARK: Survival Ascended client app id (workshop items are keyed by the game, not the server)
+        let asa_client_app_id = "2399830";
+
+        let app_dir = self
+            .app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app dir: {}", e))?;
+        let steamcmd_exe = app_dir.join("steamcmd").join("steamcmd.exe");
+        if !steamcmd_exe.exists() {
+            self.emit_console(
+                "SteamCMD not found! Please install SteamCMD first.",
+                "error",
+            );
+            return Err("SteamCMD not installed".to_string());
+        }
+
+        let workshop_dir = app_dir.join("workshop_cache");
+        let mods_dir = install_path.join("ShooterGame/Binaries/Win64/ShooterGame/Mods");
+        std::fs::create_dir_all(&mods_dir)
+            .map_err(|e| format!("Failed to create Mods folder: {}", e))?;
+
+        let mut results = Vec::new();
+        let total = mod_ids.len().max(1) as f32;
+
+        for (index, mod_id) in mod_ids.iter().enumerate() {
+            self.emit_progress(
+                "downloading",
+                index as f32 / total * 100.0,
+                &format!(
+                    "Downloading mod {} ({}/{})",
+                    mod_id,
+                    index + 1,
+                    mod_ids.len()
+                ),
+            );
+            self.emit_console(&format!("Fetching mod {} via SteamCMD...", mod_id), "info");
+
+            let output = Command::new(&steamcmd_exe)
+                .args([
+                    "+force_install_dir",
+                    &workshop_dir.to_string_lossy(),
+                    "+login",
+                    "anonymous",
+                    "+workshop_download_item",
+                    asa_client_app_id,
+                    mod_id,
+                    "+quit",
+                ])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .creation_flags(0x08000000) // CREATE_NO_WINDOW
+                .output()
+                .await
+                .map_err(|e| format!("Failed to start SteamCMD: {}", e))?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            for line in stdout.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                let line_type = if line.contains("ERROR") || line.contains("failed") {
+                    "error"
+                } else if line.starts_with("Success") {
+                    "success"
+                } else {
+                    "info"
+                };
+                self.emit_console(line, line_type);
+            }
+
+            let downloaded = workshop_dir
+                .join("steamapps/workshop/content")
+                .join(asa_client_app_id)
+                .join(mod_id);
+
+            let result = if !stdout.contains("Success. Downloaded item") || !downloaded.exists() {
+                Err(format!(
+                    "Mod {} is not available through SteamCMD (ASA mods are usually CurseForge-only). It will be downloaded by the server on launch instead.",
+                    mod_id
+                ))
+            } else {
+                crate::commands::file_manager::copy_path_recursive(
+                    &downloaded,
+                    &mods_dir.join(mod_id),
+                    true,
+                    &mut |_, _| {},
+                )
+                .map(|_| format!("Mod {} copied to server Mods folder", mod_id))
+            };
+
+            match &result {
+                Ok(msg) => self.emit_console(&format!("✓ {}", msg), "success"),
+                Err(e) => self.emit_console(&format!("✗ {}", e), "warning"),
+            }
+
+            results.push(ModPredownloadResult {
+                mod_id: mod_id.clone(),
+                success: result.is_ok(),
+                message: result.unwrap_or_else(|e| e),
+            });
+        }
+
+        let succeeded = results.iter().filter(|r| r.success).count();
+        self.emit_progress(
+            "complete",
+            100.0,
+            &format!("Pre-downloaded {}/{} mods", succeeded, results.len()),
+        );
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_server_binaries_skips_server_files() {
+        let root = std::env::temp_dir().join(format!("asa_copy_bins_{}", std::process::id()));
+        let source = root.join("source");
+        let dest = root.join("dest");
+        let _ = std::fs::remove_dir_all(&root);
+
+        let exe = source.join("ShooterGame/Binaries/Win64/ArkAscendedServer.exe");
+        std::fs::create_dir_all(exe.parent().unwrap()).unwrap();
+        std::fs::write(&exe, b"binary").unwrap();
+        let save = source.join("ShooterGame/Saved/SavedArks/TheIsland_WP.ark");
+        std::fs::create_dir_all(save.parent().unwrap()).unwrap();
+        std::fs::write(&save, b"world").unwrap();
+
+        let report = copy_server_binaries(&source, &dest, &mut |_, _| {}).unwrap();
+        assert_eq!(report.files, 1);
+        assert_eq!(report.bytes, 6);
+        assert!(dest
+            .join("ShooterGame/Binaries/Win64/ArkAscendedServer.exe")
+            .exists());
+        assert!(!dest.join("ShooterGame/Saved").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_same_contents_catches_equal_sized_files() {
+        let root = std::env::temp_dir().join(format!("asa_same_contents_{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let original = root.join("original.pak");
+        let copy = root.join("copy.pak");
+        let corrupt = root.join("corrupt.pak");
+        let contents: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&original, &contents).unwrap();
+        std::fs::write(&copy, &contents).unwrap();
+        let mut flipped = contents.clone();
+        flipped[150_000] ^= 0xFF;
+        std::fs::write(&corrupt, &flipped).unwrap();
+
+        assert!(same_contents(&original, &copy).unwrap());
+        assert!(!same_contents(&original, &corrupt).unwrap());
+        assert!(same_contents(&original, &root.join("missing.pak")).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_parse_state_code() {
+        let error =
+            parse_steamcmd_error("Error! App '2430930' state is 0x202 after update job.").unwrap();
+        assert_eq!(error.code.as_deref(), Some("0x202"));
+        assert_eq!(error.kind, "disk_space");
+    }
+
+    #[test]
+    fn test_parse_text_beats_code() {
+        let error =
+            parse_steamcmd_error("ERROR! Failed to install app '2430930' (Disk write failure)")
+                .unwrap();
+        assert_eq!(error.kind, "disk_write");
+
+        let error = parse_steamcmd_error("FAILED (Rate Limit Exceeded)").unwrap();
+        assert_eq!(error.kind, "rate_limited");
+    }
+
+    #[test]
+    fn test_unknown_code_still_reported() {
+        let error =
+            parse_steamcmd_error("Error! App '2430930' state is 0x6 after update job.").unwrap();
+        assert_eq!(error.kind, "unknown");
+        assert_eq!(error.code.as_deref(), Some("0x6"));
+    }
+
+    #[test]
+    fn test_ordinary_lines_ignored() {
+        assert!(parse_steamcmd_error(
+            "Update state (0x61) downloading, progress: 50.00 (12345678 / 24691356)"
+        )
+        .is_none());
+        assert!(parse_steamcmd_error("Success! App '2430930' fully installed.").is_none());
+        assert!(parse_steamcmd_error("Error handling disabled").is_none());
+    }
 }
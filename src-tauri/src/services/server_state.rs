@@ -0,0 +1,276 @@
+// Server status state machine
+// servers.status is written from commands, the process monitor, the log
+// watcher and the file watcher. All of them go through `set_status`, which
+// refuses transitions that make no sense (e.g. a late "online" from the log
+// watcher landing after the user already hit stop), so the UI doesn't flicker
+// or show ghost statuses.
+
+use crate::models::ServerStatus;
+use crate::AppState;
+use rusqlite::{Connection, OptionalExtension};
+
+/// Whether `to` may follow `from`. Stopped and crashed are always reachable:
+/// a dead process is ground truth no matter what we thought it was doing.
+pub fn can_transition(from: &ServerStatus, to: &ServerStatus) -> bool {
+    use ServerStatus::*;
+    match (from, to) {
+        (_, Stopped) | (_, Crashed) => true,
+        (Stopped | Crashed, Starting | Running | Updating | Restarting | Missing) => true,
+        (Starting, Running | Online | Stopping | Updating) => true,
+        (Running, Online | Stopping | Restarting | Updating) => true,
+        (Online, Stopping | Restarting | Updating) => true,
+        (Restarting, Starting | Running | Online) => true,
+        (Updating, Starting | Running) => true,
+        _ => false,
+    }
+}
+
+/// Move a server to `to` if the transition is valid.
+/// Returns Ok(false) when the status was already `to` or the transition was rejected;
+/// Err is reserved for database failures.
+pub fn set_status(conn: &Connection, server_id: i64, to: ServerStatus) -> Result<bool, String> {
+    let current: Option<String> = conn
+        .query_row(
+            "SELECT status FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some(current) = current else {
+        return Ok(false);
+    };
+
+    let from = ServerStatus::from_db(&current);
+    if from == to {
+        return Ok(false);
+    }
+    if !can_transition(&from, &to) {
+        log::warn!(
+            "⚠️ Ignoring status change for server {}: {} → {}",
+            server_id,
+            from.to_string(),
+            to.to_string()
+        );
+        return Ok(false);
+    }
+
+    // Compare-and-set on the status we validated against
+    let sql = if to == ServerStatus::Running {
+        "UPDATE servers SET status = ?1, last_started = datetime('now') WHERE id = ?2 AND status = ?3"
+    } else {
+        "UPDATE servers SET status = ?1 WHERE id = ?2 AND status = ?3"
+    };
+    let changed = conn
+        .execute(sql, rusqlite::params![to.to_string(), server_id, current])
+        .map_err(|e| e.to_string())?;
+    Ok(changed > 0)
+}
+
+/// Best-effort `set_status` for background paths that can't surface an error.
+/// Returns true if the status was changed.
+pub fn update(state: &AppState, server_id: i64, to: ServerStatus) -> bool {
+    let db = &state.db;
+    let Ok(conn) = db.get_connection() else {
+        return false;
+    };
+    match set_status(&conn, server_id, to) {
+        Ok(changed) => changed,
+        Err(e) => {
+            log::warn!("⚠️ Failed to update status for server {}: {}", server_id, e);
+            false
+        }
+    }
+}
+
+/// Mark a server as stopping; returns the status it had so a failed stop can put it back
+pub fn begin_stop(state: &AppState, server_id: i64) -> Option<ServerStatus> {
+    let db = &state.db;
+    let conn = db.get_connection().ok()?;
+    let previous: String = conn
+        .query_row(
+            "SELECT status FROM servers WHERE id = ?1",
+            [server_id],
+            |row| row.get(0),
+        )
+        .ok()?;
+    set_status(&conn, server_id, ServerStatus::Stopping)
+        .ok()
+        .filter(|changed| *changed)
+        .map(|_| ServerStatus::from_db(&previous))
+}
+
+/// Undo `begin_stop` when the process is still up. Only applies while the
+/// status is still "stopping", so a crash or stop recorded meanwhile is kept.
+pub fn abort_stop(state: &AppState, server_id: i64, previous: ServerStatus) {
+    if let Ok(conn) = state.db.get_connection() {
+        let _ = conn.execute(
+            "UPDATE servers SET status = ?1 WHERE id = ?2 AND status = 'stopping'",
+            rusqlite::params![previous.to_string(), server_id],
+        );
+    }
+}
+
+/// No server process survives an app restart; clear anything left mid-flight
+pub fn reset_stale(conn: &Connection) -> Result<usize, String> {
+    conn.execute(
+        "UPDATE servers SET status = 'stopped' WHERE status IN ('running', 'starting', 'restarting', 'updating', 'stopping', 'online')",
+        [],
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Put a server flagged missing back to stopped once its install folder is
+/// there again (drive reconnected, folder restored). Returns true if it was missing.
+pub fn clear_missing(conn: &Connection, server_id: i64) -> Result<bool, String> {
+    let changed = conn
+        .execute(
+            "UPDATE servers SET status = 'stopped' WHERE id = ?1 AND status = 'missing'",
+            [server_id],
+        )
+        .map_err(|e| e.to_string())?;
+    Ok(changed > 0)
+}
+
+/// Flag stopped servers whose install folder is gone (moved drive, renamed folder)
+/// so the UI can offer relocate_server instead of every command failing, and
+/// clear the flag on servers whose folder came back. Returns the newly flagged ids.
+pub fn flag_missing_installs(conn: &Connection) -> Result<Vec<i64>, String> {
+    let servers: Vec<(i64, String)> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, install_path FROM servers WHERE status IN ('stopped', 'crashed', 'missing')",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    let mut missing = Vec::new();
+    for (server_id, install_path) in servers {
+        if std::path::Path::new(&install_path).is_dir() {
+            if let Err(e) = clear_missing(conn, server_id) {
+                log::warn!(
+                    "⚠️ Could not clear missing flag on server {}: {}",
+                    server_id,
+                    e
+                );
+            }
+            continue;
+        }
+        log::warn!(
+            "⚠️ Install folder for server {} not found: {}",
+            server_id,
+            install_path
+        );
+        match set_status(conn, server_id, ServerStatus::Missing) {
+            Ok(true) => missing.push(server_id),
+            Ok(false) => {}
+            Err(e) => log::warn!("⚠️ Could not flag server {} as missing: {}", server_id, e),
+        }
+    }
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{db_with_server, insert_server, TempDir};
+    use ServerStatus::*;
+
+    #[test]
+    fn test_transitions() {
+        assert!(can_transition(&Stopped, &Starting));
+        assert!(can_transition(&Starting, &Running));
+        assert!(can_transition(&Running, &Online));
+        assert!(can_transition(&Online, &Stopping));
+        assert!(can_transition(&Stopping, &Stopped));
+        assert!(can_transition(&Online, &Crashed));
+
+        // Late writers must not resurrect a server that is going down
+        assert!(!can_transition(&Stopping, &Online));
+        assert!(!can_transition(&Stopped, &Online));
+        assert!(!can_transition(&Stopping, &Running));
+
+        // A missing install can only be cleared back to stopped (by relocating it)
+        assert!(can_transition(&Stopped, &Missing));
+        assert!(can_transition(&Missing, &Stopped));
+        assert!(!can_transition(&Missing, &Starting));
+        assert!(!can_transition(&Running, &Missing));
+    }
+
+    /// Every status the state machine can produce must be storable
+    #[test]
+    fn test_every_status_is_persisted() {
+        let (_dir, db, server_id) = db_with_server("server_state");
+        let conn = db.get_connection().unwrap();
+
+        let walk = [
+            Starting, Running, Online, Stopping, Stopped, Missing, Stopped, Updating, Running,
+            Restarting, Online, Crashed,
+        ];
+        for to in walk {
+            assert_eq!(
+                set_status(&conn, server_id, to.clone()),
+                Ok(true),
+                "{}",
+                to.to_string()
+            );
+            let stored: String = conn
+                .query_row(
+                    "SELECT status FROM servers WHERE id = ?1",
+                    [server_id],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(ServerStatus::from_db(&stored), to);
+        }
+    }
+
+    #[test]
+    fn test_flag_missing_installs() {
+        let dir = TempDir::new("missing_installs");
+        std::fs::create_dir_all(dir.path().join("present")).unwrap();
+        let db = crate::db::Database::new(dir.path().join("asa_manager.db")).unwrap();
+        let conn = db.get_connection().unwrap();
+        for name in ["present", "moved"] {
+            insert_server(&conn, name, &dir.path().join(name).to_string_lossy());
+        }
+
+        assert_eq!(flag_missing_installs(&conn).unwrap(), vec![2]);
+        let status: String = conn
+            .query_row("SELECT status FROM servers WHERE id = 2", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(ServerStatus::from_db(&status), Missing);
+        // Already flagged servers aren't reported again
+        assert!(flag_missing_installs(&conn).unwrap().is_empty());
+
+        // The folder is back (drive reconnected), so the flag goes away
+        std::fs::create_dir_all(dir.path().join("moved")).unwrap();
+        assert!(flag_missing_installs(&conn).unwrap().is_empty());
+        let status: String = conn
+            .query_row("SELECT status FROM servers WHERE id = 2", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(ServerStatus::from_db(&status), Stopped);
+    }
+
+    #[test]
+    fn test_clear_missing_only_touches_missing_servers() {
+        let (_dir, db, server_id) = db_with_server("clear_missing");
+        let conn = db.get_connection().unwrap();
+        assert_eq!(clear_missing(&conn, server_id), Ok(false));
+
+        assert_eq!(set_status(&conn, server_id, Missing), Ok(true));
+        assert_eq!(clear_missing(&conn, server_id), Ok(true));
+        assert_eq!(clear_missing(&conn, server_id), Ok(false));
+
+        assert_eq!(set_status(&conn, server_id, Starting), Ok(true));
+        assert_eq!(clear_missing(&conn, server_id), Ok(false));
+    }
+}
@@ -0,0 +1,403 @@
+// Typed access to the key/value settings table
+// Every known key is declared once here with its type and default, so callers
+// no longer parse strings ad hoc and a misspelled key is a compile error.
+// Keys not listed (JSON blobs such as notification channels) still go through
+// Database::get_setting / set_setting directly.
+
+use crate::db::Database;
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+/// A value that can be stored in the settings table
+pub trait SettingValue: Sized {
+    fn parse(raw: &str) -> Option<Self>;
+    fn to_raw(&self) -> String;
+}
+
+impl SettingValue for bool {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "true" | "1" => Some(true),
+            "false" | "0" | "" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_raw(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl SettingValue for u64 {
+    fn parse(raw: &str) -> Option<Self> {
+        raw.trim().parse().ok()
+    }
+
+    fn to_raw(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Optional text; an empty or whitespace-only value reads as None
+impl SettingValue for Option<String> {
+    fn parse(raw: &str) -> Option<Self> {
+        let trimmed = raw.trim();
+        Some((!trimmed.is_empty()).then(|| trimmed.to_string()))
+    }
+
+    fn to_raw(&self) -> String {
+        self.clone().unwrap_or_default()
+    }
+}
+
+/// A known settings key with its type and default
+pub struct Setting<T: 'static> {
+    pub key: &'static str,
+    pub default: T,
+}
+
+impl<T: SettingValue + Clone> Setting<T> {
+    /// Stored value, or the default when unset or unparseable
+    pub fn get(&self, db: &Database) -> T {
+        db.get_setting(self.key)
+            .ok()
+            .flatten()
+            .and_then(|raw| T::parse(&raw))
+            .unwrap_or_else(|| self.default.clone())
+    }
+
+    pub fn set(&self, db: &Database, value: &T) -> Result<(), String> {
+        db.set_setting(self.key, &value.to_raw())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Convenience for callers holding AppState rather than the Database
+    pub fn load(&self, state: &AppState) -> T {
+        self.get(&state.db)
+    }
+}
+
+pub const CURSEFORGE_API_KEY: Setting<Option<String>> = Setting {
+    key: "curseforge_api_key",
+    default: None,
+};
+pub const STEAM_API_KEY: Setting<Option<String>> = Setting {
+    key: "steam_api_key",
+    default: None,
+};
+/// Time a server may stay "running" without reaching "online"
+pub const STARTUP_TIMEOUT_MINUTES: Setting<u64> = Setting {
+    key: "startup_timeout_minutes",
+    default: 15,
+};
+/// How long a server gets to exit on its own after RCON SaveWorld + DoExit
+pub const GRACEFUL_SHUTDOWN_TIMEOUT_SECS: Setting<u64> = Setting {
+    key: "graceful_shutdown_timeout_secs",
+    default: 15,
+};
+/// Seconds an RCON connect or command may wait for the server before failing
+pub const RCON_TIMEOUT_SECS: Setting<u64> = Setting {
+    key: "rcon_timeout_secs",
+    default: 10,
+};
+/// Allow rcon_send_raw, which shows the raw protocol exchange for troubleshooting
+pub const RCON_DEBUG_ENABLED: Setting<bool> = Setting {
+    key: "rcon_debug_enabled",
+    default: false,
+};
+/// Launch servers with a piped stdin so send_console_command can write to their console.
+/// Only applies to servers started after it is turned on.
+pub const CONSOLE_STDIN_ENABLED: Setting<bool> = Setting {
+    key: "console_stdin_enabled",
+    default: false,
+};
+/// Seconds between in-game chat polls (GetChat) on online servers; 0 turns the relay off
+pub const CHAT_POLL_SECS: Setting<u64> = Setting {
+    key: "chat_poll_secs",
+    default: 5,
+};
+/// How long a stopping server gets after a plain taskkill before /F is used; 0 skips
+/// straight to the force kill
+pub const TERMINATE_TIMEOUT_SECS: Setting<u64> = Setting {
+    key: "terminate_timeout_secs",
+    default: 10,
+};
+/// Pause after a server stops before it is relaunched or its files are touched,
+/// so Windows has released its file handles
+pub const STOP_SETTLE_SECS: Setting<u64> = Setting {
+    key: "stop_settle_secs",
+    default: 3,
+};
+/// Window for collapsing repeated crash/backup-failure notifications
+pub const NOTIFICATION_THROTTLE_SECS: Setting<u64> = Setting {
+    key: "notification_throttle_secs",
+    default: 600,
+};
+/// Automatic pre-restart/pre-update backups are skipped if another backup is this recent
+pub const AUTO_BACKUP_SKIP_MINUTES: Setting<u64> = Setting {
+    key: "auto_backup_skip_minutes",
+    default: 30,
+};
+/// Take a PreUpdate backup before SteamCMD runs
+pub const BACKUP_BEFORE_UPDATE: Setting<bool> = Setting {
+    key: "backup_before_update",
+    default: false,
+};
+pub const REMOTE_API_ENABLED: Setting<bool> = Setting {
+    key: "remote_api_enabled",
+    default: false,
+};
+/// Loopback only unless the user opts into something wider
+pub const REMOTE_API_BIND: Setting<Option<String>> = Setting {
+    key: "remote_api_bind",
+    default: None,
+};
+/// Base folder for backup archives; see backup_root()
+pub const BACKUP_ROOT: Setting<Option<String>> = Setting {
+    key: "backup_root",
+    default: None,
+};
+/// Base folder for cluster transfer data; see cluster_root()
+pub const CLUSTER_ROOT: Setting<Option<String>> = Setting {
+    key: "cluster_root",
+    default: None,
+};
+/// Password for encrypted backups. There is no recovery: if this is lost or
+/// changed, backups made with the old key can only be opened with that key.
+pub const BACKUP_ENCRYPTION_KEY: Setting<Option<String>> = Setting {
+    key: "backup_encryption_key",
+    default: None,
+};
+/// S3-compatible endpoint for off-site backups, e.g. https://s3.us-west-000.backblazeb2.com
+pub const CLOUD_ENDPOINT: Setting<Option<String>> = Setting {
+    key: "cloud_endpoint",
+    default: None,
+};
+pub const CLOUD_REGION: Setting<Option<String>> = Setting {
+    key: "cloud_region",
+    default: None,
+};
+pub const CLOUD_BUCKET: Setting<Option<String>> = Setting {
+    key: "cloud_bucket",
+    default: None,
+};
+/// Optional folder inside the bucket
+pub const CLOUD_PREFIX: Setting<Option<String>> = Setting {
+    key: "cloud_prefix",
+    default: None,
+};
+pub const CLOUD_ACCESS_KEY: Setting<Option<String>> = Setting {
+    key: "cloud_access_key",
+    default: None,
+};
+pub const CLOUD_SECRET_KEY: Setting<Option<String>> = Setting {
+    key: "cloud_secret_key",
+    default: None,
+};
+/// Upload every new backup once it is written
+pub const CLOUD_AUTO_UPLOAD: Setting<bool> = Setting {
+    key: "cloud_auto_upload",
+    default: false,
+};
+pub const REMOTE_API_TOKEN: Setting<Option<String>> = Setting {
+    key: "remote_api_token",
+    default: None,
+};
+/// Manager log level ("error" through "trace"); unset means app_log::DEFAULT_LEVEL
+pub const LOG_LEVEL: Setting<Option<String>> = Setting {
+    key: "log_level",
+    default: None,
+};
+
+/// Where backups and clusters lived before these were configurable
+const LEGACY_BACKUP_ROOT: &str = "C:/ASA_Backups";
+const LEGACY_CLUSTER_ROOT: &str = "C:/ASA_Clusters";
+
+/// The configured folder, else the legacy C: location when that drive exists,
+/// else `fallback` under the app data dir
+fn resolve_root(
+    state: &AppState,
+    setting: &Setting<Option<String>>,
+    legacy: &str,
+    fallback: &str,
+) -> PathBuf {
+    if let Some(dir) = setting.load(state) {
+        return PathBuf::from(dir);
+    }
+    let legacy = PathBuf::from(legacy);
+    if legacy.parent().is_some_and(|drive| drive.exists()) {
+        return legacy;
+    }
+    state
+        .app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join(fallback))
+        .unwrap_or(legacy)
+}
+
+pub fn backup_root(state: &AppState) -> PathBuf {
+    resolve_root(state, &BACKUP_ROOT, LEGACY_BACKUP_ROOT, "ASA_Backups")
+}
+
+pub fn cluster_root(state: &AppState) -> PathBuf {
+    resolve_root(state, &CLUSTER_ROOT, LEGACY_CLUSTER_ROOT, "ASA_Clusters")
+}
+
+/// Create the folder if needed and prove we can write a file into it
+pub fn ensure_writable_dir(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Cannot create folder {}: {}", dir.display(), e))?;
+    let probe = dir.join(".asa_manager_write_test");
+    std::fs::write(&probe, b"ok")
+        .map_err(|e| format!("Folder {} is not writable: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// Check a raw value for a known key before it is stored. Unknown keys pass through.
+pub fn validate_raw(key: &str, value: &str) -> Result<(), String> {
+    if (key == BACKUP_ROOT.key || key == CLUSTER_ROOT.key) && !value.trim().is_empty() {
+        return ensure_writable_dir(Path::new(value.trim()));
+    }
+    let ok = match key {
+        k if k == STARTUP_TIMEOUT_MINUTES.key
+            || k == GRACEFUL_SHUTDOWN_TIMEOUT_SECS.key
+            || k == TERMINATE_TIMEOUT_SECS.key
+            || k == STOP_SETTLE_SECS.key
+            || k == NOTIFICATION_THROTTLE_SECS.key
+            || k == AUTO_BACKUP_SKIP_MINUTES.key
+            || k == CHAT_POLL_SECS.key =>
+        {
+            u64::parse(value).is_some()
+        }
+        k if k == RCON_TIMEOUT_SECS.key => u64::parse(value).is_some_and(|secs| secs > 0),
+        k if k == BACKUP_BEFORE_UPDATE.key
+            || k == REMOTE_API_ENABLED.key
+            || k == RCON_DEBUG_ENABLED.key
+            || k == CONSOLE_STDIN_ENABLED.key
+            || k == CLOUD_AUTO_UPLOAD.key =>
+        {
+            bool::parse(value).is_some()
+        }
+        k if k == LOG_LEVEL.key => {
+            value.trim().is_empty() || crate::services::app_log::parse_level(value).is_some()
+        }
+        _ => true,
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("Invalid value '{}' for setting {}", value, key))
+    }
+}
+
+/// All known settings with defaults applied, for the settings UI.
+/// Secrets (remote API token, backup encryption key, cloud credentials) are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    pub curseforge_api_key: Option<String>,
+    pub steam_api_key: Option<String>,
+    pub startup_timeout_minutes: u64,
+    pub graceful_shutdown_timeout_secs: u64,
+    pub terminate_timeout_secs: u64,
+    pub stop_settle_secs: u64,
+    pub rcon_timeout_secs: u64,
+    pub rcon_debug_enabled: bool,
+    pub console_stdin_enabled: bool,
+    pub chat_poll_secs: u64,
+    pub notification_throttle_secs: u64,
+    pub auto_backup_skip_minutes: u64,
+    pub backup_before_update: bool,
+    pub remote_api_enabled: bool,
+    pub remote_api_bind: Option<String>,
+    pub backup_root: Option<String>,
+    pub cluster_root: Option<String>,
+    pub cloud_endpoint: Option<String>,
+    pub cloud_region: Option<String>,
+    pub cloud_bucket: Option<String>,
+    pub cloud_prefix: Option<String>,
+    pub cloud_auto_upload: bool,
+}
+
+impl AppSettings {
+    pub fn load(db: &Database) -> Self {
+        Self {
+            curseforge_api_key: CURSEFORGE_API_KEY.get(db),
+            steam_api_key: STEAM_API_KEY.get(db),
+            startup_timeout_minutes: STARTUP_TIMEOUT_MINUTES.get(db),
+            graceful_shutdown_timeout_secs: GRACEFUL_SHUTDOWN_TIMEOUT_SECS.get(db),
+            terminate_timeout_secs: TERMINATE_TIMEOUT_SECS.get(db),
+            stop_settle_secs: STOP_SETTLE_SECS.get(db),
+            rcon_timeout_secs: RCON_TIMEOUT_SECS.get(db),
+            rcon_debug_enabled: RCON_DEBUG_ENABLED.get(db),
+            console_stdin_enabled: CONSOLE_STDIN_ENABLED.get(db),
+            chat_poll_secs: CHAT_POLL_SECS.get(db),
+            notification_throttle_secs: NOTIFICATION_THROTTLE_SECS.get(db),
+            auto_backup_skip_minutes: AUTO_BACKUP_SKIP_MINUTES.get(db),
+            backup_before_update: BACKUP_BEFORE_UPDATE.get(db),
+            remote_api_enabled: REMOTE_API_ENABLED.get(db),
+            remote_api_bind: REMOTE_API_BIND.get(db),
+            backup_root: BACKUP_ROOT.get(db),
+            cluster_root: CLUSTER_ROOT.get(db),
+            cloud_endpoint: CLOUD_ENDPOINT.get(db),
+            cloud_region: CLOUD_REGION.get(db),
+            cloud_bucket: CLOUD_BUCKET.get(db),
+            cloud_prefix: CLOUD_PREFIX.get(db),
+            cloud_auto_upload: CLOUD_AUTO_UPLOAD.get(db),
+        }
+    }
+
+    pub fn save(&self, db: &Database) -> Result<(), String> {
+        for dir in [&self.backup_root, &self.cluster_root]
+            .into_iter()
+            .flatten()
+        {
+            ensure_writable_dir(Path::new(dir))?;
+        }
+        CURSEFORGE_API_KEY.set(db, &self.curseforge_api_key)?;
+        STEAM_API_KEY.set(db, &self.steam_api_key)?;
+        STARTUP_TIMEOUT_MINUTES.set(db, &self.startup_timeout_minutes)?;
+        GRACEFUL_SHUTDOWN_TIMEOUT_SECS.set(db, &self.graceful_shutdown_timeout_secs)?;
+        TERMINATE_TIMEOUT_SECS.set(db, &self.terminate_timeout_secs)?;
+        STOP_SETTLE_SECS.set(db, &self.stop_settle_secs)?;
+        RCON_TIMEOUT_SECS.set(db, &self.rcon_timeout_secs.max(1))?;
+        RCON_DEBUG_ENABLED.set(db, &self.rcon_debug_enabled)?;
+        CONSOLE_STDIN_ENABLED.set(db, &self.console_stdin_enabled)?;
+        CHAT_POLL_SECS.set(db, &self.chat_poll_secs)?;
+        NOTIFICATION_THROTTLE_SECS.set(db, &self.notification_throttle_secs)?;
+        AUTO_BACKUP_SKIP_MINUTES.set(db, &self.auto_backup_skip_minutes)?;
+        BACKUP_BEFORE_UPDATE.set(db, &self.backup_before_update)?;
+        REMOTE_API_ENABLED.set(db, &self.remote_api_enabled)?;
+        REMOTE_API_BIND.set(db, &self.remote_api_bind)?;
+        BACKUP_ROOT.set(db, &self.backup_root)?;
+        CLUSTER_ROOT.set(db, &self.cluster_root)?;
+        CLOUD_ENDPOINT.set(db, &self.cloud_endpoint)?;
+        CLOUD_REGION.set(db, &self.cloud_region)?;
+        CLOUD_BUCKET.set(db, &self.cloud_bucket)?;
+        CLOUD_PREFIX.set(db, &self.cloud_prefix)?;
+        CLOUD_AUTO_UPLOAD.set(db, &self.cloud_auto_upload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(bool::parse("true"), Some(true));
+        assert_eq!(bool::parse(""), Some(false));
+        assert_eq!(bool::parse("yes"), None);
+        assert_eq!(u64::parse(" 30 "), Some(30));
+        assert_eq!(<Option<String>>::parse("  "), Some(None));
+        assert!(validate_raw("startup_timeout_minutes", "ten").is_err());
+        assert!(validate_raw("discord_config", "{}").is_ok());
+        assert!(validate_raw("log_level", "Debug").is_ok());
+        assert!(validate_raw("log_level", "verbose").is_err());
+        assert!(validate_raw("rcon_timeout_secs", "0").is_err());
+    }
+}
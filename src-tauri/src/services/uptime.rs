@@ -0,0 +1,200 @@
+// Uptime reporting from server_status_history
+// Time counts as "up" only while the recorded status is 'online'.
+
+use chrono::{NaiveDateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+/// Format SQLite's CURRENT_TIMESTAMP / datetime('now') use (UTC)
+const SQLITE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DowntimeWindow {
+    pub start: String,
+    pub end: String,
+    pub duration_secs: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UptimeReport {
+    pub server_id: i64,
+    pub days: u32,
+    pub range_start: String,
+    pub range_end: String,
+    pub uptime_percent: f64,
+    pub online_secs: i64,
+    pub downtime: Vec<DowntimeWindow>,
+}
+
+/// Integrate time spent online over [start, end].
+/// `initial` is the status in effect at `start` (the last change before it, if any);
+/// `changes` must be sorted by time. Returns online seconds and the offline windows.
+pub fn compute_uptime(
+    initial: Option<&str>,
+    changes: &[(NaiveDateTime, String)],
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> (i64, Vec<(NaiveDateTime, NaiveDateTime)>) {
+    let mut online_secs = 0;
+    let mut downtime = Vec::new();
+    let mut online = initial == Some("online");
+    let mut segment_start = start;
+
+    let mut close_segment = |online: bool, from: NaiveDateTime, to: NaiveDateTime| {
+        if to <= from {
+            return;
+        }
+        if online {
+            online_secs += (to - from).num_seconds();
+        } else {
+            downtime.push((from, to));
+        }
+    };
+
+    for (at, status) in changes {
+        let at = (*at).clamp(start, end);
+        let now_online = status == "online";
+        if now_online != online {
+            close_segment(online, segment_start, at);
+            segment_start = at;
+            online = now_online;
+        }
+    }
+    close_segment(online, segment_start, end);
+
+    (online_secs, downtime)
+}
+
+pub fn get_uptime(conn: &Connection, server_id: i64, days: u32) -> Result<UptimeReport, String> {
+    let end = Utc::now().naive_utc();
+    let start = end - chrono::Duration::days(days as i64);
+    let start_str = start.format(SQLITE_TIME_FORMAT).to_string();
+
+    let initial: Option<String> = conn
+        .query_row(
+            "SELECT status FROM server_status_history
+             WHERE server_id = ?1 AND changed_at < ?2
+             ORDER BY changed_at DESC, id DESC LIMIT 1",
+            params![server_id, start_str],
+            |row| row.get(0),
+        )
+        .ok();
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT changed_at, status FROM server_status_history
+             WHERE server_id = ?1 AND changed_at >= ?2
+             ORDER BY changed_at ASC, id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let changes: Vec<(NaiveDateTime, String)> = stmt
+        .query_map(params![server_id, start_str], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .filter_map(|(at, status)| {
+            NaiveDateTime::parse_from_str(&at, SQLITE_TIME_FORMAT)
+                .ok()
+                .map(|at| (at, status))
+        })
+        .collect();
+
+    let (online_secs, downtime) = compute_uptime(initial.as_deref(), &changes, start, end);
+    let range_secs = (end - start).num_seconds().max(1);
+
+    Ok(UptimeReport {
+        server_id,
+        days,
+        range_start: start.and_utc().to_rfc3339(),
+        range_end: end.and_utc().to_rfc3339(),
+        uptime_percent: online_secs as f64 * 100.0 / range_secs as f64,
+        online_secs,
+        downtime: downtime
+            .into_iter()
+            .map(|(from, to)| DowntimeWindow {
+                start: from.and_utc().to_rfc3339(),
+                end: to.and_utc().to_rfc3339(),
+                duration_secs: (to - from).num_seconds(),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, SQLITE_TIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_uptime_caps_at_range_edges() {
+        let start = at("2024-01-01 00:00:00");
+        let end = at("2024-01-01 10:00:00");
+        let changes = vec![
+            (at("2024-01-01 02:00:00"), "stopped".to_string()),
+            (at("2024-01-01 03:00:00"), "running".to_string()),
+            (at("2024-01-01 03:30:00"), "online".to_string()),
+        ];
+
+        // Online since before the range started
+        let (online, downtime) = compute_uptime(Some("online"), &changes, start, end);
+        assert_eq!(online, (2 * 3600) + (6 * 3600 + 1800));
+        assert_eq!(
+            downtime,
+            vec![(at("2024-01-01 02:00:00"), at("2024-01-01 03:30:00"))]
+        );
+    }
+
+    #[test]
+    fn test_uptime_without_history_is_all_downtime() {
+        let start = at("2024-01-01 00:00:00");
+        let end = at("2024-01-02 00:00:00");
+        let (online, downtime) = compute_uptime(None, &[], start, end);
+        assert_eq!(online, 0);
+        assert_eq!(downtime, vec![(start, end)]);
+    }
+
+    /// History written by the status trigger on a real database, the way the
+    /// process monitor and log watcher produce it
+    #[test]
+    fn test_uptime_from_status_history_trigger() {
+        use crate::models::ServerStatus;
+        use crate::services::server_state::set_status;
+
+        let (_dir, db, server_id) = crate::db::test_support::db_with_server("uptime");
+        let conn = db.get_connection().unwrap();
+
+        // Started three hours ago, ready after one hour
+        for (status, hours_ago) in [
+            (ServerStatus::Starting, 3),
+            (ServerStatus::Running, 3),
+            (ServerStatus::Online, 2),
+        ] {
+            assert!(set_status(&conn, server_id, status).unwrap());
+            conn.execute(
+                "UPDATE server_status_history SET changed_at = datetime('now', ?1)
+                 WHERE id = (SELECT MAX(id) FROM server_status_history)",
+                [format!("-{} hours", hours_ago)],
+            )
+            .unwrap();
+        }
+
+        let report = get_uptime(&conn, server_id, 1).unwrap();
+        assert!(
+            (report.online_secs - 2 * 3600).abs() <= 5,
+            "{}",
+            report.online_secs
+        );
+        assert_eq!(report.downtime.len(), 1);
+        assert!((report.downtime[0].duration_secs - 22 * 3600).abs() <= 5);
+
+        drop(conn);
+        drop(db);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,11 +1,12 @@
 import { useState, useEffect, useMemo } from 'react';
 import {
-    Save, Loader2, Map, Users, Swords, Egg, Home, Sun, Copy, FileText, Zap
+    Save, Loader2, Map, Users, Swords, Egg, Home, Sun, Copy, FileText, Zap, Package
 } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 import toast from 'react-hot-toast';
 import { MAP_PROFILES, getMapProfile } from '../../data/mapProfiles';
 import { cn } from '../../utils/helpers';
+import { applyProfileMods } from '../../utils/tauri';
 
 interface ServerConfig {
     sessionName: string;
@@ -77,18 +78,20 @@ export default function ConfigBuilder({ serverId, installPath, initialMapName, o
         [config?.mapName]
     );
 
-    // Load default config on mount
+    // Load the server's current config (or defaults for a new server) on mount
     useEffect(() => {
-        loadDefaultConfig();
-    }, []);
+        loadConfig();
+    }, [serverId]);
 
-    const loadDefaultConfig = async () => {
+    const loadConfig = async () => {
         try {
-            const defaultConfig = await invoke<ServerConfig>('get_default_config');
-            if (initialMapName) {
-                defaultConfig.mapName = initialMapName;
+            const loaded = serverId !== undefined
+                ? await invoke<ServerConfig>('get_server_generator_config', { serverId })
+                : await invoke<ServerConfig>('get_default_config');
+            if (initialMapName && serverId === undefined) {
+                loaded.mapName = initialMapName;
             }
-            setConfig(defaultConfig);
+            setConfig(loaded);
         } catch (error) {
             console.error('Failed to load config:', error);
             toast.error('Failed to load configuration');
@@ -118,6 +121,20 @@ export default function ConfigBuilder({ serverId, installPath, initialMapName, o
         }
     };
 
+    const installRecommendedMods = async () => {
+        if (!config || !serverId) return;
+
+        try {
+            const added = await applyProfileMods(serverId, config.mapName);
+            toast.success(added.length > 0
+                ? `Added ${added.length} recommended mod${added.length === 1 ? '' : 's'}`
+                : 'Recommended mods are already installed');
+        } catch (error) {
+            console.error('Failed to add recommended mods:', error);
+            toast.error(`Failed to add recommended mods: ${error}`);
+        }
+    };
+
     const generatePreview = async () => {
         if (!config) return;
 
@@ -207,6 +224,15 @@ export default function ConfigBuilder({ serverId, installPath, initialMapName, o
                         </div>
                     </div>
                     <div className="flex items-center gap-2">
+                        {serverId !== undefined && (
+                            <button
+                                onClick={installRecommendedMods}
+                                className="flex items-center gap-2 px-4 py-2 bg-slate-700/50 hover:bg-slate-700 text-white rounded-lg transition-colors"
+                            >
+                                <Package className="w-4 h-4" />
+                                Recommended Mods
+                            </button>
+                        )}
                         <button
                             onClick={generatePreview}
                             className="flex items-center gap-2 px-4 py-2 bg-slate-700/50 hover:bg-slate-700 text-white rounded-lg transition-colors"
@@ -0,0 +1,117 @@
+import { useEffect, useMemo, useState } from 'react';
+import { AlertTriangle, Loader2, Search } from 'lucide-react';
+import { getEffectiveConfig } from '../../utils/tauri';
+import type { EffectiveConfig } from '../../types';
+import { cn } from '../../utils/helpers';
+
+interface EffectiveConfigViewProps {
+    serverId: number;
+}
+
+const SOURCE_STYLES: Record<string, string> = {
+    GameUserSettings: 'bg-blue-500/10 text-blue-400',
+    Game: 'bg-purple-500/10 text-purple-400',
+    LaunchArgs: 'bg-orange-500/10 text-orange-400',
+};
+
+// Read-only view of what the server actually runs with (INI files + launch arguments)
+export const EffectiveConfigView = ({ serverId }: EffectiveConfigViewProps) => {
+    const [config, setConfig] = useState<EffectiveConfig | null>(null);
+    const [query, setQuery] = useState('');
+    const [conflictsOnly, setConflictsOnly] = useState(false);
+
+    useEffect(() => {
+        setConfig(null);
+        getEffectiveConfig(serverId).then(setConfig).catch(console.error);
+    }, [serverId]);
+
+    const settings = useMemo(() => {
+        if (!config) return [];
+        const q = query.toLowerCase();
+        return config.settings.filter(s =>
+            (!conflictsOnly || s.conflict) &&
+            (!q || s.key.toLowerCase().includes(q) || s.value.toLowerCase().includes(q))
+        );
+    }, [config, query, conflictsOnly]);
+
+    if (!config) {
+        return (
+            <div className="flex-1 flex items-center justify-center">
+                <Loader2 className="w-6 h-6 animate-spin text-slate-500" />
+            </div>
+        );
+    }
+
+    const conflictCount = config.settings.filter(s => s.conflict).length;
+
+    return (
+        <div className="flex-1 overflow-y-auto p-6">
+            <div className="max-w-5xl mx-auto space-y-4">
+                <div className="bg-slate-900/50 rounded-lg border border-slate-800 p-3 font-mono text-xs text-slate-400 break-all">
+                    {config.launchArgs.join(' ')}
+                </div>
+
+                {conflictCount > 0 && (
+                    <div className="flex items-center gap-2 text-sm text-orange-300 bg-orange-500/10 border border-orange-500/20 rounded-lg px-4 py-2">
+                        <AlertTriangle className="w-4 h-4" />
+                        {conflictCount} INI value{conflictCount === 1 ? ' is' : 's are'} overridden by launch arguments
+                    </div>
+                )}
+
+                <div className="flex items-center gap-3">
+                    <div className="relative flex-1">
+                        <Search className="w-4 h-4 absolute left-3 top-1/2 -translate-y-1/2 text-slate-500" />
+                        <input
+                            value={query}
+                            onChange={(e) => setQuery(e.target.value)}
+                            placeholder="Filter settings..."
+                            className="w-full bg-slate-900 border border-slate-700 rounded-lg pl-9 pr-3 py-2 text-sm text-white focus:outline-none focus:ring-1 focus:ring-cyan-500"
+                        />
+                    </div>
+                    <label className="flex items-center gap-2 text-sm text-slate-300 cursor-pointer">
+                        <input
+                            type="checkbox"
+                            checked={conflictsOnly}
+                            onChange={(e) => setConflictsOnly(e.target.checked)}
+                            className="accent-orange-500"
+                        />
+                        Conflicts only
+                    </label>
+                </div>
+
+                <table className="w-full text-sm">
+                    <thead>
+                        <tr className="text-left text-slate-500 text-xs uppercase tracking-wider">
+                            <th className="py-2 pr-4">Key</th>
+                            <th className="py-2 pr-4">Effective value</th>
+                            <th className="py-2">Source</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {settings.map((s, idx) => (
+                            <tr key={`${s.source}-${s.section}-${s.key}-${idx}`} className={cn('border-t border-slate-800', s.conflict && 'bg-orange-500/5')}>
+                                <td className="py-2 pr-4">
+                                    <div className="text-slate-200">{s.key}</div>
+                                    {s.section && <div className="text-xs text-slate-600">[{s.section}]</div>}
+                                </td>
+                                <td className="py-2 pr-4 font-mono text-xs text-slate-300 break-all">
+                                    {s.value}
+                                    {s.overriddenIniValue !== undefined && s.overriddenIniValue !== null && (
+                                        <div className={cn('mt-1', s.conflict ? 'text-orange-400' : 'text-slate-600')}>
+                                            INI: <span className="line-through">{s.overriddenIniValue}</span>
+                                        </div>
+                                    )}
+                                </td>
+                                <td className="py-2">
+                                    <span className={cn('px-2 py-0.5 rounded text-xs', SOURCE_STYLES[s.source])}>
+                                        {s.source === 'LaunchArgs' ? 'Launch args' : `${s.source}.ini`}
+                                    </span>
+                                </td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    );
+};
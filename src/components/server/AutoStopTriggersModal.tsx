@@ -0,0 +1,183 @@
+import { useState, useEffect } from 'react';
+import { X, Plus, Trash2, Zap, Loader2 } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { cn } from '../../utils/helpers';
+import { getAutoStopTriggers, setAutoStopTriggers } from '../../utils/tauri';
+import type { AutoStopTrigger, TriggerEvent, Server } from '../../types';
+
+interface AutoStopTriggersModalProps {
+    server: Server;
+    onClose: () => void;
+}
+
+const EVENTS: TriggerEvent[] = ['create', 'modify', 'remove', 'rename'];
+
+// Starting points for common setups
+const PRESETS: { label: string; trigger: AutoStopTrigger }[] = [
+    {
+        label: 'Marker file',
+        trigger: { matchType: 'file', pattern: 'asm-stop.request', events: ['create'], ignoreDuringStartup: false, consume: true },
+    },
+    {
+        label: 'Save completed',
+        trigger: { matchType: 'glob', pattern: 'ShooterGame/Saved/SavedArks/*.ark', events: ['rename'], ignoreDuringStartup: true, consume: false },
+    },
+    {
+        label: 'Config edited',
+        trigger: { matchType: 'glob', pattern: 'ShooterGame/Saved/Config/WindowsServer/*.ini', events: ['create', 'modify'], ignoreDuringStartup: true, consume: false },
+    },
+];
+
+export default function AutoStopTriggersModal({ server, onClose }: AutoStopTriggersModalProps) {
+    const [triggers, setTriggers] = useState<AutoStopTrigger[]>([]);
+    const [isLoading, setIsLoading] = useState(true);
+    const [isSaving, setIsSaving] = useState(false);
+
+    useEffect(() => {
+        getAutoStopTriggers(server.id)
+            .then(setTriggers)
+            .catch(error => toast.error(`Failed to load triggers: ${error}`))
+            .finally(() => setIsLoading(false));
+    }, [server.id]);
+
+    const update = (index: number, patch: Partial<AutoStopTrigger>) => {
+        setTriggers(prev => prev.map((t, i) => (i === index ? { ...t, ...patch } : t)));
+    };
+
+    const toggleEvent = (index: number, event: TriggerEvent) => {
+        const events = triggers[index].events;
+        update(index, { events: events.includes(event) ? events.filter(e => e !== event) : [...events, event] });
+    };
+
+    const save = async (next: AutoStopTrigger[]) => {
+        setIsSaving(true);
+        try {
+            setTriggers(await setAutoStopTriggers(server.id, next));
+            toast.success(next.length === 0 ? 'Default triggers restored' : 'Auto-stop triggers saved');
+        } catch (error) {
+            toast.error(`${error}`);
+        } finally {
+            setIsSaving(false);
+        }
+    };
+
+    return (
+        <div className="fixed inset-0 bg-black/60 backdrop-blur-sm flex items-center justify-center z-50 animate-in fade-in duration-200">
+            <div className="bg-slate-900 border border-slate-700 rounded-2xl shadow-2xl w-full max-w-2xl mx-4 overflow-hidden">
+                <div className="flex items-center justify-between p-5 border-b border-slate-700/50">
+                    <div className="flex items-center gap-3">
+                        <div className="p-2 bg-red-500/10 rounded-lg">
+                            <Zap className="w-5 h-5 text-red-400" />
+                        </div>
+                        <div>
+                            <h2 className="text-lg font-bold text-white">Auto-Stop Triggers</h2>
+                            <p className="text-sm text-slate-400">{server.name} — paths are relative to the install folder</p>
+                        </div>
+                    </div>
+                    <button onClick={onClose} className="p-2 hover:bg-slate-800 rounded-lg transition-colors">
+                        <X className="w-5 h-5 text-slate-400" />
+                    </button>
+                </div>
+
+                <div className="p-5 space-y-3 max-h-[60vh] overflow-y-auto">
+                    {isLoading ? (
+                        <Loader2 className="w-5 h-5 animate-spin text-slate-400 mx-auto" />
+                    ) : (
+                        triggers.map((trigger, i) => (
+                            <div key={i} className="bg-slate-800/40 border border-slate-700/50 rounded-xl p-3 space-y-2">
+                                <div className="flex items-center gap-2">
+                                    <select
+                                        value={trigger.matchType}
+                                        onChange={(e) => update(i, { matchType: e.target.value as AutoStopTrigger['matchType'] })}
+                                        className="bg-slate-900 border border-slate-700 rounded-lg px-2 py-1.5 text-sm text-white"
+                                    >
+                                        <option value="file">File</option>
+                                        <option value="glob">Glob</option>
+                                    </select>
+                                    <input
+                                        type="text"
+                                        value={trigger.pattern}
+                                        onChange={(e) => update(i, { pattern: e.target.value })}
+                                        placeholder={trigger.matchType === 'glob' ? 'ShooterGame/Saved/**/*.ini' : 'path/to/file'}
+                                        className="flex-1 bg-slate-900 border border-slate-700 rounded-lg px-3 py-1.5 text-sm text-white font-mono focus:outline-none focus:ring-2 focus:ring-sky-500"
+                                    />
+                                    <button
+                                        onClick={() => setTriggers(prev => prev.filter((_, j) => j !== i))}
+                                        className="p-1.5 hover:bg-red-600/20 text-red-400 rounded transition-colors"
+                                    >
+                                        <Trash2 className="w-4 h-4" />
+                                    </button>
+                                </div>
+                                <div className="flex flex-wrap items-center gap-2 text-xs">
+                                    {EVENTS.map(event => (
+                                        <button
+                                            key={event}
+                                            onClick={() => toggleEvent(i, event)}
+                                            className={cn(
+                                                "px-2 py-1 rounded-md border transition-colors",
+                                                trigger.events.includes(event)
+                                                    ? "bg-sky-500/20 border-sky-500/40 text-sky-300"
+                                                    : "bg-slate-900 border-slate-700 text-slate-500"
+                                            )}
+                                        >
+                                            {event}
+                                        </button>
+                                    ))}
+                                    <label className="flex items-center gap-1 ml-2 text-slate-400">
+                                        <input
+                                            type="checkbox"
+                                            checked={trigger.ignoreDuringStartup}
+                                            onChange={(e) => update(i, { ignoreDuringStartup: e.target.checked })}
+                                            className="accent-sky-500"
+                                        />
+                                        Ignore while starting
+                                    </label>
+                                    <label className="flex items-center gap-1 text-slate-400">
+                                        <input
+                                            type="checkbox"
+                                            checked={trigger.consume}
+                                            onChange={(e) => update(i, { consume: e.target.checked })}
+                                            className="accent-sky-500"
+                                        />
+                                        Delete file after
+                                    </label>
+                                </div>
+                            </div>
+                        ))
+                    )}
+
+                    <div className="flex flex-wrap items-center gap-2 pt-1">
+                        <span className="text-xs text-slate-500">Add:</span>
+                        {PRESETS.map(preset => (
+                            <button
+                                key={preset.label}
+                                onClick={() => setTriggers(prev => [...prev, { ...preset.trigger }])}
+                                className="flex items-center gap-1 px-2 py-1 bg-slate-800 hover:bg-slate-700 text-slate-300 rounded-md text-xs transition-colors"
+                            >
+                                <Plus className="w-3 h-3" />
+                                {preset.label}
+                            </button>
+                        ))}
+                    </div>
+                </div>
+
+                <div className="flex items-center justify-between p-5 border-t border-slate-700/50">
+                    <button
+                        onClick={() => save([])}
+                        disabled={isSaving}
+                        className="text-sm text-slate-400 hover:text-white transition-colors disabled:opacity-50"
+                    >
+                        Restore defaults
+                    </button>
+                    <button
+                        onClick={() => save(triggers)}
+                        disabled={isSaving || isLoading || triggers.length === 0}
+                        className="px-4 py-2 bg-sky-600 hover:bg-sky-500 disabled:opacity-50 text-white rounded-lg text-sm font-medium transition-colors"
+                    >
+                        {isSaving ? 'Saving...' : 'Save Triggers'}
+                    </button>
+                </div>
+            </div>
+        </div>
+    );
+}
@@ -0,0 +1,108 @@
+import { useState, useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { MessageSquare, Send } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { cn } from '../../utils/helpers';
+import { rconSendChat } from '../../utils/tauri';
+import type { ChatMessage } from '../../types';
+
+// Lines kept in the panel; older ones scroll away
+const MAX_MESSAGES = 300;
+
+interface ChatPanelProps {
+    serverId: number;
+    isOnline: boolean;
+}
+
+// Live in-game chat relayed from the backend's GetChat poller
+export default function ChatPanel({ serverId, isOnline }: ChatPanelProps) {
+    const [messages, setMessages] = useState<ChatMessage[]>([]);
+    const [draft, setDraft] = useState('');
+    const [isSending, setIsSending] = useState(false);
+    const listRef = useRef<HTMLDivElement>(null);
+
+    useEffect(() => {
+        setMessages([]);
+        const unlisten = listen<ChatMessage>('chat-message', (event) => {
+            if (event.payload.serverId !== serverId) return;
+            setMessages(prev => [...prev, event.payload].slice(-MAX_MESSAGES));
+        });
+        return () => { unlisten.then(fn => fn()); };
+    }, [serverId]);
+
+    useEffect(() => {
+        if (listRef.current) {
+            listRef.current.scrollTop = listRef.current.scrollHeight;
+        }
+    }, [messages]);
+
+    const send = async () => {
+        const message = draft.trim();
+        if (!message || isSending) return;
+
+        setIsSending(true);
+        try {
+            await rconSendChat(serverId, message);
+            setDraft('');
+        } catch (error) {
+            toast.error(`Failed to send chat: ${error}`);
+        } finally {
+            setIsSending(false);
+        }
+    };
+
+    return (
+        <div className="glass-panel rounded-2xl p-4 flex flex-col" style={{ height: '360px' }}>
+            <h3 className="text-lg font-semibold text-white flex items-center gap-2 mb-4">
+                <MessageSquare className="w-5 h-5 text-amber-400" />
+                In-Game Chat
+            </h3>
+
+            <div ref={listRef} className="flex-1 bg-slate-950 rounded-xl p-4 text-sm overflow-y-auto mb-4 space-y-1">
+                {messages.length === 0 ? (
+                    <p className="text-slate-600 italic">
+                        {isOnline
+                            ? 'Waiting for chat messages...'
+                            : 'Chat is relayed while the server is online.'}
+                    </p>
+                ) : (
+                    messages.map((msg, i) => (
+                        <div key={i} className="flex gap-2">
+                            <span className="text-slate-600 text-xs font-mono shrink-0 pt-0.5">
+                                {new Date(msg.timestamp).toLocaleTimeString()}
+                            </span>
+                            {msg.sender && (
+                                <span className={cn(
+                                    "font-medium shrink-0",
+                                    msg.fromManager ? "text-amber-400" : "text-cyan-400"
+                                )}>
+                                    {msg.sender}:
+                                </span>
+                            )}
+                            <span className="text-slate-300 break-words">{msg.message}</span>
+                        </div>
+                    ))
+                )}
+            </div>
+
+            <div className="flex items-center gap-3 bg-slate-900 rounded-xl px-4 py-3 border border-slate-700">
+                <input
+                    type="text"
+                    value={draft}
+                    onChange={(e) => setDraft(e.target.value)}
+                    onKeyDown={(e) => { if (e.key === 'Enter') send(); }}
+                    placeholder={isOnline ? 'Message all players...' : 'Server is offline'}
+                    disabled={!isOnline || isSending}
+                    className="flex-1 bg-transparent text-white focus:outline-none placeholder:text-slate-600 disabled:cursor-not-allowed"
+                />
+                <button
+                    onClick={send}
+                    disabled={!isOnline || isSending || !draft.trim()}
+                    className="p-2 bg-amber-600/20 hover:bg-amber-600/30 text-amber-400 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                >
+                    <Send className="w-4 h-4" />
+                </button>
+            </div>
+        </div>
+    );
+}
@@ -6,7 +6,7 @@ import {
     Copy, ArrowDownToLine, Clock
 } from 'lucide-react';
 import { useServerStore } from '../../stores/serverStore';
-import { installServer, InstallServerParams, selectFolder } from '../../utils/tauri';
+import { installServer, installServerFromExisting, cancelInstall, setCrossplayPlatforms, InstallServerParams, selectFolder } from '../../utils/tauri';
 import toast from 'react-hot-toast';
 import type { ServerType } from '../../types';
 import { listen } from '@tauri-apps/api/event';
@@ -62,7 +62,7 @@ const STEPS = [
 ];
 
 export default function InstallServerDialog({ onClose }: Props) {
-    const { addServer } = useServerStore();
+    const { servers, addServer } = useServerStore();
     const [step, setStep] = useState(1);
     const [isInstalling, setIsInstalling] = useState(false);
     const [progress, setProgress] = useState<InstallProgress | null>(null);
@@ -205,7 +205,13 @@ export default function InstallServerDialog({ onClose }: Props) {
             isError: false,
         });
         try {
+            if (formData.copyFromServerId) {
+                await installServerFromExisting(formData.copyFromServerId, formData.installPath);
+            }
             const server = await installServer(formData);
+            if (formData.crossplay) {
+                server.config.crossplayPlatforms = await setCrossplayPlatforms(server.id, ['ALL']);
+            }
             addServer(server);
             setTimeout(() => onClose(), 2000);
         } catch (error) {
@@ -216,6 +222,15 @@ export default function InstallServerDialog({ onClose }: Props) {
                 isComplete: false,
                 isError: true,
             });
+            setIsInstalling(false);
+        }
+    };
+
+    const handleCancelInstall = async () => {
+        try {
+            await cancelInstall({ installPath: formData.installPath });
+        } catch (error) {
+            toast.error(`Failed to cancel: ${error}`);
         }
     };
 
@@ -514,6 +529,28 @@ export default function InstallServerDialog({ onClose }: Props) {
                                         </p>
                                     </div>
 
+                                    {servers.length > 0 && (
+                                        <div>
+                                            <label className="flex items-center gap-2 text-sm font-medium text-slate-300 mb-2">
+                                                <Copy className="w-4 h-4" />
+                                                Game Files
+                                            </label>
+                                            <select
+                                                value={formData.copyFromServerId ?? ''}
+                                                onChange={(e) => setFormData({ ...formData, copyFromServerId: e.target.value ? Number(e.target.value) : undefined })}
+                                                className="w-full px-4 py-3 bg-slate-800/50 border border-slate-700/50 rounded-xl text-white text-sm focus:outline-none focus:ring-2 focus:ring-white/20 focus:border-transparent transition-all"
+                                            >
+                                                <option value="">Download with SteamCMD</option>
+                                                {servers.map(s => (
+                                                    <option key={s.id} value={s.id}>Copy from {s.name}</option>
+                                                ))}
+                                            </select>
+                                            <p className="text-xs text-slate-500 mt-1">
+                                                Copying skips the ~10 GB download; saves, configs and mods are not copied.
+                                            </p>
+                                        </div>
+                                    )}
+
                                     {/* PvE/PvP Toggle */}
                                     <div>
                                         <label className="flex items-center gap-2 text-sm font-medium text-slate-300 mb-3">
@@ -839,14 +876,23 @@ export default function InstallServerDialog({ onClose }: Props) {
 
                 {/* Footer Buttons - Fixed at bottom */}
                 <div className="p-4 sm:p-6 bg-slate-900 border-t border-slate-700/50 flex justify-between items-center relative z-20">
-                    <button
-                        onClick={prevStep}
-                        className="flex items-center gap-2 px-4 sm:px-6 py-2.5 sm:py-3 rounded-xl hover:bg-white/5 text-slate-400 hover:text-white transition-all font-medium text-sm sm:text-base disabled:opacity-50 disabled:cursor-not-allowed"
-                        disabled={isInstalling}
-                    >
-                        <ArrowLeft className="w-4 h-4 sm:w-5 sm:h-5" />
-                        {step === 1 ? 'Cancel' : 'Back'}
-                    </button>
+                    {isInstalling ? (
+                        <button
+                            onClick={handleCancelInstall}
+                            className="flex items-center gap-2 px-4 sm:px-6 py-2.5 sm:py-3 rounded-xl hover:bg-red-500/10 text-red-400 hover:text-red-300 transition-all font-medium text-sm sm:text-base"
+                        >
+                            <X className="w-4 h-4 sm:w-5 sm:h-5" />
+                            Cancel Install
+                        </button>
+                    ) : (
+                        <button
+                            onClick={prevStep}
+                            className="flex items-center gap-2 px-4 sm:px-6 py-2.5 sm:py-3 rounded-xl hover:bg-white/5 text-slate-400 hover:text-white transition-all font-medium text-sm sm:text-base"
+                        >
+                            <ArrowLeft className="w-4 h-4 sm:w-5 sm:h-5" />
+                            {step === 1 ? 'Cancel' : 'Back'}
+                        </button>
+                    )}
 
                     <button
                         onClick={nextStep}
@@ -0,0 +1,117 @@
+import { useState, useEffect } from 'react';
+import toast from 'react-hot-toast';
+import { Save, Eye, EyeOff } from 'lucide-react';
+import { getChatBridgeConfig, saveChatBridgeConfig } from '../../utils/tauri';
+import { useServerStore } from '../../stores/serverStore';
+import type { ChatBridgeConfig, ChatBridgeLink } from '../../types';
+
+const inputClass = "w-full px-3 py-2 bg-slate-800/50 border border-slate-700 rounded-lg text-white text-sm placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 font-mono";
+
+// Per-server mapping between in-game chat and a Discord channel
+export default function ChatBridgePanel() {
+    const { servers } = useServerStore();
+    const [config, setConfig] = useState<ChatBridgeConfig>({ botToken: '', links: [] });
+    const [showToken, setShowToken] = useState(false);
+    const [isSaving, setIsSaving] = useState(false);
+
+    useEffect(() => {
+        getChatBridgeConfig()
+            .then(setConfig)
+            .catch(error => toast.error(`Failed to load chat bridge: ${error}`));
+    }, []);
+
+    const linkFor = (serverId: number): ChatBridgeLink =>
+        config.links.find(l => l.serverId === serverId)
+        ?? { serverId, enabled: false, webhookUrl: '', channelId: '' };
+
+    const updateLink = (serverId: number, patch: Partial<ChatBridgeLink>) => {
+        setConfig(prev => {
+            const others = prev.links.filter(l => l.serverId !== serverId);
+            return { ...prev, links: [...others, { ...linkFor(serverId), ...patch }] };
+        });
+    };
+
+    const save = async () => {
+        setIsSaving(true);
+        try {
+            await saveChatBridgeConfig(config);
+            toast.success('Chat bridge saved');
+        } catch (error) {
+            toast.error(`${error}`);
+        } finally {
+            setIsSaving(false);
+        }
+    };
+
+    return (
+        <div className="space-y-4">
+            <div>
+                <label className="block text-sm font-medium text-slate-300 mb-2">Bot token (optional)</label>
+                <div className="relative">
+                    <input
+                        type={showToken ? 'text' : 'password'}
+                        value={config.botToken ?? ''}
+                        onChange={(e) => setConfig(prev => ({ ...prev, botToken: e.target.value }))}
+                        placeholder="Needed to relay Discord messages into the game"
+                        className={inputClass}
+                    />
+                    <button
+                        onClick={() => setShowToken(!showToken)}
+                        className="absolute right-3 top-1/2 -translate-y-1/2 text-slate-400 hover:text-white"
+                    >
+                        {showToken ? <EyeOff className="w-4 h-4" /> : <Eye className="w-4 h-4" />}
+                    </button>
+                </div>
+                <p className="text-xs text-slate-500 mt-1">The bot needs the Read Message History permission and the Message Content intent in the linked channels.</p>
+            </div>
+
+            {servers.length === 0 ? (
+                <p className="text-slate-500 text-sm">No servers yet.</p>
+            ) : (
+                servers.map(server => {
+                    const link = linkFor(server.id);
+                    return (
+                        <div key={server.id} className="bg-slate-800/30 border border-slate-700/50 rounded-xl p-4 space-y-3">
+                            <label className="flex items-center gap-3 cursor-pointer">
+                                <input
+                                    type="checkbox"
+                                    checked={link.enabled}
+                                    onChange={(e) => updateLink(server.id, { enabled: e.target.checked })}
+                                    className="w-4 h-4 accent-sky-500"
+                                />
+                                <span className="text-sm font-medium text-white">{server.name}</span>
+                            </label>
+                            {link.enabled && (
+                                <div className="grid md:grid-cols-2 gap-3">
+                                    <input
+                                        type="text"
+                                        value={link.webhookUrl ?? ''}
+                                        onChange={(e) => updateLink(server.id, { webhookUrl: e.target.value })}
+                                        placeholder="Webhook URL (game → Discord)"
+                                        className={inputClass}
+                                    />
+                                    <input
+                                        type="text"
+                                        value={link.channelId ?? ''}
+                                        onChange={(e) => updateLink(server.id, { channelId: e.target.value })}
+                                        placeholder="Channel ID (Discord → game)"
+                                        className={inputClass}
+                                    />
+                                </div>
+                            )}
+                        </div>
+                    );
+                })
+            )}
+
+            <button
+                onClick={save}
+                disabled={isSaving}
+                className="px-4 py-2 bg-sky-600 hover:bg-sky-500 disabled:opacity-50 text-white rounded-lg text-sm font-medium flex items-center gap-2 transition-colors"
+            >
+                <Save className="w-4 h-4" />
+                {isSaving ? 'Saving...' : 'Save Chat Bridge'}
+            </button>
+        </div>
+    );
+}
@@ -1,7 +1,8 @@
 import { useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import toast from 'react-hot-toast';
-import { CheckCircle, Download, RefreshCw, AlertTriangle } from 'lucide-react';
+import { CheckCircle, Download, RefreshCw, AlertTriangle, Archive } from 'lucide-react';
+import { exportDiagnosticsBundle, selectFolder } from '../../utils/tauri';
 
 interface DiagnosticResult {
     steamcmd_installed: boolean;
@@ -14,6 +15,7 @@ interface DiagnosticResult {
 export default function DiagnosticsPanel() {
     const [result, setResult] = useState<DiagnosticResult | null>(null);
     const [isInstalling, setIsInstalling] = useState(false);
+    const [isExporting, setIsExporting] = useState(false);
 
     const runDiagnostics = async () => {
         const toastId = toast.loading('Running diagnostics...');
@@ -65,6 +67,22 @@ export default function DiagnosticsPanel() {
         }
     };
 
+    const exportBundle = async () => {
+        const folder = await selectFolder('Choose where to save the diagnostics bundle');
+        if (!folder) return;
+
+        setIsExporting(true);
+        const stamp = new Date().toISOString().replace(/[:.]/g, '-');
+        try {
+            const path = await exportDiagnosticsBundle(`${folder}/asa-diagnostics-${stamp}.zip`);
+            toast.success(`Diagnostics bundle saved to ${path}`, { duration: 6000 });
+        } catch (error) {
+            toast.error(`Failed to export diagnostics: ${error}`);
+        } finally {
+            setIsExporting(false);
+        }
+    };
+
     return (
         <div className="space-y-4">
             <button
@@ -75,6 +93,18 @@ export default function DiagnosticsPanel() {
                 Run System Check
             </button>
 
+            <button
+                onClick={exportBundle}
+                disabled={isExporting}
+                className="w-full py-3 bg-slate-800 hover:bg-slate-700 text-slate-200 rounded-xl transition-colors font-medium flex items-center justify-center gap-2 disabled:opacity-50 disabled:cursor-wait"
+            >
+                {isExporting ? <RefreshCw className="w-5 h-5 animate-spin" /> : <Archive className="w-5 h-5" />}
+                {isExporting ? 'Exporting...' : 'Export Diagnostics Bundle'}
+            </button>
+            <p className="text-xs text-slate-500 text-center">
+                Logs, sanitized server settings, crash reports and system info in one zip for bug reports. Passwords and API keys are redacted.
+            </p>
+
             {/* Quick Fix Actions */}
             {result && !result.steamcmd_installed && (
                 <div className="animate-in slide-in-from-top-2 duration-300">
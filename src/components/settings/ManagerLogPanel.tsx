@@ -0,0 +1,78 @@
+import { useState, useEffect } from 'react';
+import toast from 'react-hot-toast';
+import { RefreshCw, Copy } from 'lucide-react';
+import { getRecentLogs, getLogLevel, setLogLevel } from '../../utils/tauri';
+
+const LEVELS = ['error', 'warn', 'info', 'debug', 'trace'];
+
+export default function ManagerLogPanel() {
+    const [lines, setLines] = useState<string[]>([]);
+    const [level, setLevel] = useState('info');
+    const [isLoading, setIsLoading] = useState(false);
+
+    const refresh = async () => {
+        setIsLoading(true);
+        try {
+            setLines(await getRecentLogs(300));
+        } catch (error) {
+            toast.error(`Failed to read manager log: ${error}`);
+        } finally {
+            setIsLoading(false);
+        }
+    };
+
+    useEffect(() => {
+        refresh();
+        getLogLevel().then(l => setLevel(l.toLowerCase())).catch(console.error);
+    }, []);
+
+    const changeLevel = async (next: string) => {
+        try {
+            await setLogLevel(next);
+            setLevel(next);
+            toast.success(`Log level set to ${next}`);
+        } catch (error) {
+            toast.error(`Failed to set log level: ${error}`);
+        }
+    };
+
+    const copyLog = () => {
+        navigator.clipboard.writeText(lines.join('\n'));
+        toast.success('Log copied to clipboard');
+    };
+
+    return (
+        <div className="space-y-4">
+            <div className="flex items-center gap-3">
+                <label className="text-sm text-slate-400">Level</label>
+                <select
+                    value={level}
+                    onChange={(e) => changeLevel(e.target.value)}
+                    className="px-3 py-2 bg-slate-800/50 border border-slate-700 rounded-lg text-white text-sm focus:outline-none focus:ring-2 focus:ring-sky-500"
+                >
+                    {LEVELS.map(l => <option key={l} value={l}>{l}</option>)}
+                </select>
+                <div className="flex-1" />
+                <button
+                    onClick={copyLog}
+                    disabled={lines.length === 0}
+                    className="px-3 py-2 bg-slate-800 hover:bg-slate-700 disabled:opacity-50 text-slate-300 rounded-lg text-sm flex items-center gap-2 transition-colors"
+                >
+                    <Copy className="w-4 h-4" />
+                    Copy
+                </button>
+                <button
+                    onClick={refresh}
+                    disabled={isLoading}
+                    className="px-3 py-2 bg-slate-800 hover:bg-slate-700 disabled:opacity-50 text-slate-300 rounded-lg text-sm flex items-center gap-2 transition-colors"
+                >
+                    <RefreshCw className={`w-4 h-4 ${isLoading ? 'animate-spin' : ''}`} />
+                    Refresh
+                </button>
+            </div>
+            <pre className="h-72 overflow-auto bg-slate-950/80 border border-slate-800 rounded-xl p-4 text-xs text-slate-300 font-mono whitespace-pre-wrap">
+                {lines.length > 0 ? lines.join('\n') : 'No log entries yet.'}
+            </pre>
+        </div>
+    );
+}
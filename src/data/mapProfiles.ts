@@ -16,6 +16,10 @@ export interface MapProfile {
     environment: 'normal' | 'desert' | 'underground' | 'ocean' | 'space' | 'varied';
 }
 
+// CurseForge ids of the quality-of-life mods suggested for every official map
+// (kept in sync with RECOMMENDED_MODS in config_generator.rs)
+const RECOMMENDED_MODS = ['928793', '929420'];
+
 export const MAP_PROFILES: MapProfile[] = [
     {
         mapId: 'TheIsland_WP',
@@ -27,7 +31,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.0,
         harvestMultiplier: 1.0,
         tamingMultiplier: 1.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Best for beginners', 'All creatures available', 'Classic ARK experience'],
         environment: 'normal'
     },
@@ -41,7 +45,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.2,
         harvestMultiplier: 1.2,
         tamingMultiplier: 1.5,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Harsh desert climate', 'Water is scarce', 'Unique creatures: Wyvern, Phoenix'],
         environment: 'desert'
     },
@@ -55,7 +59,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.0,
         harvestMultiplier: 1.0,
         tamingMultiplier: 1.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['3x larger than The Island', 'Floating islands', 'Underwater caves'],
         environment: 'varied'
     },
@@ -69,7 +73,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.2,
         harvestMultiplier: 1.0,
         tamingMultiplier: 1.5,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['No flyers allowed', 'Radiation zones require hazard suit', 'Unique creatures: Rock Drake, Reaper'],
         environment: 'underground'
     },
@@ -83,7 +87,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.5,
         harvestMultiplier: 1.5,
         tamingMultiplier: 2.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Orbital Supply Drops', 'Element veins', 'Titans & Corrupted dinos'],
         environment: 'varied'
     },
@@ -97,7 +101,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.0,
         harvestMultiplier: 1.0,
         tamingMultiplier: 1.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Wyvern trench', 'Griffin spawns', 'Huge desert and snow biomes'],
         environment: 'varied'
     },
@@ -111,7 +115,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.0,
         harvestMultiplier: 1.0,
         tamingMultiplier: 1.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Deinonychus exclusive', 'Aberration cave', 'Beautiful landscapes'],
         environment: 'varied'
     },
@@ -125,7 +129,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.5,
         harvestMultiplier: 1.2,
         tamingMultiplier: 1.5,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Latest DLC content', 'New creatures & items', 'Advanced gameplay'],
         environment: 'space'
     },
@@ -139,7 +143,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.5,
         harvestMultiplier: 1.2,
         tamingMultiplier: 2.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Mission-based gameplay', 'HLNA companion', '5 unique biomes'],
         environment: 'varied'
     },
@@ -153,7 +157,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.5,
         harvestMultiplier: 1.2,
         tamingMultiplier: 2.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Colony ship setting', 'Eden vs Rockwell side', 'Tek strider, Maewing'],
         environment: 'space'
     },
@@ -167,7 +171,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.0,
         harvestMultiplier: 1.0,
         tamingMultiplier: 1.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Premium mod content', 'Custom creatures', 'Unique gameplay'],
         environment: 'varied'
     },
@@ -181,7 +185,7 @@ export const MAP_PROFILES: MapProfile[] = [
         xpMultiplier: 1.0,
         harvestMultiplier: 1.0,
         tamingMultiplier: 1.0,
-        recommendedMods: [],
+        recommendedMods: RECOMMENDED_MODS,
         notes: ['Premium mod content', 'Dense forests', 'Custom biomes'],
         environment: 'normal'
     }
@@ -2,20 +2,34 @@ import { useState, useEffect } from 'react';
 import {
     Database as BackupIcon, Plus, RotateCcw, Trash2, Loader2, FileArchive,
     Calendar, Clock, HardDrive, CheckCircle, XCircle, Eye, Shield,
-    Settings, ChevronDown, ChevronUp, FolderOpen, Sparkles
+    Settings, ChevronDown, ChevronUp, FolderOpen, Sparkles, CloudUpload, CloudDownload
 } from 'lucide-react';
 import { formatBytes, cn } from '../utils/helpers';
 import { invoke } from '@tauri-apps/api/core';
-import { Backup } from '../types';
+import { Backup, RestoreValidation } from '../types';
 import toast from 'react-hot-toast';
 import { useServerStore } from '../stores/serverStore';
-import { getAllServers } from '../utils/tauri';
+import { getAllServers, uploadBackup, downloadBackupFromCloud } from '../utils/tauri';
 
 interface BackupOptions {
     includeConfigs: boolean;
     includeSaves: boolean;
     includeMods: boolean;
     compress: boolean;
+    quiesce: boolean;
+    encrypt: boolean;
+}
+
+// Encrypted backups fall back to the saved key; ask the user only when that doesn't open the archive
+async function withBackupPassword<T>(run: (password?: string) => Promise<T>): Promise<T> {
+    try {
+        return await run();
+    } catch (error) {
+        if (!String(error).toLowerCase().includes('password')) throw error;
+        const password = window.prompt(`${error}. Enter the backup password:`);
+        if (!password) throw error;
+        return await run(password);
+    }
 }
 
 export default function Backups() {
@@ -33,6 +47,8 @@ export default function Backups() {
         includeSaves: true,
         includeMods: false,
         compress: true,
+        quiesce: true,
+        encrypt: false,
     });
 
     // Load servers
@@ -75,10 +91,13 @@ export default function Backups() {
                 serverId: selectedServerId,
                 backupType: 'manual',
                 options: {
-                    include_configs: backupOptions.includeConfigs,
-                    include_saves: backupOptions.includeSaves,
-                    include_mods: backupOptions.includeMods,
-                    compress: backupOptions.compress,
+                    includeConfigs: backupOptions.includeConfigs,
+                    includeSaves: backupOptions.includeSaves,
+                    includeMods: backupOptions.includeMods,
+                    includeCluster: false,
+                    compressionLevel: backupOptions.compress ? 6 : 0,
+                    quiesce: backupOptions.quiesce,
+                    encrypt: backupOptions.encrypt,
                 }
             });
             toast.success('Backup created successfully');
@@ -96,8 +115,14 @@ export default function Backups() {
         if (!confirm('Are you sure? This will overwrite current server data.')) return;
 
         try {
-            await invoke('restore_backup', { backupId: id });
-            toast.success('Server restored from backup');
+            const validation = await withBackupPassword((password) =>
+                invoke<RestoreValidation | null>('restore_backup', { backupId: id, password })
+            );
+            if (validation && !validation.ok) {
+                toast.error(`Restored, but the save may not load: ${validation.issues.join('; ')}`, { duration: 8000 });
+            } else {
+                toast.success('Server restored from backup');
+            }
         } catch (error) {
             console.error('Failed to restore backup:', error);
             toast.error(`Failed to restore: ${error}`);
@@ -117,9 +142,32 @@ export default function Backups() {
         }
     };
 
+    const handleUpload = async (id: number) => {
+        const toastId = toast.loading('Uploading backup...');
+        try {
+            await uploadBackup(id);
+            toast.success('Backup uploaded to cloud storage', { id: toastId });
+            fetchBackups();
+        } catch (error) {
+            toast.error(`Upload failed: ${error}`, { id: toastId });
+        }
+    };
+
+    const handleDownload = async (id: number) => {
+        const toastId = toast.loading('Downloading backup...');
+        try {
+            await downloadBackupFromCloud(id);
+            toast.success('Backup downloaded, ready to restore', { id: toastId });
+        } catch (error) {
+            toast.error(`Download failed: ${error}`, { id: toastId });
+        }
+    };
+
     const handleVerify = async (id: number) => {
         try {
-            const isValid = await invoke<boolean>('verify_backup', { backupId: id });
+            const isValid = await withBackupPassword((password) =>
+                invoke<boolean>('verify_backup', { backupId: id, password })
+            );
             if (isValid) {
                 toast.success('Backup verified successfully');
             } else {
@@ -243,6 +291,29 @@ export default function Backups() {
                                             className="w-5 h-5 rounded border-slate-600 bg-slate-800 text-amber-500 focus:ring-amber-500"
                                         />
                                     </label>
+                                    <label className="flex items-center justify-between cursor-pointer">
+                                        <span className="text-slate-300">Save World First (running servers)</span>
+                                        <input
+                                            type="checkbox"
+                                            checked={backupOptions.quiesce}
+                                            onChange={(e) => setBackupOptions({ ...backupOptions, quiesce: e.target.checked })}
+                                            className="w-5 h-5 rounded border-slate-600 bg-slate-800 text-amber-500 focus:ring-amber-500"
+                                        />
+                                    </label>
+                                    <label className="flex items-center justify-between cursor-pointer">
+                                        <span className="text-slate-300">Encrypt (uses backup key from Settings)</span>
+                                        <input
+                                            type="checkbox"
+                                            checked={backupOptions.encrypt}
+                                            onChange={(e) => setBackupOptions({ ...backupOptions, encrypt: e.target.checked })}
+                                            className="w-5 h-5 rounded border-slate-600 bg-slate-800 text-amber-500 focus:ring-amber-500"
+                                        />
+                                    </label>
+                                    {backupOptions.encrypt && (
+                                        <p className="text-xs text-amber-400">
+                                            Encrypted backups can only be opened with the key they were made with. If the key is lost, the backup cannot be recovered.
+                                        </p>
+                                    )}
                                 </div>
                                 <div className="p-4 border-t border-slate-800 bg-slate-800/50">
                                     <button
@@ -367,6 +438,23 @@ export default function Backups() {
                                         >
                                             <Eye className="w-4 h-4" />
                                         </button>
+                                        {backup.remoteUrl ? (
+                                            <button
+                                                onClick={() => handleDownload(backup.id)}
+                                                className="p-2 bg-slate-800 hover:bg-sky-500/20 hover:text-sky-400 text-slate-300 rounded-lg transition-colors"
+                                                title={`Download from ${backup.remoteUrl}`}
+                                            >
+                                                <CloudDownload className="w-4 h-4" />
+                                            </button>
+                                        ) : (
+                                            <button
+                                                onClick={() => handleUpload(backup.id)}
+                                                className="p-2 bg-slate-800 hover:bg-sky-500/20 hover:text-sky-400 text-slate-300 rounded-lg transition-colors"
+                                                title="Upload to cloud storage"
+                                            >
+                                                <CloudUpload className="w-4 h-4" />
+                                            </button>
+                                        )}
                                         <button
                                             onClick={() => handleVerify(backup.id)}
                                             className="p-2 bg-slate-800 hover:bg-blue-500/20 hover:text-blue-400 text-slate-300 rounded-lg transition-colors"
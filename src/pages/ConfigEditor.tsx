@@ -1,6 +1,6 @@
 
 import { useState, useEffect, useMemo } from 'react';
-import { Save, Loader2, Search, Sliders, ExternalLink, FileText, Copy, Check, RotateCcw, AlertTriangle, GraduationCap, BarChart3 } from 'lucide-react';
+import { Save, Loader2, Search, Sliders, ExternalLink, FileText, Copy, Check, RotateCcw, AlertTriangle, GraduationCap, BarChart3, Eye } from 'lucide-react';
 import { cn } from '../utils/helpers';
 import { readConfig, saveConfig, updateServerSettings } from '../utils/tauri';
 import toast from 'react-hot-toast';
@@ -14,6 +14,7 @@ import { ConfigTooltip } from '../components/config/ConfigTooltip';
 import { ArrayEditor } from '../components/config/ArrayEditor';
 import { applyPreset, ConfigPreset } from '../data/presets';
 import StatMultiplierEditor from '../components/config/StatMultiplierEditor';
+import { EffectiveConfigView } from '../components/config/EffectiveConfigView';
 
 // Field Render Component
 const ConfigInput = ({
@@ -192,7 +193,7 @@ export default function ConfigEditor() {
     const [isLoading, setIsLoading] = useState(false);
     const [activeCategory, setActiveCategory] = useState<string>('server');
     const [searchQuery, setSearchQuery] = useState('');
-    const [viewMode, setViewMode] = useState<'visual' | 'gus' | 'game' | 'levels' | 'stats'>('visual');
+    const [viewMode, setViewMode] = useState<'visual' | 'gus' | 'game' | 'levels' | 'stats' | 'effective'>('visual');
 
     const [customDinoLevel, setCustomDinoLevel] = useState(150);
     const [customPlayerLevel, setCustomPlayerLevel] = useState(105);
@@ -657,6 +658,15 @@ export default function ConfigEditor() {
                     >
                         <BarChart3 className="w-4 h-4" /> Per-Stat Multipliers
                     </button>
+                    <button
+                        onClick={() => setViewMode('effective')}
+                        className={cn(
+                            "px-4 py-1.5 rounded-md text-sm font-medium transition-all flex items-center gap-2",
+                            viewMode === 'effective' ? "bg-orange-500/10 text-orange-400" : "text-slate-400 hover:bg-white/5"
+                        )}
+                    >
+                        <Eye className="w-4 h-4" /> Effective Config
+                    </button>
 
                 </div>
             </div>
@@ -865,6 +875,8 @@ export default function ConfigEditor() {
                     </div>
 
 
+                ) : viewMode === 'effective' ? (
+                    selectedServerId ? <EffectiveConfigView serverId={selectedServerId} /> : null
                 ) : (
                     <div className="flex-1 overflow-hidden relative p-4 bg-[#0f0f0f]">
                         <div className="absolute top-6 right-8 z-10">
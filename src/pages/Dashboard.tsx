@@ -3,14 +3,14 @@ import { useNavigate } from 'react-router-dom';
 import {
   Server, Activity, Cpu, HardDrive, Zap, Terminal, Copy, Puzzle,
   Play, Square, RotateCw, Clock, Database, FileEdit,
-  Sunrise, Sun, Moon, TrendingUp
+  Sunrise, Sun, Moon, TrendingUp, OctagonX
 } from 'lucide-react';
 import { toast } from 'react-hot-toast';
 import { motion, Variants } from 'framer-motion';
 import { useServerStore } from '../stores/serverStore';
 import { useUIStore } from '../stores/uiStore';
 import { cn } from '../utils/helpers';
-import { getAllServers, getSystemInfo, startServer, stopServer, restartServer, cloneServer, transferSettings, extractSaveData } from '../utils/tauri';
+import { getAllServers, getSystemInfo, startServer, stopServer, stopAllServers, restartServer, cloneServer, transferSettings, extractSaveData } from '../utils/tauri';
 import { getVersion } from '@tauri-apps/api/app';
 import PerformanceMonitor from '../components/performance/PerformanceMonitor';
 import InstallServerDialog from '../components/server/InstallServerDialog';
@@ -95,6 +95,31 @@ export default function Dashboard() {
     }
   };
 
+  const [showStopAll, setShowStopAll] = useState(false);
+  const [isStoppingAll, setIsStoppingAll] = useState(false);
+
+  const handleStopAll = async (force: boolean) => {
+    setIsStoppingAll(true);
+    try {
+      const { results, unwatchedServerIds } = await stopAllServers(force);
+      results.filter(r => r.success).forEach(r => updateServerStatus(r.serverId, 'stopped'));
+      const failed = results.filter(r => !r.success);
+      if (failed.length > 0) {
+        toast.error(`${failed.length} server(s) could not be stopped`);
+      } else {
+        toast.success(`Stopped ${results.length} server(s)`);
+      }
+      if (unwatchedServerIds.length > 0) {
+        toast(`Auto-stop file watching paused for ${unwatchedServerIds.length} server(s)`);
+      }
+    } catch (error) {
+      toast.error(`Emergency stop failed: ${error}`);
+    } finally {
+      setIsStoppingAll(false);
+      setShowStopAll(false);
+    }
+  };
+
   const handleRestartServer = async (serverId: number) => {
     try {
       updateServerStatus(serverId, 'starting');
@@ -363,14 +388,56 @@ export default function Dashboard() {
             <Terminal className="w-5 h-5 text-sky-400" />
             Server Control Hub
           </h2>
-          <button
-            onClick={() => navigate('/servers')}
-            className="text-sm text-sky-400 hover:text-sky-300 transition-colors"
-          >
-            Manage All →
-          </button>
+          <div className="flex items-center gap-4">
+            <button
+              onClick={() => setShowStopAll(true)}
+              disabled={isStoppingAll}
+              className="flex items-center gap-1.5 text-sm text-red-400 hover:text-red-300 transition-colors disabled:opacity-50"
+            >
+              <OctagonX className="w-4 h-4" />
+              Emergency Stop
+            </button>
+            <button
+              onClick={() => navigate('/servers')}
+              className="text-sm text-sky-400 hover:text-sky-300 transition-colors"
+            >
+              Manage All →
+            </button>
+          </div>
         </div>
 
+        {showStopAll && (
+          <div className="mb-4 p-4 bg-red-500/10 border border-red-500/30 rounded-xl">
+            <p className="text-sm text-red-300 mb-3">
+              Stop every running server now? Auto-stop file watching is paused until you toggle it again or restart the manager.
+              Force kill skips the graceful close and may lose unsaved progress.
+            </p>
+            <div className="flex items-center gap-2">
+              <button
+                onClick={() => handleStopAll(false)}
+                disabled={isStoppingAll}
+                className="px-4 py-2 bg-red-600 hover:bg-red-500 disabled:opacity-50 text-white rounded-lg text-sm font-medium transition-colors"
+              >
+                {isStoppingAll ? 'Stopping...' : 'Stop All'}
+              </button>
+              <button
+                onClick={() => handleStopAll(true)}
+                disabled={isStoppingAll}
+                className="px-4 py-2 bg-red-900 hover:bg-red-800 disabled:opacity-50 text-red-100 rounded-lg text-sm font-medium transition-colors"
+              >
+                Force Kill All
+              </button>
+              <button
+                onClick={() => setShowStopAll(false)}
+                disabled={isStoppingAll}
+                className="px-4 py-2 bg-slate-800 hover:bg-slate-700 text-slate-300 rounded-lg text-sm transition-colors"
+              >
+                Cancel
+              </button>
+            </div>
+          </div>
+        )}
+
         {servers.length === 0 ? (
           <div className="text-center py-12 border-2 border-dashed border-slate-700/50 rounded-xl">
             <Server className="w-12 h-12 text-slate-600 mx-auto mb-3" />
@@ -398,7 +465,9 @@ export default function Dashboard() {
                       server.status === 'stopped' && 'bg-slate-500',
                       server.status === 'crashed' && 'bg-red-500',
                       server.status === 'starting' && 'bg-yellow-500 animate-pulse',
-                      server.status === 'updating' && 'bg-blue-500 animate-pulse'
+                      server.status === 'updating' && 'bg-blue-500 animate-pulse',
+                      server.status === 'stopping' && 'bg-orange-500 animate-pulse',
+                      server.status === 'missing' && 'bg-red-900'
                     )} />
                   </div>
                   <div>
@@ -455,7 +524,9 @@ export default function Dashboard() {
                     server.status === 'stopped' && 'bg-slate-500/10 text-slate-400 border-slate-500/20',
                     server.status === 'crashed' && 'bg-red-500/10 text-red-400 border-red-500/20',
                     server.status === 'starting' && 'bg-yellow-500/10 text-yellow-400 border-yellow-500/20',
-                    server.status === 'updating' && 'bg-blue-500/10 text-blue-400 border-blue-500/20'
+                    server.status === 'updating' && 'bg-blue-500/10 text-blue-400 border-blue-500/20',
+                    server.status === 'stopping' && 'bg-orange-500/10 text-orange-400 border-orange-500/20',
+                    server.status === 'missing' && 'bg-red-900/20 text-red-300 border-red-900/40'
                   )}>
                     <span>{server.status.toUpperCase()}</span>
                     {server.status === 'running' && server.reachability && (
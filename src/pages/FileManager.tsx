@@ -220,11 +220,11 @@ export default function FileManager() {
 
     const deleteItem = async () => {
         if (!selectedFile) return;
-        if (!confirm(`Are you sure you want to delete "${selectedFile.name}"? This cannot be undone.`)) return;
+        if (!confirm(`Move "${selectedFile.name}" to the Recycle Bin?`)) return;
 
         try {
-            await invoke('delete_item', { path: selectedFile.path });
-            toast.success('Item deleted');
+            await invoke('delete_item', { path: selectedFile.path, toTrash: true });
+            toast.success('Item moved to Recycle Bin');
             loadDirectory(currentPath, false);
             setSelectedFile(null);
         } catch (err) {
@@ -2,9 +2,8 @@ import React, { useState, useEffect, useRef } from 'react';
 import { Send, Search, Download, Pause, Play, Trash2, Terminal, Users, Save, Radio, AlertTriangle, Info, Bug, RefreshCw } from 'lucide-react';
 import { cn } from '../utils/helpers';
 import { useServerStore } from '../stores/serverStore';
-import { getAllServers, startLogWatcher } from '../utils/tauri';
+import { getAllServers, startLogWatcher, invoke } from '../utils/tauri';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
-import { invoke } from '@tauri-apps/api/core';
 import toast from 'react-hot-toast';
 
 interface LogEntry {
@@ -92,7 +91,7 @@ export default function LogsConsole() {
                     const line = event.payload.line;
                     const entry: LogEntry = {
                         timestamp: parseTimestamp(line),
-                        level: parseLogLevel(line),
+                        level: event.payload.is_stderr ? 'error' : parseLogLevel(line),
                         message: line.replace(/\[\d{4}\.\d{2}\.\d{2}-\d{2}\.\d{2}\.\d{2}:\d{3}\]\[\s*\d+\]/, '').trim(),
                         raw: line,
                     };
@@ -1,11 +1,22 @@
-import React, { useState, useEffect } from 'react';
-import { Search, Download, Check, X, Loader2, Package, ExternalLink, Save, BookOpen, AlertTriangle, FileText, Terminal, Copy, Info, ListChecks, Square, CheckSquare, ArrowUp, ArrowDown, Trash2, Power } from 'lucide-react';
+import React, { useState, useEffect, useRef } from 'react';
+import { Search, Download, Check, X, Loader2, Package, ExternalLink, Save, BookOpen, AlertTriangle, FileText, Terminal, Copy, Info, ListChecks, Square, CheckSquare, ArrowUp, ArrowDown, Trash2, Power, RefreshCw } from 'lucide-react';
 import { cn } from '../utils/helpers';
-import { searchMods, installMod, generateModConfig, applyModsToServer, getModInstallInstructions, getInstalledMods, updateModOrder, uninstallMod, toggleMod, getModDescription, copyModsToServer, type ModConfigPreview } from '../utils/tauri';
+import { searchMods, installMod, generateModConfig, applyModsToServer, getModInstallInstructions, getInstalledMods, refreshModMetadata, updateModOrder, uninstallMod, toggleMod, getModDescription, copyModsToServer, type ModConfigPreview } from '../utils/tauri';
 import { ModInfo } from '../types';
 import toast from 'react-hot-toast';
 import { invoke } from '@tauri-apps/api/core';
 
+// Dotted game version comparison ("1.2.10" > "1.2.9")
+function compareGameVersions(a: string, b: string): number {
+    const pa = a.split('.').map(p => parseInt(p, 10) || 0);
+    const pb = b.split('.').map(p => parseInt(p, 10) || 0);
+    for (let i = 0; i < Math.max(pa.length, pb.length); i++) {
+        const diff = (pa[i] ?? 0) - (pb[i] ?? 0);
+        if (diff !== 0) return diff;
+    }
+    return 0;
+}
+
 interface ServerBasic {
     id: number;
     name: string;
@@ -13,6 +24,9 @@ interface ServerBasic {
 
 export default function ModManager() {
     const [searchQuery, setSearchQuery] = useState('');
+    // Bumped by the refresh button; the next search skips the backend's result cache
+    const [searchRefresh, setSearchRefresh] = useState(0);
+    const forceNextSearch = useRef(false);
     const [activeTab, setActiveTab] = useState<'available' | 'installed'>('available');
 
     // Available Mods State
@@ -22,6 +36,7 @@ export default function ModManager() {
     const [installedMods, setInstalledMods] = useState<ModInfo[]>([]);
 
     const [isLoading, setIsLoading] = useState(false);
+    const [isRefreshingMetadata, setIsRefreshingMetadata] = useState(false);
     const [servers, setServers] = useState<ServerBasic[]>([]);
     const [selectedServerId, setSelectedServerId] = useState<number | null>(null);
 
@@ -102,6 +117,26 @@ export default function ModManager() {
         }
     };
 
+    const handleRefreshMetadata = async () => {
+        if (!selectedServerId) return;
+        setIsRefreshingMetadata(true);
+        try {
+            setInstalledMods(await refreshModMetadata(selectedServerId));
+            toast.success('Mod info updated from CurseForge');
+        } catch (error) {
+            console.error('Failed to refresh mod metadata:', error);
+            toast.error(`Failed to refresh mod info: ${error}`);
+        } finally {
+            setIsRefreshingMetadata(false);
+        }
+    };
+
+    // Newest game version any installed mod targets; mods behind it likely predate the current ASA build
+    const latestGameVersion = installedMods.reduce<string | undefined>(
+        (latest, mod) => mod.gameVersion && (!latest || compareGameVersions(mod.gameVersion, latest) > 0) ? mod.gameVersion : latest,
+        undefined
+    );
+
     // Auto-load mods based on tab
     useEffect(() => {
         if (!selectedServerId) return;
@@ -115,13 +150,9 @@ export default function ModManager() {
                 console.log(`🔍 Searching for "${searchTerm}"...`);
                 setIsLoading(true);
                 try {
-                    const results = await searchMods(searchTerm, 'ASA');
-                    if (results.length === 1 && results[0].id === '0') {
-                        toast.error('CurseForge API Key required!');
-                        setAvailableMods([]);
-                    } else {
-                        setAvailableMods(results);
-                    }
+                    const forceRefresh = forceNextSearch.current;
+                    forceNextSearch.current = false;
+                    setAvailableMods(await searchMods(searchTerm, 'ASA', forceRefresh));
                 } catch (error) {
                     console.error('❌ Failed to search mods:', error);
                     toast.error(`Search failed: ${error}`);
@@ -133,10 +164,15 @@ export default function ModManager() {
 
             const timeoutId = setTimeout(() => {
                 fetchAvailable();
-            }, searchQuery.length > 0 ? 500 : 0);
+            }, searchQuery.length > 0 && !forceNextSearch.current ? 500 : 0);
             return () => clearTimeout(timeoutId);
         }
-    }, [searchQuery, activeTab, selectedServerId]);
+    }, [searchQuery, activeTab, selectedServerId, searchRefresh]);
+
+    const handleRefreshSearch = () => {
+        forceNextSearch.current = true;
+        setSearchRefresh(n => n + 1);
+    };
 
     const handleToggleSelect = (modId: string) => {
         const newSelected = new Set(selectedModIds);
@@ -551,10 +587,18 @@ export default function ModManager() {
                 {activeTab === 'available' ? (
                     <div className="relative w-full md:w-96">
                         <Search className="absolute left-4 top-1/2 -translate-y-1/2 w-5 h-5 text-slate-500" />
-                        <input type="text" value={searchQuery} onChange={(e: React.ChangeEvent<HTMLInputElement>) => setSearchQuery(e.target.value)} placeholder="Search CurseForge mods..." className="w-full pl-12 pr-4 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all" />
+                        <input type="text" value={searchQuery} onChange={(e: React.ChangeEvent<HTMLInputElement>) => setSearchQuery(e.target.value)} placeholder="Search CurseForge mods..." className="w-full pl-12 pr-12 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all" />
+                        <button onClick={handleRefreshSearch} disabled={isLoading} title="Refresh results from CurseForge" className="absolute right-3 top-1/2 -translate-y-1/2 p-1.5 rounded-lg text-slate-400 hover:text-white hover:bg-slate-700 transition-colors disabled:opacity-50">
+                            <RefreshCw className={`w-4 h-4 ${isLoading ? 'animate-spin' : ''}`} />
+                        </button>
                     </div>
                 ) : (
-                    <div className="text-lg text-slate-400 font-medium">Manage Installed Mods ({installedMods.length})</div>
+                    <div className="flex items-center gap-4">
+                        <div className="text-lg text-slate-400 font-medium">Manage Installed Mods ({installedMods.length})</div>
+                        <button onClick={handleRefreshMetadata} disabled={isRefreshingMetadata || installedMods.length === 0} title="Fetch latest file versions from CurseForge" className="flex items-center space-x-2 px-3 py-1.5 rounded-lg text-sm font-medium text-slate-400 hover:text-white hover:bg-slate-800 transition-all disabled:opacity-50">
+                            <RefreshCw className={cn('w-4 h-4', isRefreshingMetadata && 'animate-spin')} /> <span>Refresh Info</span>
+                        </button>
+                    </div>
                 )}
 
                 <div className="flex gap-4 items-center">
@@ -631,8 +675,27 @@ export default function ModManager() {
                                             <h3 className="text-lg font-bold text-white">{mod.name}</h3>
                                             <span className="px-2 py-0.5 bg-slate-800 rounded text-xs font-mono text-slate-400 border border-slate-700">ID: {mod.id}</span>
                                             {!mod.enabled && <span className="px-2 py-0.5 bg-red-500/10 text-red-400 text-xs rounded border border-red-500/20">Disabled</span>}
+                                            {mod.gameVersion && latestGameVersion && compareGameVersions(mod.gameVersion, latestGameVersion) < 0 && (
+                                                <span title={`Latest file targets ASA ${mod.gameVersion}; other mods already target ${latestGameVersion}`} className="flex items-center gap-1 px-2 py-0.5 bg-amber-500/10 text-amber-400 text-xs rounded border border-amber-500/20">
+                                                    <AlertTriangle className="w-3 h-3" /> Possibly outdated
+                                                </span>
+                                            )}
+                                            {mod.latestVersion && mod.version && mod.latestVersion !== mod.version && (
+                                                <span title={`Installed ${mod.version}; CurseForge has ${mod.latestVersion}`} className="flex items-center gap-1 px-2 py-0.5 bg-sky-500/10 text-sky-400 text-xs rounded border border-sky-500/20">
+                                                    <RefreshCw className="w-3 h-3" /> Update available
+                                                </span>
+                                            )}
                                         </div>
                                         <p className="text-slate-500 text-sm mt-1 line-clamp-1">{mod.description?.replace(/<[^>]*>?/gm, '') || 'No description'}</p>
+                                        {(mod.version || mod.gameVersion || mod.lastUpdated) && (
+                                            <p className="text-xs text-slate-500 mt-1 font-mono">
+                                                {[
+                                                    mod.version,
+                                                    mod.gameVersion && `ASA ${mod.gameVersion}`,
+                                                    mod.lastUpdated && `updated ${new Date(mod.lastUpdated).toLocaleDateString()}`,
+                                                ].filter(Boolean).join(' · ')}
+                                            </p>
+                                        )}
                                     </div>
 
                                     {/* Actions */}
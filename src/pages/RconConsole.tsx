@@ -11,12 +11,15 @@ import {
     UserX,
     Ban,
     Clock,
-    RefreshCw
+    RefreshCw,
+    Bug
 } from 'lucide-react';
 import { cn } from '../utils/helpers';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke, getAllSettings, rconSendRaw } from '../utils/tauri';
+import type { RawRconExchange } from '../types';
 import toast from 'react-hot-toast';
 import { useServerStore } from '../stores/serverStore';
+import ChatPanel from '../components/server/ChatPanel';
 
 interface RconPlayer {
     id: number;
@@ -37,6 +40,21 @@ interface CommandHistoryEntry {
     success: boolean;
 }
 
+// Packet-by-packet dump of a raw exchange for the terminal
+function formatRawExchange(exchange: RawRconExchange): string {
+    const lines = [
+        `Reply: ${exchange.responsePackets} packet(s), ended by ${exchange.endedBy} after ${exchange.elapsedMs}ms`,
+        '',
+    ];
+    for (const packet of exchange.packets) {
+        const arrow = packet.direction === 'sent' ? '→' : '←';
+        lines.push(`${arrow} id=${packet.id} type=${packet.packetType} size=${packet.size} ${JSON.stringify(packet.body)}`);
+        if (packet.hex) lines.push(`  ${packet.hex}`);
+    }
+    lines.push('', exchange.response || '(empty reply)');
+    return lines.join('\n');
+}
+
 const QUICK_COMMANDS = [
     { label: 'Save World', command: 'SaveWorld', icon: Save },
     { label: 'List Players', command: 'ListPlayers', icon: Users },
@@ -54,6 +72,7 @@ export default function RconConsole() {
     const [commandHistory, setCommandHistory] = useState<CommandHistoryEntry[]>([]);
     const [players, setPlayers] = useState<RconPlayer[]>([]);
     const [historyIndex, setHistoryIndex] = useState(-1);
+    const [rawDebugEnabled, setRawDebugEnabled] = useState(false);
     const terminalRef = useRef<HTMLDivElement>(null);
     const inputRef = useRef<HTMLInputElement>(null);
 
@@ -64,6 +83,12 @@ export default function RconConsole() {
         }
     }, [commandHistory]);
 
+    useEffect(() => {
+        getAllSettings()
+            .then(settings => setRawDebugEnabled(settings.rconDebugEnabled))
+            .catch(() => setRawDebugEnabled(false));
+    }, []);
+
     // Select first server
     useEffect(() => {
         if (servers.length > 0 && !selectedServerId) {
@@ -143,6 +168,17 @@ export default function RconConsole() {
         }
     };
 
+    const sendRaw = async () => {
+        if (!command.trim() || !selectedServerId) return;
+
+        try {
+            const exchange = await rconSendRaw(selectedServerId, command);
+            addToHistory(`[raw] ${command}`, formatRawExchange(exchange), true);
+        } catch (error) {
+            addToHistory(`[raw] ${command}`, `Error: ${error}`, false);
+        }
+    };
+
     const refreshPlayers = async () => {
         if (!selectedServerId || !isConnected) return;
 
@@ -356,6 +392,16 @@ export default function RconConsole() {
                         >
                             <Send className="w-4 h-4" />
                         </button>
+                        {rawDebugEnabled && (
+                            <button
+                                onClick={sendRaw}
+                                disabled={!selectedServerId || !command.trim()}
+                                title="Send on a separate connection and show every packet"
+                                className="p-2 bg-amber-600/20 hover:bg-amber-600/30 text-amber-400 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                            >
+                                <Bug className="w-4 h-4" />
+                            </button>
+                        )}
                     </div>
                 </div>
 
@@ -418,6 +464,10 @@ export default function RconConsole() {
                     </div>
                 </div>
             </div>
+
+            {selectedServer && (
+                <ChatPanel serverId={selectedServer.id} isOnline={selectedServer.status === 'online'} />
+            )}
         </div>
     );
 }
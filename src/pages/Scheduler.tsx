@@ -11,18 +11,19 @@ import {
     MessageSquare,
     Server,
     HardDrive,
+    Download,
     Zap
 } from 'lucide-react';
 import { cn } from '../utils/helpers';
 import toast from 'react-hot-toast';
 import { useServerStore } from '../stores/serverStore';
 import { invoke } from '@tauri-apps/api/core';
-import { getAllServers } from '../utils/tauri';
+import { getAllServers, runScheduledTaskNow } from '../utils/tauri';
 
 interface ScheduledTask {
     id: number;
     serverId: number;
-    taskType: 'restart' | 'backup' | 'rcon-command' | 'announcement' | 'save-world' | 'destroy-wild-dinos';
+    taskType: 'restart' | 'update' | 'backup' | 'rcon-command' | 'announcement' | 'save-world' | 'destroy-wild-dinos' | 'cleanup-logs' | 'wild-dino-wipe' | 'set-time-of-day';
     cronExpression: string;
     command?: string;
     message?: string;
@@ -30,15 +31,20 @@ interface ScheduledTask {
     enabled: boolean;
     lastRun?: string;
     nextRun?: string;
+    options?: Record<string, unknown>;
 }
 
 const TASK_TYPES = [
     { value: 'restart', label: 'Server Restart', icon: RefreshCw, color: 'text-orange-400' },
+    { value: 'update', label: 'Server Update', icon: Download, color: 'text-emerald-400' },
     { value: 'backup', label: 'Auto Backup', icon: HardDrive, color: 'text-blue-400' },
     { value: 'save-world', label: 'Save World', icon: Save, color: 'text-green-400' },
     { value: 'announcement', label: 'Announcement', icon: MessageSquare, color: 'text-purple-400' },
     { value: 'destroy-wild-dinos', label: 'Destroy Wild Dinos', icon: Zap, color: 'text-red-400' },
+    { value: 'wild-dino-wipe', label: 'Wild Dino Wipe', icon: Zap, color: 'text-amber-400' },
+    { value: 'set-time-of-day', label: 'Set Time of Day', icon: Clock, color: 'text-yellow-400' },
     { value: 'rcon-command', label: 'Custom RCON', icon: Server, color: 'text-cyan-400' },
+    { value: 'cleanup-logs', label: 'Cleanup Logs', icon: Trash2, color: 'text-gray-400' },
 ];
 
 const CRON_PRESETS = [
@@ -64,12 +70,20 @@ export default function Scheduler() {
         command: string;
         message: string;
         preWarningMinutes: number;
+        saveWorld: boolean;
+        timeOfDay: string;
+        keepDays: number;
+        maxTotalMb: number;
     }>({
         taskType: 'restart',
         cronExpression: '0 */6 * * *',
         command: '',
         message: '',
         preWarningMinutes: 5,
+        saveWorld: true,
+        timeOfDay: '12:00',
+        keepDays: 14,
+        maxTotalMb: 1024,
     });
 
     // Load servers
@@ -114,6 +128,21 @@ export default function Scheduler() {
         }
     };
 
+    const runTaskNow = async (taskId: number) => {
+        try {
+            toast.loading('Running task...', { id: `run-${taskId}` });
+            const ran = await runScheduledTaskNow(taskId);
+            if (ran) {
+                toast.success('Task completed', { id: `run-${taskId}` });
+            } else {
+                toast('Task skipped (server offline?)', { id: `run-${taskId}` });
+            }
+            fetchTasks();
+        } catch (error) {
+            toast.error(`Task failed: ${error}`, { id: `run-${taskId}` });
+        }
+    };
+
     const deleteTask = async (taskId: number) => {
         try {
             await invoke('delete_scheduled_task', { taskId });
@@ -124,6 +153,20 @@ export default function Scheduler() {
         }
     };
 
+    const taskOptions = () => {
+        if (newTask.taskType === 'wild-dino-wipe') {
+            return { saveWorld: newTask.saveWorld };
+        }
+        if (newTask.taskType === 'set-time-of-day') {
+            const [hour, minute] = newTask.timeOfDay.split(':').map(Number);
+            return { hour, minute };
+        }
+        if (newTask.taskType === 'cleanup-logs') {
+            return { keepDays: newTask.keepDays, maxTotalMb: newTask.maxTotalMb };
+        }
+        return null;
+    };
+
     const addTask = async () => {
         if (!selectedServerId) return;
 
@@ -136,6 +179,7 @@ export default function Scheduler() {
                     command: newTask.command || null,
                     message: newTask.message || null,
                     preWarningMinutes: newTask.preWarningMinutes,
+                    options: taskOptions(),
                 }
             });
 
@@ -147,6 +191,10 @@ export default function Scheduler() {
                 command: '',
                 message: '',
                 preWarningMinutes: 5,
+                saveWorld: true,
+                timeOfDay: '12:00',
+                keepDays: 14,
+                maxTotalMb: 1024,
             });
             toast.success('Scheduled task created');
         } catch (error) {
@@ -265,6 +313,14 @@ export default function Scheduler() {
                                 )}
                             </div>
 
+                            <button
+                                onClick={() => runTaskNow(task.id)}
+                                className="w-full py-2 mb-1 text-sm text-blue-400 hover:bg-blue-600/10 rounded-lg transition-colors"
+                            >
+                                <Zap className="w-4 h-4 inline mr-2" />
+                                Run Now
+                            </button>
+
                             <button
                                 onClick={() => deleteTask(task.id)}
                                 className="w-full py-2 text-sm text-red-400 hover:bg-red-600/10 rounded-lg transition-colors"
@@ -368,6 +424,69 @@ export default function Scheduler() {
                                 </div>
                             )}
 
+                            {/* Wild dino wipe */}
+                            {newTask.taskType === 'wild-dino-wipe' && (
+                                <div className="space-y-3">
+                                    <div>
+                                        <label className="block text-sm font-medium text-slate-300 mb-2">Warning Message</label>
+                                        <input
+                                            type="text"
+                                            value={newTask.message}
+                                            onChange={(e) => setNewTask(prev => ({ ...prev, message: e.target.value }))}
+                                            className="w-full bg-slate-900 border border-slate-700 rounded-lg px-4 py-2 text-white focus:outline-none focus:ring-2 focus:ring-purple-500"
+                                            placeholder="Wild dinos will be wiped in {minutes} minute(s)!"
+                                        />
+                                    </div>
+                                    <label className="flex items-center gap-2 text-sm text-slate-300">
+                                        <input
+                                            type="checkbox"
+                                            checked={newTask.saveWorld}
+                                            onChange={(e) => setNewTask(prev => ({ ...prev, saveWorld: e.target.checked }))}
+                                        />
+                                        Save world after the wipe
+                                    </label>
+                                </div>
+                            )}
+
+                            {/* Set time of day */}
+                            {newTask.taskType === 'set-time-of-day' && (
+                                <div>
+                                    <label className="block text-sm font-medium text-slate-300 mb-2">In-game Time</label>
+                                    <input
+                                        type="time"
+                                        value={newTask.timeOfDay}
+                                        onChange={(e) => setNewTask(prev => ({ ...prev, timeOfDay: e.target.value }))}
+                                        className="w-full bg-slate-900 border border-slate-700 rounded-lg px-4 py-2 text-white focus:outline-none focus:ring-2 focus:ring-purple-500"
+                                    />
+                                </div>
+                            )}
+
+                            {/* Cleanup logs */}
+                            {newTask.taskType === 'cleanup-logs' && (
+                                <div className="grid grid-cols-2 gap-3">
+                                    <div>
+                                        <label className="block text-sm font-medium text-slate-300 mb-2">Keep Days</label>
+                                        <input
+                                            type="number"
+                                            value={newTask.keepDays}
+                                            onChange={(e) => setNewTask(prev => ({ ...prev, keepDays: parseInt(e.target.value) || 0 }))}
+                                            min={0}
+                                            className="w-full bg-slate-900 border border-slate-700 rounded-lg px-4 py-2 text-white focus:outline-none focus:ring-2 focus:ring-purple-500"
+                                        />
+                                    </div>
+                                    <div>
+                                        <label className="block text-sm font-medium text-slate-300 mb-2">Max Total (MB)</label>
+                                        <input
+                                            type="number"
+                                            value={newTask.maxTotalMb}
+                                            onChange={(e) => setNewTask(prev => ({ ...prev, maxTotalMb: parseInt(e.target.value) || 0 }))}
+                                            min={0}
+                                            className="w-full bg-slate-900 border border-slate-700 rounded-lg px-4 py-2 text-white focus:outline-none focus:ring-2 focus:ring-purple-500"
+                                        />
+                                    </div>
+                                </div>
+                            )}
+
                             {/* Custom Command */}
                             {newTask.taskType === 'rcon-command' && (
                                 <div>
@@ -6,8 +6,9 @@ import InstallServerDialog from '../components/server/InstallServerDialog';
 import ImportServerDialog from '../components/server/ImportServerDialog';
 import ImportNonDedicatedDialog from '../components/server/ImportNonDedicatedDialog';
 import CloneOptionsModal from '../components/server/CloneOptionsModal';
+import AutoStopTriggersModal from '../components/server/AutoStopTriggersModal';
 import ConfirmDialog from '../components/ui/ConfirmDialog';
-import { startServer, stopServer, restartServer, deleteServer, getAllServers, updateServer, startLogWatcher, cloneServer, transferSettings, extractSaveData, showServerConsole, hardcoreRetryMods, startServerNoMods, toggleServerAutomation } from '../utils/tauri';
+import { preflightCheck, startServer, stopServer, restartServer, deleteServer, getAllServers, updateServer, startLogWatcher, cloneServer, transferSettings, extractSaveData, showServerConsole, sendConsoleCommand, hardcoreRetryMods, startServerNoMods, toggleServerAutomation, errorKind } from '../utils/tauri';
 import toast from 'react-hot-toast';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { getVersion } from '@tauri-apps/api/app';
@@ -27,9 +28,11 @@ export default function ServerManager() {
     const [showInstallDialog, setShowInstallDialog] = useState(false);
     const [serverLogs, setServerLogs] = useState<Record<number, string[]>>({});
     const [expandedConsoles, setExpandedConsoles] = useState<Record<number, boolean>>({});
+    const [consoleInputs, setConsoleInputs] = useState<Record<number, string>>({});
     const consoleRefs = useRef<Record<number, HTMLDivElement | null>>({});
     const [appVersion, setAppVersion] = useState<string>('');
     const [cloneModalServer, setCloneModalServer] = useState<Server | null>(null);
+    const [triggersModalServer, setTriggersModalServer] = useState<Server | null>(null);
     const [deleteConfirmServer, setDeleteConfirmServer] = useState<Server | null>(null);
     const [showImportDialog, setShowImportDialog] = useState(false);
     const [showNonDedicatedImport, setShowNonDedicatedImport] = useState(false);
@@ -41,15 +44,15 @@ export default function ServerManager() {
         await refreshServers();
     };
 
-    const handleToggleAutomation = async (serverId: number, type: 'auto_start' | 'auto_stop' | 'intelligent_mode', current: boolean) => {
+    const handleToggleAutomation = async (serverId: number, type: 'auto_start' | 'auto_stop' | 'intelligent_mode' | 'battleye_enabled', current: boolean) => {
         try {
             await toggleServerAutomation(serverId, type, !current);
-            const label = type === 'auto_start' ? 'Auto-Start' : type === 'auto_stop' ? 'Auto-Stop' : 'Intelligent Mode';
+            const label = type === 'auto_start' ? 'Auto-Start' : type === 'auto_stop' ? 'Auto-Stop' : type === 'battleye_enabled' ? 'BattlEye' : 'Intelligent Mode';
             toast.success(`${label} ${!current ? 'Enabled' : 'Disabled'}`);
             // Optimistic update
             const updatedServers = servers.map(s => {
                 if (s.id === serverId) {
-                    const key = type === 'auto_start' ? 'autoStart' : type === 'auto_stop' ? 'autoStop' : 'intelligentMode';
+                    const key = type === 'auto_start' ? 'autoStart' : type === 'auto_stop' ? 'autoStop' : type === 'battleye_enabled' ? 'battleyeEnabled' : 'intelligentMode';
                     return {
                         ...s,
                         [key]: !current
@@ -134,6 +137,22 @@ export default function ServerManager() {
     };
 
     const handleStartServer = async (serverId: number) => {
+        // Surface every known problem up front instead of the first one start_server hits
+        const preflight = await preflightCheck(serverId).catch(() => null);
+        if (preflight) {
+            const failed = preflight.items.filter(i => !i.passed);
+            const describe = (items: typeof failed) =>
+                items.map(i => i.detail ? `${i.label}: ${i.detail}` : i.label).join('\n');
+            if (!preflight.ready) {
+                toast.error(`Preflight failed:\n${describe(failed.filter(i => i.severity === 'error'))}`, { duration: 8000 });
+                return;
+            }
+            const warnings = failed.filter(i => i.severity === 'warning');
+            if (warnings.length > 0) {
+                toast(describe(warnings), { icon: '⚠️', duration: 6000 });
+            }
+        }
+
         try {
             updateServerStatus(serverId, 'starting');
             setExpandedConsoles(prev => ({ ...prev, [serverId]: true })); // Auto-expand console
@@ -142,7 +161,8 @@ export default function ServerManager() {
             updateServerStatus(serverId, 'running');
             toast.success('Server started successfully');
         } catch (error) {
-            updateServerStatus(serverId, 'stopped');
+            // An io error here means the install folder is gone and the backend marked it missing
+            updateServerStatus(serverId, errorKind(error) === 'io' ? 'missing' : 'stopped');
             toast.error(`Failed to start server: ${error}`);
         }
     };
@@ -198,13 +218,22 @@ export default function ServerManager() {
     };
 
     const handleUpdateServer = async (serverId: number) => {
+        // Follow the backend's stages ("Backing up before update...", SteamCMD progress) in one toast
+        const toastId = toast.loading('Updating server...');
+        const unlistenProgress = await listen<{ stage: string; message: string }>('install-progress', (event) => {
+            if (event.payload.stage !== 'error') {
+                toast.loading(event.payload.message, { id: toastId });
+            }
+        });
         try {
             updateServerStatus(serverId, 'updating');
             await updateServer(serverId);
-            toast.success('Server update initiated');
+            toast.success('Server updated', { id: toastId });
         } catch (error) {
             updateServerStatus(serverId, 'stopped');
-            toast.error(`Failed to update server: ${error}`);
+            toast.error(`Failed to update server: ${error}`, { id: toastId });
+        } finally {
+            unlistenProgress();
         }
     };
 
@@ -217,6 +246,17 @@ export default function ServerManager() {
         }
     };
 
+    const handleSendConsoleCommand = async (serverId: number) => {
+        const command = (consoleInputs[serverId] || '').trim();
+        if (!command) return;
+        try {
+            await sendConsoleCommand(serverId, command);
+            setConsoleInputs(prev => ({ ...prev, [serverId]: '' }));
+        } catch (error) {
+            toast.error(`${error}`);
+        }
+    };
+
     const handleHardcoreRetry = async (serverId: number) => {
         try {
             if (!window.confirm("Deep Repair will stop the server, delete the mod cache (.temp folder), and force a fresh download. This may take longer than a normal restart. Continue?")) {
@@ -340,7 +380,9 @@ export default function ServerManager() {
                                             server.status === 'stopped' && 'bg-slate-500',
                                             server.status === 'crashed' && 'bg-red-500 shadow-[0_0_15px_rgba(239,68,68,0.5)]',
                                             server.status === 'starting' && 'bg-yellow-500 animate-pulse',
-                                            server.status === 'updating' && 'bg-blue-500 animate-pulse'
+                                            server.status === 'updating' && 'bg-blue-500 animate-pulse',
+                                            server.status === 'stopping' && 'bg-orange-500 animate-pulse',
+                                            server.status === 'missing' && 'bg-red-900'
                                         )} />
                                         {server.status === 'online' && (
                                             <div className="absolute inset-0 bg-green-500 rounded-full animate-ping opacity-20"></div>
@@ -536,7 +578,10 @@ export default function ServerManager() {
                                     <span className="text-slate-400 text-sm group-hover/toggle:text-slate-200 transition-colors">Auto-Start</span>
                                 </label>
 
-                                <label className="flex items-center gap-2 cursor-pointer group/toggle">
+                                <label
+                                    className="flex items-center gap-2 cursor-pointer group/toggle"
+                                    title="Stops the server when one of its auto-stop triggers fires. By default: GameUserSettings.ini or Game.ini is edited (ignored while it is starting up), or an asm-stop.request file is placed in the install folder"
+                                >
                                     <div className="relative">
                                         <input
                                             type="checkbox"
@@ -551,6 +596,29 @@ export default function ServerManager() {
                                         <span className="text-[10px] text-slate-500">On Config Change</span>
                                     </div>
                                 </label>
+                                <button
+                                    onClick={() => setTriggersModalServer(server)}
+                                    className="-ml-2 p-1 text-slate-500 hover:text-slate-200 hover:bg-slate-800 rounded transition-colors"
+                                    title="Configure auto-stop triggers"
+                                >
+                                    <Settings className="w-3.5 h-3.5" />
+                                </button>
+
+                                <label className="flex items-center gap-2 cursor-pointer group/toggle">
+                                    <div className="relative">
+                                        <input
+                                            type="checkbox"
+                                            className="sr-only peer"
+                                            checked={server.battleyeEnabled || false}
+                                            onChange={() => handleToggleAutomation(server.id, 'battleye_enabled', server.battleyeEnabled || false)}
+                                        />
+                                        <div className="w-9 h-5 bg-slate-700 peer-focus:outline-none rounded-full peer peer-checked:after:translate-x-full rtl:peer-checked:after:-translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:start-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-4 after:w-4 after:transition-all peer-checked:bg-amber-500"></div>
+                                    </div>
+                                    <div className="flex flex-col">
+                                        <span className="text-slate-400 text-sm group-hover/toggle:text-slate-200 transition-colors">BattlEye</span>
+                                        <span className="text-[10px] text-slate-500">Next Start</span>
+                                    </div>
+                                </label>
 
                                 <label className="flex items-center gap-2 cursor-pointer group/toggle ml-auto lg:ml-0">
                                     <div className="relative">
@@ -605,8 +673,8 @@ export default function ServerManager() {
                                                     const colonIdx = line.indexOf(':');
                                                     const prefix = colonIdx > 0 && colonIdx < 30 ? line.substring(0, colonIdx).trim() : "";
 
-                                                    // Error patterns - red
-                                                    if (line.includes("Error") || line.includes("error") ||
+                                                    // Error patterns - red (piped stderr always is)
+                                                    if (line.startsWith("[stderr]") || line.includes("Error") || line.includes("error") ||
                                                         line.includes("Failed") || line.includes("failed") ||
                                                         line.includes("Couldn't") || line.includes("No machine id")) {
                                                         colorClass = "text-red-400";
@@ -664,6 +732,20 @@ export default function ServerManager() {
                                             )}
                                         </div>
                                     )}
+
+                                    {expandedConsoles[server.id] && (server.status === 'starting' || server.status === 'running' || server.status === 'online') && (
+                                        <div className="flex items-center gap-2 mt-2 bg-slate-950 rounded-lg px-3 py-2 border border-slate-800">
+                                            <span className="text-slate-600 font-mono text-xs">&gt;</span>
+                                            <input
+                                                type="text"
+                                                value={consoleInputs[server.id] || ''}
+                                                onChange={(e) => setConsoleInputs(prev => ({ ...prev, [server.id]: e.target.value }))}
+                                                onKeyDown={(e) => { if (e.key === 'Enter') handleSendConsoleCommand(server.id); }}
+                                                placeholder="Console command (needs console input enabled in Settings)"
+                                                className="flex-1 bg-transparent text-white font-mono text-xs focus:outline-none placeholder:text-slate-600"
+                                            />
+                                        </div>
+                                    )}
                                 </div>
                             )}
                         </div>
@@ -699,6 +781,13 @@ export default function ServerManager() {
                 />
             )}
 
+            {triggersModalServer && (
+                <AutoStopTriggersModal
+                    server={triggersModalServer}
+                    onClose={() => setTriggersModalServer(null)}
+                />
+            )}
+
             {/* Delete Confirmation Dialog */}
             <ConfirmDialog
                 isOpen={!!deleteConfirmServer}
@@ -1,9 +1,12 @@
 import { useState, useEffect } from 'react';
 import { Save, Key, Lock, CheckCircle, AlertCircle, ExternalLink, RefreshCw, Download, Clock, History, Undo2 } from 'lucide-react';
-import { getSetting, setSetting } from '../utils/tauri';
+import { getAllSettings, getSetting, setSetting, validateCurseforgeKey } from '../utils/tauri';
+import type { KeyValidation } from '../types';
 import toast from 'react-hot-toast';
 import { invoke } from '@tauri-apps/api/core';
 import DiagnosticsPanel from '../components/settings/DiagnosticsPanel';
+import ManagerLogPanel from '../components/settings/ManagerLogPanel';
+import ChatBridgePanel from '../components/settings/ChatBridgePanel';
 import PortValidator from '../components/settings/PortValidator';
 import PortForwardingGuide from '../components/settings/PortForwardingGuide';
 import { manualCheckForUpdates, getCurrentVersion } from '../components/UpdateChecker';
@@ -21,9 +24,19 @@ import {
 export default function Settings() {
     const [curseforgeApiKey, setCurseforgeApiKey] = useState('');
     const [steamApiKey, setSteamApiKey] = useState('');
+    const [backupEncryptionKey, setBackupEncryptionKey] = useState('');
+    const [gracefulShutdownTimeoutSecs, setGracefulShutdownTimeoutSecs] = useState(15);
+    const [terminateTimeoutSecs, setTerminateTimeoutSecs] = useState(10);
+    const [stopSettleSecs, setStopSettleSecs] = useState(3);
+    const [rconTimeoutSecs, setRconTimeoutSecs] = useState(10);
+    const [rconDebugEnabled, setRconDebugEnabled] = useState(false);
+    const [consoleStdinEnabled, setConsoleStdinEnabled] = useState(false);
+    const [chatPollSecs, setChatPollSecs] = useState(5);
     const [isLoading, setIsLoading] = useState(true);
     const [isSaving, setIsSaving] = useState(false);
     const [showCurseforgeKey, setShowCurseforgeKey] = useState(false);
+    const [curseforgeKeyCheck, setCurseforgeKeyCheck] = useState<KeyValidation | null>(null);
+    const [isCheckingKey, setIsCheckingKey] = useState(false);
     const [showSteamKey, setShowSteamKey] = useState(false);
 
     const [activeTab, setActiveTab] = useState<'api' | 'network' | 'updates'>('api');
@@ -49,12 +62,18 @@ export default function Settings() {
 
     const loadSettings = async () => {
         try {
-            const [curseforgeKey, steamKey] = await Promise.all([
-                getSetting('curseforge_api_key'),
-                getSetting('steam_api_key')
-            ]);
-            if (curseforgeKey) setCurseforgeApiKey(curseforgeKey);
-            if (steamKey) setSteamApiKey(steamKey);
+            const settings = await getAllSettings();
+            if (settings.curseforgeApiKey) setCurseforgeApiKey(settings.curseforgeApiKey);
+            if (settings.steamApiKey) setSteamApiKey(settings.steamApiKey);
+            setGracefulShutdownTimeoutSecs(settings.gracefulShutdownTimeoutSecs);
+            setTerminateTimeoutSecs(settings.terminateTimeoutSecs);
+            setStopSettleSecs(settings.stopSettleSecs);
+            setRconTimeoutSecs(settings.rconTimeoutSecs);
+            setRconDebugEnabled(settings.rconDebugEnabled);
+            setConsoleStdinEnabled(settings.consoleStdinEnabled);
+            setChatPollSecs(settings.chatPollSecs);
+            const backupKey = await getSetting('backup_encryption_key');
+            if (backupKey) setBackupEncryptionKey(backupKey);
 
             // Load update settings
             setUpdateSettingsState(getUpdateSettings());
@@ -107,7 +126,15 @@ export default function Settings() {
         try {
             await Promise.all([
                 setSetting('curseforge_api_key', curseforgeApiKey),
-                setSetting('steam_api_key', steamApiKey)
+                setSetting('steam_api_key', steamApiKey),
+                setSetting('backup_encryption_key', backupEncryptionKey),
+                setSetting('graceful_shutdown_timeout_secs', String(gracefulShutdownTimeoutSecs)),
+                setSetting('terminate_timeout_secs', String(terminateTimeoutSecs)),
+                setSetting('stop_settle_secs', String(stopSettleSecs)),
+                setSetting('rcon_timeout_secs', String(rconTimeoutSecs)),
+                setSetting('rcon_debug_enabled', String(rconDebugEnabled)),
+                setSetting('console_stdin_enabled', String(consoleStdinEnabled)),
+                setSetting('chat_poll_secs', String(chatPollSecs))
             ]);
             toast.success('Settings saved successfully!');
         } catch (error) {
@@ -118,6 +145,17 @@ export default function Settings() {
         }
     };
 
+    const handleTestCurseforgeKey = async () => {
+        setIsCheckingKey(true);
+        try {
+            setCurseforgeKeyCheck(await validateCurseforgeKey(curseforgeApiKey));
+        } catch (error) {
+            setCurseforgeKeyCheck({ valid: false, reason: String(error) });
+        } finally {
+            setIsCheckingKey(false);
+        }
+    };
+
     const copyFirewallScript = () => {
         const script = `New-NetFirewallRule -DisplayName "ARK ASA Server TCP" -Direction Inbound -LocalPort 7777,7778,27015,27020 -Protocol TCP -Action Allow\nNew-NetFirewallRule -DisplayName "ARK ASA Server UDP" -Direction Inbound -LocalPort 7777,7778,27015,27020 -Protocol UDP -Action Allow`;
         navigator.clipboard.writeText(script);
@@ -278,7 +316,10 @@ export default function Settings() {
                                     <input
                                         type={showCurseforgeKey ? 'text' : 'password'}
                                         value={curseforgeApiKey}
-                                        onChange={(e) => setCurseforgeApiKey(e.target.value)}
+                                        onChange={(e) => {
+                                            setCurseforgeApiKey(e.target.value);
+                                            setCurseforgeKeyCheck(null);
+                                        }}
                                         placeholder="Enter your CurseForge API key"
                                         className="w-full pl-12 pr-4 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all font-mono"
                                     />
@@ -307,11 +348,32 @@ export default function Settings() {
                             </div>
 
                             {curseforgeApiKey && (
-                                <div className="bg-green-500/10 border border-green-500/20 rounded-xl p-4">
+                                <div className={`rounded-xl p-4 border flex items-center justify-between gap-4 ${curseforgeKeyCheck && !curseforgeKeyCheck.valid
+                                    ? 'bg-red-500/10 border-red-500/20'
+                                    : 'bg-green-500/10 border-green-500/20'}`}>
                                     <div className="flex items-center space-x-2">
-                                        <CheckCircle className="w-5 h-5 text-green-400" />
-                                        <span className="text-green-400 font-medium">API Key configured</span>
+                                        {curseforgeKeyCheck && !curseforgeKeyCheck.valid ? (
+                                            <>
+                                                <AlertCircle className="w-5 h-5 text-red-400 flex-shrink-0" />
+                                                <span className="text-red-400 font-medium">{curseforgeKeyCheck.reason || 'API key is not valid'}</span>
+                                            </>
+                                        ) : (
+                                            <>
+                                                <CheckCircle className="w-5 h-5 text-green-400" />
+                                                <span className="text-green-400 font-medium">
+                                                    {curseforgeKeyCheck?.valid ? 'API key verified with CurseForge' : 'API Key configured'}
+                                                </span>
+                                            </>
+                                        )}
                                     </div>
+                                    <button
+                                        onClick={handleTestCurseforgeKey}
+                                        disabled={isCheckingKey}
+                                        className="px-4 py-2 bg-slate-800 hover:bg-slate-700 text-slate-200 rounded-lg text-sm flex items-center gap-2 transition-colors disabled:opacity-50 flex-shrink-0"
+                                    >
+                                        <RefreshCw className={`w-4 h-4 ${isCheckingKey ? 'animate-spin' : ''}`} />
+                                        Test Key
+                                    </button>
                                 </div>
                             )}
 
@@ -326,6 +388,145 @@ export default function Settings() {
                         </div>
                     </div>
 
+                    {/* Backup Encryption Key */}
+                    <div className="glass-panel rounded-2xl p-8">
+                        <h2 className="text-2xl font-bold text-white mb-2">Backup Encryption Key</h2>
+                        <p className="text-slate-400 mb-4">
+                            Used when "Encrypt" is ticked on a backup. Encrypted backups can only be restored with the key they were made with.
+                        </p>
+                        <div className="relative">
+                            <Lock className="absolute left-4 top-1/2 -translate-y-1/2 w-5 h-5 text-slate-500" />
+                            <input
+                                type="password"
+                                value={backupEncryptionKey}
+                                onChange={(e) => setBackupEncryptionKey(e.target.value)}
+                                placeholder="Leave empty to disable encrypted backups"
+                                className="w-full pl-12 pr-4 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all font-mono"
+                            />
+                        </div>
+                        <div className="flex items-start gap-2 mt-4 bg-amber-500/10 border border-amber-500/20 rounded-xl p-4 text-sm text-amber-300">
+                            <AlertCircle className="w-4 h-4 mt-0.5 flex-shrink-0" />
+                            <span>There is no way to recover a lost key. Keep a copy somewhere safe; changing it here does not re-encrypt older backups.</span>
+                        </div>
+                    </div>
+
+                    {/* Server Shutdown */}
+                    <div className="glass-panel rounded-2xl p-8">
+                        <h2 className="text-2xl font-bold text-white mb-2 flex items-center gap-3">
+                            <Clock className="w-6 h-6 text-sky-400" />
+                            Server Shutdown
+                        </h2>
+                        <p className="text-slate-400 mb-4">
+                            Stopping a server saves the world and sends DoExit over RCON, then asks the process to close, and only force kills it if it is still running.
+                        </p>
+                        <div className="grid md:grid-cols-3 gap-4">
+                            <div>
+                                <label className="block text-sm font-medium text-slate-300 mb-2">Graceful timeout (seconds)</label>
+                                <input
+                                    type="number"
+                                    min={0}
+                                    value={gracefulShutdownTimeoutSecs}
+                                    onChange={(e) => setGracefulShutdownTimeoutSecs(Math.max(0, parseInt(e.target.value) || 0))}
+                                    className="w-full px-4 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all font-mono"
+                                />
+                                <p className="text-xs text-slate-500 mt-1">How long to wait for the server to exit after DoExit.</p>
+                            </div>
+                            <div>
+                                <label className="block text-sm font-medium text-slate-300 mb-2">Terminate timeout (seconds)</label>
+                                <input
+                                    type="number"
+                                    min={0}
+                                    value={terminateTimeoutSecs}
+                                    onChange={(e) => setTerminateTimeoutSecs(Math.max(0, parseInt(e.target.value) || 0))}
+                                    className="w-full px-4 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all font-mono"
+                                />
+                                <p className="text-xs text-slate-500 mt-1">Wait after the close request before force killing. 0 skips straight to force kill.</p>
+                            </div>
+                            <div>
+                                <label className="block text-sm font-medium text-slate-300 mb-2">Settle delay (seconds)</label>
+                                <input
+                                    type="number"
+                                    min={0}
+                                    value={stopSettleSecs}
+                                    onChange={(e) => setStopSettleSecs(Math.max(0, parseInt(e.target.value) || 0))}
+                                    className="w-full px-4 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all font-mono"
+                                />
+                                <p className="text-xs text-slate-500 mt-1">Pause after a stop before restarting or touching the server's files.</p>
+                            </div>
+                        </div>
+                    </div>
+
+                    {/* RCON */}
+                    <div className="glass-panel rounded-2xl p-8">
+                        <h2 className="text-2xl font-bold text-white mb-2 flex items-center gap-3">
+                            <Clock className="w-6 h-6 text-sky-400" />
+                            RCON
+                        </h2>
+                        <p className="text-slate-400 mb-4">
+                            Busy or frozen servers can accept a connection and never answer. Commands give up after this long instead of hanging the console.
+                        </p>
+                        <div className="grid md:grid-cols-3 gap-4">
+                            <div>
+                                <label className="block text-sm font-medium text-slate-300 mb-2">Response timeout (seconds)</label>
+                                <input
+                                    type="number"
+                                    min={1}
+                                    value={rconTimeoutSecs}
+                                    onChange={(e) => setRconTimeoutSecs(Math.max(1, parseInt(e.target.value) || 1))}
+                                    className="w-full px-4 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all font-mono"
+                                />
+                                <p className="text-xs text-slate-500 mt-1">Applies to connecting and to every command.</p>
+                            </div>
+                            <div>
+                                <label className="block text-sm font-medium text-slate-300 mb-2">Chat poll interval (seconds)</label>
+                                <input
+                                    type="number"
+                                    min={0}
+                                    value={chatPollSecs}
+                                    onChange={(e) => setChatPollSecs(Math.max(0, parseInt(e.target.value) || 0))}
+                                    className="w-full px-4 py-3 bg-slate-800/50 border border-slate-700 rounded-xl text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-sky-500 transition-all font-mono"
+                                />
+                                <p className="text-xs text-slate-500 mt-1">How often in-game chat is read from online servers. 0 turns the chat relay off.</p>
+                            </div>
+                        </div>
+                        <label className="flex items-start gap-3 mt-6 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                checked={rconDebugEnabled}
+                                onChange={(e) => setRconDebugEnabled(e.target.checked)}
+                                className="mt-1 w-4 h-4 accent-sky-500"
+                            />
+                            <span>
+                                <span className="block text-sm font-medium text-slate-300">Raw protocol debugging</span>
+                                <span className="block text-xs text-slate-500 mt-1">Adds a Raw button to the RCON console that shows every packet of a command, including how long replies were split. Packets are also written to the manager log at debug level.</span>
+                            </span>
+                        </label>
+                        <label className="flex items-start gap-3 mt-4 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                checked={consoleStdinEnabled}
+                                onChange={(e) => setConsoleStdinEnabled(e.target.checked)}
+                                className="mt-1 w-4 h-4 accent-sky-500"
+                            />
+                            <span>
+                                <span className="block text-sm font-medium text-slate-300">Console input without RCON</span>
+                                <span className="block text-xs text-slate-500 mt-1">Launches servers with a piped stdin so commands can be typed below their console output in Server Manager. Only servers started after enabling this accept input. With AsaApi the loader owns the pipe and may not pass it on.</span>
+                            </span>
+                        </label>
+                    </div>
+
+                    {/* Discord Chat Bridge */}
+                    <div className="glass-panel rounded-2xl p-8">
+                        <h2 className="text-2xl font-bold text-white mb-2 flex items-center gap-3">
+                            <span className="bg-indigo-500/10 p-2 rounded-lg text-indigo-400">💬</span>
+                            Discord Chat Bridge
+                        </h2>
+                        <p className="text-slate-400 mb-4">
+                            Mirror in-game chat to a Discord channel and, with a bot token, post channel messages back into the game. Uses the chat poll interval above.
+                        </p>
+                        <ChatBridgePanel />
+                    </div>
+
                     {/* Info Section */}
                     <div className="glass-panel rounded-2xl p-6 border-dashed">
                         <h3 className="text-lg font-medium text-white mb-3">About API Keys</h3>
@@ -381,6 +582,18 @@ export default function Settings() {
                         </div>
                     </div>
 
+                    {/* Manager Log */}
+                    <div className="glass-panel rounded-2xl p-6">
+                        <h2 className="text-2xl font-bold text-white mb-4 flex items-center gap-3">
+                            <span className="bg-sky-500/10 p-2 rounded-lg text-sky-400">📝</span>
+                            Manager Log
+                        </h2>
+                        <p className="text-slate-400 mb-4">
+                            The manager's own activity log, kept in the app data folder. Include it when reporting a problem.
+                        </p>
+                        <ManagerLogPanel />
+                    </div>
+
                     {/* Firewall Configuration */}
                     <div className="glass-panel rounded-2xl p-6">
                         <h2 className="text-2xl font-bold text-white mb-4 flex items-center gap-3">
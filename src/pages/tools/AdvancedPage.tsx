@@ -1,16 +1,34 @@
 import { useState, useEffect } from 'react';
 import { useServerStore } from '../../stores/serverStore';
-import { updateServerSettings } from '../../utils/tauri';
-import { Cpu, Save, Loader2, AlertTriangle } from 'lucide-react';
+import { updateServerSettings, getLaunchFlagCatalog, getServerLaunchFlags, setServerLaunchFlags, setCrossplayPlatforms, getModStrategies, getModStrategy, setModStrategy } from '../../utils/tauri';
+import type { LaunchFlagCatalog, ModStrategyInfo } from '../../types';
+import { Cpu, Save, Loader2, AlertTriangle, Zap, Gamepad2, Package } from 'lucide-react';
 import { useLocation } from 'react-router-dom';
 import toast from 'react-hot-toast';
 
+const PLATFORMS = [
+    { key: 'PC', label: 'PC (Steam)' },
+    { key: 'WINGDK', label: 'PC (Microsoft Store)' },
+    { key: 'XSX', label: 'Xbox Series X|S' },
+    { key: 'PS5', label: 'PlayStation 5' },
+];
+
 export default function AdvancedPage() {
     const location = useLocation();
     const { servers, refreshServers } = useServerStore();
     const [selectedServerId, setSelectedServerId] = useState<number | null>(null);
     const [customArgs, setCustomArgs] = useState('');
     const [isLoading, setIsLoading] = useState(false);
+    const [catalog, setCatalog] = useState<LaunchFlagCatalog | null>(null);
+    const [launchFlags, setLaunchFlags] = useState<string[]>([]);
+    const [platforms, setPlatforms] = useState<string[]>([]);
+    const [modStrategies, setModStrategies] = useState<ModStrategyInfo[]>([]);
+    const [modStrategy, setModStrategyKey] = useState('runtime');
+
+    useEffect(() => {
+        getLaunchFlagCatalog().then(setCatalog).catch(console.error);
+        getModStrategies().then(setModStrategies).catch(console.error);
+    }, []);
 
     // Initialize from navigation or default
     useEffect(() => {
@@ -24,9 +42,32 @@ export default function AdvancedPage() {
         const server = servers.find(s => s.id === selectedServerId);
         if (server) {
             setCustomArgs(server.config.custom_args || '');
+            setPlatforms(server.config.crossplayPlatforms || []);
         }
+        getServerLaunchFlags(selectedServerId).then(setLaunchFlags).catch(console.error);
+        getModStrategy(selectedServerId).then(setModStrategyKey).catch(console.error);
     }, [selectedServerId, servers]);
 
+    const toggleFlag = (key: string) => {
+        setLaunchFlags(prev => prev.includes(key) ? prev.filter(k => k !== key) : [...prev, key]);
+    };
+
+    // ALL stands alone; picking a specific platform replaces it
+    const togglePlatform = (key: string) => {
+        if (key === 'ALL') {
+            setPlatforms(prev => prev.includes('ALL') ? [] : ['ALL']);
+            return;
+        }
+        setPlatforms(prev => {
+            const specific = prev.filter(p => p !== 'ALL');
+            return specific.includes(key) ? specific.filter(p => p !== key) : [...specific, key];
+        });
+    };
+
+    const applyPreset = (flags: string[]) => {
+        setLaunchFlags(prev => [...prev, ...flags.filter(f => !prev.includes(f))]);
+    };
+
     const handleSave = async () => {
         if (!selectedServerId) return;
         setIsLoading(true);
@@ -35,11 +76,14 @@ export default function AdvancedPage() {
                 serverId: selectedServerId,
                 customArgs: customArgs
             });
+            await setServerLaunchFlags(selectedServerId, launchFlags);
+            await setCrossplayPlatforms(selectedServerId, platforms);
+            await setModStrategy(selectedServerId, modStrategy);
             await refreshServers();
             toast.success('Advanced settings saved');
         } catch (err) {
             console.error(err);
-            toast.error('Failed to save settings');
+            toast.error(typeof err === 'string' ? err : 'Failed to save settings');
         } finally {
             setIsLoading(false);
         }
@@ -83,6 +127,107 @@ export default function AdvancedPage() {
             {/* Main Content */}
             <div className="flex-1 overflow-y-auto p-6 scrollbar-thin scrollbar-thumb-slate-700 scrollbar-track-transparent">
                 <div className="max-w-2xl mx-auto space-y-6">
+                    {catalog && (
+                        <div className="bg-slate-800/30 rounded-xl p-6 border border-slate-700/50">
+                            <div className="flex items-center justify-between mb-4">
+                                <h2 className="text-xl font-bold text-white flex items-center gap-2">
+                                    <Zap className="w-6 h-6 text-orange-400" />
+                                    Launch Options
+                                </h2>
+                                {catalog.presets.map(preset => (
+                                    <button
+                                        key={preset.name}
+                                        onClick={() => applyPreset(preset.flags)}
+                                        className="px-3 py-1.5 text-xs font-semibold rounded-lg bg-orange-600/20 text-orange-300 hover:bg-orange-600/30 capitalize"
+                                    >
+                                        {preset.name} preset
+                                    </button>
+                                ))}
+                            </div>
+                            <div className="space-y-2">
+                                {catalog.flags.map(flag => (
+                                    <label key={flag.key} className="flex items-start gap-3 cursor-pointer">
+                                        <input
+                                            type="checkbox"
+                                            checked={launchFlags.includes(flag.key)}
+                                            onChange={() => toggleFlag(flag.key)}
+                                            className="mt-1 accent-orange-500"
+                                        />
+                                        <div>
+                                            <div className="text-sm text-slate-200">
+                                                {flag.label} <span className="font-mono text-xs text-slate-500">{flag.flag}</span>
+                                            </div>
+                                            <div className="text-xs text-slate-500">{flag.description}</div>
+                                        </div>
+                                    </label>
+                                ))}
+                            </div>
+                        </div>
+                    )}
+
+                    <div className="bg-slate-800/30 rounded-xl p-6 border border-slate-700/50">
+                        <h2 className="text-xl font-bold text-white mb-2 flex items-center gap-2">
+                            <Gamepad2 className="w-6 h-6 text-orange-400" />
+                            Crossplay
+                        </h2>
+                        <p className="text-slate-400 text-sm mb-4">
+                            Platforms allowed to join, passed as <span className="font-mono text-xs">-ServerPlatform</span>. Leave all unchecked to use the game default.
+                        </p>
+                        <div className="grid grid-cols-2 gap-2">
+                            {PLATFORMS.map(p => (
+                                <label key={p.key} className="flex items-center gap-3 cursor-pointer">
+                                    <input
+                                        type="checkbox"
+                                        checked={platforms.includes(p.key)}
+                                        onChange={() => togglePlatform(p.key)}
+                                        className="accent-orange-500"
+                                    />
+                                    <span className="text-sm text-slate-200">{p.label}</span>
+                                </label>
+                            ))}
+                            <label className="flex items-center gap-3 cursor-pointer">
+                                <input
+                                    type="checkbox"
+                                    checked={platforms.includes('ALL')}
+                                    onChange={() => togglePlatform('ALL')}
+                                    className="accent-orange-500"
+                                />
+                                <span className="text-sm text-slate-200">All platforms</span>
+                            </label>
+                        </div>
+                    </div>
+
+                    {modStrategies.length > 1 && (
+                        <div className="bg-slate-800/30 rounded-xl p-6 border border-slate-700/50">
+                            <h2 className="text-xl font-bold text-white mb-2 flex items-center gap-2">
+                                <Package className="w-6 h-6 text-orange-400" />
+                                Mod Management
+                            </h2>
+                            <p className="text-slate-400 text-sm mb-4">
+                                How enabled mods get onto the server. Applies on next start.
+                            </p>
+                            <div className="space-y-3">
+                                {modStrategies.map(strategy => (
+                                    <label key={strategy.key} className="flex items-start gap-3 cursor-pointer">
+                                        <input
+                                            type="radio"
+                                            name="mod-strategy"
+                                            checked={modStrategy === strategy.key}
+                                            onChange={() => setModStrategyKey(strategy.key)}
+                                            className="mt-1 accent-orange-500"
+                                        />
+                                        <div>
+                                            <div className="text-sm text-slate-200">
+                                                {strategy.label} {strategy.flag && <span className="font-mono text-xs text-slate-500">{strategy.flag}</span>}
+                                            </div>
+                                            <div className="text-xs text-slate-500">{strategy.description}</div>
+                                        </div>
+                                    </label>
+                                ))}
+                            </div>
+                        </div>
+                    )}
+
                     <div className="bg-slate-800/30 rounded-xl p-6 border border-slate-700/50">
                         <h2 className="text-xl font-bold text-white mb-2 flex items-center gap-2">
                             <Cpu className="w-6 h-6 text-red-400" />
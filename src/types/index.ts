@@ -2,7 +2,7 @@
 
 export type ServerType = 'ASA';
 
-export type ServerStatus = 'stopped' | 'starting' | 'running' | 'crashed' | 'updating' | 'restarting' | 'online';
+export type ServerStatus = 'stopped' | 'starting' | 'running' | 'crashed' | 'updating' | 'restarting' | 'online' | 'stopping' | 'missing';
 
 export interface Server {
     id: number;
@@ -19,6 +19,81 @@ export interface Server {
     autoStart?: boolean;
     autoStop?: boolean;
     intelligentMode?: boolean;
+    useAsaApi?: boolean;
+    backupBeforeRestart?: boolean;
+    battleyeEnabled?: boolean;
+}
+
+/** Optional narrowing for server listings; omitted fields match everything */
+export interface ServerFilter {
+    statuses?: ServerStatus[];
+    clusterId?: number;
+    tag?: string;
+}
+
+/** Category of a structured command error (ManagerError on the Rust side) */
+export type ManagerErrorKind = 'notFound' | 'io' | 'db' | 'rcon' | 'steam' | 'validation' | 'other';
+
+export interface ManagerError {
+    kind: ManagerErrorKind;
+    message: string;
+}
+
+export type BulkServerAction = 'start' | 'stop' | 'restart' | 'update';
+
+export interface BulkActionResult {
+    serverId: number;
+    success: boolean;
+    error?: string;
+}
+
+export interface StopAllResult {
+    results: BulkActionResult[];
+    unwatchedServerIds: number[];
+}
+
+export interface BulkActionProgress {
+    action: BulkServerAction;
+    serverId: number;
+    index: number;
+    total: number;
+    stage: 'running' | 'done' | 'failed';
+    error?: string;
+}
+
+export interface ImportedSettings {
+    maxPlayers: number;
+    mapName: string;
+    sessionName: string;
+    serverPassword?: string;
+    adminPassword: string;
+    gamePort: number;
+    queryPort: number;
+    rconPort: number;
+    rconEnabled: boolean;
+}
+
+export interface ServerCandidate {
+    installPath: string;
+    hasExecutable: boolean;
+    hasConfig: boolean;
+    alreadyRegistered: boolean;
+    settings: ImportedSettings;
+}
+
+// One line of preflight_check's checklist; failed 'error' items block a start
+export interface PreflightItem {
+    id: string;
+    label: string;
+    passed: boolean;
+    severity: 'error' | 'warning';
+    detail?: string;
+}
+
+export interface PreflightReport {
+    serverId: number;
+    ready: boolean;
+    items: PreflightItem[];
 }
 
 export interface ServerPorts {
@@ -35,6 +110,109 @@ export interface ServerConfig {
     sessionName: string;
     motd?: string;
     custom_args?: string;
+    crossplayPlatforms?: string[];
+    extraSettings?: Record<string, Record<string, string>>;
+}
+
+export interface ConfigDriftMismatch {
+    field: string;
+    section: string;
+    key: string;
+    dbValue?: string;
+    iniValue?: string;
+}
+
+export interface ConfigDriftReport {
+    serverId: number;
+    filePath: string;
+    mismatches: ConfigDriftMismatch[];
+    suggestedDirection?: 'pull' | 'push';
+}
+
+// A setting as the server will see it; launch options win over INI keys of the same name
+export interface EffectiveSetting {
+    key: string;
+    value: string;
+    source: 'GameUserSettings' | 'Game' | 'LaunchArgs';
+    section?: string;
+    overriddenIniValue?: string;
+    conflict: boolean;
+}
+
+export interface EffectiveConfig {
+    serverId: number;
+    launchArgs: string[];
+    settings: EffectiveSetting[];
+}
+
+export interface DiscordConfig {
+    webhookUrl?: string;
+    notifyServerStart: boolean;
+    notifyServerStop: boolean;
+    notifyServerCrash: boolean;
+    notifyPlayerJoin: boolean;
+    notifyPlayerLeave: boolean;
+    notifyScheduledTask: boolean;
+    notifyBackup: boolean;
+    notifyServerUpdate: boolean;
+}
+
+export type TriggerMatch = 'file' | 'glob';
+export type TriggerEvent = 'create' | 'modify' | 'remove' | 'rename';
+
+/** Pattern is relative to the install folder, with '/' separators */
+export interface AutoStopTrigger {
+    matchType: TriggerMatch;
+    pattern: string;
+    events: TriggerEvent[];
+    ignoreDuringStartup: boolean;
+    consume: boolean;
+}
+
+export interface ChatBridgeLink {
+    serverId: number;
+    enabled: boolean;
+    webhookUrl?: string | null;
+    channelId?: string | null;
+}
+
+export interface ChatBridgeConfig {
+    botToken?: string | null;
+    links: ChatBridgeLink[];
+}
+
+export type NotificationEventKind =
+    | 'server_online'
+    | 'server_offline'
+    | 'server_crashed'
+    | 'backup_completed'
+    | 'backup_failed'
+    | 'server_updated';
+
+export type NotificationChannelKind =
+    | { type: 'discord'; webhookUrl: string }
+    | { type: 'webhook'; url: string; headers?: Record<string, string> }
+    | {
+          type: 'email';
+          smtpHost: string;
+          smtpPort: number;
+          username: string;
+          password: string;
+          from: string;
+          to: string[];
+          starttls?: boolean;
+      };
+
+export type NotificationChannel = NotificationChannelKind & {
+    name: string;
+    enabled: boolean;
+    /** Empty means every event */
+    events: NotificationEventKind[];
+};
+
+export interface RemoteApiStatus {
+    running: boolean;
+    bindAddress?: string;
 }
 
 export interface SystemInfo {
@@ -68,6 +246,9 @@ export interface ModInfo {
     serverType?: ServerType;
     enabled?: boolean;
     loadOrder?: number;
+    lastUpdated?: string;
+    gameVersion?: string;
+    latestVersion?: string;
 }
 
 export interface Backup {
@@ -82,6 +263,20 @@ export interface Backup {
     includesSaves: boolean;
     includesCluster: boolean;
     verified?: boolean;
+    encrypted?: boolean;
+    remoteUrl?: string | null;
+}
+
+export interface RestoreValidation {
+    ok: boolean;
+    mapName: string;
+    worldFile?: string;
+    worldSize: number;
+    profileCount: number;
+    tribeCount: number;
+    foundMaps: string[];
+    issues: string[];
+    warnings: string[];
 }
 
 export interface Cluster {
@@ -125,6 +320,26 @@ export interface PlayerStats {
     isBanned: boolean;
 }
 
+export interface PlayerSearchResult extends PlayerStats {
+    lastServerId?: number;
+    lastServerName?: string;
+}
+
+export interface PlayerListSyncResult {
+    filePath: string;
+    entries: number;
+    rconApplied: number;
+    rconErrors: string[];
+}
+
+export interface PlayerEntry {
+    index: number;
+    name: string;
+    netId: string;
+    steamId?: string;
+    eosId?: string;
+}
+
 export interface PlayerSession {
     id: number;
     serverId: number;
@@ -132,6 +347,7 @@ export interface PlayerSession {
     playerName: string;
     joinedAt: string;
     leftAt?: string;
+    eosId?: string;
 }
 
 export interface ClusterStatus {
@@ -143,6 +359,57 @@ export interface ClusterStatus {
     serverStatuses: ServerStatusInfo[];
 }
 
+export interface RconHistoryEntry {
+    id: number;
+    serverId: number;
+    command: string;
+    responseSnippet?: string;
+    success: boolean;
+    createdAt: string;
+}
+
+export interface RconFavorite {
+    id: number;
+    serverId: number;
+    label: string;
+    command: string;
+}
+
+/** Payload of the "chat-message" event */
+export interface ChatMessage {
+    serverId: number;
+    sender?: string | null;
+    message: string;
+    raw: string;
+    timestamp: string;
+    fromManager: boolean;
+}
+
+export type RconCommandCategory = 'player' | 'world' | 'dino' | 'server';
+
+export interface RconCommandInfo {
+    name: string;
+    args: string;
+    description: string;
+    category: RconCommandCategory;
+}
+
+export interface DowntimeWindow {
+    start: string;
+    end: string;
+    durationSecs: number;
+}
+
+export interface UptimeReport {
+    serverId: number;
+    days: number;
+    rangeStart: string;
+    rangeEnd: string;
+    uptimePercent: number;
+    onlineSecs: number;
+    downtime: DowntimeWindow[];
+}
+
 export interface ServerStatusInfo {
     serverId: number;
     serverName: string;
@@ -161,3 +428,127 @@ export interface PluginInfo {
     installPath: string;
 }
 
+export interface PluginConfigFile {
+    filePath: string;
+    content: string;
+}
+
+
+export interface KeyValidation {
+    valid: boolean;
+    reason?: string | null;
+}
+
+export interface AppSettings {
+    curseforgeApiKey?: string | null;
+    steamApiKey?: string | null;
+    startupTimeoutMinutes: number;
+    notificationThrottleSecs: number;
+    autoBackupSkipMinutes: number;
+    backupBeforeUpdate: boolean;
+    gracefulShutdownTimeoutSecs: number;
+    terminateTimeoutSecs: number;
+    stopSettleSecs: number;
+    rconTimeoutSecs: number;
+    rconDebugEnabled: boolean;
+    consoleStdinEnabled: boolean;
+    chatPollSecs: number;
+    remoteApiEnabled: boolean;
+    remoteApiBind?: string | null;
+    backupRoot?: string | null;
+    clusterRoot?: string | null;
+    cloudEndpoint?: string | null;
+    cloudRegion?: string | null;
+    cloudBucket?: string | null;
+    cloudPrefix?: string | null;
+    cloudAutoUpload: boolean;
+}
+
+export type ModConflictKind = 'duplicateId' | 'missingDependency' | 'disabledDependency' | 'incompatible' | 'loadOrder';
+
+export interface ModConflict {
+    kind: ModConflictKind;
+    modIds: string[];
+    message: string;
+}
+
+export interface ModConflictReport {
+    conflicts: ModConflict[];
+    suggestedOrder?: string[] | null;
+    metadataChecked: boolean;
+}
+
+export interface ModLoadRules {
+    loadFirst: string[];
+    incompatible: [string, string][];
+}
+
+// One execution of a scheduled task; success is null while still running
+export interface TaskRun {
+    id: number;
+    taskId: number;
+    startedAt: string;
+    finishedAt?: string;
+    success?: boolean;
+    // Unset while the run is in progress
+    outcome?: 'success' | 'failed' | 'skipped';
+    message?: string;
+}
+
+// Per-server result of an RCON command sent to every running server
+export interface RconFanoutResult {
+    serverId: number;
+    serverName: string;
+    success: boolean;
+    skipped: boolean;
+    response?: string;
+    error?: string;
+}
+
+// Result of checking RCON credentials without keeping the connection
+export interface RconTestResult {
+    success: boolean;
+    failure?: 'refused' | 'auth_failed' | 'timeout' | 'unreachable' | 'command_failed';
+    message: string;
+    response?: string;
+}
+
+// One packet of a raw RCON exchange (see rcon_protocol.rs)
+export interface RawRconPacket {
+    direction: 'sent' | 'received';
+    id: number;
+    packetType: number;
+    size: number;
+    body: string;
+    hex: string;
+}
+
+export interface RawRconExchange {
+    command: string;
+    response: string;
+    responsePackets: number;
+    packets: RawRconPacket[];
+    endedBy: 'marker' | 'idle' | 'timeout' | 'closed';
+    elapsedMs: number;
+}
+
+// A toggleable launch option (see launch_args.rs LAUNCH_FLAGS)
+export interface LaunchFlagInfo {
+    key: string;
+    flag: string;
+    label: string;
+    description: string;
+}
+
+export interface LaunchFlagCatalog {
+    flags: LaunchFlagInfo[];
+    presets: { name: string; flags: string[] }[];
+}
+
+// How a server gets its mods (see launch_args.rs MOD_STRATEGIES)
+export interface ModStrategyInfo {
+    key: string;
+    flag?: string;
+    label: string;
+    description: string;
+}
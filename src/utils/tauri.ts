@@ -1,17 +1,93 @@
 // Frontend wrapper for Tauri commands
-import { invoke } from '@tauri-apps/api/core';
+import { invoke as tauriInvoke } from '@tauri-apps/api/core';
 import type {
+    ManagerError,
+    ManagerErrorKind,
     Server,
+    ServerFilter,
+    ServerCandidate,
+    PreflightReport,
+    TaskRun,
+    RconFanoutResult,
+    RconTestResult,
+    RawRconExchange,
+    LaunchFlagCatalog,
+    ModStrategyInfo,
+    BulkServerAction,
+    BulkActionResult,
+    StopAllResult,
     SystemInfo,
+    AppSettings,
+    KeyValidation,
+    ModConflictReport,
+    ModLoadRules,
     ModInfo,
     Backup,
     Cluster,
     ClusterStatus,
     ServerType,
     PlayerStats,
+    PlayerSearchResult,
+    PlayerListSyncResult,
+    PlayerEntry,
     PlayerSession,
+    ConfigDriftReport,
+    EffectiveConfig,
+    RemoteApiStatus,
+    DiscordConfig,
+    ChatBridgeConfig,
+    AutoStopTrigger,
+    NotificationChannel,
+    UptimeReport,
+    RconHistoryEntry,
+    RconFavorite,
+    ChatMessage,
+    RconCommandInfo,
+    RestoreValidation,
 } from '../types';
 
+// ============================================================================
+// Errors
+// ============================================================================
+
+/**
+ * Thrown for commands that return a structured ManagerError. Stringifies to the plain
+ * message, so `${error}` in toasts reads the same as the older String errors.
+ */
+export class CommandError extends Error {
+    kind: ManagerErrorKind;
+
+    constructor({ kind, message }: ManagerError) {
+        super(message);
+        this.name = 'CommandError';
+        this.kind = kind;
+    }
+
+    toString(): string {
+        return this.message;
+    }
+}
+
+function isManagerError(error: unknown): error is ManagerError {
+    return typeof error === 'object' && error !== null
+        && typeof (error as ManagerError).kind === 'string'
+        && typeof (error as ManagerError).message === 'string';
+}
+
+/** Tauri's invoke, with structured errors turned into CommandError */
+export async function invoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
+    try {
+        return await tauriInvoke<T>(cmd, args);
+    } catch (error) {
+        throw isManagerError(error) ? new CommandError(error) : error;
+    }
+}
+
+/** The kind of a command error, or null for plain String errors */
+export function errorKind(error: unknown): ManagerErrorKind | null {
+    return error instanceof CommandError ? error.kind : null;
+}
+
 // ============================================================================
 // System Commands
 // ============================================================================
@@ -36,12 +112,88 @@ export async function setSetting(key: string, value: string): Promise<void> {
     return await invoke('set_setting', { key, value });
 }
 
+export async function validateCurseforgeKey(key: string): Promise<KeyValidation> {
+    return await invoke('validate_curseforge_key', { key });
+}
+
+export async function getAllSettings(): Promise<AppSettings> {
+    return await invoke('get_all_settings');
+}
+
+export async function saveAllSettings(settings: AppSettings): Promise<void> {
+    return await invoke('save_all_settings', { settings });
+}
+
+export async function getDiscordConfig(): Promise<DiscordConfig> {
+    return await invoke('get_discord_config');
+}
+
+export async function saveDiscordConfig(config: DiscordConfig): Promise<void> {
+    return await invoke('save_discord_config', { config });
+}
+
+export async function getChatBridgeConfig(): Promise<ChatBridgeConfig> {
+    return await invoke('get_chat_bridge_config');
+}
+
+export async function saveChatBridgeConfig(config: ChatBridgeConfig): Promise<void> {
+    return await invoke('save_chat_bridge_config', { config });
+}
+
+export async function getNotificationChannels(): Promise<NotificationChannel[]> {
+    return await invoke('get_notification_channels');
+}
+
+export async function saveNotificationChannels(channels: NotificationChannel[]): Promise<void> {
+    return await invoke('save_notification_channels', { channels });
+}
+
+export async function testNotification(channel: NotificationChannel): Promise<void> {
+    return await invoke('test_notification', { channel });
+}
+
+export async function getRemoteApiStatus(): Promise<RemoteApiStatus> {
+    return await invoke('get_remote_api_status');
+}
+
+export async function restartRemoteApi(): Promise<RemoteApiStatus> {
+    return await invoke('restart_remote_api');
+}
+
+export async function regenerateRemoteApiToken(): Promise<string> {
+    return await invoke('regenerate_remote_api_token');
+}
+
 // ============================================================================
 // Server Commands
 // ============================================================================
 
-export async function getAllServers(): Promise<Server[]> {
-    return await invoke('get_all_servers');
+export async function getAllServers(filter?: ServerFilter, limit?: number, offset?: number): Promise<Server[]> {
+    return await invoke('get_all_servers', { filter, limit, offset });
+}
+
+export async function countServers(filter?: ServerFilter): Promise<number> {
+    return await invoke('count_servers', { filter });
+}
+
+export async function addServerTag(serverId: number, tag: string): Promise<void> {
+    return await invoke('add_server_tag', { serverId, tag });
+}
+
+export async function removeServerTag(serverId: number, tag: string): Promise<void> {
+    return await invoke('remove_server_tag', { serverId, tag });
+}
+
+export async function getServerTags(serverId: number): Promise<string[]> {
+    return await invoke('get_server_tags', { serverId });
+}
+
+export async function getAllTags(): Promise<string[]> {
+    return await invoke('get_all_tags');
+}
+
+export async function getServersByTag(tag: string): Promise<Server[]> {
+    return await invoke('get_servers_by_tag', { tag });
 }
 
 export async function getServerById(serverId: number): Promise<Server | null> {
@@ -59,6 +211,7 @@ export interface InstallServerParams {
     rconPort: number;
     pveMode?: boolean; // true = PvE, false = PvP
     crossplay?: boolean; // true = Enable crossplay for PC/Console players
+    copyFromServerId?: number; // Seed game files from this server instead of downloading
 }
 
 export async function installServer(params: InstallServerParams): Promise<Server> {
@@ -73,6 +226,20 @@ export async function installServer(params: InstallServerParams): Promise<Server
     });
 }
 
+// Stops a running SteamCMD install/update; pass installPath for a server still being created
+export async function cancelInstall(params: { serverId?: number; installPath?: string }): Promise<boolean> {
+    return await invoke('cancel_install', { serverId: params.serverId, installPath: params.installPath });
+}
+
+// Copies game files from an installed server so installing at newPath only has to validate
+export async function installServerFromExisting(sourceServerId: number, newPath: string): Promise<{ files: number; bytes: number }> {
+    return await invoke('install_server_from_existing', { sourceServerId, newPath });
+}
+
+export async function preflightCheck(serverId: number): Promise<PreflightReport> {
+    return await invoke('preflight_check', { serverId });
+}
+
 export async function startServer(serverId: number): Promise<void> {
     return await invoke('start_server', { serverId });
 }
@@ -89,6 +256,25 @@ export async function restartServer(serverId: number): Promise<void> {
     return await invoke('restart_server', { serverId });
 }
 
+// Progress is emitted as 'bulk-action-progress'
+export async function bulkServerAction(
+    serverIds: number[],
+    action: BulkServerAction,
+    staggerSeconds?: number,
+    tag?: string
+): Promise<BulkActionResult[]> {
+    return await invoke('bulk_server_action', { serverIds, action, staggerSeconds, tag });
+}
+
+// Emergency stop for every running server; also emitted as 'stop-all-complete'
+export async function stopAllServers(force: boolean): Promise<StopAllResult> {
+    return await invoke('stop_all_servers', { force });
+}
+
+export async function beginMaintenance(serverId: number, countdownMinutes: number, message?: string): Promise<void> {
+    return await invoke('begin_maintenance', { serverId, countdownMinutes, message });
+}
+
 export async function deleteServer(serverId: number): Promise<void> {
     return await invoke('delete_server', { serverId });
 }
@@ -105,10 +291,27 @@ export async function importServer(installPath: string, name: string): Promise<S
     return await invoke('import_server', { installPath, name });
 }
 
-export async function toggleServerAutomation(serverId: number, toggleType: 'auto_start' | 'auto_stop' | 'intelligent_mode', enabled: boolean): Promise<void> {
+export async function scanForServers(rootPath: string): Promise<ServerCandidate[]> {
+    return await invoke('scan_for_servers', { rootPath });
+}
+
+export async function relocateServer(serverId: number, newInstallPath: string): Promise<void> {
+    return await invoke('relocate_server', { serverId, newInstallPath });
+}
+
+export async function toggleServerAutomation(serverId: number, toggleType: 'auto_start' | 'auto_stop' | 'intelligent_mode' | 'use_asa_api' | 'backup_before_restart' | 'battleye_enabled', enabled: boolean): Promise<void> {
     return await invoke('toggle_automation', { serverId, toggleType, enabled });
 }
 
+export async function getAutoStopTriggers(serverId: number): Promise<AutoStopTrigger[]> {
+    return await invoke('get_auto_stop_triggers', { serverId });
+}
+
+// An empty list restores the default triggers; returns what is now in effect
+export async function setAutoStopTriggers(serverId: number, triggers: AutoStopTrigger[]): Promise<AutoStopTrigger[]> {
+    return await invoke('set_auto_stop_triggers', { serverId, triggers });
+}
+
 export async function importNonDedicatedSave(serverId: number, sourcePath: string, importType: 'file' | 'folder'): Promise<string> {
     return await invoke('import_non_dedicated_save', { serverId, sourcePath, importType });
 }
@@ -154,12 +357,26 @@ export async function showServerConsole(serverId: number): Promise<void> {
     return await invoke('show_server_console', { serverId });
 }
 
+// Only reaches servers launched while console_stdin_enabled was on
+export async function sendConsoleCommand(serverId: number, command: string): Promise<void> {
+    return await invoke('send_console_command', { serverId, command });
+}
+
+export async function getServerUptime(serverId: number, days: number): Promise<UptimeReport> {
+    return await invoke('get_server_uptime', { serverId, days });
+}
+
 // ============================================================================
 // Mod Commands
 // ============================================================================
 
-export async function searchMods(query: string, serverType: ServerType): Promise<ModInfo[]> {
-    return await invoke('search_mods', { query, serverType });
+// Results are cached briefly per query; forceRefresh goes to CurseForge regardless
+export async function searchMods(query: string, serverType: ServerType, forceRefresh?: boolean): Promise<ModInfo[]> {
+    return await invoke('search_mods', { query, serverType, forceRefresh });
+}
+
+export async function clearModSearchCache(): Promise<void> {
+    return await invoke('clear_mod_search_cache');
 }
 
 export async function getModDescription(modId: string): Promise<string> {
@@ -170,10 +387,20 @@ export async function installMod(serverId: number, modInfo: ModInfo): Promise<vo
     return await invoke('install_mod', { serverId, modInfo });
 }
 
+/** Add the map profile's recommended mods to the server; returns the ids that were added */
+export async function applyProfileMods(serverId: number, mapId: string): Promise<string[]> {
+    return await invoke('apply_profile_mods', { serverId, mapId });
+}
+
 export async function getInstalledMods(serverId: number): Promise<ModInfo[]> {
     return await invoke('get_installed_mods', { serverId });
 }
 
+// Pulls version, game version and file date from CurseForge; returns the updated list
+export async function refreshModMetadata(serverId: number): Promise<ModInfo[]> {
+    return await invoke('refresh_mod_metadata', { serverId });
+}
+
 export async function uninstallMod(serverId: number, modId: string): Promise<void> {
     return await invoke('uninstall_mod', { serverId, modId });
 }
@@ -236,6 +463,33 @@ export async function saveConfig(serverId: number, configType: string, content:
     return await invoke('save_config', { serverId, configType, content });
 }
 
+export async function checkConfigDrift(serverId: number): Promise<ConfigDriftReport> {
+    return await invoke('check_config_drift', { serverId });
+}
+
+export async function resolveConfigDrift(serverId: number, direction: 'pull' | 'push'): Promise<ConfigDriftReport> {
+    return await invoke('resolve_config_drift', { serverId, direction });
+}
+
+export async function getEffectiveConfig(serverId: number): Promise<EffectiveConfig> {
+    return await invoke('get_effective_config', { serverId });
+}
+
+export async function startServerWithOverrides(
+    serverId: number,
+    configOverrides: Record<string, Record<string, string>>
+): Promise<string> {
+    return await invoke('start_server_with_overrides', { serverId, configOverrides });
+}
+
+export async function revertOverrides(serverId: number): Promise<void> {
+    return await invoke('revert_overrides', { serverId });
+}
+
+export async function getActiveOverrides(serverId: number): Promise<string | null> {
+    return await invoke('get_active_overrides', { serverId });
+}
+
 // ============================================================================
 // Backup Commands
 // ============================================================================
@@ -248,16 +502,28 @@ export async function getBackups(serverId: number): Promise<Backup[]> {
     return await invoke('get_backups', { serverId });
 }
 
-export async function restoreBackup(backupId: number): Promise<void> {
-    return await invoke('restore_backup', { backupId });
+export async function restoreBackup(backupId: number, password?: string): Promise<RestoreValidation | null> {
+    return await invoke('restore_backup', { backupId, password });
+}
+
+export async function restoreSingleFile(backupId: number, archiveInnerPath: string, destOverride?: string, password?: string): Promise<string> {
+    return await invoke('restore_single_file', { backupId, archiveInnerPath, destOverride, password });
 }
 
 export async function deleteBackup(backupId: number): Promise<void> {
     return await invoke('delete_backup', { backupId });
 }
 
-export async function verifyBackup(backupId: number): Promise<boolean> {
-    return await invoke('verify_backup', { backupId });
+export async function uploadBackup(backupId: number): Promise<string> {
+    return await invoke('upload_backup', { backupId });
+}
+
+export async function downloadBackupFromCloud(backupId: number): Promise<void> {
+    return await invoke('download_backup_from_cloud', { backupId });
+}
+
+export async function verifyBackup(backupId: number, password?: string): Promise<boolean> {
+    return await invoke('verify_backup', { backupId, password });
 }
 
 export async function getBackupContents(backupId: number): Promise<string[]> {
@@ -320,20 +586,66 @@ export async function setPlayerBan(steamId: string, banned: boolean): Promise<vo
     return await invoke('set_player_ban', { steamId, banned });
 }
 
-export async function searchPlayers(query: string): Promise<PlayerStats[]> {
-    return await invoke('search_players', { query });
+export async function bulkSetPlayerWhitelist(steamIds: string[], whitelisted: boolean): Promise<number> {
+    return await invoke('bulk_set_player_whitelist', { steamIds, whitelisted });
+}
+
+export async function bulkSetPlayerBan(steamIds: string[], banned: boolean): Promise<number> {
+    return await invoke('bulk_set_player_ban', { steamIds, banned });
+}
+
+// Adds the manager's list to the server file; prune also removes ids the manager doesn't list
+export async function syncWhitelistToServer(serverId: number, prune?: boolean): Promise<PlayerListSyncResult> {
+    return await invoke('sync_whitelist_to_server', { serverId, prune });
+}
+
+export async function syncBanlistToServer(serverId: number, prune?: boolean): Promise<PlayerListSyncResult> {
+    return await invoke('sync_banlist_to_server', { serverId, prune });
+}
+
+export async function exportBanlist(serverId: number, dest: string): Promise<number> {
+    return await invoke('export_banlist', { serverId, dest });
+}
+
+export async function exportWhitelist(serverId: number, dest: string): Promise<number> {
+    return await invoke('export_whitelist', { serverId, dest });
+}
+
+// Reads the server's own list file when no source is given
+export async function importBanlist(serverId: number, source?: string): Promise<number> {
+    return await invoke('import_banlist', { serverId, source });
+}
+
+export async function importWhitelist(serverId: number, source?: string): Promise<number> {
+    return await invoke('import_whitelist', { serverId, source });
+}
+
+export async function syncPlayerSessions(serverId: number): Promise<PlayerEntry[]> {
+    return await invoke('sync_player_sessions', { serverId });
+}
+
+export async function searchPlayers(query: string, limit?: number, offset?: number): Promise<PlayerSearchResult[]> {
+    return await invoke('search_players', { query, limit, offset });
 }
 
 // ============================================================================
 // Plugin Commands
 // ============================================================================
 
-import type { PluginInfo } from '../types';
+import type { PluginInfo, PluginConfigFile } from '../types';
 
 export async function checkAsaApiInstalled(serverId: number): Promise<boolean> {
     return await invoke('check_asa_api_installed', { serverId });
 }
 
+export async function installAsaApi(serverId: number): Promise<string> {
+    return await invoke('install_asa_api', { serverId });
+}
+
+export async function getAsaApiVersion(serverId: number): Promise<string | null> {
+    return await invoke('get_asa_api_version', { serverId });
+}
+
 export async function getPluginDirectory(serverId: number): Promise<string> {
     return await invoke('get_plugin_directory', { serverId });
 }
@@ -346,6 +658,10 @@ export async function importPluginArchive(serverId: number, archivePath: string)
     return await invoke('import_plugin_archive', { serverId, archivePath });
 }
 
+export async function installPluginFromUrl(serverId: number, url: string): Promise<PluginInfo> {
+    return await invoke('install_plugin_from_url', { serverId, url });
+}
+
 export async function getInstalledPlugins(serverId: number): Promise<PluginInfo[]> {
     return await invoke('get_installed_plugins', { serverId });
 }
@@ -357,3 +673,149 @@ export async function uninstallPlugin(serverId: number, pluginId: string): Promi
 export async function togglePlugin(serverId: number, pluginId: string, enabled: boolean): Promise<void> {
     return await invoke('toggle_plugin', { serverId, pluginId, enabled });
 }
+
+export async function readPluginConfig(serverId: number, pluginId: string): Promise<PluginConfigFile> {
+    return await invoke('read_plugin_config', { serverId, pluginId });
+}
+
+export async function writePluginConfig(serverId: number, pluginId: string, content: string): Promise<void> {
+    return await invoke('write_plugin_config', { serverId, pluginId, content });
+}
+
+// ============================================================================
+// RCON Commands
+// ============================================================================
+
+export async function getRconHistory(serverId: number, limit?: number): Promise<RconHistoryEntry[]> {
+    return await invoke('get_rcon_history', { serverId, limit });
+}
+
+export async function clearRconHistory(serverId: number): Promise<void> {
+    return await invoke('clear_rcon_history', { serverId });
+}
+
+export async function saveRconFavorite(serverId: number, label: string, command: string): Promise<RconFavorite> {
+    return await invoke('save_rcon_favorite', { serverId, label, command });
+}
+
+export async function getRconFavorites(serverId: number): Promise<RconFavorite[]> {
+    return await invoke('get_rcon_favorites', { serverId });
+}
+
+export async function deleteRconFavorite(id: number): Promise<void> {
+    return await invoke('delete_rcon_favorite', { id });
+}
+
+export async function getRconCommandCatalog(): Promise<RconCommandInfo[]> {
+    return await invoke('get_rcon_command_catalog');
+}
+
+// Debug only (rcon_debug_enabled): every packet of one command on a throwaway connection
+export async function rconSendRaw(serverId: number, command: string): Promise<RawRconExchange> {
+    return await invoke('rcon_send_raw', { serverId, command });
+}
+
+// ============================================================================
+// Mod Conflicts
+// ============================================================================
+
+export async function detectModConflicts(serverId: number): Promise<ModConflictReport> {
+    return await invoke('detect_mod_conflicts', { serverId });
+}
+
+export async function getModLoadRules(): Promise<ModLoadRules> {
+    return await invoke('get_mod_load_rules');
+}
+
+export async function saveModLoadRules(rules: ModLoadRules): Promise<void> {
+    return await invoke('save_mod_load_rules', { rules });
+}
+
+export async function getTaskHistory(taskId: number, limit?: number): Promise<TaskRun[]> {
+    return await invoke('get_task_history', { taskId, limit });
+}
+
+// Runs the task once without touching its schedule; false means the task skipped itself
+export async function runScheduledTaskNow(taskId: number): Promise<boolean> {
+    return await invoke('run_scheduled_task_now', { taskId });
+}
+
+export async function rconSendAll(command: string): Promise<RconFanoutResult[]> {
+    return await invoke('rcon_send_all', { command });
+}
+
+export async function rconSendChat(serverId: number, message: string): Promise<ChatMessage> {
+    return await invoke('rcon_send_chat', { serverId, message });
+}
+
+export async function rconBroadcastAll(message: string): Promise<RconFanoutResult[]> {
+    return await invoke('rcon_broadcast_all', { message });
+}
+
+export async function rconTest(address: string, port: number, password: string): Promise<RconTestResult> {
+    return await invoke('rcon_test', { address, port, password });
+}
+
+export type ModListFormat = 'active_mods' | 'json' | 'curseforge';
+
+export async function exportModList(serverId: number, format: ModListFormat): Promise<string> {
+    return await invoke('export_mod_list', { serverId, format });
+}
+
+// Accepts any export format; replace drops mods that aren't in the list
+export async function importModList(serverId: number, content: string, replace?: boolean): Promise<number> {
+    return await invoke('import_mod_list', { serverId, content, replace });
+}
+
+export async function getLaunchFlagCatalog(): Promise<LaunchFlagCatalog> {
+    return await invoke('get_launch_flag_catalog');
+}
+
+export async function getServerLaunchFlags(serverId: number): Promise<string[]> {
+    return await invoke('get_server_launch_flags', { serverId });
+}
+
+export async function setServerLaunchFlags(serverId: number, flags: string[]): Promise<void> {
+    return await invoke('set_server_launch_flags', { serverId, flags });
+}
+
+// Adds the preset's flags to the server's current ones and returns the result
+export async function applyLaunchPreset(serverId: number, preset: string): Promise<string[]> {
+    return await invoke('apply_launch_preset', { serverId, preset });
+}
+
+// Platforms: PC, XSX, PS5, WINGDK or ALL; an empty list drops -ServerPlatform
+export async function setCrossplayPlatforms(serverId: number, platforms: string[]): Promise<string[]> {
+    return await invoke('set_crossplay_platforms', { serverId, platforms });
+}
+
+export async function getModStrategies(): Promise<ModStrategyInfo[]> {
+    return await invoke('get_mod_strategies');
+}
+
+export async function getModStrategy(serverId: number): Promise<string> {
+    return await invoke('get_mod_strategy', { serverId });
+}
+
+export async function setModStrategy(serverId: number, strategy: string): Promise<void> {
+    return await invoke('set_mod_strategy', { serverId, strategy });
+}
+
+// Manager log (app_data_dir/logs/manager.log)
+export async function getRecentLogs(lines?: number): Promise<string[]> {
+    return await invoke('get_recent_logs', { lines });
+}
+
+export async function getLogLevel(): Promise<string> {
+    return await invoke('get_log_level');
+}
+
+// error, warn, info, debug or trace
+export async function setLogLevel(level: string): Promise<void> {
+    return await invoke('set_log_level', { level });
+}
+
+// Zip of logs, sanitized DB rows, crash reports and system info for bug reports; returns the path written
+export async function exportDiagnosticsBundle(dest: string): Promise<string> {
+    return await invoke('export_diagnostics_bundle', { dest });
+}